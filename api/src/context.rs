@@ -19,7 +19,9 @@ use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::error;
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    MempoolClientRequest, MempoolClientSender, PendingTransactionInfo, SubmissionStatus,
+};
 use aptos_state_view::TStateView;
 use aptos_storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
@@ -55,6 +57,7 @@ use std::{
     sync::{Arc, RwLock, RwLockWriteGuard},
     time::Instant,
 };
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 // Context holds application scope context
 #[derive(Clone)]
@@ -66,6 +69,7 @@ pub struct Context {
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
     gas_estimation_cache: Arc<RwLock<GasEstimationCache>>,
     gas_limit_cache: Arc<RwLock<GasLimitCache>>,
+    transaction_stream_permits: Arc<Semaphore>,
 }
 
 impl std::fmt::Debug for Context {
@@ -81,6 +85,9 @@ impl Context {
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
     ) -> Self {
+        let transaction_stream_permits = Arc::new(Semaphore::new(
+            node_config.api.max_concurrent_transaction_streams,
+        ));
         Self {
             chain_id,
             db,
@@ -100,9 +107,19 @@ impl Context {
                 last_updated_epoch: None,
                 block_gas_limit: None,
             })),
+            transaction_stream_permits,
         }
     }
 
+    /// Reserves one of the `max_concurrent_transaction_streams` slots for a transaction stream,
+    /// which is released when the permit is dropped. Returns `None` if all of them are in use.
+    pub fn try_acquire_transaction_stream_permit(&self) -> Option<OwnedSemaphorePermit> {
+        self.transaction_stream_permits
+            .clone()
+            .try_acquire_owned()
+            .ok()
+    }
+
     pub fn max_transactions_page_size(&self) -> u16 {
         self.node_config.api.max_transactions_page_size
     }
@@ -751,6 +768,26 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    /// Returns the transactions pending in mempool of an account, or of all the accounts if none
+    /// is given, ordered by account and sequence number
+    pub async fn get_pending_transactions(
+        &self,
+        account: Option<AccountAddress>,
+        limit: usize,
+    ) -> Result<Vec<PendingTransactionInfo>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetPendingTransactions(
+                account, limit, req_sender,
+            ))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
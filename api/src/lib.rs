@@ -18,12 +18,14 @@ mod index;
 mod log;
 pub mod metrics;
 mod page;
+mod rate_limit;
 mod response;
 mod runtime;
 mod set_failpoints;
 mod state;
 #[cfg(test)]
 pub mod tests;
+mod transaction_stream;
 mod transactions;
 mod view_function;
 
@@ -33,3 +33,12 @@ pub static REQUEST_SOURCE_CLIENT: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static RATE_LIMIT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_rate_limit",
+        "API requests checked by the rate limiter grouped by client (API key name, or ip for the others), route class and result",
+        &["client", "route_class", "result"]
+    )
+    .unwrap()
+});
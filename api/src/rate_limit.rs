@@ -0,0 +1,254 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::RATE_LIMIT;
+use aptos_api_types::{AptosError, AptosErrorCode};
+use aptos_config::config::{ApiQuotaConfig, ApiRateLimitConfig};
+use aptos_infallible::Mutex;
+use aptos_logger::debug;
+use aptos_rate_limiter::rate_limit::{Bucket, SharedBucket, TokenBucketRateLimiter};
+use poem::{
+    http::{header, HeaderName, StatusCode},
+    Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+use poem_openapi::payload::Json;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Paths of the requests which are much more expensive to serve than the others, and have their
+/// own budget. The query string is ignored, so e.g. simulations at an older ledger version or with
+/// state overrides use the same budget as the other simulations.
+const EXPENSIVE_PATHS: [&str; 3] = [
+    "/v1/view",
+    "/v1/transactions/simulate",
+    "/v1/transactions/batch",
+];
+
+/// Header set by the proxies in front of the API with the addresses of the clients
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Client label in the metrics of the requests without a known API key
+const IP_CLIENT: &str = "ip";
+
+/// Interval at which the buckets of the IP addresses that stopped sending requests are dropped
+const GARBAGE_COLLECTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// This middleware enforces the per-client quotas of requests. Clients are identified by the API
+/// key they send, or by their IP address if they don't send a known key. Requests over quota are
+/// rejected with a 429 status code and a Retry-After header.
+#[derive(Clone)]
+pub struct RateLimit {
+    limiters: Option<Arc<RateLimiters>>,
+}
+
+impl RateLimit {
+    pub fn new(config: &ApiRateLimitConfig) -> Self {
+        let limiters = config.enabled.then(|| Arc::new(RateLimiters::new(config)));
+        Self { limiters }
+    }
+
+    /// Periodically drops the buckets of the IP addresses which got all their tokens back, to
+    /// bound the memory used by the rate limiter.
+    pub async fn garbage_collect_periodically(self) {
+        let limiters = match self.limiters {
+            Some(limiters) => limiters,
+            None => return,
+        };
+        let mut interval = tokio::time::interval(GARBAGE_COLLECTION_INTERVAL);
+        loop {
+            interval.tick().await;
+            let num_removed = limiters.ip.garbage_collect_full_buckets()
+                + limiters.ip_expensive.garbage_collect_full_buckets();
+            debug!("Garbage collected {} API rate limiter buckets", num_removed);
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for RateLimit {
+    type Output = RateLimitEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        RateLimitEndpoint {
+            inner: ep,
+            limiters: self.limiters.clone(),
+        }
+    }
+}
+
+/// Endpoint for RateLimit middleware.
+pub struct RateLimitEndpoint<E> {
+    inner: E,
+    limiters: Option<Arc<RateLimiters>>,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for RateLimitEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        if let Some(limiters) = &self.limiters {
+            let expensive = EXPENSIVE_PATHS.contains(&req.uri().path().trim_end_matches('/'));
+            if let Some((client, bucket)) = limiters.bucket(&req, expensive) {
+                let route_class = if expensive { "expensive" } else { "default" };
+                let result = bucket.lock().acquire_all_tokens(1);
+                if let Err(ready_time) = result {
+                    RATE_LIMIT
+                        .with_label_values(&[client, route_class, "throttled"])
+                        .inc();
+                    return Ok(too_many_requests(ready_time));
+                }
+                RATE_LIMIT
+                    .with_label_values(&[client, route_class, "allowed"])
+                    .inc();
+            }
+        }
+
+        self.inner.call(req).await.map(IntoResponse::into_response)
+    }
+}
+
+/// Builds the response to a throttled request, which can be retried once the bucket has enough
+/// tokens again
+fn too_many_requests(ready_time: Option<Instant>) -> Response {
+    let retry_after_secs = ready_time.map_or(1, |ready_time| {
+        let wait = ready_time.saturating_duration_since(Instant::now());
+        // Round up, so that the request isn't retried before the bucket is refilled
+        (wait.as_secs() + u64::from(wait.subsec_nanos() > 0)).max(1)
+    });
+    Json(AptosError::new_with_error_code(
+        format!("Too many requests, retry in {} second(s)", retry_after_secs),
+        AptosErrorCode::RateLimitExceeded,
+    ))
+    .with_status(StatusCode::TOO_MANY_REQUESTS)
+    .with_header(header::RETRY_AFTER, retry_after_secs)
+    .into_response()
+}
+
+/// Buckets of a client with an API key
+struct ApiKeyBuckets {
+    name: String,
+    requests: SharedBucket,
+    expensive_requests: SharedBucket,
+}
+
+struct RateLimiters {
+    api_key_header: HeaderName,
+    use_x_forwarded_for: bool,
+    /// The buckets of the API keys, by key
+    api_keys: HashMap<String, ApiKeyBuckets>,
+    ip: TokenBucketRateLimiter<IpAddr>,
+    ip_expensive: TokenBucketRateLimiter<IpAddr>,
+}
+
+impl RateLimiters {
+    fn new(config: &ApiRateLimitConfig) -> Self {
+        let initial_percentage = config.initial_bucket_fill_percentage;
+        let new_bucket = |name: &str, size: usize, rate: usize| {
+            Arc::new(Mutex::new(Bucket::new(
+                "api_key".to_string(),
+                String::new(),
+                name.to_string(),
+                size.saturating_mul(initial_percentage as usize) / 100,
+                size,
+                rate,
+                None,
+            )))
+        };
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|api_key| {
+                let quota = &api_key.quota;
+                let buckets = ApiKeyBuckets {
+                    name: api_key.name.clone(),
+                    requests: new_bucket(&api_key.name, quota.bucket_size, quota.bucket_rate),
+                    expensive_requests: new_bucket(
+                        &api_key.name,
+                        quota.expensive_bucket_size,
+                        quota.expensive_bucket_rate,
+                    ),
+                };
+                (api_key.key.clone(), buckets)
+            })
+            .collect();
+
+        let ApiQuotaConfig {
+            bucket_size,
+            bucket_rate,
+            expensive_bucket_size,
+            expensive_bucket_rate,
+        } = config.ip_quota;
+        Self {
+            api_key_header: HeaderName::try_from(config.api_key_header.as_str())
+                .expect("Invalid API key header name"),
+            use_x_forwarded_for: config.use_x_forwarded_for,
+            api_keys,
+            ip: TokenBucketRateLimiter::new(
+                "api_ip",
+                String::new(),
+                initial_percentage,
+                bucket_size,
+                bucket_rate,
+                None,
+            ),
+            ip_expensive: TokenBucketRateLimiter::new(
+                "api_ip_expensive",
+                String::new(),
+                initial_percentage,
+                expensive_bucket_size,
+                expensive_bucket_rate,
+                None,
+            ),
+        }
+    }
+
+    /// Returns the metrics label of the client sending the request, and the bucket to take a
+    /// token from. Requests sent with an unknown API key are treated like those without a key.
+    /// Returns `None` if the client can't be identified.
+    fn bucket(&self, req: &Request, expensive: bool) -> Option<(&str, SharedBucket)> {
+        let api_key = req
+            .headers()
+            .get(&self.api_key_header)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|key| self.api_keys.get(key));
+        if let Some(buckets) = api_key {
+            let bucket = if expensive {
+                &buckets.expensive_requests
+            } else {
+                &buckets.requests
+            };
+            return Some((buckets.name.as_str(), bucket.clone()));
+        }
+
+        let ip = self.client_ip(req)?;
+        let limiter = if expensive {
+            &self.ip_expensive
+        } else {
+            &self.ip
+        };
+        Some((IP_CLIENT, limiter.bucket(ip)))
+    }
+
+    fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        if self.use_x_forwarded_for {
+            // The last address is the one added by the proxy in front of the API, the others
+            // can be forged by the client
+            let forwarded_ip = req
+                .headers()
+                .get(X_FORWARDED_FOR)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.rsplit(',').next())
+                .and_then(|ip| ip.trim().parse().ok());
+            if forwarded_ip.is_some() {
+                return forwarded_ip;
+            }
+        }
+        req.remote_addr()
+            .as_socket_addr()
+            .map(|address| address.ip())
+    }
+}
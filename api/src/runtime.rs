@@ -5,8 +5,8 @@
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
     context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    log::middleware_log, rate_limit::RateLimit, set_failpoints, state::StateApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::X_APTOS_CLIENT;
@@ -118,6 +118,7 @@ pub fn attach_poem_to_runtime(
     let context = Arc::new(context);
 
     let size_limit = context.content_length_limit();
+    let rate_limit = RateLimit::new(&config.api.rate_limit);
 
     let api_service = get_api_service(context.clone());
 
@@ -176,6 +177,9 @@ pub fn attach_poem_to_runtime(
                 header::ACCEPT,
             ]);
 
+        // Drop the rate limiting state of the clients that stopped sending requests
+        tokio::spawn(rate_limit.clone().garbage_collect_periodically());
+
         // Build routes for the API
         let route = Route::new()
             .nest(
@@ -191,6 +195,9 @@ pub fn attach_poem_to_runtime(
                         poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
                     ),
             )
+            // NOTE: Keep this before the CORS middleware, so that throttled requests
+            // get the CORS headers and browsers can read the response.
+            .with(rate_limit)
             .with(cors)
             .with(PostSizeLimit::new(size_limit))
             // NOTE: Make sure to keep this after all the `with` middleware.
@@ -20,6 +20,7 @@ use aptos_api_types::{
 use aptos_state_view::TStateView;
 use aptos_types::{
     access_path::AccessPath,
+    state_proof::{StateValueWithProof, StateValueWithStateProof},
     state_store::{state_key::StateKey, table::TableHandle},
 };
 use aptos_vm::data_cache::AsMoveResolver;
@@ -252,6 +253,76 @@ impl StateApi {
 
         self.raw_value(&accept_type, request.0, ledger_version.0)
     }
+
+    /// Get state proof
+    ///
+    /// Get the latest ledger info with signatures, along with the epoch change proof from the
+    /// epoch of the version known to the client to the latest epoch. Light clients use it to
+    /// ratchet their trusted state forward, starting from a waypoint.
+    ///
+    /// The epoch change proof is capped, if it doesn't reach the latest epoch the client needs to
+    /// request it again from the newly trusted version.
+    #[oai(
+        path = "/experimental/state_proof",
+        method = "get",
+        operation_id = "get_state_proof",
+        tag = "ApiTags::Experimental",
+        hidden
+    )]
+    async fn get_state_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Latest ledger version trusted by the client
+        known_version: Query<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_state_proof")?;
+
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get state proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get state proof", &accept_type)?;
+
+        self.state_proof(&accept_type, known_version.0 .0)
+    }
+
+    /// Get state value with proof.
+    ///
+    /// Get the state value identified by the key provided in the request body at the version of
+    /// the latest ledger info, along with the proofs authenticating it against that ledger info,
+    /// and the state proof from the version known to the client to that ledger info. The value
+    /// is absent if the state key doesn't exist, which is proven as well.
+    #[oai(
+        path = "/experimental/state_values/proof",
+        method = "post",
+        operation_id = "get_state_value_with_proof",
+        tag = "ApiTags::Experimental",
+        hidden
+    )]
+    async fn get_state_value_with_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Request that carries the state key.
+        request: Json<RawStateValueRequest>,
+        /// Latest ledger version trusted by the client
+        known_version: Query<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_state_value_with_proof")?;
+
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get state value with proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get state value with proof", &accept_type)?;
+
+        self.state_value_with_proof(&accept_type, request.0, known_version.0 .0)
+    }
 }
 
 impl StateApi {
@@ -579,4 +650,190 @@ impl StateApi {
             },
         }
     }
+
+    /// Retrieve the state proof from the known version to the latest ledger info
+    pub fn state_proof(
+        &self,
+        accept_type: &AcceptType,
+        known_version: u64,
+    ) -> BasicResultWith404<MoveValue> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_info_with_sigs = self
+            .context
+            .get_latest_ledger_info_with_signatures()
+            .context("Failed to retrieve latest ledger info with signatures")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        check_known_version(known_version, ledger_info_with_sigs.ledger_info().version()).map_err(
+            |err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            },
+        )?;
+
+        let state_proof = self
+            .context
+            .db
+            .get_state_proof_with_ledger_info(known_version, ledger_info_with_sigs)
+            .context(format!(
+                "Failed to retrieve state proof from version {}",
+                known_version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let bytes = bcs::to_bytes(&state_proof)
+            .context("Failed serializing state proof")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        match accept_type {
+            AcceptType::Json => Err(api_forbidden(
+                "Get state proof",
+                "This serves only bytes. Use other APIs for Json.",
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
+    /// Retrieve the state value with proof at the version of the latest ledger info
+    pub fn state_value_with_proof(
+        &self,
+        accept_type: &AcceptType,
+        request: RawStateValueRequest,
+        known_version: u64,
+    ) -> BasicResultWith404<MoveValue> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let state_key: StateKey = bcs::from_bytes(&request.key.0)
+            .context(format!(
+                "Failed deserializing state key. key: {}",
+                request.key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+        let ledger_info_with_sigs = self
+            .context
+            .get_latest_ledger_info_with_signatures()
+            .context("Failed to retrieve latest ledger info with signatures")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        // The latest ledger info is always at the end of a block, which is a state checkpoint.
+        let version = ledger_info_with_sigs.ledger_info().version();
+        check_known_version(known_version, version).map_err(|err| {
+            BasicErrorWith404::bad_request_with_code(
+                err,
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )
+        })?;
+
+        let db = &self.context.db;
+        let state_proof = db
+            .get_state_proof_with_ledger_info(known_version, ledger_info_with_sigs)
+            .context(format!(
+                "Failed to retrieve state proof from version {}",
+                known_version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let (value, sparse_merkle_proof) = db
+            .get_state_value_with_proof_by_version(&state_key, version)
+            .context(format!(
+                "Failed fetching state value with proof. key: {}",
+                request.key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let transaction_info_with_proof = db
+            .get_transaction_by_version(version, version, false)
+            .context(format!(
+                "Failed fetching transaction info with proof at version {}",
+                version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?
+            .proof;
+
+        let bytes = bcs::to_bytes(&StateValueWithStateProof::new(
+            state_proof,
+            StateValueWithProof::new(
+                version,
+                value,
+                transaction_info_with_proof,
+                sparse_merkle_proof,
+            ),
+        ))
+        .context(format!(
+            "Failed serializing state value with proof. key: {}",
+            request.key
+        ))
+        .map_err(|err| {
+            BasicErrorWith404::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+        })?;
+
+        match accept_type {
+            AcceptType::Json => Err(api_forbidden(
+                "Get state value with proof",
+                "This serves only bytes. Use other APIs for Json.",
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+}
+
+fn check_known_version(known_version: u64, ledger_version: u64) -> anyhow::Result<()> {
+    if known_version > ledger_version {
+        anyhow::bail!(
+            "Known version {} is newer than the latest ledger version {}",
+            known_version,
+            ledger_version
+        );
+    }
+    Ok(())
 }
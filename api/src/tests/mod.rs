@@ -11,9 +11,11 @@ mod invalid_post_request_test;
 mod modules;
 mod multisig_transactions_test;
 mod objects;
+mod rate_limit_test;
 mod resource_groups;
 mod state_test;
 mod string_resource_test;
+mod transaction_stream_test;
 mod transaction_vector_test;
 mod transactions_test;
 mod view_function;
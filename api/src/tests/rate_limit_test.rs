@@ -0,0 +1,96 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context_with_config;
+use aptos_api_test_context::current_function_name;
+use aptos_config::config::{ApiKeyConfig, ApiQuotaConfig, ApiRateLimitConfig, NodeConfig};
+use hyper::header::RETRY_AFTER;
+
+const API_KEY: &str = "test-api-key";
+
+fn rate_limited_node_config() -> NodeConfig {
+    let quota = ApiQuotaConfig {
+        bucket_size: 2,
+        bucket_rate: 1,
+        expensive_bucket_size: 1,
+        expensive_bucket_rate: 1,
+    };
+    let mut node_config = NodeConfig::default();
+    node_config.api.rate_limit = ApiRateLimitConfig {
+        enabled: true,
+        ip_quota: quota,
+        api_keys: vec![ApiKeyConfig {
+            name: "test".into(),
+            key: API_KEY.into(),
+            quota: ApiQuotaConfig {
+                bucket_size: 100,
+                ..quota
+            },
+        }],
+        ..Default::default()
+    };
+    node_config
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_rate_limit_per_ip() {
+    let context =
+        new_test_context_with_config(current_function_name!(), rate_limited_node_config());
+    context.get("/").await;
+    context.get("/").await;
+
+    // The bucket of the client is empty, so it must wait for the next refill
+    let resp = context
+        .reply(warp::test::request().method("GET").path("/v1/"))
+        .await;
+    assert_eq!(resp.status(), 429);
+    let retry_after: u64 = resp.headers()[RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(retry_after, 1);
+    let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(body["error_code"], "rate_limit_exceeded");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_rate_limit_per_api_key() {
+    let context =
+        new_test_context_with_config(current_function_name!(), rate_limited_node_config());
+    context.get("/").await;
+    context.get("/").await;
+
+    // The bucket of the IP address is empty, but not the one of the API key
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path("/v1/")
+                .header("x-aptos-api-key", API_KEY),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_rate_limit_expensive_paths() {
+    let context =
+        new_test_context_with_config(current_function_name!(), rate_limited_node_config());
+    let simulate = || {
+        warp::test::request()
+            .method("POST")
+            .path("/v1/transactions/simulate?ledger_version=0")
+            .header("content-type", "application/json")
+            .body("{}")
+    };
+
+    // The request is invalid, but still counts against the budget of the expensive paths
+    let resp = context.reply(simulate()).await;
+    assert_eq!(resp.status(), 400);
+    let resp = context.reply(simulate()).await;
+    assert_eq!(resp.status(), 429);
+
+    // The budget of the other paths is separate
+    context.get("/").await;
+}
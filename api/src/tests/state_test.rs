@@ -3,13 +3,26 @@
 
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
-use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
+use aptos_api_types::mime_types::BCS;
+use aptos_sdk::{
+    rest_client::{error::RestError, verifying_client::VerifyingClient, Client},
+    transaction_builder::aptos_stdlib::aptos_token_stdlib,
+    types::LocalAccount,
+};
 use aptos_storage_interface::DbReader;
-use move_core_types::account_address::AccountAddress;
+use aptos_types::{
+    access_path::AccessPath,
+    account_config::AccountResource,
+    state_proof::{StateProof, StateValueWithProof, StateValueWithStateProof},
+    state_store::state_key::StateKey,
+    waypoint::Waypoint,
+};
+use move_core_types::{account_address::AccountAddress, move_resource::MoveStructType};
 use move_package::BuildConfig;
 use serde::Serialize;
 use serde_json::{json, Value};
-use std::{convert::TryInto, path::PathBuf};
+use std::{convert::TryInto, path::PathBuf, str::FromStr};
+use url::Url;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_resource() {
@@ -215,6 +228,144 @@ async fn test_merkle_leaves_with_nft_transfer() {
     assert_eq!(num_leaves_after_return_nft, num_leaves_at_beginning + 1);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_state_proof() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+    let latest_version = context.db.get_latest_version().unwrap();
+
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path("/experimental/state_proof?known_version=0"))
+                .header("Accept", BCS),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let state_proof: StateProof = bcs::from_bytes(resp.body()).unwrap();
+    assert_eq!(state_proof.latest_ledger_info().version(), latest_version);
+    // The genesis epoch ending ledger info moves a client from the genesis waypoint to the
+    // latest epoch.
+    assert_eq!(state_proof.epoch_changes().ledger_info_with_sigs.len(), 1);
+
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path(&format!(
+                    "/experimental/state_proof?known_version={}",
+                    latest_version + 1
+                )))
+                .header("Accept", BCS),
+        )
+        .await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_state_value_with_proof() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let state_key = account_resource_state_key(account.address());
+    let (state_proof, state_value_with_proof) =
+        api_get_state_value_with_proof(&context, &state_key).await;
+    let ledger_info = state_proof.latest_ledger_info();
+    state_value_with_proof
+        .verify(ledger_info, &state_key)
+        .unwrap();
+    let account_resource: AccountResource =
+        bcs::from_bytes(state_value_with_proof.value().unwrap().bytes()).unwrap();
+    assert_eq!(account_resource.sequence_number(), 0);
+
+    // The proof doesn't hold for another state key.
+    let missing_state_key = account_resource_state_key(context.gen_account().address());
+    assert!(state_value_with_proof
+        .verify(ledger_info, &missing_state_key)
+        .is_err());
+
+    // The absence of a state value is proven as well.
+    let (state_proof, state_value_with_proof) =
+        api_get_state_value_with_proof(&context, &missing_state_key).await;
+    assert!(state_value_with_proof.value().is_none());
+    state_value_with_proof
+        .verify(state_proof.latest_ledger_info(), &missing_state_key)
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_verifying_client_verify_state_value() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let genesis_ledger_info = context.db.get_epoch_ending_ledger_info(0).unwrap();
+    let genesis_waypoint = Waypoint::new_epoch_boundary(genesis_ledger_info.ledger_info()).unwrap();
+    let new_verifying_client = |waypoint| {
+        VerifyingClient::new(
+            Client::new(Url::parse("http://localhost:8080").unwrap()),
+            waypoint,
+        )
+    };
+
+    let state_key = account_resource_state_key(account.address());
+    let (state_proof, state_value_with_proof) =
+        api_get_state_value_with_proof(&context, &state_key).await;
+    let verifying_client = new_verifying_client(genesis_waypoint);
+    let value = verifying_client
+        .verify_state_value(&state_key, &state_proof, state_value_with_proof.clone())
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    let account_resource: AccountResource = bcs::from_bytes(value.bytes()).unwrap();
+    assert_eq!(account_resource.sequence_number(), 0);
+    assert_eq!(
+        verifying_client.trusted_state().version(),
+        state_proof.latest_ledger_info().version()
+    );
+
+    // The state value is proven for another state key.
+    let other_state_key = account_resource_state_key(context.gen_account().address());
+    let err = new_verifying_client(genesis_waypoint)
+        .verify_state_value(
+            &other_state_key,
+            &state_proof,
+            state_value_with_proof.clone(),
+        )
+        .unwrap_err();
+    assert!(matches!(err, RestError::InvalidProof(_)));
+
+    // The state value is proven against an older ledger info than the one of the state proof.
+    let other_account = context.gen_account();
+    let txn = context.create_user_account(&other_account).await;
+    context.commit_block(&vec![txn]).await;
+    let newer_state_proof = api_get_state_proof(&context, 0).await;
+    assert!(newer_state_proof.latest_ledger_info().version() > state_value_with_proof.version());
+    let err = new_verifying_client(genesis_waypoint)
+        .verify_state_value(
+            &state_key,
+            &newer_state_proof,
+            state_value_with_proof.clone(),
+        )
+        .unwrap_err();
+    assert!(matches!(err, RestError::InvalidProof(_)));
+
+    // The state proof doesn't start from the trusted waypoint.
+    let other_waypoint = Waypoint::from_str(&format!("0:{}", "0".repeat(64))).unwrap();
+    let verifying_client = new_verifying_client(other_waypoint);
+    let err = verifying_client
+        .verify_state_value(&state_key, &state_proof, state_value_with_proof)
+        .unwrap_err();
+    assert!(matches!(err, RestError::InvalidProof(_)));
+    assert_eq!(verifying_client.trusted_state().version(), 0);
+}
+
 #[ignore] // TODO: deactivate because of module-bundle publish not longer there; reactivate.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_table_item() {
@@ -317,6 +468,47 @@ fn get_table_item(handle: AccountAddress) -> String {
     format!("/tables/{}/item", handle)
 }
 
+fn account_resource_state_key(address: AccountAddress) -> StateKey {
+    StateKey::access_path(
+        AccessPath::resource_access_path(address, AccountResource::struct_tag()).unwrap(),
+    )
+}
+
+async fn api_get_state_proof(context: &TestContext, known_version: u64) -> StateProof {
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path(&format!(
+                    "/experimental/state_proof?known_version={}",
+                    known_version
+                )))
+                .header("Accept", BCS),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    bcs::from_bytes(resp.body()).unwrap()
+}
+
+async fn api_get_state_value_with_proof(
+    context: &TestContext,
+    state_key: &StateKey,
+) -> (StateProof, StateValueWithProof) {
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("POST")
+                .path(&context.prepend_path("/experimental/state_values/proof?known_version=0"))
+                .header("Accept", BCS)
+                .json(&json!({ "key": hex::encode(bcs::to_bytes(state_key).unwrap()) })),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    bcs::from_bytes::<StateValueWithStateProof>(resp.body())
+        .unwrap()
+        .into_inner()
+}
+
 async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
     let module = build_test_module(account.address()).await;
 
@@ -0,0 +1,139 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use aptos_api_test_context::{current_function_name, ApiSpecificConfig, TestContext};
+use aptos_config::config::NodeConfig;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::time::timeout;
+
+/// Creates a test node with the transaction stream enabled
+fn new_stream_test_context(test_name: String, max_concurrent_streams: usize) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.transaction_stream_enabled = true;
+    node_config.api.max_concurrent_transaction_streams = max_concurrent_streams;
+    new_test_context_with_config(test_name, node_config)
+}
+
+/// Opens a transaction stream on the test node with the given query string
+async fn open_stream(context: &TestContext, query: &str) -> reqwest::Response {
+    let ApiSpecificConfig::V1(address) = context.api_specific_config;
+    let resp = reqwest::get(format!(
+        "http://{}/v1/transactions/stream{}",
+        address, query
+    ))
+    .await
+    .unwrap();
+    assert_eq!(resp.status(), 200);
+    resp
+}
+
+/// Reads from the stream until `count` transactions have been received
+async fn read_transactions(resp: &mut reqwest::Response, count: usize) -> Vec<Value> {
+    let mut buffer = String::new();
+    let mut transactions = vec![];
+    while transactions.len() < count {
+        let chunk = timeout(Duration::from_secs(10), resp.chunk())
+            .await
+            .expect("timed out waiting for streamed transactions")
+            .unwrap()
+            .expect("stream closed unexpectedly");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+
+        // Events are separated by an empty line, keep any trailing partial event
+        while let Some(end) = buffer.find("\n\n") {
+            let event: String = buffer.drain(..end + 2).collect();
+            for line in event.lines() {
+                if let Some(data) = line.strip_prefix("data:") {
+                    transactions.push(serde_json::from_str(data.trim()).unwrap());
+                }
+            }
+        }
+    }
+    transactions
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_from_start_version() {
+    let mut context = new_stream_test_context(current_function_name!(), 10);
+    let mut root_account = context.root_account().await;
+    let account = context.gen_account();
+    let txn = context.create_user_account_by(&mut root_account, &account);
+    context.commit_block(&vec![txn]).await;
+
+    let mut resp = open_stream(&context, "?start=0").await;
+    let transactions = read_transactions(&mut resp, 2).await;
+    assert_eq!(transactions[0]["type"], "genesis_transaction");
+    assert_eq!(transactions[0]["version"], "0");
+    assert_eq!(transactions[1]["version"], "1");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_follows_new_commits_with_sender_filter() {
+    let mut context = new_stream_test_context(current_function_name!(), 10);
+    let mut root_account = context.root_account().await;
+    let mut resp = open_stream(
+        &context,
+        &format!(
+            "?sender={}&entry_function=0x1::aptos_account::create_account",
+            root_account.address()
+        ),
+    )
+    .await;
+
+    let mut txns = vec![];
+    for _ in 0..3 {
+        let account = context.gen_account();
+        let txn = context.create_user_account_by(&mut root_account, &account);
+        context.commit_block(&vec![txn.clone()]).await;
+        txns.push(txn);
+    }
+
+    let transactions = read_transactions(&mut resp, 3).await;
+    for (streamed, txn) in transactions.iter().zip(txns.iter()) {
+        assert_eq!(streamed["type"], "user_transaction");
+        assert_eq!(streamed["sender"], root_account.address().to_hex_literal());
+        assert_eq!(
+            streamed["sequence_number"],
+            txn.sequence_number().to_string()
+        );
+        assert_eq!(
+            streamed["payload"]["function"],
+            "0x1::aptos_account::create_account"
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_with_invalid_entry_function() {
+    let context = new_stream_test_context(current_function_name!(), 10);
+    let resp = context
+        .expect_status_code(400)
+        .get("/transactions/stream?entry_function=0x1::aptos_account")
+        .await;
+    assert_eq!(resp["error_code"], "web_framework_error");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_disabled_by_default() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(403)
+        .get("/transactions/stream")
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_concurrency_limit() {
+    let context = new_stream_test_context(current_function_name!(), 2);
+    let _first = open_stream(&context, "").await;
+    let _second = open_stream(&context, "").await;
+
+    let resp = context
+        .expect_status_code(503)
+        .get("/transactions/stream")
+        .await;
+    assert_eq!(resp["error_code"], "rate_limit_exceeded");
+}
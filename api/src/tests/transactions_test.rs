@@ -5,6 +5,7 @@
 use super::new_test_context;
 use crate::tests::new_test_context_with_config;
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
+use aptos_api_types::{mime_types::BCS, MempoolTransactionBcs, MempoolTransactionSubmitter};
 use aptos_config::config::NodeConfig;
 use aptos_crypto::{
     ed25519::Ed25519PrivateKey,
@@ -24,6 +25,7 @@ use move_core_types::{
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
 };
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use poem_openapi::types::ParseFromJSON;
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use serde_json::json;
@@ -420,6 +422,110 @@ async fn test_get_pending_transaction_by_hash() {
     context.check_golden_output(not_found);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_pending_transactions() {
+    let mut context = new_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let (account1, account2) = (context.gen_account(), context.gen_account());
+    let txn1 = context.create_user_account_by(&mut root_account, &account1);
+    let txn2 = context.create_user_account_by(&mut root_account, &account2);
+    let path = format!(
+        "/accounts/{}/pending_transactions",
+        txn1.sender().to_hex_literal()
+    );
+
+    // Submit the second transaction first, so that it's parked
+    let pending_txn2 = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn2).unwrap())
+        .await;
+    let txns = context.get(&path).await;
+    assert_eq!(txns.as_array().unwrap().len(), 1);
+    assert_json(txns[0]["transaction"].clone(), pending_txn2.clone());
+    assert_eq!(txns[0]["parked"], true);
+    assert_eq!(txns[0]["timeline_state"], "not_ready");
+    assert_eq!(txns[0]["submitted_by"], "client");
+
+    // Submit the first transaction, which unparks the second one
+    let pending_txn1 = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn1).unwrap())
+        .await;
+    let txns = context.get(&path).await;
+    assert_eq!(txns.as_array().unwrap().len(), 2);
+    assert_json(txns[0]["transaction"].clone(), pending_txn1);
+    assert_json(txns[1]["transaction"].clone(), pending_txn2);
+    for txn in txns.as_array().unwrap() {
+        assert_eq!(txn["parked"], false);
+        assert_eq!(txn["timeline_state"], "ready");
+    }
+
+    // There are no pending transactions for the other accounts
+    let txns = context
+        .get(&format!(
+            "/accounts/{}/pending_transactions",
+            context.gen_account().address().to_hex_literal()
+        ))
+        .await;
+    assert!(txns.as_array().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_transactions() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.mempool_inspection_enabled = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let mut root_account = context.root_account().await;
+    let (account1, account2) = (context.gen_account(), context.gen_account());
+    let txn1 = context.create_user_account_by(&mut root_account, &account1);
+    let txn2 = context.create_user_account_by(&mut root_account, &account2);
+    for txn in [&txn1, &txn2] {
+        context
+            .expect_status_code(202)
+            .post_bcs_txn("/transactions", bcs::to_bytes(txn).unwrap())
+            .await;
+    }
+
+    let txns = context.get("/mempool/transactions").await;
+    assert_eq!(txns.as_array().unwrap().len(), 2);
+    assert_eq!(
+        txns[0]["transaction"]["hash"],
+        txn1.committed_hash().to_hex_literal()
+    );
+    assert_eq!(
+        txns[1]["transaction"]["hash"],
+        txn2.committed_hash().to_hex_literal()
+    );
+
+    let txns = context.get("/mempool/transactions?limit=1").await;
+    assert_eq!(txns.as_array().unwrap().len(), 1);
+
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path("/mempool/transactions"))
+                .header("Accept", BCS),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let txns: Vec<MempoolTransactionBcs> = bcs::from_bytes(resp.body()).unwrap();
+    assert_eq!(txns.len(), 2);
+    assert_eq!(txns[0].transaction, txn1);
+    assert_eq!(txns[1].transaction, txn2);
+    assert_eq!(txns[0].submitted_by, MempoolTransactionSubmitter::Client);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_transactions_disabled() {
+    let mut context = new_test_context(current_function_name!());
+
+    context
+        .expect_status_code(403)
+        .get("/mempool/transactions")
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_signing_message_with_entry_function_payload() {
     let mut context = new_test_context(current_function_name!());
@@ -1192,6 +1298,106 @@ async fn test_gas_estimation_disabled() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_overrides() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+    let receiver = context.gen_account();
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": "0x1::aptos_account::transfer",
+        "type_arguments": [],
+        "arguments": [receiver.address().to_hex_literal(), "100000000000"]
+    });
+
+    // The account can't afford the transfer
+    let resp = context
+        .simulate_transaction_with_overrides(&account, payload.clone(), None, json!([]), 200)
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+
+    // Pretend the account holds enough coins
+    let coin_store = "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>";
+    let mut resource = context
+        .get(&format!(
+            "/accounts/{}/resource/{}",
+            account.address(),
+            utf8_percent_encode(coin_store, NON_ALPHANUMERIC)
+        ))
+        .await;
+    resource["data"]["coin"]["value"] = json!("200000000000");
+    let resp = context
+        .simulate_transaction_with_overrides(
+            &account,
+            payload,
+            None,
+            json!([{
+                "type": "resource_override",
+                "address": account.address().to_hex_literal(),
+                "resource_type": coin_store,
+                "data": resource["data"],
+            }]),
+            200,
+        )
+        .await;
+    assert!(resp[0]["success"].as_bool().unwrap());
+    let changes = resp[0]["changes"].as_array().unwrap();
+    assert!(changes
+        .iter()
+        .any(|change| change["address"] == json!(receiver.address().to_hex_literal())));
+
+    // Nothing is persisted
+    let resource = context
+        .get(&format!(
+            "/accounts/{}/resource/{}",
+            account.address(),
+            utf8_percent_encode(coin_store, NON_ALPHANUMERIC)
+        ))
+        .await;
+    assert_ne!(resource["data"]["coin"]["value"], json!("200000000000"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_overrides_at_ledger_version() {
+    let mut context = new_test_context(current_function_name!());
+    let version_before_account = context.get_latest_ledger_info().version();
+    let account = context.create_account().await;
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": "0x1::aptos_account::transfer",
+        "type_arguments": [],
+        "arguments": [account.address().to_hex_literal(), "1"]
+    });
+
+    let resp = context
+        .simulate_transaction_with_overrides(&account, payload.clone(), None, json!([]), 200)
+        .await;
+    assert!(resp[0]["success"].as_bool().unwrap());
+
+    // The account doesn't exist yet at the earlier version
+    let resp = context
+        .simulate_transaction_with_overrides(
+            &account,
+            payload.clone(),
+            Some(version_before_account),
+            json!([]),
+            200,
+        )
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+
+    let future_version = context.get_latest_ledger_info().version() + 100;
+    context
+        .simulate_transaction_with_overrides(
+            &account,
+            payload,
+            Some(future_version),
+            json!([]),
+            400,
+        )
+        .await;
+}
+
 fn gen_string(len: u64) -> String {
     let mut rng = thread_rng();
     std::iter::repeat(())
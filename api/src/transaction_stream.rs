@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{accept_type::AcceptType, context::Context};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{AsConverter, HexEncodedBytes, StreamedTransaction, TransactionOnChainData};
+use aptos_logger::warn;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{MultisigTransactionPayload, Transaction, TransactionPayload},
+};
+use aptos_vm::data_cache::AsMoveResolver;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::OwnedSemaphorePermit;
+
+/// Filter applied to committed transactions before they are pushed to a
+/// stream. Every filter that is set must match for a transaction to be sent.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionFilter {
+    /// Only user transactions sent by this account
+    pub sender: Option<AccountAddress>,
+    /// Only user transactions calling this entry function, directly or
+    /// through a multisig payload
+    pub entry_function: Option<(ModuleId, Identifier)>,
+    /// Only transactions that emitted at least one event of this type
+    pub event_type: Option<StructTag>,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, txn: &TransactionOnChainData) -> bool {
+        if self.sender.is_some() || self.entry_function.is_some() {
+            let signed_txn = match &txn.transaction {
+                Transaction::UserTransaction(signed_txn) => signed_txn,
+                _ => return false,
+            };
+
+            if let Some(sender) = &self.sender {
+                if &signed_txn.sender() != sender {
+                    return false;
+                }
+            }
+
+            if let Some((module, function)) = &self.entry_function {
+                let entry_function = match signed_txn.payload() {
+                    TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+                    TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+                        Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                            Some(entry_function)
+                        },
+                        None => None,
+                    },
+                    _ => None,
+                };
+                match entry_function {
+                    Some(entry_function)
+                        if entry_function.module() == module
+                            && entry_function.function() == function.as_ident_str() => {},
+                    _ => return false,
+                }
+            }
+        }
+
+        if let Some(event_type) = &self.event_type {
+            let matches_event = txn.events.iter().any(|event| match event.type_tag() {
+                TypeTag::Struct(struct_tag) => **struct_tag == *event_type,
+                _ => false,
+            });
+            if !matches_event {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Stream of committed transactions starting at `start_version`
+///
+/// The stream follows the ledger as it grows, checking for new transactions
+/// every `transaction_stream_poll_interval_ms`, and ends if reading from the
+/// DB fails (e.g. because the requested versions have been pruned). The
+/// stream holds `permit` until it is dropped.
+pub fn transaction_stream(
+    context: Arc<Context>,
+    accept_type: AcceptType,
+    start_version: u64,
+    filter: TransactionFilter,
+    permit: OwnedSemaphorePermit,
+) -> BoxStream<'static, StreamedTransaction> {
+    let poll_interval =
+        Duration::from_millis(context.node_config.api.transaction_stream_poll_interval_ms);
+
+    stream::unfold(start_version, move |next_version| {
+        // Moves the permit into the closure, so that it lives as long as the stream
+        let _permit = &permit;
+        let context = context.clone();
+        let accept_type = accept_type.clone();
+        let filter = filter.clone();
+        async move {
+            loop {
+                match next_batch(&context, &accept_type, &filter, next_version) {
+                    Ok(Some((batch, next_version))) => return Some((batch, next_version)),
+                    Ok(None) => tokio::time::sleep(poll_interval).await,
+                    Err(err) => {
+                        warn!(
+                            "Closing transaction stream at version {}: {:#}",
+                            next_version, err
+                        );
+                        return None;
+                    },
+                }
+            }
+        }
+    })
+    .flat_map(stream::iter)
+    .boxed()
+}
+
+/// Reads and renders the next page of transactions starting at `start_version`,
+/// returning the transactions matching the filter and the next version to read.
+/// Returns `None` if there are no new committed transactions yet.
+fn next_batch(
+    context: &Context,
+    accept_type: &AcceptType,
+    filter: &TransactionFilter,
+    start_version: u64,
+) -> anyhow::Result<Option<(Vec<StreamedTransaction>, u64)>> {
+    let ledger_info = context.get_latest_ledger_info_wrapped()?;
+    let ledger_version = ledger_info.version();
+    if start_version > ledger_version {
+        return Ok(None);
+    }
+
+    let limit = std::cmp::min(
+        context.max_transactions_page_size() as u64,
+        ledger_version - start_version + 1,
+    ) as u16;
+    let data = context
+        .get_transactions(start_version, limit, ledger_version)
+        .context("Failed to read raw transactions from storage")?;
+    let next_version = start_version + data.len() as u64;
+
+    let transactions = match accept_type {
+        AcceptType::Json => {
+            // Track the block timestamp over the whole page, so that filtered
+            // out block metadata transactions still update it.
+            let mut timestamp = context.db.get_block_timestamp(start_version)?;
+            let data: Vec<_> = data
+                .into_iter()
+                .filter_map(|txn| {
+                    if let Some(block_metadata) = txn.transaction.try_as_block_metadata() {
+                        timestamp = block_metadata.timestamp_usecs();
+                    }
+                    filter.matches(&txn).then_some((timestamp, txn))
+                })
+                .collect();
+            if data.is_empty() {
+                vec![]
+            } else {
+                let state_view = context.latest_state_view()?;
+                let resolver = state_view.as_move_resolver();
+                let converter = resolver.as_converter(context.db.clone());
+                data.into_iter()
+                    .map(|(timestamp, txn)| {
+                        converter
+                            .try_into_onchain_transaction(timestamp, txn)
+                            .map(StreamedTransaction::Json)
+                    })
+                    .collect::<anyhow::Result<_>>()
+                    .context("Failed to convert transaction data from storage")?
+            }
+        },
+        AcceptType::Bcs => data
+            .into_iter()
+            .filter(|txn| filter.matches(txn))
+            .map(|txn| {
+                bcs::to_bytes(&txn)
+                    .map(|bytes| StreamedTransaction::Bcs(HexEncodedBytes::from(bytes)))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    Ok(Some((transactions, next_version)))
+}
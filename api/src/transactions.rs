@@ -9,29 +9,40 @@ use crate::{
     context::Context,
     failpoint::fail_point_poem,
     generate_error_response, generate_success_response,
-    page::Page,
+    page::{determine_limit, Page},
     response::{
         api_disabled, api_forbidden, transaction_not_found_by_hash,
-        transaction_not_found_by_version, BadRequestError, BasicError, BasicErrorWith404,
-        BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
+        transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
+        BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
         InsufficientStorageError, InternalError,
     },
+    transaction_stream::{transaction_stream, TransactionFilter},
     ApiTags,
 };
 use anyhow::{anyhow, Context as AnyhowContext};
 use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
-    AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
-    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
-    MAX_RECURSIVE_TYPES_ALLOWED, U64,
+    AsConverter, EncodeSubmissionRequest, EntryFunctionId, GasEstimation, GasEstimationBcs,
+    HashValue, HexEncodedBytes, LedgerInfo, MempoolTimelineState, MempoolTransaction,
+    MempoolTransactionBcs, MempoolTransactionSubmitter, MoveStructTag, MoveType,
+    PendingTransaction, SimulateTransactionRequest, StateOverride, StreamedTransaction,
+    SubmitTransactionRequest, Transaction, TransactionData, TransactionOnChainData,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
+    VerifyInput, VerifyInputWithRecursion, MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_mempool::{PendingTransactionInfo, SubmittedBy, TimelineState};
+use aptos_state_view::{
+    account_with_state_view::AccountWithStateView, overridden_state_view::OverriddenStateView,
+    StateView,
+};
 use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
     account_config::CoinStoreResource,
     account_view::AccountView,
     mempool_status::MempoolStatusCode,
+    state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
         EntryFunction, ExecutionStatus, MultisigTransactionPayload, RawTransaction,
         RawTransactionWithData, SignedTransaction, TransactionPayload, TransactionStatus,
@@ -39,12 +50,25 @@ use aptos_types::{
     vm_status::StatusCode,
 };
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use futures::stream::BoxStream;
+use move_binary_format::CompiledModule;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use poem_openapi::{
     param::{Path, Query},
-    payload::Json,
+    payload::{EventStream, Json},
     ApiRequest, OpenApi,
 };
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+/// Interval at which keep-alive comments are sent on idle transaction streams
+const TRANSACTION_STREAM_KEEP_ALIVE: Duration = Duration::from_secs(15);
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 
@@ -99,6 +123,26 @@ impl VerifyInput for SubmitTransactionPost {
     }
 }
 
+// We need a custom type here because we use different types for each of the
+// content types possible for the POST data.
+#[derive(ApiRequest, Debug)]
+pub enum SimulateTransactionPost {
+    #[oai(content_type = "application/json")]
+    Json(Json<SimulateTransactionRequest>),
+
+    #[oai(content_type = "application/x.aptos.signed_transaction+bcs")]
+    Bcs(Bcs),
+}
+
+impl VerifyInput for SimulateTransactionPost {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            SimulateTransactionPost::Json(inner) => inner.0.verify(),
+            SimulateTransactionPost::Bcs(_) => Ok(()),
+        }
+    }
+}
+
 // We need a custom type here because we use different types for each of the
 // content types possible for the POST data.
 #[derive(ApiRequest, Debug)]
@@ -171,6 +215,112 @@ impl TransactionsApi {
         self.list(&accept_type, page)
     }
 
+    /// Stream transactions
+    ///
+    /// Open a server-sent event stream of on-chain committed transactions. Each
+    /// event carries one transaction, rendered as JSON, or as the hex encoded BCS
+    /// of the TransactionOnChainData if BCS is requested. The stream keeps
+    /// following the ledger as new transactions are committed.
+    ///
+    /// The stream can be resumed by passing the version after the last received
+    /// transaction as the start version. If that version has been pruned, then a
+    /// 410 will be returned.
+    ///
+    /// Transactions can optionally be filtered by sender, entry function, or the
+    /// type of events they emit. When several filters are given, a transaction
+    /// must match all of them to be sent.
+    ///
+    /// The number of streams open at the same time on a node is limited. If the
+    /// limit has been reached, then a 503 will be returned.
+    #[oai(
+        path = "/transactions/stream",
+        method = "get",
+        operation_id = "stream_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn stream_transactions(
+        &self,
+        accept_type: AcceptType,
+        /// Ledger version to start streaming transactions from
+        ///
+        /// If not provided, defaults to the next transaction to be committed
+        start: Query<Option<U64>>,
+        /// Only stream user transactions sent by this account
+        sender: Query<Option<Address>>,
+        /// Only stream user transactions calling this entry function, e.g.
+        /// `0x1::aptos_account::transfer`
+        entry_function: Query<Option<EntryFunctionId>>,
+        /// Only stream transactions emitting an event of this type, e.g.
+        /// `0x1::coin::DepositEvent`
+        event_type: Query<Option<MoveStructTag>>,
+    ) -> poem::Result<EventStream<BoxStream<'static, StreamedTransaction>>, BasicErrorWith404> {
+        fail_point_poem("endpoint_stream_transactions")?;
+        if !self.context.node_config.api.transaction_stream_enabled {
+            return Err(api_disabled("Stream transactions"));
+        }
+        self.context
+            .check_api_output_enabled("Stream transactions", &accept_type)?;
+
+        let entry_function = entry_function
+            .0
+            .map(|entry_function| -> anyhow::Result<(ModuleId, Identifier)> {
+                entry_function.verify()?;
+                Ok((entry_function.module.into(), entry_function.name.into()))
+            })
+            .transpose()
+            .context("'entry_function' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let event_type = event_type
+            .0
+            .map(|event_type| -> anyhow::Result<StructTag> {
+                event_type.verify(0)?;
+                event_type.try_into()
+            })
+            .transpose()
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let filter = TransactionFilter {
+            sender: sender.0.map(|address| address.into()),
+            entry_function,
+            event_type,
+        };
+
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let start_version = match start.0 {
+            Some(start) => {
+                if start.0 < ledger_info.oldest_ledger_version.0 {
+                    return Err(version_pruned(start.0, &ledger_info));
+                }
+                start.0
+            },
+            None => ledger_info.version() + 1,
+        };
+
+        let permit = self
+            .context
+            .try_acquire_transaction_stream_permit()
+            .ok_or_else(|| {
+                BasicErrorWith404::service_unavailable_with_code(
+                    "Too many transaction streams are open, retry later",
+                    AptosErrorCode::RateLimitExceeded,
+                    &ledger_info,
+                )
+            })?;
+
+        Ok(EventStream::new(transaction_stream(
+            self.context.clone(),
+            accept_type,
+            start_version,
+            filter,
+            permit,
+        ))
+        .keep_alive(TRANSACTION_STREAM_KEEP_ALIVE))
+    }
+
     /// Get transaction by hash
     ///
     /// Look up a transaction by its hash. This is the same hash that is returned
@@ -267,6 +417,76 @@ impl TransactionsApi {
         self.list_by_account(&accept_type, page, address.0)
     }
 
+    /// Get account pending transactions
+    ///
+    /// Retrieves the transactions from an account which are waiting in the mempool
+    /// of this node, ordered by sequence number, along with their state in mempool.
+    /// Transactions submitted to other nodes, which haven't been broadcast to this
+    /// node yet, aren't returned.
+    ///
+    /// To retrieve on-chain committed transactions, use /accounts/{address}/transactions.
+    #[oai(
+        path = "/accounts/:address/pending_transactions",
+        method = "get",
+        operation_id = "get_account_pending_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_account_pending_transactions(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+    ) -> BasicResult<Vec<MempoolTransaction>> {
+        fail_point_poem("endpoint_get_account_pending_transactions")?;
+        self.context
+            .check_api_output_enabled("Get account pending transactions", &accept_type)?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        self.list_pending(
+            &accept_type,
+            &ledger_info,
+            Some(address.0.into()),
+            self.context.node_config.mempool.capacity_per_user,
+        )
+        .await
+    }
+
+    /// Get mempool transactions
+    ///
+    /// Retrieves the transactions waiting in the mempool of this node, ordered by
+    /// sender and sequence number, along with their state in mempool. This allows
+    /// node operators to inspect the content of mempool, and must be enabled with
+    /// `mempool_inspection_enabled` in the API config.
+    #[oai(
+        path = "/mempool/transactions",
+        method = "get",
+        operation_id = "get_mempool_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_mempool_transactions(
+        &self,
+        accept_type: AcceptType,
+        /// Max number of transactions to retrieve.
+        ///
+        /// If not provided, defaults to the max page size of this API
+        limit: Query<Option<u16>>,
+    ) -> BasicResult<Vec<MempoolTransaction>> {
+        fail_point_poem("endpoint_get_mempool_transactions")?;
+        if !self.context.node_config.api.mempool_inspection_enabled {
+            return Err(api_disabled("Get mempool transactions"));
+        }
+        self.context
+            .check_api_output_enabled("Get mempool transactions", &accept_type)?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let max_page_size = self
+            .context
+            .node_config
+            .api
+            .max_mempool_inspection_page_size;
+        let limit = determine_limit(limit.0, max_page_size, max_page_size, &ledger_info)?;
+        self.list_pending(&accept_type, &ledger_info, None, limit as usize)
+            .await
+    }
+
     /// Submit transaction
     ///
     /// This endpoint accepts transaction submissions in two formats.
@@ -394,6 +614,12 @@ impl TransactionsApi {
     ///
     /// To use this endpoint with BCS, you must submit a SignedTransaction
     /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
+    ///
+    /// The transaction can be simulated against the state at an older ledger version, and when
+    /// using JSON, `state_overrides` can be provided to replace resources or modules before
+    /// simulating. This can be used to see how a transaction would behave if e.g. an account held
+    /// a different `CoinStore` balance or a module was already upgraded. Overrides are never
+    /// persisted.
     #[oai(
         path = "/transactions/simulate",
         method = "post",
@@ -412,7 +638,10 @@ impl TransactionsApi {
         /// If set to true, the transaction will use a higher price than the original
         /// estimate.
         estimate_prioritized_gas_unit_price: Query<Option<bool>>,
-        data: SubmitTransactionPost,
+        /// Ledger version to simulate the transaction at, the latest version is used if not
+        /// provided
+        ledger_version: Query<Option<U64>>,
+        data: SimulateTransactionPost,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         data.verify()
             .context("Simulated transaction invalid")
@@ -429,7 +658,74 @@ impl TransactionsApi {
         self.context
             .check_api_output_enabled("Simulate transaction", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
-        let mut signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
+        let version = match ledger_version.0 {
+            Some(version) => {
+                let version = version.0;
+                if version > ledger_info.version() {
+                    return Err(SubmitTransactionError::bad_request_with_code(
+                        format!(
+                            "Ledger version {} is newer than the latest ledger version",
+                            version
+                        ),
+                        AptosErrorCode::VersionNotFound,
+                        &ledger_info,
+                    ));
+                } else if version < ledger_info.oldest_ledger_version.0 {
+                    return Err(SubmitTransactionError::bad_request_with_code(
+                        format!("Ledger version {} has been pruned", version),
+                        AptosErrorCode::VersionPruned,
+                        &ledger_info,
+                    ));
+                }
+                version
+            },
+            None => ledger_info.version(),
+        };
+        let state_view = self
+            .context
+            .state_view_at_version(version)
+            .context(format!("Failed to read state at version {}", version))
+            .map_err(|err| {
+                SubmitTransactionError::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        let (data, state_overrides) = match data {
+            SimulateTransactionPost::Json(data) => {
+                let SimulateTransactionRequest {
+                    transaction,
+                    state_overrides,
+                } = data.0;
+                (
+                    SubmitTransactionPost::Json(Json(transaction)),
+                    state_overrides.unwrap_or_default(),
+                )
+            },
+            SimulateTransactionPost::Bcs(data) => (SubmitTransactionPost::Bcs(data), vec![]),
+        };
+        let overrides = self.state_overrides(&ledger_info, &state_view, state_overrides)?;
+        let state_view = OverriddenStateView::new(&state_view, &overrides);
+
+        let mut signed_transaction = match data {
+            // The overrides must also be visible when decoding the request, e.g. when calling a
+            // function that only exists in an overridden module
+            SubmitTransactionPost::Json(data) => state_view
+                .as_move_resolver()
+                .as_converter(self.context.db.clone())
+                .try_into_signed_transaction_poem(data.0, self.context.chain_id())
+                .context("Failed to create SignedTransaction from SubmitTransactionRequest")
+                .map_err(|err| {
+                    SubmitTransactionError::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        &ledger_info,
+                    )
+                })?,
+            data => self.get_signed_transaction(&ledger_info, data)?,
+        };
 
         let estimated_gas_unit_price = match (
             estimate_gas_unit_price.0.unwrap_or_default(),
@@ -456,30 +752,31 @@ impl TransactionsApi {
                 / u64::from(gas_params.txn.gas_unit_scaling_factor);
             let max_number_of_gas_units = u64::from(gas_params.txn.maximum_number_of_gas_units);
 
-            // Retrieve account balance to determine max gas available
-            let account_state = self
-                .context
-                .get_account_state(
-                    signed_transaction.sender(),
-                    ledger_info.version(),
-                    &ledger_info,
-                )?
-                .ok_or_else(|| {
-                    SubmitTransactionError::bad_request_with_code(
-                        "Account not found",
-                        AptosErrorCode::InvalidInput,
+            // Retrieve account balance to determine max gas available, from the state the
+            // transaction is simulated against
+            let sender = signed_transaction.sender();
+            let account = AccountWithStateView::new(&sender, &state_view);
+            let account_exists = account
+                .get_account_resource()
+                .map_err(|err| {
+                    SubmitTransactionError::internal_with_code(
+                        format!("Failed to get account resource {}", err),
+                        AptosErrorCode::InternalError,
                         &ledger_info,
                     )
-                })?;
-            let coin_store: CoinStoreResource = account_state
+                })?
+                .is_some();
+            if !account_exists {
+                return Err(SubmitTransactionError::bad_request_with_code(
+                    "Account not found",
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                ));
+            }
+            let coin_store: CoinStoreResource = account
                 .get_coin_store_resource()
                 .and_then(|inner| {
-                    inner.ok_or_else(|| {
-                        anyhow!(
-                            "No coin store found for account {}",
-                            signed_transaction.sender()
-                        )
-                    })
+                    inner.ok_or_else(|| anyhow!("No coin store found for account {}", sender))
                 })
                 .map_err(|err| {
                     SubmitTransactionError::internal_with_code(
@@ -521,14 +818,19 @@ impl TransactionsApi {
             );
         }
 
-        self.simulate(&accept_type, ledger_info, signed_transaction)
-            .await
+        self.simulate(
+            &accept_type,
+            ledger_info,
+            &state_view,
+            version,
+            signed_transaction,
+        )
     }
 
     /// Encode submission
     ///
     /// This endpoint accepts an EncodeSubmissionRequest, which internally is a
-    /// UserTransactionRequestInner (and optionally secondary signers) encoded
+    /// UserTransactionRequestInner (and optionally secondary signers and a fee payer) encoded
     /// as JSON, validates the request format, and then returns that request
     /// encoded in BCS. The client can then use this to create a transaction
     /// signature to be used in a SubmitTransactionRequest, which it then
@@ -832,6 +1134,61 @@ impl TransactionsApi {
         }
     }
 
+    /// List the transactions pending in mempool of an account, or of all the accounts
+    async fn list_pending(
+        &self,
+        accept_type: &AcceptType,
+        ledger_info: &LedgerInfo,
+        account: Option<AccountAddress>,
+        limit: usize,
+    ) -> BasicResult<Vec<MempoolTransaction>> {
+        let txns = self
+            .context
+            .get_pending_transactions(account, limit)
+            .await
+            .context("Failed to get pending transactions from mempool")
+            .map_err(|err| {
+                BasicError::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+            })?;
+
+        match accept_type {
+            AcceptType::Json => {
+                let state_view = self.context.latest_state_view_poem(ledger_info)?;
+                let resolver = state_view.as_move_resolver();
+                let converter = resolver.as_converter(self.context.db.clone());
+                let txns = txns
+                    .into_iter()
+                    .map(|info| {
+                        let txn = mempool_transaction_bcs(info);
+                        let transaction =
+                            converter.try_into_pending_transaction_poem(txn.transaction.clone())?;
+                        Ok(MempoolTransaction {
+                            transaction,
+                            timeline_state: txn.timeline_state,
+                            parked: txn.parked,
+                            ranking_score: txn.ranking_score.into(),
+                            insertion_timestamp_usecs: txn.insertion_timestamp_usecs.into(),
+                            submitted_by: txn.submitted_by,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to convert pending transactions from mempool")
+                    .map_err(|err| {
+                        BasicError::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            ledger_info,
+                        )
+                    })?;
+                BasicResponse::try_from_json((txns, ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Bcs => {
+                let txns: Vec<_> = txns.into_iter().map(mempool_transaction_bcs).collect();
+                BasicResponse::try_from_bcs((txns, ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
     /// Parses a single signed transaction
     fn get_signed_transaction(
         &self,
@@ -1160,30 +1517,190 @@ impl TransactionsApi {
     // TODO: This function leverages a lot of types from aptos_types, use the
     // local API types and just return those directly, instead of converting
     // from these types in render_transactions.
-    /// Simulate a transaction in the VM
+    /// Simulate a transaction in the VM against the state at `version`
     ///
     /// Note: this returns a `Vec<UserTransaction>`, but for backwards compatibility, this can't
     /// be removed even though, there is only one possible transaction
-    pub async fn simulate(
+    pub fn simulate<S: StateView>(
         &self,
         accept_type: &AcceptType,
         ledger_info: LedgerInfo,
+        state_view: &S,
+        version: u64,
         txn: SignedTransaction,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
+        let simulated_txn = Self::simulate_in_state_view(&ledger_info, state_view, version, txn)?;
+
+        match accept_type {
+            AcceptType::Json => {
+                // The transaction is rendered against the state it was simulated in, so that e.g.
+                // resources of overridden modules can be decoded
+                let resolver = state_view.as_move_resolver();
+                let transaction = self
+                    .context
+                    .db
+                    .get_block_timestamp(version)
+                    .and_then(|timestamp| {
+                        resolver
+                            .as_converter(self.context.db.clone())
+                            .try_into_onchain_transaction(timestamp, simulated_txn)
+                    })
+                    .context("Failed to convert simulated transaction")
+                    .map_err(|err| {
+                        SubmitTransactionError::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+
+                // Users can only make requests to simulate UserTransactions, so unpack
+                // the Transaction into a UserTransaction.
+                let user_transaction = match transaction {
+                    Transaction::UserTransaction(user_txn) => *user_txn,
+                    _ => {
+                        return Err(SubmitTransactionError::internal_with_code(
+                            "Simulation transaction resulted in a non-UserTransaction",
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        ))
+                    },
+                };
+                BasicResponse::try_from_json((
+                    vec![user_transaction],
+                    &ledger_info,
+                    BasicResponseStatus::Ok,
+                ))
+            },
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((simulated_txn, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
+    /// Converts the requested state overrides into the state items they replace
+    fn state_overrides<S: StateView>(
+        &self,
+        ledger_info: &LedgerInfo,
+        state_view: &S,
+        state_overrides: Vec<StateOverride>,
+    ) -> Result<HashMap<StateKey, Option<StateValue>>, SubmitTransactionError> {
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
+
+        let mut overrides = HashMap::new();
+        for state_override in state_overrides {
+            let (state_key, value) = match state_override {
+                StateOverride::ResourceOverride(resource) => {
+                    let struct_tag: StructTag = resource
+                        .resource_type
+                        .try_into()
+                        .context("Invalid resource type")
+                        .map_err(|err| {
+                            SubmitTransactionError::bad_request_with_code(
+                                err,
+                                AptosErrorCode::InvalidInput,
+                                ledger_info,
+                            )
+                        })?;
+                    let value = match resource.data {
+                        Some(data) => Some(
+                            converter
+                                .try_into_vm_value(
+                                    &TypeTag::Struct(Box::new(struct_tag.clone())),
+                                    data,
+                                )
+                                .and_then(|value| {
+                                    value
+                                        .simple_serialize()
+                                        .ok_or_else(|| anyhow!("Failed to serialize resource"))
+                                })
+                                .context(format!(
+                                    "Failed to convert override data into a {} resource",
+                                    struct_tag
+                                ))
+                                .map_err(|err| {
+                                    SubmitTransactionError::bad_request_with_code(
+                                        err,
+                                        AptosErrorCode::InvalidInput,
+                                        ledger_info,
+                                    )
+                                })?,
+                        ),
+                        None => None,
+                    };
+                    let access_path =
+                        AccessPath::resource_access_path(resource.address.into(), struct_tag)
+                            .map_err(|err| {
+                                SubmitTransactionError::bad_request_with_code(
+                                    err,
+                                    AptosErrorCode::InvalidInput,
+                                    ledger_info,
+                                )
+                            })?;
+                    (StateKey::access_path(access_path), value)
+                },
+                StateOverride::ModuleOverride(module) => {
+                    let bytecode = module.bytecode.0;
+                    let compiled_module = CompiledModule::deserialize(&bytecode)
+                        .context("Invalid module bytecode")
+                        .map_err(|err| {
+                            SubmitTransactionError::bad_request_with_code(
+                                err,
+                                AptosErrorCode::InvalidInput,
+                                ledger_info,
+                            )
+                        })?;
+                    let access_path = AccessPath::code_access_path(compiled_module.self_id());
+                    (StateKey::access_path(access_path), Some(bytecode))
+                },
+            };
+            // Keep the metadata of an overridden state item, so e.g. its deposit is refunded the
+            // same way as if it had been modified on chain
+            let value = match value {
+                Some(bytes) => Some(
+                    match state_view
+                        .get_state_value(&state_key)
+                        .map_err(|err| {
+                            SubmitTransactionError::internal_with_code(
+                                err,
+                                AptosErrorCode::InternalError,
+                                ledger_info,
+                            )
+                        })?
+                        .and_then(StateValue::into_metadata)
+                    {
+                        Some(metadata) => StateValue::new_with_metadata(bytes, metadata),
+                        None => StateValue::new_legacy(bytes),
+                    },
+                ),
+                None => None,
+            };
+            overrides.insert(state_key, value);
+        }
+        Ok(overrides)
+    }
+
+    /// Executes a transaction in the VM without committing it, and builds up the transaction as
+    /// if it was committed at `version`
+    fn simulate_in_state_view<S: StateView>(
+        ledger_info: &LedgerInfo,
+        state_view: &S,
+        version: u64,
+        txn: SignedTransaction,
+    ) -> Result<TransactionOnChainData, SubmitTransactionError> {
         // Transactions shouldn't have a valid signature or this could be used to attack
         if txn.signature_is_valid() {
             return Err(SubmitTransactionError::bad_request_with_code(
                 "Simulated transactions must have a non-valid signature",
                 AptosErrorCode::InvalidInput,
-                &ledger_info,
+                ledger_info,
             ));
         }
 
         // Simulate transaction
-        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
         let move_resolver = state_view.as_move_resolver();
         let (_, output) = AptosVM::simulate_signed_transaction(&txn, &move_resolver);
-        let version = ledger_info.version();
 
         // Ensure that all known statuses return their values in the output (even if they aren't supposed to)
         let exe_status = match output.status().clone() {
@@ -1204,46 +1721,14 @@ impl TransactionsApi {
             output.gas_used(),
             exe_status,
         );
-        let simulated_txn = TransactionOnChainData {
+        Ok(TransactionOnChainData {
             version,
             transaction: txn,
             info,
             events: output.events().to_vec(),
             accumulator_root_hash: zero_hash,
             changes: output.write_set().clone(),
-        };
-
-        match accept_type {
-            AcceptType::Json => {
-                let transactions = self
-                    .context
-                    .render_transactions_non_sequential(&ledger_info, vec![simulated_txn])?;
-
-                // Users can only make requests to simulate UserTransactions, so unpack
-                // the Vec<Transaction> into Vec<UserTransaction>.
-                let mut user_transactions = Vec::new();
-                for transaction in transactions.into_iter() {
-                    match transaction {
-                        Transaction::UserTransaction(user_txn) => user_transactions.push(*user_txn),
-                        _ => {
-                            return Err(SubmitTransactionError::internal_with_code(
-                                "Simulation transaction resulted in a non-UserTransaction",
-                                AptosErrorCode::InternalError,
-                                &ledger_info,
-                            ))
-                        },
-                    }
-                }
-                BasicResponse::try_from_json((
-                    user_transactions,
-                    &ledger_info,
-                    BasicResponseStatus::Ok,
-                ))
-            },
-            AcceptType::Bcs => {
-                BasicResponse::try_from_bcs((simulated_txn, &ledger_info, BasicResponseStatus::Ok))
-            },
-        }
+        })
     }
 
     /// Encode message as BCS
@@ -1271,8 +1756,23 @@ impl TransactionsApi {
                 BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
             })?;
 
-        let raw_message = match request.secondary_signers {
-            Some(secondary_signer_addresses) => signing_message(
+        let raw_message = match (request.secondary_signers, request.fee_payer_address) {
+            (secondary_signers, Some(fee_payer_address)) => signing_message(
+                &RawTransactionWithData::new_multi_agent_with_fee_payer(
+                    raw_txn,
+                    secondary_signers
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|v| v.into())
+                        .collect(),
+                    fee_payer_address.into(),
+                ),
+            )
+            .context("Invalid transaction to generate signing message")
+            .map_err(|err| {
+                BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
+            })?,
+            (Some(secondary_signer_addresses), None) => signing_message(
                 &RawTransactionWithData::new_multi_agent(
                     raw_txn,
                     secondary_signer_addresses
@@ -1285,7 +1785,7 @@ impl TransactionsApi {
             .map_err(|err| {
                 BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
             })?,
-            None => raw_txn
+            (None, None) => raw_txn
                 .signing_message()
                 .context("Invalid transaction to generate signing message")
                 .map_err(|err| {
@@ -1325,3 +1825,31 @@ fn override_gas_parameters(
     // TODO: Check that signature is null, this would just be helpful for downstream use
     SignedTransaction::new_with_authenticator(raw_txn, signed_txn.authenticator())
 }
+
+/// Converts a transaction pending in mempool into its API representation
+fn mempool_transaction_bcs(info: PendingTransactionInfo) -> MempoolTransactionBcs {
+    let timeline_state = match info.timeline_state {
+        TimelineState::Ready(_) => MempoolTimelineState::Ready,
+        TimelineState::NotReady => MempoolTimelineState::NotReady,
+        TimelineState::NonQualified => MempoolTimelineState::NonQualified,
+    };
+    let submitted_by = match info.insertion_info.submitted_by {
+        SubmittedBy::Client => MempoolTransactionSubmitter::Client,
+        SubmittedBy::Downstream => MempoolTransactionSubmitter::Downstream,
+        SubmittedBy::PeerValidator => MempoolTransactionSubmitter::PeerValidator,
+    };
+    let insertion_timestamp_usecs = info
+        .insertion_info
+        .insertion_time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as u64;
+    MempoolTransactionBcs {
+        transaction: info.txn,
+        timeline_state,
+        parked: info.parked,
+        ranking_score: info.ranking_score,
+        insertion_timestamp_usecs,
+        submitted_by,
+    }
+}
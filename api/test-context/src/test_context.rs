@@ -830,6 +830,34 @@ impl TestContext {
         payload: Value,
         status_code: u16,
     ) -> Value {
+        let request = self.simulation_request(sender, payload).await;
+        self.expect_status_code(status_code)
+            .post("/transactions/simulate", request)
+            .await
+    }
+
+    pub async fn simulate_transaction_with_overrides(
+        &mut self,
+        sender: &LocalAccount,
+        payload: Value,
+        ledger_version: Option<u64>,
+        state_overrides: Value,
+        status_code: u16,
+    ) -> Value {
+        let mut request = self.simulation_request(sender, payload).await;
+        request["state_overrides"] = state_overrides;
+        let path = match ledger_version {
+            Some(ledger_version) => {
+                format!("/transactions/simulate?ledger_version={}", ledger_version)
+            },
+            None => "/transactions/simulate".to_string(),
+        };
+        self.expect_status_code(status_code)
+            .post(&path, request)
+            .await
+    }
+
+    async fn simulation_request(&mut self, sender: &LocalAccount, payload: Value) -> Value {
         let mut request = json!({
             "sender": sender.address(),
             "sequence_number": sender.sequence_number().to_string(),
@@ -860,10 +888,7 @@ impl TestContext {
             "public_key": HexEncodedBytes::from(sender.public_key().to_bytes().to_vec()),
             "signature": HexEncodedBytes::from(sig.to_bytes().to_vec()),
         });
-
-        self.expect_status_code(status_code)
-            .post("/transactions/simulate", request)
-            .await
+        request
     }
 
     pub fn prepend_path(&self, path: &str) -> String {
@@ -101,6 +101,8 @@ pub enum AptosErrorCode {
     HealthCheckFailed = 500,
     /// The mempool is full, no new transactions can be submitted.
     MempoolIsFull = 501,
+    /// The client sent too many requests, and should retry later.
+    RateLimitExceeded = 502,
 
     /// Internal server error
     InternalError = 600,
@@ -45,10 +45,14 @@ pub use table::{RawTableItemRequest, TableItemRequest};
 pub use transaction::{
     AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
     DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
-    GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction, ModuleBundlePayload,
-    MultiAgentSignature, MultiEd25519Signature, MultisigPayload, MultisigTransactionPayload,
-    PendingTransaction, ScriptPayload, ScriptWriteSet, SubmitTransactionRequest, Transaction,
-    TransactionData, TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
+    FeePayerSignature, GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction,
+    MempoolTimelineState, MempoolTransaction, MempoolTransactionBcs, MempoolTransactionSubmitter,
+    ModuleBundlePayload, ModuleOverride, MultiAgentSignature, MultiEd25519Signature,
+    MultiKeyMemberScheme, MultiKeyMemberSignature, MultiKeyPublicKey, MultiKeySignature,
+    MultisigPayload, MultisigTransactionPayload, PendingTransaction, ResourceOverride,
+    ScriptPayload, ScriptWriteSet, Secp256k1EcdsaSignature, SimulateTransactionRequest,
+    StateOverride, StreamedTransaction, SubmitTransactionRequest, Transaction, TransactionData,
+    TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
     TransactionSignature, TransactionSigningMessage, TransactionsBatchSingleSubmissionFailure,
     TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
     UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
@@ -11,17 +11,24 @@ use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
     ed25519::{self, Ed25519PublicKey, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH},
     multi_ed25519::{self, MultiEd25519PublicKey, BITMAP_NUM_OF_BYTES, MAX_NUM_OF_KEYS},
+    secp256k1_ecdsa::{
+        self, Secp256k1EcdsaPublicKey, SECP256K1_ECDSA_PUBLIC_KEY_LENGTH,
+        SECP256K1_ECDSA_SIGNATURE_LENGTH,
+    },
 };
 use aptos_types::{
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
     contract_event::{ContractEvent, EventWithVersion},
     transaction::{
-        authenticator::{AccountAuthenticator, TransactionAuthenticator, MAX_NUM_OF_SIGS},
+        authenticator::{
+            self, AccountAuthenticator, AnyPublicKey, AnySignature, MultiKey,
+            TransactionAuthenticator, MAX_NUM_OF_SIGS,
+        },
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
@@ -151,6 +158,18 @@ impl
     }
 }
 
+/// A committed transaction pushed over the transaction stream
+///
+/// When JSON is requested, this is the rendered `Transaction`. When BCS is
+/// requested, this is the hex encoded BCS of the `TransactionOnChainData`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(untagged)]
+#[oai(one_of)]
+pub enum StreamedTransaction {
+    Json(Transaction),
+    Bcs(HexEncodedBytes),
+}
+
 /// Enum of the different types of transactions in Aptos
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -352,6 +371,57 @@ impl From<(SignedTransaction, TransactionPayload)> for PendingTransaction {
     }
 }
 
+/// A transaction waiting in mempool, along with its state in mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolTransaction {
+    pub transaction: PendingTransaction,
+    pub timeline_state: MempoolTimelineState,
+    /// Whether the transaction is parked, i.e. it can't be included in the next block because
+    /// of a gap in the sequence numbers of its sender
+    pub parked: bool,
+    pub ranking_score: U64,
+    pub insertion_timestamp_usecs: U64,
+    pub submitted_by: MempoolTransactionSubmitter,
+}
+
+/// A transaction waiting in mempool, along with its state in mempool, as returned when requesting
+/// BCS
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolTransactionBcs {
+    pub transaction: SignedTransaction,
+    pub timeline_state: MempoolTimelineState,
+    pub parked: bool,
+    pub ranking_score: u64,
+    pub insertion_timestamp_usecs: u64,
+    pub submitted_by: MempoolTransactionSubmitter,
+}
+
+/// Whether a transaction waiting in mempool is broadcast to the peers of the node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum MempoolTimelineState {
+    /// The transaction is ready to be broadcast
+    Ready,
+    /// The transaction may be broadcast later, e.g. once it is not parked anymore
+    NotReady,
+    /// The transaction will never be broadcast, e.g. because it was received from a validator
+    NonQualified,
+}
+
+/// Where a transaction waiting in mempool was received from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum MempoolTransactionSubmitter {
+    /// The transaction was submitted to the REST API of the node
+    Client,
+    /// The transaction was broadcast by a downstream peer
+    Downstream,
+    /// The transaction was broadcast by another validator
+    PeerValidator,
+}
+
 /// A transaction submitted by a user to change the state of the blockchain
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct UserTransaction {
@@ -410,6 +480,69 @@ pub struct TransactionsBatchSingleSubmissionFailure {
     pub transaction_index: usize,
 }
 
+/// A request to simulate a transaction, optionally with a set of ephemeral overrides applied on
+/// top of the state it is simulated against
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SimulateTransactionRequest {
+    #[serde(flatten)]
+    #[oai(flatten)]
+    pub transaction: SubmitTransactionRequest,
+    /// Overrides applied to the state before simulating the transaction, these are never persisted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<Vec<StateOverride>>,
+}
+
+impl VerifyInput for SimulateTransactionRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.transaction.verify()?;
+        for state_override in self.state_overrides.iter().flatten() {
+            state_override.verify()?;
+        }
+        Ok(())
+    }
+}
+
+/// An override of a single state item, used when simulating a transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
+pub enum StateOverride {
+    ResourceOverride(ResourceOverride),
+    ModuleOverride(ModuleOverride),
+}
+
+impl VerifyInput for StateOverride {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            StateOverride::ResourceOverride(inner) => inner.resource_type.verify(0),
+            StateOverride::ModuleOverride(inner) => {
+                if inner.bytecode.inner().is_empty() {
+                    bail!("Module override bytecode must not be empty")
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Replace the value of a resource, or add it if it doesn't exist
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ResourceOverride {
+    pub address: Address,
+    pub resource_type: MoveStructTag,
+    /// The resource in the same JSON format as returned by the resource endpoints, the resource is
+    /// deleted if not provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// Replace the code of a module, or add it if it doesn't exist
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ModuleOverride {
+    /// The compiled module, the address and name are read from the bytecode
+    pub bytecode: HexEncodedBytes,
+}
+
 // TODO: Rename this to remove the Inner when we cut over.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct UserTransactionRequestInner {
@@ -469,6 +602,9 @@ pub struct EncodeSubmissionRequest {
     /// Secondary signer accounts of the request for Multi-agent
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secondary_signers: Option<Vec<Address>>,
+    /// Fee payer account of the request, if gas is paid by an account other than the sender
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_payer_address: Option<Address>,
 }
 
 impl VerifyInput for EncodeSubmissionRequest {
@@ -848,6 +984,9 @@ pub enum TransactionSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
     MultiAgentSignature(MultiAgentSignature),
+    FeePayerSignature(FeePayerSignature),
+    Secp256k1EcdsaSignature(Secp256k1EcdsaSignature),
+    MultiKeySignature(MultiKeySignature),
 }
 
 impl VerifyInput for TransactionSignature {
@@ -856,6 +995,9 @@ impl VerifyInput for TransactionSignature {
             TransactionSignature::Ed25519Signature(inner) => inner.verify(),
             TransactionSignature::MultiEd25519Signature(inner) => inner.verify(),
             TransactionSignature::MultiAgentSignature(inner) => inner.verify(),
+            TransactionSignature::FeePayerSignature(inner) => inner.verify(),
+            TransactionSignature::Secp256k1EcdsaSignature(inner) => inner.verify(),
+            TransactionSignature::MultiKeySignature(inner) => inner.verify(),
         }
     }
 }
@@ -868,6 +1010,9 @@ impl TryFrom<TransactionSignature> for TransactionAuthenticator {
             TransactionSignature::Ed25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiEd25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiAgentSignature(sig) => sig.try_into()?,
+            TransactionSignature::FeePayerSignature(sig) => sig.try_into()?,
+            TransactionSignature::Secp256k1EcdsaSignature(sig) => sig.try_into()?,
+            TransactionSignature::MultiKeySignature(sig) => sig.try_into()?,
         })
     }
 }
@@ -1064,18 +1209,260 @@ impl TryFrom<MultiEd25519Signature> for AccountAuthenticator {
     }
 }
 
+/// A single secp256k1 ECDSA signature
+///
+/// The public key is the 65-byte uncompressed key, and the signature is the 64-byte `r || s`
+/// form (without a recovery id) over the SHA3-256 hash of the signing message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct Secp256k1EcdsaSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+}
+
+impl VerifyInput for Secp256k1EcdsaSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        let public_key_len = self.public_key.inner().len();
+        let signature_len = self.signature.inner().len();
+        if public_key_len != SECP256K1_ECDSA_PUBLIC_KEY_LENGTH {
+            bail!(
+                "Secp256k1Ecdsa signature's public key is an invalid number of bytes, should be {} bytes but found {}",
+                SECP256K1_ECDSA_PUBLIC_KEY_LENGTH, public_key_len
+            )
+        } else if signature_len != SECP256K1_ECDSA_SIGNATURE_LENGTH {
+            bail!(
+                "Secp256k1Ecdsa signature length is an invalid number of bytes, should be {} bytes but found {}",
+                SECP256K1_ECDSA_SIGNATURE_LENGTH, signature_len
+            )
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl TryFrom<Secp256k1EcdsaSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256k1EcdsaSignature) -> Result<Self, Self::Error> {
+        let Secp256k1EcdsaSignature {
+            public_key,
+            signature,
+        } = value;
+        Ok(TransactionAuthenticator::secp256k1_ecdsa(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256k1EcdsaPublicKey")?,
+            signature
+                .inner()
+                .try_into()
+                .context("Failed to parse given signature as a Secp256k1EcdsaSignature")?,
+        ))
+    }
+}
+
+impl TryFrom<Secp256k1EcdsaSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256k1EcdsaSignature) -> Result<Self, Self::Error> {
+        let Secp256k1EcdsaSignature {
+            public_key,
+            signature,
+        } = value;
+        Ok(AccountAuthenticator::secp256k1_ecdsa(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256k1EcdsaPublicKey")?,
+            signature
+                .inner()
+                .try_into()
+                .context("Failed to parse given signature as a Secp256k1EcdsaSignature")?,
+        ))
+    }
+}
+
+/// Signature scheme of a member of a multi key
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum MultiKeyMemberScheme {
+    Ed25519,
+    Secp256k1Ecdsa,
+}
+
+/// A public key of a member of a multi key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MultiKeyPublicKey {
+    pub scheme: MultiKeyMemberScheme,
+    pub public_key: HexEncodedBytes,
+}
+
+/// A signature of a member of a multi key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MultiKeyMemberSignature {
+    pub scheme: MultiKeyMemberScheme,
+    pub signature: HexEncodedBytes,
+}
+
+/// A multi key signature
+///
+/// This allows k-of-n signing for a transaction, where the keys can be of different schemes
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MultiKeySignature {
+    /// The public keys of the members of the multi key
+    pub public_keys: Vec<MultiKeyPublicKey>,
+    /// The signatures, in the order of the public keys that signed
+    pub signatures: Vec<MultiKeyMemberSignature>,
+    /// The number of signatures required for a successful transaction
+    pub signatures_required: u8,
+    pub bitmap: HexEncodedBytes,
+}
+
+impl VerifyInput for MultiKeySignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        if self.public_keys.is_empty() {
+            bail!("MultiKey signature has no public keys")
+        } else if self.signatures.is_empty() {
+            bail!("MultiKey signature has no signatures")
+        } else if self.public_keys.len() > MAX_NUM_OF_KEYS {
+            bail!(
+                "MultiKey signature has over the maximum number of public keys {}",
+                MAX_NUM_OF_KEYS
+            )
+        } else if self.signatures.len() > MAX_NUM_OF_SIGS {
+            bail!(
+                "MultiKey signature has over the maximum number of signatures {}",
+                MAX_NUM_OF_SIGS
+            )
+        } else if self.signatures.len() < self.signatures_required as usize {
+            bail!("MultiKey signature does not have enough signatures to pass the threshold")
+        } else if self.signatures_required == 0 {
+            bail!("MultiKey signature threshold must be greater than 0")
+        } else if self.bitmap.inner().len() != BITMAP_NUM_OF_BYTES {
+            bail!(
+                "MultiKey signature has an invalid number of bitmap bytes {} expected {}",
+                self.bitmap.inner().len(),
+                BITMAP_NUM_OF_BYTES
+            );
+        }
+        for public_key in self.public_keys.iter() {
+            let expected_len = match public_key.scheme {
+                MultiKeyMemberScheme::Ed25519 => ED25519_PUBLIC_KEY_LENGTH,
+                MultiKeyMemberScheme::Secp256k1Ecdsa => SECP256K1_ECDSA_PUBLIC_KEY_LENGTH,
+            };
+            if public_key.public_key.inner().len() != expected_len {
+                bail!("MultiKey signature has a public key with the wrong public key length")
+            }
+        }
+        for signature in self.signatures.iter() {
+            let expected_len = match signature.scheme {
+                MultiKeyMemberScheme::Ed25519 => ED25519_SIGNATURE_LENGTH,
+                MultiKeyMemberScheme::Secp256k1Ecdsa => SECP256K1_ECDSA_SIGNATURE_LENGTH,
+            };
+            if signature.signature.inner().len() != expected_len {
+                bail!("MultiKey signature has a signature with the wrong signature length")
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<MultiKeySignature> for (MultiKey, authenticator::MultiKeySignature) {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MultiKeySignature) -> Result<Self, Self::Error> {
+        let MultiKeySignature {
+            public_keys,
+            signatures,
+            signatures_required,
+            bitmap,
+        } = value;
+
+        let public_keys = public_keys
+            .into_iter()
+            .map(|public_key| {
+                let bytes = public_key.public_key.inner();
+                Ok(match public_key.scheme {
+                    MultiKeyMemberScheme::Ed25519 => {
+                        AnyPublicKey::ed25519(bytes.try_into().context(
+                            "Failed to parse given public_key bytes as a Ed25519PublicKey",
+                        )?)
+                    },
+                    MultiKeyMemberScheme::Secp256k1Ecdsa => {
+                        AnyPublicKey::secp256k1_ecdsa(bytes.try_into().context(
+                            "Failed to parse given public_key bytes as a Secp256k1EcdsaPublicKey",
+                        )?)
+                    },
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let signatures = signatures
+            .into_iter()
+            .map(|signature| {
+                let bytes = signature.signature.inner();
+                Ok(match signature.scheme {
+                    MultiKeyMemberScheme::Ed25519 => AnySignature::ed25519(
+                        bytes
+                            .try_into()
+                            .context("Failed to parse given signature as a Ed25519Signature")?,
+                    ),
+                    MultiKeyMemberScheme::Secp256k1Ecdsa => {
+                        AnySignature::secp256k1_ecdsa(bytes.try_into().context(
+                            "Failed to parse given signature as a Secp256k1EcdsaSignature",
+                        )?)
+                    },
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok((
+            MultiKey::new(public_keys, signatures_required)?,
+            authenticator::MultiKeySignature::new_with_signatures_and_bitmap(
+                signatures,
+                bitmap
+                    .inner()
+                    .try_into()
+                    .context("Failed to parse given bitmap as a MultiKey bitmap")?,
+            ),
+        ))
+    }
+}
+
+impl TryFrom<MultiKeySignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MultiKeySignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.try_into()?;
+        Ok(TransactionAuthenticator::multi_key(public_key, signature))
+    }
+}
+
+impl TryFrom<MultiKeySignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MultiKeySignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.try_into()?;
+        Ok(AccountAuthenticator::multi_key(public_key, signature))
+    }
+}
+
 /// Account signature scheme
 ///
-/// The account signature scheme allows you to have two types of accounts:
+/// The account signature scheme allows you to have four types of accounts:
 ///
 ///   1. A single Ed25519 key account, one private key
 ///   2. A k-of-n multi-Ed25519 key account, multiple private keys, such that k-of-n must sign a transaction.
+///   3. A single secp256k1 ECDSA key account, one private key
+///   4. A k-of-n multi key account, multiple private keys of mixed schemes, such that k-of-n must sign a transaction.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
 pub enum AccountSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
+    Secp256k1EcdsaSignature(Secp256k1EcdsaSignature),
+    MultiKeySignature(MultiKeySignature),
 }
 
 impl VerifyInput for AccountSignature {
@@ -1083,6 +1470,8 @@ impl VerifyInput for AccountSignature {
         match self {
             AccountSignature::Ed25519Signature(inner) => inner.verify(),
             AccountSignature::MultiEd25519Signature(inner) => inner.verify(),
+            AccountSignature::Secp256k1EcdsaSignature(inner) => inner.verify(),
+            AccountSignature::MultiKeySignature(inner) => inner.verify(),
         }
     }
 }
@@ -1094,6 +1483,8 @@ impl TryFrom<AccountSignature> for AccountAuthenticator {
         Ok(match sig {
             AccountSignature::Ed25519Signature(s) => s.try_into()?,
             AccountSignature::MultiEd25519Signature(s) => s.try_into()?,
+            AccountSignature::Secp256k1EcdsaSignature(s) => s.try_into()?,
+            AccountSignature::MultiKeySignature(s) => s.try_into()?,
         })
     }
 }
@@ -1152,6 +1543,65 @@ impl TryFrom<MultiAgentSignature> for TransactionAuthenticator {
     }
 }
 
+/// Fee payer signature for fee payer transactions
+///
+/// This allows you to have transactions across multiple accounts, with gas paid by a separate
+/// fee payer account instead of the sender
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct FeePayerSignature {
+    pub sender: AccountSignature,
+    /// The other involved parties' addresses
+    pub secondary_signer_addresses: Vec<Address>,
+    /// The associated signatures, in the same order as the secondary addresses
+    pub secondary_signers: Vec<AccountSignature>,
+    /// The address of the account paying for gas
+    pub fee_payer_address: Address,
+    /// The signature of the fee payer
+    pub fee_payer_signer: AccountSignature,
+}
+
+impl VerifyInput for FeePayerSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.sender.verify()?;
+
+        if self.secondary_signers.len() != self.secondary_signer_addresses.len() {
+            bail!("FeePayer secondary signatures don't match addresses length")
+        }
+
+        for signer in self.secondary_signers.iter() {
+            signer.verify()?;
+        }
+        self.fee_payer_signer.verify()
+    }
+}
+
+impl TryFrom<FeePayerSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: FeePayerSignature) -> Result<Self, Self::Error> {
+        let FeePayerSignature {
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+            fee_payer_address,
+            fee_payer_signer,
+        } = value;
+        Ok(TransactionAuthenticator::fee_payer(
+            sender.try_into()?,
+            secondary_signer_addresses
+                .into_iter()
+                .map(|a| a.into())
+                .collect(),
+            secondary_signers
+                .into_iter()
+                .map(|s| s.try_into())
+                .collect::<anyhow::Result<_>>()?,
+            fee_payer_address.into(),
+            fee_payer_signer.try_into()?,
+        ))
+    }
+}
+
 impl From<(&Ed25519PublicKey, &ed25519::Ed25519Signature)> for Ed25519Signature {
     fn from((pk, sig): (&Ed25519PublicKey, &ed25519::Ed25519Signature)) -> Self {
         Self {
@@ -1161,6 +1611,25 @@ impl From<(&Ed25519PublicKey, &ed25519::Ed25519Signature)> for Ed25519Signature
     }
 }
 
+impl
+    From<(
+        &Secp256k1EcdsaPublicKey,
+        &secp256k1_ecdsa::Secp256k1EcdsaSignature,
+    )> for Secp256k1EcdsaSignature
+{
+    fn from(
+        (pk, sig): (
+            &Secp256k1EcdsaPublicKey,
+            &secp256k1_ecdsa::Secp256k1EcdsaSignature,
+        ),
+    ) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: sig.to_bytes().to_vec().into(),
+        }
+    }
+}
+
 impl
     From<(
         &MultiEd25519PublicKey,
@@ -1190,6 +1659,37 @@ impl
     }
 }
 
+impl From<(&MultiKey, &authenticator::MultiKeySignature)> for MultiKeySignature {
+    fn from((pk, sig): (&MultiKey, &authenticator::MultiKeySignature)) -> Self {
+        Self {
+            public_keys: pk
+                .public_keys()
+                .iter()
+                .map(|k| MultiKeyPublicKey {
+                    scheme: match k {
+                        AnyPublicKey::Ed25519 { .. } => MultiKeyMemberScheme::Ed25519,
+                        AnyPublicKey::Secp256k1Ecdsa { .. } => MultiKeyMemberScheme::Secp256k1Ecdsa,
+                    },
+                    public_key: k.to_bytes().into(),
+                })
+                .collect(),
+            signatures: sig
+                .signatures()
+                .iter()
+                .map(|s| MultiKeyMemberSignature {
+                    scheme: match s {
+                        AnySignature::Ed25519 { .. } => MultiKeyMemberScheme::Ed25519,
+                        AnySignature::Secp256k1Ecdsa { .. } => MultiKeyMemberScheme::Secp256k1Ecdsa,
+                    },
+                    signature: s.to_bytes().into(),
+                })
+                .collect(),
+            signatures_required: pk.signatures_required(),
+            bitmap: sig.bitmap().to_vec().into(),
+        }
+    }
+}
+
 impl From<&AccountAuthenticator> for AccountSignature {
     fn from(auth: &AccountAuthenticator) -> Self {
         use AccountAuthenticator::*;
@@ -1202,6 +1702,14 @@ impl From<&AccountAuthenticator> for AccountSignature {
                 public_key,
                 signature,
             } => Self::MultiEd25519Signature((public_key, signature).into()),
+            Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256k1EcdsaSignature((public_key, signature).into()),
+            AccountAuthenticator::MultiKey {
+                public_key,
+                signature,
+            } => Self::MultiKeySignature((public_key, signature).into()),
         }
     }
 }
@@ -1228,6 +1736,34 @@ impl
     }
 }
 
+impl
+    From<(
+        &AccountAuthenticator,
+        &Vec<AccountAddress>,
+        &Vec<AccountAuthenticator>,
+        &AccountAddress,
+        &AccountAuthenticator,
+    )> for FeePayerSignature
+{
+    fn from(
+        (sender, addresses, signers, fee_payer_address, fee_payer_signer): (
+            &AccountAuthenticator,
+            &Vec<AccountAddress>,
+            &Vec<AccountAuthenticator>,
+            &AccountAddress,
+            &AccountAuthenticator,
+        ),
+    ) -> Self {
+        Self {
+            sender: sender.into(),
+            secondary_signer_addresses: addresses.iter().map(|address| (*address).into()).collect(),
+            secondary_signers: signers.iter().map(|s| s.into()).collect(),
+            fee_payer_address: (*fee_payer_address).into(),
+            fee_payer_signer: fee_payer_signer.into(),
+        }
+    }
+}
+
 impl From<TransactionAuthenticator> for TransactionSignature {
     fn from(auth: TransactionAuthenticator) -> Self {
         use TransactionAuthenticator::*;
@@ -1247,6 +1783,30 @@ impl From<TransactionAuthenticator> for TransactionSignature {
             } => Self::MultiAgentSignature(
                 (sender, secondary_signer_addresses, secondary_signers).into(),
             ),
+            FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => Self::FeePayerSignature(
+                (
+                    sender,
+                    secondary_signer_addresses,
+                    secondary_signers,
+                    fee_payer_address,
+                    fee_payer_signer,
+                )
+                    .into(),
+            ),
+            Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256k1EcdsaSignature((public_key, signature).into()),
+            TransactionAuthenticator::MultiKey {
+                public_key,
+                signature,
+            } => Self::MultiKeySignature((public_key, signature).into()),
         }
     }
 }
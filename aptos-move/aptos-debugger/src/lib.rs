@@ -1,6 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod output_diff;
+mod state_overrides;
+
+pub use crate::{
+    output_diff::{Diff, TransactionOutputDiff},
+    state_overrides::StateOverrides,
+};
 use anyhow::{format_err, Result};
 use aptos_gas::{
     AbstractValueSizeGasParameters, ChangeSetConfigs, NativeGasParameters, StandardGasMeter,
@@ -9,7 +16,7 @@ use aptos_gas::{
 use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
 use aptos_resource_viewer::{AnnotatedAccountStateBlob, AptosValueAnnotator};
 use aptos_rest_client::Client;
-use aptos_state_view::TStateView;
+use aptos_state_view::{overridden_state_view::OverriddenStateView, TStateView};
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
@@ -103,6 +110,37 @@ impl AptosDebugger {
         Ok((status, output, gas_profiler.finish()))
     }
 
+    /// Replays the transaction committed at `version` on top of the historical state modified by
+    /// `overrides`, and compares the result with the output that was committed on chain.
+    pub async fn replay_transaction_with_overrides(
+        &self,
+        version: Version,
+        overrides: &StateOverrides,
+    ) -> Result<(TransactionOutput, TransactionOutputDiff)> {
+        let (txn, _txn_info, expected_output) = self
+            .debugger
+            .get_committed_transaction_with_output(version)
+            .await?;
+
+        let base_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let state_values = overrides.state_values(&base_view)?;
+        let state_view = OverriddenStateView::new(&base_view, &state_values);
+        let output = AptosVM::execute_block(vec![txn], &state_view, None)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format_err!("No output for transaction at version {}", version))?;
+
+        let diff = TransactionOutputDiff::new(&expected_output, &output);
+        Ok((output, diff))
+    }
+
+    /// Reads an on-chain config from the state right before `version`.
+    pub fn on_chain_config_at_version<T: OnChainConfig>(&self, version: Version) -> Option<T> {
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        T::fetch_config(&StorageAdapter::new(&state_view))
+    }
+
     pub async fn execute_past_transactions(
         &self,
         mut begin: Version,
@@ -1,12 +1,18 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
-use aptos_debugger::AptosDebugger;
+use anyhow::{bail, format_err, Result};
+use aptos_debugger::{AptosDebugger, StateOverrides};
+use aptos_framework::{BuildOptions, BuiltPackage};
 use aptos_rest_client::Client;
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::{Features, GasScheduleV2},
+};
 use aptos_vm::AptosVM;
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use move_core_types::language_storage::StructTag;
+use std::{path::PathBuf, str::FromStr};
 use url::Url;
 
 #[derive(Subcommand)]
@@ -21,14 +27,88 @@ pub struct Argument {
     #[clap(subcommand)]
     target: Target,
 
-    #[clap(long)]
-    begin_version: u64,
+    #[clap(long, required_unless_present = "replay-version")]
+    begin_version: Option<u64>,
 
-    #[clap(long)]
-    limit: u64,
+    #[clap(long, required_unless_present = "replay-version")]
+    limit: Option<u64>,
 
     #[clap(long, default_value = "1")]
     concurrency_level: usize,
+
+    /// Replay the single transaction at this version with the overrides below, and print the
+    /// differences with the output that was committed on chain.
+    #[clap(long, conflicts_with_all = &["begin-version", "limit"])]
+    replay_version: Option<u64>,
+
+    #[clap(flatten)]
+    overrides: OverrideArgs,
+}
+
+#[derive(Parser)]
+pub struct OverrideArgs {
+    /// Path to a local Move package whose modules replace the on-chain ones.
+    #[clap(long, parse(from_os_str))]
+    override_package: Vec<PathBuf>,
+
+    /// Resource to replace, given as `<ADDRESS> <STRUCT_TAG> <BCS_FILE>`.
+    #[clap(long, number_of_values = 3, multiple_occurrences = true)]
+    override_resource: Vec<String>,
+
+    /// Path to a JSON encoded `GasScheduleV2` replacing the on-chain gas schedule.
+    #[clap(long, parse(from_os_str))]
+    gas_schedule: Option<PathBuf>,
+
+    /// Feature flags to enable on top of the on-chain ones.
+    #[clap(long, multiple_values = true)]
+    enable_features: Vec<u64>,
+
+    /// Feature flags to disable on top of the on-chain ones.
+    #[clap(long, multiple_values = true)]
+    disable_features: Vec<u64>,
+}
+
+impl OverrideArgs {
+    fn state_overrides(&self, debugger: &AptosDebugger, version: u64) -> Result<StateOverrides> {
+        let mut overrides = StateOverrides::new();
+
+        for package_path in &self.override_package {
+            let package = BuiltPackage::build(package_path.clone(), BuildOptions::default())?;
+            overrides.override_package(&package)?;
+        }
+
+        for resource in self.override_resource.chunks(3) {
+            let (address, struct_tag, path) = match resource {
+                [address, struct_tag, path] => (address, struct_tag, path),
+                _ => bail!("--override-resource expects <ADDRESS> <STRUCT_TAG> <BCS_FILE>"),
+            };
+            overrides.override_resource(
+                AccountAddress::from_hex_literal(address)?,
+                StructTag::from_str(struct_tag)?,
+                Some(std::fs::read(path)?),
+            )?;
+        }
+
+        if let Some(path) = &self.gas_schedule {
+            let gas_schedule: GasScheduleV2 = serde_json::from_slice(&std::fs::read(path)?)?;
+            overrides.override_on_chain_config(&gas_schedule)?;
+        }
+
+        if !self.enable_features.is_empty() || !self.disable_features.is_empty() {
+            // Overriding a default feature set would change far more than the requested flags.
+            let features = debugger
+                .on_chain_config_at_version::<Features>(version)
+                .ok_or_else(|| {
+                    format_err!(
+                        "Failed to read the on-chain features at version {}",
+                        version
+                    )
+                })?;
+            overrides.override_features(features, &self.enable_features, &self.disable_features)?;
+        }
+
+        Ok(overrides)
+    }
 }
 
 #[tokio::main]
@@ -44,10 +124,24 @@ async fn main() -> Result<()> {
         Target::DB { path } => AptosDebugger::db(path)?,
     };
 
+    if let Some(version) = args.replay_version {
+        let overrides = args.overrides.state_overrides(&debugger, version)?;
+        let (output, diff) = debugger
+            .replay_transaction_with_overrides(version, &overrides)
+            .await?;
+        println!("{:#?}", output);
+        println!("{}", diff);
+        return Ok(());
+    }
+
+    let (begin_version, limit) = match (args.begin_version, args.limit) {
+        (Some(begin_version), Some(limit)) => (begin_version, limit),
+        _ => bail!("--begin-version and --limit are required unless --replay-version is given"),
+    };
     println!(
         "{:#?}",
         debugger
-            .execute_past_transactions(args.begin_version, args.limit)
+            .execute_past_transactions(begin_version, limit)
             .await?
     );
 
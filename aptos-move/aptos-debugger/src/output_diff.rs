@@ -0,0 +1,265 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    contract_event::ContractEvent,
+    state_store::state_key::StateKey,
+    transaction::{TransactionOutput, TransactionStatus},
+    write_set::WriteOp,
+};
+use std::{collections::BTreeMap, fmt};
+
+/// The difference of a single item between the expected (on-chain) and the actual (replayed)
+/// output.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diff<T> {
+    /// Only present in the replayed output.
+    Added(T),
+    /// Only present in the on-chain output.
+    Removed(T),
+    /// Present in both outputs, with different values.
+    Changed { expected: T, actual: T },
+}
+
+/// A structured diff between the output of a transaction that was committed on chain and the
+/// output of replaying it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TransactionOutputDiff {
+    /// `(expected, actual)`, if the status differs.
+    pub status: Option<(TransactionStatus, TransactionStatus)>,
+    /// `(expected, actual)`, if the gas used differs.
+    pub gas_used: Option<(u64, u64)>,
+    pub write_set: BTreeMap<StateKey, Diff<WriteOp>>,
+    /// Events are compared by their position in the output.
+    pub events: BTreeMap<usize, Diff<ContractEvent>>,
+}
+
+impl TransactionOutputDiff {
+    pub fn new(expected: &TransactionOutput, actual: &TransactionOutput) -> Self {
+        let status = (expected.status() != actual.status())
+            .then(|| (expected.status().clone(), actual.status().clone()));
+        let gas_used = (expected.gas_used() != actual.gas_used())
+            .then(|| (expected.gas_used(), actual.gas_used()));
+
+        let expected_writes: BTreeMap<_, _> = expected.write_set().iter().collect();
+        let actual_writes: BTreeMap<_, _> = actual.write_set().iter().collect();
+        let mut write_set = BTreeMap::new();
+        for (key, expected_op) in &expected_writes {
+            match actual_writes.get(key) {
+                Some(actual_op) if actual_op == expected_op => (),
+                Some(actual_op) => {
+                    write_set.insert((*key).clone(), Diff::Changed {
+                        expected: (*expected_op).clone(),
+                        actual: (*actual_op).clone(),
+                    });
+                },
+                None => {
+                    write_set.insert((*key).clone(), Diff::Removed((*expected_op).clone()));
+                },
+            }
+        }
+        for (key, actual_op) in &actual_writes {
+            if !expected_writes.contains_key(key) {
+                write_set.insert((*key).clone(), Diff::Added((*actual_op).clone()));
+            }
+        }
+
+        let mut events = BTreeMap::new();
+        let num_events = std::cmp::max(expected.events().len(), actual.events().len());
+        for idx in 0..num_events {
+            match (expected.events().get(idx), actual.events().get(idx)) {
+                (Some(expected), Some(actual)) if expected == actual => (),
+                (Some(expected), Some(actual)) => {
+                    events.insert(idx, Diff::Changed {
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    });
+                },
+                (Some(expected), None) => {
+                    events.insert(idx, Diff::Removed(expected.clone()));
+                },
+                (None, Some(actual)) => {
+                    events.insert(idx, Diff::Added(actual.clone()));
+                },
+                (None, None) => unreachable!("index is bounded by the longest event list"),
+            }
+        }
+
+        Self {
+            status,
+            gas_used,
+            write_set,
+            events,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.gas_used.is_none()
+            && self.write_set.is_empty()
+            && self.events.is_empty()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for Diff<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diff::Added(actual) => write!(f, "+ {:?}", actual),
+            Diff::Removed(expected) => write!(f, "- {:?}", expected),
+            Diff::Changed { expected, actual } => {
+                write!(f, "- {:?}\n    + {:?}", expected, actual)
+            },
+        }
+    }
+}
+
+impl fmt::Display for TransactionOutputDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences with the on-chain output");
+        }
+        if let Some((expected, actual)) = &self.status {
+            writeln!(f, "status:\n    - {:?}\n    + {:?}", expected, actual)?;
+        }
+        if let Some((expected, actual)) = &self.gas_used {
+            writeln!(f, "gas used:\n    - {}\n    + {}", expected, actual)?;
+        }
+        if !self.write_set.is_empty() {
+            writeln!(f, "write set:")?;
+            for (key, diff) in &self.write_set {
+                writeln!(f, "  {:?}\n    {}", key, diff)?;
+            }
+        }
+        if !self.events.is_empty() {
+            writeln!(f, "events:")?;
+            for (idx, diff) in &self.events {
+                writeln!(f, "  #{}\n    {}", idx, diff)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        account_address::AccountAddress,
+        event::EventKey,
+        transaction::ExecutionStatus,
+        write_set::{WriteSet, WriteSetMut},
+    };
+    use move_core_types::language_storage::TypeTag;
+
+    fn state_key(name: &str) -> StateKey {
+        StateKey::raw(name.as_bytes().to_vec())
+    }
+
+    fn event(data: u8) -> ContractEvent {
+        ContractEvent::new(
+            EventKey::new(0, AccountAddress::ONE),
+            data as u64,
+            TypeTag::U8,
+            vec![data],
+        )
+    }
+
+    fn output(
+        write_ops: Vec<(&str, WriteOp)>,
+        events: Vec<ContractEvent>,
+        gas_used: u64,
+        status: TransactionStatus,
+    ) -> TransactionOutput {
+        let write_set: WriteSet = WriteSetMut::new(
+            write_ops
+                .into_iter()
+                .map(|(name, op)| (state_key(name), op)),
+        )
+        .freeze()
+        .unwrap();
+        TransactionOutput::new(write_set, events, gas_used, status)
+    }
+
+    #[test]
+    fn test_same_output() {
+        let expected = output(
+            vec![("a", WriteOp::Modification(vec![1]))],
+            vec![event(1)],
+            10,
+            TransactionStatus::Keep(ExecutionStatus::Success),
+        );
+        let diff = TransactionOutputDiff::new(&expected, &expected.clone());
+        assert!(diff.is_empty());
+        assert_eq!(diff, TransactionOutputDiff::default());
+        assert_eq!(
+            diff.to_string(),
+            "No differences with the on-chain output\n"
+        );
+    }
+
+    #[test]
+    fn test_different_output() {
+        let expected = output(
+            vec![
+                ("changed", WriteOp::Modification(vec![1])),
+                ("removed", WriteOp::Deletion),
+                ("same", WriteOp::Modification(vec![2])),
+            ],
+            vec![event(1), event(2)],
+            10,
+            TransactionStatus::Keep(ExecutionStatus::OutOfGas),
+        );
+        let actual = output(
+            vec![
+                ("added", WriteOp::Creation(vec![3])),
+                ("changed", WriteOp::Modification(vec![4])),
+                ("same", WriteOp::Modification(vec![2])),
+            ],
+            vec![event(1), event(3), event(4)],
+            20,
+            TransactionStatus::Keep(ExecutionStatus::Success),
+        );
+
+        let diff = TransactionOutputDiff::new(&expected, &actual);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.status,
+            Some((
+                TransactionStatus::Keep(ExecutionStatus::OutOfGas),
+                TransactionStatus::Keep(ExecutionStatus::Success)
+            ))
+        );
+        assert_eq!(diff.gas_used, Some((10, 20)));
+        assert_eq!(
+            diff.write_set,
+            BTreeMap::from([
+                (state_key("added"), Diff::Added(WriteOp::Creation(vec![3]))),
+                (state_key("changed"), Diff::Changed {
+                    expected: WriteOp::Modification(vec![1]),
+                    actual: WriteOp::Modification(vec![4]),
+                }),
+                (state_key("removed"), Diff::Removed(WriteOp::Deletion)),
+            ])
+        );
+        assert_eq!(
+            diff.events,
+            BTreeMap::from([
+                (1, Diff::Changed {
+                    expected: event(2),
+                    actual: event(3),
+                }),
+                (2, Diff::Added(event(4))),
+            ])
+        );
+
+        let actual = output(vec![], vec![], 10, expected.status().clone());
+        let diff = TransactionOutputDiff::new(&expected, &actual);
+        assert_eq!(diff.status, None);
+        assert_eq!(diff.gas_used, None);
+        assert_eq!(diff.write_set.len(), 3);
+        assert_eq!(
+            diff.events,
+            BTreeMap::from([(0, Diff::Removed(event(1))), (1, Diff::Removed(event(2)))])
+        );
+    }
+}
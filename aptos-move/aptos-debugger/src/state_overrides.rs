@@ -0,0 +1,216 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use aptos_framework::BuiltPackage;
+use aptos_state_view::TStateView;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    on_chain_config::{Features, OnChainConfig},
+    state_store::{state_key::StateKey, state_value::StateValue},
+};
+use move_binary_format::CompiledModule;
+use move_core_types::language_storage::StructTag;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A set of modifications applied on top of the historical state before a transaction is
+/// replayed, e.g. to test a hotfix against a real failure.
+///
+/// A value of `None` means the state item is treated as deleted.
+#[derive(Clone, Debug, Default)]
+pub struct StateOverrides {
+    overrides: HashMap<StateKey, Option<Vec<u8>>>,
+}
+
+impl StateOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Turns the overrides into state values on top of `base`. An overridden state item keeps
+    /// the metadata (e.g. the storage deposit) of the item it replaces in `base`.
+    pub fn state_values<S: TStateView<Key = StateKey>>(
+        &self,
+        base: &S,
+    ) -> Result<HashMap<StateKey, Option<StateValue>>> {
+        self.overrides
+            .iter()
+            .map(|(state_key, bytes)| {
+                let value = match bytes {
+                    Some(bytes) => Some(
+                        match base
+                            .get_state_value(state_key)?
+                            .and_then(StateValue::into_metadata)
+                        {
+                            Some(metadata) => {
+                                StateValue::new_with_metadata(bytes.clone(), metadata)
+                            },
+                            None => StateValue::new_legacy(bytes.clone()),
+                        },
+                    ),
+                    None => None,
+                };
+                Ok((state_key.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Overrides the raw value stored under `state_key`.
+    pub fn override_state_value(&mut self, state_key: StateKey, bytes: Option<Vec<u8>>) {
+        self.overrides.insert(state_key, bytes);
+    }
+
+    /// Replaces the code of a module, or adds it if it doesn't exist yet.
+    pub fn override_module(&mut self, module: &CompiledModule) -> Result<()> {
+        let mut bytes = vec![];
+        module.serialize(&mut bytes).map_err(|err| {
+            format_err!("Failed to serialize module {}: {:?}", module.self_id(), err)
+        })?;
+        self.override_state_value(
+            StateKey::access_path(AccessPath::code_access_path(module.self_id())),
+            Some(bytes),
+        );
+        Ok(())
+    }
+
+    /// Replaces the code of all the modules of a locally built package.
+    ///
+    /// Note that the package metadata stored in the `PackageRegistry` is not updated.
+    pub fn override_package(&mut self, package: &BuiltPackage) -> Result<()> {
+        for module in package.modules() {
+            self.override_module(module)?;
+        }
+        Ok(())
+    }
+
+    /// Overrides the BCS encoded value of a resource stored under `address`.
+    pub fn override_resource(
+        &mut self,
+        address: AccountAddress,
+        struct_tag: StructTag,
+        bytes: Option<Vec<u8>>,
+    ) -> Result<()> {
+        self.override_state_value(
+            StateKey::access_path(AccessPath::resource_access_path(address, struct_tag)?),
+            bytes,
+        );
+        Ok(())
+    }
+
+    /// Overrides an on-chain config, e.g. the gas schedule.
+    pub fn override_on_chain_config<T: OnChainConfig + Serialize>(
+        &mut self,
+        config: &T,
+    ) -> Result<()> {
+        self.override_state_value(
+            StateKey::access_path(T::access_path()?),
+            Some(bcs::to_bytes(config)?),
+        );
+        Ok(())
+    }
+
+    /// Overrides the on-chain feature flags by enabling and disabling the given flags on top of
+    /// `features`.
+    pub fn override_features(
+        &mut self,
+        mut features: Features,
+        enabled: &[u64],
+        disabled: &[u64],
+    ) -> Result<()> {
+        for flag in enabled {
+            set_feature(&mut features, *flag, true);
+        }
+        for flag in disabled {
+            set_feature(&mut features, *flag, false);
+        }
+        self.override_on_chain_config(&features)
+    }
+}
+
+fn set_feature(features: &mut Features, flag: u64, enabled: bool) {
+    let byte_index = (flag / 8) as usize;
+    let bit_mask = 1 << (flag % 8);
+    if features.features.len() <= byte_index {
+        features.features.resize(byte_index + 1, 0);
+    }
+    if enabled {
+        features.features[byte_index] |= bit_mask;
+    } else {
+        features.features[byte_index] &= !bit_mask;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_state_view::in_memory_state_view::InMemoryStateView;
+    use aptos_types::state_store::state_value::StateValueMetadata;
+
+    fn state_key(name: &str) -> StateKey {
+        StateKey::raw(name.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_state_values_keep_metadata() {
+        let metadata = StateValueMetadata::V0 {
+            payer: AccountAddress::ONE,
+            deposit: 100,
+            creation_time_usecs: 42,
+        };
+        let base = InMemoryStateView::new(HashMap::from([
+            (
+                state_key("with_metadata"),
+                StateValue::new_with_metadata(vec![1], metadata.clone()),
+            ),
+            (state_key("legacy"), StateValue::new_legacy(vec![2])),
+            (state_key("deleted"), StateValue::new_legacy(vec![3])),
+        ]));
+
+        let mut overrides = StateOverrides::new();
+        assert!(overrides.is_empty());
+        overrides.override_state_value(state_key("with_metadata"), Some(vec![10]));
+        overrides.override_state_value(state_key("legacy"), Some(vec![20]));
+        overrides.override_state_value(state_key("new"), Some(vec![30]));
+        overrides.override_state_value(state_key("deleted"), None);
+        assert!(!overrides.is_empty());
+
+        let values = overrides.state_values(&base).unwrap();
+        assert_eq!(values.len(), 4);
+        assert_eq!(
+            values[&state_key("with_metadata")],
+            Some(StateValue::new_with_metadata(vec![10], metadata))
+        );
+        assert_eq!(
+            values[&state_key("legacy")],
+            Some(StateValue::new_legacy(vec![20]))
+        );
+        assert_eq!(
+            values[&state_key("new")],
+            Some(StateValue::new_legacy(vec![30]))
+        );
+        assert_eq!(values[&state_key("deleted")], None);
+    }
+
+    #[test]
+    fn test_override_features() {
+        let features = Features {
+            features: vec![0b0000_0011],
+        };
+
+        let mut overrides = StateOverrides::new();
+        overrides
+            .override_features(features, &[2, 9], &[0])
+            .unwrap();
+        let bytes = overrides.overrides[&StateKey::access_path(Features::access_path().unwrap())]
+            .clone()
+            .unwrap();
+        let features: Features = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(features.features, vec![0b0000_0110, 0b0000_0010]);
+    }
+}
@@ -25,7 +25,7 @@ use std::{
 };
 
 /// Wrapper to help render the underlying data in formats desirable by the flamegraph.
-struct Render<'a, T>(&'a T);
+pub(crate) struct Render<'a, T>(pub(crate) &'a T);
 
 impl<'a> Display for Render<'a, AccountAddress> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -138,7 +138,7 @@ impl<'a> Display for Render<'a, WriteOpType> {
     }
 }
 
-fn strip_trailing_zeros_and_decimal_point(mut s: &str) -> &str {
+pub(crate) fn strip_trailing_zeros_and_decimal_point(mut s: &str) -> &str {
     while let Some(stripped) = s.strip_suffix('0') {
         s = stripped
     }
@@ -4,6 +4,7 @@
 mod flamegraph;
 mod log;
 mod profiler;
+mod report;
 
 pub use log::{FrameName, TransactionGasLog};
 pub use profiler::GasProfiler;
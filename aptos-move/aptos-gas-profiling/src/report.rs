@@ -0,0 +1,376 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    flamegraph::{strip_trailing_zeros_and_decimal_point, Render},
+    log::{CallFrame, ExecutionGasEvent, TransactionGasLog},
+};
+use move_core_types::gas_algebra::InternalGas;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+/// Aggregated costs of all the calls to a single function.
+#[derive(Default)]
+struct FunctionTotals {
+    calls: u64,
+    /// Cost incurred in the function itself, excluding the functions it calls.
+    exclusive: InternalGas,
+    /// Cost incurred in the function and all the functions it calls.
+    inclusive: InternalGas,
+}
+
+impl TransactionGasLog {
+    /// Computes the per-function execution costs by walking the call graph, and returns them
+    /// along with the total cost of the call graph.
+    ///
+    /// Native functions and resource loads are accounted to the function in which they happen,
+    /// and natives are also listed on their own. The inclusive cost of a recursive function only
+    /// counts its outermost call, so that the costs of the inner calls are not counted twice.
+    fn function_totals(&self) -> (InternalGas, BTreeMap<String, FunctionTotals>) {
+        fn visit(
+            frame: &CallFrame,
+            stack: &mut Vec<String>,
+            totals: &mut BTreeMap<String, FunctionTotals>,
+        ) -> InternalGas {
+            let name = format!("{}", frame.name);
+            let is_recursive_call = stack.contains(&name);
+            stack.push(name);
+
+            let mut exclusive = InternalGas::new(0);
+            let mut inclusive = InternalGas::new(0);
+
+            for event in &frame.events {
+                use ExecutionGasEvent::*;
+
+                match event {
+                    Loc(_) => (),
+                    Bytecode { cost, .. } | LoadResource { cost, .. } => exclusive += *cost,
+                    Call(inner_frame) => inclusive += visit(inner_frame, stack, totals),
+                    CallNative {
+                        module_id,
+                        fn_name,
+                        ty_args,
+                        cost,
+                    } => {
+                        let entry = totals
+                            .entry(format!(
+                                "{}",
+                                Render(&(module_id, fn_name.as_ident_str(), ty_args.as_slice()))
+                            ))
+                            .or_default();
+                        entry.calls += 1;
+                        entry.exclusive += *cost;
+                        entry.inclusive += *cost;
+                        exclusive += *cost;
+                    },
+                }
+            }
+            inclusive += exclusive;
+
+            let name = stack.pop().expect("frame was pushed above");
+            let entry = totals.entry(name).or_default();
+            entry.calls += 1;
+            entry.exclusive += exclusive;
+            if !is_recursive_call {
+                entry.inclusive += inclusive;
+            }
+            inclusive
+        }
+
+        let mut totals = BTreeMap::new();
+        let total = visit(&self.call_graph, &mut vec![], &mut totals);
+        (total, totals)
+    }
+
+    fn render_gas(&self, gas: InternalGas) -> String {
+        let scaled = u64::from(gas) as f64 / u64::from(self.gas_scaling_factor) as f64;
+        strip_trailing_zeros_and_decimal_point(&format!("{:.8}", scaled)).to_string()
+    }
+
+    /// Generates a self-contained HTML report, including the execution & IO and the storage fee
+    /// flamegraphs, a breakdown of the storage fees and the execution costs of each function.
+    pub fn to_html_report(&self, title: String) -> anyhow::Result<String> {
+        let mut html = String::new();
+        self.write_html_report(&mut html, &title)?;
+        Ok(html)
+    }
+
+    fn write_html_report(&self, html: &mut String, title: &str) -> anyhow::Result<()> {
+        let exec_io_graph = self.to_flamegraph(format!("{} -- Execution & IO", title))?;
+        let storage_graph = self
+            .storage
+            .to_flamegraph(format!("{} -- Storage Fee", title))?;
+
+        let (execution_total, function_totals) = self.function_totals();
+        let io_total = self
+            .write_set_transient
+            .iter()
+            .fold(InternalGas::new(0), |acc, item| acc + item.cost);
+        let storage = &self.storage;
+        let storage_total = storage
+            .write_set_storage
+            .iter()
+            .map(|item| u64::from(item.cost))
+            .chain(storage.events.iter().map(|event| u64::from(event.cost)))
+            .sum::<u64>()
+            .saturating_add(u64::from(storage.txn_storage))
+            .saturating_sub(u64::from(storage.event_discount));
+
+        writeln!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>",
+            Escape(title)
+        )?;
+        writeln!(
+            html,
+            "<style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} \
+             td, th {{ border: 1px solid #ccc; padding: 2px 8px; }} \
+             td.num {{ text-align: right; }}</style>\n</head>\n<body>"
+        )?;
+        writeln!(html, "<h1>{}</h1>", Escape(title))?;
+
+        // Summary
+        writeln!(html, "<h2>Summary</h2>\n<table>")?;
+        writeln!(
+            html,
+            "<tr><td>Intrinsic</td><td class=\"num\">{} gas units</td></tr>",
+            self.render_gas(self.intrinsic_cost)
+        )?;
+        writeln!(
+            html,
+            "<tr><td>Execution</td><td class=\"num\">{} gas units</td></tr>",
+            self.render_gas(execution_total)
+        )?;
+        writeln!(
+            html,
+            "<tr><td>IO</td><td class=\"num\">{} gas units</td></tr>",
+            self.render_gas(io_total)
+        )?;
+        writeln!(
+            html,
+            "<tr><td>Storage</td><td class=\"num\">{} Octa</td></tr>\n</table>",
+            storage_total
+        )?;
+
+        // Execution & IO
+        writeln!(html, "<h2>Execution &amp; IO</h2>")?;
+        match &exec_io_graph {
+            Some(graph) => writeln!(html, "{}", String::from_utf8_lossy(graph))?,
+            None => writeln!(html, "<p>No execution or IO costs.</p>")?,
+        }
+
+        writeln!(
+            html,
+            "<h3>Functions</h3>\n<table>\n<tr><th>Function</th><th>Calls</th>\
+             <th>Exclusive (gas units)</th><th>Inclusive (gas units)</th></tr>"
+        )?;
+        let mut totals = function_totals.into_iter().collect::<Vec<_>>();
+        totals.sort_by(|(_, a), (_, b)| b.inclusive.cmp(&a.inclusive));
+        for (name, totals) in totals {
+            writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+                 <td class=\"num\">{}</td></tr>",
+                Escape(&name),
+                totals.calls,
+                self.render_gas(totals.exclusive),
+                self.render_gas(totals.inclusive)
+            )?;
+        }
+        writeln!(html, "</table>")?;
+
+        // Storage fees
+        writeln!(html, "<h2>Storage Fees</h2>")?;
+        match &storage_graph {
+            Some(graph) => writeln!(html, "{}", String::from_utf8_lossy(graph))?,
+            None => writeln!(html, "<p>No storage fees.</p>")?,
+        }
+
+        writeln!(
+            html,
+            "<h3>Write Set</h3>\n<table>\n<tr><th>Operation</th><th>State Key</th>\
+             <th>Fee (Octa)</th></tr>"
+        )?;
+        for item in &storage.write_set_storage {
+            writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+                Render(&item.op_type),
+                Escape(&format!("{}", Render(&item.key))),
+                u64::from(item.cost)
+            )?;
+        }
+        writeln!(html, "</table>")?;
+
+        writeln!(
+            html,
+            "<h3>Events</h3>\n<table>\n<tr><th>Type</th><th>Fee (Octa)</th></tr>"
+        )?;
+        for event in &storage.events {
+            writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num\">{}</td></tr>",
+                Escape(&event.ty.to_string()),
+                u64::from(event.cost)
+            )?;
+        }
+        writeln!(
+            html,
+            "<tr><td>Discount</td><td class=\"num\">-{}</td></tr>\n</table>",
+            u64::from(storage.event_discount)
+        )?;
+
+        writeln!(
+            html,
+            "<h3>Transaction</h3>\n<p>{} Octa</p>\n</body>\n</html>",
+            u64::from(storage.txn_storage)
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Escapes the characters that have a special meaning in HTML.
+struct Escape<'a>(&'a str);
+
+impl<'a> fmt::Display for Escape<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '&' => f.write_str("&amp;")?,
+                '"' => f.write_str("&quot;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::StorageFees;
+    use aptos_gas::{Fee, GasScalingFactor};
+    use move_binary_format::file_format_common::Opcodes;
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    };
+
+    fn bytecode(cost: u64) -> ExecutionGasEvent {
+        ExecutionGasEvent::Bytecode {
+            op: Opcodes::NOP,
+            cost: InternalGas::new(cost),
+        }
+    }
+
+    fn native(cost: u64) -> ExecutionGasEvent {
+        ExecutionGasEvent::CallNative {
+            module_id: ModuleId::new(AccountAddress::ONE, Identifier::new("m").unwrap()),
+            fn_name: Identifier::new("native").unwrap(),
+            ty_args: vec![],
+            cost: InternalGas::new(cost),
+        }
+    }
+
+    fn frame(name: &str, events: Vec<ExecutionGasEvent>) -> CallFrame {
+        let mut frame = CallFrame::new_function(
+            ModuleId::new(AccountAddress::ONE, Identifier::new("m").unwrap()),
+            Identifier::new(name).unwrap(),
+            vec![],
+        );
+        frame.events = events;
+        frame
+    }
+
+    fn call(name: &str, events: Vec<ExecutionGasEvent>) -> ExecutionGasEvent {
+        ExecutionGasEvent::Call(frame(name, events))
+    }
+
+    fn function_totals(call_graph: CallFrame) -> (u64, BTreeMap<String, (u64, u64, u64)>) {
+        let log = TransactionGasLog {
+            gas_scaling_factor: GasScalingFactor::new(1),
+            intrinsic_cost: InternalGas::new(0),
+            call_graph,
+            write_set_transient: vec![],
+            storage: StorageFees {
+                write_set_storage: vec![],
+                events: vec![],
+                event_discount: Fee::new(0),
+                txn_storage: Fee::new(0),
+            },
+        };
+        let (total, totals) = log.function_totals();
+        let totals = totals
+            .into_iter()
+            .map(|(name, totals)| {
+                let name = name.rsplit("::").next().unwrap().to_string();
+                let totals = (
+                    totals.calls,
+                    u64::from(totals.exclusive),
+                    u64::from(totals.inclusive),
+                );
+                (name, totals)
+            })
+            .collect();
+        (u64::from(total), totals)
+    }
+
+    #[test]
+    fn test_flat_call_graph() {
+        let (total, totals) = function_totals(frame("main", vec![bytecode(1), native(2)]));
+        assert_eq!(total, 3);
+        assert_eq!(
+            totals,
+            BTreeMap::from([
+                ("main".to_string(), (1, 3, 3)),
+                ("native".to_string(), (1, 2, 2)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_nested_call_graph() {
+        let (total, totals) = function_totals(frame("main", vec![
+            bytecode(1),
+            call("f", vec![bytecode(2), call("g", vec![bytecode(4)])]),
+            call("g", vec![bytecode(8)]),
+        ]));
+        assert_eq!(total, 15);
+        assert_eq!(
+            totals,
+            BTreeMap::from([
+                ("main".to_string(), (1, 1, 15)),
+                ("f".to_string(), (1, 2, 6)),
+                ("g".to_string(), (2, 12, 12)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_recursive_call_graph() {
+        // main -> f -> g -> f -> f
+        let (total, totals) = function_totals(frame("main", vec![
+            bytecode(1),
+            call("f", vec![
+                bytecode(2),
+                call("g", vec![
+                    bytecode(4),
+                    call("f", vec![bytecode(8), call("f", vec![bytecode(16)])]),
+                ]),
+            ]),
+        ]));
+        assert_eq!(total, 31);
+        assert_eq!(
+            totals,
+            BTreeMap::from([
+                ("main".to_string(), (1, 1, 31)),
+                ("f".to_string(), (3, 26, 30)),
+                ("g".to_string(), (1, 4, 28)),
+            ])
+        );
+    }
+}
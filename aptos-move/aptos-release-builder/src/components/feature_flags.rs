@@ -39,6 +39,9 @@ pub enum FeatureFlag {
     SignatureCheckerV2,
     StorageSlotMetadata,
     ChargeInvariantViolation,
+    FeePayerEnabled,
+    Secp256k1EcdsaAuthenticator,
+    MultiKeyAuthenticator,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -156,6 +159,11 @@ impl From<FeatureFlag> for AptosFeatureFlag {
             FeatureFlag::SignatureCheckerV2 => AptosFeatureFlag::SIGNATURE_CHECKER_V2,
             FeatureFlag::StorageSlotMetadata => AptosFeatureFlag::STORAGE_SLOT_METADATA,
             FeatureFlag::ChargeInvariantViolation => AptosFeatureFlag::CHARGE_INVARIANT_VIOLATION,
+            FeatureFlag::FeePayerEnabled => AptosFeatureFlag::FEE_PAYER_ENABLED,
+            FeatureFlag::Secp256k1EcdsaAuthenticator => {
+                AptosFeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR
+            },
+            FeatureFlag::MultiKeyAuthenticator => AptosFeatureFlag::MULTI_KEY_AUTHENTICATOR,
         }
     }
 }
@@ -196,6 +204,11 @@ impl From<AptosFeatureFlag> for FeatureFlag {
             AptosFeatureFlag::SIGNATURE_CHECKER_V2 => FeatureFlag::SignatureCheckerV2,
             AptosFeatureFlag::STORAGE_SLOT_METADATA => FeatureFlag::StorageSlotMetadata,
             AptosFeatureFlag::CHARGE_INVARIANT_VIOLATION => FeatureFlag::ChargeInvariantViolation,
+            AptosFeatureFlag::FEE_PAYER_ENABLED => FeatureFlag::FeePayerEnabled,
+            AptosFeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR => {
+                FeatureFlag::Secp256k1EcdsaAuthenticator
+            },
+            AptosFeatureFlag::MULTI_KEY_AUTHENTICATOR => FeatureFlag::MultiKeyAuthenticator,
         }
     }
 }
@@ -0,0 +1,781 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed Rust bindings for a compiled Move package.
+//!
+//! For each module of the package, the bindings contain a builder of the transaction payload of
+//! each entry function, an async caller of each view function, and a serde struct for each struct
+//! of the module, e.g. its resources and events. The bindings are written against the `aptos-sdk`
+//! crate.
+
+use heck::SnakeCase;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{
+        FunctionDefinition, FunctionDefinitionIndex, SignatureToken, StructFieldInformation,
+        StructHandleIndex,
+    },
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, CORE_CODE_ADDRESS},
+};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use std::{
+    collections::BTreeSet,
+    io::{Result, Write},
+};
+
+/// A module of a compiled package, along with what its bytecode doesn't tell about it.
+pub struct PackageModule {
+    pub module: CompiledModule,
+    /// The view functions of the module, as declared in its metadata.
+    pub view_functions: BTreeSet<Identifier>,
+    /// The source map of the module, which names the parameters of its functions, if available.
+    pub source_map: Option<SourceMap>,
+}
+
+/// Output typed Rust bindings for the modules of a package, as one Rust module per Move module.
+pub fn output(out: &mut dyn Write, package_name: &str, modules: &[PackageModule]) -> Result<()> {
+    let mut emitter = BindingsEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+        types: RustTypes {
+            bound_structs: bound_structs(modules),
+        },
+    };
+    emitter.output_preamble(package_name)?;
+    for module in modules {
+        emitter.output_module(module)?;
+    }
+    Ok(())
+}
+
+/// Names used by the generated entry function builders, which the parameters must not shadow.
+const ENTRY_FUNCTION_NAMES: &[&str] = &["module_id"];
+
+/// Names used by the generated view function callers, which the parameters must not shadow.
+const VIEW_CALLER_NAMES: &[&str] = &[
+    "client",
+    "ledger_version",
+    "module_id",
+    "request",
+    "response",
+];
+
+// The generated code refers to the items of other crates by their absolute paths, as any name
+// brought into scope could be shadowed by a struct of the package, e.g. one named `String`.
+const ACCOUNT_ADDRESS: &str = "::aptos_sdk::move_types::account_address::AccountAddress";
+const API_TYPES: &str = "::aptos_sdk::rest_client::aptos_api_types";
+const IDENT_STR: &str = "::aptos_sdk::move_types::identifier::IdentStr";
+const IDENT_STR_MACRO: &str = "::aptos_sdk::move_types::ident_str!";
+const MOVE_STRUCT_TYPE: &str = "::aptos_sdk::move_types::move_resource::MoveStructType";
+const REST_CLIENT: &str = "::aptos_sdk::rest_client::Client";
+const REST_ERROR: &str = "::aptos_sdk::rest_client::error::RestError";
+const REST_RESPONSE: &str = "::aptos_sdk::rest_client::Response";
+const TRANSACTION: &str = "::aptos_sdk::types::transaction";
+const TYPE_TAG: &str = "::aptos_sdk::move_types::language_storage::TypeTag";
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Turns a Move identifier into a valid Rust identifier.
+fn escape(name: &str) -> String {
+    match name {
+        "self" | "Self" | "super" | "crate" => format!("{}_", name),
+        _ if RUST_KEYWORDS.contains(&name) => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
+
+/// Returns the structs of the package which have bindings, i.e. those whose fields all have Rust
+/// types, possibly through other structs of the package.
+fn bound_structs(modules: &[PackageModule]) -> BTreeSet<(ModuleId, Identifier)> {
+    let mut bound: BTreeSet<_> = modules
+        .iter()
+        .flat_map(|package_module| {
+            let module = &package_module.module;
+            module
+                .struct_defs()
+                .iter()
+                .filter(|def| matches!(def.field_information, StructFieldInformation::Declared(_)))
+                .map(|def| {
+                    let handle = module.struct_handle_at(def.struct_handle);
+                    (
+                        module.self_id(),
+                        module.identifier_at(handle.name).to_owned(),
+                    )
+                })
+        })
+        .collect();
+
+    // Drop the structs with unbound fields until all the remaining ones are bound.
+    loop {
+        let types = RustTypes {
+            bound_structs: bound.clone(),
+        };
+        let unbound: Vec<_> = modules
+            .iter()
+            .flat_map(|package_module| {
+                let module = &package_module.module;
+                module.struct_defs().iter().filter_map(|def| {
+                    let fields = match &def.field_information {
+                        StructFieldInformation::Declared(fields) => fields,
+                        StructFieldInformation::Native => return None,
+                    };
+                    let handle = module.struct_handle_at(def.struct_handle);
+                    let key = (
+                        module.self_id(),
+                        module.identifier_at(handle.name).to_owned(),
+                    );
+                    let is_bound = fields
+                        .iter()
+                        .all(|field| types.rust_type(module, &field.signature.0, true).is_some());
+                    (bound.contains(&key) && !is_bound).then_some(key)
+                })
+            })
+            .collect();
+        if unbound.is_empty() {
+            return bound;
+        }
+        for key in unbound {
+            bound.remove(&key);
+        }
+    }
+}
+
+/// Shared state for the bindings generator.
+struct BindingsEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// The Rust types of the Move types.
+    types: RustTypes,
+}
+
+impl<T> BindingsEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self, package_name: &str) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// This file was generated from the ABI of the Move package `{0}` with the tool
+// `aptos-sdk-builder`. Do not modify!
+//
+// The bindings depend on the `aptos-sdk`, `serde` and `serde_json` crates.
+
+#![allow(dead_code)]
+#![allow(clippy::too_many_arguments)]
+
+/// Deserializes the return value of a view function at the given position.
+fn view_return<T: ::serde::de::DeserializeOwned>(
+    values: &[::std::vec::Vec<u8>],
+    index: usize,
+) -> ::std::result::Result<T, {1}> {{
+    Ok(::aptos_sdk::bcs::from_bytes(
+        values.get(index).ok_or(::aptos_sdk::bcs::Error::Eof)?,
+    )?)
+}}"#,
+            package_name, REST_ERROR,
+        )
+    }
+
+    fn output_module(&mut self, package_module: &PackageModule) -> Result<()> {
+        let module = &package_module.module;
+        let module_id = module.self_id();
+        writeln!(
+            self.out,
+            "\npub mod {} {{",
+            escape(module_id.name().as_str())
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "pub const MODULE_ADDRESS: {0} = {0}::new({1:?});",
+            ACCOUNT_ADDRESS,
+            module_id.address().into_bytes()
+        )?;
+        writeln!(
+            self.out,
+            r#"
+pub fn module_id() -> ::aptos_sdk::move_types::language_storage::ModuleId {{
+    ::aptos_sdk::move_types::language_storage::ModuleId::new(
+        MODULE_ADDRESS,
+        {}("{}").to_owned(),
+    )
+}}"#,
+            IDENT_STR_MACRO,
+            module_id.name()
+        )?;
+
+        for def in module.struct_defs() {
+            let handle = module.struct_handle_at(def.struct_handle);
+            let name = module.identifier_at(handle.name);
+            match &def.field_information {
+                StructFieldInformation::Declared(fields)
+                    if self
+                        .types
+                        .bound_structs
+                        .contains(&(module_id.clone(), name.to_owned())) =>
+                {
+                    let fields = fields
+                        .iter()
+                        .map(|field| {
+                            (
+                                escape(module.identifier_at(field.name).as_str()),
+                                self.types
+                                    .rust_type(module, &field.signature.0, true)
+                                    .expect("bound struct must have bound fields"),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    self.output_struct(module, def.struct_handle, &fields)?;
+                },
+                _ => writeln!(
+                    self.out,
+                    "\n// The struct `{}` has no binding, as some of its fields have no Rust type.",
+                    name
+                )?,
+            }
+        }
+
+        for (index, def) in module.function_defs().iter().enumerate() {
+            let handle = module.function_handle_at(def.function);
+            let name = module.identifier_at(handle.name);
+            let is_view = package_module.view_functions.contains(name);
+            if def.is_entry {
+                self.output_entry_function(package_module, index, def)?;
+            }
+            if is_view {
+                self.output_view_function(package_module, index, def)?;
+            }
+        }
+
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_struct(
+        &mut self,
+        module: &CompiledModule,
+        struct_handle: StructHandleIndex,
+        fields: &[(String, String)],
+    ) -> Result<()> {
+        let handle = module.struct_handle_at(struct_handle);
+        let name = module.identifier_at(handle.name);
+        // Phantom type parameters don't show up in the layout of the struct.
+        let type_params = handle
+            .type_parameters
+            .iter()
+            .enumerate()
+            .filter(|(_, param)| !param.is_phantom)
+            .map(|(index, _)| format!("T{}", index))
+            .collect::<Vec<_>>();
+        let generics = if type_params.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", type_params.join(", "))
+        };
+        // A type parameter may only be used by the type arguments of a type whose binding drops
+        // them, e.g. `Table`, in which case it is marked as used with a field absent from the layout.
+        let used_names = fields
+            .iter()
+            .flat_map(|(_, field_type)| {
+                field_type.split(|c: char| !c.is_alphanumeric() && c != '_')
+            })
+            .collect::<BTreeSet<_>>();
+        let unused_type_params = type_params
+            .iter()
+            .filter(|type_param| !used_names.contains(type_param.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        writeln!(
+            self.out,
+            "\n/// The Rust representation of the Move struct `{}::{}`.",
+            module.self_id(),
+            name
+        )?;
+        writeln!(
+            self.out,
+            "#[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]"
+        )?;
+        writeln!(
+            self.out,
+            "pub struct {}{} {{",
+            escape(name.as_str()),
+            generics
+        )?;
+        self.out.indent();
+        for (field_name, field_type) in fields {
+            writeln!(self.out, "pub {}: {},", field_name, field_type)?;
+        }
+        if !unused_type_params.is_empty() {
+            writeln!(self.out, "#[serde(skip)]")?;
+            writeln!(
+                self.out,
+                "pub _phantom: ::std::marker::PhantomData<({},)>,",
+                unused_type_params.join(", ")
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        // The type of a generic struct depends on its type arguments.
+        if !handle.type_parameters.is_empty() {
+            return Ok(());
+        }
+        writeln!(
+            self.out,
+            r#"
+impl {3} for {0} {{
+    const ADDRESS: {4} = MODULE_ADDRESS;
+    const MODULE_NAME: &'static {5} = {6}("{1}");
+    const STRUCT_NAME: &'static {5} = {6}("{2}");
+}}"#,
+            escape(name.as_str()),
+            module.self_id().name(),
+            name,
+            MOVE_STRUCT_TYPE,
+            ACCOUNT_ADDRESS,
+            IDENT_STR,
+            IDENT_STR_MACRO,
+        )?;
+        if handle.abilities.has_key() {
+            writeln!(
+                self.out,
+                r#"
+impl ::aptos_sdk::move_types::move_resource::MoveResource for {0} {{}}
+
+impl {0} {{
+    /// Fetches the resource stored under the account.
+    pub async fn fetch(
+        client: &{1},
+        address: {2},
+    ) -> ::std::result::Result<{3}<Self>, {4}> {{
+        client
+            .get_account_resource_bcs(
+                address,
+                &<Self as {5}>::struct_tag().to_string(),
+            )
+            .await
+    }}
+}}"#,
+                escape(name.as_str()),
+                REST_CLIENT,
+                ACCOUNT_ADDRESS,
+                REST_RESPONSE,
+                REST_ERROR,
+                MOVE_STRUCT_TYPE,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_entry_function(
+        &mut self,
+        package_module: &PackageModule,
+        index: usize,
+        def: &FunctionDefinition,
+    ) -> Result<()> {
+        let module = &package_module.module;
+        let handle = module.function_handle_at(def.function);
+        let name = module.identifier_at(handle.name);
+        // The signers of an entry function are the senders of the transaction.
+        let params = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| !is_signer(token))
+            .collect::<Vec<_>>();
+        let (type_arg_names, param_names) =
+            function_parameter_names(package_module, index, ENTRY_FUNCTION_NAMES);
+
+        let mut args = vec![];
+        for (param_index, token) in &params {
+            match self.types.rust_type(module, token, false) {
+                Some(rust_type) => args.push((param_names[*param_index].clone(), rust_type)),
+                None => {
+                    return writeln!(
+                        self.out,
+                        "\n// The entry function `{}` has no binding, as some of its parameters \
+                         have no Rust type.",
+                        name
+                    )
+                },
+            }
+        }
+
+        writeln!(
+            self.out,
+            "\n/// Builds the payload of a transaction calling the entry function `{}::{}`.",
+            module.self_id(),
+            name
+        )?;
+        writeln!(self.out, "pub fn {}(", escape(name.as_str()))?;
+        self.out.indent();
+        for type_arg_name in &type_arg_names {
+            writeln!(self.out, "{}: {},", type_arg_name, TYPE_TAG)?;
+        }
+        for (arg_name, rust_type) in &args {
+            writeln!(self.out, "{}: {},", arg_name, rust_type)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, ") -> {}::TransactionPayload {{", TRANSACTION)?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "{0}::TransactionPayload::EntryFunction({0}::EntryFunction::new(",
+            TRANSACTION
+        )?;
+        self.out.indent();
+        writeln!(self.out, "module_id(),")?;
+        writeln!(self.out, "{}(\"{}\").to_owned(),", IDENT_STR_MACRO, name)?;
+        writeln!(self.out, "vec![{}],", type_arg_names.join(", "))?;
+        writeln!(self.out, "vec![")?;
+        self.out.indent();
+        for (arg_name, _) in &args {
+            writeln!(
+                self.out,
+                "::aptos_sdk::bcs::to_bytes(&{}).unwrap(),",
+                arg_name
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "],")?;
+        self.out.unindent();
+        writeln!(self.out, "))")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_view_function(
+        &mut self,
+        package_module: &PackageModule,
+        index: usize,
+        def: &FunctionDefinition,
+    ) -> Result<()> {
+        let module = &package_module.module;
+        let handle = module.function_handle_at(def.function);
+        let name = module.identifier_at(handle.name);
+        let (type_arg_names, param_names) =
+            function_parameter_names(package_module, index, VIEW_CALLER_NAMES);
+
+        let args = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .zip(param_names)
+            .map(|(token, arg_name)| {
+                Some((
+                    self.types.rust_type(module, token, false)?,
+                    json_argument(module, token, &arg_name)?,
+                    arg_name,
+                ))
+            })
+            .collect::<Option<Vec<_>>>();
+        let return_types = module
+            .signature_at(handle.return_)
+            .0
+            .iter()
+            .map(|token| self.types.rust_type(module, token, false))
+            .collect::<Option<Vec<_>>>();
+        let (args, return_types) = match (args, return_types) {
+            (Some(args), Some(return_types)) => (args, return_types),
+            _ => {
+                return writeln!(
+                    self.out,
+                    "\n// The view function `{}` has no binding, as some of its parameters or \
+                     return values have no Rust type.",
+                    name
+                )
+            },
+        };
+        let (return_type, return_value) = match return_types.len() {
+            1 => (
+                return_types[0].clone(),
+                "super::view_return(&values, 0)?".to_string(),
+            ),
+            len => (
+                format!("({})", return_types.join(", ")),
+                format!(
+                    "({})",
+                    (0..len)
+                        .map(|index| format!("super::view_return(&values, {})?", index))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+        };
+
+        writeln!(
+            self.out,
+            "\n/// Calls the view function `{}::{}` at the given ledger version, or the latest one.",
+            module.self_id(),
+            name
+        )?;
+        // An entry function may also be a view function, in which case its payload builder
+        // already takes its name.
+        let fn_name = if def.is_entry {
+            format!("view_{}", name)
+        } else {
+            escape(name.as_str())
+        };
+        writeln!(self.out, "pub async fn {}(", fn_name)?;
+        self.out.indent();
+        writeln!(self.out, "client: &{},", REST_CLIENT)?;
+        for type_arg_name in &type_arg_names {
+            writeln!(self.out, "{}: {},", type_arg_name, TYPE_TAG)?;
+        }
+        for (rust_type, _, arg_name) in &args {
+            writeln!(self.out, "{}: {},", arg_name, rust_type)?;
+        }
+        writeln!(self.out, "ledger_version: ::std::option::Option<u64>,")?;
+        self.out.unindent();
+        writeln!(
+            self.out,
+            ") -> ::std::result::Result<{}<{}>, {}> {{",
+            REST_RESPONSE, return_type, REST_ERROR
+        )?;
+        self.out.indent();
+        writeln!(self.out, "let request = {}::ViewRequest {{", API_TYPES)?;
+        self.out.indent();
+        writeln!(self.out, "function: {}::EntryFunctionId {{", API_TYPES)?;
+        self.out.indent();
+        writeln!(self.out, "module: module_id().into(),")?;
+        writeln!(self.out, "name: {}(\"{}\").into(),", IDENT_STR_MACRO, name)?;
+        self.out.unindent();
+        writeln!(self.out, "}},")?;
+        writeln!(
+            self.out,
+            "type_arguments: vec![{}],",
+            type_arg_names
+                .iter()
+                .map(|type_arg_name| format!("{}::MoveType::from(&{})", API_TYPES, type_arg_name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(self.out, "arguments: vec![")?;
+        self.out.indent();
+        for (_, json_argument, _) in &args {
+            writeln!(self.out, "{},", json_argument)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "],")?;
+        self.out.unindent();
+        writeln!(self.out, "}};")?;
+        writeln!(
+            self.out,
+            "let response = client.view_bcs(&request, ledger_version).await?;"
+        )?;
+        writeln!(self.out, "response.and_then(|values| Ok({}))", return_value)?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+}
+
+/// Maps Move types to Rust types.
+struct RustTypes {
+    /// The structs of the package which have bindings.
+    bound_structs: BTreeSet<(ModuleId, Identifier)>,
+}
+
+impl RustTypes {
+    /// Returns the Rust type of a Move type, or `None` if it has none. The type parameters of a
+    /// struct are only in scope for its fields.
+    fn rust_type(
+        &self,
+        module: &CompiledModule,
+        token: &SignatureToken,
+        in_struct: bool,
+    ) -> Option<String> {
+        use SignatureToken::*;
+        Some(match token {
+            Bool => "bool".to_string(),
+            U8 => "u8".to_string(),
+            U16 => "u16".to_string(),
+            U32 => "u32".to_string(),
+            U64 => "u64".to_string(),
+            U128 => "u128".to_string(),
+            U256 => "::aptos_sdk::move_types::u256::U256".to_string(),
+            Address => ACCOUNT_ADDRESS.to_string(),
+            Vector(inner) => format!(
+                "::std::vec::Vec<{}>",
+                self.rust_type(module, inner, in_struct)?
+            ),
+            Struct(idx) => self.rust_struct_type(module, *idx, &[], in_struct)?,
+            StructInstantiation(idx, type_args) => {
+                self.rust_struct_type(module, *idx, type_args, in_struct)?
+            },
+            TypeParameter(idx) if in_struct => format!("T{}", idx),
+            TypeParameter(_) | Signer | Reference(_) | MutableReference(_) => return None,
+        })
+    }
+
+    fn rust_struct_type(
+        &self,
+        module: &CompiledModule,
+        idx: StructHandleIndex,
+        type_args: &[SignatureToken],
+        in_struct: bool,
+    ) -> Option<String> {
+        let handle = module.struct_handle_at(idx);
+        let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+        let name = module.identifier_at(handle.name);
+
+        // The framework types with the same layout as a Rust type.
+        if module_id.address() == &CORE_CODE_ADDRESS {
+            match (module_id.name().as_str(), name.as_str()) {
+                ("string", "String") => return Some("::std::string::String".to_string()),
+                ("option", "Option") => {
+                    return Some(format!(
+                        "::std::option::Option<{}>",
+                        self.rust_type(module, type_args.get(0)?, in_struct)?
+                    ))
+                },
+                ("object", "Object") => return Some(ACCOUNT_ADDRESS.to_string()),
+                ("event", "EventHandle") => {
+                    return Some("::aptos_sdk::types::event::EventHandle".to_string())
+                },
+                ("table", "Table") => {
+                    return Some("::aptos_sdk::types::state_store::table::TableHandle".to_string())
+                },
+                _ => {},
+            }
+        }
+
+        if !self
+            .bound_structs
+            .contains(&(module_id.clone(), name.to_owned()))
+        {
+            return None;
+        }
+        let type_args = type_args
+            .iter()
+            .zip(&handle.type_parameters)
+            .filter(|(_, param)| !param.is_phantom)
+            .map(|(type_arg, _)| self.rust_type(module, type_arg, in_struct))
+            .collect::<Option<Vec<_>>>()?;
+        let path = format!(
+            "super::{}::{}",
+            escape(module_id.name().as_str()),
+            escape(name.as_str())
+        );
+        Some(
+            if type_args.is_empty() {
+                path
+            } else {
+                format!("{}<{}>", path, type_args.join(", "))
+            },
+        )
+    }
+}
+
+/// Returns the expression encoding the value of a view function argument as JSON, or `None`
+/// if the API can't parse the type.
+fn json_argument(module: &CompiledModule, token: &SignatureToken, value: &str) -> Option<String> {
+    use SignatureToken::*;
+    Some(match token {
+        Bool | U8 | U16 | U32 => format!("::serde_json::json!({})", value),
+        U64 => format!("::serde_json::json!({}::U64::from({}))", API_TYPES, value),
+        U128 => format!("::serde_json::json!({}::U128::from({}))", API_TYPES, value),
+        U256 => format!("::serde_json::json!({}::U256::from({}))", API_TYPES, value),
+        Address => format!(
+            "::serde_json::json!({}::Address::from({}))",
+            API_TYPES, value
+        ),
+        Vector(inner) if **inner == U8 => format!(
+            "::serde_json::json!({}::HexEncodedBytes::from({}))",
+            API_TYPES, value
+        ),
+        Vector(inner) => format!(
+            "::serde_json::Value::Array({}.into_iter().map(|x| {}).collect())",
+            value,
+            json_argument(module, inner, "x")?
+        ),
+        Struct(idx) | StructInstantiation(idx, _) => {
+            let handle = module.struct_handle_at(*idx);
+            let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+            if module_id.address() != &CORE_CODE_ADDRESS {
+                return None;
+            }
+            match (
+                module_id.name().as_str(),
+                module.identifier_at(handle.name).as_str(),
+                token,
+            ) {
+                ("string", "String", _) => format!("::serde_json::json!({})", value),
+                ("object", "Object", _) => format!(
+                    "::serde_json::json!({}::Address::from({}))",
+                    API_TYPES, value
+                ),
+                ("option", "Option", StructInstantiation(_, type_args)) => format!(
+                    "::serde_json::json!({{ \"vec\": {}.into_iter().map(|x| {}).collect::<::std::vec::Vec<_>>() }})",
+                    value,
+                    json_argument(module, type_args.get(0)?, "x")?
+                ),
+                _ => return None,
+            }
+        },
+        TypeParameter(_) | Signer | Reference(_) | MutableReference(_) => return None,
+    })
+}
+
+fn is_signer(token: &SignatureToken) -> bool {
+    match token {
+        SignatureToken::Signer => true,
+        SignatureToken::Reference(inner) => matches!(**inner, SignatureToken::Signer),
+        _ => false,
+    }
+}
+
+/// Returns the Rust names of the type parameters and parameters of a function, taken from the
+/// source map if available, distinct from each other and from the reserved names.
+fn function_parameter_names(
+    package_module: &PackageModule,
+    index: usize,
+    reserved: &[&str],
+) -> (Vec<String>, Vec<String>) {
+    let module = &package_module.module;
+    let handle = module.function_handle_at(module.function_defs()[index].function);
+    let source_map = package_module.source_map.as_ref().and_then(|source_map| {
+        source_map
+            .get_function_source_map(FunctionDefinitionIndex(index as u16))
+            .ok()
+    });
+    let type_param_names = (0..handle.type_parameters.len()).map(|param_index| {
+        source_map
+            .and_then(|source_map| source_map.type_parameters.get(param_index))
+            .map_or_else(
+                || format!("t{}", param_index),
+                |(name, _)| name.to_snake_case(),
+            )
+    });
+    let param_names = (0..module.signature_at(handle.parameters).len()).map(|param_index| {
+        source_map
+            .and_then(|source_map| source_map.parameters.get(param_index))
+            .map_or_else(
+                || format!("arg{}", param_index),
+                |(name, _)| name.to_snake_case(),
+            )
+    });
+
+    let mut used: BTreeSet<String> = reserved.iter().map(|name| name.to_string()).collect();
+    let mut unique = |name: String| {
+        let mut name = escape(&name);
+        while !used.insert(name.clone()) {
+            name.push('_');
+        }
+        name
+    };
+    let param_names: Vec<_> = param_names.map(&mut unique).collect();
+    let type_param_names = type_param_names.map(&mut unique).collect();
+    (type_param_names, param_names)
+}
@@ -0,0 +1,132 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_framework::get_view_functions;
+use aptos_sdk_builder::bindings::{self, PackageModule};
+use std::process::Command;
+
+fn framework_bindings(package_name: &str) -> String {
+    let package = aptos_cached_packages::head_release_bundle()
+        .packages
+        .iter()
+        .find(|package| package.name() == package_name)
+        .unwrap();
+    let modules: Vec<_> = (0..package.code().len())
+        .map(|index| {
+            let module = package.compiled_module_at(index).unwrap();
+            PackageModule {
+                view_functions: get_view_functions(&module),
+                module,
+                source_map: None,
+            }
+        })
+        .collect();
+    let mut out = vec![];
+    bindings::output(&mut out, package.name(), &modules).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_framework_bindings() {
+    let bindings = framework_bindings("AptosFramework");
+
+    // Entry functions skip their signers.
+    assert!(bindings.contains("pub mod aptos_account {"));
+    assert!(bindings.contains(
+        "pub fn transfer(\n        \
+         arg1: ::aptos_sdk::move_types::account_address::AccountAddress,\n        \
+         arg2: u64,\n    \
+         ) -> ::aptos_sdk::types::transaction::TransactionPayload {"
+    ));
+
+    // View functions encode their arguments as JSON.
+    assert!(bindings.contains("pub mod coin {"));
+    assert!(bindings.contains(
+        "pub async fn balance(\n        \
+         client: &::aptos_sdk::rest_client::Client,\n        \
+         t0: ::aptos_sdk::move_types::language_storage::TypeTag,"
+    ));
+    assert!(bindings.contains(
+        "::serde_json::json!(::aptos_sdk::rest_client::aptos_api_types::Address::from(arg0)),"
+    ));
+
+    // Phantom type parameters are omitted, and resources can be fetched.
+    assert!(bindings.contains("pub struct CoinStore {"));
+    assert!(bindings.contains("pub coin: super::coin::Coin,"));
+    assert!(bindings
+        .contains("impl ::aptos_sdk::move_types::move_resource::MoveResource for Account {}"));
+
+    // Type parameters only used by the erased type arguments of a table are marked as used.
+    assert!(bindings.contains("pub struct VotingForum<T0> {"));
+    assert!(bindings.contains("pub _phantom: ::std::marker::PhantomData<(T0,)>,"));
+}
+
+#[test]
+// Ignored because it builds aptos-sdk in a separate project, which takes minutes. Run it with
+// `cargo test -p aptos-sdk-builder --test bindings -- --ignored` after changing the bindings.
+#[ignore]
+fn test_framework_bindings_compile() {
+    let workspace_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let dir = tempfile::tempdir().unwrap();
+
+    // Build against the workspace's lockfile and patches so the bindings are checked with the
+    // same dependency versions as the rest of the tree.
+    let workspace_manifest = std::fs::read_to_string(workspace_dir.join("Cargo.toml")).unwrap();
+    let patches = &workspace_manifest[workspace_manifest.find("[patch.crates-io]").unwrap()..];
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        format!(
+            r#"[package]
+name = "framework-bindings"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aptos-sdk = {{ path = "{}" }}
+serde = {{ version = "1.0.137", features = ["derive"] }}
+serde_json = "1.0.81"
+
+[workspace]
+
+{}"#,
+            workspace_dir.join("sdk").display(),
+            patches
+        ),
+    )
+    .unwrap();
+    std::fs::copy(
+        workspace_dir.join("Cargo.lock"),
+        dir.path().join("Cargo.lock"),
+    )
+    .unwrap();
+
+    // The standard library has modules and structs with the same names as the items the bindings
+    // use, e.g. `bcs` and `string::String`.
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "pub mod aptos_framework;\npub mod move_stdlib;\n",
+    )
+    .unwrap();
+    for (package_name, module_name) in [
+        ("MoveStdlib", "move_stdlib"),
+        ("AptosFramework", "aptos_framework"),
+    ] {
+        std::fs::write(
+            dir.path().join(format!("src/{}.rs", module_name)),
+            framework_bindings(package_name),
+        )
+        .unwrap();
+    }
+
+    // Use a stable `target` dir to avoid recompiling the dependencies every time, apart from the
+    // workspace's one, as the dependencies are built with other features.
+    let status = Command::new("cargo")
+        .current_dir(dir.path())
+        .arg("check")
+        .arg("--target-dir")
+        .arg(workspace_dir.join("target/bindings-check"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
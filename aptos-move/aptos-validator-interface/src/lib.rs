@@ -17,7 +17,7 @@ use aptos_types::{
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
-    transaction::{Transaction, TransactionInfo, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
 };
 use lru::LruCache;
 use move_binary_format::file_format::CompiledModule;
@@ -46,6 +46,13 @@ pub trait AptosValidatorInterface: Sync {
         limit: u64,
     ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)>;
 
+    /// Returns the transaction committed at `version`, together with its info and the output
+    /// (write set, events, gas used and status) that was committed on chain.
+    async fn get_committed_transaction_with_output(
+        &self,
+        version: Version,
+    ) -> Result<(Transaction, TransactionInfo, TransactionOutput)>;
+
     async fn get_latest_version(&self) -> Result<Version>;
 
     async fn get_version_by_account_sequence(
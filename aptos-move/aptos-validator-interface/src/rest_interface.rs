@@ -3,7 +3,7 @@
 
 use crate::AptosValidatorInterface;
 use anyhow::{anyhow, Result};
-use aptos_api_types::{AptosError, AptosErrorCode};
+use aptos_api_types::{AptosError, AptosErrorCode, TransactionData};
 use aptos_rest_client::{
     error::{AptosErrorResponse, RestError},
     Client,
@@ -12,7 +12,7 @@ use aptos_types::{
     account_address::AccountAddress,
     account_state::AccountState,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, TransactionInfo, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
 };
 use std::collections::BTreeMap;
 
@@ -92,6 +92,32 @@ impl AptosValidatorInterface for RestDebuggerInterface {
         Ok((txns, txn_infos))
     }
 
+    async fn get_committed_transaction_with_output(
+        &self,
+        version: Version,
+    ) -> Result<(Transaction, TransactionInfo, TransactionOutput)> {
+        match self
+            .0
+            .get_transaction_by_version_bcs(version)
+            .await?
+            .into_inner()
+        {
+            TransactionData::OnChain(txn) => {
+                let output = TransactionOutput::new(
+                    txn.changes,
+                    txn.events,
+                    txn.info.gas_used(),
+                    txn.info.status().clone().into(),
+                );
+                Ok((txn.transaction, txn.info, output))
+            },
+            TransactionData::Pending(_) => Err(anyhow!(
+                "Transaction at version {} is not committed",
+                version
+            )),
+        }
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         Ok(self.0.get_ledger_information().await?.into_inner().version)
     }
@@ -14,7 +14,7 @@ use aptos_types::{
     account_address::AccountAddress,
     account_state::AccountState,
     state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValue},
-    transaction::{Transaction, TransactionInfo, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
 };
 use std::{path::Path, sync::Arc};
 
@@ -79,6 +79,39 @@ impl AptosValidatorInterface for DBDebuggerInterface {
         Ok((txns, txn_infos))
     }
 
+    async fn get_committed_transaction_with_output(
+        &self,
+        version: Version,
+    ) -> Result<(Transaction, TransactionInfo, TransactionOutput)> {
+        let txn = self
+            .0
+            .get_transaction_iterator(version, 1)?
+            .next()
+            .ok_or_else(|| anyhow!("Transaction at version {} not found", version))??;
+        let txn_info = self
+            .0
+            .get_transaction_info_iterator(version, 1)?
+            .next()
+            .ok_or_else(|| anyhow!("Transaction info at version {} not found", version))??;
+        let events = self
+            .0
+            .get_events_iterator(version, 1)?
+            .next()
+            .ok_or_else(|| anyhow!("Events at version {} not found", version))??;
+        let write_set = self
+            .0
+            .get_write_set_iterator(version, 1)?
+            .next()
+            .ok_or_else(|| anyhow!("Write set at version {} not found", version))??;
+        let output = TransactionOutput::new(
+            write_set,
+            events,
+            txn_info.gas_used(),
+            txn_info.status().clone().into(),
+        );
+        Ok((txn, txn_info, output))
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         let (version, _) = self
             .0
@@ -34,9 +34,9 @@ use aptos_types::{
     block_metadata::BlockMetadata,
     on_chain_config::{new_epoch_event_key, FeatureFlag, TimedFeatureOverride},
     transaction::{
-        EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle, Multisig,
-        MultisigTransactionPayload, SignatureCheckedTransaction, SignedTransaction, Transaction,
-        TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
+        authenticator::Scheme, EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle,
+        Multisig, MultisigTransactionPayload, SignatureCheckedTransaction, SignedTransaction,
+        Transaction, TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
         WriteSetPayload,
     },
     vm_status::{AbortLocation, StatusCode, VMStatus},
@@ -1625,6 +1625,33 @@ impl VMAdapter for AptosVM {
             ));
         }
 
+        if !self
+            .0
+            .get_features()
+            .is_enabled(FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR)
+            && txn
+                .authenticator_ref()
+                .all_signers()
+                .iter()
+                .flat_map(|signer| signer.key_schemes())
+                .any(|scheme| matches!(scheme, Scheme::Secp256k1Ecdsa))
+        {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING, None));
+        }
+
+        if !self
+            .0
+            .get_features()
+            .is_enabled(FeatureFlag::MULTI_KEY_AUTHENTICATOR)
+            && txn
+                .authenticator_ref()
+                .all_signers()
+                .iter()
+                .any(|signer| matches!(signer.scheme(), Scheme::MultiKey))
+        {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING, None));
+        }
+
         Ok(())
     }
 
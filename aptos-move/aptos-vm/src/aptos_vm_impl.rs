@@ -7,7 +7,10 @@ use crate::{
     data_cache::StorageAdapter,
     errors::{convert_epilogue_error, convert_prologue_error, expect_only_successful_execution},
     move_vm_ext::{MoveResolverExt, MoveVmExt, SessionExt, SessionId},
-    system_module_names::{MULTISIG_ACCOUNT_MODULE, VALIDATE_MULTISIG_TRANSACTION},
+    system_module_names::{
+        FEE_PAYER_SCRIPT_PROLOGUE_NAME, MULTISIG_ACCOUNT_MODULE, USER_EPILOGUE_GAS_PAYER_NAME,
+        VALIDATE_MULTISIG_TRANSACTION,
+    },
     transaction_metadata::TransactionMetadata,
 };
 use aptos_framework::RuntimeModuleMetadataV1;
@@ -33,6 +36,7 @@ use fail::fail_point;
 use move_binary_format::{errors::VMResult, CompiledModule};
 use move_core_types::{
     gas_algebra::NumArgs,
+    identifier::IdentStr,
     language_storage::ModuleId,
     move_resource::MoveStructType,
     value::{serialize_values, MoveValue},
@@ -360,8 +364,9 @@ impl AptosVMImpl {
         Ok(())
     }
 
-    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME` function
-    /// or `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` function stored in the `ACCOUNT_MODULE` on chain.
+    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME`,
+    /// `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` or `FEE_PAYER_SCRIPT_PROLOGUE_NAME` function stored in
+    /// the `ACCOUNT_MODULE` on chain.
     pub(crate) fn run_script_prologue(
         &self,
         session: &mut SessionExt,
@@ -381,7 +386,25 @@ impl AptosVMImpl {
             .iter()
             .map(|auth_key| MoveValue::vector_u8(auth_key.to_vec()))
             .collect();
-        let args = if txn_data.is_multi_agent() {
+        let args = if let Some(fee_payer) = txn_data.fee_payer() {
+            let fee_payer_auth_key = txn_data
+                .fee_payer_authentication_key
+                .clone()
+                .ok_or(VMStatus::Error(StatusCode::UNREACHABLE, None))?;
+            vec![
+                MoveValue::Signer(txn_data.sender),
+                MoveValue::U64(txn_sequence_number),
+                MoveValue::vector_u8(txn_authentication_key),
+                MoveValue::vector_address(txn_data.secondary_signers()),
+                MoveValue::Vector(secondary_auth_keys),
+                MoveValue::Address(fee_payer),
+                MoveValue::vector_u8(fee_payer_auth_key),
+                MoveValue::U64(txn_gas_price.into()),
+                MoveValue::U64(txn_max_gas_units.into()),
+                MoveValue::U64(txn_expiration_timestamp_secs),
+                MoveValue::U8(chain_id.id()),
+            ]
+        } else if txn_data.is_multi_agent() {
             vec![
                 MoveValue::Signer(txn_data.sender),
                 MoveValue::U64(txn_sequence_number),
@@ -405,10 +428,14 @@ impl AptosVMImpl {
                 MoveValue::vector_u8(txn_data.script_hash.clone()),
             ]
         };
-        let prologue_function_name = if txn_data.is_multi_agent() {
-            &transaction_validation.multi_agent_prologue_name
+        let prologue_function_name = if txn_data.fee_payer().is_some() {
+            FEE_PAYER_SCRIPT_PROLOGUE_NAME
+        } else if txn_data.is_multi_agent() {
+            transaction_validation
+                .multi_agent_prologue_name
+                .as_ident_str()
         } else {
-            &transaction_validation.script_prologue_name
+            transaction_validation.script_prologue_name.as_ident_str()
         };
         session
             .execute_function_bypass_visibility(
@@ -501,6 +528,39 @@ impl AptosVMImpl {
             .or_else(|err| convert_prologue_error(transaction_validation, err, log_context))
     }
 
+    /// Returns the epilogue function to call and its arguments, which charge gas to the fee payer
+    /// if the transaction has one and to the sender otherwise.
+    fn user_epilogue_function_and_args(
+        &self,
+        gas_remaining: Gas,
+        txn_data: &TransactionMetadata,
+    ) -> (&IdentStr, Vec<MoveValue>) {
+        let transaction_validation = self.transaction_validation();
+        let txn_sequence_number = txn_data.sequence_number();
+        let txn_gas_price = txn_data.gas_unit_price();
+        let txn_max_gas_units = txn_data.max_gas_amount();
+        match txn_data.fee_payer() {
+            Some(fee_payer) => (USER_EPILOGUE_GAS_PAYER_NAME, vec![
+                MoveValue::Signer(txn_data.sender),
+                MoveValue::Address(fee_payer),
+                MoveValue::U64(txn_sequence_number),
+                MoveValue::U64(txn_gas_price.into()),
+                MoveValue::U64(txn_max_gas_units.into()),
+                MoveValue::U64(gas_remaining.into()),
+            ]),
+            None => (
+                transaction_validation.user_epilogue_name.as_ident_str(),
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+        }
+    }
+
     /// Run the epilogue of a transaction by calling into `EPILOGUE_NAME` function stored
     /// in the `ACCOUNT_MODULE` on chain.
     pub(crate) fn run_success_epilogue(
@@ -518,22 +578,15 @@ impl AptosVMImpl {
         });
 
         let transaction_validation = self.transaction_validation();
-        let txn_sequence_number = txn_data.sequence_number();
-        let txn_gas_price = txn_data.gas_unit_price();
-        let txn_max_gas_units = txn_data.max_gas_amount();
+        let (epilogue_function_name, args) =
+            self.user_epilogue_function_and_args(gas_remaining, txn_data);
         session
             .execute_function_bypass_visibility(
                 &transaction_validation.module_id(),
-                &transaction_validation.user_epilogue_name,
+                epilogue_function_name,
                 // TODO: Deprecate this once we remove gas currency on the Move side.
                 vec![],
-                serialize_values(&vec![
-                    MoveValue::Signer(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price.into()),
-                    MoveValue::U64(txn_max_gas_units.into()),
-                    MoveValue::U64(gas_remaining.into()),
-                ]),
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
@@ -551,32 +604,21 @@ impl AptosVMImpl {
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
         let transaction_validation = self.transaction_validation();
-        let txn_sequence_number = txn_data.sequence_number();
-        let txn_gas_price = txn_data.gas_unit_price();
-        let txn_max_gas_units = txn_data.max_gas_amount();
+        let (epilogue_function_name, args) =
+            self.user_epilogue_function_and_args(gas_remaining, txn_data);
         session
             .execute_function_bypass_visibility(
                 &transaction_validation.module_id(),
-                &transaction_validation.user_epilogue_name,
+                epilogue_function_name,
                 // TODO: Deprecate this once we remove gas currency on the Move side.
                 vec![],
-                serialize_values(&vec![
-                    MoveValue::Signer(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price.into()),
-                    MoveValue::U64(txn_max_gas_units.into()),
-                    MoveValue::U64(gas_remaining.into()),
-                ]),
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
             .map_err(expect_no_verification_errors)
             .or_else(|e| {
-                expect_only_successful_execution(
-                    e,
-                    transaction_validation.user_epilogue_name.as_str(),
-                    log_context,
-                )
+                expect_only_successful_execution(e, epilogue_function_name.as_str(), log_context)
             })
     }
 
@@ -1,13 +1,24 @@
 // Copyright © Aptos Foundation
 
-use crate::block_executor::BlockAptosVM;
-use aptos_state_view::StateView;
+use crate::{
+    block_executor::BlockAptosVM,
+    sharded_block_executor::{
+        cross_shard_client::{
+            CrossShardClient, CrossShardRequirements, CrossShardStateExchange,
+            LocalCrossShardClient,
+        },
+        total_supply::{total_supply_override, total_supply_state_key},
+    },
+};
+use aptos_state_view::{overridden_state_view::OverriddenStateView, StateView};
 use aptos_types::{
-    block_executor::partitioner::ExecutableTransactions,
+    block_executor::partitioner::{ExecutableTransactions, SubBlock, SubBlocksForShard},
+    state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{Transaction, TransactionOutput},
+    write_set::TransactionWrite,
 };
-use move_core_types::vm_status::VMStatus;
-use std::sync::Arc;
+use move_core_types::vm_status::{StatusCode, VMStatus};
+use std::{collections::HashMap, sync::Arc};
 
 pub trait BlockExecutorClient {
     fn execute_block<S: StateView + Sync>(
@@ -17,6 +28,17 @@ pub trait BlockExecutorClient {
         concurrency_level: usize,
         maybe_block_gas_limit: Option<u64>,
     ) -> Result<Vec<TransactionOutput>, VMStatus>;
+
+    /// Executes the sub blocks of a shard of a partitioned block in order, exchanging with the
+    /// other shards the values of the state items their transactions depend on.
+    fn execute_sub_blocks<S: StateView + Sync>(
+        &self,
+        sub_blocks: SubBlocksForShard<Transaction>,
+        cross_shard_requirements: CrossShardRequirements,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus>;
 }
 
 impl BlockExecutorClient for LocalExecutorClient {
@@ -36,10 +58,41 @@ impl BlockExecutorClient for LocalExecutorClient {
             maybe_block_gas_limit,
         )
     }
+
+    fn execute_sub_blocks<S: StateView + Sync>(
+        &self,
+        sub_blocks: SubBlocksForShard<Transaction>,
+        cross_shard_requirements: CrossShardRequirements,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let cross_shard_client = self.cross_shard_client.as_deref().ok_or_else(|| {
+            VMStatus::Error(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                Some("executor client has no cross shard client".to_string()),
+            )
+        })?;
+        let mut cross_shard_state_exchange =
+            CrossShardStateExchange::new(cross_shard_client, cross_shard_requirements);
+        let ret = self.execute_sub_blocks_in_order(
+            sub_blocks,
+            &mut cross_shard_state_exchange,
+            state_view,
+            concurrency_level,
+            maybe_block_gas_limit,
+        );
+        if ret.is_err() {
+            cross_shard_state_exchange.broadcast_stop();
+        }
+        ret
+    }
 }
 
 pub struct LocalExecutorClient {
     executor_thread_pool: Arc<rayon::ThreadPool>,
+    // Exchanges the values of the state items with the other shards of a partitioned block.
+    cross_shard_client: Option<Box<dyn CrossShardClient>>,
 }
 
 impl LocalExecutorClient {
@@ -53,14 +106,142 @@ impl LocalExecutorClient {
 
         Self {
             executor_thread_pool,
+            cross_shard_client: None,
+        }
+    }
+
+    /// Creates a client that can execute the sub blocks of a partitioned block, exchanging the
+    /// cross shard state with the other shards through `cross_shard_client`.
+    pub fn with_cross_shard_client(
+        num_threads: usize,
+        cross_shard_client: Box<dyn CrossShardClient>,
+    ) -> Self {
+        Self {
+            cross_shard_client: Some(cross_shard_client),
+            ..Self::new(num_threads)
         }
     }
 
     pub fn create_local_clients(num_shards: usize, num_threads: Option<usize>) -> Vec<Self> {
         let num_threads = num_threads
             .unwrap_or_else(|| (num_cpus::get() as f64 / num_shards as f64).ceil() as usize);
-        (0..num_shards)
-            .map(|_| LocalExecutorClient::new(num_threads))
+        LocalCrossShardClient::create_clients(num_shards)
+            .into_iter()
+            .map(|cross_shard_client| {
+                LocalExecutorClient::with_cross_shard_client(
+                    num_threads,
+                    Box::new(cross_shard_client),
+                )
+            })
             .collect()
     }
+
+    /// Executes each sub block against the state written by the previous sub blocks of the shard
+    /// and the remote writes it depends on, which are received from the other shards once their
+    /// sub blocks are executed.
+    fn execute_sub_blocks_in_order<S: StateView + Sync>(
+        &self,
+        sub_blocks: SubBlocksForShard<Transaction>,
+        cross_shard_state_exchange: &mut CrossShardStateExchange,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let total_supply = match total_supply_state_key(state_view)? {
+            Some(state_key) => total_supply_override(state_view, &state_key)?
+                .map(|total_supply| (state_key, total_supply)),
+            None => None,
+        };
+        let mut cross_shard_state = HashMap::new();
+        let mut outputs = vec![];
+        for (round, sub_block) in sub_blocks.sub_blocks.into_iter().enumerate() {
+            let remote_writes = cross_shard_state_exchange
+                .receive_required_writes(round)
+                .ok_or_else(|| {
+                    VMStatus::Error(
+                        StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                        Some("remote shard failed to execute the block".to_string()),
+                    )
+                })?;
+            cross_shard_state.extend(remote_writes);
+            if let Some((state_key, total_supply)) = &total_supply {
+                cross_shard_state.insert(state_key.clone(), Some(total_supply.clone()));
+            }
+
+            let sub_block_outputs = self.execute_sub_block(
+                sub_block,
+                &cross_shard_state,
+                cross_shard_state_exchange,
+                state_view,
+                concurrency_level,
+                maybe_block_gas_limit,
+            )?;
+            for output in &sub_block_outputs {
+                for (state_key, write_op) in output.write_set().iter() {
+                    if total_supply
+                        .as_ref()
+                        .map_or(true, |(total_supply_key, _)| state_key != total_supply_key)
+                    {
+                        cross_shard_state.insert(state_key.clone(), write_op.as_state_value());
+                    }
+                }
+            }
+            outputs.extend(sub_block_outputs);
+        }
+        Ok(outputs)
+    }
+
+    /// Executes a sub block and sends the values written by its transactions to the shards that
+    /// depend on them.
+    fn execute_sub_block<S: StateView + Sync>(
+        &self,
+        sub_block: SubBlock<Transaction>,
+        cross_shard_state: &HashMap<StateKey, Option<StateValue>>,
+        cross_shard_state_exchange: &CrossShardStateExchange,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let start_index = sub_block.start_index;
+        let transactions = sub_block
+            .into_iter()
+            .map(|txn_with_deps| txn_with_deps.txn)
+            .collect();
+        let outputs = self.execute_block(
+            transactions,
+            &OverriddenStateView::new(state_view, cross_shard_state),
+            concurrency_level,
+            maybe_block_gas_limit,
+        )?;
+
+        for offset in 0..outputs.len() {
+            for (state_key, shard_id) in
+                cross_shard_state_exchange.dependent_shards(start_index + offset)
+            {
+                // The value right after the transaction, which is either written by the
+                // transaction or a previous transaction of the sub block, or was already there
+                // before the sub block.
+                let value = match outputs[..=offset]
+                    .iter()
+                    .rev()
+                    .find_map(|output| output.write_set().get(state_key))
+                {
+                    Some(write_op) => write_op.as_state_value(),
+                    None => match cross_shard_state.get(state_key) {
+                        Some(value) => value.clone(),
+                        None => state_view
+                            .get_state_value(state_key)
+                            .map_err(|_| VMStatus::Error(StatusCode::STORAGE_ERROR, None))?,
+                    },
+                };
+                cross_shard_state_exchange.send_remote_write(
+                    *shard_id,
+                    start_index + offset,
+                    state_key.clone(),
+                    value,
+                );
+            }
+        }
+        Ok(outputs)
+    }
 }
@@ -0,0 +1,280 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_infallible::Mutex;
+use aptos_types::{
+    block_executor::partitioner::{ShardId, SubBlocksForShard, TxnIndex},
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::analyzed_transaction::{AnalyzedTransaction, StorageLocation},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::mpsc::{Receiver, Sender},
+};
+
+/// Identifies an execution of a partitioned block. The cross shard clients outlive the blocks, so
+/// the messages of a previous (failed) block must be told apart from the ones of the current block.
+pub type ExecutionId = u64;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum CrossShardMsg {
+    RemoteTxnWriteMsg(RemoteTxnWrite),
+    // Sent by a shard that failed to execute its sub blocks, as the shards depending on it would
+    // otherwise wait forever.
+    StopMsg(ExecutionId),
+}
+
+impl CrossShardMsg {
+    pub fn execution_id(&self) -> ExecutionId {
+        match self {
+            CrossShardMsg::RemoteTxnWriteMsg(write) => write.execution_id,
+            CrossShardMsg::StopMsg(execution_id) => *execution_id,
+        }
+    }
+}
+
+/// The value of a state item right after the execution of a transaction, sent to the shards whose
+/// transactions depend on it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RemoteTxnWrite {
+    execution_id: ExecutionId,
+    txn_index: TxnIndex,
+    state_key: StateKey,
+    value: Option<StateValue>,
+}
+
+impl RemoteTxnWrite {
+    pub fn new(
+        execution_id: ExecutionId,
+        txn_index: TxnIndex,
+        state_key: StateKey,
+        value: Option<StateValue>,
+    ) -> Self {
+        Self {
+            execution_id,
+            txn_index,
+            state_key,
+            value,
+        }
+    }
+}
+
+/// Sends and receives the messages exchanged by the executor shards of a partitioned block.
+pub trait CrossShardClient: Send + Sync {
+    fn send_cross_shard_msg(&self, shard_id: ShardId, msg: CrossShardMsg);
+
+    /// Blocks until a message from another shard is received. Returns `None` if the other shards
+    /// can't be reached anymore.
+    fn receive_cross_shard_msg(&self) -> Option<CrossShardMsg>;
+}
+
+/// A [`CrossShardClient`] for executor shards running in the same process.
+pub struct LocalCrossShardClient {
+    message_rx: Mutex<Receiver<CrossShardMsg>>,
+    message_txs: Mutex<Vec<Sender<CrossShardMsg>>>,
+}
+
+impl LocalCrossShardClient {
+    /// Creates the clients of all the shards, connected in a full mesh.
+    pub fn create_clients(num_shards: usize) -> Vec<Self> {
+        let (message_txs, message_rxs): (Vec<_>, Vec<_>) =
+            (0..num_shards).map(|_| std::sync::mpsc::channel()).unzip();
+        message_rxs
+            .into_iter()
+            .map(|message_rx| Self {
+                message_rx: Mutex::new(message_rx),
+                message_txs: Mutex::new(message_txs.clone()),
+            })
+            .collect()
+    }
+}
+
+impl CrossShardClient for LocalCrossShardClient {
+    fn send_cross_shard_msg(&self, shard_id: ShardId, msg: CrossShardMsg) {
+        // The receiving shard is gone only if its executor shut down.
+        let _ = self.message_txs.lock()[shard_id].send(msg);
+    }
+
+    fn receive_cross_shard_msg(&self) -> Option<CrossShardMsg> {
+        self.message_rx.lock().recv().ok()
+    }
+}
+
+/// The cross shard dependencies of the sub blocks of a shard: the remote writes each sub block
+/// depends on, and the remote shards to send the writes of the shard to.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CrossShardRequirements {
+    execution_id: ExecutionId,
+    // The remote writes each sub block of the shard depends on, by round.
+    required_writes: Vec<HashSet<(TxnIndex, StateKey)>>,
+    // The remote shards to send the written values to, by the index of the writing transaction.
+    dependent_shards: HashMap<TxnIndex, Vec<(StateKey, ShardId)>>,
+}
+
+impl CrossShardRequirements {
+    /// Creates the requirements of all the shards of a partitioned block. `sub_block_ranges` are
+    /// the transaction indices of the non empty sub blocks of the block along with the shard
+    /// executing them, in the order of the block.
+    ///
+    /// The cross shard dependencies are resolved from the transaction indices of the required
+    /// edges, dependencies on transactions of the same shard are satisfied by the shard itself.
+    pub fn create(
+        execution_id: ExecutionId,
+        sub_blocks: &[SubBlocksForShard<AnalyzedTransaction>],
+        sub_block_ranges: &[(Range<TxnIndex>, ShardId)],
+    ) -> Vec<Self> {
+        let shard_of = |txn_index: TxnIndex| {
+            let pos = sub_block_ranges.partition_point(|(range, _)| range.end <= txn_index);
+            sub_block_ranges
+                .get(pos)
+                .filter(|(range, _)| range.contains(&txn_index))
+                .map(|(_, shard_id)| *shard_id)
+        };
+
+        let mut required_writes = vec![];
+        let mut dependent_shards = vec![HashMap::new(); sub_blocks.len()];
+        for (shard_id, sub_blocks_for_shard) in sub_blocks.iter().enumerate() {
+            let mut required_writes_for_shard = vec![];
+            for sub_block in sub_blocks_for_shard.sub_block_iter() {
+                let mut required_writes_for_sub_block = HashSet::new();
+                for txn in sub_block.iter() {
+                    for (required_txn, locations) in
+                        txn.cross_shard_dependencies().required_edges_iter()
+                    {
+                        let source_shard_id = match shard_of(required_txn.txn_index) {
+                            Some(source_shard_id) if source_shard_id != shard_id => source_shard_id,
+                            _ => continue,
+                        };
+                        // Wildcard locations can't be resolved to state items, the partitioner
+                        // doesn't produce them for now.
+                        for state_key in locations.iter().filter_map(|location| match location {
+                            StorageLocation::Specific(state_key) => Some(state_key),
+                            _ => None,
+                        }) {
+                            if required_writes_for_sub_block
+                                .insert((required_txn.txn_index, state_key.clone()))
+                            {
+                                let dependents: &mut Vec<_> = dependent_shards[source_shard_id]
+                                    .entry(required_txn.txn_index)
+                                    .or_default();
+                                if !dependents.contains(&(state_key.clone(), shard_id)) {
+                                    dependents.push((state_key.clone(), shard_id));
+                                }
+                            }
+                        }
+                    }
+                }
+                required_writes_for_shard.push(required_writes_for_sub_block);
+            }
+            required_writes.push(required_writes_for_shard);
+        }
+
+        required_writes
+            .into_iter()
+            .zip(dependent_shards)
+            .map(|(required_writes, dependent_shards)| Self {
+                execution_id,
+                required_writes,
+                dependent_shards,
+            })
+            .collect()
+    }
+}
+
+/// Exchanges the values of the state items the transactions of different shards depend on, for
+/// the sub blocks of a shard.
+pub struct CrossShardStateExchange<'a> {
+    cross_shard_client: &'a dyn CrossShardClient,
+    requirements: CrossShardRequirements,
+    // The values received from the remote shards, a value can be needed by several sub blocks.
+    received_writes: HashMap<(TxnIndex, StateKey), Option<StateValue>>,
+}
+
+impl<'a> CrossShardStateExchange<'a> {
+    pub fn new(
+        cross_shard_client: &'a dyn CrossShardClient,
+        requirements: CrossShardRequirements,
+    ) -> Self {
+        Self {
+            cross_shard_client,
+            requirements,
+            received_writes: HashMap::new(),
+        }
+    }
+
+    /// Returns the remote shards that depend on the values written by the transaction.
+    pub fn dependent_shards(&self, txn_index: TxnIndex) -> &[(StateKey, ShardId)] {
+        self.requirements
+            .dependent_shards
+            .get(&txn_index)
+            .map_or(&[], |dependents| dependents.as_slice())
+    }
+
+    pub fn send_remote_write(
+        &self,
+        shard_id: ShardId,
+        txn_index: TxnIndex,
+        state_key: StateKey,
+        value: Option<StateValue>,
+    ) {
+        let write =
+            RemoteTxnWrite::new(self.requirements.execution_id, txn_index, state_key, value);
+        self.cross_shard_client
+            .send_cross_shard_msg(shard_id, CrossShardMsg::RemoteTxnWriteMsg(write));
+    }
+
+    /// Notifies the shards depending on this shard that it won't send the values they depend on.
+    pub fn broadcast_stop(&self) {
+        let shard_ids: HashSet<_> = self
+            .requirements
+            .dependent_shards
+            .values()
+            .flatten()
+            .map(|(_, shard_id)| *shard_id)
+            .collect();
+        for shard_id in shard_ids {
+            self.cross_shard_client.send_cross_shard_msg(
+                shard_id,
+                CrossShardMsg::StopMsg(self.requirements.execution_id),
+            );
+        }
+    }
+
+    /// Blocks until all the remote writes the sub block of the round depends on are received.
+    /// Returns `None` if a remote shard failed to execute the block.
+    pub fn receive_required_writes(
+        &mut self,
+        round: usize,
+    ) -> Option<Vec<(StateKey, Option<StateValue>)>> {
+        let required_writes = self
+            .requirements
+            .required_writes
+            .get(round)
+            .cloned()
+            .unwrap_or_default();
+        let mut values = Vec::with_capacity(required_writes.len());
+        for key in required_writes {
+            loop {
+                if let Some(value) = self.received_writes.get(&key) {
+                    values.push((key.1, value.clone()));
+                    break;
+                }
+                let msg = self.cross_shard_client.receive_cross_shard_msg()?;
+                // The messages of previous blocks are left over by the failed ones.
+                if msg.execution_id() != self.requirements.execution_id {
+                    continue;
+                }
+                match msg {
+                    CrossShardMsg::RemoteTxnWriteMsg(write) => {
+                        self.received_writes
+                            .insert((write.txn_index, write.state_key), write.value);
+                    },
+                    CrossShardMsg::StopMsg(_) => return None,
+                }
+            }
+        }
+        Some(values)
+    }
+}
@@ -66,6 +66,28 @@ impl<S: StateView + Sync + Send + 'static, E: BlockExecutorClient> ExecutorShard
                     drop(state_view);
                     self.result_tx.send(ret).unwrap();
                 },
+                ExecutorShardCommand::ExecuteSubBlocks(
+                    state_view,
+                    sub_blocks,
+                    cross_shard_requirements,
+                    concurrency_level_per_shard,
+                    maybe_block_gas_limit,
+                ) => {
+                    trace!(
+                        "Shard {} received ExecuteSubBlocks command of {} sub blocks",
+                        self.shard_id,
+                        sub_blocks.num_sub_blocks()
+                    );
+                    let ret = self.executor_client.execute_sub_blocks(
+                        sub_blocks,
+                        cross_shard_requirements,
+                        state_view.as_ref(),
+                        concurrency_level_per_shard,
+                        maybe_block_gas_limit,
+                    );
+                    drop(state_view);
+                    self.result_tx.send(ret).unwrap();
+                },
                 ExecutorShardCommand::Stop => {
                     break;
                 },
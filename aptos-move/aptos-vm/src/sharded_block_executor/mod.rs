@@ -2,15 +2,24 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sharded_block_executor::{counters::NUM_EXECUTOR_SHARDS, executor_shard::ExecutorShard};
+use crate::sharded_block_executor::{
+    counters::NUM_EXECUTOR_SHARDS, cross_shard_client::CrossShardRequirements,
+    executor_shard::ExecutorShard, total_supply::aggregate_total_supply,
+};
 use aptos_block_partitioner::{BlockPartitioner, UniformPartitioner};
 use aptos_logger::{error, info, trace};
 use aptos_state_view::StateView;
-use aptos_types::transaction::{Transaction, TransactionOutput};
+use aptos_types::{
+    block_executor::partitioner::{
+        ShardId, SubBlock, SubBlocksForShard, TransactionWithDependencies, TxnIndex,
+    },
+    transaction::{analyzed_transaction::AnalyzedTransaction, Transaction, TransactionOutput},
+};
 use block_executor_client::BlockExecutorClient;
 use move_core_types::vm_status::VMStatus;
 use std::{
     marker::PhantomData,
+    ops::Range,
     sync::{
         mpsc::{Receiver, Sender},
         Arc,
@@ -20,7 +29,9 @@ use std::{
 
 pub mod block_executor_client;
 mod counters;
+pub mod cross_shard_client;
 mod executor_shard;
+pub mod total_supply;
 
 /// A wrapper around sharded block executors that manages multiple shards and aggregates the results.
 pub struct ShardedBlockExecutor<S: StateView + Sync + Send + 'static> {
@@ -34,6 +45,13 @@ pub struct ShardedBlockExecutor<S: StateView + Sync + Send + 'static> {
 
 pub enum ExecutorShardCommand<S> {
     ExecuteBlock(Arc<S>, Vec<Transaction>, usize, Option<u64>),
+    ExecuteSubBlocks(
+        Arc<S>,
+        SubBlocksForShard<Transaction>,
+        CrossShardRequirements,
+        usize,
+        Option<u64>,
+    ),
     Stop,
 }
 
@@ -103,6 +121,116 @@ impl<S: StateView + Sync + Send + 'static> ShardedBlockExecutor<S> {
         }
         Ok(aggregated_results)
     }
+
+    /// Execute a block partitioned by the `ShardedBlockPartitioner`, the sub blocks of each shard
+    /// being dispatched to the executor shard of the same index. The shards execute their sub
+    /// blocks concurrently and exchange the values of the state items their transactions depend
+    /// on, the outputs are returned in the order of the global transaction indices.
+    pub fn execute_partitioned_block(
+        &self,
+        state_view: Arc<S>,
+        sub_blocks: Vec<SubBlocksForShard<AnalyzedTransaction>>,
+        concurrency_level_per_shard: usize,
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        assert_eq!(
+            sub_blocks.len(),
+            self.num_executor_shards,
+            "Block must be partitioned into as many shards as executor shards"
+        );
+        NUM_EXECUTOR_SHARDS.set(self.num_executor_shards as i64);
+        let sub_block_ranges = sub_block_ranges(&sub_blocks);
+        let cross_shard_requirements =
+            CrossShardRequirements::create(rand::random(), &sub_blocks, &sub_block_ranges);
+        for (i, (sub_blocks, cross_shard_requirements)) in sub_blocks
+            .into_iter()
+            .zip(cross_shard_requirements.into_iter())
+            .enumerate()
+        {
+            self.command_txs[i]
+                .send(ExecutorShardCommand::ExecuteSubBlocks(
+                    state_view.clone(),
+                    into_transactions(sub_blocks),
+                    cross_shard_requirements,
+                    concurrency_level_per_shard,
+                    maybe_block_gas_limit,
+                ))
+                .unwrap();
+        }
+
+        // wait for all the shards, even if one of them failed, so that no result is left behind
+        // for the next block
+        trace!("ShardedBlockExecutor Waiting for results of partitioned block");
+        let results: Vec<_> = self
+            .result_rxs
+            .iter()
+            .map(|result_rx| result_rx.recv().unwrap())
+            .collect();
+        let mut shard_outputs = results
+            .into_iter()
+            .map(|result| result.map(|outputs| outputs.into_iter()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // the sub blocks of a shard are executed in order, so the outputs of each shard are
+        // interleaved by following the order of the sub blocks in the block
+        let mut outputs = vec![];
+        for (range, shard_id) in &sub_block_ranges {
+            outputs.extend(shard_outputs[*shard_id].by_ref().take(range.len()));
+        }
+        let ranges: Vec<_> = sub_block_ranges
+            .into_iter()
+            .map(|(range, _)| range)
+            .collect();
+        aggregate_total_supply(state_view.as_ref(), &mut outputs, &ranges)?;
+        Ok(outputs)
+    }
+}
+
+/// Returns the ranges of global transaction indices of the non empty sub blocks along with the
+/// shard they belong to, in the order of the block.
+fn sub_block_ranges(
+    sub_blocks: &[SubBlocksForShard<AnalyzedTransaction>],
+) -> Vec<(Range<TxnIndex>, ShardId)> {
+    let mut ranges: Vec<_> = sub_blocks
+        .iter()
+        .enumerate()
+        .flat_map(|(shard_id, sub_blocks_for_shard)| {
+            sub_blocks_for_shard
+                .sub_block_iter()
+                .filter(|sub_block| !sub_block.is_empty())
+                .map(move |sub_block| (sub_block.start_index..sub_block.end_index(), shard_id))
+        })
+        .collect();
+    ranges.sort_by_key(|(range, _)| range.start);
+    ranges
+}
+
+/// Strips the analysis of the transactions of a shard, which is only needed to partition the
+/// block, before they are sent to the executor shard.
+fn into_transactions(
+    sub_blocks: SubBlocksForShard<AnalyzedTransaction>,
+) -> SubBlocksForShard<Transaction> {
+    SubBlocksForShard {
+        shard_id: sub_blocks.shard_id,
+        sub_blocks: sub_blocks
+            .sub_blocks
+            .into_iter()
+            .map(|sub_block| {
+                SubBlock::new(
+                    sub_block.start_index,
+                    sub_block
+                        .into_iter()
+                        .map(|txn_with_deps| {
+                            TransactionWithDependencies::new(
+                                txn_with_deps.txn.into_inner(),
+                                txn_with_deps.cross_shard_dependencies,
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    }
 }
 
 impl<S: StateView + Sync + Send + 'static> Drop for ShardedBlockExecutor<S> {
@@ -0,0 +1,173 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_state_view::StateView;
+use aptos_types::{
+    access_path::AccessPath,
+    account_config::CoinInfoResource,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::TransactionOutput,
+    write_set::WriteOp,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    move_resource::MoveStructType,
+    vm_status::{StatusCode, VMStatus},
+};
+use std::ops::Range;
+
+/// The value of the total supply seen by the shards. Each sub block is executed against this
+/// value instead of the actual total supply, so that the outputs only carry the change of the
+/// total supply, which is then applied to the actual value once all the sub blocks are executed.
+/// It is large enough for the burns and small enough for the mints of a block.
+pub const TOTAL_SUPPLY_AGGR_BASE_VAL: u128 = u128::MAX >> 1;
+
+/// Returns the state key of the aggregator tracking the total supply of APT, as found in the
+/// `CoinInfo<AptosCoin>` resource. Every transaction burns its gas fee and so writes to it, which
+/// would make all the transactions of a block conflicting. Returns `None` if the total supply is
+/// not tracked by an aggregator.
+pub fn total_supply_state_key<S: StateView>(state_view: &S) -> Result<Option<StateKey>, VMStatus> {
+    let access_path =
+        AccessPath::resource_access_path(AccountAddress::ONE, CoinInfoResource::struct_tag())
+            .map_err(|e| VMStatus::Error(StatusCode::STORAGE_ERROR, Some(e.to_string())))?;
+    let bytes = match state_view
+        .get_state_value_bytes(&StateKey::access_path(access_path))
+        .map_err(|e| VMStatus::Error(StatusCode::STORAGE_ERROR, Some(e.to_string())))?
+    {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+    let coin_info = bcs::from_bytes::<CoinInfoResource>(&bytes).map_err(|e| {
+        VMStatus::Error(
+            StatusCode::FAILED_TO_DESERIALIZE_RESOURCE,
+            Some(e.to_string()),
+        )
+    })?;
+    Ok(coin_info
+        .supply()
+        .as_ref()
+        .and_then(|supply| supply.aggregator.as_ref())
+        .map(|aggregator| aggregator.state_key()))
+}
+
+/// Returns the overridden total supply state item the sub blocks are executed against, or `None`
+/// if the total supply state item doesn't exist.
+pub fn total_supply_override<S: StateView>(
+    state_view: &S,
+    total_supply_state_key: &StateKey,
+) -> Result<Option<StateValue>, VMStatus> {
+    let value = match state_view
+        .get_state_value(total_supply_state_key)
+        .map_err(|e| VMStatus::Error(StatusCode::STORAGE_ERROR, Some(e.to_string())))?
+    {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let bytes = bcs::to_bytes(&TOTAL_SUPPLY_AGGR_BASE_VAL).map_err(serialization_error)?;
+    Ok(Some(match value.into_metadata() {
+        None => StateValue::new_legacy(bytes),
+        Some(metadata) => StateValue::new_with_metadata(bytes, metadata),
+    }))
+}
+
+/// Replaces the total supply written by the transactions, which is relative to
+/// `TOTAL_SUPPLY_AGGR_BASE_VAL`, with the actual total supply. `sub_block_ranges` are the ranges
+/// of `outputs` that were executed as a sub block, in the order of the block.
+pub fn aggregate_total_supply<S: StateView>(
+    state_view: &S,
+    outputs: &mut [TransactionOutput],
+    sub_block_ranges: &[Range<usize>],
+) -> Result<(), VMStatus> {
+    let total_supply_state_key = match total_supply_state_key(state_view)? {
+        Some(state_key) => state_key,
+        None => return Ok(()),
+    };
+    let mut total_supply = match state_view
+        .get_state_value_bytes(&total_supply_state_key)
+        .map_err(|e| VMStatus::Error(StatusCode::STORAGE_ERROR, Some(e.to_string())))?
+    {
+        Some(bytes) => deserialize_total_supply(&bytes)?,
+        None => return Ok(()),
+    };
+    for range in sub_block_ranges {
+        let sub_block_base = total_supply;
+        for output in &mut outputs[range.clone()] {
+            let relative_value = match output
+                .write_set()
+                .get(&total_supply_state_key)
+                .and_then(|op| op.bytes())
+            {
+                Some(bytes) => deserialize_total_supply(bytes)?,
+                None => continue,
+            };
+            total_supply = if relative_value >= TOTAL_SUPPLY_AGGR_BASE_VAL {
+                sub_block_base.checked_add(relative_value - TOTAL_SUPPLY_AGGR_BASE_VAL)
+            } else {
+                sub_block_base.checked_sub(TOTAL_SUPPLY_AGGR_BASE_VAL - relative_value)
+            }
+            .ok_or_else(|| {
+                VMStatus::Error(
+                    StatusCode::ARITHMETIC_ERROR,
+                    Some("total supply overflow".to_string()),
+                )
+            })?;
+            *output = with_total_supply(output.clone(), &total_supply_state_key, total_supply)?;
+        }
+    }
+    Ok(())
+}
+
+fn with_total_supply(
+    output: TransactionOutput,
+    total_supply_state_key: &StateKey,
+    total_supply: u128,
+) -> Result<TransactionOutput, VMStatus> {
+    let (write_set, events, gas_used, status) = output.unpack();
+    let mut write_set = write_set.into_mut();
+    let bytes = bcs::to_bytes(&total_supply).map_err(serialization_error)?;
+    let op = match write_set.get(total_supply_state_key) {
+        Some(WriteOp::Creation(_)) => WriteOp::Creation(bytes),
+        Some(WriteOp::Modification(_)) => WriteOp::Modification(bytes),
+        Some(WriteOp::CreationWithMetadata { metadata, .. }) => WriteOp::CreationWithMetadata {
+            data: bytes,
+            metadata: metadata.clone(),
+        },
+        Some(WriteOp::ModificationWithMetadata { metadata, .. }) => {
+            WriteOp::ModificationWithMetadata {
+                data: bytes,
+                metadata: metadata.clone(),
+            }
+        },
+        Some(op @ (WriteOp::Deletion | WriteOp::DeletionWithMetadata { .. })) => op.clone(),
+        None => {
+            return Err(VMStatus::Error(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                Some("total supply is not written by the transaction".to_string()),
+            ))
+        },
+    };
+    write_set.insert((total_supply_state_key.clone(), op));
+    let write_set = write_set.freeze().map_err(|e| {
+        VMStatus::Error(
+            StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+            Some(e.to_string()),
+        )
+    })?;
+    Ok(TransactionOutput::new(write_set, events, gas_used, status))
+}
+
+fn deserialize_total_supply(bytes: &[u8]) -> Result<u128, VMStatus> {
+    bcs::from_bytes::<u128>(bytes).map_err(|e| {
+        VMStatus::Error(
+            StatusCode::FAILED_TO_DESERIALIZE_RESOURCE,
+            Some(format!(
+                "total supply is not a u128 aggregator value: {}",
+                e
+            )),
+        )
+    })
+}
+
+fn serialization_error(e: bcs::Error) -> VMStatus {
+    VMStatus::Error(StatusCode::VALUE_SERIALIZATION_ERROR, Some(e.to_string()))
+}
@@ -0,0 +1,76 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_language_e2e_tests::account::TransactionBuilder;
+use aptos_types::{
+    account_address::AccountAddress, account_config::CoinStoreResource,
+    on_chain_config::FeatureFlag, transaction::TransactionStatus,
+};
+use move_core_types::{move_resource::MoveStructType, vm_status::StatusCode};
+
+#[test]
+fn test_fee_payer_pays_for_gas() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::FEE_PAYER_ENABLED], vec![]);
+
+    let alice = h.new_account_with_balance_and_sequence_number(1_000, 0);
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+    let fee_payer = h.new_account_with_key_pair();
+
+    let alice_start = read_coin(&h, alice.address());
+    let bob_start = read_coin(&h, bob.address());
+    let fee_payer_start = read_coin(&h, fee_payer.address());
+
+    let transaction = TransactionBuilder::new(alice.clone())
+        .fee_payer(fee_payer.clone())
+        .payload(aptos_stdlib::aptos_account_transfer(*bob.address(), 1_000))
+        .sequence_number(h.sequence_number(alice.address()))
+        .max_gas_amount(1_000_000)
+        .gas_unit_price(1)
+        .sign_fee_payer();
+
+    let output = h.run_raw(transaction);
+    assert_success!(output.status().to_owned());
+
+    // Alice can send her whole balance since gas is charged to the fee payer
+    assert_eq!(read_coin(&h, alice.address()), alice_start - 1_000);
+    assert_eq!(read_coin(&h, bob.address()), bob_start + 1_000);
+    assert_eq!(
+        read_coin(&h, fee_payer.address()),
+        fee_payer_start - output.gas_used()
+    );
+    assert_eq!(h.sequence_number(alice.address()), 1);
+    assert_eq!(h.sequence_number(fee_payer.address()), 0);
+}
+
+#[test]
+fn test_fee_payer_not_enabled() {
+    let mut h = MoveHarness::new_with_features(vec![], vec![FeatureFlag::FEE_PAYER_ENABLED]);
+
+    let alice = h.new_account_with_key_pair();
+    let fee_payer = h.new_account_with_key_pair();
+
+    let transaction = TransactionBuilder::new(alice.clone())
+        .fee_payer(fee_payer.clone())
+        .payload(aptos_stdlib::aptos_account_transfer(
+            *fee_payer.address(),
+            1,
+        ))
+        .sequence_number(h.sequence_number(alice.address()))
+        .max_gas_amount(1_000_000)
+        .gas_unit_price(1)
+        .sign_fee_payer();
+
+    let output = h.run_raw(transaction);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Discard(StatusCode::FEE_PAYER_NOT_ENABLED)
+    );
+}
+
+fn read_coin(h: &MoveHarness, account: &AccountAddress) -> u64 {
+    h.read_resource::<CoinStoreResource>(account, CoinStoreResource::struct_tag())
+        .unwrap()
+        .coin()
+}
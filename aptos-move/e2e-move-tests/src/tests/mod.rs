@@ -9,6 +9,7 @@ mod code_publishing;
 mod common;
 mod constructor_args;
 mod error_map;
+mod fee_payer;
 mod fungible_asset;
 mod gas;
 mod generate_upgrade_script;
@@ -20,6 +21,7 @@ mod max_loop_depth;
 mod memory_quota;
 mod metadata;
 mod mint_nft;
+mod multi_key;
 mod new_integer_types;
 mod nft_dao;
 mod offer_rotation_capability;
@@ -28,6 +30,7 @@ mod per_category_gas_limits;
 mod resource_groups;
 mod rotate_auth_key;
 mod scripts;
+mod secp256k1_ecdsa;
 mod simple_defi;
 mod smart_data_structures;
 mod stake;
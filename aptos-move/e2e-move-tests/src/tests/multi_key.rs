@@ -0,0 +1,173 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_crypto::{
+    ed25519::Ed25519PrivateKey, secp256k1_ecdsa::Secp256k1EcdsaPrivateKey, PrivateKey, SigningKey,
+    Uniform,
+};
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::AccountResource,
+    on_chain_config::FeatureFlag,
+    transaction::{
+        authenticator::{
+            AnyPublicKey, AnySignature, AuthenticationKey, MultiKey, MultiKeySignature, Scheme,
+        },
+        RawTransaction, SignedTransaction, TransactionStatus,
+    },
+};
+use move_core_types::{move_resource::MoveStructType, vm_status::StatusCode};
+use rand::rngs::OsRng;
+
+/// `error::invalid_state(ESCHEME_NOT_ENABLED)` of `account.move`
+const ESCHEME_NOT_ENABLED: u64 = 0x3_0015;
+
+struct MultiKeyAccount {
+    ed25519_key: Ed25519PrivateKey,
+    secp256k1_key: Secp256k1EcdsaPrivateKey,
+    other_ed25519_key: Ed25519PrivateKey,
+    multi_key: MultiKey,
+}
+
+impl MultiKeyAccount {
+    /// A 2-of-3 multi key whose second member is a secp256k1 ECDSA key
+    fn generate() -> Self {
+        let ed25519_key = Ed25519PrivateKey::generate(&mut OsRng);
+        let secp256k1_key = Secp256k1EcdsaPrivateKey::generate(&mut OsRng);
+        let other_ed25519_key = Ed25519PrivateKey::generate(&mut OsRng);
+        let multi_key = MultiKey::new(
+            vec![
+                AnyPublicKey::ed25519(ed25519_key.public_key()),
+                AnyPublicKey::secp256k1_ecdsa(secp256k1_key.public_key()),
+                AnyPublicKey::ed25519(other_ed25519_key.public_key()),
+            ],
+            2,
+        )
+        .unwrap();
+        Self {
+            ed25519_key,
+            secp256k1_key,
+            other_ed25519_key,
+            multi_key,
+        }
+    }
+
+    fn rotate(&self, h: &mut MoveHarness, account: &Account) -> TransactionStatus {
+        h.run_transaction_payload(
+            account,
+            aptos_stdlib::account_rotate_authentication_key_call(
+                Scheme::MultiKey as u8,
+                self.multi_key.to_bytes(),
+            ),
+        )
+    }
+
+    /// Signs a transfer from `sender` with the members at the given indices
+    fn sign_transfer(
+        &self,
+        h: &MoveHarness,
+        sender: &Account,
+        signers: &[u8],
+    ) -> SignedTransaction {
+        let raw_txn: RawTransaction = sender
+            .transaction()
+            .payload(aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 1))
+            .sequence_number(h.sequence_number(sender.address()))
+            .max_gas_amount(1_000_000)
+            .gas_unit_price(100)
+            .raw();
+        let signatures = signers
+            .iter()
+            .map(|index| {
+                let signature = match index {
+                    0 => AnySignature::ed25519(self.ed25519_key.sign(&raw_txn).unwrap()),
+                    1 => AnySignature::secp256k1_ecdsa(self.secp256k1_key.sign(&raw_txn).unwrap()),
+                    2 => AnySignature::ed25519(self.other_ed25519_key.sign(&raw_txn).unwrap()),
+                    _ => unreachable!(),
+                };
+                (*index, signature)
+            })
+            .collect();
+        SignedTransaction::new_multi_key(
+            raw_txn,
+            self.multi_key.clone(),
+            MultiKeySignature::new(signatures).unwrap(),
+        )
+    }
+}
+
+#[test]
+fn test_multi_key_k_of_n() {
+    let mut h = MoveHarness::new_with_features(
+        vec![
+            FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR,
+            FeatureFlag::MULTI_KEY_AUTHENTICATOR,
+        ],
+        vec![],
+    );
+    let account = h.new_account_with_key_pair();
+    let multi_key_account = MultiKeyAccount::generate();
+
+    assert_success!(multi_key_account.rotate(&mut h, &account));
+    let account_resource = h
+        .read_resource::<AccountResource>(account.address(), AccountResource::struct_tag())
+        .unwrap();
+    assert_eq!(
+        account_resource.authentication_key(),
+        AuthenticationKey::multi_key(&multi_key_account.multi_key).to_vec()
+    );
+
+    // Any two of the three keys can sign, whatever their scheme.
+    for signers in [[0, 1], [1, 2], [0, 2]] {
+        let txn = multi_key_account.sign_transfer(&h, &account, &signers);
+        assert_success!(h.run(txn));
+    }
+
+    // A single signature is not enough.
+    let txn = multi_key_account.sign_transfer(&h, &account, &[1]);
+    assert_eq!(
+        h.run_raw(txn).status(),
+        &TransactionStatus::Discard(StatusCode::INVALID_SIGNATURE)
+    );
+}
+
+#[test]
+fn test_multi_key_not_enabled() {
+    let mut h =
+        MoveHarness::new_with_features(vec![FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR], vec![
+            FeatureFlag::MULTI_KEY_AUTHENTICATOR,
+        ]);
+    let account = h.new_account_with_key_pair();
+    let multi_key_account = MultiKeyAccount::generate();
+
+    assert_abort!(
+        multi_key_account.rotate(&mut h, &account),
+        ESCHEME_NOT_ENABLED
+    );
+
+    let txn = multi_key_account.sign_transfer(&h, &account, &[0, 2]);
+    assert_eq!(
+        h.run_raw(txn).status(),
+        &TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
+
+#[test]
+fn test_multi_key_with_secp256k1_ecdsa_member_not_enabled() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::MULTI_KEY_AUTHENTICATOR], vec![
+        FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR,
+    ]);
+    let account = h.new_account_with_key_pair();
+    let multi_key_account = MultiKeyAccount::generate();
+    assert_success!(multi_key_account.rotate(&mut h, &account));
+
+    // The multi key has a secp256k1 ECDSA member, even if only the Ed25519 members sign.
+    let txn = multi_key_account.sign_transfer(&h, &account, &[0, 2]);
+    assert_eq!(
+        h.run_raw(txn).status(),
+        &TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
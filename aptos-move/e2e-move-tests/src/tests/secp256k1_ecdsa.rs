@@ -0,0 +1,103 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_crypto::{
+    secp256k1_ecdsa::Secp256k1EcdsaPrivateKey, PrivateKey, SigningKey, Uniform, ValidCryptoMaterial,
+};
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::AccountResource,
+    on_chain_config::FeatureFlag,
+    transaction::{
+        authenticator::{AuthenticationKey, Scheme},
+        SignedTransaction, TransactionStatus,
+    },
+};
+use move_core_types::{move_resource::MoveStructType, vm_status::StatusCode};
+use rand::rngs::OsRng;
+
+/// `error::invalid_state(ESCHEME_NOT_ENABLED)` of `account.move`
+const ESCHEME_NOT_ENABLED: u64 = 0x3_0015;
+
+fn rotate(
+    h: &mut MoveHarness,
+    account: &Account,
+    private_key: &Secp256k1EcdsaPrivateKey,
+) -> TransactionStatus {
+    h.run_transaction_payload(
+        account,
+        aptos_stdlib::account_rotate_authentication_key_call(
+            Scheme::Secp256k1Ecdsa as u8,
+            private_key.public_key().to_bytes().to_vec(),
+        ),
+    )
+}
+
+fn sign_transfer(
+    h: &MoveHarness,
+    sender: &Account,
+    private_key: &Secp256k1EcdsaPrivateKey,
+) -> SignedTransaction {
+    let raw_txn = sender
+        .transaction()
+        .payload(aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 1))
+        .sequence_number(h.sequence_number(sender.address()))
+        .max_gas_amount(1_000_000)
+        .gas_unit_price(100)
+        .raw();
+    let signature = private_key.sign(&raw_txn).unwrap();
+    SignedTransaction::new_secp256k1_ecdsa(raw_txn, private_key.public_key(), signature)
+}
+
+#[test]
+fn test_secp256k1_ecdsa_signed_transaction() {
+    let mut h =
+        MoveHarness::new_with_features(vec![FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR], vec![]);
+    let account = h.new_account_with_key_pair();
+    let private_key = Secp256k1EcdsaPrivateKey::generate(&mut OsRng);
+
+    assert_success!(rotate(&mut h, &account, &private_key));
+    let account_resource = h
+        .read_resource::<AccountResource>(account.address(), AccountResource::struct_tag())
+        .unwrap();
+    assert_eq!(
+        account_resource.authentication_key(),
+        AuthenticationKey::secp256k1_ecdsa(&private_key.public_key()).to_vec()
+    );
+
+    let txn = sign_transfer(&h, &account, &private_key);
+    assert_success!(h.run(txn));
+    assert_eq!(h.sequence_number(account.address()), 2);
+
+    // The account's previous Ed25519 key can no longer sign for it.
+    let txn = account
+        .transaction()
+        .payload(aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 1))
+        .sequence_number(h.sequence_number(account.address()))
+        .max_gas_amount(1_000_000)
+        .gas_unit_price(100)
+        .sign();
+    assert_eq!(
+        h.run_raw(txn).status(),
+        &TransactionStatus::Discard(StatusCode::INVALID_AUTH_KEY)
+    );
+}
+
+#[test]
+fn test_secp256k1_ecdsa_not_enabled() {
+    let mut h =
+        MoveHarness::new_with_features(vec![], vec![FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR]);
+    let account = h.new_account_with_key_pair();
+    let private_key = Secp256k1EcdsaPrivateKey::generate(&mut OsRng);
+
+    assert_abort!(rotate(&mut h, &account, &private_key), ESCHEME_NOT_ENABLED);
+
+    let txn = sign_transfer(&h, &account, &private_key);
+    assert_eq!(
+        h.run_raw(txn).status(),
+        &TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
@@ -1,6 +1,7 @@
 module aptos_framework::account {
     use std::bcs;
     use std::error;
+    use std::features;
     use std::hash;
     use std::option::{Self, Option};
     use std::signer;
@@ -110,6 +111,10 @@ module aptos_framework::account {
     const ED25519_SCHEME: u8 = 0;
     /// Scheme identifier for MultiEd25519 signatures used to derive authentication keys for MultiEd25519 public keys.
     const MULTI_ED25519_SCHEME: u8 = 1;
+    /// Scheme identifier for Secp256k1 ECDSA signatures used to derive authentication keys for Secp256k1 ECDSA public keys.
+    const SECP256K1_ECDSA_SCHEME: u8 = 2;
+    /// Scheme identifier for K-of-N signatures of keys of mixed schemes used to derive authentication keys for multi keys.
+    const MULTI_KEY_SCHEME: u8 = 3;
     /// Scheme identifier used when hashing an account's address together with a seed to derive the address (not the
     /// authentication key) of a resource account. This is an abuse of the notion of a scheme identifier which, for now,
     /// serves to domain separate hashes used to derive resource account addresses from hashes used to derive
@@ -157,6 +162,8 @@ module aptos_framework::account {
     const ENO_SIGNER_CAPABILITY_OFFERED: u64 = 19;
     // This account has exceeded the allocated GUIDs it can create. It should be impossible to reach this number for real applications.
     const EEXCEEDED_MAX_GUID_CREATION_NUM: u64 = 20;
+    /// The signature scheme of the new key is not enabled yet
+    const ESCHEME_NOT_ENABLED: u64 = 21;
 
     /// Explicitly separate the GUID space between Object and Account to prevent accidental overlap.
     const MAX_GUID_CREATION_NUM: u64 = 0x4000000000000;
@@ -264,6 +271,41 @@ module aptos_framework::account {
         account_resource.authentication_key = new_auth_key;
     }
 
+    /// Rotates the authentication key of the account to the one derived from `new_public_key_bytes` of `new_scheme`,
+    /// without a proof of knowledge of the new key. This is meant for the schemes whose signatures can't be verified in
+    /// Move, e.g. Secp256k1 ECDSA or the multi key scheme whose members can be of mixed schemes, which are only accepted
+    /// once their feature flag is enabled. As the new key isn't verified, the `OriginatingAddress` table is not updated.
+    entry fun rotate_authentication_key_call(
+        account: &signer,
+        new_scheme: u8,
+        new_public_key_bytes: vector<u8>,
+    ) acquires Account {
+        let addr = signer::address_of(account);
+        assert!(exists_at(addr), error::not_found(EACCOUNT_DOES_NOT_EXIST));
+        if (new_scheme == SECP256K1_ECDSA_SCHEME) {
+            assert!(features::secp256k1_ecdsa_authenticator_enabled(), error::invalid_state(ESCHEME_NOT_ENABLED));
+        } else if (new_scheme == MULTI_KEY_SCHEME) {
+            assert!(features::multi_key_authenticator_enabled(), error::invalid_state(ESCHEME_NOT_ENABLED));
+        } else {
+            assert!(
+                new_scheme == ED25519_SCHEME || new_scheme == MULTI_ED25519_SCHEME,
+                error::invalid_argument(EINVALID_SCHEME)
+            );
+        };
+        let new_auth_key = new_public_key_bytes;
+        vector::push_back(&mut new_auth_key, new_scheme);
+        let new_auth_key = hash::sha3_256(new_auth_key);
+        let account_resource = borrow_global_mut<Account>(addr);
+        event::emit_event<KeyRotationEvent>(
+            &mut account_resource.key_rotation_events,
+            KeyRotationEvent {
+                old_authentication_key: account_resource.authentication_key,
+                new_authentication_key: new_auth_key,
+            }
+        );
+        account_resource.authentication_key = new_auth_key;
+    }
+
     /// Generic authentication key rotation function that allows the user to rotate their authentication key from any scheme to any scheme.
     /// To authorize the rotation, we need two signatures:
     /// - the first signature `cap_rotate_key` refers to the signature by the account owner's current key on a valid `RotationProofChallenge`,
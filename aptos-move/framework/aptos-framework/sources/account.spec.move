@@ -73,6 +73,19 @@ spec aptos_framework::account {
         ensures account_resource.authentication_key == new_auth_key;
     }
 
+    /// The Account existed under the signer before the call.
+    /// The new scheme is a known one, and enabled if it is Secp256k1 ECDSA or multi key.
+    spec rotate_authentication_key_call(account: &signer, new_scheme: u8, new_public_key_bytes: vector<u8>) {
+        let addr = signer::address_of(account);
+        aborts_if !exists<Account>(addr);
+        aborts_if new_scheme == SECP256K1_ECDSA_SCHEME
+            && !features::spec_is_enabled(features::SECP256K1_ECDSA_AUTHENTICATOR);
+        aborts_if new_scheme == MULTI_KEY_SCHEME && !features::spec_is_enabled(features::MULTI_KEY_AUTHENTICATOR);
+        aborts_if new_scheme != ED25519_SCHEME && new_scheme != MULTI_ED25519_SCHEME
+            && new_scheme != SECP256K1_ECDSA_SCHEME && new_scheme != MULTI_KEY_SCHEME;
+        modifies global<Account>(addr);
+    }
+
     spec fun spec_assert_valid_rotation_proof_signature_and_get_auth_key(scheme: u8, public_key_bytes: vector<u8>, signature: vector<u8>, challenge: RotationProofChallenge): vector<u8>;
 
     spec assert_valid_rotation_proof_signature_and_get_auth_key(scheme: u8, public_key_bytes: vector<u8>, signature: vector<u8>, challenge: &RotationProofChallenge): vector<u8> {
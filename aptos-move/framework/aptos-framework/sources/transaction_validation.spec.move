@@ -27,6 +27,7 @@ spec aptos_framework::transaction_validation {
         use aptos_framework::account::{Account};
         use aptos_framework::coin::{CoinStore};
         sender: signer;
+        gas_payer: address;
         txn_sequence_number: u64;
         txn_authentication_key: vector<u8>;
         txn_gas_price: u64;
@@ -48,12 +49,13 @@ spec aptos_framework::transaction_validation {
         let max_transaction_fee = txn_gas_price * txn_max_gas_units;
         aborts_if max_transaction_fee > MAX_U64;
         aborts_if !(txn_sequence_number == global<Account>(transaction_sender).sequence_number);
-        aborts_if !exists<CoinStore<AptosCoin>>(transaction_sender);
-        aborts_if !(global<CoinStore<AptosCoin>>(transaction_sender).coin.value >= max_transaction_fee);
+        aborts_if !exists<CoinStore<AptosCoin>>(gas_payer);
+        aborts_if !(global<CoinStore<AptosCoin>>(gas_payer).coin.value >= max_transaction_fee);
     }
 
     spec prologue_common(
         sender: signer,
+        gas_payer: address,
         txn_sequence_number: u64,
         txn_authentication_key: vector<u8>,
         txn_gas_price: u64,
@@ -74,6 +76,7 @@ spec aptos_framework::transaction_validation {
         chain_id: u8,
     ) {
         include PrologueCommonAbortsIf {
+            gas_payer: signer::address_of(sender),
             txn_authentication_key: txn_public_key
         };
     }
@@ -89,10 +92,39 @@ spec aptos_framework::transaction_validation {
         _script_hash: vector<u8>,
     ) {
         include PrologueCommonAbortsIf {
+            gas_payer: signer::address_of(sender),
             txn_authentication_key: txn_public_key
         };
     }
 
+    spec schema MultiAgentPrologueCommonAbortsIf {
+        secondary_signer_addresses: vector<address>;
+        secondary_signer_public_key_hashes: vector<vector<u8>>;
+
+        // Vectors to be `zipped with` should be of equal length.
+        let num_secondary_signers = len(secondary_signer_addresses);
+        aborts_if len(secondary_signer_public_key_hashes) != num_secondary_signers;
+
+        // If any account does not exist, or public key hash does not match, abort.
+        aborts_if exists i in 0..num_secondary_signers:
+            !account::exists_at(secondary_signer_addresses[i])
+                || secondary_signer_public_key_hashes[i] !=
+                    account::get_authentication_key(secondary_signer_addresses[i]);
+
+        // By the end, all secondary signers account should exist and public key hash should match.
+        ensures forall i in 0..num_secondary_signers:
+            account::exists_at(secondary_signer_addresses[i])
+                && secondary_signer_public_key_hashes[i] ==
+                    account::get_authentication_key(secondary_signer_addresses[i]);
+    }
+
+    spec multi_agent_common_prologue(
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_public_key_hashes: vector<vector<u8>>,
+    ) {
+        include MultiAgentPrologueCommonAbortsIf;
+    }
+
     /// Aborts if length of public key hashed vector
     /// not equal the number of singers.
     spec multi_agent_script_prologue (
@@ -107,36 +139,63 @@ spec aptos_framework::transaction_validation {
         chain_id: u8,
     ) {
         include PrologueCommonAbortsIf {
+            gas_payer: signer::address_of(sender),
             txn_authentication_key: txn_sender_public_key
         };
+        include MultiAgentPrologueCommonAbortsIf;
+    }
 
-        // Vectors to be `zipped with` should be of equal length.
-        let num_secondary_signers = len(secondary_signer_addresses);
-        aborts_if len(secondary_signer_public_key_hashes) != num_secondary_signers;
-
-        // If any account does not exist, or public key hash does not match, abort.
-        aborts_if exists i in 0..num_secondary_signers:
-            !account::exists_at(secondary_signer_addresses[i])
-                || secondary_signer_public_key_hashes[i] !=
-                    account::get_authentication_key(secondary_signer_addresses[i]);
+    /// Aborts if fee payer transactions are not enabled, or the fee payer
+    /// account does not exist or its authentication key does not match.
+    spec fee_payer_script_prologue(
+        sender: signer,
+        txn_sequence_number: u64,
+        txn_sender_public_key: vector<u8>,
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_public_key_hashes: vector<vector<u8>>,
+        fee_payer_address: address,
+        fee_payer_public_key_hash: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+    ) {
+        aborts_if !features::spec_is_enabled(features::FEE_PAYER_ENABLED);
+        include PrologueCommonAbortsIf {
+            gas_payer: fee_payer_address,
+            txn_authentication_key: txn_sender_public_key
+        };
+        include MultiAgentPrologueCommonAbortsIf;
+        aborts_if !account::exists_at(fee_payer_address);
+        aborts_if !(fee_payer_public_key_hash == account::get_authentication_key(fee_payer_address));
+    }
 
-        // By the end, all secondary signers account should exist and public key hash should match.
-        ensures forall i in 0..num_secondary_signers:
-            account::exists_at(secondary_signer_addresses[i])
-                && secondary_signer_public_key_hashes[i] ==
-                    account::get_authentication_key(secondary_signer_addresses[i]);
+    /// Same as `epilogue_gas_payer`, with the sender paying for gas.
+    spec epilogue(
+        account: signer,
+        txn_sequence_number: u64,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64
+    ) {
+        include EpilogueGasPayerAbortsIf { gas_payer: signer::address_of(account) };
     }
 
     /// Abort according to the conditions.
     /// `AptosCoinCapabilities` and `CoinInfo` should exists.
     /// Skip transaction_fee::burn_fee verification.
-    spec epilogue(
+    spec epilogue_gas_payer(
         account: signer,
+        gas_payer: address,
         _txn_sequence_number: u64,
         txn_gas_price: u64,
         txn_max_gas_units: u64,
         gas_units_remaining: u64
     ) {
+        include EpilogueGasPayerAbortsIf;
+    }
+
+    spec schema EpilogueGasPayerAbortsIf {
         use std::option;
         use aptos_std::type_info;
         use aptos_framework::account::{Account};
@@ -145,6 +204,11 @@ spec aptos_framework::transaction_validation {
         use aptos_framework::coin::{CoinStore, CoinInfo};
         use aptos_framework::optional_aggregator;
         use aptos_framework::transaction_fee::{AptosCoinCapabilities, CollectedFeesPerBlock};
+        account: signer;
+        gas_payer: address;
+        txn_gas_price: u64;
+        txn_max_gas_units: u64;
+        gas_units_remaining: u64;
 
         aborts_if !(txn_max_gas_units >= gas_units_remaining);
         let gas_used = txn_max_gas_units - gas_units_remaining;
@@ -152,16 +216,16 @@ spec aptos_framework::transaction_validation {
         aborts_if !(txn_gas_price * gas_used <= MAX_U64);
         let transaction_fee_amount = txn_gas_price * gas_used;
 
-        let addr = signer::address_of(account);
-        aborts_if !exists<CoinStore<AptosCoin>>(addr);
+        aborts_if !exists<CoinStore<AptosCoin>>(gas_payer);
         // Sufficiency of funds
-        aborts_if !(global<CoinStore<AptosCoin>>(addr).coin.value >= transaction_fee_amount);
+        aborts_if !(global<CoinStore<AptosCoin>>(gas_payer).coin.value >= transaction_fee_amount);
 
+        let addr = signer::address_of(account);
         aborts_if !exists<Account>(addr);
         aborts_if !(global<Account>(addr).sequence_number < MAX_U64);
 
-        let pre_balance = global<coin::CoinStore<AptosCoin>>(addr).coin.value;
-        let post balance = global<coin::CoinStore<AptosCoin>>(addr).coin.value;
+        let pre_balance = global<coin::CoinStore<AptosCoin>>(gas_payer).coin.value;
+        let post balance = global<coin::CoinStore<AptosCoin>>(gas_payer).coin.value;
         let pre_account = global<account::Account>(addr);
         let post account = global<account::Account>(addr);
         ensures balance == pre_balance - transaction_fee_amount;
@@ -183,12 +247,12 @@ spec aptos_framework::transaction_validation {
         aborts_if if (features::spec_is_enabled(features::COLLECT_AND_DISTRIBUTE_GAS_FEES)) {
             !exists<CollectedFeesPerBlock>(@aptos_framework)
                 || transaction_fee_amount > 0 &&
-                    ( // `exists<CoinStore<AptosCoin>>(addr)` checked above.
+                    ( // `exists<CoinStore<AptosCoin>>(gas_payer)` checked above.
                       // Sufficiency of funds is checked above.
                       aggr_val + transaction_fee_amount > aggr_lim
                         || aggr_val + transaction_fee_amount > MAX_U128)
         } else {
-            // Existence of CoinStore in `addr` is checked above.
+            // Existence of CoinStore in `gas_payer` is checked above.
             // Sufficiency of funds is checked above.
             !exists<AptosCoinCapabilities>(@aptos_framework) ||
             // Existence of APT's CoinInfo
@@ -129,6 +129,15 @@ pub enum EntryFunctionCall {
         cap_update_table: Vec<u8>,
     },
 
+    /// Rotates the authentication key of the account to the one derived from `new_public_key_bytes` of `new_scheme`,
+    /// without a proof of knowledge of the new key. This is meant for the schemes whose signatures can't be verified in
+    /// Move, e.g. Secp256k1 ECDSA or the multi key scheme whose members can be of mixed schemes, which are only accepted
+    /// once their feature flag is enabled. As the new key isn't verified, the `OriginatingAddress` table is not updated.
+    AccountRotateAuthenticationKeyCall {
+        new_scheme: u8,
+        new_public_key_bytes: Vec<u8>,
+    },
+
     AccountRotateAuthenticationKeyWithRotationCapability {
         rotation_cap_offerer_address: AccountAddress,
         new_scheme: u8,
@@ -852,6 +861,10 @@ impl EntryFunctionCall {
                 cap_rotate_key,
                 cap_update_table,
             ),
+            AccountRotateAuthenticationKeyCall {
+                new_scheme,
+                new_public_key_bytes,
+            } => account_rotate_authentication_key_call(new_scheme, new_public_key_bytes),
             AccountRotateAuthenticationKeyWithRotationCapability {
                 rotation_cap_offerer_address,
                 new_scheme,
@@ -1492,6 +1505,31 @@ pub fn account_rotate_authentication_key(
     ))
 }
 
+/// Rotates the authentication key of the account to the one derived from `new_public_key_bytes` of `new_scheme`,
+/// without a proof of knowledge of the new key. This is meant for the schemes whose signatures can't be verified in
+/// Move, e.g. Secp256k1 ECDSA or the multi key scheme whose members can be of mixed schemes, which are only accepted
+/// once their feature flag is enabled. As the new key isn't verified, the `OriginatingAddress` table is not updated.
+pub fn account_rotate_authentication_key_call(
+    new_scheme: u8,
+    new_public_key_bytes: Vec<u8>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("account").to_owned(),
+        ),
+        ident_str!("rotate_authentication_key_call").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&new_scheme).unwrap(),
+            bcs::to_bytes(&new_public_key_bytes).unwrap(),
+        ],
+    ))
+}
+
 pub fn account_rotate_authentication_key_with_rotation_capability(
     rotation_cap_offerer_address: AccountAddress,
     new_scheme: u8,
@@ -3694,6 +3732,19 @@ mod decoder {
         }
     }
 
+    pub fn account_rotate_authentication_key_call(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::AccountRotateAuthenticationKeyCall {
+                new_scheme: bcs::from_bytes(script.args().get(0)?).ok()?,
+                new_public_key_bytes: bcs::from_bytes(script.args().get(1)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn account_rotate_authentication_key_with_rotation_capability(
         payload: &TransactionPayload,
     ) -> Option<EntryFunctionCall> {
@@ -4977,6 +5028,10 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "account_rotate_authentication_key".to_string(),
             Box::new(decoder::account_rotate_authentication_key),
         );
+        map.insert(
+            "account_rotate_authentication_key_call".to_string(),
+            Box::new(decoder::account_rotate_authentication_key_call),
+        );
         map.insert(
             "account_rotate_authentication_key_with_rotation_capability".to_string(),
             Box::new(decoder::account_rotate_authentication_key_with_rotation_capability),
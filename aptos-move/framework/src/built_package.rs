@@ -3,12 +3,13 @@
 
 use crate::{
     docgen::DocgenOptions,
-    extended_checks,
+    extended_checks, get_view_functions,
     natives::code::{ModuleMetadata, MoveOption, PackageDep, PackageMetadata, UpgradePolicy},
     zip_metadata, zip_metadata_str, RuntimeModuleMetadataV1, APTOS_METADATA_KEY,
     APTOS_METADATA_KEY_V1, METADATA_V1_MIN_FILE_FORMAT_VERSION,
 };
 use anyhow::bail;
+use aptos_sdk_builder::bindings::PackageModule;
 use aptos_types::{account_address::AccountAddress, transaction::EntryABI};
 use clap::Parser;
 use codespan_reporting::{
@@ -29,7 +30,7 @@ use move_package::{
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, BTreeSet},
-    io::stderr,
+    io::{stderr, Write},
     path::{Path, PathBuf},
 };
 
@@ -242,6 +243,26 @@ impl BuiltPackage {
             })
     }
 
+    /// Writes typed Rust bindings for the modules of the package.
+    pub fn generate_rust_bindings(&self, out: &mut dyn Write) -> anyhow::Result<()> {
+        let modules: Vec<_> = self
+            .package
+            .root_modules()
+            .filter_map(|unit| match &unit.unit {
+                CompiledUnit::Module(NamedCompiledModule {
+                    module, source_map, ..
+                }) => Some(PackageModule {
+                    module: module.clone(),
+                    view_functions: get_view_functions(module),
+                    source_map: Some(source_map.clone()),
+                }),
+                CompiledUnit::Script(_) => None,
+            })
+            .collect();
+        aptos_sdk_builder::bindings::output(out, self.name(), &modules)?;
+        Ok(())
+    }
+
     /// Returns the number of scripts in the package.
     pub fn script_count(&self) -> usize {
         self.package.scripts().count()
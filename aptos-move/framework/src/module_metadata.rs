@@ -16,7 +16,7 @@ use move_core_types::{
 };
 use move_vm_runtime::move_vm::MoveVM;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use thiserror::Error;
 
 /// The minimal file format version from which the V1 metadata is supported
@@ -197,6 +197,20 @@ pub fn get_metadata_from_compiled_module(
     }
 }
 
+/// Returns the view functions of a compiled module, as declared in its metadata.
+pub fn get_view_functions(module: &CompiledModule) -> BTreeSet<Identifier> {
+    get_metadata_from_compiled_module(module)
+        .map(|metadata| {
+            metadata
+                .fun_attributes
+                .into_iter()
+                .filter(|(_, attrs)| attrs.iter().any(|attr| attr.is_view_function()))
+                .filter_map(|(name, _)| Identifier::new(name).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 // This is mostly a copy paste of the existing function
 // get_metadata_from_compiled_module. In the API types there is a unifying trait for
 // modules and scripts called Bytecode that could help eliminate this duplication,
@@ -410,6 +410,9 @@ pub fn default_features() -> Vec<FeatureFlag> {
         FeatureFlag::CRYPTOGRAPHY_ALGEBRA_NATIVES,
         FeatureFlag::BLS12_381_STRUCTURES,
         FeatureFlag::CHARGE_INVARIANT_VIOLATION,
+        FeatureFlag::FEE_PAYER_ENABLED,
+        FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR,
+        FeatureFlag::MULTI_KEY_AUTHENTICATOR,
     ]
 }
 
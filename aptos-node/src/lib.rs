@@ -461,7 +461,7 @@ pub fn setup_environment_and_start_node(
     services::start_node_inspection_service(&node_config, peers_and_metadata.clone());
 
     // Set up the storage database and any RocksDB checkpoints
-    let (aptos_db, db_rw, backup_service, genesis_waypoint) =
+    let (aptos_db, db_rw, backup_service, genesis_waypoint, restore_handler) =
         storage::initialize_database_and_checkpoints(&mut node_config)?;
 
     // Set the Aptos VM configurations
@@ -517,6 +517,7 @@ pub fn setup_environment_and_start_node(
             genesis_waypoint,
             event_subscription_service,
             db_rw.clone(),
+            restore_handler,
         )?;
 
     // Bootstrap the API and indexer
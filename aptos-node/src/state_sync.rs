@@ -9,6 +9,7 @@ use aptos_data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
     streaming_service::DataStreamingService,
 };
+use aptos_db::backup::restore_handler::RestoreHandler;
 use aptos_event_notifications::{EventSubscriptionService, ReconfigNotificationListener};
 use aptos_executor::chunk_executor::ChunkExecutor;
 use aptos_infallible::RwLock;
@@ -79,6 +80,7 @@ pub fn start_state_sync_and_get_notification_handles(
     waypoint: Waypoint,
     event_subscription_service: EventSubscriptionService,
     db_rw: DbReaderWriter,
+    restore_handler: Option<RestoreHandler>,
 ) -> anyhow::Result<(
     StateSyncRuntimes,
     MempoolNotificationListener,
@@ -103,7 +105,7 @@ pub fn start_state_sync_and_get_notification_handles(
 
     // Start the data streaming service
     let (streaming_service_client, streaming_service_runtime) =
-        setup_data_streaming_service(node_config.state_sync, aptos_data_client.clone())?;
+        setup_data_streaming_service(node_config.state_sync.clone(), aptos_data_client.clone())?;
 
     // Create the chunk executor and persistent storage
     let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new(db_rw.clone()));
@@ -134,6 +136,7 @@ pub fn start_state_sync_and_get_notification_handles(
         aptos_data_client,
         streaming_service_client,
         TimeService::real(),
+        restore_handler,
     );
 
     // Create a new state sync runtime handle
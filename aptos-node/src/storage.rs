@@ -3,7 +3,7 @@
 
 use anyhow::anyhow;
 use aptos_config::{config::NodeConfig, utils::get_genesis_txn};
-use aptos_db::AptosDB;
+use aptos_db::{backup::restore_handler::RestoreHandler, AptosDB};
 use aptos_executor::db_bootstrapper::maybe_bootstrap;
 use aptos_logger::{debug, info};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
@@ -16,16 +16,28 @@ use tokio::runtime::Runtime;
 pub(crate) fn bootstrap_db(
     aptos_db: AptosDB,
     backup_service_address: SocketAddr,
-) -> (Arc<AptosDB>, DbReaderWriter, Option<Runtime>) {
+) -> (
+    Arc<AptosDB>,
+    DbReaderWriter,
+    Option<Runtime>,
+    Option<RestoreHandler>,
+) {
     use aptos_backup_service::start_backup_service;
+    use aptos_db::GetRestoreHandler;
 
     let (aptos_db, db_rw) = DbReaderWriter::wrap(aptos_db);
     let db_backup_service = start_backup_service(backup_service_address, aptos_db.clone());
-    (aptos_db, db_rw, Some(db_backup_service))
+    let restore_handler = aptos_db.get_restore_handler();
+    (
+        aptos_db,
+        db_rw,
+        Some(db_backup_service),
+        Some(restore_handler),
+    )
 }
 
 /// In consensus-only mode, return a in-memory based [FakeAptosDB] and
-/// do not run the backup service.
+/// do not run the backup service (or support restoring from a backup).
 #[cfg(feature = "consensus-only-perf-test")]
 pub(crate) fn bootstrap_db(
     aptos_db: AptosDB,
@@ -34,11 +46,12 @@ pub(crate) fn bootstrap_db(
     Arc<aptos_db::fake_aptosdb::FakeAptosDB>,
     DbReaderWriter,
     Option<Runtime>,
+    Option<RestoreHandler>,
 ) {
     use aptos_db::fake_aptosdb::FakeAptosDB;
 
     let (aptos_db, db_rw) = DbReaderWriter::wrap(FakeAptosDB::new(aptos_db));
-    (aptos_db, db_rw, None)
+    (aptos_db, db_rw, None, None)
 }
 
 /// Creates a RocksDb checkpoint for the consensus_db, state_sync_db,
@@ -84,10 +97,16 @@ fn create_rocksdb_checkpoint_and_change_working_dir(
 
 /// Creates any rocksdb checkpoints, opens the storage database,
 /// starts the backup service, handles genesis initialization and returns
-/// the various handles.
+/// the various handles (including the handler used to restore from a backup).
 pub fn initialize_database_and_checkpoints(
     node_config: &mut NodeConfig,
-) -> anyhow::Result<(Arc<dyn DbReader>, DbReaderWriter, Option<Runtime>, Waypoint)> {
+) -> anyhow::Result<(
+    Arc<dyn DbReader>,
+    DbReaderWriter,
+    Option<Runtime>,
+    Waypoint,
+    Option<RestoreHandler>,
+)> {
     // If required, create RocksDB checkpoints and change the working directory.
     // This is test-only.
     if let Some(working_dir) = node_config.base.working_dir.clone() {
@@ -106,7 +125,7 @@ pub fn initialize_database_and_checkpoints(
         node_config.storage.max_num_nodes_per_lru_cache_shard,
     )
     .map_err(|err| anyhow!("DB failed to open {}", err))?;
-    let (aptos_db, db_rw, backup_service) =
+    let (aptos_db, db_rw, backup_service, restore_handler) =
         bootstrap_db(aptos_db, node_config.storage.backup_service_address);
 
     // TODO: handle non-genesis waypoints for state sync!
@@ -125,5 +144,11 @@ pub fn initialize_database_and_checkpoints(
         instant.elapsed().as_millis()
     );
 
-    Ok((aptos_db, db_rw, backup_service, genesis_waypoint))
+    Ok((
+        aptos_db,
+        db_rw,
+        backup_service,
+        genesis_waypoint,
+        restore_handler,
+    ))
 }
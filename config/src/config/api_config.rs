@@ -4,8 +4,9 @@
 
 use crate::{
     config::{
-        config_sanitizer::ConfigSanitizer, gas_estimation_config::GasEstimationConfig,
-        node_config_loader::NodeType, Error, NodeConfig,
+        api_rate_limit_config::ApiRateLimitConfig, config_sanitizer::ConfigSanitizer,
+        gas_estimation_config::GasEstimationConfig, node_config_loader::NodeType, Error,
+        NodeConfig,
     },
     utils,
 };
@@ -48,6 +49,18 @@ pub struct ApiConfig {
     /// Enables transaction simulation
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
+    /// Enables the server-sent event stream of committed transactions
+    #[serde(default = "default_disabled")]
+    pub transaction_stream_enabled: bool,
+    /// Maximum number of transaction streams that can be open at the same time
+    pub max_concurrent_transaction_streams: usize,
+    /// Interval in milliseconds at which open transaction streams check for newly committed transactions
+    pub transaction_stream_poll_interval_ms: u64,
+    /// Enables the API listing the content of mempool, for operators to inspect it
+    #[serde(default = "default_disabled")]
+    pub mempool_inspection_enabled: bool,
+    /// Maximum number of transactions returned by the mempool inspection API
+    pub max_mempool_inspection_page_size: u16,
     /// Maximum number of transactions that can be sent with the Batch submit API
     pub max_submit_transaction_batch_size: usize,
     /// Maximum page size for transaction paginated APIs
@@ -72,6 +85,8 @@ pub struct ApiConfig {
     pub runtime_worker_multiplier: usize,
     /// Configs for computing unit gas price estimation
     pub gas_estimation: GasEstimationConfig,
+    /// Configs for the per-client rate limiting of the requests
+    pub rate_limit: ApiRateLimitConfig,
 }
 
 pub const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -81,6 +96,9 @@ pub const DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE: usize = 10;
 pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
 pub const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 pub const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
+pub const DEFAULT_TRANSACTION_STREAM_POLL_INTERVAL_MS: u64 = 100;
+pub const DEFAULT_MAX_CONCURRENT_TRANSACTION_STREAMS: usize = 100;
+pub const DEFAULT_MAX_MEMPOOL_INSPECTION_PAGE_SIZE: u16 = 1000;
 pub const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000; // We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
 
 fn default_enabled() -> bool {
@@ -107,6 +125,11 @@ impl Default for ApiConfig {
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
             transaction_simulation_enabled: default_enabled(),
+            transaction_stream_enabled: default_disabled(),
+            max_concurrent_transaction_streams: DEFAULT_MAX_CONCURRENT_TRANSACTION_STREAMS,
+            transaction_stream_poll_interval_ms: DEFAULT_TRANSACTION_STREAM_POLL_INTERVAL_MS,
+            mempool_inspection_enabled: default_disabled(),
+            max_mempool_inspection_page_size: DEFAULT_MAX_MEMPOOL_INSPECTION_PAGE_SIZE,
             max_submit_transaction_batch_size: DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE,
             max_transactions_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_events_page_size: DEFAULT_MAX_PAGE_SIZE,
@@ -116,6 +139,7 @@ impl Default for ApiConfig {
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
+            rate_limit: ApiRateLimitConfig::default(),
         }
     }
 }
@@ -163,7 +187,27 @@ impl ConfigSanitizer for ApiConfig {
             ));
         }
 
+        // Validate the transaction stream properties
+        if api_config.transaction_stream_enabled
+            && api_config.transaction_stream_poll_interval_ms == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "transaction_stream_poll_interval_ms must be greater than 0!".into(),
+            ));
+        }
+
+        // Validate the mempool inspection properties
+        if api_config.mempool_inspection_enabled && api_config.max_mempool_inspection_page_size == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "max_mempool_inspection_page_size must be greater than 0!".into(),
+            ));
+        }
+
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
+        ApiRateLimitConfig::sanitize(node_config, node_type, chain_id)?;
 
         Ok(())
     }
@@ -227,4 +271,44 @@ mod tests {
             .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_stream_poll_interval() {
+        // Create a node config with an invalid stream poll interval
+        let mut node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                transaction_stream_enabled: true,
+                transaction_stream_poll_interval_ms: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because
+        // the stream poll interval is invalid.
+        let error = ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_mempool_inspection_page_size() {
+        // Create a node config with an invalid mempool inspection page size
+        let mut node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                mempool_inspection_enabled: true,
+                max_mempool_inspection_page_size: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because
+        // the mempool inspection page size is invalid.
+        let error = ApiConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
@@ -0,0 +1,268 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub const DEFAULT_API_KEY_HEADER: &str = "x-aptos-api-key";
+
+/// Configuration of the per-client rate limiting of the REST API. Clients are identified by
+/// the API key they send, or by their IP address if they don't send a known key.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiRateLimitConfig {
+    /// Enables the rate limiting of the requests
+    pub enabled: bool,
+    /// Name of the header holding the API key of a request
+    pub api_key_header: String,
+    /// Uses the last address of the `X-Forwarded-For` header as the IP address of a client,
+    /// which should only be enabled when the API is behind a trusted proxy (e.g. a load balancer)
+    pub use_x_forwarded_for: bool,
+    /// Initial percentage of the buckets of a new client to be filled
+    pub initial_bucket_fill_percentage: u8,
+    /// Quota of each IP address sending requests without an API key
+    pub ip_quota: ApiQuotaConfig,
+    /// The API keys, each with its own quota
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+impl Default for ApiRateLimitConfig {
+    fn default() -> ApiRateLimitConfig {
+        ApiRateLimitConfig {
+            enabled: false,
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            use_x_forwarded_for: false,
+            initial_bucket_fill_percentage: 100,
+            ip_quota: ApiQuotaConfig::default(),
+            api_keys: vec![],
+        }
+    }
+}
+
+/// Token bucket sizes and fill rates (in requests per second) of a client. Expensive requests
+/// (view function calls, transaction simulations and batch submissions) have their own budget,
+/// and don't use the budget of the other requests.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiQuotaConfig {
+    /// Maximum number of requests that can be sent in a burst
+    pub bucket_size: usize,
+    /// Number of requests per second that can be sent
+    pub bucket_rate: usize,
+    /// Maximum number of expensive requests that can be sent in a burst
+    pub expensive_bucket_size: usize,
+    /// Number of expensive requests per second that can be sent
+    pub expensive_bucket_rate: usize,
+}
+
+impl Default for ApiQuotaConfig {
+    fn default() -> ApiQuotaConfig {
+        ApiQuotaConfig {
+            bucket_size: 200,
+            bucket_rate: 100,
+            expensive_bucket_size: 20,
+            expensive_bucket_rate: 10,
+        }
+    }
+}
+
+/// An API key, with the quota of the clients sending it
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Name of the key, used in the metrics and logs instead of the key itself
+    pub name: String,
+    /// The key sent by the clients
+    pub key: String,
+    /// Quota shared by all the clients sending the key
+    #[serde(default)]
+    pub quota: ApiQuotaConfig,
+}
+
+impl ConfigSanitizer for ApiRateLimitConfig {
+    fn sanitize(
+        node_config: &mut NodeConfig,
+        _node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let rate_limit_config = &node_config.api.rate_limit;
+
+        // If rate limiting is disabled, we don't need to do anything
+        if !rate_limit_config.enabled {
+            return Ok(());
+        }
+
+        let api_key_header = &rate_limit_config.api_key_header;
+        if api_key_header.is_empty()
+            || !api_key_header
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "api_key_header {:?} is not a valid header name",
+                    api_key_header
+                ),
+            ));
+        }
+        if rate_limit_config.initial_bucket_fill_percentage > 100 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "initial_bucket_fill_percentage {} must be <= 100",
+                    rate_limit_config.initial_bucket_fill_percentage
+                ),
+            ));
+        }
+
+        // Validate the quotas, and that the keys can be told apart
+        verify_quota(&sanitizer_name, "ip_quota", &rate_limit_config.ip_quota)?;
+        let mut names = HashSet::new();
+        let mut keys = HashSet::new();
+        for api_key in &rate_limit_config.api_keys {
+            if api_key.name.is_empty() || api_key.key.is_empty() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The name and key of an API key must not be empty!".into(),
+                ));
+            }
+            if !names.insert(&api_key.name) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!("Duplicate API key name: {}", api_key.name),
+                ));
+            }
+            if !keys.insert(&api_key.key) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!("The API key named {} is already used", api_key.name),
+                ));
+            }
+            verify_quota(&sanitizer_name, &api_key.name, &api_key.quota)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Verifies that the buckets of a quota can be created
+fn verify_quota(
+    sanitizer_name: &str,
+    quota_name: &str,
+    quota: &ApiQuotaConfig,
+) -> Result<(), Error> {
+    for (size, rate) in [
+        (quota.bucket_size, quota.bucket_rate),
+        (quota.expensive_bucket_size, quota.expensive_bucket_rate),
+    ] {
+        if rate == 0 || size < rate {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "The bucket rates of {} must be > 0 and <= the bucket sizes, got size {} and rate {}",
+                    quota_name, size, rate
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApiConfig;
+
+    fn node_config_with_rate_limit(rate_limit: ApiRateLimitConfig) -> NodeConfig {
+        NodeConfig {
+            api: ApiConfig {
+                rate_limit,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn api_key(name: &str, key: &str) -> ApiKeyConfig {
+        ApiKeyConfig {
+            name: name.into(),
+            key: key.into(),
+            quota: ApiQuotaConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_sanitize_valid_rate_limit() {
+        // Create a node config with rate limiting enabled
+        let mut node_config = node_config_with_rate_limit(ApiRateLimitConfig {
+            enabled: true,
+            api_keys: vec![api_key("indexer", "key1"), api_key("wallet", "key2")],
+            ..Default::default()
+        });
+
+        // Sanitize the config and verify that it succeeds
+        ApiRateLimitConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_invalid_quota() {
+        // Create a node config with a fill rate larger than the bucket size
+        let mut node_config = node_config_with_rate_limit(ApiRateLimitConfig {
+            enabled: true,
+            ip_quota: ApiQuotaConfig {
+                expensive_bucket_size: 1,
+                expensive_bucket_rate: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        // Sanitize the config and verify that it fails because the quota is invalid
+        let error = ApiRateLimitConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the quota isn't checked if rate limiting is disabled
+        node_config.api.rate_limit.enabled = false;
+        ApiRateLimitConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_duplicate_api_keys() {
+        // Create a node config with two names for the same key
+        let mut node_config = node_config_with_rate_limit(ApiRateLimitConfig {
+            enabled: true,
+            api_keys: vec![api_key("indexer", "key"), api_key("wallet", "key")],
+            ..Default::default()
+        });
+
+        // Sanitize the config and verify that it fails because the key is duplicated
+        let error = ApiRateLimitConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+}
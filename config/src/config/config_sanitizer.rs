@@ -5,8 +5,8 @@ use crate::config::{
     node_config_loader::NodeType,
     utils::{are_failpoints_enabled, get_config_name},
     ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig,
-    IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig, NodeConfig,
-    PeerMonitoringServiceConfig, StateSyncConfig, StorageConfig,
+    IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig, NetworkConfig,
+    NodeConfig, PeerMonitoringServiceConfig, StateSyncConfig, StorageConfig, TransportProtocol,
 };
 use aptos_types::chain_id::ChainId;
 use std::collections::HashSet;
@@ -144,6 +144,9 @@ fn sanitize_fullnode_network_configs(
             ));
         }
 
+        // Verify that the listen address matches the transport
+        sanitize_network_transport(&sanitizer_name, fullnode_network_config)?;
+
         // Prepare the network id
         fullnode_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -194,6 +197,9 @@ fn sanitize_validator_network_config(
             ));
         }
 
+        // Verify that the listen address matches the transport
+        sanitize_network_transport(&sanitizer_name, validator_network_config)?;
+
         // Prepare the network id
         validator_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -201,10 +207,55 @@ fn sanitize_validator_network_config(
     Ok(())
 }
 
+/// Sanitize the listen address and proxy protocol of the network config
+/// according to its transport protocol
+fn sanitize_network_transport(
+    sanitizer_name: &str,
+    network_config: &NetworkConfig,
+) -> Result<(), Error> {
+    let network_id = network_config.network_id;
+    let listen_address = &network_config.listen_address;
+    match network_config.transport_protocol {
+        TransportProtocol::Tcp => {
+            if listen_address.is_quic_addr() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name.to_string(),
+                    format!(
+                        "The listen address of the {} network is a QUIC address ({}), but the transport protocol is TCP!",
+                        network_id, listen_address
+                    ),
+                ));
+            }
+        },
+        TransportProtocol::Quic => {
+            if !listen_address.is_quic_addr() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name.to_string(),
+                    format!(
+                        "The transport protocol of the {} network is QUIC, but the listen address ({}) is not a QUIC address!",
+                        network_id, listen_address
+                    ),
+                ));
+            }
+            if network_config.enable_proxy_protocol {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name.to_string(),
+                    format!(
+                        "Proxy protocol is not supported by the QUIC transport of the {} network!",
+                        network_id
+                    ),
+                ));
+            }
+        },
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{config::NetworkConfig, network_id::NetworkId};
+    use crate::network_id::NetworkId;
 
     #[test]
     fn test_sanitize_missing_pfn_network_configs() {
@@ -372,4 +423,79 @@ mod tests {
         .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_network_transport_mismatch() {
+        // Create a fullnode config that listens on a QUIC address over TCP
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                listen_address: "/ip4/0.0.0.0/udp/6182/quic".parse().unwrap(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a validator config that uses QUIC but listens on a TCP address
+        let mut node_config = NodeConfig {
+            validator_network: Some(NetworkConfig {
+                network_id: NetworkId::Validator,
+                transport_protocol: TransportProtocol::Quic,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_validator_network_config(
+            &mut node_config,
+            NodeType::Validator,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_quic_proxy_protocol() {
+        // Create a validator config that enables proxy protocol over QUIC
+        let mut node_config = NodeConfig {
+            validator_network: Some(NetworkConfig {
+                network_id: NetworkId::Validator,
+                listen_address: "/ip4/0.0.0.0/udp/6180/quic".parse().unwrap(),
+                transport_protocol: TransportProtocol::Quic,
+                enable_proxy_protocol: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_validator_network_config(
+            &mut node_config,
+            NodeType::Validator,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable proxy protocol and verify that the config is valid
+        let validator_network = node_config.validator_network.as_mut().unwrap();
+        validator_network.enable_proxy_protocol = false;
+        sanitize_validator_network_config(
+            &mut node_config,
+            NodeType::Validator,
+            ChainId::testnet(),
+        )
+        .unwrap();
+    }
 }
@@ -52,6 +52,9 @@ pub struct MempoolConfig {
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// Minimum increase of the gas unit price (in percent) for a transaction to replace the
+    /// pending transaction with the same sender and sequence number.
+    pub replace_by_fee_min_bump_percentage: u64,
 }
 
 impl Default for MempoolConfig {
@@ -75,6 +78,7 @@ impl Default for MempoolConfig {
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            replace_by_fee_min_bump_percentage: 10,
         }
     }
 }
@@ -82,6 +82,10 @@ pub struct NetworkConfig {
     // TODO: Add support for multiple listen/advertised addresses in config.
     /// The address that this node is listening on for new connections.
     pub listen_address: NetworkAddress,
+    /// The transport used to connect to peers. The listen address must use the
+    /// same transport (e.g., "/ip4/0.0.0.0/udp/6180/quic" for QUIC), as should
+    /// the addresses advertised by the other peers on this network.
+    pub transport_protocol: TransportProtocol,
     /// Select this to enforce that both peers should authenticate each other, otherwise
     /// authentication only occurs for outgoing connections.
     pub mutual_authentication: bool,
@@ -135,6 +139,7 @@ impl NetworkConfig {
             discovery_methods: Vec::new(),
             identity: Identity::None,
             listen_address: "/ip4/0.0.0.0/tcp/6180".parse().unwrap(),
+            transport_protocol: TransportProtocol::default(),
             mutual_authentication,
             network_id,
             runtime_threads: None,
@@ -320,6 +325,23 @@ impl NetworkConfig {
     }
 }
 
+/// The transport used to connect to the peers of a network
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransportProtocol {
+    /// All messages to a peer are sent in order over a single TCP stream
+    Tcp,
+    /// QUIC over UDP, where each RPC is sent on its own stream (avoiding
+    /// head-of-line blocking under packet loss)
+    Quic,
+}
+
+impl Default for TransportProtocol {
+    fn default() -> Self {
+        TransportProtocol::Tcp
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DiscoveryMethod {
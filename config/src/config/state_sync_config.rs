@@ -9,6 +9,7 @@ use crate::config::{
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::PathBuf;
 
 // The maximum message size per state sync message
 const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024; /* 4 MiB */
@@ -23,7 +24,7 @@ const MAX_TRANSACTION_OUTPUT_CHUNK_SIZE: u64 = 1000;
 const MAX_CONCURRENT_REQUESTS: u64 = 6;
 const MAX_CONCURRENT_STATE_REQUESTS: u64 = 6;
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StateSyncConfig {
     pub data_streaming_service: DataStreamingServiceConfig,
@@ -44,6 +45,9 @@ pub enum BootstrappingMode {
     ExecuteTransactionsFromGenesis,
     /// Executes transactions or applies outputs from genesis (whichever is faster)
     ExecuteOrApplyFromGenesis,
+    /// Restores the epoch endings, the latest state snapshot and the transactions
+    /// from a backup storage (before syncing the rest from peers)
+    RestoreFromBackup,
 }
 
 impl BootstrappingMode {
@@ -57,6 +61,7 @@ impl BootstrappingMode {
                 "execute_transactions_from_genesis"
             },
             BootstrappingMode::ExecuteOrApplyFromGenesis => "execute_or_apply_from_genesis",
+            BootstrappingMode::RestoreFromBackup => "restore_from_backup",
         }
     }
 }
@@ -86,9 +91,11 @@ impl ContinuousSyncingMode {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StateSyncDriverConfig {
+    /// The backup to restore from (when bootstrapping from a backup storage)
+    pub backup_restore: BackupRestoreConfig,
     /// The mode by which to bootstrap
     pub bootstrapping_mode: BootstrappingMode,
     /// The maximum time taken to process a commit notification
@@ -122,6 +129,7 @@ pub struct StateSyncDriverConfig {
 impl Default for StateSyncDriverConfig {
     fn default() -> Self {
         Self {
+            backup_restore: BackupRestoreConfig::default(),
             bootstrapping_mode: BootstrappingMode::ApplyTransactionOutputsFromGenesis,
             commit_notification_timeout_ms: 5000,
             continuous_syncing_mode: ContinuousSyncingMode::ApplyTransactionOutputs,
@@ -139,6 +147,42 @@ impl Default for StateSyncDriverConfig {
     }
 }
 
+/// The config for restoring a node from a backup storage (i.e., when the
+/// bootstrapping mode is `RestoreFromBackup`).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackupRestoreConfig {
+    /// The backup storage to restore from
+    pub backup_storage: Option<BackupStorageConfig>,
+    /// The maximum number of concurrent backup file downloads
+    pub concurrent_downloads: usize,
+    /// The directory in which to cache the backup metadata (defaults to a temporary directory)
+    pub metadata_cache_dir: Option<PathBuf>,
+    /// The number of threads used to replay the transactions after the state snapshot
+    pub replay_concurrency_level: usize,
+}
+
+impl Default for BackupRestoreConfig {
+    fn default() -> Self {
+        Self {
+            backup_storage: None,
+            concurrent_downloads: 8,
+            metadata_cache_dir: None,
+            replay_concurrency_level: 4,
+        }
+    }
+}
+
+/// The backup storage holding the backups to restore from
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum BackupStorageConfig {
+    /// Backups stored in a local directory
+    LocalFs { dir: PathBuf },
+    /// Backups accessed through the commands in the given command adapter config file
+    CommandAdapter { config_path: PathBuf },
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageServiceConfig {
@@ -286,10 +330,43 @@ impl Default for AptosDataClientConfig {
 
 impl ConfigSanitizer for StateSyncConfig {
     fn sanitize(
-        _node_config: &mut NodeConfig,
+        node_config: &mut NodeConfig,
+        node_type: NodeType,
+        chain_id: ChainId,
+    ) -> Result<(), Error> {
+        // Sanitize the driver config
+        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)
+    }
+}
+
+impl ConfigSanitizer for StateSyncDriverConfig {
+    fn sanitize(
+        node_config: &mut NodeConfig,
         _node_type: NodeType,
         _chain_id: ChainId,
     ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let state_sync_driver_config = &node_config.state_sync.state_sync_driver;
+
+        // Verify that a backup storage is given when restoring from a backup
+        if state_sync_driver_config.bootstrapping_mode == BootstrappingMode::RestoreFromBackup {
+            let backup_restore_config = &state_sync_driver_config.backup_restore;
+            if backup_restore_config.backup_storage.is_none() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The backup storage must be set when bootstrapping from a backup!".into(),
+                ));
+            }
+            if backup_restore_config.concurrent_downloads == 0
+                || backup_restore_config.replay_concurrency_level == 0
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The backup restore concurrency levels must be > 0!".into(),
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -473,6 +550,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sanitize_restore_from_backup() {
+        // Create a node config that restores from a backup without a backup storage
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    bootstrapping_mode: BootstrappingMode::RestoreFromBackup,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            StateSyncConfig::sanitize(&mut node_config, NodeType::PublicFullnode, ChainId::test())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Set the backup storage, and verify that the config is now valid
+        node_config
+            .state_sync
+            .state_sync_driver
+            .backup_restore
+            .backup_storage = Some(BackupStorageConfig::LocalFs {
+            dir: PathBuf::from("/opt/aptos/backups"),
+        });
+        StateSyncConfig::sanitize(&mut node_config, NodeType::PublicFullnode, ChainId::test())
+            .unwrap();
+    }
+
     #[test]
     fn test_optimize_prefetcher_mainnet_validator() {
         // Create a default node config
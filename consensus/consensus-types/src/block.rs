@@ -8,6 +8,7 @@ use crate::{
     quorum_cert::QuorumCert,
 };
 use anyhow::{bail, ensure, format_err};
+use aptos_bitvec::BitVec;
 use aptos_crypto::{bls12381, hash::CryptoHash, HashValue};
 use aptos_infallible::duration_since_epoch;
 use aptos_types::{
@@ -203,6 +204,39 @@ impl Block {
         }
     }
 
+    /// Blocks ordered by DAG consensus are not signed: every validator derives the same block
+    /// from its local DAG.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_for_dag(
+        epoch: u64,
+        round: Round,
+        timestamp_usecs: u64,
+        payload: Payload,
+        author: Author,
+        failed_authors: Vec<(Round, Author)>,
+        parent_block_info: BlockInfo,
+        parents_bitvec: BitVec,
+        node_digests: Vec<HashValue>,
+    ) -> Self {
+        let block_data = BlockData::new_for_dag(
+            epoch,
+            round,
+            timestamp_usecs,
+            payload,
+            author,
+            failed_authors,
+            parent_block_info,
+            parents_bitvec,
+            node_digests,
+        );
+
+        Block {
+            id: block_data.hash(),
+            block_data,
+            signature: None,
+        }
+    }
+
     pub fn new_proposal(
         payload: Payload,
         round: Round,
@@ -250,6 +284,7 @@ impl Block {
         match self.block_data.block_type() {
             BlockType::Genesis => bail!("We should not accept genesis from others"),
             BlockType::NilBlock { .. } => self.quorum_cert().verify(validator),
+            BlockType::DAGBlock { .. } => bail!("We should not accept DAG blocks from others"),
             BlockType::Proposal { author, .. } => {
                 let signature = self
                     .signature
@@ -7,6 +7,7 @@ use crate::{
     quorum_cert::QuorumCert,
     vote_data::VoteData,
 };
+use aptos_bitvec::BitVec;
 use aptos_crypto::hash::HashValue;
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use aptos_types::{
@@ -42,6 +43,18 @@ pub enum BlockType {
     /// from the previous epoch.  The genesis block is used as the first root block of the
     /// BlockTree for all epochs.
     Genesis,
+    /// A block ordered by DAG consensus: the merged payload of the causal history of a committed
+    /// anchor node. DAG blocks are not signed, their quorum certificate is a placeholder that
+    /// carries the parent block and the authors of the anchor's parents.
+    DAGBlock {
+        /// Author of the anchor node
+        author: Author,
+        /// Anchors of the previous anchor rounds that were skipped by the ordering rule.
+        failed_authors: Vec<(Round, Author)>,
+        payload: Payload,
+        /// Digests of the ordered nodes, in the order their payloads were merged.
+        node_digests: Vec<HashValue>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
@@ -79,10 +92,9 @@ pub struct BlockData {
 
 impl BlockData {
     pub fn author(&self) -> Option<Author> {
-        if let BlockType::Proposal { author, .. } = self.block_type {
-            Some(author)
-        } else {
-            None
+        match self.block_type {
+            BlockType::Proposal { author, .. } | BlockType::DAGBlock { author, .. } => Some(author),
+            _ => None,
         }
     }
 
@@ -99,10 +111,11 @@ impl BlockData {
     }
 
     pub fn payload(&self) -> Option<&Payload> {
-        if let BlockType::Proposal { payload, .. } = &self.block_type {
-            Some(payload)
-        } else {
-            None
+        match &self.block_type {
+            BlockType::Proposal { payload, .. } | BlockType::DAGBlock { payload, .. } => {
+                Some(payload)
+            },
+            _ => None,
         }
     }
 
@@ -126,6 +139,10 @@ impl BlockData {
         matches!(self.block_type, BlockType::NilBlock { .. })
     }
 
+    pub fn is_dag_block(&self) -> bool {
+        matches!(self.block_type, BlockType::DAGBlock { .. })
+    }
+
     /// the list of consecutive proposers from the immediately preceeding
     /// rounds that didn't produce a successful block
     pub fn failed_authors(&self) -> Option<&Vec<(Round, Author)>> {
@@ -134,6 +151,9 @@ impl BlockData {
                 ref failed_authors, ..
             } => Some(failed_authors),
             BlockType::NilBlock { ref failed_authors } => Some(failed_authors),
+            BlockType::DAGBlock {
+                ref failed_authors, ..
+            } => Some(failed_authors),
             BlockType::Genesis => None,
         }
     }
@@ -232,6 +252,43 @@ impl BlockData {
         }
     }
 
+    /// Creates the block for an anchor ordered by DAG consensus. The placeholder quorum certificate
+    /// certifies `parent_block_info` so that the block chains to the previously ordered one, and
+    /// its signature bitvec records the authors of the anchor's parents, which are reported as
+    /// the voters in the block metadata.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_for_dag(
+        epoch: u64,
+        round: Round,
+        timestamp_usecs: u64,
+        payload: Payload,
+        author: Author,
+        failed_authors: Vec<(Round, Author)>,
+        parent_block_info: BlockInfo,
+        parents_bitvec: BitVec,
+        node_digests: Vec<HashValue>,
+    ) -> Self {
+        let quorum_cert = QuorumCert::new(
+            VoteData::new(parent_block_info.clone(), parent_block_info.clone()),
+            LedgerInfoWithSignatures::new(
+                LedgerInfo::new(parent_block_info, HashValue::zero()),
+                AggregateSignature::new(parents_bitvec, None),
+            ),
+        );
+        Self {
+            epoch,
+            round,
+            timestamp_usecs,
+            quorum_cert,
+            block_type: BlockType::DAGBlock {
+                author,
+                failed_authors,
+                payload,
+                node_digests,
+            },
+        }
+    }
+
     /// It's a reconfiguration suffix block if the parent block's executed state indicates next epoch.
     pub fn is_reconfiguration_suffix(&self) -> bool {
         self.quorum_cert.certified_block().has_reconfiguration()
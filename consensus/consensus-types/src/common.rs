@@ -90,6 +90,13 @@ impl ProofWithData {
             status: Arc::new(Mutex::new(None)),
         }
     }
+
+    /// Merges the proofs of `other`. The data status is reset, as the transactions of the merged
+    /// proofs have to be requested again.
+    pub fn extend(&mut self, other: ProofWithData) {
+        self.proofs.extend(other.proofs);
+        self.status = Arc::new(Mutex::new(None));
+    }
 }
 
 /// The payload in block.
@@ -130,6 +137,21 @@ impl Payload {
         matches!(self, Payload::DirectMempool(_))
     }
 
+    /// Appends the content of `other`, which must be of the same kind of payload.
+    pub fn extend(self, other: Payload) -> Self {
+        match (self, other) {
+            (Payload::DirectMempool(mut txns), Payload::DirectMempool(other_txns)) => {
+                txns.extend(other_txns);
+                Payload::DirectMempool(txns)
+            },
+            (Payload::InQuorumStore(mut proofs), Payload::InQuorumStore(other_proofs)) => {
+                proofs.extend(other_proofs);
+                Payload::InQuorumStore(proofs)
+            },
+            (_, _) => unreachable!("Cannot extend payloads of different kinds"),
+        }
+    }
+
     /// This is computationally expensive on the first call
     pub fn size(&self) -> usize {
         match self {
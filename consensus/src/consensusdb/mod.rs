@@ -18,8 +18,14 @@ use anyhow::Result;
 use aptos_consensus_types::{block::Block, quorum_cert::QuorumCert};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
-use aptos_schemadb::{Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME};
-use schema::{BLOCK_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME};
+use aptos_schemadb::{
+    schema::Schema, Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
+pub use schema::dag::{CertifiedNodeSchema, DagVoteSchema, NodeSchema};
+use schema::{
+    BLOCK_CF_NAME, CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, NODE_CF_NAME, QC_CF_NAME,
+    SINGLE_ENTRY_CF_NAME,
+};
 use std::{collections::HashMap, iter::Iterator, path::Path, time::Instant};
 
 /// The name of the consensus db file
@@ -52,6 +58,9 @@ impl ConsensusDB {
             BLOCK_CF_NAME,
             QC_CF_NAME,
             SINGLE_ENTRY_CF_NAME,
+            NODE_CF_NAME,
+            DAG_VOTE_CF_NAME,
+            CERTIFIED_NODE_CF_NAME,
         ];
 
         let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
@@ -187,4 +196,26 @@ impl ConsensusDB {
         iter.seek_to_first();
         Ok(iter.collect::<Result<HashMap<HashValue, QuorumCert>>>()?)
     }
+
+    pub fn put<S: Schema>(&self, key: &S::Key, value: &S::Value) -> Result<(), DbError> {
+        let batch = SchemaBatch::new();
+        batch.put::<S>(key, value)?;
+        self.commit(batch)
+    }
+
+    pub fn delete<S: Schema>(&self, keys: Vec<S::Key>) -> Result<(), DbError> {
+        let batch = SchemaBatch::new();
+        keys.iter().try_for_each(|key| batch.delete::<S>(key))?;
+        self.commit(batch)
+    }
+
+    pub fn get<S: Schema>(&self, key: &S::Key) -> Result<Option<S::Value>, DbError> {
+        Ok(self.db.get::<S>(key)?)
+    }
+
+    pub fn get_all<S: Schema>(&self) -> Result<Vec<(S::Key, S::Value)>, DbError> {
+        let mut iter = self.db.iter::<S>(ReadOptions::default())?;
+        iter.seek_to_first();
+        Ok(iter.collect::<Result<Vec<(S::Key, S::Value)>>>()?)
+    }
 }
@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the physical storage schemas for DAG consensus.
+//!
+//! The node of the latest round proposed by this validator.
+//! ```text
+//! |<--key-->|<--value-->|
+//! |   ()    |    node   |
+//! ```
+//!
+//! The votes this validator sent, identified by the node id.
+//! ```text
+//! |<---key--->|<--value-->|
+//! |  node_id  |    vote   |
+//! ```
+//!
+//! The certified nodes in the DAG, identified by the node digest.
+//! ```text
+//! |<--key-->|<-----value---->|
+//! |  digest | certified_node |
+//! ```
+
+use super::{CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, NODE_CF_NAME};
+use crate::dag::{CertifiedNode, Node, NodeId, Vote};
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use aptos_schemadb::{
+    schema::{KeyCodec, Schema, ValueCodec},
+    ColumnFamilyName,
+};
+
+#[derive(Debug)]
+pub struct NodeSchema;
+
+impl Schema for NodeSchema {
+    type Key = ();
+    type Value = Node;
+
+    const COLUMN_FAMILY_NAME: ColumnFamilyName = NODE_CF_NAME;
+}
+
+impl KeyCodec<NodeSchema> for () {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl ValueCodec<NodeSchema> for Node {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct DagVoteSchema;
+
+impl Schema for DagVoteSchema {
+    type Key = NodeId;
+    type Value = Vote;
+
+    const COLUMN_FAMILY_NAME: ColumnFamilyName = DAG_VOTE_CF_NAME;
+}
+
+impl KeyCodec<DagVoteSchema> for NodeId {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl ValueCodec<DagVoteSchema> for Vote {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct CertifiedNodeSchema;
+
+impl Schema for CertifiedNodeSchema {
+    type Key = HashValue;
+    type Value = CertifiedNode;
+
+    const COLUMN_FAMILY_NAME: ColumnFamilyName = CERTIFIED_NODE_CF_NAME;
+}
+
+impl KeyCodec<CertifiedNodeSchema> for HashValue {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(HashValue::from_slice(data)?)
+    }
+}
+
+impl ValueCodec<CertifiedNodeSchema> for CertifiedNode {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,46 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::dag::NodeCertificate;
+use aptos_consensus_types::common::Payload;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use aptos_types::{aggregate_signature::AggregateSignature, validator_signer::ValidatorSigner};
+
+fn new_node(signer: &ValidatorSigner) -> Node {
+    Node::new(1, 1, signer.author(), 0, Payload::empty(false), vec![])
+}
+
+#[test]
+fn test_encode_decode() {
+    let signer = ValidatorSigner::random(None);
+    let node = new_node(&signer);
+    assert_encode_decode::<NodeSchema>(&(), &node);
+
+    let vote = Vote::new(node.metadata(), &signer).unwrap();
+    assert_encode_decode::<DagVoteSchema>(&node.id(), &vote);
+
+    let certified_node = CertifiedNode::new(
+        node.clone(),
+        NodeCertificate::new(node.metadata(), AggregateSignature::empty()),
+    );
+    assert_encode_decode::<CertifiedNodeSchema>(&node.digest(), &certified_node);
+}
+
+mod node {
+    use super::*;
+
+    test_no_panic_decoding!(NodeSchema);
+}
+
+mod dag_vote {
+    use super::*;
+
+    test_no_panic_decoding!(DagVoteSchema);
+}
+
+mod certified_node {
+    use super::*;
+
+    test_no_panic_decoding!(CertifiedNodeSchema);
+}
@@ -0,0 +1,158 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::{dag_store::Dag, storage::DAGStorage, types::CertifiedNode},
+    experimental::buffer_manager::OrderedBlocks,
+    payload_manager::PayloadManager,
+};
+use anyhow::{anyhow, ensure};
+use aptos_bitvec::BitVec;
+use aptos_consensus_types::{
+    block::Block,
+    common::{Author, Payload, Round},
+    executed_block::ExecutedBlock,
+};
+use aptos_crypto::{hash::ACCUMULATOR_PLACEHOLDER_HASH, HashValue};
+use aptos_executor_types::StateComputeResult;
+use aptos_infallible::RwLock;
+use aptos_logger::error;
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+};
+use async_trait::async_trait;
+use futures_channel::mpsc::UnboundedSender;
+use std::sync::Arc;
+
+/// Receives the causal histories ordered by the `OrderRule`.
+#[async_trait]
+pub trait Notifier: Send {
+    /// `ordered_nodes` ends with the anchor, `failed_authors` contains the skipped anchors.
+    async fn send_ordered_nodes(
+        &mut self,
+        ordered_nodes: Vec<Arc<CertifiedNode>>,
+        failed_authors: Vec<(Round, Author)>,
+    ) -> anyhow::Result<()>;
+}
+
+/// Turns each ordered causal history into a block that is sent to the buffer manager for
+/// execution, and garbage collects the DAG once the block is committed.
+pub struct NotifierAdapter {
+    executor_channel: UnboundedSender<OrderedBlocks>,
+    storage: Arc<dyn DAGStorage>,
+    dag: Arc<RwLock<Dag>>,
+    parent_block_info: BlockInfo,
+    epoch_state: Arc<EpochState>,
+    payload_manager: Arc<PayloadManager>,
+    max_failed_authors_to_store: usize,
+    dag_window_size: Round,
+}
+
+impl NotifierAdapter {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        executor_channel: UnboundedSender<OrderedBlocks>,
+        storage: Arc<dyn DAGStorage>,
+        dag: Arc<RwLock<Dag>>,
+        parent_block_info: BlockInfo,
+        epoch_state: Arc<EpochState>,
+        payload_manager: Arc<PayloadManager>,
+        max_failed_authors_to_store: usize,
+        dag_window_size: Round,
+    ) -> Self {
+        Self {
+            executor_channel,
+            storage,
+            dag,
+            parent_block_info,
+            epoch_state,
+            payload_manager,
+            max_failed_authors_to_store,
+            dag_window_size,
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for NotifierAdapter {
+    async fn send_ordered_nodes(
+        &mut self,
+        ordered_nodes: Vec<Arc<CertifiedNode>>,
+        mut failed_authors: Vec<(Round, Author)>,
+    ) -> anyhow::Result<()> {
+        let anchor = ordered_nodes
+            .last()
+            .ok_or_else(|| anyhow!("no ordered nodes"))?
+            .clone();
+        ensure!(
+            anchor.epoch() == self.epoch_state.epoch,
+            "anchor from a different epoch"
+        );
+
+        let mut payload = Payload::empty(!anchor.payload().is_direct());
+        let mut node_digests = vec![];
+        for node in &ordered_nodes {
+            payload = payload.extend(node.payload().clone());
+            node_digests.push(node.digest());
+        }
+
+        let validators = self.epoch_state.verifier.get_ordered_account_addresses();
+        let mut parents_bitvec = BitVec::with_num_bits(validators.len() as u16);
+        for parent in anchor.parents() {
+            if let Some(index) = validators
+                .iter()
+                .position(|author| author == parent.author())
+            {
+                parents_bitvec.set(index as u16);
+            }
+        }
+
+        let skip = failed_authors
+            .len()
+            .saturating_sub(self.max_failed_authors_to_store);
+        failed_authors.drain(..skip);
+
+        // block timestamps must be strictly increasing
+        let timestamp = anchor
+            .timestamp()
+            .max(self.parent_block_info.timestamp_usecs() + 1);
+        let block = Block::new_for_dag(
+            anchor.epoch(),
+            anchor.round(),
+            timestamp,
+            payload,
+            *anchor.author(),
+            failed_authors,
+            self.parent_block_info.clone(),
+            parents_bitvec,
+            node_digests,
+        );
+        self.payload_manager.prefetch_payload_data(&block).await;
+
+        let block_info = block.gen_block_info(*ACCUMULATOR_PLACEHOLDER_HASH, 0, None);
+        self.parent_block_info = block_info.clone();
+        let ordered_proof = LedgerInfoWithSignatures::new(
+            LedgerInfo::new(block_info, anchor.digest()),
+            AggregateSignature::empty(),
+        );
+
+        let dag = self.dag.clone();
+        let storage = self.storage.clone();
+        let lowest_round = anchor.round().saturating_sub(self.dag_window_size);
+        self.executor_channel
+            .unbounded_send(OrderedBlocks {
+                ordered_blocks: vec![ExecutedBlock::new(block, StateComputeResult::new_dummy())],
+                ordered_proof,
+                callback: Box::new(move |_, _| {
+                    let digests: Vec<HashValue> = dag.write().prune(lowest_round);
+                    if let Err(e) = storage.delete_certified_nodes(digests) {
+                        error!("Failed to delete pruned certified nodes: {:?}", e);
+                    }
+                }),
+            })
+            .map_err(|_| anyhow!("buffer manager is dropped"))
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::common::{Author, Round};
+
+/// Elects the anchor of an (odd) anchor round, whose causal history gets ordered once enough
+/// nodes of the next round link to it.
+pub trait AnchorElection: Send + Sync {
+    fn get_anchor(&self, round: Round) -> Author;
+}
+
+/// Rotates the anchor among the validators, one anchor every two rounds.
+pub struct RoundRobinAnchorElection {
+    validators: Vec<Author>,
+}
+
+impl RoundRobinAnchorElection {
+    pub fn new(validators: Vec<Author>) -> Self {
+        Self { validators }
+    }
+}
+
+impl AnchorElection for RoundRobinAnchorElection {
+    fn get_anchor(&self, round: Round) -> Author {
+        self.validators[(round / 2) as usize % self.validators.len()]
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::{
+        adapter::NotifierAdapter,
+        anchor_election::RoundRobinAnchorElection,
+        dag_driver::{DagDriver, DagPayloadConfig},
+        dag_fetcher::{DagFetcher, FetchRequestHandler},
+        dag_handler::NetworkHandler,
+        dag_store::Dag,
+        order_rule::OrderRule,
+        rb_handler::NodeBroadcastHandler,
+        reliable_broadcast::{DAGNetworkSender, ReliableBroadcast},
+        storage::DAGStorage,
+    },
+    experimental::buffer_manager::OrderedBlocks,
+    network::IncomingDAGRequest,
+    payload_manager::PayloadManager,
+    state_replication::PayloadClient,
+    util::time_service::TimeService,
+};
+use aptos_channels::aptos_channel;
+use aptos_consensus_types::{block::Block, common::Author};
+use aptos_infallible::RwLock;
+use aptos_logger::{info, warn};
+use aptos_types::{
+    block_info::BlockInfo, epoch_state::EpochState, ledger_info::LedgerInfo,
+    on_chain_config::DagConsensusConfigV1, validator_signer::ValidatorSigner,
+};
+use futures_channel::mpsc::UnboundedSender;
+use std::{sync::Arc, time::Duration};
+
+/// Timeout of the RPCs fetching missing nodes.
+const FETCH_RPC_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Creates the DAG consensus components of an epoch, recovering the DAG from `storage`.
+/// `latest_ledger_info` is the latest committed ledger info, which is either the last block
+/// ordered by the DAG in this epoch or the ledger info that ended the previous epoch.
+///
+/// The returned handler and fetcher are expected to be spawned by the caller.
+#[allow(clippy::too_many_arguments)]
+pub fn bootstrap_dag(
+    self_peer: Author,
+    signer: ValidatorSigner,
+    epoch_state: Arc<EpochState>,
+    latest_ledger_info: LedgerInfo,
+    storage: Arc<dyn DAGStorage>,
+    rb_network_sender: Arc<dyn DAGNetworkSender>,
+    time_service: Arc<dyn TimeService>,
+    payload_manager: Arc<PayloadManager>,
+    payload_client: Arc<dyn PayloadClient>,
+    executor_channel: UnboundedSender<OrderedBlocks>,
+    dag_rpc_rx: aptos_channel::Receiver<Author, IncomingDAGRequest>,
+    dag_config: DagConsensusConfigV1,
+    payload_config: DagPayloadConfig,
+    quorum_store_enabled: bool,
+) -> (NetworkHandler, DagFetcher) {
+    let validators = epoch_state.verifier.get_ordered_account_addresses();
+    let author_to_index = epoch_state.verifier.address_to_validator_index().clone();
+    let dag_window_size = dag_config.dag_ordering_causal_history_window as u64;
+
+    let (parent_block_info, committed_anchor) = if latest_ledger_info.epoch() == epoch_state.epoch {
+        (
+            latest_ledger_info.commit_info().clone(),
+            Some((
+                latest_ledger_info.round(),
+                latest_ledger_info.consensus_data_hash(),
+            )),
+        )
+    } else {
+        // the first block of the epoch extends the genesis block of the epoch
+        let genesis_id = Block::make_genesis_block_from_ledger_info(&latest_ledger_info).id();
+        (
+            BlockInfo::new(
+                latest_ledger_info.epoch() + 1,
+                0,
+                genesis_id,
+                latest_ledger_info.transaction_accumulator_hash(),
+                latest_ledger_info.version(),
+                latest_ledger_info.timestamp_usecs(),
+                None,
+            ),
+            None,
+        )
+    };
+
+    // recover the certified nodes of the epoch, the nodes of older epochs are deleted
+    let mut certified_nodes = storage.get_certified_nodes().unwrap_or_else(|e| {
+        warn!("Failed to read the certified nodes from storage: {:?}", e);
+        vec![]
+    });
+    let mut to_delete = vec![];
+    certified_nodes.retain(|(digest, node)| {
+        let retain = node.epoch() == epoch_state.epoch;
+        if !retain {
+            to_delete.push(*digest);
+        }
+        retain
+    });
+    certified_nodes.sort_by_key(|(_, node)| node.round());
+    let lowest_round =
+        committed_anchor.map_or(1, |(round, _)| round.saturating_sub(dag_window_size).max(1));
+    let mut dag = Dag::new(author_to_index, lowest_round);
+    for (digest, node) in certified_nodes {
+        if node.round() < lowest_round || dag.add_node(node).is_err() {
+            to_delete.push(digest);
+        }
+    }
+    if let Err(e) = storage.delete_certified_nodes(to_delete) {
+        warn!("Failed to delete stale certified nodes: {:?}", e);
+    }
+
+    // the nodes in the causal history of the committed anchor are already ordered
+    let lowest_unordered_anchor_round = match committed_anchor {
+        Some((round, digest)) => {
+            for node in dag.reachable(&[digest], lowest_round) {
+                dag.mark_ordered(node.digest());
+            }
+            round + 2
+        },
+        None => 1,
+    };
+    info!(
+        "Bootstrapping DAG of epoch {} at round {}, lowest unordered anchor round {}",
+        epoch_state.epoch,
+        dag.highest_round(),
+        lowest_unordered_anchor_round
+    );
+    let dag = Arc::new(RwLock::new(dag));
+
+    let notifier = NotifierAdapter::new(
+        executor_channel,
+        storage.clone(),
+        dag.clone(),
+        parent_block_info,
+        epoch_state.clone(),
+        payload_manager,
+        dag_config.max_failed_authors_to_store,
+        dag_window_size,
+    );
+    let order_rule = OrderRule::new(
+        epoch_state.clone(),
+        lowest_unordered_anchor_round,
+        dag.clone(),
+        Box::new(RoundRobinAnchorElection::new(validators.clone())),
+        Box::new(notifier),
+        dag_window_size,
+    );
+
+    let (fetched_tx, fetched_rx) = tokio::sync::mpsc::channel(100);
+    let (dag_fetcher, fetch_requester) = DagFetcher::new(
+        epoch_state.clone(),
+        rb_network_sender.clone(),
+        dag.clone(),
+        fetched_tx,
+        FETCH_RPC_TIMEOUT,
+    );
+    let fetch_requester = Arc::new(fetch_requester);
+
+    let reliable_broadcast = Arc::new(ReliableBroadcast::new(validators, rb_network_sender));
+    let dag_driver = DagDriver::new(
+        self_peer,
+        epoch_state.clone(),
+        dag.clone(),
+        payload_client,
+        reliable_broadcast,
+        time_service,
+        storage.clone(),
+        order_rule,
+        fetch_requester.clone(),
+        payload_config,
+        quorum_store_enabled,
+    );
+    let node_receiver = NodeBroadcastHandler::new(
+        dag.clone(),
+        Arc::new(signer),
+        epoch_state.clone(),
+        storage,
+        fetch_requester,
+        quorum_store_enabled,
+    );
+    let fetch_receiver = FetchRequestHandler::new(dag, epoch_state.clone());
+
+    let handler = NetworkHandler::new(
+        epoch_state,
+        dag_rpc_rx,
+        node_receiver,
+        dag_driver,
+        fetch_receiver,
+        fetched_rx,
+    );
+    (handler, dag_fetcher)
+}
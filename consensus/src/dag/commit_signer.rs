@@ -0,0 +1,51 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::experimental::signing_phase::CommitSignerProvider;
+use aptos_crypto::bls12381;
+use aptos_safety_rules::Error;
+use aptos_types::{
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_signer::ValidatorSigner,
+};
+
+/// Signs the commit votes of the blocks ordered by DAG consensus. The ordered ledger infos are
+/// derived locally from the DAG and carry no signatures, so only the consistency between the
+/// ordered and the executed ledger info is checked.
+pub struct DagCommitSigner {
+    signer: ValidatorSigner,
+}
+
+impl DagCommitSigner {
+    pub fn new(signer: ValidatorSigner) -> Self {
+        Self { signer }
+    }
+}
+
+impl CommitSignerProvider for DagCommitSigner {
+    fn sign_commit_vote(
+        &self,
+        ledger_info: LedgerInfoWithSignatures,
+        new_ledger_info: LedgerInfo,
+    ) -> Result<bls12381::Signature, Error> {
+        let old_ledger_info = ledger_info.ledger_info();
+
+        if !old_ledger_info.commit_info().is_ordered_only() {
+            return Err(Error::InvalidOrderedLedgerInfo(old_ledger_info.to_string()));
+        }
+
+        if !old_ledger_info
+            .commit_info()
+            .match_ordered_only(new_ledger_info.commit_info())
+        {
+            return Err(Error::InconsistentExecutionResult(
+                old_ledger_info.commit_info().to_string(),
+                new_ledger_info.commit_info().to_string(),
+            ));
+        }
+
+        self.signer
+            .sign(&new_ledger_info)
+            .map_err(|err| Error::SerializationError(err.to_string()))
+    }
+}
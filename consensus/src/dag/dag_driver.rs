@@ -0,0 +1,269 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::{
+        dag_fetcher::FetchRequester,
+        dag_store::Dag,
+        order_rule::OrderRule,
+        reliable_broadcast::ReliableBroadcast,
+        storage::DAGStorage,
+        types::{
+            CertificateAckState, CertifiedAck, CertifiedNode, Node, NodeMetadata, SignatureBuilder,
+        },
+    },
+    state_replication::PayloadClient,
+    util::time_service::TimeService,
+};
+use anyhow::{bail, ensure};
+use aptos_consensus_types::common::{Author, Payload, PayloadFilter, Round};
+use aptos_infallible::RwLock;
+use aptos_logger::{debug, error};
+use aptos_types::epoch_state::EpochState;
+use futures::{
+    future::{AbortHandle, Abortable},
+    FutureExt,
+};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::oneshot;
+
+/// Limits of the payload of the nodes proposed by this validator.
+#[derive(Clone, Copy, Debug)]
+pub struct DagPayloadConfig {
+    pub max_sending_txns_per_round: u64,
+    pub max_sending_size_per_round_bytes: u64,
+    pub payload_pull_max_poll_time: Duration,
+}
+
+/// Adds the certified nodes to the DAG and proposes a new node every time the DAG has enough
+/// strong links for the next round.
+pub struct DagDriver {
+    author: Author,
+    epoch_state: Arc<EpochState>,
+    dag: Arc<RwLock<Dag>>,
+    payload_client: Arc<dyn PayloadClient>,
+    reliable_broadcast: Arc<ReliableBroadcast>,
+    current_round: Round,
+    time_service: Arc<dyn TimeService>,
+    rb_abort_handle: Option<AbortHandle>,
+    storage: Arc<dyn DAGStorage>,
+    order_rule: OrderRule,
+    fetch_requester: Arc<FetchRequester>,
+    payload_config: DagPayloadConfig,
+    quorum_store_enabled: bool,
+}
+
+impl DagDriver {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        author: Author,
+        epoch_state: Arc<EpochState>,
+        dag: Arc<RwLock<Dag>>,
+        payload_client: Arc<dyn PayloadClient>,
+        reliable_broadcast: Arc<ReliableBroadcast>,
+        time_service: Arc<dyn TimeService>,
+        storage: Arc<dyn DAGStorage>,
+        order_rule: OrderRule,
+        fetch_requester: Arc<FetchRequester>,
+        payload_config: DagPayloadConfig,
+        quorum_store_enabled: bool,
+    ) -> Self {
+        let current_round = dag.read().highest_round();
+        Self {
+            author,
+            epoch_state,
+            dag,
+            payload_client,
+            reliable_broadcast,
+            current_round,
+            time_service,
+            rb_abort_handle: None,
+            storage,
+            order_rule,
+            fetch_requester,
+            payload_config,
+            quorum_store_enabled,
+        }
+    }
+
+    pub fn current_round(&self) -> Round {
+        self.current_round
+    }
+
+    /// Resumes the broadcast of the node that was pending before a restart, so that a different
+    /// node is never proposed for the same round. Otherwise enters the next round if possible.
+    pub async fn initialize(&mut self) {
+        match self.storage.get_node() {
+            Ok(Some(node))
+                if node.epoch() == self.epoch_state.epoch && node.round() >= self.current_round =>
+            {
+                self.current_round = node.round();
+                self.broadcast_node(node);
+                return;
+            },
+            Ok(_) => {},
+            Err(e) => error!("Failed to read the pending node from storage: {:?}", e),
+        }
+
+        let is_empty = {
+            let dag = self.dag.read();
+            dag.lowest_round() == dag.highest_round()
+                && dag.get_nodes_by_round(dag.highest_round()).next().is_none()
+        };
+        if is_empty {
+            self.enter_new_round(self.current_round, vec![]).await;
+        } else {
+            self.check_new_round().await;
+        }
+    }
+
+    /// Processes a certified node received from its author. Nodes with missing parents are
+    /// handed to the fetcher and added once their causal history is fetched.
+    pub async fn process(&mut self, node: CertifiedNode) -> anyhow::Result<CertifiedAck> {
+        ensure!(node.epoch() == self.epoch_state.epoch, "different epoch");
+        node.verify(&self.epoch_state.verifier)?;
+        node.payload()
+            .verify(&self.epoch_state.verifier, self.quorum_store_enabled)?;
+
+        let has_missing_parents = {
+            let dag = self.dag.read();
+            node.parents()
+                .iter()
+                .any(|parent| parent.round() >= dag.lowest_round() && !dag.exists(parent.digest()))
+        };
+        if has_missing_parents {
+            if let Err(e) = self.fetch_requester.request_for_certified_node(node) {
+                error!("Failed to fetch the causal history: {:?}", e);
+            }
+            bail!("missing parents");
+        }
+        self.add_node(node).await?;
+        Ok(CertifiedAck::new(self.epoch_state.epoch))
+    }
+
+    /// Adds a certified node whose parents are in the DAG.
+    pub async fn add_node(&mut self, node: CertifiedNode) -> anyhow::Result<()> {
+        if self.dag.read().exists(&node.digest()) {
+            return Ok(());
+        }
+        let metadata = node.metadata();
+        self.storage.save_certified_node(&node)?;
+        self.dag.write().add_node(node)?;
+        self.order_rule.process_new_node(&metadata).await?;
+        self.check_new_round().await;
+        Ok(())
+    }
+
+    async fn check_new_round(&mut self) {
+        let (highest_round, strong_links) = {
+            let dag = self.dag.read();
+            (
+                dag.highest_round(),
+                dag.get_unlinked_nodes_for_new_round(&self.epoch_state.verifier),
+            )
+        };
+        if highest_round < self.current_round {
+            return;
+        }
+        if let Some(strong_links) = strong_links {
+            self.enter_new_round(highest_round + 1, strong_links).await;
+        }
+    }
+
+    async fn enter_new_round(&mut self, new_round: Round, strong_links: Vec<NodeMetadata>) {
+        debug!("Entering DAG round {}", new_round);
+        let payload_filter = {
+            let dag = self.dag.read();
+            let payloads: Vec<Payload> = (dag.lowest_round()..=dag.highest_round())
+                .flat_map(|round| dag.get_nodes_by_round(round))
+                .map(|node| node.payload().clone())
+                .collect();
+            PayloadFilter::from(&payloads.iter().collect::<Vec<_>>())
+        };
+        let payload = match self
+            .payload_client
+            .pull_payload(
+                self.payload_config.payload_pull_max_poll_time,
+                self.payload_config.max_sending_txns_per_round,
+                self.payload_config.max_sending_size_per_round_bytes,
+                payload_filter,
+                Box::pin(async {}),
+                false,
+                0,
+                0.0,
+            )
+            .await
+        {
+            Ok(payload) => payload,
+            Err(e) => {
+                error!(
+                    "Failed to pull the payload, proposing an empty node: {:?}",
+                    e
+                );
+                Payload::empty(self.quorum_store_enabled)
+            },
+        };
+        let timestamp = self.time_service.get_current_timestamp().as_micros() as u64;
+        let node = Node::new(
+            self.epoch_state.epoch,
+            new_round,
+            self.author,
+            timestamp,
+            payload,
+            strong_links,
+        );
+        if let Err(e) = self.storage.save_node(&node) {
+            error!("Failed to save the node, not proposing: {:?}", e);
+            return;
+        }
+        self.dag.write().mark_nodes_linked(node.parents());
+        self.current_round = new_round;
+        self.broadcast_node(node);
+    }
+
+    /// Broadcasts the node for votes and then broadcasts the resulting certified node. The
+    /// broadcast of the previous round is aborted.
+    fn broadcast_node(&mut self, node: Node) {
+        let rb = self.reliable_broadcast.clone();
+        let epoch_state = self.epoch_state.clone();
+        let core_task = async move {
+            let (signature_tx, signature_rx) = oneshot::channel();
+            let (_signature_cancel_tx, signature_cancel_rx) = oneshot::channel();
+            rb.broadcast(
+                node.clone(),
+                SignatureBuilder::new(node.metadata(), epoch_state.clone()),
+                signature_tx,
+                signature_cancel_rx,
+            )
+            .await;
+            let signatures = match signature_rx.await {
+                Ok(signatures) => signatures,
+                Err(_) => return,
+            };
+            debug!("Node {} is certified", node.id());
+            let certified_node = CertifiedNode::new(node, signatures);
+            let (ack_tx, _ack_rx) = oneshot::channel();
+            let (_ack_cancel_tx, ack_cancel_rx) = oneshot::channel();
+            rb.broadcast(
+                certified_node,
+                CertificateAckState::new(epoch_state.verifier.len()),
+                ack_tx,
+                ack_cancel_rx,
+            )
+            .await;
+        };
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        tokio::spawn(Abortable::new(core_task, abort_registration).map(|_| ()));
+        if let Some(prev_handle) = self.rb_abort_handle.replace(abort_handle) {
+            prev_handle.abort();
+        }
+    }
+}
+
+impl Drop for DagDriver {
+    fn drop(&mut self) {
+        if let Some(handle) = self.rb_abort_handle.take() {
+            handle.abort();
+        }
+    }
+}
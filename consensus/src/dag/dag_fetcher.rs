@@ -0,0 +1,235 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    dag_store::Dag,
+    reliable_broadcast::{DAGNetworkSender, RBMessage},
+    types::{CertifiedNode, DAGMessage, FetchResponse, Node, NodeMetadata, RemoteFetchRequest},
+};
+use anyhow::{anyhow, ensure};
+use aptos_consensus_types::common::Author;
+use aptos_infallible::RwLock;
+use aptos_logger::{debug, error};
+use aptos_types::epoch_state::EpochState;
+use futures::StreamExt;
+use rand::seq::SliceRandom;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+/// A node whose causal history needs to be fetched from remote peers before it can be processed.
+pub enum LocalFetchRequest {
+    /// A node that was broadcast for votes, its missing parents are fetched from the author.
+    Node(Node),
+    /// A certified node, its missing causal history is fetched from its signers.
+    CertifiedNode(CertifiedNode),
+}
+
+impl LocalFetchRequest {
+    fn targets(&self) -> Vec<NodeMetadata> {
+        match self {
+            LocalFetchRequest::Node(node) => node.parents().to_vec(),
+            LocalFetchRequest::CertifiedNode(node) => node.parents().to_vec(),
+        }
+    }
+
+    fn responders(&self, epoch_state: &EpochState) -> Vec<Author> {
+        match self {
+            LocalFetchRequest::Node(node) => vec![*node.author()],
+            LocalFetchRequest::CertifiedNode(node) => node.signers(&epoch_state.verifier),
+        }
+    }
+}
+
+/// Hands the nodes with missing parents over to the `DagFetcher`.
+pub struct FetchRequester {
+    request_tx: Sender<LocalFetchRequest>,
+}
+
+impl FetchRequester {
+    pub fn new(request_tx: Sender<LocalFetchRequest>) -> Self {
+        Self { request_tx }
+    }
+
+    pub fn request_for_node(&self, node: Node) -> anyhow::Result<()> {
+        self.request_tx
+            .try_send(LocalFetchRequest::Node(node))
+            .map_err(|e| anyhow!("unable to request fetch: {}", e))
+    }
+
+    pub fn request_for_certified_node(&self, node: CertifiedNode) -> anyhow::Result<()> {
+        self.request_tx
+            .try_send(LocalFetchRequest::CertifiedNode(node))
+            .map_err(|e| anyhow!("unable to request fetch: {}", e))
+    }
+}
+
+/// Fetches the missing causal history of nodes from remote peers. The fetched certified nodes,
+/// followed by the requesting certified node if any, are sent in round order to `fetched_tx` to
+/// be added to the DAG.
+pub struct DagFetcher {
+    epoch_state: Arc<EpochState>,
+    network: Arc<dyn DAGNetworkSender>,
+    dag: Arc<RwLock<Dag>>,
+    request_rx: Receiver<LocalFetchRequest>,
+    fetched_tx: Sender<CertifiedNode>,
+    rpc_timeout: Duration,
+}
+
+impl DagFetcher {
+    pub fn new(
+        epoch_state: Arc<EpochState>,
+        network: Arc<dyn DAGNetworkSender>,
+        dag: Arc<RwLock<Dag>>,
+        fetched_tx: Sender<CertifiedNode>,
+        rpc_timeout: Duration,
+    ) -> (Self, FetchRequester) {
+        let (request_tx, request_rx) = tokio::sync::mpsc::channel(16);
+        (
+            Self {
+                epoch_state,
+                network,
+                dag,
+                request_rx,
+                fetched_tx,
+                rpc_timeout,
+            },
+            FetchRequester::new(request_tx),
+        )
+    }
+
+    pub async fn start(mut self) {
+        while let Some(request) = self.request_rx.recv().await {
+            let missing_targets: Vec<_> = {
+                let dag = self.dag.read();
+                request
+                    .targets()
+                    .into_iter()
+                    .filter(|target| {
+                        target.round() >= dag.lowest_round() && !dag.exists(target.digest())
+                    })
+                    .collect()
+            };
+            if !missing_targets.is_empty() {
+                match self
+                    .fetch(missing_targets, request.responders(&self.epoch_state))
+                    .await
+                {
+                    Ok(nodes) => {
+                        for node in nodes {
+                            if self.fetched_tx.send(node).await.is_err() {
+                                return;
+                            }
+                        }
+                    },
+                    Err(e) => {
+                        error!("Failed to fetch the missing nodes: {:?}", e);
+                        continue;
+                    },
+                }
+            }
+            if let LocalFetchRequest::CertifiedNode(node) = request {
+                if self.fetched_tx.send(node).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Tries the responders in a random order until one of them returns the causal history of the
+    /// targets.
+    async fn fetch(
+        &self,
+        targets: Vec<NodeMetadata>,
+        mut responders: Vec<Author>,
+    ) -> anyhow::Result<Vec<CertifiedNode>> {
+        let bitmask = {
+            let dag = self.dag.read();
+            dag.bitmask(dag.lowest_round())
+        };
+        let request = RemoteFetchRequest::new(self.epoch_state.epoch, targets.clone(), bitmask);
+        responders.shuffle(&mut rand::thread_rng());
+
+        let mut responses = futures::stream::iter(responders.into_iter().map(|responder| {
+            let message = DAGMessage::FetchRequest(request.clone()).into_network_message();
+            async move {
+                (
+                    responder,
+                    self.network
+                        .send_rpc(responder, message, self.rpc_timeout)
+                        .await,
+                )
+            }
+        }))
+        .buffered(1);
+        while let Some((responder, result)) = responses.next().await {
+            match result
+                .and_then(FetchResponse::from_network_message)
+                .and_then(|response| self.verify_response(&targets, response))
+            {
+                Ok(nodes) => return Ok(nodes),
+                Err(e) => debug!("Failed to fetch from {}: {:?}", responder, e),
+            }
+        }
+        Err(anyhow!("no responder returned the missing nodes"))
+    }
+
+    fn verify_response(
+        &self,
+        targets: &[NodeMetadata],
+        response: FetchResponse,
+    ) -> anyhow::Result<Vec<CertifiedNode>> {
+        response.verify(&self.epoch_state.verifier)?;
+        let mut nodes = response.certified_nodes();
+        for target in targets {
+            ensure!(
+                nodes.iter().any(|node| node.digest() == *target.digest()),
+                "missing target {}",
+                target.id()
+            );
+        }
+        nodes.sort_by_key(|node| node.round());
+        Ok(nodes)
+    }
+}
+
+/// Serves the causal history of the requested targets, excluding the nodes the requester
+/// already has.
+pub struct FetchRequestHandler {
+    dag: Arc<RwLock<Dag>>,
+    epoch_state: Arc<EpochState>,
+}
+
+impl FetchRequestHandler {
+    pub fn new(dag: Arc<RwLock<Dag>>, epoch_state: Arc<EpochState>) -> Self {
+        Self { dag, epoch_state }
+    }
+
+    pub fn process(&self, request: RemoteFetchRequest) -> anyhow::Result<FetchResponse> {
+        ensure!(request.epoch() == self.epoch_state.epoch, "different epoch");
+        let dag = self.dag.read();
+        ensure!(
+            request
+                .targets()
+                .iter()
+                .all(|target| dag.exists(target.digest())),
+            "targets are missing"
+        );
+        let bitmask = request.exists_bitmask();
+        let digests: Vec<_> = request
+            .targets()
+            .iter()
+            .map(|target| *target.digest())
+            .collect();
+        let mut nodes: Vec<_> = dag
+            .reachable(&digests, bitmask.first_round())
+            .into_iter()
+            .filter(|node| {
+                dag.author_index(node.author())
+                    .map_or(true, |index| !bitmask.has(node.round(), index))
+            })
+            .map(|node| node.as_ref().clone())
+            .collect();
+        nodes.sort_by_key(|node| node.round());
+        Ok(FetchResponse::new(self.epoch_state.epoch, nodes))
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::{
+        dag_driver::DagDriver,
+        dag_fetcher::FetchRequestHandler,
+        rb_handler::NodeBroadcastHandler,
+        types::{CertifiedNode, DAGMessage},
+    },
+    network::IncomingDAGRequest,
+};
+use aptos_channels::aptos_channel;
+use aptos_consensus_types::common::Author;
+use aptos_logger::{debug, warn};
+use aptos_network::protocols::network::RpcError;
+use aptos_types::epoch_state::EpochState;
+use bytes::Bytes;
+use futures::StreamExt;
+use futures_channel::oneshot;
+use std::sync::Arc;
+use tokio::sync::mpsc::Receiver;
+
+/// Dispatches the DAG RPCs to the handler of each message type and sends back the responses.
+pub struct NetworkHandler {
+    epoch_state: Arc<EpochState>,
+    dag_rpc_rx: aptos_channel::Receiver<Author, IncomingDAGRequest>,
+    node_receiver: NodeBroadcastHandler,
+    dag_driver: DagDriver,
+    fetch_receiver: FetchRequestHandler,
+    fetched_rx: Receiver<CertifiedNode>,
+}
+
+impl NetworkHandler {
+    pub fn new(
+        epoch_state: Arc<EpochState>,
+        dag_rpc_rx: aptos_channel::Receiver<Author, IncomingDAGRequest>,
+        node_receiver: NodeBroadcastHandler,
+        dag_driver: DagDriver,
+        fetch_receiver: FetchRequestHandler,
+        fetched_rx: Receiver<CertifiedNode>,
+    ) -> Self {
+        Self {
+            epoch_state,
+            dag_rpc_rx,
+            node_receiver,
+            dag_driver,
+            fetch_receiver,
+            fetched_rx,
+        }
+    }
+
+    pub async fn start(mut self, mut shutdown_rx: oneshot::Receiver<oneshot::Sender<()>>) {
+        self.dag_driver.initialize().await;
+        let ack_tx = loop {
+            tokio::select! {
+                Some(request) = self.dag_rpc_rx.next() => {
+                    self.process_rpc(request).await;
+                },
+                Some(node) = self.fetched_rx.recv() => {
+                    if let Err(e) = self.dag_driver.add_node(node).await {
+                        warn!("Failed to add the fetched node: {:?}", e);
+                    }
+                },
+                ack_tx = &mut shutdown_rx => break ack_tx,
+            }
+        };
+        // drop the driver to abort its pending broadcast before acknowledging
+        drop(self);
+        if let Ok(ack_tx) = ack_tx {
+            let _ = ack_tx.send(());
+        }
+    }
+
+    async fn process_rpc(&mut self, request: IncomingDAGRequest) {
+        let IncomingDAGRequest {
+            req,
+            sender,
+            protocol,
+            response_sender,
+        } = request;
+        let response = match DAGMessage::try_from(req) {
+            Ok(message) if message.epoch() == self.epoch_state.epoch => {
+                debug!("Received DAG message {} from {}", message.name(), sender);
+                match message {
+                    DAGMessage::NodeMsg(node) => self
+                        .node_receiver
+                        .process(node, sender)
+                        .map(DAGMessage::VoteMsg),
+                    DAGMessage::CertifiedNodeMsg(node) => self
+                        .dag_driver
+                        .process(node)
+                        .await
+                        .map(DAGMessage::CertifiedAckMsg),
+                    DAGMessage::FetchRequest(request) => self
+                        .fetch_receiver
+                        .process(request)
+                        .map(DAGMessage::FetchResponse),
+                    message => Err(anyhow::anyhow!(
+                        "unexpected DAG request: {}",
+                        message.name()
+                    )),
+                }
+            },
+            Ok(message) => Err(anyhow::anyhow!(
+                "DAG message from a different epoch: {}",
+                message.epoch()
+            )),
+            Err(e) => Err(e),
+        };
+
+        let response = response
+            .and_then(|message| {
+                protocol
+                    .to_bytes(&message.into_network_message())
+                    .map(Bytes::from)
+            })
+            .map_err(|e| {
+                debug!("Failed to process the DAG request from {}: {:?}", sender, e);
+                RpcError::ApplicationError(e)
+            });
+        let _ = response_sender.send(response);
+    }
+}
@@ -1,147 +1,16 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::dag::types::{CertifiedNode, DagSnapshotBitmask, NodeMetadata};
 use anyhow::{anyhow, ensure};
-use aptos_consensus_types::common::{Author, Payload, Round};
-use aptos_crypto::{
-    hash::{CryptoHash, CryptoHasher},
-    HashValue,
-};
-use aptos_crypto_derive::CryptoHasher;
-use aptos_types::{aggregate_signature::AggregateSignature, validator_verifier::ValidatorVerifier};
-use serde::{Deserialize, Serialize};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
+use aptos_types::validator_verifier::ValidatorVerifier;
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    ops::Deref,
     sync::Arc,
 };
 
-/// Represents the metadata about the node, without payload and parents from Node
-#[derive(Clone, Serialize, Deserialize)]
-pub struct NodeMetadata {
-    epoch: u64,
-    round: Round,
-    author: Author,
-    timestamp: u64,
-    digest: HashValue,
-}
-
-/// Node representation in the DAG, parents contain 2f+1 strong links (links to previous round)
-/// plus weak links (links to lower round)
-#[derive(Clone, Serialize, Deserialize, CryptoHasher)]
-pub struct Node {
-    metadata: NodeMetadata,
-    payload: Payload,
-    parents: Vec<NodeMetadata>,
-}
-
-impl Node {
-    pub fn new(
-        epoch: u64,
-        round: Round,
-        author: Author,
-        timestamp: u64,
-        payload: Payload,
-        parents: Vec<NodeMetadata>,
-    ) -> Self {
-        let digest = Self::calculate_digest(epoch, round, author, timestamp, &payload, &parents);
-
-        Self {
-            metadata: NodeMetadata {
-                epoch,
-                round,
-                author,
-                timestamp,
-                digest,
-            },
-            payload,
-            parents,
-        }
-    }
-
-    /// Calculate the node digest based on all fields in the node
-    fn calculate_digest(
-        epoch: u64,
-        round: Round,
-        author: Author,
-        timestamp: u64,
-        payload: &Payload,
-        parents: &Vec<NodeMetadata>,
-    ) -> HashValue {
-        #[derive(Serialize)]
-        struct NodeWithoutDigest<'a> {
-            epoch: u64,
-            round: Round,
-            author: Author,
-            timestamp: u64,
-            payload: &'a Payload,
-            parents: &'a Vec<NodeMetadata>,
-        }
-
-        impl<'a> CryptoHash for NodeWithoutDigest<'a> {
-            type Hasher = NodeHasher;
-
-            fn hash(&self) -> HashValue {
-                let mut state = Self::Hasher::new();
-                let bytes = bcs::to_bytes(&self).expect("Unable to serialize node");
-                state.update(&bytes);
-                state.finish()
-            }
-        }
-
-        let node_with_out_digest = NodeWithoutDigest {
-            epoch,
-            round,
-            author,
-            timestamp,
-            payload,
-            parents,
-        };
-        node_with_out_digest.hash()
-    }
-
-    pub fn digest(&self) -> HashValue {
-        self.metadata.digest
-    }
-
-    pub fn metadata(&self) -> NodeMetadata {
-        self.metadata.clone()
-    }
-}
-
-/// Quorum signatures over the node digest
-#[derive(Clone)]
-pub struct NodeCertificate {
-    digest: HashValue,
-    signatures: AggregateSignature,
-}
-
-impl NodeCertificate {
-    pub fn new(digest: HashValue, signatures: AggregateSignature) -> Self {
-        Self { digest, signatures }
-    }
-}
-
-#[derive(Clone)]
-pub struct CertifiedNode {
-    node: Node,
-    certificate: NodeCertificate,
-}
-
-impl CertifiedNode {
-    pub fn new(node: Node, certificate: NodeCertificate) -> Self {
-        Self { node, certificate }
-    }
-}
-
-impl Deref for CertifiedNode {
-    type Target = Node;
-
-    fn deref(&self) -> &Self::Target {
-        &self.node
-    }
-}
-
 /// Data structure that stores the DAG representation, it maintains both hash based index and
 /// round based index.
 pub struct Dag {
@@ -151,6 +20,8 @@ pub struct Dag {
     author_to_index: HashMap<Author, usize>,
     /// Highest head nodes that are not linked by other nodes
     highest_unlinked_nodes_by_author: Vec<Option<Arc<CertifiedNode>>>,
+    /// Nodes that were already ordered as part of the causal history of an anchor
+    ordered_digests: HashSet<HashValue>,
 }
 
 impl Dag {
@@ -163,10 +34,11 @@ impl Dag {
             nodes_by_round,
             author_to_index,
             highest_unlinked_nodes_by_author: vec![None; num_nodes],
+            ordered_digests: HashSet::new(),
         }
     }
 
-    fn lowest_round(&self) -> Round {
+    pub fn lowest_round(&self) -> Round {
         *self
             .nodes_by_round
             .first_key_value()
@@ -174,7 +46,7 @@ impl Dag {
             .unwrap_or(&0)
     }
 
-    fn highest_round(&self) -> Round {
+    pub fn highest_round(&self) -> Round {
         *self
             .nodes_by_round
             .last_key_value()
@@ -186,17 +58,21 @@ impl Dag {
         let node = Arc::new(node);
         let index = *self
             .author_to_index
-            .get(&node.metadata.author)
+            .get(node.author())
             .ok_or_else(|| anyhow!("unknown author"))?;
-        let round = node.metadata.round;
+        let round = node.round();
         ensure!(round >= self.lowest_round(), "round too low");
         ensure!(round <= self.highest_round() + 1, "round too high");
-        for parent in &node.parents {
-            ensure!(self.exists(&parent.digest), "parent not exist");
+        // parents below the lowest round have been pruned
+        for parent in node.parents() {
+            ensure!(
+                parent.round() < self.lowest_round() || self.exists(parent.digest()),
+                "parent not exist"
+            );
         }
         ensure!(
             self.nodes_by_digest
-                .insert(node.metadata.digest, node.clone())
+                .insert(node.digest(), node.clone())
                 .is_none(),
             "duplicate node"
         );
@@ -211,7 +87,7 @@ impl Dag {
         if round
             > self.highest_unlinked_nodes_by_author[index]
                 .as_ref()
-                .map_or(0, |node| node.metadata.round)
+                .map_or(0, |node| node.round())
         {
             self.highest_unlinked_nodes_by_author[index].replace(node);
         }
@@ -236,8 +112,8 @@ impl Dag {
                 .iter()
                 .filter_map(|maybe_node| {
                     maybe_node.as_ref().and_then(|node| {
-                        if node.metadata.round == current_round {
-                            Some(&node.metadata.author)
+                        if node.round() == current_round {
+                            Some(node.author())
                         } else {
                             None
                         }
@@ -250,7 +126,7 @@ impl Dag {
             Some(
                 self.highest_unlinked_nodes_by_author
                     .iter()
-                    .filter_map(|maybe_node| maybe_node.as_ref().map(|node| node.metadata.clone()))
+                    .filter_map(|maybe_node| maybe_node.as_ref().map(|node| node.metadata()))
                     .collect(),
             )
         } else {
@@ -259,13 +135,111 @@ impl Dag {
     }
 
     pub fn mark_nodes_linked(&mut self, node_metadata: &[NodeMetadata]) {
-        let digests: HashSet<_> = node_metadata.iter().map(|node| node.digest).collect();
+        let digests: HashSet<_> = node_metadata.iter().map(|node| *node.digest()).collect();
         for maybe_node in &mut self.highest_unlinked_nodes_by_author {
             if let Some(node) = maybe_node {
-                if digests.contains(&node.metadata.digest) {
+                if digests.contains(&node.digest()) {
                     *maybe_node = None;
                 }
             }
         }
     }
+
+    pub fn get_node_by_round_author(
+        &self,
+        round: Round,
+        author: &Author,
+    ) -> Option<&Arc<CertifiedNode>> {
+        let index = self.author_to_index.get(author)?;
+        self.nodes_by_round
+            .get(&round)
+            .and_then(|nodes| nodes[*index].as_ref())
+    }
+
+    pub fn get_nodes_by_round(
+        &self,
+        round: Round,
+    ) -> impl Iterator<Item = &Arc<CertifiedNode>> + '_ {
+        self.nodes_by_round
+            .get(&round)
+            .into_iter()
+            .flat_map(|nodes| nodes.iter().flatten())
+    }
+
+    /// Returns the nodes reachable from `from` (included) through parent links, down to
+    /// `until_round`.
+    pub fn reachable(&self, from: &[HashValue], until_round: Round) -> Vec<Arc<CertifiedNode>> {
+        let mut visited = HashSet::new();
+        let mut to_visit: Vec<_> = from.to_vec();
+        let mut reachable = vec![];
+        while let Some(digest) = to_visit.pop() {
+            if !visited.insert(digest) {
+                continue;
+            }
+            if let Some(node) = self.nodes_by_digest.get(&digest) {
+                for parent in node.parents() {
+                    if parent.round() >= until_round {
+                        to_visit.push(*parent.digest());
+                    }
+                }
+                reachable.push(node.clone());
+            }
+        }
+        reachable
+    }
+
+    pub fn is_reachable(&self, from: &HashValue, target: &NodeMetadata) -> bool {
+        self.reachable(&[*from], target.round())
+            .iter()
+            .any(|node| node.digest() == *target.digest())
+    }
+
+    pub fn mark_ordered(&mut self, digest: HashValue) {
+        self.ordered_digests.insert(digest);
+    }
+
+    pub fn is_ordered(&self, digest: &HashValue) -> bool {
+        self.ordered_digests.contains(digest)
+    }
+
+    pub fn author_index(&self, author: &Author) -> Option<usize> {
+        self.author_to_index.get(author).copied()
+    }
+
+    /// Returns which nodes exist in the DAG from `first_round` up to the highest round.
+    pub fn bitmask(&self, first_round: Round) -> DagSnapshotBitmask {
+        let first_round = first_round.max(self.lowest_round());
+        let bitmask = (first_round..=self.highest_round())
+            .map(|round| match self.nodes_by_round.get(&round) {
+                Some(nodes) => nodes.iter().map(|node| node.is_some()).collect(),
+                None => vec![false; self.author_to_index.len()],
+            })
+            .collect();
+        DagSnapshotBitmask::new(first_round, bitmask)
+    }
+
+    /// Removes the nodes of the rounds lower than `lowest_round` and returns their digests.
+    pub fn prune(&mut self, lowest_round: Round) -> Vec<HashValue> {
+        if lowest_round <= self.lowest_round() {
+            return vec![];
+        }
+        let num_nodes = self.author_to_index.len();
+        let mut retained = self.nodes_by_round.split_off(&lowest_round);
+        retained
+            .entry(lowest_round)
+            .or_insert_with(|| vec![None; num_nodes]);
+        let pruned = std::mem::replace(&mut self.nodes_by_round, retained);
+
+        let digests: Vec<_> = pruned
+            .into_values()
+            .flatten()
+            .flatten()
+            .map(|node| node.digest())
+            .collect();
+        for digest in &digests {
+            self.nodes_by_digest.remove(digest);
+            self.ordered_digests.remove(digest);
+        }
+        digests
+    }
 }
@@ -0,0 +1,141 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    adapter::Notifier,
+    anchor_election::AnchorElection,
+    dag_store::Dag,
+    types::{CertifiedNode, NodeMetadata},
+};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_infallible::RwLock;
+use aptos_types::epoch_state::EpochState;
+use std::sync::Arc;
+
+/// Bullshark-style commit rule: an anchor is elected every odd round, and it's ordered once the
+/// nodes of the next round that link to it carry at least f+1 voting power. Ordering an anchor
+/// also orders the previous unordered anchors that are reachable from it, and each anchor orders
+/// its unordered causal history.
+pub struct OrderRule {
+    epoch_state: Arc<EpochState>,
+    lowest_unordered_anchor_round: Round,
+    dag: Arc<RwLock<Dag>>,
+    anchor_election: Box<dyn AnchorElection>,
+    notifier: Box<dyn Notifier>,
+    dag_window_size: Round,
+}
+
+impl OrderRule {
+    pub fn new(
+        epoch_state: Arc<EpochState>,
+        lowest_unordered_anchor_round: Round,
+        dag: Arc<RwLock<Dag>>,
+        anchor_election: Box<dyn AnchorElection>,
+        notifier: Box<dyn Notifier>,
+        dag_window_size: Round,
+    ) -> Self {
+        Self {
+            epoch_state,
+            lowest_unordered_anchor_round,
+            dag,
+            anchor_election,
+            notifier,
+            dag_window_size,
+        }
+    }
+
+    pub fn lowest_unordered_anchor_round(&self) -> Round {
+        self.lowest_unordered_anchor_round
+    }
+
+    /// Called for every node added to the DAG. A node of an odd round may be an anchor whose
+    /// votes were added before it, a node of an even round is a potential vote for the anchor of
+    /// the previous round.
+    pub async fn process_new_node(&mut self, node_metadata: &NodeMetadata) -> anyhow::Result<()> {
+        let round = node_metadata.round();
+        let anchor_round = if round % 2 == 1 { round } else { round - 1 };
+        if anchor_round < self.lowest_unordered_anchor_round {
+            return Ok(());
+        }
+        match self.check_votes_for_anchor(anchor_round) {
+            Some(anchor) => self.finalize_order(anchor).await,
+            None => Ok(()),
+        }
+    }
+
+    fn check_votes_for_anchor(&self, anchor_round: Round) -> Option<Arc<CertifiedNode>> {
+        let anchor_author = self.anchor_election.get_anchor(anchor_round);
+        let dag = self.dag.read();
+        let anchor = dag.get_node_by_round_author(anchor_round, &anchor_author)?;
+        let verifier = &self.epoch_state.verifier;
+        let votes: u128 = dag
+            .get_nodes_by_round(anchor_round + 1)
+            .filter(|node| {
+                node.parents()
+                    .iter()
+                    .any(|parent| parent.digest() == &anchor.digest())
+            })
+            .map(|node| verifier.get_voting_power(node.author()).unwrap_or(0) as u128)
+            .sum();
+        // f+1 votes guarantee that any quorum of the next round contains a path to the anchor
+        let minority_power = verifier.total_voting_power() - verifier.quorum_voting_power() + 1;
+        if votes >= minority_power {
+            Some(anchor.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Orders `anchor` and the unordered anchors of the previous rounds that it reaches, oldest
+    /// first.
+    async fn finalize_order(&mut self, anchor: Arc<CertifiedNode>) -> anyhow::Result<()> {
+        let to_order = {
+            let mut dag = self.dag.write();
+            let mut anchors = vec![];
+            let mut failed_authors: Vec<(Round, Author)> = vec![];
+            let mut current = anchor.clone();
+            let mut round = anchor.round();
+            while round >= self.lowest_unordered_anchor_round + 2 {
+                round -= 2;
+                let author = self.anchor_election.get_anchor(round);
+                match dag.get_node_by_round_author(round, &author) {
+                    Some(prev_anchor)
+                        if dag.is_reachable(&current.digest(), &prev_anchor.metadata()) =>
+                    {
+                        failed_authors.reverse();
+                        anchors.push((current, std::mem::take(&mut failed_authors)));
+                        current = prev_anchor.clone();
+                    },
+                    _ => failed_authors.push((round, author)),
+                }
+            }
+            failed_authors.reverse();
+            anchors.push((current, failed_authors));
+            anchors.reverse();
+
+            let mut to_order = vec![];
+            for (anchor, failed_authors) in anchors {
+                let until_round = anchor.round().saturating_sub(self.dag_window_size);
+                let mut ordered_nodes: Vec<_> = dag
+                    .reachable(&[anchor.digest()], until_round)
+                    .into_iter()
+                    .filter(|node| !dag.is_ordered(&node.digest()))
+                    .collect();
+                ordered_nodes.sort_by_key(|node| (node.round(), dag.author_index(node.author())));
+                for node in &ordered_nodes {
+                    dag.mark_ordered(node.digest());
+                }
+                to_order.push((ordered_nodes, failed_authors));
+            }
+            to_order
+        };
+        self.lowest_unordered_anchor_round = anchor.round() + 2;
+
+        for (ordered_nodes, failed_authors) in to_order {
+            self.notifier
+                .send_ordered_nodes(ordered_nodes, failed_authors)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    dag_fetcher::FetchRequester,
+    dag_store::Dag,
+    storage::DAGStorage,
+    types::{Node, NodeId, Vote},
+};
+use anyhow::{bail, ensure};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_infallible::RwLock;
+use aptos_logger::error;
+use aptos_types::{epoch_state::EpochState, validator_signer::ValidatorSigner};
+use std::{collections::BTreeMap, mem, sync::Arc};
+
+/// Votes on the nodes broadcast by the other validators. A validator votes for at most one node
+/// per author and round, the votes are persisted so that the promise holds across restarts.
+pub struct NodeBroadcastHandler {
+    dag: Arc<RwLock<Dag>>,
+    votes_by_round_peer: BTreeMap<Round, BTreeMap<Author, Vote>>,
+    signer: Arc<ValidatorSigner>,
+    epoch_state: Arc<EpochState>,
+    storage: Arc<dyn DAGStorage>,
+    fetch_requester: Arc<FetchRequester>,
+    quorum_store_enabled: bool,
+}
+
+impl NodeBroadcastHandler {
+    pub fn new(
+        dag: Arc<RwLock<Dag>>,
+        signer: Arc<ValidatorSigner>,
+        epoch_state: Arc<EpochState>,
+        storage: Arc<dyn DAGStorage>,
+        fetch_requester: Arc<FetchRequester>,
+        quorum_store_enabled: bool,
+    ) -> Self {
+        let epoch = epoch_state.epoch;
+        let votes_by_round_peer = read_votes_from_storage(&storage, epoch);
+
+        Self {
+            dag,
+            votes_by_round_peer,
+            signer,
+            epoch_state,
+            storage,
+            fetch_requester,
+            quorum_store_enabled,
+        }
+    }
+
+    /// Removes the votes of the rounds that were pruned from the DAG.
+    fn gc(&mut self) {
+        let lowest_round = self.dag.read().lowest_round();
+        let to_retain = self.votes_by_round_peer.split_off(&lowest_round);
+        let to_delete = mem::replace(&mut self.votes_by_round_peer, to_retain);
+        let epoch = self.epoch_state.epoch;
+
+        let node_ids = to_delete
+            .into_iter()
+            .flat_map(|(round, votes)| {
+                votes
+                    .into_keys()
+                    .map(move |author| NodeId::new(epoch, round, author))
+            })
+            .collect::<Vec<_>>();
+        if !node_ids.is_empty() {
+            if let Err(e) = self.storage.delete_votes(node_ids) {
+                error!("Failed to delete the votes of pruned rounds: {:?}", e);
+            }
+        }
+    }
+
+    fn validate(&self, node: &Node) -> anyhow::Result<()> {
+        ensure!(node.epoch() == self.epoch_state.epoch, "different epoch");
+        node.verify(&self.epoch_state.verifier)?;
+        node.payload()
+            .verify(&self.epoch_state.verifier, self.quorum_store_enabled)?;
+
+        let missing_parents: Vec<_> = {
+            let dag = self.dag.read();
+            ensure!(node.round() >= dag.lowest_round(), "round too low");
+            node.parents()
+                .iter()
+                .filter(|parent| {
+                    parent.round() >= dag.lowest_round() && !dag.exists(parent.digest())
+                })
+                .cloned()
+                .collect()
+        };
+        if !missing_parents.is_empty() {
+            // the node will be voted on once the parents are fetched and the author retries
+            if let Err(e) = self.fetch_requester.request_for_node(node.clone()) {
+                error!("Failed to fetch the parents of {}: {:?}", node.id(), e);
+            }
+            bail!("{} missing parents", missing_parents.len());
+        }
+        Ok(())
+    }
+
+    pub fn process(&mut self, node: Node, sender: Author) -> anyhow::Result<Vote> {
+        ensure!(node.author() == &sender, "node not sent by its author");
+        self.validate(&node)?;
+
+        let votes_by_peer = self
+            .votes_by_round_peer
+            .entry(node.round())
+            .or_insert_with(BTreeMap::new);
+        if let Some(vote) = votes_by_peer.get(node.author()) {
+            ensure!(
+                vote.metadata() == &node.metadata(),
+                "equivocating node {}",
+                node.id()
+            );
+            return Ok(vote.clone());
+        }
+
+        let vote = Vote::new(node.metadata(), &self.signer)?;
+        self.storage.save_vote(&node.id(), &vote)?;
+        votes_by_peer.insert(*node.author(), vote.clone());
+        self.gc();
+        Ok(vote)
+    }
+}
+
+fn read_votes_from_storage(
+    storage: &Arc<dyn DAGStorage>,
+    epoch: u64,
+) -> BTreeMap<Round, BTreeMap<Author, Vote>> {
+    let mut votes_by_round_peer = BTreeMap::new();
+    let mut stale_votes = vec![];
+    match storage.get_votes() {
+        Ok(votes) => {
+            for (node_id, vote) in votes {
+                if node_id.epoch() == epoch {
+                    votes_by_round_peer
+                        .entry(node_id.round())
+                        .or_insert_with(BTreeMap::new)
+                        .insert(*node_id.author(), vote);
+                } else {
+                    stale_votes.push(node_id);
+                }
+            }
+        },
+        Err(e) => error!("Failed to read the votes from storage: {:?}", e),
+    }
+    if let Err(e) = storage.delete_votes(stale_votes) {
+        error!("Failed to delete the votes of previous epochs: {:?}", e);
+    }
+    votes_by_round_peer
+}
@@ -8,19 +8,17 @@ use futures::{stream::FuturesUnordered, StreamExt};
 use std::{future::Future, sync::Arc, time::Duration};
 use tokio::sync::oneshot;
 
-pub trait DAGMessage: Sized + Clone {
+pub trait RBMessage: Sized + Clone {
     fn from_network_message(msg: ConsensusMsg) -> anyhow::Result<Self>;
 
     fn into_network_message(self) -> ConsensusMsg;
 }
 
 pub trait BroadcastStatus {
-    type Message: DAGMessage;
-    type Ack: DAGMessage;
+    type Message: RBMessage;
+    type Ack: RBMessage;
     type Aggregated;
 
-    fn empty(validators: Vec<Author>) -> Self;
-
     fn add(&mut self, peer: Author, ack: Self::Ack) -> anyhow::Result<Option<Self::Aggregated>>;
 }
 
@@ -34,6 +32,9 @@ pub trait DAGNetworkSender: Send + Sync {
     ) -> anyhow::Result<ConsensusMsg>;
 }
 
+/// Delay before resending the message to a peer that failed to acknowledge it.
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct ReliableBroadcast {
     validators: Vec<Author>,
     network_sender: Arc<dyn DAGNetworkSender>,
@@ -50,6 +51,7 @@ impl ReliableBroadcast {
     pub fn broadcast<S: BroadcastStatus>(
         &self,
         message: S::Message,
+        mut aggregating: S,
         return_tx: oneshot::Sender<S::Aggregated>,
         mut cancel_rx: oneshot::Receiver<()>,
     ) -> impl Future<Output = ()> {
@@ -57,11 +59,11 @@ impl ReliableBroadcast {
         let network_message = message.into_network_message();
         let network_sender = self.network_sender.clone();
         async move {
-            let mut aggregating = S::empty(receivers.clone());
             let mut fut = FuturesUnordered::new();
-            let send_message = |receiver, message| {
+            let send_message = |receiver, message, delay| {
                 let network_sender = network_sender.clone();
                 async move {
+                    tokio::time::sleep(delay).await;
                     (
                         receiver,
                         network_sender
@@ -71,7 +73,11 @@ impl ReliableBroadcast {
                 }
             };
             for receiver in receivers {
-                fut.push(send_message(receiver, network_message.clone()));
+                fut.push(send_message(
+                    receiver,
+                    network_message.clone(),
+                    Duration::ZERO,
+                ));
             }
             loop {
                 tokio::select! {
@@ -85,7 +91,7 @@ impl ReliableBroadcast {
                                     }
                                 }
                             },
-                            Err(_) => fut.push(send_message(receiver, network_message.clone())),
+                            Err(_) => fut.push(send_message(receiver, network_message.clone(), RETRY_INTERVAL)),
                         }
                     }
                     _ = &mut cancel_rx => {
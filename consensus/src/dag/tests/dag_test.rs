@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::dag::dag_store::{CertifiedNode, Dag, Node, NodeCertificate, NodeMetadata};
+use crate::dag::{
+    dag_store::Dag,
+    types::{CertifiedNode, Node, NodeCertificate, NodeMetadata},
+};
 use aptos_consensus_types::common::{Author, Payload, Round};
 use aptos_types::{
     aggregate_signature::AggregateSignature, validator_verifier::random_validator_verifier,
@@ -87,11 +90,11 @@ fn test_dag_insertion_failure() {
     assert!(dag.add_node(node).is_err());
 }
 
-fn new_node(round: Round, author: Author, parents: Vec<NodeMetadata>) -> CertifiedNode {
+pub(super) fn new_node(round: Round, author: Author, parents: Vec<NodeMetadata>) -> CertifiedNode {
     let node = Node::new(1, round, author, 0, Payload::empty(false), parents);
-    let digest = node.digest();
+    let metadata = node.metadata();
     CertifiedNode::new(
         node,
-        NodeCertificate::new(digest, AggregateSignature::empty()),
+        NodeCertificate::new(metadata, AggregateSignature::empty()),
     )
 }
@@ -0,0 +1,57 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    dag_fetcher::FetchRequestHandler,
+    dag_store::Dag,
+    tests::dag_test::new_node,
+    types::{NodeMetadata, RemoteFetchRequest},
+};
+use aptos_infallible::RwLock;
+use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
+use std::sync::Arc;
+
+#[test]
+fn test_fetch_request_handler() {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+    let author_to_index = verifier.address_to_validator_index().clone();
+    let epoch_state = Arc::new(EpochState { epoch: 1, verifier });
+
+    let mut dag = Dag::new(author_to_index.clone(), 1);
+    let mut parents: Vec<NodeMetadata> = vec![];
+    for round in 1..=3 {
+        let mut round_nodes = vec![];
+        for signer in &signers {
+            let node = new_node(round, signer.author(), parents.clone());
+            round_nodes.push(node.metadata());
+            dag.add_node(node).unwrap();
+        }
+        parents = round_nodes;
+    }
+    let target = parents[0].clone();
+    let dag = Arc::new(RwLock::new(dag));
+    let handler = FetchRequestHandler::new(dag.clone(), epoch_state);
+
+    // the requester has nothing
+    let empty_bitmask = Dag::new(author_to_index.clone(), 1).bitmask(1);
+    let request = RemoteFetchRequest::new(1, vec![target.clone()], empty_bitmask);
+    let nodes = handler.process(request).unwrap().certified_nodes();
+    assert_eq!(nodes.len(), 4 + 4 + 1);
+    assert!(nodes
+        .windows(2)
+        .all(|pair| pair[0].round() <= pair[1].round()));
+
+    // the requester has the first round
+    let mut requester_dag = Dag::new(author_to_index, 1);
+    for node in dag.read().get_nodes_by_round(1) {
+        requester_dag.add_node(node.as_ref().clone()).unwrap();
+    }
+    let request = RemoteFetchRequest::new(1, vec![target.clone()], requester_dag.bitmask(1));
+    let nodes = handler.process(request).unwrap().certified_nodes();
+    assert_eq!(nodes.len(), 4 + 1);
+    assert_eq!(nodes.last().unwrap().metadata(), target);
+
+    // requests of a different epoch are rejected
+    let request = RemoteFetchRequest::new(2, vec![target], requester_dag.bitmask(1));
+    assert!(handler.process(request).is_err());
+}
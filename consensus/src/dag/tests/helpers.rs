@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    storage::DAGStorage,
+    types::{CertifiedNode, Node, NodeId, Vote},
+};
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use std::collections::HashMap;
+
+/// In-memory `DAGStorage` for tests.
+pub(super) struct MockStorage {
+    node: Mutex<Option<Node>>,
+    votes: Mutex<HashMap<NodeId, Vote>>,
+    certified_nodes: Mutex<HashMap<HashValue, CertifiedNode>>,
+}
+
+impl MockStorage {
+    pub(super) fn new() -> Self {
+        Self {
+            node: Mutex::new(None),
+            votes: Mutex::new(HashMap::new()),
+            certified_nodes: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl DAGStorage for MockStorage {
+    fn save_node(&self, node: &Node) -> anyhow::Result<()> {
+        self.node.lock().replace(node.clone());
+        Ok(())
+    }
+
+    fn get_node(&self) -> anyhow::Result<Option<Node>> {
+        Ok(self.node.lock().clone())
+    }
+
+    fn delete_node(&self) -> anyhow::Result<()> {
+        self.node.lock().take();
+        Ok(())
+    }
+
+    fn save_vote(&self, node_id: &NodeId, vote: &Vote) -> anyhow::Result<()> {
+        self.votes.lock().insert(node_id.clone(), vote.clone());
+        Ok(())
+    }
+
+    fn get_votes(&self) -> anyhow::Result<Vec<(NodeId, Vote)>> {
+        Ok(self.votes.lock().clone().into_iter().collect())
+    }
+
+    fn delete_votes(&self, node_ids: Vec<NodeId>) -> anyhow::Result<()> {
+        for node_id in node_ids {
+            self.votes.lock().remove(&node_id);
+        }
+        Ok(())
+    }
+
+    fn save_certified_node(&self, node: &CertifiedNode) -> anyhow::Result<()> {
+        self.certified_nodes
+            .lock()
+            .insert(node.digest(), node.clone());
+        Ok(())
+    }
+
+    fn get_certified_nodes(&self) -> anyhow::Result<Vec<(HashValue, CertifiedNode)>> {
+        Ok(self.certified_nodes.lock().clone().into_iter().collect())
+    }
+
+    fn delete_certified_nodes(&self, digests: Vec<HashValue>) -> anyhow::Result<()> {
+        for digest in digests {
+            self.certified_nodes.lock().remove(&digest);
+        }
+        Ok(())
+    }
+}
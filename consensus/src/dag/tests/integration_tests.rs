@@ -0,0 +1,143 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::{
+        bootstrap::bootstrap_dag, dag_driver::DagPayloadConfig,
+        reliable_broadcast::DAGNetworkSender, tests::helpers::MockStorage,
+    },
+    experimental::buffer_manager::OrderedBlocks,
+    network::IncomingDAGRequest,
+    network_interface::ConsensusMsg,
+    payload_manager::PayloadManager,
+    test_utils::MockPayloadManager,
+    util::time_service::ClockTimeService,
+};
+use anyhow::bail;
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_consensus_types::common::Author;
+use aptos_crypto::HashValue;
+use aptos_network::ProtocolId;
+use aptos_types::{
+    epoch_state::EpochState,
+    ledger_info::LedgerInfo,
+    on_chain_config::{DagConsensusConfigV1, ValidatorSet},
+    validator_verifier::random_validator_verifier,
+};
+use async_trait::async_trait;
+use futures::{channel::oneshot, StreamExt};
+use futures_channel::mpsc::unbounded;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// Delivers the RPCs directly to the DAG handler of the receiver.
+struct MockNetworkSender {
+    author: Author,
+    handlers: HashMap<Author, aptos_channel::Sender<Author, IncomingDAGRequest>>,
+}
+
+#[async_trait]
+impl DAGNetworkSender for MockNetworkSender {
+    async fn send_rpc(
+        &self,
+        receiver: Author,
+        message: ConsensusMsg,
+        timeout: Duration,
+    ) -> anyhow::Result<ConsensusMsg> {
+        let req = match message {
+            ConsensusMsg::DAGMessage(req) => req,
+            _ => bail!("unexpected message"),
+        };
+        let (response_sender, response_rx) = oneshot::channel();
+        let protocol = ProtocolId::ConsensusRpcBcs;
+        self.handlers[&receiver].push(self.author, IncomingDAGRequest {
+            req,
+            sender: self.author,
+            protocol,
+            response_sender,
+        })?;
+        let response_bytes = tokio::time::timeout(timeout, response_rx).await???;
+        protocol.from_bytes(&response_bytes)
+    }
+}
+
+#[tokio::test]
+async fn test_dag_ordering_agreement() {
+    const NUM_NODES: usize = 4;
+    const NUM_BLOCKS: usize = 5;
+
+    let (signers, verifier) = random_validator_verifier(NUM_NODES, None, false);
+    let validator_set = ValidatorSet::from(&verifier);
+    let epoch_state = Arc::new(EpochState { epoch: 1, verifier });
+    let genesis_ledger_info = LedgerInfo::mock_genesis(Some(validator_set));
+
+    let mut handler_txs = HashMap::new();
+    let mut handler_rxs = vec![];
+    for signer in &signers {
+        let (tx, rx) = aptos_channel::new(QueueStyle::FIFO, 100, None);
+        handler_txs.insert(signer.author(), tx);
+        handler_rxs.push(rx);
+    }
+
+    let mut ordered_rxs = vec![];
+    let mut shutdown_txs = vec![];
+    for (signer, dag_rpc_rx) in signers.iter().zip(handler_rxs) {
+        let (ordered_tx, ordered_rx) = unbounded::<OrderedBlocks>();
+        ordered_rxs.push(ordered_rx);
+        let (network_handler, dag_fetcher) = bootstrap_dag(
+            signer.author(),
+            signer.clone(),
+            epoch_state.clone(),
+            genesis_ledger_info.clone(),
+            Arc::new(MockStorage::new()),
+            Arc::new(MockNetworkSender {
+                author: signer.author(),
+                handlers: handler_txs.clone(),
+            }),
+            Arc::new(ClockTimeService::new(tokio::runtime::Handle::current())),
+            Arc::new(PayloadManager::DirectMempool),
+            Arc::new(MockPayloadManager::new(None)),
+            ordered_tx,
+            dag_rpc_rx,
+            DagConsensusConfigV1::default(),
+            DagPayloadConfig {
+                max_sending_txns_per_round: 100,
+                max_sending_size_per_round_bytes: 1024 * 1024,
+                payload_pull_max_poll_time: Duration::from_millis(10),
+            },
+            false,
+        );
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        shutdown_txs.push(shutdown_tx);
+        tokio::spawn(dag_fetcher.start());
+        tokio::spawn(network_handler.start(shutdown_rx));
+    }
+
+    let mut ordered_block_ids: Vec<Vec<HashValue>> = vec![];
+    for ordered_rx in ordered_rxs.iter_mut() {
+        let mut block_ids = vec![];
+        while block_ids.len() < NUM_BLOCKS {
+            let ordered_blocks = ordered_rx.next().await.unwrap();
+            for block in ordered_blocks.ordered_blocks {
+                assert_eq!(
+                    ordered_blocks
+                        .ordered_proof
+                        .ledger_info()
+                        .commit_info()
+                        .id(),
+                    block.id()
+                );
+                block_ids.push(block.id());
+            }
+        }
+        block_ids.truncate(NUM_BLOCKS);
+        ordered_block_ids.push(block_ids);
+    }
+    // all the nodes order the same blocks
+    assert!(ordered_block_ids.windows(2).all(|pair| pair[0] == pair[1]));
+
+    for shutdown_tx in shutdown_txs {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        shutdown_tx.send(ack_tx).unwrap();
+        ack_rx.await.unwrap();
+    }
+}
@@ -2,4 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod dag_test;
+mod fetcher_test;
+mod helpers;
+mod integration_tests;
+mod order_rule_tests;
+mod rb_handler_tests;
 mod reliable_broadcast_tests;
@@ -0,0 +1,165 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    adapter::Notifier,
+    anchor_election::RoundRobinAnchorElection,
+    dag_store::Dag,
+    order_rule::OrderRule,
+    tests::dag_test::new_node,
+    types::{CertifiedNode, NodeMetadata},
+};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_infallible::RwLock;
+use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+type OrderedNodes = (Vec<Arc<CertifiedNode>>, Vec<(Round, Author)>);
+
+struct TestNotifier {
+    tx: UnboundedSender<OrderedNodes>,
+}
+
+#[async_trait]
+impl Notifier for TestNotifier {
+    async fn send_ordered_nodes(
+        &mut self,
+        ordered_nodes: Vec<Arc<CertifiedNode>>,
+        failed_authors: Vec<(Round, Author)>,
+    ) -> anyhow::Result<()> {
+        self.tx.send((ordered_nodes, failed_authors))?;
+        Ok(())
+    }
+}
+
+fn create_order_rule(
+    num_validators: usize,
+) -> (
+    OrderRule,
+    Arc<RwLock<Dag>>,
+    Vec<Author>,
+    UnboundedReceiver<OrderedNodes>,
+) {
+    let (_, verifier) = random_validator_verifier(num_validators, None, false);
+    let validators = verifier.get_ordered_account_addresses();
+    let dag = Arc::new(RwLock::new(Dag::new(
+        verifier.address_to_validator_index().clone(),
+        1,
+    )));
+    let (tx, rx) = unbounded_channel();
+    let order_rule = OrderRule::new(
+        Arc::new(EpochState { epoch: 1, verifier }),
+        1,
+        dag.clone(),
+        Box::new(RoundRobinAnchorElection::new(validators.clone())),
+        Box::new(TestNotifier { tx }),
+        10,
+    );
+    (order_rule, dag, validators, rx)
+}
+
+/// Adds a node of `round` for each of `authors`, linking to all the nodes of the previous round.
+async fn add_round(
+    order_rule: &mut OrderRule,
+    dag: &Arc<RwLock<Dag>>,
+    round: Round,
+    authors: &[Author],
+) -> Vec<NodeMetadata> {
+    let parents: Vec<_> = dag
+        .read()
+        .get_nodes_by_round(round - 1)
+        .map(|node| node.metadata())
+        .collect();
+    let mut metadata = vec![];
+    for author in authors {
+        let node = new_node(round, *author, parents.clone());
+        metadata.push(node.metadata());
+        dag.write().add_node(node).unwrap();
+        order_rule
+            .process_new_node(metadata.last().unwrap())
+            .await
+            .unwrap();
+    }
+    metadata
+}
+
+#[tokio::test]
+async fn test_order_rule_orders_anchors() {
+    let (mut order_rule, dag, validators, mut rx) = create_order_rule(4);
+
+    let round_1 = add_round(&mut order_rule, &dag, 1, &validators).await;
+    assert!(rx.try_recv().is_err());
+
+    // f+1 = 2 votes are needed for the anchor of round 1
+    add_round(&mut order_rule, &dag, 2, &validators[0..1]).await;
+    assert!(rx.try_recv().is_err());
+    add_round(&mut order_rule, &dag, 2, &validators[1..]).await;
+    let (ordered, failed_authors) = rx.try_recv().unwrap();
+    assert_eq!(ordered.len(), 1);
+    assert_eq!(ordered[0].metadata(), round_1[0]);
+    assert!(failed_authors.is_empty());
+    assert!(rx.try_recv().is_err());
+    assert_eq!(order_rule.lowest_unordered_anchor_round(), 3);
+
+    let round_3 = add_round(&mut order_rule, &dag, 3, &validators).await;
+    add_round(&mut order_rule, &dag, 4, &validators).await;
+    let (ordered, failed_authors) = rx.try_recv().unwrap();
+    // the rest of round 1, round 2 and the anchor of round 3
+    assert_eq!(ordered.len(), 3 + 4 + 1);
+    assert_eq!(ordered.last().unwrap().metadata(), round_3[1]);
+    assert!(ordered
+        .windows(2)
+        .all(|pair| pair[0].round() <= pair[1].round()));
+    assert!(failed_authors.is_empty());
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_order_rule_skips_missing_anchor() {
+    let (mut order_rule, dag, validators, mut rx) = create_order_rule(4);
+
+    // the anchor of round 1 is missing
+    add_round(&mut order_rule, &dag, 1, &validators[1..]).await;
+    add_round(&mut order_rule, &dag, 2, &validators).await;
+    assert!(rx.try_recv().is_err());
+
+    let round_3 = add_round(&mut order_rule, &dag, 3, &validators).await;
+    add_round(&mut order_rule, &dag, 4, &validators).await;
+    let (ordered, failed_authors) = rx.try_recv().unwrap();
+    assert_eq!(ordered.len(), 3 + 4 + 1);
+    assert_eq!(ordered.last().unwrap().metadata(), round_3[1]);
+    assert_eq!(failed_authors, vec![(1, validators[0])]);
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_order_rule_orders_previous_anchors() {
+    let (mut order_rule, dag, validators, mut rx) = create_order_rule(4);
+
+    // only one vote for the anchor of round 1
+    let round_1 = add_round(&mut order_rule, &dag, 1, &validators).await;
+    add_round(&mut order_rule, &dag, 2, &validators[0..1]).await;
+    let parents: Vec<_> = round_1[1..].to_vec();
+    for author in &validators[1..] {
+        let node = new_node(2, *author, parents.clone());
+        let metadata = node.metadata();
+        dag.write().add_node(node).unwrap();
+        order_rule.process_new_node(&metadata).await.unwrap();
+    }
+    assert!(rx.try_recv().is_err());
+
+    // the anchor of round 3 reaches the anchor of round 1 through the node of validator 0
+    let round_3 = add_round(&mut order_rule, &dag, 3, &validators).await;
+    add_round(&mut order_rule, &dag, 4, &validators).await;
+    let (ordered, failed_authors) = rx.try_recv().unwrap();
+    assert_eq!(ordered, vec![dag
+        .read()
+        .get_node(round_1[0].digest())
+        .unwrap()]);
+    assert!(failed_authors.is_empty());
+    let (ordered, _) = rx.try_recv().unwrap();
+    assert_eq!(ordered.last().unwrap().metadata(), round_3[1]);
+    assert_eq!(ordered.len(), 3 + 4 + 1);
+}
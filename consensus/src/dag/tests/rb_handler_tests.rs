@@ -0,0 +1,90 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    dag_fetcher::{FetchRequester, LocalFetchRequest},
+    dag_store::Dag,
+    rb_handler::NodeBroadcastHandler,
+    storage::DAGStorage,
+    tests::helpers::MockStorage,
+    types::Node,
+};
+use aptos_consensus_types::common::Payload;
+use aptos_infallible::RwLock;
+use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
+use std::sync::Arc;
+
+#[test]
+fn test_node_broadcast_receiver() {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+    let author_to_index = verifier.address_to_validator_index().clone();
+    let epoch_state = Arc::new(EpochState { epoch: 1, verifier });
+    let storage = Arc::new(MockStorage::new());
+    let dag = Arc::new(RwLock::new(Dag::new(author_to_index, 1)));
+    let (request_tx, mut request_rx) = tokio::sync::mpsc::channel(10);
+
+    let mut rb_receiver = NodeBroadcastHandler::new(
+        dag,
+        Arc::new(signers[3].clone()),
+        epoch_state.clone(),
+        storage.clone(),
+        Arc::new(FetchRequester::new(request_tx)),
+        false,
+    );
+
+    let node = Node::new(1, 1, signers[0].author(), 0, Payload::empty(false), vec![]);
+    let vote = rb_receiver
+        .process(node.clone(), signers[0].author())
+        .unwrap();
+    assert!(vote
+        .verify(signers[3].author(), &epoch_state.verifier)
+        .is_ok());
+    assert_eq!(storage.get_votes().unwrap().len(), 1);
+
+    // the same node gets the same vote
+    assert_eq!(
+        rb_receiver
+            .process(node.clone(), signers[0].author())
+            .unwrap(),
+        vote
+    );
+    // a node sent by another validator is rejected
+    assert!(rb_receiver.process(node, signers[1].author()).is_err());
+    // an equivocating node is rejected
+    let equivocating_node = Node::new(1, 1, signers[0].author(), 1, Payload::empty(false), vec![]);
+    assert!(rb_receiver
+        .process(equivocating_node, signers[0].author())
+        .is_err());
+
+    // a node with missing parents is handed to the fetcher
+    let parents = signers[0..3]
+        .iter()
+        .map(|signer| Node::new(1, 1, signer.author(), 0, Payload::empty(false), vec![]).metadata())
+        .collect();
+    let node = Node::new(1, 2, signers[1].author(), 0, Payload::empty(false), parents);
+    assert!(rb_receiver
+        .process(node.clone(), signers[1].author())
+        .is_err());
+    match request_rx.try_recv().unwrap() {
+        LocalFetchRequest::Node(fetch_node) => assert_eq!(fetch_node, node),
+        LocalFetchRequest::CertifiedNode(_) => panic!("unexpected fetch request"),
+    }
+
+    // the votes survive a restart
+    let (request_tx, _request_rx) = tokio::sync::mpsc::channel(10);
+    let mut rb_receiver = NodeBroadcastHandler::new(
+        Arc::new(RwLock::new(Dag::new(
+            epoch_state.verifier.address_to_validator_index().clone(),
+            1,
+        ))),
+        Arc::new(signers[3].clone()),
+        epoch_state,
+        storage,
+        Arc::new(FetchRequester::new(request_tx)),
+        false,
+    );
+    let equivocating_node = Node::new(1, 1, signers[0].author(), 2, Payload::empty(false), vec![]);
+    assert!(rb_receiver
+        .process(equivocating_node, signers[0].author())
+        .is_err());
+}
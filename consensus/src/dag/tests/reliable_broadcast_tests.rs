@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    dag::reliable_broadcast::{BroadcastStatus, DAGMessage, DAGNetworkSender, ReliableBroadcast},
+    dag::reliable_broadcast::{BroadcastStatus, DAGNetworkSender, RBMessage, ReliableBroadcast},
     network_interface::ConsensusMsg,
 };
 use anyhow::bail;
@@ -21,7 +21,7 @@ use tokio::sync::oneshot;
 #[derive(Serialize, Deserialize, Clone)]
 struct TestMessage(Vec<u8>);
 
-impl DAGMessage for TestMessage {
+impl RBMessage for TestMessage {
     fn from_network_message(msg: ConsensusMsg) -> anyhow::Result<Self> {
         match msg {
             ConsensusMsg::DAGTestMessage(payload) => Ok(Self(payload)),
@@ -37,7 +37,7 @@ impl DAGMessage for TestMessage {
 #[derive(Serialize, Deserialize, Clone)]
 struct TestAck;
 
-impl DAGMessage for TestAck {
+impl RBMessage for TestAck {
     fn from_network_message(_: ConsensusMsg) -> anyhow::Result<Self> {
         Ok(TestAck)
     }
@@ -52,17 +52,19 @@ struct TestBroadcastStatus {
     received: HashSet<Author>,
 }
 
-impl BroadcastStatus for TestBroadcastStatus {
-    type Ack = TestAck;
-    type Aggregated = HashSet<Author>;
-    type Message = TestMessage;
-
-    fn empty(receivers: Vec<Author>) -> Self {
+impl TestBroadcastStatus {
+    fn new(receivers: &[Author]) -> Self {
         Self {
             threshold: receivers.len(),
             received: HashSet::new(),
         }
     }
+}
+
+impl BroadcastStatus for TestBroadcastStatus {
+    type Ack = TestAck;
+    type Aggregated = HashSet<Author>;
+    type Message = TestMessage;
 
     fn add(&mut self, peer: Author, _ack: Self::Ack) -> anyhow::Result<Option<Self::Aggregated>> {
         self.received.insert(peer);
@@ -124,7 +126,12 @@ async fn test_reliable_broadcast() {
     let message = TestMessage(vec![1, 2, 3]);
     let (tx, rx) = oneshot::channel();
     let (_cancel_tx, cancel_rx) = oneshot::channel();
-    tokio::spawn(rb.broadcast::<TestBroadcastStatus>(message, tx, cancel_rx));
+    tokio::spawn(rb.broadcast(
+        message,
+        TestBroadcastStatus::new(&validators),
+        tx,
+        cancel_rx,
+    ));
     assert_eq!(rx.await.unwrap(), validators.into_iter().collect());
 }
 
@@ -141,13 +148,23 @@ async fn test_reliable_broadcast_cancel() {
     let (tx, rx) = oneshot::channel();
     let (cancel_tx, cancel_rx) = oneshot::channel();
     cancel_tx.send(()).unwrap();
-    tokio::spawn(rb.broadcast::<TestBroadcastStatus>(message.clone(), tx, cancel_rx));
+    tokio::spawn(rb.broadcast(
+        message.clone(),
+        TestBroadcastStatus::new(&validators),
+        tx,
+        cancel_rx,
+    ));
     assert!(rx.await.is_err());
 
     // implicit drop cancel
     let (tx, rx) = oneshot::channel();
     let (cancel_tx, cancel_rx) = oneshot::channel();
     drop(cancel_tx);
-    tokio::spawn(rb.broadcast::<TestBroadcastStatus>(message, tx, cancel_rx));
+    tokio::spawn(rb.broadcast(
+        message,
+        TestBroadcastStatus::new(&validators),
+        tx,
+        cancel_rx,
+    ));
     assert!(rx.await.is_err());
 }
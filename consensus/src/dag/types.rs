@@ -0,0 +1,637 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::reliable_broadcast::{BroadcastStatus, RBMessage},
+    network_interface::ConsensusMsg,
+};
+use anyhow::{bail, ensure};
+use aptos_consensus_types::common::{Author, Payload, Round};
+use aptos_crypto::{
+    bls12381,
+    hash::{CryptoHash, CryptoHasher},
+    HashValue,
+};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_types::{
+    aggregate_signature::{AggregateSignature, PartialSignatures},
+    epoch_state::EpochState,
+    validator_signer::ValidatorSigner,
+    validator_verifier::ValidatorVerifier,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    ops::Deref,
+    sync::Arc,
+};
+
+/// Identifies the node an author proposed in a round. An honest author proposes at most one node
+/// per round, so it's used to detect equivocation.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct NodeId {
+    epoch: u64,
+    round: Round,
+    author: Author,
+}
+
+impl NodeId {
+    pub fn new(epoch: u64, round: Round, author: Author) -> Self {
+        Self {
+            epoch,
+            round,
+            author,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn round(&self) -> Round {
+        self.round
+    }
+
+    pub fn author(&self) -> &Author {
+        &self.author
+    }
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "NodeId: [epoch: {}, round: {}, author: {}]",
+            self.epoch, self.round, self.author
+        )
+    }
+}
+
+/// Represents the metadata about the node, without payload and parents from Node
+#[derive(
+    Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, CryptoHasher, BCSCryptoHash,
+)]
+pub struct NodeMetadata {
+    epoch: u64,
+    round: Round,
+    author: Author,
+    timestamp: u64,
+    digest: HashValue,
+}
+
+impl NodeMetadata {
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn round(&self) -> Round {
+        self.round
+    }
+
+    pub fn author(&self) -> &Author {
+        &self.author
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn digest(&self) -> &HashValue {
+        &self.digest
+    }
+
+    pub fn id(&self) -> NodeId {
+        NodeId::new(self.epoch, self.round, self.author)
+    }
+}
+
+/// Node representation in the DAG, parents contain 2f+1 strong links (links to previous round)
+/// plus weak links (links to lower round)
+#[derive(Clone, Serialize, Deserialize, CryptoHasher, Debug, PartialEq, Eq)]
+pub struct Node {
+    metadata: NodeMetadata,
+    payload: Payload,
+    parents: Vec<NodeMetadata>,
+}
+
+impl Node {
+    pub fn new(
+        epoch: u64,
+        round: Round,
+        author: Author,
+        timestamp: u64,
+        payload: Payload,
+        parents: Vec<NodeMetadata>,
+    ) -> Self {
+        let digest = Self::calculate_digest(epoch, round, author, timestamp, &payload, &parents);
+
+        Self {
+            metadata: NodeMetadata {
+                epoch,
+                round,
+                author,
+                timestamp,
+                digest,
+            },
+            payload,
+            parents,
+        }
+    }
+
+    /// Calculate the node digest based on all fields in the node
+    fn calculate_digest(
+        epoch: u64,
+        round: Round,
+        author: Author,
+        timestamp: u64,
+        payload: &Payload,
+        parents: &Vec<NodeMetadata>,
+    ) -> HashValue {
+        #[derive(Serialize)]
+        struct NodeWithoutDigest<'a> {
+            epoch: u64,
+            round: Round,
+            author: Author,
+            timestamp: u64,
+            payload: &'a Payload,
+            parents: &'a Vec<NodeMetadata>,
+        }
+
+        impl<'a> CryptoHash for NodeWithoutDigest<'a> {
+            type Hasher = NodeHasher;
+
+            fn hash(&self) -> HashValue {
+                let mut state = Self::Hasher::new();
+                let bytes = bcs::to_bytes(&self).expect("Unable to serialize node");
+                state.update(&bytes);
+                state.finish()
+            }
+        }
+
+        let node_with_out_digest = NodeWithoutDigest {
+            epoch,
+            round,
+            author,
+            timestamp,
+            payload,
+            parents,
+        };
+        node_with_out_digest.hash()
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.metadata.digest
+    }
+
+    pub fn metadata(&self) -> NodeMetadata {
+        self.metadata.clone()
+    }
+
+    pub fn id(&self) -> NodeId {
+        self.metadata.id()
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.metadata.epoch
+    }
+
+    pub fn round(&self) -> Round {
+        self.metadata.round
+    }
+
+    pub fn author(&self) -> &Author {
+        &self.metadata.author
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.metadata.timestamp
+    }
+
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    pub fn parents(&self) -> &[NodeMetadata] {
+        &self.parents
+    }
+
+    /// Checks that the digest matches the content of the node and that the node links to enough
+    /// nodes of the previous round. Nodes of the first round of the epoch have no parents.
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            self.digest()
+                == Self::calculate_digest(
+                    self.metadata.epoch,
+                    self.metadata.round,
+                    self.metadata.author,
+                    self.metadata.timestamp,
+                    &self.payload,
+                    &self.parents,
+                ),
+            "invalid digest"
+        );
+        ensure!(
+            verifier.get_voting_power(self.author()).is_some(),
+            "unknown author"
+        );
+
+        let mut parent_authors = HashSet::new();
+        for parent in &self.parents {
+            ensure!(
+                parent.epoch == self.epoch(),
+                "parent from a different epoch"
+            );
+            ensure!(parent.round < self.round(), "parent round too high");
+            ensure!(
+                parent_authors.insert(parent.author),
+                "multiple parents from the same author"
+            );
+        }
+        if self.round() > 1 {
+            verifier
+                .check_voting_power(
+                    self.parents
+                        .iter()
+                        .filter(|parent| parent.round + 1 == self.round())
+                        .map(|parent| &parent.author),
+                )
+                .map_err(|e| anyhow::anyhow!("not enough strong links: {}", e))?;
+        } else {
+            ensure!(self.parents.is_empty(), "first round node with parents");
+        }
+        Ok(())
+    }
+}
+
+/// Quorum signatures over the node metadata
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct NodeCertificate {
+    metadata: NodeMetadata,
+    signatures: AggregateSignature,
+}
+
+impl NodeCertificate {
+    pub fn new(metadata: NodeMetadata, signatures: AggregateSignature) -> Self {
+        Self {
+            metadata,
+            signatures,
+        }
+    }
+
+    pub fn metadata(&self) -> &NodeMetadata {
+        &self.metadata
+    }
+
+    pub fn signatures(&self) -> &AggregateSignature {
+        &self.signatures
+    }
+
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        Ok(verifier.verify_multi_signatures(&self.metadata, &self.signatures)?)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct CertifiedNode {
+    node: Node,
+    certificate: NodeCertificate,
+}
+
+impl CertifiedNode {
+    pub fn new(node: Node, certificate: NodeCertificate) -> Self {
+        Self { node, certificate }
+    }
+
+    pub fn certificate(&self) -> &NodeCertificate {
+        &self.certificate
+    }
+
+    /// Returns the validators that signed the node, which are expected to have its causal history.
+    pub fn signers(&self, verifier: &ValidatorVerifier) -> Vec<Author> {
+        self.certificate
+            .signatures
+            .get_voter_addresses(&verifier.get_ordered_account_addresses())
+    }
+
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            self.certificate.metadata == self.node.metadata,
+            "certificate doesn't match the node"
+        );
+        self.node.verify(verifier)?;
+        self.certificate.verify(verifier)
+    }
+}
+
+impl Deref for CertifiedNode {
+    type Target = Node;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+/// A validator's signature on a node, promising to not vote for another node of the same author
+/// in the same round.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Vote {
+    metadata: NodeMetadata,
+    signature: bls12381::Signature,
+}
+
+impl Vote {
+    pub fn new(metadata: NodeMetadata, signer: &ValidatorSigner) -> anyhow::Result<Self> {
+        let signature = signer.sign(&metadata)?;
+        Ok(Self {
+            metadata,
+            signature,
+        })
+    }
+
+    pub fn metadata(&self) -> &NodeMetadata {
+        &self.metadata
+    }
+
+    pub fn signature(&self) -> &bls12381::Signature {
+        &self.signature
+    }
+
+    pub fn verify(&self, author: Author, verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        Ok(verifier.verify(author, &self.metadata, &self.signature)?)
+    }
+}
+
+/// Aggregates the votes on a node into a certificate.
+pub struct SignatureBuilder {
+    metadata: NodeMetadata,
+    partial_signatures: PartialSignatures,
+    epoch_state: Arc<EpochState>,
+}
+
+impl SignatureBuilder {
+    pub fn new(metadata: NodeMetadata, epoch_state: Arc<EpochState>) -> Self {
+        Self {
+            metadata,
+            partial_signatures: PartialSignatures::empty(),
+            epoch_state,
+        }
+    }
+}
+
+impl BroadcastStatus for SignatureBuilder {
+    type Ack = Vote;
+    type Aggregated = NodeCertificate;
+    type Message = Node;
+
+    fn add(&mut self, peer: Author, ack: Self::Ack) -> anyhow::Result<Option<Self::Aggregated>> {
+        ensure!(self.metadata == ack.metadata, "vote for a different node");
+        ack.verify(peer, &self.epoch_state.verifier)?;
+        self.partial_signatures.add_signature(peer, ack.signature);
+        let verifier = &self.epoch_state.verifier;
+        if verifier
+            .check_voting_power(self.partial_signatures.signatures().keys())
+            .is_ok()
+        {
+            let signatures = verifier.aggregate_signatures(&self.partial_signatures)?;
+            Ok(Some(NodeCertificate::new(
+                self.metadata.clone(),
+                signatures,
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Acknowledges that a certified node was added to the DAG of the sender.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct CertifiedAck {
+    epoch: u64,
+}
+
+impl CertifiedAck {
+    pub fn new(epoch: u64) -> Self {
+        Self { epoch }
+    }
+}
+
+/// Completes once all validators acknowledged the certified node.
+pub struct CertificateAckState {
+    num_validators: usize,
+    received: HashSet<Author>,
+}
+
+impl CertificateAckState {
+    pub fn new(num_validators: usize) -> Self {
+        Self {
+            num_validators,
+            received: HashSet::new(),
+        }
+    }
+}
+
+impl BroadcastStatus for CertificateAckState {
+    type Ack = CertifiedAck;
+    type Aggregated = ();
+    type Message = CertifiedNode;
+
+    fn add(&mut self, peer: Author, _ack: Self::Ack) -> anyhow::Result<Option<Self::Aggregated>> {
+        self.received.insert(peer);
+        if self.received.len() == self.num_validators {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The nodes a validator has in its DAG, starting from `first_round`, indexed by round and then by
+/// validator index.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct DagSnapshotBitmask {
+    bitmask: Vec<Vec<bool>>,
+    first_round: Round,
+}
+
+impl DagSnapshotBitmask {
+    pub fn new(first_round: Round, bitmask: Vec<Vec<bool>>) -> Self {
+        Self {
+            bitmask,
+            first_round,
+        }
+    }
+
+    pub fn first_round(&self) -> Round {
+        self.first_round
+    }
+
+    pub fn has(&self, round: Round, author_index: usize) -> bool {
+        round
+            .checked_sub(self.first_round)
+            .and_then(|offset| self.bitmask.get(offset as usize))
+            .and_then(|round_bitmask| round_bitmask.get(author_index))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+/// Requests the causal history of the targets, excluding the nodes the requester already has.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct RemoteFetchRequest {
+    epoch: u64,
+    targets: Vec<NodeMetadata>,
+    exists_bitmask: DagSnapshotBitmask,
+}
+
+impl RemoteFetchRequest {
+    pub fn new(epoch: u64, targets: Vec<NodeMetadata>, exists_bitmask: DagSnapshotBitmask) -> Self {
+        Self {
+            epoch,
+            targets,
+            exists_bitmask,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn targets(&self) -> &[NodeMetadata] {
+        &self.targets
+    }
+
+    pub fn exists_bitmask(&self) -> &DagSnapshotBitmask {
+        &self.exists_bitmask
+    }
+}
+
+/// The missing nodes of the requested causal history, ordered by round.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct FetchResponse {
+    epoch: u64,
+    certified_nodes: Vec<CertifiedNode>,
+}
+
+impl FetchResponse {
+    pub fn new(epoch: u64, certified_nodes: Vec<CertifiedNode>) -> Self {
+        Self {
+            epoch,
+            certified_nodes,
+        }
+    }
+
+    pub fn certified_nodes(self) -> Vec<CertifiedNode> {
+        self.certified_nodes
+    }
+
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        for node in &self.certified_nodes {
+            ensure!(node.epoch() == self.epoch, "node from a different epoch");
+            node.verify(verifier)?;
+        }
+        Ok(())
+    }
+}
+
+/// The messages exchanged by the DAG protocol, all of them are sent through RPCs.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum DAGMessage {
+    NodeMsg(Node),
+    VoteMsg(Vote),
+    CertifiedNodeMsg(CertifiedNode),
+    CertifiedAckMsg(CertifiedAck),
+    FetchRequest(RemoteFetchRequest),
+    FetchResponse(FetchResponse),
+}
+
+impl DAGMessage {
+    pub fn name(&self) -> &str {
+        match self {
+            DAGMessage::NodeMsg(_) => "NodeMsg",
+            DAGMessage::VoteMsg(_) => "VoteMsg",
+            DAGMessage::CertifiedNodeMsg(_) => "CertifiedNodeMsg",
+            DAGMessage::CertifiedAckMsg(_) => "CertifiedAckMsg",
+            DAGMessage::FetchRequest(_) => "FetchRequest",
+            DAGMessage::FetchResponse(_) => "FetchResponse",
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        match self {
+            DAGMessage::NodeMsg(node) => node.epoch(),
+            DAGMessage::VoteMsg(vote) => vote.metadata.epoch,
+            DAGMessage::CertifiedNodeMsg(node) => node.epoch(),
+            DAGMessage::CertifiedAckMsg(ack) => ack.epoch,
+            DAGMessage::FetchRequest(request) => request.epoch,
+            DAGMessage::FetchResponse(response) => response.epoch,
+        }
+    }
+
+    pub fn into_network_message(self) -> ConsensusMsg {
+        ConsensusMsg::DAGMessage(DAGNetworkMessage {
+            epoch: self.epoch(),
+            data: bcs::to_bytes(&self).expect("Unable to serialize DAG message"),
+        })
+    }
+
+    pub fn from_network_message(msg: ConsensusMsg) -> anyhow::Result<Self> {
+        match msg {
+            ConsensusMsg::DAGMessage(msg) => msg.try_into(),
+            _ => bail!("unexpected consensus message: {}", msg.name()),
+        }
+    }
+}
+
+/// The network envelope of the DAG messages, the epoch is kept outside of the serialized message
+/// so that messages of other epochs can be dropped without deserializing them.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DAGNetworkMessage {
+    epoch: u64,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+impl DAGNetworkMessage {
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+}
+
+impl TryFrom<DAGNetworkMessage> for DAGMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: DAGNetworkMessage) -> Result<Self, Self::Error> {
+        let message: DAGMessage = bcs::from_bytes(&msg.data)?;
+        ensure!(message.epoch() == msg.epoch, "epoch mismatch");
+        Ok(message)
+    }
+}
+
+macro_rules! impl_rb_message {
+    ($($variant:ident($message:ty)),* $(,)?) => {
+        $(
+            impl RBMessage for $message {
+                fn from_network_message(msg: ConsensusMsg) -> anyhow::Result<Self> {
+                    match DAGMessage::from_network_message(msg)? {
+                        DAGMessage::$variant(message) => Ok(message),
+                        message => bail!("unexpected DAG message: {}", message.name()),
+                    }
+                }
+
+                fn into_network_message(self) -> ConsensusMsg {
+                    DAGMessage::$variant(self).into_network_message()
+                }
+            }
+        )*
+    };
+}
+
+impl_rb_message!(
+    NodeMsg(Node),
+    VoteMsg(Vote),
+    CertifiedNodeMsg(CertifiedNode),
+    CertifiedAckMsg(CertifiedAck),
+    FetchRequest(RemoteFetchRequest),
+    FetchResponse(FetchResponse),
+);
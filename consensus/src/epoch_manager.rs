@@ -8,11 +8,13 @@ use crate::{
         BlockStore,
     },
     counters,
+    dag::{bootstrap_dag, DagCommitSigner, DagPayloadConfig},
     error::{error_kind, DbError},
     experimental::{
         buffer_manager::{OrderedBlocks, ResetRequest},
         decoupled_execution_utils::prepare_phases_and_buffer_manager,
         ordering_state_computer::OrderingStateComputer,
+        signing_phase::CommitSignerProvider,
     },
     liveness::{
         cached_proposer_election::CachedProposerElection,
@@ -32,8 +34,8 @@ use crate::{
     metrics_safety_rules::MetricsSafetyRules,
     monitor,
     network::{
-        IncomingBatchRetrievalRequest, IncomingBlockRetrievalRequest, IncomingRpcRequest,
-        NetworkReceivers, NetworkSender,
+        IncomingBatchRetrievalRequest, IncomingBlockRetrievalRequest, IncomingDAGRequest,
+        IncomingRpcRequest, NetworkReceivers, NetworkSender,
     },
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     payload_client::QuorumStoreClient,
@@ -57,13 +59,16 @@ use aptos_config::config::{ConsensusConfig, NodeConfig};
 use aptos_consensus_types::{
     common::{Author, Round},
     epoch_retrieval::EpochRetrievalRequest,
+    request_response::GetPayloadCommand,
 };
 use aptos_event_notifications::ReconfigNotificationListener;
+use aptos_global_constants::CONSENSUS_KEY;
 use aptos_infallible::{duration_since_epoch, Mutex};
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_network::{application::interface::NetworkClient, protocols::network::Event};
 use aptos_safety_rules::SafetyRulesManager;
+use aptos_secure_storage::{KVStorage, Storage};
 use aptos_types::{
     account_address::AccountAddress,
     epoch_change::EpochChangeProof,
@@ -72,6 +77,7 @@ use aptos_types::{
         LeaderReputationType, OnChainConfigPayload, OnChainConsensusConfig, OnChainExecutionConfig,
         ProposerElectionType, ValidatorSet,
     },
+    validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
 };
 use fail::fail_point;
@@ -140,6 +146,9 @@ pub struct EpochManager {
     bounded_executor: BoundedExecutor,
     // recovery_mode is set to true when the recovery manager is spawned
     recovery_mode: bool,
+    // channels to the DAG consensus handler
+    dag_rpc_tx: Option<aptos_channel::Sender<AccountAddress, IncomingDAGRequest>>,
+    dag_shutdown_tx: Option<oneshot::Sender<oneshot::Sender<()>>>,
 }
 
 impl EpochManager {
@@ -186,6 +195,8 @@ impl EpochManager {
             batch_retrieval_tx: None,
             bounded_executor,
             recovery_mode: false,
+            dag_rpc_tx: None,
+            dag_shutdown_tx: None,
         }
     }
 
@@ -222,7 +233,17 @@ impl EpochManager {
             .verifier
             .get_ordered_account_addresses_iter()
             .collect::<Vec<_>>();
-        match &onchain_config.proposer_election_type() {
+        let proposer_election_type = match onchain_config.proposer_election_type() {
+            Some(proposer_election_type) => proposer_election_type,
+            None => {
+                // Only DAG consensus has no proposer election, and it never creates one.
+                error!(
+                    "On-chain consensus config has no proposer election, using rotating proposer"
+                );
+                return Box::new(RotatingProposer::new(proposers, 1));
+            },
+        };
+        match proposer_election_type {
             ProposerElectionType::RotatingProposer(contiguous_rounds) => {
                 Box::new(RotatingProposer::new(proposers, *contiguous_rounds))
             },
@@ -480,12 +501,13 @@ impl EpochManager {
     }
 
     /// this function spawns the phases and a buffer manager
-    /// it sets `self.commit_msg_tx` to a new aptos_channel::Sender and returns an OrderingStateComputer
+    /// it sets `self.commit_msg_tx` to a new aptos_channel::Sender and returns the channel to send
+    /// the ordered blocks to the buffer manager
     fn spawn_decoupled_execution(
         &mut self,
-        safety_rules_container: Arc<Mutex<MetricsSafetyRules>>,
+        commit_signer_provider: Arc<dyn CommitSignerProvider>,
         verifier: ValidatorVerifier,
-    ) -> OrderingStateComputer {
+    ) -> UnboundedSender<OrderedBlocks> {
         let network_sender = NetworkSender::new(
             self.author,
             self.network_sender.clone(),
@@ -503,13 +525,13 @@ impl EpochManager {
         );
 
         self.buffer_manager_msg_tx = Some(commit_msg_tx);
-        self.buffer_manager_reset_tx = Some(reset_tx.clone());
+        self.buffer_manager_reset_tx = Some(reset_tx);
 
         let (execution_phase, signing_phase, persisting_phase, buffer_manager) =
             prepare_phases_and_buffer_manager(
                 self.author,
                 self.commit_state_computer.clone(),
-                safety_rules_container,
+                commit_signer_provider,
                 network_sender,
                 commit_msg_rx,
                 self.commit_state_computer.clone(),
@@ -523,7 +545,7 @@ impl EpochManager {
         tokio::spawn(persisting_phase.start());
         tokio::spawn(buffer_manager.start());
 
-        OrderingStateComputer::new(block_tx, self.commit_state_computer.clone(), reset_tx)
+        block_tx
     }
 
     async fn shutdown_current_processor(&mut self) {
@@ -539,6 +561,18 @@ impl EpochManager {
         }
         self.round_manager_tx = None;
 
+        if let Some(close_tx) = self.dag_shutdown_tx.take() {
+            // Release the previous DAG handler, which aborts its pending broadcast
+            let (ack_tx, ack_rx) = oneshot::channel();
+            close_tx
+                .send(ack_tx)
+                .expect("[EpochManager] Fail to drop DAG handler");
+            ack_rx
+                .await
+                .expect("[EpochManager] Fail to drop DAG handler");
+        }
+        self.dag_rpc_tx = None;
+
         // Shutdown the previous buffer manager, to release the SafetyRule client
         self.buffer_manager_msg_tx = None;
         if let Some(mut tx) = self.buffer_manager_reset_tx.take() {
@@ -653,30 +687,11 @@ impl EpochManager {
         let (consensus_to_quorum_store_tx, consensus_to_quorum_store_rx) =
             mpsc::channel(self.config.intra_consensus_channel_buffer_size);
 
-        let mut quorum_store_builder = if self.quorum_store_enabled {
-            info!("Building QuorumStore");
-            QuorumStoreBuilder::QuorumStore(InnerBuilder::new(
-                self.epoch(),
-                self.author,
-                epoch_state.verifier.len() as u64,
-                self.config.quorum_store.clone(),
-                consensus_to_quorum_store_rx,
-                self.quorum_store_to_mempool_sender.clone(),
-                self.config.mempool_txn_pull_timeout_ms,
-                self.storage.aptos_db().clone(),
-                network_sender.clone(),
-                epoch_state.verifier.clone(),
-                self.config.safety_rules.backend.clone(),
-                self.quorum_store_storage.clone(),
-            ))
-        } else {
-            info!("Building DirectMempool");
-            QuorumStoreBuilder::DirectMempool(DirectMempoolInnerBuilder::new(
-                consensus_to_quorum_store_rx,
-                self.quorum_store_to_mempool_sender.clone(),
-                self.config.mempool_txn_pull_timeout_ms,
-            ))
-        };
+        let mut quorum_store_builder = self.create_quorum_store_builder(
+            &epoch_state,
+            consensus_to_quorum_store_rx,
+            network_sender.clone(),
+        );
 
         let (payload_manager, quorum_store_msg_tx) = quorum_store_builder.init_payload_manager();
         let transaction_shuffler =
@@ -700,9 +715,16 @@ impl EpochManager {
             transaction_deduper,
         );
         let state_computer = if onchain_consensus_config.decoupled_execution() {
-            Arc::new(self.spawn_decoupled_execution(
+            let block_tx = self.spawn_decoupled_execution(
                 safety_rules_container.clone(),
                 epoch_state.verifier.clone(),
+            );
+            Arc::new(OrderingStateComputer::new(
+                block_tx,
+                self.commit_state_computer.clone(),
+                self.buffer_manager_reset_tx
+                    .clone()
+                    .expect("buffer manager is spawned"),
             ))
         } else {
             self.commit_state_computer.clone()
@@ -809,10 +831,18 @@ impl EpochManager {
 
         self.epoch_state = Some(Arc::new(epoch_state.clone()));
 
+        let consensus_config = onchain_consensus_config.unwrap_or_default();
+        let execution_config = onchain_execution_config.unwrap_or_default();
+        if consensus_config.is_dag_enabled() {
+            self.quorum_store_enabled = self.enable_quorum_store(&consensus_config);
+            self.recovery_mode = false;
+            self.start_new_epoch_with_dag(epoch_state, consensus_config, execution_config)
+                .await;
+            return;
+        }
+
         match self.storage.start() {
             LivenessStorageData::FullRecoveryData(initial_data) => {
-                let consensus_config = onchain_consensus_config.unwrap_or_default();
-                let execution_config = onchain_execution_config.unwrap_or_default();
                 self.quorum_store_enabled = self.enable_quorum_store(&consensus_config);
                 self.recovery_mode = false;
                 self.start_round_manager(
@@ -830,6 +860,154 @@ impl EpochManager {
         }
     }
 
+    async fn start_new_epoch_with_dag(
+        &mut self,
+        epoch_state: EpochState,
+        onchain_consensus_config: OnChainConsensusConfig,
+        onchain_execution_config: OnChainExecutionConfig,
+    ) {
+        let epoch = epoch_state.epoch;
+        counters::EPOCH.set(epoch_state.epoch as i64);
+        counters::CURRENT_EPOCH_VALIDATORS.set(epoch_state.verifier.len() as i64);
+        info!(
+            epoch = epoch_state.epoch,
+            validators = epoch_state.verifier.to_string(),
+            "Starting new epoch with DAG consensus",
+        );
+        let epoch_state = Arc::new(epoch_state);
+        let dag_config = onchain_consensus_config
+            .dag_config()
+            .cloned()
+            .expect("DAG consensus is enabled");
+
+        let signer = {
+            let storage: Storage = (&self.config.safety_rules.backend).into();
+            if let Err(error) = storage.available() {
+                panic!("Storage is not available: {:?}", error);
+            }
+            let private_key = storage
+                .get(CONSENSUS_KEY)
+                .map(|v| v.value)
+                .expect("Unable to get private key");
+            ValidatorSigner::new(self.author, private_key)
+        };
+
+        let network_sender = NetworkSender::new(
+            self.author,
+            self.network_sender.clone(),
+            self.self_sender.clone(),
+            epoch_state.verifier.clone(),
+        );
+
+        // Start QuorumStore
+        let (consensus_to_quorum_store_tx, consensus_to_quorum_store_rx) =
+            mpsc::channel(self.config.intra_consensus_channel_buffer_size);
+        let mut quorum_store_builder = self.create_quorum_store_builder(
+            &epoch_state,
+            consensus_to_quorum_store_rx,
+            network_sender.clone(),
+        );
+        let (payload_manager, quorum_store_msg_tx) = quorum_store_builder.init_payload_manager();
+        self.quorum_store_msg_tx = quorum_store_msg_tx;
+        let payload_client = QuorumStoreClient::new(
+            consensus_to_quorum_store_tx,
+            self.config.quorum_store_pull_timeout_ms,
+            self.config.wait_for_full_blocks_above_recent_fill_threshold,
+            self.config.wait_for_full_blocks_above_pending_blocks,
+        );
+        self.commit_state_computer.new_epoch(
+            &epoch_state,
+            payload_manager.clone(),
+            create_transaction_shuffler(onchain_execution_config.transaction_shuffler_type()),
+            onchain_execution_config.block_gas_limit(),
+            create_transaction_deduper(onchain_execution_config.transaction_deduper_type()),
+        );
+        if let Some((quorum_store_coordinator_tx, batch_retrieval_rx)) =
+            quorum_store_builder.start()
+        {
+            self.quorum_store_coordinator_tx = Some(quorum_store_coordinator_tx);
+            self.batch_retrieval_tx = Some(batch_retrieval_rx);
+        }
+
+        // The blocks ordered by the DAG are executed and committed by the buffer manager
+        let block_tx = self.spawn_decoupled_execution(
+            Arc::new(DagCommitSigner::new(signer.clone())),
+            epoch_state.verifier.clone(),
+        );
+
+        let latest_ledger_info = self
+            .storage
+            .aptos_db()
+            .get_latest_ledger_info()
+            .expect("could not get latest ledger info");
+        let (dag_rpc_tx, dag_rpc_rx) = aptos_channel::new(QueueStyle::FIFO, 10, None);
+        self.dag_rpc_tx = Some(dag_rpc_tx);
+        let (dag_shutdown_tx, dag_shutdown_rx) = oneshot::channel();
+        self.dag_shutdown_tx = Some(dag_shutdown_tx);
+
+        let (network_handler, dag_fetcher) = bootstrap_dag(
+            self.author,
+            signer,
+            epoch_state,
+            latest_ledger_info.ledger_info().clone(),
+            self.storage.consensus_db(),
+            Arc::new(network_sender),
+            self.time_service.clone(),
+            payload_manager,
+            Arc::new(payload_client),
+            block_tx,
+            dag_rpc_rx,
+            dag_config,
+            DagPayloadConfig {
+                max_sending_txns_per_round: self
+                    .config
+                    .max_sending_block_txns(self.quorum_store_enabled),
+                max_sending_size_per_round_bytes: self
+                    .config
+                    .max_sending_block_bytes(self.quorum_store_enabled),
+                payload_pull_max_poll_time: Duration::from_millis(
+                    self.config.quorum_store_poll_time_ms,
+                ),
+            },
+            self.quorum_store_enabled,
+        );
+
+        tokio::spawn(dag_fetcher.start());
+        tokio::spawn(network_handler.start(dag_shutdown_rx));
+    }
+
+    fn create_quorum_store_builder(
+        &self,
+        epoch_state: &EpochState,
+        consensus_to_quorum_store_rx: mpsc::Receiver<GetPayloadCommand>,
+        network_sender: NetworkSender,
+    ) -> QuorumStoreBuilder {
+        if self.quorum_store_enabled {
+            info!("Building QuorumStore");
+            QuorumStoreBuilder::QuorumStore(InnerBuilder::new(
+                self.epoch(),
+                self.author,
+                epoch_state.verifier.len() as u64,
+                self.config.quorum_store.clone(),
+                consensus_to_quorum_store_rx,
+                self.quorum_store_to_mempool_sender.clone(),
+                self.config.mempool_txn_pull_timeout_ms,
+                self.storage.aptos_db().clone(),
+                network_sender,
+                epoch_state.verifier.clone(),
+                self.config.safety_rules.backend.clone(),
+                self.quorum_store_storage.clone(),
+            ))
+        } else {
+            info!("Building DirectMempool");
+            QuorumStoreBuilder::DirectMempool(DirectMempoolInnerBuilder::new(
+                consensus_to_quorum_store_rx,
+                self.quorum_store_to_mempool_sender.clone(),
+                self.config.mempool_txn_pull_timeout_ms,
+            ))
+        }
+    }
+
     fn enable_quorum_store(&mut self, onchain_config: &OnChainConsensusConfig) -> bool {
         fail_point!("consensus::start_new_epoch::disable_qs", |_| false);
         onchain_config.quorum_store_enabled()
@@ -1061,6 +1239,18 @@ impl EpochManager {
                     Err(anyhow::anyhow!("Quorum store not started"))
                 }
             },
+            IncomingRpcRequest::DAGRequest(request) => {
+                ensure!(
+                    request.req.epoch() == self.epoch(),
+                    "[EpochManager] DAG request from a different epoch {}",
+                    request.req.epoch()
+                );
+                if let Some(tx) = &self.dag_rpc_tx {
+                    tx.push(peer_id, request)
+                } else {
+                    Err(anyhow::anyhow!("DAG not bootstrapped"))
+                }
+            },
         }
     }
 
@@ -46,12 +46,32 @@ pub struct SigningResponse {
     pub commit_ledger_info: LedgerInfo,
 }
 
+/// Signs the commit votes of the executed blocks. Ordering protocols that don't certify the
+/// ordered ledger info with a quorum of signatures (e.g. DAG consensus) provide their own signer.
+pub trait CommitSignerProvider: Send + Sync {
+    fn sign_commit_vote(
+        &self,
+        ledger_info: LedgerInfoWithSignatures,
+        new_ledger_info: LedgerInfo,
+    ) -> Result<bls12381::Signature, Error>;
+}
+
+impl CommitSignerProvider for Mutex<MetricsSafetyRules> {
+    fn sign_commit_vote(
+        &self,
+        ledger_info: LedgerInfoWithSignatures,
+        new_ledger_info: LedgerInfo,
+    ) -> Result<bls12381::Signature, Error> {
+        self.lock().sign_commit_vote(ledger_info, new_ledger_info)
+    }
+}
+
 pub struct SigningPhase {
-    safety_rule_handle: Arc<Mutex<MetricsSafetyRules>>,
+    safety_rule_handle: Arc<dyn CommitSignerProvider>,
 }
 
 impl SigningPhase {
-    pub fn new(safety_rule_handle: Arc<Mutex<MetricsSafetyRules>>) -> Self {
+    pub fn new(safety_rule_handle: Arc<dyn CommitSignerProvider>) -> Self {
         Self { safety_rule_handle }
     }
 }
@@ -70,7 +90,6 @@ impl StatelessPipeline for SigningPhase {
         SigningResponse {
             signature_result: self
                 .safety_rule_handle
-                .lock()
                 .sign_commit_vote(ordered_ledger_info, commit_ledger_info.clone()),
             commit_ledger_info,
         }
@@ -5,6 +5,7 @@
 use crate::{
     block_storage::tracing::{observe_block, BlockStage},
     counters,
+    dag::{DAGNetworkMessage, DAGNetworkSender},
     logging::LogEvent,
     monitor,
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
@@ -60,10 +61,19 @@ pub struct IncomingBatchRetrievalRequest {
     pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
 }
 
+#[derive(Debug)]
+pub struct IncomingDAGRequest {
+    pub req: DAGNetworkMessage,
+    pub sender: Author,
+    pub protocol: ProtocolId,
+    pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
+}
+
 #[derive(Debug)]
 pub enum IncomingRpcRequest {
     BlockRetrieval(IncomingBlockRetrievalRequest),
     BatchRetrieval(IncomingBatchRetrievalRequest),
+    DAGRequest(IncomingDAGRequest),
 }
 
 /// Just a convenience struct to keep all the network proxy receiving queues in one place.
@@ -380,6 +390,38 @@ impl QuorumStoreSender for NetworkSender {
     }
 }
 
+#[async_trait::async_trait]
+impl DAGNetworkSender for NetworkSender {
+    async fn send_rpc(
+        &self,
+        receiver: Author,
+        message: ConsensusMsg,
+        timeout: Duration,
+    ) -> anyhow::Result<ConsensusMsg> {
+        fail_point!("consensus::send::any", |_| {
+            Err(anyhow::anyhow!("Injected error in send_rpc"))
+        });
+        if receiver == self.author {
+            // The DAG protocol sends its messages to itself as well, so unlike other rpcs, self
+            // rpc is supported by looping the request back through the self sender.
+            let (tx, rx) = oneshot::channel();
+            let protocol = ProtocolId::ConsensusRpcBcs;
+            let self_msg = Event::RpcRequest(self.author, message, protocol, tx);
+            self.self_sender.clone().send(self_msg).await?;
+            let response_bytes = tokio::time::timeout(timeout, rx).await???;
+            protocol.from_bytes(&response_bytes)
+        } else {
+            counters::CONSENSUS_SENT_MSGS
+                .with_label_values(&[message.name()])
+                .inc();
+            self.consensus_network_client
+                .send_rpc(receiver, message, timeout)
+                .await
+                .map_err(|e| anyhow!(e))
+        }
+    }
+}
+
 pub struct NetworkTask {
     consensus_messages_tx: aptos_channel::Sender<
         (AccountAddress, Discriminant<ConsensusMsg>),
@@ -419,8 +461,10 @@ impl NetworkTask {
             50,
             Some(&counters::QUORUM_STORE_CHANNEL_MSGS),
         );
+        // DAG requests are delivered through the rpc channel as well, so it has to buffer more than
+        // the latest request of each peer.
         let (rpc_tx, rpc_rx) =
-            aptos_channel::new(QueueStyle::LIFO, 1, Some(&counters::RPC_CHANNEL_MSGS));
+            aptos_channel::new(QueueStyle::FIFO, 10, Some(&counters::RPC_CHANNEL_MSGS));
 
         // Verify the network events have been constructed correctly
         let network_and_events = network_service_events.into_network_and_events();
@@ -556,6 +600,21 @@ impl NetworkTask {
                             warn!(error = ?e, "aptos channel closed");
                         }
                     },
+                    ConsensusMsg::DAGMessage(req) => {
+                        counters::CONSENSUS_RECEIVED_MSGS
+                            .with_label_values(&["DAGMessage"])
+                            .inc();
+                        let req_with_callback =
+                            IncomingRpcRequest::DAGRequest(IncomingDAGRequest {
+                                req,
+                                sender: peer_id,
+                                protocol,
+                                response_sender: callback,
+                            });
+                        if let Err(e) = self.rpc_tx.push(peer_id, (peer_id, req_with_callback)) {
+                            warn!(error = ?e, "aptos channel closed");
+                        }
+                    },
                     _ => {
                         warn!(remote_peer = peer_id, "Unexpected msg: {:?}", msg);
                         continue;
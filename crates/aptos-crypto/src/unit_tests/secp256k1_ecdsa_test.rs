@@ -0,0 +1,120 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    secp256k1_ecdsa::{
+        Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature,
+        SECP256K1_ECDSA_PRIVATE_KEY_LENGTH, SECP256K1_ECDSA_PUBLIC_KEY_LENGTH,
+        SECP256K1_ECDSA_SIGNATURE_LENGTH,
+    },
+    test_utils::{random_serializable_struct, uniform_keypair_strategy},
+    traits::*,
+};
+use core::convert::TryFrom;
+use proptest::{collection::vec, prelude::*};
+
+proptest! {
+    #[test]
+    fn test_keys_encode(keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()) {
+        {
+            let encoded = keypair.private_key.to_encoded_string().unwrap();
+            prop_assert_eq!(2 + 2 * SECP256K1_ECDSA_PRIVATE_KEY_LENGTH, encoded.len());
+            let decoded = Secp256k1EcdsaPrivateKey::from_encoded_string(&encoded);
+            prop_assert_eq!(Some(keypair.private_key), decoded.ok());
+        }
+        {
+            let encoded = keypair.public_key.to_encoded_string().unwrap();
+            prop_assert_eq!(2 + 2 * SECP256K1_ECDSA_PUBLIC_KEY_LENGTH, encoded.len());
+            let decoded = Secp256k1EcdsaPublicKey::from_encoded_string(&encoded);
+            prop_assert_eq!(Some(keypair.public_key), decoded.ok());
+        }
+    }
+
+    #[test]
+    fn test_keys_custom_serialisation(
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        {
+            let serialized: &[u8] = &(keypair.private_key.to_bytes());
+            prop_assert_eq!(SECP256K1_ECDSA_PRIVATE_KEY_LENGTH, serialized.len());
+            let deserialized = Secp256k1EcdsaPrivateKey::try_from(serialized);
+            prop_assert_eq!(Some(keypair.private_key), deserialized.ok());
+        }
+        {
+            let serialized: &[u8] = &(keypair.public_key.to_bytes());
+            prop_assert_eq!(SECP256K1_ECDSA_PUBLIC_KEY_LENGTH, serialized.len());
+            let deserialized = Secp256k1EcdsaPublicKey::try_from(serialized);
+            prop_assert_eq!(Some(keypair.public_key), deserialized.ok());
+        }
+    }
+
+    #[test]
+    fn test_signature_verification_custom_serialisation(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&message).unwrap();
+        let serialized: &[u8] = &(signature.to_bytes());
+        prop_assert_eq!(SECP256K1_ECDSA_SIGNATURE_LENGTH, serialized.len());
+        let deserialized = Secp256k1EcdsaSignature::try_from(serialized).unwrap();
+        prop_assert!(deserialized.verify(&message, &keypair.public_key).is_ok());
+    }
+
+    #[test]
+    fn test_signature_verification_from_arbitrary(
+        msg in vec(proptest::num::u8::ANY, 1..128),
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>(),
+        other_keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign_arbitrary_message(&msg);
+        prop_assert!(signature.verify_arbitrary_msg(&msg, &keypair.public_key).is_ok());
+        prop_assert!(signature.verify_arbitrary_msg(&msg, &other_keypair.public_key).is_err());
+
+        let mut other_msg = msg.clone();
+        other_msg.push(0);
+        prop_assert!(signature.verify_arbitrary_msg(&other_msg, &keypair.public_key).is_err());
+    }
+
+    // Check for low S.
+    #[test]
+    fn test_signature_malleability(
+        message in random_serializable_struct(),
+        keypair in uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&message).unwrap();
+        prop_assert!(!signature.0.s.is_high());
+
+        // (r, -s) is a valid ECDSA signature for the same message, but must be rejected.
+        let mut mauled = signature.0;
+        mauled.s = -mauled.s;
+        let mauled_bytes = mauled.serialize();
+        prop_assert_eq!(
+            Secp256k1EcdsaSignature::try_from(&mauled_bytes[..]),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+
+        let mauled = Secp256k1EcdsaSignature(mauled);
+        prop_assert!(mauled.verify(&message, &keypair.public_key).is_err());
+    }
+}
+
+#[test]
+fn test_public_key_deserialization() {
+    let public_key = Secp256k1EcdsaPublicKey::from(&Secp256k1EcdsaPrivateKey::genesis());
+    let bytes = public_key.to_bytes();
+
+    // Compressed keys are not accepted
+    let compressed = public_key.0.serialize_compressed();
+    assert_eq!(
+        Secp256k1EcdsaPublicKey::try_from(&compressed[..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+
+    // Not a point on the curve
+    let mut invalid = bytes;
+    invalid[SECP256K1_ECDSA_PUBLIC_KEY_LENGTH - 1] ^= 1;
+    assert_eq!(
+        Secp256k1EcdsaPublicKey::try_from(&invalid[..]),
+        Err(CryptoMaterialError::DeserializationError)
+    );
+}
@@ -640,7 +640,7 @@ pub struct MoveStructTag {
 pub struct Signature {
     #[prost(enumeration="signature::Type", tag="1")]
     pub r#type: i32,
-    #[prost(oneof="signature::Signature", tags="2, 3, 4")]
+    #[prost(oneof="signature::Signature", tags="2, 3, 4, 5, 6, 7")]
     pub signature: ::core::option::Option<signature::Signature>,
 }
 /// Nested message and enum types in `Signature`.
@@ -652,6 +652,9 @@ pub mod signature {
         Ed25519 = 1,
         MultiEd25519 = 2,
         MultiAgent = 3,
+        FeePayer = 4,
+        Secp256k1Ecdsa = 5,
+        MultiKey = 6,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -664,6 +667,9 @@ pub mod signature {
                 Type::Ed25519 => "TYPE_ED25519",
                 Type::MultiEd25519 => "TYPE_MULTI_ED25519",
                 Type::MultiAgent => "TYPE_MULTI_AGENT",
+                Type::FeePayer => "TYPE_FEE_PAYER",
+                Type::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+                Type::MultiKey => "TYPE_MULTI_KEY",
             }
         }
     }
@@ -675,6 +681,12 @@ pub mod signature {
         MultiEd25519(super::MultiEd25519Signature),
         #[prost(message, tag="4")]
         MultiAgent(super::MultiAgentSignature),
+        #[prost(message, tag="5")]
+        FeePayer(super::FeePayerSignature),
+        #[prost(message, tag="6")]
+        Secp256k1Ecdsa(super::Secp256k1EcdsaSignature),
+        #[prost(message, tag="7")]
+        MultiKey(super::MultiKeySignature),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -685,6 +697,13 @@ pub struct Ed25519Signature {
     pub signature: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Secp256k1EcdsaSignature {
+    #[prost(bytes="vec", tag="1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MultiEd25519Signature {
     #[prost(bytes="vec", repeated, tag="1")]
     pub public_keys: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
@@ -696,6 +715,77 @@ pub struct MultiEd25519Signature {
     pub public_key_indices: ::prost::alloc::vec::Vec<u32>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnyPublicKey {
+    #[prost(enumeration="any_public_key::Type", tag="1")]
+    pub r#type: i32,
+    #[prost(bytes="vec", tag="2")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+}
+/// Nested message and enum types in `AnyPublicKey`.
+pub mod any_public_key {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Type {
+        Unspecified = 0,
+        Ed25519 = 1,
+        Secp256k1Ecdsa = 2,
+    }
+    impl Type {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Type::Unspecified => "TYPE_UNSPECIFIED",
+                Type::Ed25519 => "TYPE_ED25519",
+                Type::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+            }
+        }
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnySignature {
+    #[prost(enumeration="any_signature::Type", tag="1")]
+    pub r#type: i32,
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Nested message and enum types in `AnySignature`.
+pub mod any_signature {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Type {
+        Unspecified = 0,
+        Ed25519 = 1,
+        Secp256k1Ecdsa = 2,
+    }
+    impl Type {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Type::Unspecified => "TYPE_UNSPECIFIED",
+                Type::Ed25519 => "TYPE_ED25519",
+                Type::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+            }
+        }
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultiKeySignature {
+    #[prost(message, repeated, tag="1")]
+    pub public_keys: ::prost::alloc::vec::Vec<AnyPublicKey>,
+    #[prost(message, repeated, tag="2")]
+    pub signatures: ::prost::alloc::vec::Vec<AnySignature>,
+    #[prost(uint32, tag="3")]
+    pub signatures_required: u32,
+    #[prost(uint32, repeated, tag="4")]
+    pub public_key_indices: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MultiAgentSignature {
     #[prost(message, optional, tag="1")]
     pub sender: ::core::option::Option<AccountSignature>,
@@ -705,10 +795,23 @@ pub struct MultiAgentSignature {
     pub secondary_signers: ::prost::alloc::vec::Vec<AccountSignature>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeePayerSignature {
+    #[prost(message, optional, tag="1")]
+    pub sender: ::core::option::Option<AccountSignature>,
+    #[prost(string, repeated, tag="2")]
+    pub secondary_signer_addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag="3")]
+    pub secondary_signers: ::prost::alloc::vec::Vec<AccountSignature>,
+    #[prost(string, tag="4")]
+    pub fee_payer_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="5")]
+    pub fee_payer_signer: ::core::option::Option<AccountSignature>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AccountSignature {
     #[prost(enumeration="account_signature::Type", tag="1")]
     pub r#type: i32,
-    #[prost(oneof="account_signature::Signature", tags="2, 3")]
+    #[prost(oneof="account_signature::Signature", tags="2, 3, 4, 5")]
     pub signature: ::core::option::Option<account_signature::Signature>,
 }
 /// Nested message and enum types in `AccountSignature`.
@@ -719,6 +822,8 @@ pub mod account_signature {
         Unspecified = 0,
         Ed25519 = 1,
         MultiEd25519 = 2,
+        Secp256k1Ecdsa = 3,
+        MultiKey = 4,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -730,6 +835,8 @@ pub mod account_signature {
                 Type::Unspecified => "TYPE_UNSPECIFIED",
                 Type::Ed25519 => "TYPE_ED25519",
                 Type::MultiEd25519 => "TYPE_MULTI_ED25519",
+                Type::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+                Type::MultiKey => "TYPE_MULTI_KEY",
             }
         }
     }
@@ -739,6 +846,10 @@ pub mod account_signature {
         Ed25519(super::Ed25519Signature),
         #[prost(message, tag="3")]
         MultiEd25519(super::MultiEd25519Signature),
+        #[prost(message, tag="4")]
+        Secp256k1Ecdsa(super::Secp256k1EcdsaSignature),
+        #[prost(message, tag="5")]
+        MultiKey(super::MultiKeySignature),
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -816,7 +927,7 @@ impl MoveAbility {
 }
 /// Encoded file descriptor set for the `aptos.transaction.v1` package
 pub const FILE_DESCRIPTOR_SET: &[u8] = &[
-    0x0a, 0xd5, 0xdb, 0x01, 0x0a, 0x26, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2f, 0x74, 0x72, 0x61, 0x6e,
+    0x0a, 0xce, 0xf7, 0x01, 0x0a, 0x26, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2f, 0x74, 0x72, 0x61, 0x6e,
     0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2f, 0x76, 0x31, 0x2f, 0x74, 0x72, 0x61, 0x6e, 0x73,
     0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x14, 0x61, 0x70,
     0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e,
@@ -1408,7 +1519,7 @@ pub const FILE_DESCRIPTOR_SET: &[u8] = &[
     0x18, 0x04, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x1e, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74,
     0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x4d, 0x6f,
     0x76, 0x65, 0x54, 0x79, 0x70, 0x65, 0x52, 0x11, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x69, 0x63, 0x54,
-    0x79, 0x70, 0x65, 0x50, 0x61, 0x72, 0x61, 0x6d, 0x73, 0x22, 0x96, 0x03, 0x0a, 0x09, 0x53, 0x69,
+    0x79, 0x70, 0x65, 0x50, 0x61, 0x72, 0x61, 0x6d, 0x73, 0x22, 0xc3, 0x05, 0x0a, 0x09, 0x53, 0x69,
     0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x12, 0x38, 0x0a, 0x04, 0x74, 0x79, 0x70, 0x65, 0x18,
     0x01, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x24, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72,
     0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x53, 0x69, 0x67,
@@ -1427,26 +1538,89 @@ pub const FILE_DESCRIPTOR_SET: &[u8] = &[
     0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69,
     0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x4d, 0x75, 0x6c, 0x74, 0x69, 0x41, 0x67, 0x65, 0x6e, 0x74,
     0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x0a, 0x6d, 0x75, 0x6c,
-    0x74, 0x69, 0x41, 0x67, 0x65, 0x6e, 0x74, 0x22, 0x5c, 0x0a, 0x04, 0x54, 0x79, 0x70, 0x65, 0x12,
-    0x14, 0x0a, 0x10, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x55, 0x4e, 0x53, 0x50, 0x45, 0x43, 0x49, 0x46,
-    0x49, 0x45, 0x44, 0x10, 0x00, 0x12, 0x10, 0x0a, 0x0c, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x45, 0x44,
-    0x32, 0x35, 0x35, 0x31, 0x39, 0x10, 0x01, 0x12, 0x16, 0x0a, 0x12, 0x54, 0x59, 0x50, 0x45, 0x5f,
-    0x4d, 0x55, 0x4c, 0x54, 0x49, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31, 0x39, 0x10, 0x02, 0x12,
-    0x14, 0x0a, 0x10, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x4d, 0x55, 0x4c, 0x54, 0x49, 0x5f, 0x41, 0x47,
-    0x45, 0x4e, 0x54, 0x10, 0x03, 0x42, 0x0b, 0x0a, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75,
-    0x72, 0x65, 0x22, 0x4f, 0x0a, 0x10, 0x45, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x53, 0x69, 0x67,
-    0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x12, 0x1d, 0x0a, 0x0a, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63,
-    0x5f, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x09, 0x70, 0x75, 0x62, 0x6c,
-    0x69, 0x63, 0x4b, 0x65, 0x79, 0x12, 0x1c, 0x0a, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75,
-    0x72, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74,
-    0x75, 0x72, 0x65, 0x22, 0xa4, 0x01, 0x0a, 0x15, 0x4d, 0x75, 0x6c, 0x74, 0x69, 0x45, 0x64, 0x32,
-    0x35, 0x35, 0x31, 0x39, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x12, 0x1f, 0x0a,
-    0x0b, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65, 0x79, 0x73, 0x18, 0x01, 0x20, 0x03,
-    0x28, 0x0c, 0x52, 0x0a, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b, 0x65, 0x79, 0x73, 0x12, 0x1e,
-    0x0a, 0x0a, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x73, 0x18, 0x02, 0x20, 0x03,
-    0x28, 0x0c, 0x52, 0x0a, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x73, 0x12, 0x1c,
-    0x0a, 0x09, 0x74, 0x68, 0x72, 0x65, 0x73, 0x68, 0x6f, 0x6c, 0x64, 0x18, 0x03, 0x20, 0x01, 0x28,
-    0x0d, 0x52, 0x09, 0x74, 0x68, 0x72, 0x65, 0x73, 0x68, 0x6f, 0x6c, 0x64, 0x12, 0x2c, 0x0a, 0x12,
+    0x74, 0x69, 0x41, 0x67, 0x65, 0x6e, 0x74, 0x12, 0x46, 0x0a, 0x09, 0x66, 0x65, 0x65, 0x5f, 0x70,
+    0x61, 0x79, 0x65, 0x72, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x27, 0x2e, 0x61, 0x70, 0x74,
+    0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76,
+    0x31, 0x2e, 0x46, 0x65, 0x65, 0x50, 0x61, 0x79, 0x65, 0x72, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74,
+    0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x08, 0x66, 0x65, 0x65, 0x50, 0x61, 0x79, 0x65, 0x72, 0x12,
+    0x58, 0x0a, 0x0f, 0x73, 0x65, 0x63, 0x70, 0x32, 0x35, 0x36, 0x6b, 0x31, 0x5f, 0x65, 0x63, 0x64,
+    0x73, 0x61, 0x18, 0x06, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x2d, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73,
+    0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e,
+    0x53, 0x65, 0x63, 0x70, 0x32, 0x35, 0x36, 0x6b, 0x31, 0x45, 0x63, 0x64, 0x73, 0x61, 0x53, 0x69,
+    0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x0e, 0x73, 0x65, 0x63, 0x70, 0x32,
+    0x35, 0x36, 0x6b, 0x31, 0x45, 0x63, 0x64, 0x73, 0x61, 0x12, 0x46, 0x0a, 0x09, 0x6d, 0x75, 0x6c,
+    0x74, 0x69, 0x5f, 0x6b, 0x65, 0x79, 0x18, 0x07, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x27, 0x2e, 0x61,
+    0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e,
+    0x2e, 0x76, 0x31, 0x2e, 0x4d, 0x75, 0x6c, 0x74, 0x69, 0x4b, 0x65, 0x79, 0x53, 0x69, 0x67, 0x6e,
+    0x61, 0x74, 0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x08, 0x6d, 0x75, 0x6c, 0x74, 0x69, 0x4b, 0x65,
+    0x79, 0x22, 0x9e, 0x01, 0x0a, 0x04, 0x54, 0x79, 0x70, 0x65, 0x12, 0x14, 0x0a, 0x10, 0x54, 0x59,
+    0x50, 0x45, 0x5f, 0x55, 0x4e, 0x53, 0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10, 0x00,
+    0x12, 0x10, 0x0a, 0x0c, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31, 0x39,
+    0x10, 0x01, 0x12, 0x16, 0x0a, 0x12, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x4d, 0x55, 0x4c, 0x54, 0x49,
+    0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31, 0x39, 0x10, 0x02, 0x12, 0x14, 0x0a, 0x10, 0x54, 0x59,
+    0x50, 0x45, 0x5f, 0x4d, 0x55, 0x4c, 0x54, 0x49, 0x5f, 0x41, 0x47, 0x45, 0x4e, 0x54, 0x10, 0x03,
+    0x12, 0x12, 0x0a, 0x0e, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x46, 0x45, 0x45, 0x5f, 0x50, 0x41, 0x59,
+    0x45, 0x52, 0x10, 0x04, 0x12, 0x18, 0x0a, 0x14, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x53, 0x45, 0x43,
+    0x50, 0x32, 0x35, 0x36, 0x4b, 0x31, 0x5f, 0x45, 0x43, 0x44, 0x53, 0x41, 0x10, 0x05, 0x12, 0x12,
+    0x0a, 0x0e, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x4d, 0x55, 0x4c, 0x54, 0x49, 0x5f, 0x4b, 0x45, 0x59,
+    0x10, 0x06, 0x42, 0x0b, 0x0a, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x22,
+    0x4f, 0x0a, 0x10, 0x45, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74,
+    0x75, 0x72, 0x65, 0x12, 0x1d, 0x0a, 0x0a, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65,
+    0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x09, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b,
+    0x65, 0x79, 0x12, 0x1c, 0x0a, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x18,
+    0x02, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65,
+    0x22, 0x56, 0x0a, 0x17, 0x53, 0x65, 0x63, 0x70, 0x32, 0x35, 0x36, 0x6b, 0x31, 0x45, 0x63, 0x64,
+    0x73, 0x61, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x12, 0x1d, 0x0a, 0x0a, 0x70,
+    0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65, 0x79, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0c, 0x52,
+    0x09, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b, 0x65, 0x79, 0x12, 0x1c, 0x0a, 0x09, 0x73, 0x69,
+    0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x09, 0x73,
+    0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x22, 0xa4, 0x01, 0x0a, 0x15, 0x4d, 0x75, 0x6c,
+    0x74, 0x69, 0x45, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75,
+    0x72, 0x65, 0x12, 0x1f, 0x0a, 0x0b, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65, 0x79,
+    0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0c, 0x52, 0x0a, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b,
+    0x65, 0x79, 0x73, 0x12, 0x1e, 0x0a, 0x0a, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65,
+    0x73, 0x18, 0x02, 0x20, 0x03, 0x28, 0x0c, 0x52, 0x0a, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75,
+    0x72, 0x65, 0x73, 0x12, 0x1c, 0x0a, 0x09, 0x74, 0x68, 0x72, 0x65, 0x73, 0x68, 0x6f, 0x6c, 0x64,
+    0x18, 0x03, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x09, 0x74, 0x68, 0x72, 0x65, 0x73, 0x68, 0x6f, 0x6c,
+    0x64, 0x12, 0x2c, 0x0a, 0x12, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65, 0x79, 0x5f,
+    0x69, 0x6e, 0x64, 0x69, 0x63, 0x65, 0x73, 0x18, 0x04, 0x20, 0x03, 0x28, 0x0d, 0x52, 0x10, 0x70,
+    0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b, 0x65, 0x79, 0x49, 0x6e, 0x64, 0x69, 0x63, 0x65, 0x73, 0x22,
+    0xb4, 0x01, 0x0a, 0x0c, 0x41, 0x6e, 0x79, 0x50, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b, 0x65, 0x79,
+    0x12, 0x3b, 0x0a, 0x04, 0x74, 0x79, 0x70, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x27,
+    0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69,
+    0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x41, 0x6e, 0x79, 0x50, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b,
+    0x65, 0x79, 0x2e, 0x54, 0x79, 0x70, 0x65, 0x52, 0x04, 0x74, 0x79, 0x70, 0x65, 0x12, 0x1d, 0x0a,
+    0x0a, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65, 0x79, 0x18, 0x02, 0x20, 0x01, 0x28,
+    0x0c, 0x52, 0x09, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b, 0x65, 0x79, 0x22, 0x48, 0x0a, 0x04,
+    0x54, 0x79, 0x70, 0x65, 0x12, 0x14, 0x0a, 0x10, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x55, 0x4e, 0x53,
+    0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10, 0x00, 0x12, 0x10, 0x0a, 0x0c, 0x54, 0x59,
+    0x50, 0x45, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31, 0x39, 0x10, 0x01, 0x12, 0x18, 0x0a, 0x14,
+    0x54, 0x59, 0x50, 0x45, 0x5f, 0x53, 0x45, 0x43, 0x50, 0x32, 0x35, 0x36, 0x4b, 0x31, 0x5f, 0x45,
+    0x43, 0x44, 0x53, 0x41, 0x10, 0x02, 0x22, 0xb3, 0x01, 0x0a, 0x0c, 0x41, 0x6e, 0x79, 0x53, 0x69,
+    0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x12, 0x3b, 0x0a, 0x04, 0x74, 0x79, 0x70, 0x65, 0x18,
+    0x01, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x27, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72,
+    0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x41, 0x6e, 0x79,
+    0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x2e, 0x54, 0x79, 0x70, 0x65, 0x52, 0x04,
+    0x74, 0x79, 0x70, 0x65, 0x12, 0x1c, 0x0a, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72,
+    0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0c, 0x52, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75,
+    0x72, 0x65, 0x22, 0x48, 0x0a, 0x04, 0x54, 0x79, 0x70, 0x65, 0x12, 0x14, 0x0a, 0x10, 0x54, 0x59,
+    0x50, 0x45, 0x5f, 0x55, 0x4e, 0x53, 0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10, 0x00,
+    0x12, 0x10, 0x0a, 0x0c, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31, 0x39,
+    0x10, 0x01, 0x12, 0x18, 0x0a, 0x14, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x53, 0x45, 0x43, 0x50, 0x32,
+    0x35, 0x36, 0x4b, 0x31, 0x5f, 0x45, 0x43, 0x44, 0x53, 0x41, 0x10, 0x02, 0x22, 0xfb, 0x01, 0x0a,
+    0x11, 0x4d, 0x75, 0x6c, 0x74, 0x69, 0x4b, 0x65, 0x79, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75,
+    0x72, 0x65, 0x12, 0x43, 0x0a, 0x0b, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65, 0x79,
+    0x73, 0x18, 0x01, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x22, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e,
+    0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x41,
+    0x6e, 0x79, 0x50, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x4b, 0x65, 0x79, 0x52, 0x0a, 0x70, 0x75, 0x62,
+    0x6c, 0x69, 0x63, 0x4b, 0x65, 0x79, 0x73, 0x12, 0x42, 0x0a, 0x0a, 0x73, 0x69, 0x67, 0x6e, 0x61,
+    0x74, 0x75, 0x72, 0x65, 0x73, 0x18, 0x02, 0x20, 0x03, 0x28, 0x0b, 0x32, 0x22, 0x2e, 0x61, 0x70,
+    0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e,
+    0x76, 0x31, 0x2e, 0x41, 0x6e, 0x79, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x52,
+    0x0a, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x73, 0x12, 0x2f, 0x0a, 0x13, 0x73,
+    0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x73, 0x5f, 0x72, 0x65, 0x71, 0x75, 0x69, 0x72,
+    0x65, 0x64, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x12, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74,
+    0x75, 0x72, 0x65, 0x73, 0x52, 0x65, 0x71, 0x75, 0x69, 0x72, 0x65, 0x64, 0x12, 0x2c, 0x0a, 0x12,
     0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, 0x5f, 0x6b, 0x65, 0x79, 0x5f, 0x69, 0x6e, 0x64, 0x69, 0x63,
     0x65, 0x73, 0x18, 0x04, 0x20, 0x03, 0x28, 0x0d, 0x52, 0x10, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63,
     0x4b, 0x65, 0x79, 0x49, 0x6e, 0x64, 0x69, 0x63, 0x65, 0x73, 0x22, 0xe8, 0x01, 0x0a, 0x13, 0x4d,
@@ -1464,1116 +1638,1277 @@ pub const FILE_DESCRIPTOR_SET: &[u8] = &[
     0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e,
     0x76, 0x31, 0x2e, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74,
     0x75, 0x72, 0x65, 0x52, 0x10, 0x73, 0x65, 0x63, 0x6f, 0x6e, 0x64, 0x61, 0x72, 0x79, 0x53, 0x69,
-    0x67, 0x6e, 0x65, 0x72, 0x73, 0x22, 0xc0, 0x02, 0x0a, 0x10, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e,
-    0x74, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x12, 0x3f, 0x0a, 0x04, 0x74, 0x79,
-    0x70, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x2b, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73,
-    0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e,
-    0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65,
-    0x2e, 0x54, 0x79, 0x70, 0x65, 0x52, 0x04, 0x74, 0x79, 0x70, 0x65, 0x12, 0x42, 0x0a, 0x07, 0x65,
-    0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x26, 0x2e, 0x61,
-    0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e,
-    0x2e, 0x76, 0x31, 0x2e, 0x45, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x53, 0x69, 0x67, 0x6e, 0x61,
-    0x74, 0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x07, 0x65, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x12,
-    0x52, 0x0a, 0x0d, 0x6d, 0x75, 0x6c, 0x74, 0x69, 0x5f, 0x65, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39,
-    0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x2b, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74,
-    0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x4d, 0x75,
-    0x6c, 0x74, 0x69, 0x45, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74,
-    0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x0c, 0x6d, 0x75, 0x6c, 0x74, 0x69, 0x45, 0x64, 0x32, 0x35,
-    0x35, 0x31, 0x39, 0x22, 0x46, 0x0a, 0x04, 0x54, 0x79, 0x70, 0x65, 0x12, 0x14, 0x0a, 0x10, 0x54,
-    0x59, 0x50, 0x45, 0x5f, 0x55, 0x4e, 0x53, 0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10,
-    0x00, 0x12, 0x10, 0x0a, 0x0c, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31,
-    0x39, 0x10, 0x01, 0x12, 0x16, 0x0a, 0x12, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x4d, 0x55, 0x4c, 0x54,
-    0x49, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31, 0x39, 0x10, 0x02, 0x42, 0x0b, 0x0a, 0x09, 0x73,
-    0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x2a, 0xea, 0x02, 0x0a, 0x09, 0x4d, 0x6f, 0x76,
-    0x65, 0x54, 0x79, 0x70, 0x65, 0x73, 0x12, 0x1a, 0x0a, 0x16, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54,
-    0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x4e, 0x53, 0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44,
-    0x10, 0x00, 0x12, 0x13, 0x0a, 0x0f, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53,
-    0x5f, 0x42, 0x4f, 0x4f, 0x4c, 0x10, 0x01, 0x12, 0x11, 0x0a, 0x0d, 0x4d, 0x4f, 0x56, 0x45, 0x5f,
-    0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x38, 0x10, 0x02, 0x12, 0x12, 0x0a, 0x0e, 0x4d, 0x4f,
-    0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x31, 0x36, 0x10, 0x0c, 0x12, 0x12,
-    0x0a, 0x0e, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x33, 0x32,
-    0x10, 0x0d, 0x12, 0x12, 0x0a, 0x0e, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53,
-    0x5f, 0x55, 0x36, 0x34, 0x10, 0x03, 0x12, 0x13, 0x0a, 0x0f, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54,
-    0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x31, 0x32, 0x38, 0x10, 0x04, 0x12, 0x13, 0x0a, 0x0f, 0x4d,
-    0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x32, 0x35, 0x36, 0x10, 0x0e,
-    0x12, 0x16, 0x0a, 0x12, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x41,
-    0x44, 0x44, 0x52, 0x45, 0x53, 0x53, 0x10, 0x05, 0x12, 0x15, 0x0a, 0x11, 0x4d, 0x4f, 0x56, 0x45,
-    0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x53, 0x49, 0x47, 0x4e, 0x45, 0x52, 0x10, 0x06, 0x12,
-    0x15, 0x0a, 0x11, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x56, 0x45,
-    0x43, 0x54, 0x4f, 0x52, 0x10, 0x07, 0x12, 0x15, 0x0a, 0x11, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54,
-    0x59, 0x50, 0x45, 0x53, 0x5f, 0x53, 0x54, 0x52, 0x55, 0x43, 0x54, 0x10, 0x08, 0x12, 0x21, 0x0a,
-    0x1d, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x47, 0x45, 0x4e, 0x45,
-    0x52, 0x49, 0x43, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x50, 0x41, 0x52, 0x41, 0x4d, 0x10, 0x09,
-    0x12, 0x18, 0x0a, 0x14, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x52,
-    0x45, 0x46, 0x45, 0x52, 0x45, 0x4e, 0x43, 0x45, 0x10, 0x0a, 0x12, 0x19, 0x0a, 0x15, 0x4d, 0x4f,
-    0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x4e, 0x50, 0x41, 0x52, 0x53, 0x41,
-    0x42, 0x4c, 0x45, 0x10, 0x0b, 0x2a, 0x87, 0x01, 0x0a, 0x0b, 0x4d, 0x6f, 0x76, 0x65, 0x41, 0x62,
-    0x69, 0x6c, 0x69, 0x74, 0x79, 0x12, 0x1c, 0x0a, 0x18, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x41, 0x42,
-    0x49, 0x4c, 0x49, 0x54, 0x59, 0x5f, 0x55, 0x4e, 0x53, 0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45,
-    0x44, 0x10, 0x00, 0x12, 0x15, 0x0a, 0x11, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c,
-    0x49, 0x54, 0x59, 0x5f, 0x43, 0x4f, 0x50, 0x59, 0x10, 0x01, 0x12, 0x15, 0x0a, 0x11, 0x4d, 0x4f,
-    0x56, 0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54, 0x59, 0x5f, 0x44, 0x52, 0x4f, 0x50, 0x10,
-    0x02, 0x12, 0x16, 0x0a, 0x12, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54,
-    0x59, 0x5f, 0x53, 0x54, 0x4f, 0x52, 0x45, 0x10, 0x03, 0x12, 0x14, 0x0a, 0x10, 0x4d, 0x4f, 0x56,
-    0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54, 0x59, 0x5f, 0x4b, 0x45, 0x59, 0x10, 0x04, 0x4a,
-    0x8e, 0x84, 0x01, 0x0a, 0x07, 0x12, 0x05, 0x03, 0x00, 0xbc, 0x03, 0x01, 0x0a, 0x4e, 0x0a, 0x01,
-    0x0c, 0x12, 0x03, 0x03, 0x00, 0x12, 0x32, 0x44, 0x20, 0x43, 0x6f, 0x70, 0x79, 0x72, 0x69, 0x67,
-    0x68, 0x74, 0x20, 0xc2, 0xa9, 0x20, 0x41, 0x70, 0x74, 0x6f, 0x73, 0x20, 0x46, 0x6f, 0x75, 0x6e,
-    0x64, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x0a, 0x20, 0x53, 0x50, 0x44, 0x58, 0x2d, 0x4c, 0x69, 0x63,
-    0x65, 0x6e, 0x73, 0x65, 0x2d, 0x49, 0x64, 0x65, 0x6e, 0x74, 0x69, 0x66, 0x69, 0x65, 0x72, 0x3a,
-    0x20, 0x41, 0x70, 0x61, 0x63, 0x68, 0x65, 0x2d, 0x32, 0x2e, 0x30, 0x0a, 0x0a, 0x08, 0x0a, 0x01,
-    0x02, 0x12, 0x03, 0x05, 0x00, 0x1d, 0x0a, 0x09, 0x0a, 0x02, 0x03, 0x00, 0x12, 0x03, 0x07, 0x00,
-    0x2e, 0x0a, 0xa3, 0x05, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x12, 0x00, 0x20, 0x01, 0x1a, 0x96,
-    0x05, 0x20, 0x41, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x20, 0x6f, 0x6e, 0x20, 0x41, 0x70, 0x74,
-    0x6f, 0x73, 0x20, 0x68, 0x6f, 0x6c, 0x64, 0x73, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63,
-    0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x69, 0x6e, 0x20, 0x63, 0x68, 0x72, 0x6f, 0x6e, 0x6f, 0x6c,
-    0x6f, 0x67, 0x69, 0x63, 0x61, 0x6c, 0x20, 0x6f, 0x72, 0x64, 0x65, 0x72, 0x20, 0x28, 0x6f, 0x72,
-    0x64, 0x65, 0x72, 0x65, 0x64, 0x20, 0x62, 0x79, 0x20, 0x61, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73,
-    0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x6d, 0x6f, 0x6e, 0x6f, 0x74, 0x6f, 0x6e, 0x69,
-    0x63, 0x61, 0x6c, 0x6c, 0x79, 0x20, 0x69, 0x6e, 0x63, 0x72, 0x65, 0x61, 0x73, 0x69, 0x6e, 0x67,
-    0x20, 0x60, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x60, 0x20, 0x66, 0x69, 0x65, 0x6c, 0x64,
-    0x29, 0x0a, 0x20, 0x41, 0x6c, 0x6c, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x20, 0x73, 0x74,
-    0x61, 0x72, 0x74, 0x20, 0x77, 0x69, 0x74, 0x68, 0x20, 0x61, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63,
-    0x6b, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63,
-    0x74, 0x69, 0x6f, 0x6e, 0x60, 0x2c, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x61, 0x72, 0x65, 0x20, 0x66,
-    0x6f, 0x6c, 0x6c, 0x6f, 0x77, 0x65, 0x64, 0x20, 0x62, 0x79, 0x20, 0x7a, 0x65, 0x72, 0x6f, 0x20,
-    0x6f, 0x72, 0x20, 0x6d, 0x6f, 0x72, 0x65, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74,
-    0x69, 0x6f, 0x6e, 0x73, 0x2e, 0x0a, 0x20, 0x54, 0x68, 0x65, 0x20, 0x6e, 0x65, 0x78, 0x74, 0x20,
-    0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x54, 0x72,
-    0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x20, 0x64, 0x65, 0x6e, 0x6f, 0x74,
-    0x65, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x65, 0x6e, 0x64, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68,
-    0x65, 0x20, 0x63, 0x75, 0x72, 0x72, 0x65, 0x6e, 0x74, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x2c,
-    0x20, 0x61, 0x6e, 0x64, 0x20, 0x74, 0x68, 0x65, 0x20, 0x73, 0x74, 0x61, 0x72, 0x74, 0x20, 0x6f,
-    0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x6e, 0x65, 0x78, 0x74, 0x20, 0x6f, 0x6e, 0x65, 0x2e, 0x0a,
-    0x0a, 0x20, 0x54, 0x68, 0x65, 0x20, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x20, 0x60, 0x68, 0x65, 0x69,
-    0x67, 0x68, 0x74, 0x60, 0x20, 0x69, 0x73, 0x20, 0x61, 0x20, 0x73, 0x74, 0x72, 0x69, 0x63, 0x74,
-    0x6c, 0x79, 0x20, 0x6d, 0x6f, 0x6e, 0x6f, 0x74, 0x6f, 0x6e, 0x69, 0x63, 0x61, 0x6c, 0x6c, 0x79,
-    0x20, 0x69, 0x6e, 0x63, 0x72, 0x65, 0x61, 0x73, 0x69, 0x6e, 0x67, 0x20, 0x63, 0x6f, 0x75, 0x6e,
-    0x74, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x20,
-    0x6f, 0x66, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x2c, 0x0a, 0x20, 0x61, 0x6e, 0x64, 0x20,
-    0x74, 0x68, 0x65, 0x72, 0x65, 0x20, 0x77, 0x69, 0x6c, 0x6c, 0x20, 0x6e, 0x65, 0x76, 0x65, 0x72,
-    0x20, 0x62, 0x65, 0x20, 0x61, 0x20, 0x67, 0x61, 0x70, 0x20, 0x69, 0x6e, 0x20, 0x74, 0x68, 0x65,
-    0x20, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x73, 0x2e, 0x20, 0x49, 0x74, 0x20, 0x69, 0x73, 0x20,
-    0x61, 0x6c, 0x73, 0x6f, 0x20, 0x61, 0x20, 0x75, 0x6e, 0x69, 0x71, 0x75, 0x65, 0x20, 0x69, 0x64,
-    0x65, 0x6e, 0x74, 0x69, 0x66, 0x69, 0x65, 0x72, 0x3a, 0x20, 0x74, 0x68, 0x65, 0x72, 0x65, 0x20,
-    0x77, 0x69, 0x6c, 0x6c, 0x20, 0x6e, 0x65, 0x76, 0x65, 0x72, 0x20, 0x62, 0x65, 0x20, 0x74, 0x77,
-    0x6f, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x20, 0x77, 0x69, 0x74, 0x68, 0x0a, 0x20, 0x74,
-    0x68, 0x65, 0x20, 0x73, 0x61, 0x6d, 0x65, 0x20, 0x60, 0x68, 0x65, 0x69, 0x67, 0x68, 0x74, 0x60,
-    0x2e, 0x0a, 0x0a, 0x20, 0x54, 0x68, 0x65, 0x20, 0x47, 0x65, 0x6e, 0x65, 0x73, 0x69, 0x73, 0x20,
-    0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x28, 0x76, 0x65, 0x72,
-    0x73, 0x69, 0x6f, 0x6e, 0x20, 0x30, 0x29, 0x20, 0x69, 0x73, 0x20, 0x63, 0x6f, 0x6e, 0x74, 0x61,
-    0x69, 0x6e, 0x65, 0x64, 0x20, 0x77, 0x69, 0x74, 0x68, 0x69, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20,
-    0x66, 0x69, 0x72, 0x73, 0x74, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x2c, 0x20, 0x77, 0x68, 0x69,
-    0x63, 0x68, 0x20, 0x68, 0x61, 0x73, 0x20, 0x61, 0x20, 0x68, 0x65, 0x69, 0x67, 0x68, 0x74, 0x20,
-    0x6f, 0x66, 0x20, 0x60, 0x30, 0x60, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03,
-    0x12, 0x08, 0x0d, 0x0a, 0xde, 0x01, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x15, 0x02,
-    0x2f, 0x1a, 0xd0, 0x01, 0x20, 0x54, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x20, 0x72,
-    0x65, 0x70, 0x72, 0x65, 0x73, 0x65, 0x6e, 0x74, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x74, 0x69,
-    0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x60,
-    0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x54, 0x72, 0x61,
-    0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x20, 0x28, 0x6f, 0x72, 0x20, 0x60, 0x47,
-    0x65, 0x6e, 0x65, 0x73, 0x69, 0x73, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f,
-    0x6e, 0x60, 0x20, 0x66, 0x6f, 0x72, 0x20, 0x74, 0x68, 0x65, 0x20, 0x67, 0x65, 0x6e, 0x65, 0x73,
-    0x69, 0x73, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x29, 0x0a, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x65,
-    0x76, 0x65, 0x72, 0x79, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e,
-    0x20, 0x69, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20, 0x60, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63,
-    0x74, 0x69, 0x6f, 0x6e, 0x73, 0x60, 0x20, 0x77, 0x69, 0x6c, 0x6c, 0x20, 0x68, 0x61, 0x76, 0x65,
-    0x20, 0x74, 0x68, 0x65, 0x20, 0x73, 0x61, 0x6d, 0x65, 0x20, 0x60, 0x74, 0x69, 0x6d, 0x65, 0x73,
-    0x74, 0x61, 0x6d, 0x70, 0x60, 0x20, 0x61, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x62, 0x6c, 0x6f,
-    0x63, 0x6b, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x06, 0x12, 0x03, 0x15,
-    0x02, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x15, 0x21, 0x2a,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x15, 0x2d, 0x2e, 0x0a, 0x88,
-    0x01, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x18, 0x02, 0x29, 0x1a, 0x7b, 0x20, 0x48,
-    0x65, 0x69, 0x67, 0x68, 0x74, 0x20, 0x72, 0x65, 0x70, 0x72, 0x65, 0x73, 0x65, 0x6e, 0x74, 0x73,
-    0x20, 0x74, 0x68, 0x65, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x20, 0x6e, 0x75, 0x6d, 0x62, 0x65,
-    0x72, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x75, 0x6c, 0x74, 0x69, 0x6d, 0x61, 0x74, 0x65, 0x6c, 0x79,
-    0x2c, 0x20, 0x69, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x20, 0x6f,
-    0x66, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61,
-    0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x20, 0x74, 0x68, 0x61,
-    0x74, 0x20, 0x68, 0x61, 0x70, 0x70, 0x65, 0x6e, 0x65, 0x64, 0x20, 0x6f, 0x6e, 0x20, 0x74, 0x68,
-    0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
-    0x01, 0x05, 0x12, 0x03, 0x18, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01,
-    0x12, 0x03, 0x18, 0x09, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03,
-    0x18, 0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x08, 0x12, 0x03, 0x18, 0x14,
-    0x28, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x02, 0x01, 0x08, 0x06, 0x12, 0x03, 0x18, 0x15, 0x27,
-    0x0a, 0x87, 0x02, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x02, 0x12, 0x03, 0x1c, 0x02, 0x28, 0x1a, 0xf9,
-    0x01, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x68,
-    0x6f, 0x6c, 0x64, 0x73, 0x20, 0x61, 0x6c, 0x6c, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63,
-    0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x74, 0x68, 0x61, 0x74, 0x20, 0x68, 0x61, 0x70, 0x70, 0x65,
-    0x6e, 0x65, 0x64, 0x20, 0x69, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20, 0x42, 0x6c, 0x6f, 0x63, 0x6b,
-    0x2c, 0x20, 0x77, 0x68, 0x69, 0x63, 0x68, 0x20, 0x69, 0x73, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73,
-    0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x74, 0x68, 0x61, 0x74, 0x20, 0x68, 0x61, 0x70,
-    0x70, 0x65, 0x6e, 0x65, 0x64, 0x20, 0x73, 0x74, 0x61, 0x72, 0x74, 0x69, 0x6e, 0x67, 0x20, 0x77,
-    0x69, 0x74, 0x68, 0x20, 0x28, 0x61, 0x6e, 0x64, 0x20, 0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x69,
-    0x6e, 0x67, 0x29, 0x0a, 0x20, 0x61, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74,
+    0x67, 0x6e, 0x65, 0x72, 0x73, 0x22, 0xe4, 0x02, 0x0a, 0x11, 0x46, 0x65, 0x65, 0x50, 0x61, 0x79,
+    0x65, 0x72, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x12, 0x3e, 0x0a, 0x06, 0x73,
+    0x65, 0x6e, 0x64, 0x65, 0x72, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x26, 0x2e, 0x61, 0x70,
+    0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e,
+    0x76, 0x31, 0x2e, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74,
+    0x75, 0x72, 0x65, 0x52, 0x06, 0x73, 0x65, 0x6e, 0x64, 0x65, 0x72, 0x12, 0x3c, 0x0a, 0x1a, 0x73,
+    0x65, 0x63, 0x6f, 0x6e, 0x64, 0x61, 0x72, 0x79, 0x5f, 0x73, 0x69, 0x67, 0x6e, 0x65, 0x72, 0x5f,
+    0x61, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73, 0x65, 0x73, 0x18, 0x02, 0x20, 0x03, 0x28, 0x09, 0x52,
+    0x18, 0x73, 0x65, 0x63, 0x6f, 0x6e, 0x64, 0x61, 0x72, 0x79, 0x53, 0x69, 0x67, 0x6e, 0x65, 0x72,
+    0x41, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73, 0x65, 0x73, 0x12, 0x53, 0x0a, 0x11, 0x73, 0x65, 0x63,
+    0x6f, 0x6e, 0x64, 0x61, 0x72, 0x79, 0x5f, 0x73, 0x69, 0x67, 0x6e, 0x65, 0x72, 0x73, 0x18, 0x03,
+    0x20, 0x03, 0x28, 0x0b, 0x32, 0x26, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61,
+    0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x41, 0x63, 0x63, 0x6f,
+    0x75, 0x6e, 0x74, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x52, 0x10, 0x73, 0x65,
+    0x63, 0x6f, 0x6e, 0x64, 0x61, 0x72, 0x79, 0x53, 0x69, 0x67, 0x6e, 0x65, 0x72, 0x73, 0x12, 0x2a,
+    0x0a, 0x11, 0x66, 0x65, 0x65, 0x5f, 0x70, 0x61, 0x79, 0x65, 0x72, 0x5f, 0x61, 0x64, 0x64, 0x72,
+    0x65, 0x73, 0x73, 0x18, 0x04, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0f, 0x66, 0x65, 0x65, 0x50, 0x61,
+    0x79, 0x65, 0x72, 0x41, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73, 0x12, 0x50, 0x0a, 0x10, 0x66, 0x65,
+    0x65, 0x5f, 0x70, 0x61, 0x79, 0x65, 0x72, 0x5f, 0x73, 0x69, 0x67, 0x6e, 0x65, 0x72, 0x18, 0x05,
+    0x20, 0x01, 0x28, 0x0b, 0x32, 0x26, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61,
+    0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x41, 0x63, 0x63, 0x6f,
+    0x75, 0x6e, 0x74, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x52, 0x0e, 0x66, 0x65,
+    0x65, 0x50, 0x61, 0x79, 0x65, 0x72, 0x53, 0x69, 0x67, 0x6e, 0x65, 0x72, 0x22, 0x90, 0x04, 0x0a,
+    0x10, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72,
+    0x65, 0x12, 0x3f, 0x0a, 0x04, 0x74, 0x79, 0x70, 0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0e, 0x32,
+    0x2b, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74,
+    0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x53, 0x69,
+    0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x2e, 0x54, 0x79, 0x70, 0x65, 0x52, 0x04, 0x74, 0x79,
+    0x70, 0x65, 0x12, 0x42, 0x0a, 0x07, 0x65, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x18, 0x02, 0x20,
+    0x01, 0x28, 0x0b, 0x32, 0x26, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e,
+    0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x45, 0x64, 0x32, 0x35, 0x35,
+    0x31, 0x39, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x07, 0x65,
+    0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x12, 0x52, 0x0a, 0x0d, 0x6d, 0x75, 0x6c, 0x74, 0x69, 0x5f,
+    0x65, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x2b, 0x2e,
+    0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f,
+    0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x4d, 0x75, 0x6c, 0x74, 0x69, 0x45, 0x64, 0x32, 0x35, 0x35, 0x31,
+    0x39, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x48, 0x00, 0x52, 0x0c, 0x6d, 0x75,
+    0x6c, 0x74, 0x69, 0x45, 0x64, 0x32, 0x35, 0x35, 0x31, 0x39, 0x12, 0x58, 0x0a, 0x0f, 0x73, 0x65,
+    0x63, 0x70, 0x32, 0x35, 0x36, 0x6b, 0x31, 0x5f, 0x65, 0x63, 0x64, 0x73, 0x61, 0x18, 0x04, 0x20,
+    0x01, 0x28, 0x0b, 0x32, 0x2d, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e, 0x74, 0x72, 0x61, 0x6e,
+    0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x53, 0x65, 0x63, 0x70, 0x32,
+    0x35, 0x36, 0x6b, 0x31, 0x45, 0x63, 0x64, 0x73, 0x61, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75,
+    0x72, 0x65, 0x48, 0x00, 0x52, 0x0e, 0x73, 0x65, 0x63, 0x70, 0x32, 0x35, 0x36, 0x6b, 0x31, 0x45,
+    0x63, 0x64, 0x73, 0x61, 0x12, 0x46, 0x0a, 0x09, 0x6d, 0x75, 0x6c, 0x74, 0x69, 0x5f, 0x6b, 0x65,
+    0x79, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x27, 0x2e, 0x61, 0x70, 0x74, 0x6f, 0x73, 0x2e,
+    0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x76, 0x31, 0x2e, 0x4d,
+    0x75, 0x6c, 0x74, 0x69, 0x4b, 0x65, 0x79, 0x53, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65,
+    0x48, 0x00, 0x52, 0x08, 0x6d, 0x75, 0x6c, 0x74, 0x69, 0x4b, 0x65, 0x79, 0x22, 0x74, 0x0a, 0x04,
+    0x54, 0x79, 0x70, 0x65, 0x12, 0x14, 0x0a, 0x10, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x55, 0x4e, 0x53,
+    0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10, 0x00, 0x12, 0x10, 0x0a, 0x0c, 0x54, 0x59,
+    0x50, 0x45, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35, 0x31, 0x39, 0x10, 0x01, 0x12, 0x16, 0x0a, 0x12,
+    0x54, 0x59, 0x50, 0x45, 0x5f, 0x4d, 0x55, 0x4c, 0x54, 0x49, 0x5f, 0x45, 0x44, 0x32, 0x35, 0x35,
+    0x31, 0x39, 0x10, 0x02, 0x12, 0x18, 0x0a, 0x14, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x53, 0x45, 0x43,
+    0x50, 0x32, 0x35, 0x36, 0x4b, 0x31, 0x5f, 0x45, 0x43, 0x44, 0x53, 0x41, 0x10, 0x03, 0x12, 0x12,
+    0x0a, 0x0e, 0x54, 0x59, 0x50, 0x45, 0x5f, 0x4d, 0x55, 0x4c, 0x54, 0x49, 0x5f, 0x4b, 0x45, 0x59,
+    0x10, 0x04, 0x42, 0x0b, 0x0a, 0x09, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x2a,
+    0xea, 0x02, 0x0a, 0x09, 0x4d, 0x6f, 0x76, 0x65, 0x54, 0x79, 0x70, 0x65, 0x73, 0x12, 0x1a, 0x0a,
+    0x16, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x4e, 0x53, 0x50,
+    0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10, 0x00, 0x12, 0x13, 0x0a, 0x0f, 0x4d, 0x4f, 0x56,
+    0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x42, 0x4f, 0x4f, 0x4c, 0x10, 0x01, 0x12, 0x11,
+    0x0a, 0x0d, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x38, 0x10,
+    0x02, 0x12, 0x12, 0x0a, 0x0e, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f,
+    0x55, 0x31, 0x36, 0x10, 0x0c, 0x12, 0x12, 0x0a, 0x0e, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59,
+    0x50, 0x45, 0x53, 0x5f, 0x55, 0x33, 0x32, 0x10, 0x0d, 0x12, 0x12, 0x0a, 0x0e, 0x4d, 0x4f, 0x56,
+    0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x36, 0x34, 0x10, 0x03, 0x12, 0x13, 0x0a,
+    0x0f, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x55, 0x31, 0x32, 0x38,
+    0x10, 0x04, 0x12, 0x13, 0x0a, 0x0f, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53,
+    0x5f, 0x55, 0x32, 0x35, 0x36, 0x10, 0x0e, 0x12, 0x16, 0x0a, 0x12, 0x4d, 0x4f, 0x56, 0x45, 0x5f,
+    0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x41, 0x44, 0x44, 0x52, 0x45, 0x53, 0x53, 0x10, 0x05, 0x12,
+    0x15, 0x0a, 0x11, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x53, 0x49,
+    0x47, 0x4e, 0x45, 0x52, 0x10, 0x06, 0x12, 0x15, 0x0a, 0x11, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54,
+    0x59, 0x50, 0x45, 0x53, 0x5f, 0x56, 0x45, 0x43, 0x54, 0x4f, 0x52, 0x10, 0x07, 0x12, 0x15, 0x0a,
+    0x11, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x53, 0x54, 0x52, 0x55,
+    0x43, 0x54, 0x10, 0x08, 0x12, 0x21, 0x0a, 0x1d, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50,
+    0x45, 0x53, 0x5f, 0x47, 0x45, 0x4e, 0x45, 0x52, 0x49, 0x43, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x5f,
+    0x50, 0x41, 0x52, 0x41, 0x4d, 0x10, 0x09, 0x12, 0x18, 0x0a, 0x14, 0x4d, 0x4f, 0x56, 0x45, 0x5f,
+    0x54, 0x59, 0x50, 0x45, 0x53, 0x5f, 0x52, 0x45, 0x46, 0x45, 0x52, 0x45, 0x4e, 0x43, 0x45, 0x10,
+    0x0a, 0x12, 0x19, 0x0a, 0x15, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x54, 0x59, 0x50, 0x45, 0x53, 0x5f,
+    0x55, 0x4e, 0x50, 0x41, 0x52, 0x53, 0x41, 0x42, 0x4c, 0x45, 0x10, 0x0b, 0x2a, 0x87, 0x01, 0x0a,
+    0x0b, 0x4d, 0x6f, 0x76, 0x65, 0x41, 0x62, 0x69, 0x6c, 0x69, 0x74, 0x79, 0x12, 0x1c, 0x0a, 0x18,
+    0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54, 0x59, 0x5f, 0x55, 0x4e, 0x53,
+    0x50, 0x45, 0x43, 0x49, 0x46, 0x49, 0x45, 0x44, 0x10, 0x00, 0x12, 0x15, 0x0a, 0x11, 0x4d, 0x4f,
+    0x56, 0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54, 0x59, 0x5f, 0x43, 0x4f, 0x50, 0x59, 0x10,
+    0x01, 0x12, 0x15, 0x0a, 0x11, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54,
+    0x59, 0x5f, 0x44, 0x52, 0x4f, 0x50, 0x10, 0x02, 0x12, 0x16, 0x0a, 0x12, 0x4d, 0x4f, 0x56, 0x45,
+    0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54, 0x59, 0x5f, 0x53, 0x54, 0x4f, 0x52, 0x45, 0x10, 0x03,
+    0x12, 0x14, 0x0a, 0x10, 0x4d, 0x4f, 0x56, 0x45, 0x5f, 0x41, 0x42, 0x49, 0x4c, 0x49, 0x54, 0x59,
+    0x5f, 0x4b, 0x45, 0x59, 0x10, 0x04, 0x4a, 0xe0, 0x93, 0x01, 0x0a, 0x07, 0x12, 0x05, 0x03, 0x00,
+    0xf0, 0x03, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x05, 0x00, 0x1d, 0x0a, 0x09, 0x0a,
+    0x02, 0x03, 0x00, 0x12, 0x03, 0x07, 0x00, 0x2e, 0x0a, 0xa3, 0x05, 0x0a, 0x02, 0x04, 0x00, 0x12,
+    0x04, 0x12, 0x00, 0x20, 0x01, 0x1a, 0x96, 0x05, 0x20, 0x41, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b,
+    0x20, 0x6f, 0x6e, 0x20, 0x41, 0x70, 0x74, 0x6f, 0x73, 0x20, 0x68, 0x6f, 0x6c, 0x64, 0x73, 0x20,
+    0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x69, 0x6e, 0x20,
+    0x63, 0x68, 0x72, 0x6f, 0x6e, 0x6f, 0x6c, 0x6f, 0x67, 0x69, 0x63, 0x61, 0x6c, 0x20, 0x6f, 0x72,
+    0x64, 0x65, 0x72, 0x20, 0x28, 0x6f, 0x72, 0x64, 0x65, 0x72, 0x65, 0x64, 0x20, 0x62, 0x79, 0x20,
+    0x61, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x6d,
+    0x6f, 0x6e, 0x6f, 0x74, 0x6f, 0x6e, 0x69, 0x63, 0x61, 0x6c, 0x6c, 0x79, 0x20, 0x69, 0x6e, 0x63,
+    0x72, 0x65, 0x61, 0x73, 0x69, 0x6e, 0x67, 0x20, 0x60, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e,
+    0x60, 0x20, 0x66, 0x69, 0x65, 0x6c, 0x64, 0x29, 0x0a, 0x20, 0x41, 0x6c, 0x6c, 0x20, 0x62, 0x6c,
+    0x6f, 0x63, 0x6b, 0x73, 0x20, 0x73, 0x74, 0x61, 0x72, 0x74, 0x20, 0x77, 0x69, 0x74, 0x68, 0x20,
+    0x61, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61,
+    0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x2c, 0x20, 0x61, 0x6e,
+    0x64, 0x20, 0x61, 0x72, 0x65, 0x20, 0x66, 0x6f, 0x6c, 0x6c, 0x6f, 0x77, 0x65, 0x64, 0x20, 0x62,
+    0x79, 0x20, 0x7a, 0x65, 0x72, 0x6f, 0x20, 0x6f, 0x72, 0x20, 0x6d, 0x6f, 0x72, 0x65, 0x20, 0x74,
+    0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x2e, 0x0a, 0x20, 0x54, 0x68,
+    0x65, 0x20, 0x6e, 0x65, 0x78, 0x74, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74,
     0x61, 0x64, 0x61, 0x74, 0x61, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e,
-    0x60, 0x2c, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x65, 0x76, 0x65, 0x72, 0x79, 0x20, 0x6f, 0x74, 0x68,
-    0x65, 0x72, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x75,
-    0x70, 0x20, 0x74, 0x6f, 0x20, 0x28, 0x62, 0x75, 0x74, 0x20, 0x65, 0x78, 0x63, 0x6c, 0x75, 0x64,
-    0x69, 0x6e, 0x67, 0x29, 0x20, 0x74, 0x68, 0x65, 0x20, 0x6e, 0x65, 0x78, 0x74, 0x20, 0x60, 0x42,
+    0x60, 0x20, 0x64, 0x65, 0x6e, 0x6f, 0x74, 0x65, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x65, 0x6e,
+    0x64, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x75, 0x72, 0x72, 0x65, 0x6e, 0x74,
+    0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x2c, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x74, 0x68, 0x65, 0x20,
+    0x73, 0x74, 0x61, 0x72, 0x74, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x6e, 0x65, 0x78,
+    0x74, 0x20, 0x6f, 0x6e, 0x65, 0x2e, 0x0a, 0x0a, 0x20, 0x54, 0x68, 0x65, 0x20, 0x42, 0x6c, 0x6f,
+    0x63, 0x6b, 0x20, 0x60, 0x68, 0x65, 0x69, 0x67, 0x68, 0x74, 0x60, 0x20, 0x69, 0x73, 0x20, 0x61,
+    0x20, 0x73, 0x74, 0x72, 0x69, 0x63, 0x74, 0x6c, 0x79, 0x20, 0x6d, 0x6f, 0x6e, 0x6f, 0x74, 0x6f,
+    0x6e, 0x69, 0x63, 0x61, 0x6c, 0x6c, 0x79, 0x20, 0x69, 0x6e, 0x63, 0x72, 0x65, 0x61, 0x73, 0x69,
+    0x6e, 0x67, 0x20, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20,
+    0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x20, 0x6f, 0x66, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73,
+    0x2c, 0x0a, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x74, 0x68, 0x65, 0x72, 0x65, 0x20, 0x77, 0x69, 0x6c,
+    0x6c, 0x20, 0x6e, 0x65, 0x76, 0x65, 0x72, 0x20, 0x62, 0x65, 0x20, 0x61, 0x20, 0x67, 0x61, 0x70,
+    0x20, 0x69, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x73, 0x2e,
+    0x20, 0x49, 0x74, 0x20, 0x69, 0x73, 0x20, 0x61, 0x6c, 0x73, 0x6f, 0x20, 0x61, 0x20, 0x75, 0x6e,
+    0x69, 0x71, 0x75, 0x65, 0x20, 0x69, 0x64, 0x65, 0x6e, 0x74, 0x69, 0x66, 0x69, 0x65, 0x72, 0x3a,
+    0x20, 0x74, 0x68, 0x65, 0x72, 0x65, 0x20, 0x77, 0x69, 0x6c, 0x6c, 0x20, 0x6e, 0x65, 0x76, 0x65,
+    0x72, 0x20, 0x62, 0x65, 0x20, 0x74, 0x77, 0x6f, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x73, 0x20,
+    0x77, 0x69, 0x74, 0x68, 0x0a, 0x20, 0x74, 0x68, 0x65, 0x20, 0x73, 0x61, 0x6d, 0x65, 0x20, 0x60,
+    0x68, 0x65, 0x69, 0x67, 0x68, 0x74, 0x60, 0x2e, 0x0a, 0x0a, 0x20, 0x54, 0x68, 0x65, 0x20, 0x47,
+    0x65, 0x6e, 0x65, 0x73, 0x69, 0x73, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69,
+    0x6f, 0x6e, 0x20, 0x28, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x20, 0x30, 0x29, 0x20, 0x69,
+    0x73, 0x20, 0x63, 0x6f, 0x6e, 0x74, 0x61, 0x69, 0x6e, 0x65, 0x64, 0x20, 0x77, 0x69, 0x74, 0x68,
+    0x69, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20, 0x66, 0x69, 0x72, 0x73, 0x74, 0x20, 0x62, 0x6c, 0x6f,
+    0x63, 0x6b, 0x2c, 0x20, 0x77, 0x68, 0x69, 0x63, 0x68, 0x20, 0x68, 0x61, 0x73, 0x20, 0x61, 0x20,
+    0x68, 0x65, 0x69, 0x67, 0x68, 0x74, 0x20, 0x6f, 0x66, 0x20, 0x60, 0x30, 0x60, 0x0a, 0x0a, 0x0a,
+    0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x12, 0x08, 0x0d, 0x0a, 0xde, 0x01, 0x0a, 0x04, 0x04,
+    0x00, 0x02, 0x00, 0x12, 0x03, 0x15, 0x02, 0x2f, 0x1a, 0xd0, 0x01, 0x20, 0x54, 0x69, 0x6d, 0x65,
+    0x73, 0x74, 0x61, 0x6d, 0x70, 0x20, 0x72, 0x65, 0x70, 0x72, 0x65, 0x73, 0x65, 0x6e, 0x74, 0x73,
+    0x20, 0x74, 0x68, 0x65, 0x20, 0x74, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x20, 0x6f,
+    0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74, 0x61,
+    0x64, 0x61, 0x74, 0x61, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60,
+    0x20, 0x28, 0x6f, 0x72, 0x20, 0x60, 0x47, 0x65, 0x6e, 0x65, 0x73, 0x69, 0x73, 0x54, 0x72, 0x61,
+    0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x20, 0x66, 0x6f, 0x72, 0x20, 0x74, 0x68,
+    0x65, 0x20, 0x67, 0x65, 0x6e, 0x65, 0x73, 0x69, 0x73, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x29,
+    0x0a, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x65, 0x76, 0x65, 0x72, 0x79, 0x20, 0x74, 0x72, 0x61, 0x6e,
+    0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x69, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20, 0x60,
+    0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x60, 0x20, 0x77, 0x69,
+    0x6c, 0x6c, 0x20, 0x68, 0x61, 0x76, 0x65, 0x20, 0x74, 0x68, 0x65, 0x20, 0x73, 0x61, 0x6d, 0x65,
+    0x20, 0x60, 0x74, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x60, 0x20, 0x61, 0x73, 0x20,
+    0x74, 0x68, 0x65, 0x20, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x15, 0x21, 0x2a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
+    0x00, 0x03, 0x12, 0x03, 0x15, 0x2d, 0x2e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x06,
+    0x12, 0x03, 0x15, 0x02, 0x20, 0x0a, 0x88, 0x01, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03,
+    0x18, 0x02, 0x29, 0x1a, 0x7b, 0x20, 0x48, 0x65, 0x69, 0x67, 0x68, 0x74, 0x20, 0x72, 0x65, 0x70,
+    0x72, 0x65, 0x73, 0x65, 0x6e, 0x74, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x62, 0x6c, 0x6f, 0x63,
+    0x6b, 0x20, 0x6e, 0x75, 0x6d, 0x62, 0x65, 0x72, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x75, 0x6c, 0x74,
+    0x69, 0x6d, 0x61, 0x74, 0x65, 0x6c, 0x79, 0x2c, 0x20, 0x69, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20,
+    0x63, 0x6f, 0x75, 0x6e, 0x74, 0x20, 0x6f, 0x66, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d,
+    0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69,
+    0x6f, 0x6e, 0x60, 0x20, 0x74, 0x68, 0x61, 0x74, 0x20, 0x68, 0x61, 0x70, 0x70, 0x65, 0x6e, 0x65,
+    0x64, 0x20, 0x6f, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2e, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x18, 0x09, 0x0f, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x18, 0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x01, 0x05, 0x12, 0x03, 0x18, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
+    0x02, 0x01, 0x08, 0x12, 0x03, 0x18, 0x14, 0x28, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x02, 0x01,
+    0x08, 0x06, 0x12, 0x03, 0x18, 0x15, 0x27, 0x0a, 0x87, 0x02, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x02,
+    0x12, 0x03, 0x1c, 0x02, 0x28, 0x1a, 0xf9, 0x01, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63,
+    0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x68, 0x6f, 0x6c, 0x64, 0x73, 0x20, 0x61, 0x6c, 0x6c, 0x20,
+    0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x74, 0x68, 0x61,
+    0x74, 0x20, 0x68, 0x61, 0x70, 0x70, 0x65, 0x6e, 0x65, 0x64, 0x20, 0x69, 0x6e, 0x20, 0x74, 0x68,
+    0x65, 0x20, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x2c, 0x20, 0x77, 0x68, 0x69, 0x63, 0x68, 0x20, 0x69,
+    0x73, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x74,
+    0x68, 0x61, 0x74, 0x20, 0x68, 0x61, 0x70, 0x70, 0x65, 0x6e, 0x65, 0x64, 0x20, 0x73, 0x74, 0x61,
+    0x72, 0x74, 0x69, 0x6e, 0x67, 0x20, 0x77, 0x69, 0x74, 0x68, 0x20, 0x28, 0x61, 0x6e, 0x64, 0x20,
+    0x69, 0x6e, 0x63, 0x6c, 0x75, 0x64, 0x69, 0x6e, 0x67, 0x29, 0x0a, 0x20, 0x61, 0x20, 0x60, 0x42,
     0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x54, 0x72, 0x61, 0x6e,
-    0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
-    0x02, 0x02, 0x04, 0x12, 0x03, 0x1c, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02,
-    0x06, 0x12, 0x03, 0x1c, 0x0b, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12,
-    0x03, 0x1c, 0x17, 0x23, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x03, 0x12, 0x03, 0x1c,
-    0x26, 0x27, 0x0a, 0x99, 0x01, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03, 0x1f, 0x02, 0x16,
-    0x1a, 0x8b, 0x01, 0x20, 0x43, 0x68, 0x61, 0x69, 0x6e, 0x20, 0x49, 0x44, 0x20, 0x69, 0x6e, 0x66,
-    0x6f, 0x72, 0x6d, 0x73, 0x20, 0x75, 0x73, 0x20, 0x77, 0x68, 0x69, 0x63, 0x68, 0x20, 0x63, 0x68,
-    0x61, 0x69, 0x6e, 0x20, 0x77, 0x65, 0x27, 0x72, 0x65, 0x20, 0x74, 0x72, 0x79, 0x69, 0x6e, 0x67,
-    0x20, 0x74, 0x6f, 0x20, 0x69, 0x6e, 0x64, 0x65, 0x78, 0x2c, 0x20, 0x74, 0x68, 0x69, 0x73, 0x20,
-    0x69, 0x73, 0x20, 0x69, 0x6d, 0x70, 0x6f, 0x72, 0x74, 0x61, 0x6e, 0x74, 0x20, 0x74, 0x6f, 0x20,
-    0x65, 0x6e, 0x73, 0x75, 0x72, 0x65, 0x20, 0x74, 0x68, 0x61, 0x74, 0x20, 0x77, 0x65, 0x27, 0x72,
-    0x65, 0x20, 0x6e, 0x6f, 0x74, 0x20, 0x6d, 0x69, 0x78, 0x69, 0x6e, 0x67, 0x20, 0x63, 0x68, 0x61,
-    0x69, 0x6e, 0x73, 0x20, 0x77, 0x69, 0x74, 0x68, 0x69, 0x6e, 0x20, 0x61, 0x20, 0x73, 0x69, 0x6e,
-    0x67, 0x6c, 0x65, 0x20, 0x70, 0x69, 0x70, 0x65, 0x6c, 0x69, 0x6e, 0x65, 0x2e, 0x0a, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x05, 0x12, 0x03, 0x1f, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03, 0x1f, 0x09, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
-    0x02, 0x03, 0x03, 0x12, 0x03, 0x1f, 0x14, 0x15, 0x0a, 0x8c, 0x04, 0x0a, 0x02, 0x04, 0x01, 0x12,
-    0x04, 0x27, 0x00, 0x3e, 0x01, 0x1a, 0xff, 0x03, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63,
-    0x74, 0x69, 0x6f, 0x6e, 0x20, 0x61, 0x73, 0x20, 0x69, 0x74, 0x20, 0x68, 0x61, 0x70, 0x70, 0x65,
-    0x6e, 0x65, 0x64, 0x20, 0x6f, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e,
-    0x2c, 0x20, 0x74, 0x68, 0x65, 0x72, 0x65, 0x20, 0x61, 0x72, 0x65, 0x20, 0x34, 0x20, 0x74, 0x79,
-    0x70, 0x65, 0x73, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69,
-    0x6f, 0x6e, 0x73, 0x3a, 0x0a, 0x20, 0x2d, 0x20, 0x55, 0x73, 0x65, 0x72, 0x20, 0x54, 0x72, 0x61,
-    0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a, 0x20, 0x61, 0x20, 0x75, 0x73, 0x65, 0x72,
-    0x20, 0x69, 0x6e, 0x69, 0x74, 0x69, 0x61, 0x74, 0x65, 0x64, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73,
-    0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x74, 0x6f, 0x20, 0x69, 0x6e, 0x74, 0x65, 0x72, 0x61,
-    0x63, 0x74, 0x20, 0x77, 0x69, 0x74, 0x68, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69,
-    0x6e, 0x0a, 0x20, 0x2d, 0x20, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x20, 0x4d, 0x65, 0x74, 0x61, 0x64,
-    0x61, 0x74, 0x61, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a,
-    0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x67, 0x65,
-    0x6e, 0x65, 0x72, 0x61, 0x74, 0x65, 0x64, 0x20, 0x62, 0x79, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63,
-    0x68, 0x61, 0x69, 0x6e, 0x20, 0x74, 0x6f, 0x20, 0x67, 0x72, 0x6f, 0x75, 0x70, 0x20, 0x74, 0x6f,
-    0x67, 0x65, 0x74, 0x68, 0x65, 0x72, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69,
-    0x6f, 0x6e, 0x73, 0x20, 0x66, 0x6f, 0x72, 0x6d, 0x69, 0x6e, 0x67, 0x20, 0x61, 0x20, 0x22, 0x62,
-    0x6c, 0x6f, 0x63, 0x6b, 0x22, 0x0a, 0x20, 0x2d, 0x20, 0x53, 0x74, 0x61, 0x74, 0x65, 0x20, 0x43,
-    0x68, 0x65, 0x63, 0x6b, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61,
-    0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69,
-    0x6f, 0x6e, 0x73, 0x20, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x61, 0x74, 0x65, 0x64, 0x20, 0x62, 0x79,
-    0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x20, 0x73, 0x6f, 0x20, 0x77, 0x68,
-    0x65, 0x6e, 0x20, 0x76, 0x61, 0x6c, 0x69, 0x64, 0x61, 0x74, 0x6f, 0x72, 0x20, 0x61, 0x67, 0x72,
-    0x65, 0x65, 0x64, 0x20, 0x6f, 0x6e, 0x20, 0x61, 0x20, 0x70, 0x61, 0x72, 0x74, 0x69, 0x63, 0x75,
-    0x6c, 0x61, 0x72, 0x20, 0x67, 0x6c, 0x6f, 0x62, 0x61, 0x6c, 0x20, 0x73, 0x74, 0x61, 0x74, 0x65,
-    0x0a, 0x20, 0x2d, 0x20, 0x47, 0x65, 0x6e, 0x65, 0x73, 0x69, 0x73, 0x20, 0x54, 0x72, 0x61, 0x6e,
-    0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a, 0x20, 0x74, 0x68, 0x65, 0x20, 0x66, 0x69, 0x72,
-    0x73, 0x74, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x6f,
-    0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2c, 0x20, 0x77, 0x69, 0x74,
-    0x68, 0x20, 0x61, 0x6c, 0x6c, 0x20, 0x63, 0x6f, 0x72, 0x65, 0x20, 0x63, 0x6f, 0x6e, 0x74, 0x72,
-    0x61, 0x63, 0x74, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x76, 0x61, 0x6c, 0x69, 0x64, 0x61, 0x74, 0x6f,
-    0x72, 0x20, 0x69, 0x6e, 0x66, 0x6f, 0x72, 0x6d, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x62, 0x61,
-    0x6b, 0x65, 0x64, 0x20, 0x69, 0x6e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x01, 0x01, 0x12, 0x03,
-    0x27, 0x08, 0x13, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x00, 0x12, 0x03, 0x28, 0x02, 0x2f,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x06, 0x12, 0x03, 0x28, 0x02, 0x20, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x01, 0x12, 0x03, 0x28, 0x21, 0x2a, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x01, 0x02, 0x00, 0x03, 0x12, 0x03, 0x28, 0x2d, 0x2e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01,
-    0x02, 0x01, 0x12, 0x03, 0x29, 0x02, 0x2a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x05,
-    0x12, 0x03, 0x29, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x01, 0x12, 0x03,
-    0x29, 0x09, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x03, 0x12, 0x03, 0x29, 0x13,
-    0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x08, 0x12, 0x03, 0x29, 0x15, 0x29, 0x0a,
-    0x0d, 0x0a, 0x06, 0x04, 0x01, 0x02, 0x01, 0x08, 0x06, 0x12, 0x03, 0x29, 0x16, 0x28, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x01, 0x02, 0x02, 0x12, 0x03, 0x2a, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x01, 0x02, 0x02, 0x06, 0x12, 0x03, 0x2a, 0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
-    0x02, 0x01, 0x12, 0x03, 0x2a, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x03,
-    0x12, 0x03, 0x2a, 0x19, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x03, 0x12, 0x03, 0x2b,
-    0x02, 0x28, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x05, 0x12, 0x03, 0x2b, 0x02, 0x08,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x01, 0x12, 0x03, 0x2b, 0x09, 0x0e, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x03, 0x12, 0x03, 0x2b, 0x11, 0x12, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x01, 0x02, 0x03, 0x08, 0x12, 0x03, 0x2b, 0x13, 0x27, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01,
-    0x02, 0x03, 0x08, 0x06, 0x12, 0x03, 0x2b, 0x14, 0x26, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02,
-    0x04, 0x12, 0x03, 0x2c, 0x02, 0x2f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x04, 0x05, 0x12,
-    0x03, 0x2c, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x04, 0x01, 0x12, 0x03, 0x2c,
-    0x09, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x04, 0x03, 0x12, 0x03, 0x2c, 0x18, 0x19,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x04, 0x08, 0x12, 0x03, 0x2c, 0x1a, 0x2e, 0x0a, 0x0d,
-    0x0a, 0x06, 0x04, 0x01, 0x02, 0x04, 0x08, 0x06, 0x12, 0x03, 0x2c, 0x1b, 0x2d, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x01, 0x04, 0x00, 0x12, 0x04, 0x2e, 0x02, 0x34, 0x03, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x01, 0x04, 0x00, 0x01, 0x12, 0x03, 0x2e, 0x07, 0x16, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04,
-    0x00, 0x02, 0x00, 0x12, 0x03, 0x2f, 0x04, 0x25, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00,
-    0x02, 0x00, 0x01, 0x12, 0x03, 0x2f, 0x04, 0x20, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00,
-    0x02, 0x00, 0x02, 0x12, 0x03, 0x2f, 0x23, 0x24, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00,
-    0x02, 0x01, 0x12, 0x03, 0x30, 0x04, 0x21, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02,
-    0x01, 0x01, 0x12, 0x03, 0x30, 0x04, 0x1c, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02,
-    0x01, 0x02, 0x12, 0x03, 0x30, 0x1f, 0x20, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02,
-    0x02, 0x12, 0x03, 0x31, 0x04, 0x28, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x02,
-    0x01, 0x12, 0x03, 0x31, 0x04, 0x23, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x02,
-    0x02, 0x12, 0x03, 0x31, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02, 0x03,
-    0x12, 0x03, 0x32, 0x04, 0x2a, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x03, 0x01,
-    0x12, 0x03, 0x32, 0x04, 0x25, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x03, 0x02,
-    0x12, 0x03, 0x32, 0x28, 0x29, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02, 0x04, 0x12,
-    0x03, 0x33, 0x04, 0x1e, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12,
-    0x03, 0x33, 0x04, 0x19, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12,
-    0x03, 0x33, 0x1c, 0x1d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x05, 0x12, 0x03, 0x36, 0x02,
-    0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x06, 0x12, 0x03, 0x36, 0x02, 0x11, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x01, 0x12, 0x03, 0x36, 0x12, 0x16, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x01, 0x02, 0x05, 0x03, 0x12, 0x03, 0x36, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x01, 0x08, 0x00, 0x12, 0x04, 0x38, 0x02, 0x3d, 0x03, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x08,
-    0x00, 0x01, 0x12, 0x03, 0x38, 0x08, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x06, 0x12,
-    0x03, 0x39, 0x04, 0x30, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06, 0x06, 0x12, 0x03, 0x39,
-    0x04, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06, 0x01, 0x12, 0x03, 0x39, 0x1d, 0x2b,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06, 0x03, 0x12, 0x03, 0x39, 0x2e, 0x2f, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x01, 0x02, 0x07, 0x12, 0x03, 0x3a, 0x04, 0x23, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x01, 0x02, 0x07, 0x06, 0x12, 0x03, 0x3a, 0x04, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
-    0x07, 0x01, 0x12, 0x03, 0x3a, 0x17, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x07, 0x03,
-    0x12, 0x03, 0x3a, 0x21, 0x22, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x08, 0x12, 0x03, 0x3b,
-    0x04, 0x34, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x08, 0x06, 0x12, 0x03, 0x3b, 0x04, 0x1e,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x08, 0x01, 0x12, 0x03, 0x3b, 0x1f, 0x2f, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x01, 0x02, 0x08, 0x03, 0x12, 0x03, 0x3b, 0x32, 0x33, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x01, 0x02, 0x09, 0x12, 0x03, 0x3c, 0x04, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
-    0x09, 0x06, 0x12, 0x03, 0x3c, 0x04, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x09, 0x01,
-    0x12, 0x03, 0x3c, 0x14, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x09, 0x03, 0x12, 0x03,
-    0x3c, 0x1b, 0x1d, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x02, 0x12, 0x04, 0x40, 0x00, 0x47, 0x01, 0x0a,
-    0x0a, 0x0a, 0x03, 0x04, 0x02, 0x01, 0x12, 0x03, 0x40, 0x08, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
-    0x02, 0x02, 0x00, 0x12, 0x03, 0x41, 0x02, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00,
-    0x05, 0x12, 0x03, 0x41, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x01, 0x12,
-    0x03, 0x41, 0x09, 0x0b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x03, 0x12, 0x03, 0x41,
-    0x0e, 0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x01, 0x12, 0x03, 0x42, 0x02, 0x28, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x05, 0x12, 0x03, 0x42, 0x02, 0x08, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x02, 0x02, 0x01, 0x01, 0x12, 0x03, 0x42, 0x09, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x02, 0x02, 0x01, 0x03, 0x12, 0x03, 0x42, 0x11, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02,
-    0x01, 0x08, 0x12, 0x03, 0x42, 0x13, 0x27, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x02, 0x02, 0x01, 0x08,
-    0x06, 0x12, 0x03, 0x42, 0x14, 0x26, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x02, 0x12, 0x03,
-    0x43, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x04, 0x12, 0x03, 0x43, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x06, 0x12, 0x03, 0x43, 0x0b, 0x10, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x01, 0x12, 0x03, 0x43, 0x11, 0x17, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x02, 0x02, 0x02, 0x03, 0x12, 0x03, 0x43, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
-    0x02, 0x02, 0x03, 0x12, 0x03, 0x44, 0x02, 0x28, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03,
-    0x05, 0x12, 0x03, 0x44, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x01, 0x12,
-    0x03, 0x44, 0x08, 0x23, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x03, 0x12, 0x03, 0x44,
-    0x26, 0x27, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x04, 0x12, 0x03, 0x45, 0x02, 0x16, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x05, 0x12, 0x03, 0x45, 0x02, 0x08, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x02, 0x02, 0x04, 0x01, 0x12, 0x03, 0x45, 0x09, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x02, 0x02, 0x04, 0x03, 0x12, 0x03, 0x45, 0x14, 0x15, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02,
-    0x05, 0x12, 0x03, 0x46, 0x02, 0x2e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x04, 0x12,
-    0x03, 0x46, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x05, 0x12, 0x03, 0x46,
-    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x01, 0x12, 0x03, 0x46, 0x12, 0x29,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x03, 0x12, 0x03, 0x46, 0x2c, 0x2d, 0x0a, 0x0a,
-    0x0a, 0x02, 0x04, 0x03, 0x12, 0x04, 0x49, 0x00, 0x4c, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x03,
-    0x01, 0x12, 0x03, 0x49, 0x08, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x00, 0x12, 0x03,
-    0x4a, 0x02, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x06, 0x12, 0x03, 0x4a, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x01, 0x12, 0x03, 0x4a, 0x0b, 0x12, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x03, 0x12, 0x03, 0x4a, 0x15, 0x16, 0x0a, 0x0b, 0x0a,
-    0x04, 0x04, 0x03, 0x02, 0x01, 0x12, 0x03, 0x4b, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
-    0x02, 0x01, 0x04, 0x12, 0x03, 0x4b, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01,
-    0x06, 0x12, 0x03, 0x4b, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x01, 0x12,
-    0x03, 0x4b, 0x11, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x03, 0x12, 0x03, 0x4b,
-    0x1a, 0x1b, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x04, 0x12, 0x04, 0x4e, 0x00, 0x4f, 0x01, 0x0a, 0x0a,
-    0x0a, 0x03, 0x04, 0x04, 0x01, 0x12, 0x03, 0x4e, 0x08, 0x22, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x05,
-    0x12, 0x04, 0x51, 0x00, 0x54, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x05, 0x01, 0x12, 0x03, 0x51,
-    0x08, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x00, 0x12, 0x03, 0x52, 0x02, 0x25, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x06, 0x12, 0x03, 0x52, 0x02, 0x18, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x05, 0x02, 0x00, 0x01, 0x12, 0x03, 0x52, 0x19, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x05, 0x02, 0x00, 0x03, 0x12, 0x03, 0x52, 0x23, 0x24, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02,
-    0x01, 0x12, 0x03, 0x53, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x04, 0x12,
-    0x03, 0x53, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x06, 0x12, 0x03, 0x53,
-    0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x01, 0x12, 0x03, 0x53, 0x11, 0x17,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x03, 0x12, 0x03, 0x53, 0x1a, 0x1b, 0x0a, 0x0a,
-    0x0a, 0x02, 0x04, 0x06, 0x12, 0x04, 0x56, 0x00, 0x5c, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x06,
-    0x01, 0x12, 0x03, 0x56, 0x08, 0x0d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x00, 0x12, 0x03,
-    0x57, 0x02, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x06, 0x12, 0x03, 0x57, 0x02,
-    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x01, 0x12, 0x03, 0x57, 0x0b, 0x0e, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x03, 0x12, 0x03, 0x57, 0x11, 0x12, 0x0a, 0x0b, 0x0a,
-    0x04, 0x04, 0x06, 0x02, 0x01, 0x12, 0x03, 0x58, 0x02, 0x32, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06,
-    0x02, 0x01, 0x05, 0x12, 0x03, 0x58, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01,
-    0x01, 0x12, 0x03, 0x58, 0x09, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x03, 0x12,
-    0x03, 0x58, 0x1b, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x08, 0x12, 0x03, 0x58,
-    0x1d, 0x31, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x06, 0x02, 0x01, 0x08, 0x06, 0x12, 0x03, 0x58, 0x1e,
-    0x30, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x02, 0x12, 0x03, 0x59, 0x02, 0x14, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x06, 0x12, 0x03, 0x59, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x06, 0x02, 0x02, 0x01, 0x12, 0x03, 0x59, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06,
-    0x02, 0x02, 0x03, 0x12, 0x03, 0x59, 0x12, 0x13, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x03,
-    0x12, 0x03, 0x5a, 0x02, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x03, 0x05, 0x12, 0x03,
-    0x5a, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x03, 0x01, 0x12, 0x03, 0x5a, 0x09,
-    0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x03, 0x03, 0x12, 0x03, 0x5a, 0x14, 0x15, 0x0a,
-    0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x04, 0x12, 0x03, 0x5b, 0x02, 0x12, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x06, 0x02, 0x04, 0x05, 0x12, 0x03, 0x5b, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06,
-    0x02, 0x04, 0x01, 0x12, 0x03, 0x5b, 0x09, 0x0d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x04,
-    0x03, 0x12, 0x03, 0x5b, 0x10, 0x11, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x07, 0x12, 0x04, 0x5e, 0x00,
-    0x68, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x07, 0x01, 0x12, 0x03, 0x5e, 0x08, 0x17, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x07, 0x02, 0x00, 0x12, 0x03, 0x5f, 0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x07, 0x02, 0x00, 0x05, 0x12, 0x03, 0x5f, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02,
-    0x00, 0x01, 0x12, 0x03, 0x5f, 0x08, 0x0c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x03,
-    0x12, 0x03, 0x5f, 0x0f, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x01, 0x12, 0x03, 0x60,
-    0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x05, 0x12, 0x03, 0x60, 0x02, 0x07,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x01, 0x12, 0x03, 0x60, 0x08, 0x19, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x03, 0x12, 0x03, 0x60, 0x1c, 0x1d, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x07, 0x02, 0x02, 0x12, 0x03, 0x61, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02,
-    0x02, 0x05, 0x12, 0x03, 0x61, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x02, 0x01,
-    0x12, 0x03, 0x61, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x02, 0x03, 0x12, 0x03,
-    0x61, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x03, 0x12, 0x03, 0x62, 0x02, 0x2b,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x04, 0x12, 0x03, 0x62, 0x02, 0x0a, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x05, 0x12, 0x03, 0x62, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x07, 0x02, 0x03, 0x01, 0x12, 0x03, 0x62, 0x11, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07,
-    0x02, 0x03, 0x03, 0x12, 0x03, 0x62, 0x29, 0x2a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x04,
-    0x12, 0x03, 0x63, 0x02, 0x2b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x04, 0x05, 0x12, 0x03,
-    0x63, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x04, 0x01, 0x12, 0x03, 0x63, 0x09,
-    0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x04, 0x03, 0x12, 0x03, 0x63, 0x14, 0x15, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x04, 0x08, 0x12, 0x03, 0x63, 0x16, 0x2a, 0x0a, 0x0d, 0x0a,
-    0x06, 0x04, 0x07, 0x02, 0x04, 0x08, 0x06, 0x12, 0x03, 0x63, 0x17, 0x29, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x07, 0x02, 0x05, 0x12, 0x03, 0x64, 0x02, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02,
-    0x05, 0x05, 0x12, 0x03, 0x64, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x05, 0x01,
-    0x12, 0x03, 0x64, 0x07, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x05, 0x03, 0x12, 0x03,
-    0x64, 0x11, 0x12, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x06, 0x12, 0x03, 0x65, 0x02, 0x17,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x05, 0x12, 0x03, 0x65, 0x02, 0x08, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x01, 0x12, 0x03, 0x65, 0x09, 0x12, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x07, 0x02, 0x06, 0x03, 0x12, 0x03, 0x65, 0x15, 0x16, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07,
-    0x02, 0x07, 0x12, 0x03, 0x66, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x07, 0x05,
-    0x12, 0x03, 0x66, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x07, 0x01, 0x12, 0x03,
-    0x66, 0x08, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x07, 0x03, 0x12, 0x03, 0x66, 0x20,
-    0x21, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x08, 0x12, 0x03, 0x67, 0x02, 0x26, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x07, 0x02, 0x08, 0x04, 0x12, 0x03, 0x67, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x07, 0x02, 0x08, 0x06, 0x12, 0x03, 0x67, 0x0b, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07,
-    0x02, 0x08, 0x01, 0x12, 0x03, 0x67, 0x1a, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x08,
-    0x03, 0x12, 0x03, 0x67, 0x24, 0x25, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x08, 0x12, 0x04, 0x6a, 0x00,
-    0x6d, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x08, 0x01, 0x12, 0x03, 0x6a, 0x08, 0x10, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x08, 0x02, 0x00, 0x12, 0x03, 0x6b, 0x02, 0x32, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x08, 0x02, 0x00, 0x05, 0x12, 0x03, 0x6b, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02,
-    0x00, 0x01, 0x12, 0x03, 0x6b, 0x09, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x03,
-    0x12, 0x03, 0x6b, 0x1b, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x08, 0x12, 0x03,
-    0x6b, 0x1d, 0x31, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x08, 0x02, 0x00, 0x08, 0x06, 0x12, 0x03, 0x6b,
-    0x1e, 0x30, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x01, 0x12, 0x03, 0x6c, 0x02, 0x1d, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x05, 0x12, 0x03, 0x6c, 0x02, 0x08, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x08, 0x02, 0x01, 0x01, 0x12, 0x03, 0x6c, 0x09, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x08, 0x02, 0x01, 0x03, 0x12, 0x03, 0x6c, 0x1b, 0x1c, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x09, 0x12,
-    0x04, 0x6f, 0x00, 0x77, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x09, 0x01, 0x12, 0x03, 0x6f, 0x08,
-    0x1e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x00, 0x12, 0x03, 0x70, 0x02, 0x14, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x05, 0x12, 0x03, 0x70, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x09, 0x02, 0x00, 0x01, 0x12, 0x03, 0x70, 0x09, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09,
-    0x02, 0x00, 0x03, 0x12, 0x03, 0x70, 0x12, 0x13, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x01,
-    0x12, 0x03, 0x71, 0x02, 0x32, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x05, 0x12, 0x03,
-    0x71, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x01, 0x12, 0x03, 0x71, 0x09,
-    0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x03, 0x12, 0x03, 0x71, 0x1b, 0x1c, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x08, 0x12, 0x03, 0x71, 0x1d, 0x31, 0x0a, 0x0d, 0x0a,
-    0x06, 0x04, 0x09, 0x02, 0x01, 0x08, 0x06, 0x12, 0x03, 0x71, 0x1e, 0x30, 0x0a, 0x0b, 0x0a, 0x04,
-    0x04, 0x09, 0x02, 0x02, 0x12, 0x03, 0x72, 0x02, 0x31, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02,
-    0x02, 0x05, 0x12, 0x03, 0x72, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x02, 0x01,
-    0x12, 0x03, 0x72, 0x09, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x02, 0x03, 0x12, 0x03,
-    0x72, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x02, 0x08, 0x12, 0x03, 0x72, 0x1c,
-    0x30, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x09, 0x02, 0x02, 0x08, 0x06, 0x12, 0x03, 0x72, 0x1d, 0x2f,
-    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x03, 0x12, 0x03, 0x73, 0x02, 0x31, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x09, 0x02, 0x03, 0x05, 0x12, 0x03, 0x73, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x09, 0x02, 0x03, 0x01, 0x12, 0x03, 0x73, 0x09, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02,
-    0x03, 0x03, 0x12, 0x03, 0x73, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x03, 0x08,
-    0x12, 0x03, 0x73, 0x1c, 0x30, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x09, 0x02, 0x03, 0x08, 0x06, 0x12,
-    0x03, 0x73, 0x1d, 0x2f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x04, 0x12, 0x03, 0x74, 0x02,
-    0x3f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x04, 0x06, 0x12, 0x03, 0x74, 0x02, 0x20, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x04, 0x01, 0x12, 0x03, 0x74, 0x21, 0x3a, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x09, 0x02, 0x04, 0x03, 0x12, 0x03, 0x74, 0x3d, 0x3e, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
-    0x09, 0x02, 0x05, 0x12, 0x03, 0x75, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x05,
-    0x06, 0x12, 0x03, 0x75, 0x02, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x05, 0x01, 0x12,
-    0x03, 0x75, 0x15, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x05, 0x03, 0x12, 0x03, 0x75,
-    0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x06, 0x12, 0x03, 0x76, 0x02, 0x1a, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x06, 0x06, 0x12, 0x03, 0x76, 0x02, 0x0b, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x09, 0x02, 0x06, 0x01, 0x12, 0x03, 0x76, 0x0c, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x09, 0x02, 0x06, 0x03, 0x12, 0x03, 0x76, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x02, 0x04, 0x0a, 0x12,
-    0x05, 0x79, 0x00, 0x86, 0x01, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x0a, 0x01, 0x12, 0x03, 0x79,
-    0x08, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0a, 0x04, 0x00, 0x12, 0x04, 0x7b, 0x02, 0x7f, 0x03,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x04, 0x00, 0x01, 0x12, 0x03, 0x7b, 0x07, 0x13, 0x0a, 0x0d,
-    0x0a, 0x06, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x7c, 0x04, 0x23, 0x0a, 0x0e, 0x0a,
-    0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x7c, 0x04, 0x1e, 0x0a, 0x0e, 0x0a,
-    0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x03, 0x7c, 0x21, 0x22, 0x0a, 0x0d, 0x0a,
-    0x06, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x7d, 0x04, 0x28, 0x0a, 0x0e, 0x0a, 0x07,
-    0x04, 0x0a, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x7d, 0x04, 0x23, 0x0a, 0x0e, 0x0a, 0x07,
-    0x04, 0x0a, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x03, 0x7d, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x06,
-    0x04, 0x0a, 0x04, 0x00, 0x02, 0x02, 0x12, 0x03, 0x7e, 0x04, 0x28, 0x0a, 0x0e, 0x0a, 0x07, 0x04,
-    0x0a, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x7e, 0x04, 0x23, 0x0a, 0x0e, 0x0a, 0x07, 0x04,
-    0x0a, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x03, 0x7e, 0x26, 0x27, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x0a, 0x02, 0x00, 0x12, 0x04, 0x81, 0x01, 0x02, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02,
-    0x00, 0x06, 0x12, 0x04, 0x81, 0x01, 0x02, 0x0e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00,
-    0x01, 0x12, 0x04, 0x81, 0x01, 0x0f, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00, 0x03,
-    0x12, 0x04, 0x81, 0x01, 0x20, 0x21, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x0a, 0x08, 0x00, 0x12, 0x06,
-    0x82, 0x01, 0x02, 0x85, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x08, 0x00, 0x01, 0x12,
-    0x04, 0x82, 0x01, 0x08, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0a, 0x02, 0x01, 0x12, 0x04, 0x83,
-    0x01, 0x04, 0x28, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x01, 0x06, 0x12, 0x04, 0x83, 0x01,
-    0x04, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x01, 0x01, 0x12, 0x04, 0x83, 0x01, 0x13,
+    0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x2c, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x65, 0x76,
+    0x65, 0x72, 0x79, 0x20, 0x6f, 0x74, 0x68, 0x65, 0x72, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61,
+    0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x75, 0x70, 0x20, 0x74, 0x6f, 0x20, 0x28, 0x62, 0x75, 0x74,
+    0x20, 0x65, 0x78, 0x63, 0x6c, 0x75, 0x64, 0x69, 0x6e, 0x67, 0x29, 0x20, 0x74, 0x68, 0x65, 0x20,
+    0x6e, 0x65, 0x78, 0x74, 0x20, 0x60, 0x42, 0x6c, 0x6f, 0x63, 0x6b, 0x4d, 0x65, 0x74, 0x61, 0x64,
+    0x61, 0x74, 0x61, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x60, 0x2e,
+    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x1c, 0x17, 0x23, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x03, 0x12, 0x03, 0x1c, 0x26, 0x27, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x02, 0x04, 0x12, 0x03, 0x1c, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x02, 0x06, 0x12, 0x03, 0x1c, 0x0b, 0x16, 0x0a, 0x99, 0x01, 0x0a, 0x04, 0x04, 0x00,
+    0x02, 0x03, 0x12, 0x03, 0x1f, 0x02, 0x16, 0x1a, 0x8b, 0x01, 0x20, 0x43, 0x68, 0x61, 0x69, 0x6e,
+    0x20, 0x49, 0x44, 0x20, 0x69, 0x6e, 0x66, 0x6f, 0x72, 0x6d, 0x73, 0x20, 0x75, 0x73, 0x20, 0x77,
+    0x68, 0x69, 0x63, 0x68, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x20, 0x77, 0x65, 0x27, 0x72, 0x65,
+    0x20, 0x74, 0x72, 0x79, 0x69, 0x6e, 0x67, 0x20, 0x74, 0x6f, 0x20, 0x69, 0x6e, 0x64, 0x65, 0x78,
+    0x2c, 0x20, 0x74, 0x68, 0x69, 0x73, 0x20, 0x69, 0x73, 0x20, 0x69, 0x6d, 0x70, 0x6f, 0x72, 0x74,
+    0x61, 0x6e, 0x74, 0x20, 0x74, 0x6f, 0x20, 0x65, 0x6e, 0x73, 0x75, 0x72, 0x65, 0x20, 0x74, 0x68,
+    0x61, 0x74, 0x20, 0x77, 0x65, 0x27, 0x72, 0x65, 0x20, 0x6e, 0x6f, 0x74, 0x20, 0x6d, 0x69, 0x78,
+    0x69, 0x6e, 0x67, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x73, 0x20, 0x77, 0x69, 0x74, 0x68, 0x69,
+    0x6e, 0x20, 0x61, 0x20, 0x73, 0x69, 0x6e, 0x67, 0x6c, 0x65, 0x20, 0x70, 0x69, 0x70, 0x65, 0x6c,
+    0x69, 0x6e, 0x65, 0x2e, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03,
+    0x1f, 0x09, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x03, 0x12, 0x03, 0x1f, 0x14,
+    0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x05, 0x12, 0x03, 0x1f, 0x02, 0x08, 0x0a,
+    0x8c, 0x04, 0x0a, 0x02, 0x04, 0x01, 0x12, 0x04, 0x27, 0x00, 0x3e, 0x01, 0x1a, 0xff, 0x03, 0x20,
+    0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x61, 0x73, 0x20, 0x69,
+    0x74, 0x20, 0x68, 0x61, 0x70, 0x70, 0x65, 0x6e, 0x65, 0x64, 0x20, 0x6f, 0x6e, 0x20, 0x74, 0x68,
+    0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x2c, 0x20, 0x74, 0x68, 0x65, 0x72, 0x65, 0x20, 0x61,
+    0x72, 0x65, 0x20, 0x34, 0x20, 0x74, 0x79, 0x70, 0x65, 0x73, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x72,
+    0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x3a, 0x0a, 0x20, 0x2d, 0x20, 0x55,
+    0x73, 0x65, 0x72, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a,
+    0x20, 0x61, 0x20, 0x75, 0x73, 0x65, 0x72, 0x20, 0x69, 0x6e, 0x69, 0x74, 0x69, 0x61, 0x74, 0x65,
+    0x64, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x74, 0x6f,
+    0x20, 0x69, 0x6e, 0x74, 0x65, 0x72, 0x61, 0x63, 0x74, 0x20, 0x77, 0x69, 0x74, 0x68, 0x20, 0x74,
+    0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x0a, 0x20, 0x2d, 0x20, 0x42, 0x6c, 0x6f, 0x63,
+    0x6b, 0x20, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73,
+    0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74,
+    0x69, 0x6f, 0x6e, 0x73, 0x20, 0x67, 0x65, 0x6e, 0x65, 0x72, 0x61, 0x74, 0x65, 0x64, 0x20, 0x62,
+    0x79, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x20, 0x74, 0x6f, 0x20, 0x67,
+    0x72, 0x6f, 0x75, 0x70, 0x20, 0x74, 0x6f, 0x67, 0x65, 0x74, 0x68, 0x65, 0x72, 0x20, 0x74, 0x72,
+    0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x66, 0x6f, 0x72, 0x6d, 0x69,
+    0x6e, 0x67, 0x20, 0x61, 0x20, 0x22, 0x62, 0x6c, 0x6f, 0x63, 0x6b, 0x22, 0x0a, 0x20, 0x2d, 0x20,
+    0x53, 0x74, 0x61, 0x74, 0x65, 0x20, 0x43, 0x68, 0x65, 0x63, 0x6b, 0x70, 0x6f, 0x69, 0x6e, 0x74,
+    0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a, 0x20, 0x74, 0x72,
+    0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x73, 0x20, 0x67, 0x65, 0x6e, 0x65, 0x72,
+    0x61, 0x74, 0x65, 0x64, 0x20, 0x62, 0x79, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61, 0x69,
+    0x6e, 0x20, 0x73, 0x6f, 0x20, 0x77, 0x68, 0x65, 0x6e, 0x20, 0x76, 0x61, 0x6c, 0x69, 0x64, 0x61,
+    0x74, 0x6f, 0x72, 0x20, 0x61, 0x67, 0x72, 0x65, 0x65, 0x64, 0x20, 0x6f, 0x6e, 0x20, 0x61, 0x20,
+    0x70, 0x61, 0x72, 0x74, 0x69, 0x63, 0x75, 0x6c, 0x61, 0x72, 0x20, 0x67, 0x6c, 0x6f, 0x62, 0x61,
+    0x6c, 0x20, 0x73, 0x74, 0x61, 0x74, 0x65, 0x0a, 0x20, 0x2d, 0x20, 0x47, 0x65, 0x6e, 0x65, 0x73,
+    0x69, 0x73, 0x20, 0x54, 0x72, 0x61, 0x6e, 0x73, 0x61, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x3a, 0x20,
+    0x74, 0x68, 0x65, 0x20, 0x66, 0x69, 0x72, 0x73, 0x74, 0x20, 0x74, 0x72, 0x61, 0x6e, 0x73, 0x61,
+    0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x68, 0x61,
+    0x69, 0x6e, 0x2c, 0x20, 0x77, 0x69, 0x74, 0x68, 0x20, 0x61, 0x6c, 0x6c, 0x20, 0x63, 0x6f, 0x72,
+    0x65, 0x20, 0x63, 0x6f, 0x6e, 0x74, 0x72, 0x61, 0x63, 0x74, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x76,
+    0x61, 0x6c, 0x69, 0x64, 0x61, 0x74, 0x6f, 0x72, 0x20, 0x69, 0x6e, 0x66, 0x6f, 0x72, 0x6d, 0x61,
+    0x74, 0x69, 0x6f, 0x6e, 0x20, 0x62, 0x61, 0x6b, 0x65, 0x64, 0x20, 0x69, 0x6e, 0x0a, 0x0a, 0x0a,
+    0x0a, 0x03, 0x04, 0x01, 0x01, 0x12, 0x03, 0x27, 0x08, 0x13, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01,
+    0x02, 0x00, 0x12, 0x03, 0x28, 0x02, 0x2f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x01,
+    0x12, 0x03, 0x28, 0x21, 0x2a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x03, 0x12, 0x03,
+    0x28, 0x2d, 0x2e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x06, 0x12, 0x03, 0x28, 0x02,
+    0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x01, 0x12, 0x03, 0x29, 0x02, 0x2a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x01, 0x12, 0x03, 0x29, 0x09, 0x10, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x01, 0x02, 0x01, 0x03, 0x12, 0x03, 0x29, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01,
+    0x02, 0x01, 0x05, 0x12, 0x03, 0x29, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01,
+    0x08, 0x12, 0x03, 0x29, 0x15, 0x29, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x02, 0x01, 0x08, 0x06,
+    0x12, 0x03, 0x29, 0x16, 0x28, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x02, 0x12, 0x03, 0x2a,
+    0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x01, 0x12, 0x03, 0x2a, 0x12, 0x16,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x03, 0x12, 0x03, 0x2a, 0x19, 0x1a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x06, 0x12, 0x03, 0x2a, 0x02, 0x11, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x01, 0x02, 0x03, 0x12, 0x03, 0x2b, 0x02, 0x28, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x03, 0x01, 0x12, 0x03, 0x2b, 0x09, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x03,
+    0x12, 0x03, 0x2b, 0x11, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x05, 0x12, 0x03,
+    0x2b, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x08, 0x12, 0x03, 0x2b, 0x13,
+    0x27, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x02, 0x03, 0x08, 0x06, 0x12, 0x03, 0x2b, 0x14, 0x26,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x04, 0x12, 0x03, 0x2c, 0x02, 0x2f, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x04, 0x01, 0x12, 0x03, 0x2c, 0x09, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x04, 0x03, 0x12, 0x03, 0x2c, 0x18, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x04, 0x05, 0x12, 0x03, 0x2c, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x04, 0x08,
+    0x12, 0x03, 0x2c, 0x1a, 0x2e, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x02, 0x04, 0x08, 0x06, 0x12,
+    0x03, 0x2c, 0x1b, 0x2d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x05, 0x12, 0x03, 0x36, 0x02,
+    0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x01, 0x12, 0x03, 0x36, 0x12, 0x16, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x03, 0x12, 0x03, 0x36, 0x19, 0x1a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x05, 0x06, 0x12, 0x03, 0x36, 0x02, 0x11, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x01, 0x02, 0x06, 0x12, 0x03, 0x39, 0x04, 0x30, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06,
+    0x01, 0x12, 0x03, 0x39, 0x1d, 0x2b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06, 0x03, 0x12,
+    0x03, 0x39, 0x2e, 0x2f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06, 0x06, 0x12, 0x03, 0x39,
+    0x04, 0x1c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x07, 0x12, 0x03, 0x3a, 0x04, 0x23, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x07, 0x01, 0x12, 0x03, 0x3a, 0x17, 0x1e, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x07, 0x03, 0x12, 0x03, 0x3a, 0x21, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x07, 0x06, 0x12, 0x03, 0x3a, 0x04, 0x16, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02,
+    0x08, 0x12, 0x03, 0x3b, 0x04, 0x34, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x08, 0x01, 0x12,
+    0x03, 0x3b, 0x1f, 0x2f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x08, 0x03, 0x12, 0x03, 0x3b,
+    0x32, 0x33, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x08, 0x06, 0x12, 0x03, 0x3b, 0x04, 0x1e,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x09, 0x12, 0x03, 0x3c, 0x04, 0x1e, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x09, 0x01, 0x12, 0x03, 0x3c, 0x14, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x09, 0x03, 0x12, 0x03, 0x3c, 0x1b, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x09, 0x06, 0x12, 0x03, 0x3c, 0x04, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x01, 0x04, 0x00, 0x12,
+    0x04, 0x2e, 0x02, 0x34, 0x03, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x04, 0x00, 0x01, 0x12, 0x03,
+    0x2e, 0x07, 0x16, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x2f,
+    0x04, 0x25, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x2f,
+    0x04, 0x20, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x03, 0x2f,
+    0x23, 0x24, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x30, 0x04,
+    0x21, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x30, 0x04,
+    0x1c, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x03, 0x30, 0x1f,
+    0x20, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02, 0x02, 0x12, 0x03, 0x31, 0x04, 0x28,
+    0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x31, 0x04, 0x23,
+    0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x03, 0x31, 0x26, 0x27,
+    0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03, 0x32, 0x04, 0x2a, 0x0a,
+    0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03, 0x32, 0x04, 0x25, 0x0a,
+    0x0e, 0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x03, 0x32, 0x28, 0x29, 0x0a,
+    0x0d, 0x0a, 0x06, 0x04, 0x01, 0x04, 0x00, 0x02, 0x04, 0x12, 0x03, 0x33, 0x04, 0x1e, 0x0a, 0x0e,
+    0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x03, 0x33, 0x04, 0x19, 0x0a, 0x0e,
+    0x0a, 0x07, 0x04, 0x01, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12, 0x03, 0x33, 0x1c, 0x1d, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x01, 0x08, 0x00, 0x12, 0x04, 0x38, 0x02, 0x3d, 0x03, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x01, 0x08, 0x00, 0x01, 0x12, 0x03, 0x38, 0x08, 0x10, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x02,
+    0x12, 0x04, 0x40, 0x00, 0x47, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x02, 0x01, 0x12, 0x03, 0x40,
+    0x08, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x00, 0x12, 0x03, 0x41, 0x02, 0x10, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x00, 0x01, 0x12, 0x03, 0x41, 0x09, 0x0b, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x02, 0x02, 0x00, 0x03, 0x12, 0x03, 0x41, 0x0e, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x02, 0x02, 0x00, 0x05, 0x12, 0x03, 0x41, 0x02, 0x08, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02,
+    0x01, 0x12, 0x03, 0x42, 0x02, 0x28, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x01, 0x12,
+    0x03, 0x42, 0x09, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x03, 0x12, 0x03, 0x42,
+    0x11, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x05, 0x12, 0x03, 0x42, 0x02, 0x08,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x01, 0x08, 0x12, 0x03, 0x42, 0x13, 0x27, 0x0a, 0x0d,
+    0x0a, 0x06, 0x04, 0x02, 0x02, 0x01, 0x08, 0x06, 0x12, 0x03, 0x42, 0x14, 0x26, 0x0a, 0x0b, 0x0a,
+    0x04, 0x04, 0x02, 0x02, 0x02, 0x12, 0x03, 0x43, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02,
+    0x02, 0x02, 0x01, 0x12, 0x03, 0x43, 0x11, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02,
+    0x03, 0x12, 0x03, 0x43, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x04, 0x12,
+    0x03, 0x43, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x02, 0x06, 0x12, 0x03, 0x43,
+    0x0b, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x03, 0x12, 0x03, 0x44, 0x02, 0x28, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x03, 0x01, 0x12, 0x03, 0x44, 0x08, 0x23, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x02, 0x02, 0x03, 0x03, 0x12, 0x03, 0x44, 0x26, 0x27, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x02, 0x02, 0x03, 0x05, 0x12, 0x03, 0x44, 0x02, 0x07, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02,
+    0x04, 0x12, 0x03, 0x45, 0x02, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x01, 0x12,
+    0x03, 0x45, 0x09, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x03, 0x12, 0x03, 0x45,
+    0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x04, 0x05, 0x12, 0x03, 0x45, 0x02, 0x08,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x02, 0x02, 0x05, 0x12, 0x03, 0x46, 0x02, 0x2e, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x02, 0x02, 0x05, 0x01, 0x12, 0x03, 0x46, 0x12, 0x29, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x02, 0x02, 0x05, 0x03, 0x12, 0x03, 0x46, 0x2c, 0x2d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02,
+    0x05, 0x04, 0x12, 0x03, 0x46, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x02, 0x02, 0x05, 0x05,
+    0x12, 0x03, 0x46, 0x0b, 0x11, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x03, 0x12, 0x04, 0x49, 0x00, 0x4c,
+    0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x03, 0x01, 0x12, 0x03, 0x49, 0x08, 0x1a, 0x0a, 0x0b, 0x0a,
+    0x04, 0x04, 0x03, 0x02, 0x00, 0x12, 0x03, 0x4a, 0x02, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03,
+    0x02, 0x00, 0x01, 0x12, 0x03, 0x4a, 0x0b, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00,
+    0x03, 0x12, 0x03, 0x4a, 0x15, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x00, 0x06, 0x12,
+    0x03, 0x4a, 0x02, 0x0a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x03, 0x02, 0x01, 0x12, 0x03, 0x4b, 0x02,
+    0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x01, 0x12, 0x03, 0x4b, 0x11, 0x17, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x03, 0x02, 0x01, 0x03, 0x12, 0x03, 0x4b, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x03, 0x02, 0x01, 0x04, 0x12, 0x03, 0x4b, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x03, 0x02, 0x01, 0x06, 0x12, 0x03, 0x4b, 0x0b, 0x10, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x04, 0x12,
+    0x04, 0x4e, 0x00, 0x4f, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x04, 0x01, 0x12, 0x03, 0x4e, 0x08,
+    0x22, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x05, 0x12, 0x04, 0x51, 0x00, 0x54, 0x01, 0x0a, 0x0a, 0x0a,
+    0x03, 0x04, 0x05, 0x01, 0x12, 0x03, 0x51, 0x08, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02,
+    0x00, 0x12, 0x03, 0x52, 0x02, 0x25, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x01, 0x12,
+    0x03, 0x52, 0x19, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x03, 0x12, 0x03, 0x52,
+    0x23, 0x24, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x00, 0x06, 0x12, 0x03, 0x52, 0x02, 0x18,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x05, 0x02, 0x01, 0x12, 0x03, 0x53, 0x02, 0x1c, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x05, 0x02, 0x01, 0x01, 0x12, 0x03, 0x53, 0x11, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x05, 0x02, 0x01, 0x03, 0x12, 0x03, 0x53, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02,
+    0x01, 0x04, 0x12, 0x03, 0x53, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x05, 0x02, 0x01, 0x06,
+    0x12, 0x03, 0x53, 0x0b, 0x10, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x06, 0x12, 0x04, 0x56, 0x00, 0x5c,
+    0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x06, 0x01, 0x12, 0x03, 0x56, 0x08, 0x0d, 0x0a, 0x0b, 0x0a,
+    0x04, 0x04, 0x06, 0x02, 0x00, 0x12, 0x03, 0x57, 0x02, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06,
+    0x02, 0x00, 0x01, 0x12, 0x03, 0x57, 0x0b, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00,
+    0x03, 0x12, 0x03, 0x57, 0x11, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x00, 0x06, 0x12,
+    0x03, 0x57, 0x02, 0x0a, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x01, 0x12, 0x03, 0x58, 0x02,
+    0x32, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x01, 0x12, 0x03, 0x58, 0x09, 0x18, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x01, 0x03, 0x12, 0x03, 0x58, 0x1b, 0x1c, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x06, 0x02, 0x01, 0x05, 0x12, 0x03, 0x58, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x06, 0x02, 0x01, 0x08, 0x12, 0x03, 0x58, 0x1d, 0x31, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x06, 0x02,
+    0x01, 0x08, 0x06, 0x12, 0x03, 0x58, 0x1e, 0x30, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x02,
+    0x12, 0x03, 0x59, 0x02, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x01, 0x12, 0x03,
+    0x59, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x03, 0x12, 0x03, 0x59, 0x12,
+    0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x02, 0x06, 0x12, 0x03, 0x59, 0x02, 0x0a, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x03, 0x12, 0x03, 0x5a, 0x02, 0x16, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x06, 0x02, 0x03, 0x01, 0x12, 0x03, 0x5a, 0x09, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06,
+    0x02, 0x03, 0x03, 0x12, 0x03, 0x5a, 0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x03,
+    0x05, 0x12, 0x03, 0x5a, 0x02, 0x08, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x06, 0x02, 0x04, 0x12, 0x03,
+    0x5b, 0x02, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x04, 0x01, 0x12, 0x03, 0x5b, 0x09,
+    0x0d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x04, 0x03, 0x12, 0x03, 0x5b, 0x10, 0x11, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x06, 0x02, 0x04, 0x05, 0x12, 0x03, 0x5b, 0x02, 0x08, 0x0a, 0x0a, 0x0a,
+    0x02, 0x04, 0x07, 0x12, 0x04, 0x5e, 0x00, 0x68, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x07, 0x01,
+    0x12, 0x03, 0x5e, 0x08, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x00, 0x12, 0x03, 0x5f,
+    0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x01, 0x12, 0x03, 0x5f, 0x08, 0x0c,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x03, 0x12, 0x03, 0x5f, 0x0f, 0x10, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x07, 0x02, 0x00, 0x05, 0x12, 0x03, 0x5f, 0x02, 0x07, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x07, 0x02, 0x01, 0x12, 0x03, 0x60, 0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02,
+    0x01, 0x01, 0x12, 0x03, 0x60, 0x08, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x03,
+    0x12, 0x03, 0x60, 0x1c, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x01, 0x05, 0x12, 0x03,
+    0x60, 0x02, 0x07, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x02, 0x12, 0x03, 0x61, 0x02, 0x1c,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x02, 0x01, 0x12, 0x03, 0x61, 0x08, 0x17, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x07, 0x02, 0x02, 0x03, 0x12, 0x03, 0x61, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x07, 0x02, 0x02, 0x05, 0x12, 0x03, 0x61, 0x02, 0x07, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07,
+    0x02, 0x03, 0x12, 0x03, 0x62, 0x02, 0x2b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x01,
+    0x12, 0x03, 0x62, 0x11, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x03, 0x12, 0x03,
+    0x62, 0x29, 0x2a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x04, 0x12, 0x03, 0x62, 0x02,
+    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x03, 0x05, 0x12, 0x03, 0x62, 0x0b, 0x10, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x04, 0x12, 0x03, 0x63, 0x02, 0x2b, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x07, 0x02, 0x04, 0x01, 0x12, 0x03, 0x63, 0x09, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07,
+    0x02, 0x04, 0x03, 0x12, 0x03, 0x63, 0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x04,
+    0x05, 0x12, 0x03, 0x63, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x04, 0x08, 0x12,
+    0x03, 0x63, 0x16, 0x2a, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x07, 0x02, 0x04, 0x08, 0x06, 0x12, 0x03,
+    0x63, 0x17, 0x29, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x05, 0x12, 0x03, 0x64, 0x02, 0x13,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x05, 0x01, 0x12, 0x03, 0x64, 0x07, 0x0e, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x07, 0x02, 0x05, 0x03, 0x12, 0x03, 0x64, 0x11, 0x12, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x07, 0x02, 0x05, 0x05, 0x12, 0x03, 0x64, 0x02, 0x06, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07,
+    0x02, 0x06, 0x12, 0x03, 0x65, 0x02, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x01,
+    0x12, 0x03, 0x65, 0x09, 0x12, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x03, 0x12, 0x03,
+    0x65, 0x15, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x06, 0x05, 0x12, 0x03, 0x65, 0x02,
+    0x08, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x07, 0x12, 0x03, 0x66, 0x02, 0x22, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x07, 0x02, 0x07, 0x01, 0x12, 0x03, 0x66, 0x08, 0x1d, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x07, 0x02, 0x07, 0x03, 0x12, 0x03, 0x66, 0x20, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07,
+    0x02, 0x07, 0x05, 0x12, 0x03, 0x66, 0x02, 0x07, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x07, 0x02, 0x08,
+    0x12, 0x03, 0x67, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x08, 0x01, 0x12, 0x03,
+    0x67, 0x1a, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x08, 0x03, 0x12, 0x03, 0x67, 0x24,
+    0x25, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x08, 0x04, 0x12, 0x03, 0x67, 0x02, 0x0a, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x07, 0x02, 0x08, 0x06, 0x12, 0x03, 0x67, 0x0b, 0x19, 0x0a, 0x0a, 0x0a,
+    0x02, 0x04, 0x08, 0x12, 0x04, 0x6a, 0x00, 0x6d, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x08, 0x01,
+    0x12, 0x03, 0x6a, 0x08, 0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02, 0x00, 0x12, 0x03, 0x6b,
+    0x02, 0x32, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x01, 0x12, 0x03, 0x6b, 0x09, 0x18,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x03, 0x12, 0x03, 0x6b, 0x1b, 0x1c, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x08, 0x02, 0x00, 0x05, 0x12, 0x03, 0x6b, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x08, 0x02, 0x00, 0x08, 0x12, 0x03, 0x6b, 0x1d, 0x31, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x08,
+    0x02, 0x00, 0x08, 0x06, 0x12, 0x03, 0x6b, 0x1e, 0x30, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x08, 0x02,
+    0x01, 0x12, 0x03, 0x6c, 0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x01, 0x12,
+    0x03, 0x6c, 0x09, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x03, 0x12, 0x03, 0x6c,
+    0x1b, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x08, 0x02, 0x01, 0x05, 0x12, 0x03, 0x6c, 0x02, 0x08,
+    0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x09, 0x12, 0x04, 0x6f, 0x00, 0x77, 0x01, 0x0a, 0x0a, 0x0a, 0x03,
+    0x04, 0x09, 0x01, 0x12, 0x03, 0x6f, 0x08, 0x1e, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x00,
+    0x12, 0x03, 0x70, 0x02, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x01, 0x12, 0x03,
+    0x70, 0x09, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x03, 0x12, 0x03, 0x70, 0x12,
+    0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x00, 0x05, 0x12, 0x03, 0x70, 0x02, 0x08, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x01, 0x12, 0x03, 0x71, 0x02, 0x32, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x09, 0x02, 0x01, 0x01, 0x12, 0x03, 0x71, 0x09, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09,
+    0x02, 0x01, 0x03, 0x12, 0x03, 0x71, 0x1b, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01,
+    0x05, 0x12, 0x03, 0x71, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x01, 0x08, 0x12,
+    0x03, 0x71, 0x1d, 0x31, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x09, 0x02, 0x01, 0x08, 0x06, 0x12, 0x03,
+    0x71, 0x1e, 0x30, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x02, 0x12, 0x03, 0x72, 0x02, 0x31,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x02, 0x01, 0x12, 0x03, 0x72, 0x09, 0x17, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x09, 0x02, 0x02, 0x03, 0x12, 0x03, 0x72, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x09, 0x02, 0x02, 0x05, 0x12, 0x03, 0x72, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09,
+    0x02, 0x02, 0x08, 0x12, 0x03, 0x72, 0x1c, 0x30, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x09, 0x02, 0x02,
+    0x08, 0x06, 0x12, 0x03, 0x72, 0x1d, 0x2f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x03, 0x12,
+    0x03, 0x73, 0x02, 0x31, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x03, 0x01, 0x12, 0x03, 0x73,
+    0x09, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x03, 0x03, 0x12, 0x03, 0x73, 0x1a, 0x1b,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x03, 0x05, 0x12, 0x03, 0x73, 0x02, 0x08, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x09, 0x02, 0x03, 0x08, 0x12, 0x03, 0x73, 0x1c, 0x30, 0x0a, 0x0d, 0x0a, 0x06,
+    0x04, 0x09, 0x02, 0x03, 0x08, 0x06, 0x12, 0x03, 0x73, 0x1d, 0x2f, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x09, 0x02, 0x04, 0x12, 0x03, 0x74, 0x02, 0x3f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x04,
+    0x01, 0x12, 0x03, 0x74, 0x21, 0x3a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x04, 0x03, 0x12,
+    0x03, 0x74, 0x3d, 0x3e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x04, 0x06, 0x12, 0x03, 0x74,
+    0x02, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02, 0x05, 0x12, 0x03, 0x75, 0x02, 0x21, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x05, 0x01, 0x12, 0x03, 0x75, 0x15, 0x1c, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x09, 0x02, 0x05, 0x03, 0x12, 0x03, 0x75, 0x1f, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x09, 0x02, 0x05, 0x06, 0x12, 0x03, 0x75, 0x02, 0x14, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x09, 0x02,
+    0x06, 0x12, 0x03, 0x76, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x06, 0x01, 0x12,
+    0x03, 0x76, 0x0c, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x06, 0x03, 0x12, 0x03, 0x76,
+    0x18, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x09, 0x02, 0x06, 0x06, 0x12, 0x03, 0x76, 0x02, 0x0b,
+    0x0a, 0x0b, 0x0a, 0x02, 0x04, 0x0a, 0x12, 0x05, 0x79, 0x00, 0x86, 0x01, 0x01, 0x0a, 0x0a, 0x0a,
+    0x03, 0x04, 0x0a, 0x01, 0x12, 0x03, 0x79, 0x08, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0a, 0x02,
+    0x00, 0x12, 0x04, 0x81, 0x01, 0x02, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00, 0x01,
+    0x12, 0x04, 0x81, 0x01, 0x0f, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00, 0x03, 0x12,
+    0x04, 0x81, 0x01, 0x20, 0x21, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x00, 0x06, 0x12, 0x04,
+    0x81, 0x01, 0x02, 0x0e, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0a, 0x02, 0x01, 0x12, 0x04, 0x83, 0x01,
+    0x04, 0x28, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x01, 0x01, 0x12, 0x04, 0x83, 0x01, 0x13,
     0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x01, 0x03, 0x12, 0x04, 0x83, 0x01, 0x26, 0x27,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0a, 0x02, 0x02, 0x12, 0x04, 0x84, 0x01, 0x04, 0x28, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x0a, 0x02, 0x02, 0x06, 0x12, 0x04, 0x84, 0x01, 0x04, 0x12, 0x0a, 0x0d, 0x0a,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x02, 0x01, 0x06, 0x12, 0x04, 0x83, 0x01, 0x04, 0x12, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x0a, 0x02, 0x02, 0x12, 0x04, 0x84, 0x01, 0x04, 0x28, 0x0a, 0x0d, 0x0a,
     0x05, 0x04, 0x0a, 0x02, 0x02, 0x01, 0x12, 0x04, 0x84, 0x01, 0x13, 0x23, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x0a, 0x02, 0x02, 0x03, 0x12, 0x04, 0x84, 0x01, 0x26, 0x27, 0x0a, 0x0c, 0x0a, 0x02, 0x04,
-    0x0b, 0x12, 0x06, 0x88, 0x01, 0x00, 0x8b, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0b, 0x01,
-    0x12, 0x04, 0x88, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0b, 0x02, 0x00, 0x12, 0x04,
-    0x89, 0x01, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x00, 0x05, 0x12, 0x04, 0x89,
-    0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x00, 0x01, 0x12, 0x04, 0x89, 0x01,
-    0x09, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x00, 0x03, 0x12, 0x04, 0x89, 0x01, 0x16,
-    0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0b, 0x02, 0x01, 0x12, 0x04, 0x8a, 0x01, 0x02, 0x1b, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x01, 0x06, 0x12, 0x04, 0x8a, 0x01, 0x02, 0x0f, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x0b, 0x02, 0x01, 0x01, 0x12, 0x04, 0x8a, 0x01, 0x10, 0x16, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x0b, 0x02, 0x01, 0x03, 0x12, 0x04, 0x8a, 0x01, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x02,
-    0x04, 0x0c, 0x12, 0x06, 0x8d, 0x01, 0x00, 0x90, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0c,
-    0x01, 0x12, 0x04, 0x8d, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0c, 0x02, 0x00, 0x12,
-    0x04, 0x8e, 0x01, 0x02, 0x2f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x00, 0x04, 0x12, 0x04,
-    0x8e, 0x01, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x00, 0x06, 0x12, 0x04, 0x8e,
-    0x01, 0x0b, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x00, 0x01, 0x12, 0x04, 0x8e, 0x01,
-    0x1a, 0x2a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x00, 0x03, 0x12, 0x04, 0x8e, 0x01, 0x2d,
-    0x2e, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0c, 0x02, 0x01, 0x12, 0x04, 0x8f, 0x01, 0x02, 0x1c, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x01, 0x04, 0x12, 0x04, 0x8f, 0x01, 0x02, 0x0a, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x0c, 0x02, 0x01, 0x06, 0x12, 0x04, 0x8f, 0x01, 0x0b, 0x10, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x0c, 0x02, 0x01, 0x01, 0x12, 0x04, 0x8f, 0x01, 0x11, 0x17, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x0c, 0x02, 0x01, 0x03, 0x12, 0x04, 0x8f, 0x01, 0x1a, 0x1b, 0x0a, 0x0c, 0x0a, 0x02, 0x04,
-    0x0d, 0x12, 0x06, 0x92, 0x01, 0x00, 0xa8, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0d, 0x01,
-    0x12, 0x04, 0x92, 0x01, 0x08, 0x16, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x0d, 0x04, 0x00, 0x12, 0x06,
-    0x94, 0x01, 0x02, 0x9c, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x04, 0x00, 0x01, 0x12,
-    0x04, 0x94, 0x01, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x00, 0x12,
-    0x04, 0x95, 0x01, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x00, 0x01,
-    0x12, 0x04, 0x95, 0x01, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x00,
-    0x02, 0x12, 0x04, 0x95, 0x01, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02,
-    0x01, 0x12, 0x04, 0x96, 0x01, 0x04, 0x1b, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02,
-    0x01, 0x01, 0x12, 0x04, 0x96, 0x01, 0x04, 0x16, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00,
-    0x02, 0x01, 0x02, 0x12, 0x04, 0x96, 0x01, 0x19, 0x1a, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04,
-    0x00, 0x02, 0x02, 0x12, 0x04, 0x97, 0x01, 0x04, 0x1d, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04,
-    0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0x97, 0x01, 0x04, 0x18, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d,
-    0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0x97, 0x01, 0x1b, 0x1c, 0x0a, 0x0e, 0x0a, 0x06, 0x04,
-    0x0d, 0x04, 0x00, 0x02, 0x03, 0x12, 0x04, 0x98, 0x01, 0x04, 0x1f, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
-    0x0d, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x04, 0x98, 0x01, 0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07,
-    0x04, 0x0d, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x04, 0x98, 0x01, 0x1d, 0x1e, 0x0a, 0x0e, 0x0a,
-    0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x04, 0x12, 0x04, 0x99, 0x01, 0x04, 0x1a, 0x0a, 0x0f, 0x0a,
-    0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x04, 0x99, 0x01, 0x04, 0x15, 0x0a, 0x0f,
-    0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12, 0x04, 0x99, 0x01, 0x18, 0x19, 0x0a,
-    0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x05, 0x12, 0x04, 0x9a, 0x01, 0x04, 0x1c, 0x0a,
-    0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x05, 0x01, 0x12, 0x04, 0x9a, 0x01, 0x04, 0x17,
-    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x05, 0x02, 0x12, 0x04, 0x9a, 0x01, 0x1a,
-    0x1b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x06, 0x12, 0x04, 0x9b, 0x01, 0x04,
-    0x1e, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x06, 0x01, 0x12, 0x04, 0x9b, 0x01,
-    0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x06, 0x02, 0x12, 0x04, 0x9b,
-    0x01, 0x1c, 0x1d, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x00, 0x12, 0x04, 0x9e, 0x01, 0x02,
-    0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x00, 0x06, 0x12, 0x04, 0x9e, 0x01, 0x02, 0x06,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x00, 0x01, 0x12, 0x04, 0x9e, 0x01, 0x07, 0x0b, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x00, 0x03, 0x12, 0x04, 0x9e, 0x01, 0x0e, 0x0f, 0x0a, 0x0e,
-    0x0a, 0x04, 0x04, 0x0d, 0x08, 0x00, 0x12, 0x06, 0xa0, 0x01, 0x02, 0xa7, 0x01, 0x03, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x0d, 0x08, 0x00, 0x01, 0x12, 0x04, 0xa0, 0x01, 0x08, 0x0e, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x0d, 0x02, 0x01, 0x12, 0x04, 0xa1, 0x01, 0x04, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x0d, 0x02, 0x01, 0x06, 0x12, 0x04, 0xa1, 0x01, 0x04, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d,
-    0x02, 0x01, 0x01, 0x12, 0x04, 0xa1, 0x01, 0x11, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02,
-    0x01, 0x03, 0x12, 0x04, 0xa1, 0x01, 0x21, 0x22, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x02,
-    0x12, 0x04, 0xa2, 0x01, 0x04, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x02, 0x06, 0x12,
-    0x04, 0xa2, 0x01, 0x04, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x02, 0x01, 0x12, 0x04,
-    0xa2, 0x01, 0x13, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x02, 0x03, 0x12, 0x04, 0xa2,
-    0x01, 0x25, 0x26, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x03, 0x12, 0x04, 0xa3, 0x01, 0x04,
-    0x2a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x03, 0x06, 0x12, 0x04, 0xa3, 0x01, 0x04, 0x13,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x03, 0x01, 0x12, 0x04, 0xa3, 0x01, 0x14, 0x25, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x03, 0x03, 0x12, 0x04, 0xa3, 0x01, 0x28, 0x29, 0x0a, 0x0c,
-    0x0a, 0x04, 0x04, 0x0d, 0x02, 0x04, 0x12, 0x04, 0xa4, 0x01, 0x04, 0x21, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x0d, 0x02, 0x04, 0x06, 0x12, 0x04, 0xa4, 0x01, 0x04, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x0d, 0x02, 0x04, 0x01, 0x12, 0x04, 0xa4, 0x01, 0x10, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d,
-    0x02, 0x04, 0x03, 0x12, 0x04, 0xa4, 0x01, 0x1f, 0x20, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02,
-    0x05, 0x12, 0x04, 0xa5, 0x01, 0x04, 0x25, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x05, 0x06,
-    0x12, 0x04, 0xa5, 0x01, 0x04, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x05, 0x01, 0x12,
-    0x04, 0xa5, 0x01, 0x12, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x05, 0x03, 0x12, 0x04,
-    0xa5, 0x01, 0x23, 0x24, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x06, 0x12, 0x04, 0xa6, 0x01,
-    0x04, 0x28, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x06, 0x06, 0x12, 0x04, 0xa6, 0x01, 0x04,
-    0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x06, 0x01, 0x12, 0x04, 0xa6, 0x01, 0x13, 0x23,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x06, 0x03, 0x12, 0x04, 0xa6, 0x01, 0x26, 0x27, 0x0a,
-    0x0c, 0x0a, 0x02, 0x04, 0x0e, 0x12, 0x06, 0xaa, 0x01, 0x00, 0xae, 0x01, 0x01, 0x0a, 0x0b, 0x0a,
-    0x03, 0x04, 0x0e, 0x01, 0x12, 0x04, 0xaa, 0x01, 0x08, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0e,
-    0x02, 0x00, 0x12, 0x04, 0xab, 0x01, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x00,
-    0x05, 0x12, 0x04, 0xab, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x00, 0x01,
-    0x12, 0x04, 0xab, 0x01, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x00, 0x03, 0x12,
-    0x04, 0xab, 0x01, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0e, 0x02, 0x01, 0x12, 0x04, 0xac,
-    0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x01, 0x05, 0x12, 0x04, 0xac, 0x01,
-    0x02, 0x07, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x01, 0x01, 0x12, 0x04, 0xac, 0x01, 0x08,
-    0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x01, 0x03, 0x12, 0x04, 0xac, 0x01, 0x19, 0x1a,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0e, 0x02, 0x02, 0x12, 0x04, 0xad, 0x01, 0x02, 0x1a, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x0e, 0x02, 0x02, 0x06, 0x12, 0x04, 0xad, 0x01, 0x02, 0x0e, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x0e, 0x02, 0x02, 0x01, 0x12, 0x04, 0xad, 0x01, 0x0f, 0x15, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x0e, 0x02, 0x02, 0x03, 0x12, 0x04, 0xad, 0x01, 0x18, 0x19, 0x0a, 0x0c, 0x0a, 0x02, 0x04,
-    0x0f, 0x12, 0x06, 0xb0, 0x01, 0x00, 0xb5, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0f, 0x01,
-    0x12, 0x04, 0xb0, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0f, 0x02, 0x00, 0x12, 0x04,
-    0xb1, 0x01, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x00, 0x05, 0x12, 0x04, 0xb1,
-    0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x00, 0x01, 0x12, 0x04, 0xb1, 0x01,
-    0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x00, 0x03, 0x12, 0x04, 0xb1, 0x01, 0x13,
-    0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0f, 0x02, 0x01, 0x12, 0x04, 0xb2, 0x01, 0x02, 0x1b, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x01, 0x05, 0x12, 0x04, 0xb2, 0x01, 0x02, 0x07, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x0f, 0x02, 0x01, 0x01, 0x12, 0x04, 0xb2, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x0f, 0x02, 0x01, 0x03, 0x12, 0x04, 0xb2, 0x01, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x04,
-    0x04, 0x0f, 0x02, 0x02, 0x12, 0x04, 0xb3, 0x01, 0x02, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f,
-    0x02, 0x02, 0x06, 0x12, 0x04, 0xb3, 0x01, 0x02, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02,
-    0x02, 0x01, 0x12, 0x04, 0xb3, 0x01, 0x10, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x02,
-    0x03, 0x12, 0x04, 0xb3, 0x01, 0x17, 0x18, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0f, 0x02, 0x03, 0x12,
-    0x04, 0xb4, 0x01, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x03, 0x05, 0x12, 0x04,
-    0xb4, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x03, 0x01, 0x12, 0x04, 0xb4,
-    0x01, 0x09, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x03, 0x03, 0x12, 0x04, 0xb4, 0x01,
-    0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x10, 0x12, 0x06, 0xb7, 0x01, 0x00, 0xbc, 0x01, 0x01,
-    0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x10, 0x01, 0x12, 0x04, 0xb7, 0x01, 0x08, 0x17, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x10, 0x02, 0x00, 0x12, 0x04, 0xb8, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x10, 0x02, 0x00, 0x05, 0x12, 0x04, 0xb8, 0x01, 0x02, 0x07, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10,
-    0x02, 0x00, 0x01, 0x12, 0x04, 0xb8, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02,
-    0x00, 0x03, 0x12, 0x04, 0xb8, 0x01, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x10, 0x02, 0x01,
-    0x12, 0x04, 0xb9, 0x01, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x01, 0x05, 0x12,
-    0x04, 0xb9, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x01, 0x01, 0x12, 0x04,
-    0xb9, 0x01, 0x09, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x01, 0x03, 0x12, 0x04, 0xb9,
-    0x01, 0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x10, 0x02, 0x02, 0x12, 0x04, 0xba, 0x01, 0x02,
-    0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x02, 0x05, 0x12, 0x04, 0xba, 0x01, 0x02, 0x08,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x02, 0x01, 0x12, 0x04, 0xba, 0x01, 0x09, 0x0c, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x02, 0x03, 0x12, 0x04, 0xba, 0x01, 0x0f, 0x10, 0x0a, 0x0c,
-    0x0a, 0x04, 0x04, 0x10, 0x02, 0x03, 0x12, 0x04, 0xbb, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x10, 0x02, 0x03, 0x06, 0x12, 0x04, 0xbb, 0x01, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x10, 0x02, 0x03, 0x01, 0x12, 0x04, 0xbb, 0x01, 0x12, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10,
-    0x02, 0x03, 0x03, 0x12, 0x04, 0xbb, 0x01, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x11, 0x12,
-    0x06, 0xbe, 0x01, 0x00, 0xc1, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x11, 0x01, 0x12, 0x04,
-    0xbe, 0x01, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x11, 0x02, 0x00, 0x12, 0x04, 0xbf, 0x01,
-    0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x00, 0x05, 0x12, 0x04, 0xbf, 0x01, 0x02,
-    0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x00, 0x01, 0x12, 0x04, 0xbf, 0x01, 0x09, 0x0c,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x00, 0x03, 0x12, 0x04, 0xbf, 0x01, 0x0f, 0x10, 0x0a,
-    0x0c, 0x0a, 0x04, 0x04, 0x11, 0x02, 0x01, 0x12, 0x04, 0xc0, 0x01, 0x02, 0x16, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x11, 0x02, 0x01, 0x05, 0x12, 0x04, 0xc0, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x11, 0x02, 0x01, 0x01, 0x12, 0x04, 0xc0, 0x01, 0x09, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x11, 0x02, 0x01, 0x03, 0x12, 0x04, 0xc0, 0x01, 0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x12,
-    0x12, 0x06, 0xc3, 0x01, 0x00, 0xc7, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x12, 0x01, 0x12,
-    0x04, 0xc3, 0x01, 0x08, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x12, 0x02, 0x00, 0x12, 0x04, 0xc4,
-    0x01, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x00, 0x05, 0x12, 0x04, 0xc4, 0x01,
-    0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x00, 0x01, 0x12, 0x04, 0xc4, 0x01, 0x09,
-    0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x00, 0x03, 0x12, 0x04, 0xc4, 0x01, 0x13, 0x14,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x12, 0x02, 0x01, 0x12, 0x04, 0xc5, 0x01, 0x02, 0x1b, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x12, 0x02, 0x01, 0x05, 0x12, 0x04, 0xc5, 0x01, 0x02, 0x07, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x12, 0x02, 0x01, 0x01, 0x12, 0x04, 0xc5, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x12, 0x02, 0x01, 0x03, 0x12, 0x04, 0xc5, 0x01, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x12, 0x02, 0x02, 0x12, 0x04, 0xc6, 0x01, 0x02, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02,
-    0x02, 0x06, 0x12, 0x04, 0xc6, 0x01, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x02,
-    0x01, 0x12, 0x04, 0xc6, 0x01, 0x15, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x02, 0x03,
-    0x12, 0x04, 0xc6, 0x01, 0x1c, 0x1d, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x13, 0x12, 0x06, 0xc9, 0x01,
-    0x00, 0xcf, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x13, 0x01, 0x12, 0x04, 0xc9, 0x01, 0x08,
-    0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x13, 0x02, 0x00, 0x12, 0x04, 0xca, 0x01, 0x02, 0x15, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x00, 0x05, 0x12, 0x04, 0xca, 0x01, 0x02, 0x08, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x13, 0x02, 0x00, 0x01, 0x12, 0x04, 0xca, 0x01, 0x09, 0x10, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x13, 0x02, 0x00, 0x03, 0x12, 0x04, 0xca, 0x01, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04,
-    0x04, 0x13, 0x02, 0x01, 0x12, 0x04, 0xcb, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13,
-    0x02, 0x01, 0x05, 0x12, 0x04, 0xcb, 0x01, 0x02, 0x07, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02,
-    0x01, 0x01, 0x12, 0x04, 0xcb, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x01,
-    0x03, 0x12, 0x04, 0xcb, 0x01, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x13, 0x02, 0x02, 0x12,
-    0x04, 0xcc, 0x01, 0x02, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x02, 0x06, 0x12, 0x04,
-    0xcc, 0x01, 0x02, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x02, 0x01, 0x12, 0x04, 0xcc,
-    0x01, 0x10, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x02, 0x03, 0x12, 0x04, 0xcc, 0x01,
-    0x17, 0x18, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x13, 0x02, 0x03, 0x12, 0x04, 0xcd, 0x01, 0x02, 0x16,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x03, 0x05, 0x12, 0x04, 0xcd, 0x01, 0x02, 0x08, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x03, 0x01, 0x12, 0x04, 0xcd, 0x01, 0x09, 0x11, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x13, 0x02, 0x03, 0x03, 0x12, 0x04, 0xcd, 0x01, 0x14, 0x15, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x13, 0x02, 0x04, 0x12, 0x04, 0xce, 0x01, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x13, 0x02, 0x04, 0x05, 0x12, 0x04, 0xce, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13,
-    0x02, 0x04, 0x01, 0x12, 0x04, 0xce, 0x01, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02,
-    0x04, 0x03, 0x12, 0x04, 0xce, 0x01, 0x10, 0x11, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x14, 0x12, 0x06,
-    0xd1, 0x01, 0x00, 0xd6, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x14, 0x01, 0x12, 0x04, 0xd1,
-    0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x14, 0x02, 0x00, 0x12, 0x04, 0xd2, 0x01, 0x02,
-    0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x00, 0x05, 0x12, 0x04, 0xd2, 0x01, 0x02, 0x08,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x00, 0x01, 0x12, 0x04, 0xd2, 0x01, 0x09, 0x0c, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x00, 0x03, 0x12, 0x04, 0xd2, 0x01, 0x0f, 0x10, 0x0a, 0x0c,
-    0x0a, 0x04, 0x04, 0x14, 0x02, 0x01, 0x12, 0x04, 0xd3, 0x01, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x14, 0x02, 0x01, 0x05, 0x12, 0x04, 0xd3, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x14, 0x02, 0x01, 0x01, 0x12, 0x04, 0xd3, 0x01, 0x09, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14,
-    0x02, 0x01, 0x03, 0x12, 0x04, 0xd3, 0x01, 0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x14, 0x02,
-    0x02, 0x12, 0x04, 0xd4, 0x01, 0x02, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x02, 0x05,
-    0x12, 0x04, 0xd4, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x02, 0x01, 0x12,
-    0x04, 0xd4, 0x01, 0x09, 0x0e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x02, 0x03, 0x12, 0x04,
-    0xd4, 0x01, 0x11, 0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x14, 0x02, 0x03, 0x12, 0x04, 0xd5, 0x01,
-    0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x03, 0x05, 0x12, 0x04, 0xd5, 0x01, 0x02,
-    0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x03, 0x01, 0x12, 0x04, 0xd5, 0x01, 0x09, 0x13,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x03, 0x03, 0x12, 0x04, 0xd5, 0x01, 0x16, 0x17, 0x0a,
-    0x0c, 0x0a, 0x02, 0x04, 0x15, 0x12, 0x06, 0xd8, 0x01, 0x00, 0xdd, 0x01, 0x01, 0x0a, 0x0b, 0x0a,
-    0x03, 0x04, 0x15, 0x01, 0x12, 0x04, 0xd8, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x15,
-    0x02, 0x00, 0x12, 0x04, 0xd9, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x00,
-    0x05, 0x12, 0x04, 0xd9, 0x01, 0x02, 0x07, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x00, 0x01,
-    0x12, 0x04, 0xd9, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x00, 0x03, 0x12,
-    0x04, 0xd9, 0x01, 0x19, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x15, 0x02, 0x01, 0x12, 0x04, 0xda,
-    0x01, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x01, 0x05, 0x12, 0x04, 0xda, 0x01,
-    0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x01, 0x01, 0x12, 0x04, 0xda, 0x01, 0x09,
-    0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x01, 0x03, 0x12, 0x04, 0xda, 0x01, 0x12, 0x13,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x15, 0x02, 0x02, 0x12, 0x04, 0xdb, 0x01, 0x02, 0x11, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x15, 0x02, 0x02, 0x05, 0x12, 0x04, 0xdb, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x15, 0x02, 0x02, 0x01, 0x12, 0x04, 0xdb, 0x01, 0x09, 0x0c, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x15, 0x02, 0x02, 0x03, 0x12, 0x04, 0xdb, 0x01, 0x0f, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x15, 0x02, 0x03, 0x12, 0x04, 0xdc, 0x01, 0x02, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02,
-    0x03, 0x06, 0x12, 0x04, 0xdc, 0x01, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x03,
-    0x01, 0x12, 0x04, 0xdc, 0x01, 0x11, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x03, 0x03,
-    0x12, 0x04, 0xdc, 0x01, 0x18, 0x19, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x16, 0x12, 0x06, 0xdf, 0x01,
-    0x00, 0xf2, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x16, 0x01, 0x12, 0x04, 0xdf, 0x01, 0x08,
-    0x1a, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x16, 0x04, 0x00, 0x12, 0x06, 0xe0, 0x01, 0x02, 0xe7, 0x01,
-    0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x04, 0x00, 0x01, 0x12, 0x04, 0xe0, 0x01, 0x07, 0x0b,
-    0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x00, 0x12, 0x04, 0xe1, 0x01, 0x04, 0x19,
-    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xe1, 0x01, 0x04,
-    0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0xe1, 0x01,
-    0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0xe2, 0x01,
-    0x04, 0x24, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xe2,
-    0x01, 0x04, 0x1f, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04,
-    0xe2, 0x01, 0x22, 0x23, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04,
-    0xe3, 0x01, 0x04, 0x1c, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12,
-    0x04, 0xe3, 0x01, 0x04, 0x17, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x02, 0x02,
-    0x12, 0x04, 0xe3, 0x01, 0x1a, 0x1b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x03,
-    0x12, 0x04, 0xe4, 0x01, 0x04, 0x23, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x03,
-    0x01, 0x12, 0x04, 0xe4, 0x01, 0x04, 0x1e, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02,
-    0x03, 0x02, 0x12, 0x04, 0xe4, 0x01, 0x21, 0x22, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00,
-    0x02, 0x04, 0x12, 0x04, 0xe5, 0x01, 0x04, 0x1f, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00,
-    0x02, 0x04, 0x01, 0x12, 0x04, 0xe5, 0x01, 0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04,
-    0x00, 0x02, 0x04, 0x02, 0x12, 0x04, 0xe5, 0x01, 0x1d, 0x1e, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16,
-    0x04, 0x00, 0x02, 0x05, 0x12, 0x04, 0xe6, 0x01, 0x04, 0x1e, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16,
-    0x04, 0x00, 0x02, 0x05, 0x01, 0x12, 0x04, 0xe6, 0x01, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
-    0x16, 0x04, 0x00, 0x02, 0x05, 0x02, 0x12, 0x04, 0xe6, 0x01, 0x1c, 0x1d, 0x0a, 0x0c, 0x0a, 0x04,
-    0x04, 0x16, 0x02, 0x00, 0x12, 0x04, 0xe9, 0x01, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16,
-    0x02, 0x00, 0x06, 0x12, 0x04, 0xe9, 0x01, 0x02, 0x06, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02,
-    0x00, 0x01, 0x12, 0x04, 0xe9, 0x01, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x00,
-    0x03, 0x12, 0x04, 0xe9, 0x01, 0x0e, 0x0f, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x16, 0x08, 0x00, 0x12,
-    0x06, 0xeb, 0x01, 0x02, 0xf1, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x08, 0x00, 0x01,
-    0x12, 0x04, 0xeb, 0x01, 0x08, 0x0f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x01, 0x12, 0x04,
-    0xec, 0x01, 0x04, 0x34, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x01, 0x06, 0x12, 0x04, 0xec,
-    0x01, 0x04, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x01, 0x01, 0x12, 0x04, 0xec, 0x01,
-    0x19, 0x2f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x01, 0x03, 0x12, 0x04, 0xec, 0x01, 0x32,
-    0x33, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x02, 0x12, 0x04, 0xed, 0x01, 0x04, 0x25, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x02, 0x06, 0x12, 0x04, 0xed, 0x01, 0x04, 0x11, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x16, 0x02, 0x02, 0x01, 0x12, 0x04, 0xed, 0x01, 0x12, 0x20, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x16, 0x02, 0x02, 0x03, 0x12, 0x04, 0xed, 0x01, 0x23, 0x24, 0x0a, 0x0c, 0x0a, 0x04,
-    0x04, 0x16, 0x02, 0x03, 0x12, 0x04, 0xee, 0x01, 0x04, 0x32, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16,
-    0x02, 0x03, 0x06, 0x12, 0x04, 0xee, 0x01, 0x04, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02,
-    0x03, 0x01, 0x12, 0x04, 0xee, 0x01, 0x18, 0x2d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x03,
-    0x03, 0x12, 0x04, 0xee, 0x01, 0x30, 0x31, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x04, 0x12,
-    0x04, 0xef, 0x01, 0x04, 0x2a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x04, 0x06, 0x12, 0x04,
-    0xef, 0x01, 0x04, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x04, 0x01, 0x12, 0x04, 0xef,
-    0x01, 0x14, 0x25, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x04, 0x03, 0x12, 0x04, 0xef, 0x01,
-    0x28, 0x29, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x05, 0x12, 0x04, 0xf0, 0x01, 0x04, 0x29,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x05, 0x06, 0x12, 0x04, 0xf0, 0x01, 0x04, 0x13, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x05, 0x01, 0x12, 0x04, 0xf0, 0x01, 0x14, 0x24, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x16, 0x02, 0x05, 0x03, 0x12, 0x04, 0xf0, 0x01, 0x27, 0x28, 0x0a, 0x0c, 0x0a,
-    0x02, 0x04, 0x17, 0x12, 0x06, 0xf4, 0x01, 0x00, 0xf9, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
-    0x17, 0x01, 0x12, 0x04, 0xf4, 0x01, 0x08, 0x1c, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x17, 0x02, 0x00,
-    0x12, 0x04, 0xf5, 0x01, 0x02, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x00, 0x06, 0x12,
-    0x04, 0xf5, 0x01, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x00, 0x01, 0x12, 0x04,
-    0xf5, 0x01, 0x12, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x00, 0x03, 0x12, 0x04, 0xf5,
-    0x01, 0x1d, 0x1e, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x17, 0x02, 0x01, 0x12, 0x04, 0xf6, 0x01, 0x02,
-    0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x01, 0x04, 0x12, 0x04, 0xf6, 0x01, 0x02, 0x0a,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x01, 0x06, 0x12, 0x04, 0xf6, 0x01, 0x0b, 0x13, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x01, 0x01, 0x12, 0x04, 0xf6, 0x01, 0x14, 0x22, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x17, 0x02, 0x01, 0x03, 0x12, 0x04, 0xf6, 0x01, 0x25, 0x26, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x17, 0x02, 0x02, 0x12, 0x04, 0xf7, 0x01, 0x02, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x17, 0x02, 0x02, 0x04, 0x12, 0x04, 0xf7, 0x01, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17,
-    0x02, 0x02, 0x05, 0x12, 0x04, 0xf7, 0x01, 0x0b, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02,
-    0x02, 0x01, 0x12, 0x04, 0xf7, 0x01, 0x12, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x02,
-    0x03, 0x12, 0x04, 0xf7, 0x01, 0x1e, 0x1f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x17, 0x02, 0x03, 0x12,
-    0x04, 0xf8, 0x01, 0x02, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x03, 0x05, 0x12, 0x04,
-    0xf8, 0x01, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x03, 0x01, 0x12, 0x04, 0xf8,
-    0x01, 0x09, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x03, 0x03, 0x12, 0x04, 0xf8, 0x01,
-    0x21, 0x22, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x18, 0x12, 0x06, 0xfb, 0x01, 0x00, 0xfe, 0x01, 0x01,
-    0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x18, 0x01, 0x12, 0x04, 0xfb, 0x01, 0x08, 0x1a, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x18, 0x02, 0x00, 0x12, 0x04, 0xfc, 0x01, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x18, 0x02, 0x00, 0x05, 0x12, 0x04, 0xfc, 0x01, 0x02, 0x07, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18,
-    0x02, 0x00, 0x01, 0x12, 0x04, 0xfc, 0x01, 0x08, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18, 0x02,
-    0x00, 0x03, 0x12, 0x04, 0xfc, 0x01, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x18, 0x02, 0x01,
-    0x12, 0x04, 0xfd, 0x01, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18, 0x02, 0x01, 0x06, 0x12,
-    0x04, 0xfd, 0x01, 0x02, 0x0e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18, 0x02, 0x01, 0x01, 0x12, 0x04,
-    0xfd, 0x01, 0x0f, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18, 0x02, 0x01, 0x03, 0x12, 0x04, 0xfd,
-    0x01, 0x15, 0x16, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x19, 0x12, 0x06, 0x80, 0x02, 0x00, 0x84, 0x02,
-    0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x19, 0x01, 0x12, 0x04, 0x80, 0x02, 0x08, 0x15, 0x0a, 0x0c,
-    0x0a, 0x04, 0x04, 0x19, 0x02, 0x00, 0x12, 0x04, 0x81, 0x02, 0x02, 0x1e, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x19, 0x02, 0x00, 0x06, 0x12, 0x04, 0x81, 0x02, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x19, 0x02, 0x00, 0x01, 0x12, 0x04, 0x81, 0x02, 0x15, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19,
-    0x02, 0x00, 0x03, 0x12, 0x04, 0x81, 0x02, 0x1c, 0x1d, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x19, 0x02,
-    0x01, 0x12, 0x04, 0x82, 0x02, 0x02, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x01, 0x04,
-    0x12, 0x04, 0x82, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x01, 0x06, 0x12,
-    0x04, 0x82, 0x02, 0x0b, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x01, 0x01, 0x12, 0x04,
-    0x82, 0x02, 0x14, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x01, 0x03, 0x12, 0x04, 0x82,
-    0x02, 0x25, 0x26, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x19, 0x02, 0x02, 0x12, 0x04, 0x83, 0x02, 0x02,
-    0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x02, 0x04, 0x12, 0x04, 0x83, 0x02, 0x02, 0x0a,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x02, 0x05, 0x12, 0x04, 0x83, 0x02, 0x0b, 0x11, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x02, 0x01, 0x12, 0x04, 0x83, 0x02, 0x12, 0x1b, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x19, 0x02, 0x02, 0x03, 0x12, 0x04, 0x83, 0x02, 0x1e, 0x1f, 0x0a, 0x0c, 0x0a,
-    0x02, 0x04, 0x1a, 0x12, 0x06, 0x86, 0x02, 0x00, 0x89, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
-    0x1a, 0x01, 0x12, 0x04, 0x86, 0x02, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1a, 0x02, 0x00,
-    0x12, 0x04, 0x87, 0x02, 0x02, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x00, 0x05, 0x12,
-    0x04, 0x87, 0x02, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x00, 0x01, 0x12, 0x04,
-    0x87, 0x02, 0x09, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x00, 0x03, 0x12, 0x04, 0x87,
-    0x02, 0x1c, 0x1d, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1a, 0x02, 0x01, 0x12, 0x04, 0x88, 0x02, 0x02,
-    0x3e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01, 0x04, 0x12, 0x04, 0x88, 0x02, 0x02, 0x0a,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01, 0x06, 0x12, 0x04, 0x88, 0x02, 0x0b, 0x25, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01, 0x01, 0x12, 0x04, 0x88, 0x02, 0x26, 0x39, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01, 0x03, 0x12, 0x04, 0x88, 0x02, 0x3c, 0x3d, 0x0a, 0x0c, 0x0a,
-    0x02, 0x04, 0x1b, 0x12, 0x06, 0x8b, 0x02, 0x00, 0x96, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
-    0x1b, 0x01, 0x12, 0x04, 0x8b, 0x02, 0x08, 0x22, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x1b, 0x04, 0x00,
-    0x12, 0x06, 0x8c, 0x02, 0x02, 0x8f, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x04, 0x00,
-    0x01, 0x12, 0x04, 0x8c, 0x02, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1b, 0x04, 0x00, 0x02,
-    0x00, 0x12, 0x04, 0x8d, 0x02, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b, 0x04, 0x00, 0x02,
-    0x00, 0x01, 0x12, 0x04, 0x8d, 0x02, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b, 0x04, 0x00,
-    0x02, 0x00, 0x02, 0x12, 0x04, 0x8d, 0x02, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1b, 0x04,
-    0x00, 0x02, 0x01, 0x12, 0x04, 0x8e, 0x02, 0x04, 0x24, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b, 0x04,
-    0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0x8e, 0x02, 0x04, 0x1f, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b,
-    0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0x8e, 0x02, 0x22, 0x23, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x1b, 0x02, 0x00, 0x12, 0x04, 0x91, 0x02, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02,
-    0x00, 0x06, 0x12, 0x04, 0x91, 0x02, 0x02, 0x06, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x00,
-    0x01, 0x12, 0x04, 0x91, 0x02, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x00, 0x03,
-    0x12, 0x04, 0x91, 0x02, 0x0e, 0x0f, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x1b, 0x08, 0x00, 0x12, 0x06,
-    0x93, 0x02, 0x02, 0x95, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x08, 0x00, 0x01, 0x12,
-    0x04, 0x93, 0x02, 0x08, 0x0f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1b, 0x02, 0x01, 0x12, 0x04, 0x94,
-    0x02, 0x04, 0x34, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x01, 0x06, 0x12, 0x04, 0x94, 0x02,
-    0x04, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x01, 0x01, 0x12, 0x04, 0x94, 0x02, 0x19,
-    0x2f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x01, 0x03, 0x12, 0x04, 0x94, 0x02, 0x32, 0x33,
-    0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x1c, 0x12, 0x06, 0x98, 0x02, 0x00, 0x9a, 0x02, 0x01, 0x0a, 0x0b,
-    0x0a, 0x03, 0x04, 0x1c, 0x01, 0x12, 0x04, 0x98, 0x02, 0x08, 0x1b, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x1c, 0x02, 0x00, 0x12, 0x04, 0x99, 0x02, 0x02, 0x2a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1c, 0x02,
-    0x00, 0x04, 0x12, 0x04, 0x99, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1c, 0x02, 0x00,
-    0x06, 0x12, 0x04, 0x99, 0x02, 0x0b, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1c, 0x02, 0x00, 0x01,
-    0x12, 0x04, 0x99, 0x02, 0x1e, 0x25, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1c, 0x02, 0x00, 0x03, 0x12,
-    0x04, 0x99, 0x02, 0x28, 0x29, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x1d, 0x12, 0x06, 0x9c, 0x02, 0x00,
-    0x9f, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1d, 0x01, 0x12, 0x04, 0x9c, 0x02, 0x08, 0x1a,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1d, 0x02, 0x00, 0x12, 0x04, 0x9d, 0x02, 0x02, 0x15, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x1d, 0x02, 0x00, 0x05, 0x12, 0x04, 0x9d, 0x02, 0x02, 0x07, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x1d, 0x02, 0x00, 0x01, 0x12, 0x04, 0x9d, 0x02, 0x08, 0x10, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x1d, 0x02, 0x00, 0x03, 0x12, 0x04, 0x9d, 0x02, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x1d, 0x02, 0x01, 0x12, 0x04, 0x9e, 0x02, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02,
-    0x01, 0x06, 0x12, 0x04, 0x9e, 0x02, 0x02, 0x0c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02, 0x01,
-    0x01, 0x12, 0x04, 0x9e, 0x02, 0x0d, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02, 0x01, 0x03,
-    0x12, 0x04, 0x9e, 0x02, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x1e, 0x12, 0x06, 0xa1, 0x02,
-    0x00, 0xa7, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1e, 0x01, 0x12, 0x04, 0xa1, 0x02, 0x08,
-    0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1e, 0x02, 0x00, 0x12, 0x04, 0xa2, 0x02, 0x02, 0x15, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x00, 0x05, 0x12, 0x04, 0xa2, 0x02, 0x02, 0x08, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x1e, 0x02, 0x00, 0x01, 0x12, 0x04, 0xa2, 0x02, 0x09, 0x10, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x1e, 0x02, 0x00, 0x03, 0x12, 0x04, 0xa2, 0x02, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04,
-    0x04, 0x1e, 0x02, 0x01, 0x12, 0x04, 0xa3, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e,
-    0x02, 0x01, 0x05, 0x12, 0x04, 0xa3, 0x02, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02,
-    0x01, 0x01, 0x12, 0x04, 0xa3, 0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x01,
-    0x03, 0x12, 0x04, 0xa3, 0x02, 0x10, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1e, 0x02, 0x02, 0x12,
-    0x04, 0xa4, 0x02, 0x02, 0x24, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x02, 0x04, 0x12, 0x04,
-    0xa4, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x02, 0x06, 0x12, 0x04, 0xa4,
-    0x02, 0x0b, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x02, 0x01, 0x12, 0x04, 0xa4, 0x02,
-    0x18, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x02, 0x03, 0x12, 0x04, 0xa4, 0x02, 0x22,
-    0x23, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1e, 0x02, 0x03, 0x12, 0x04, 0xa5, 0x02, 0x02, 0x2e, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x03, 0x04, 0x12, 0x04, 0xa5, 0x02, 0x02, 0x0a, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x1e, 0x02, 0x03, 0x06, 0x12, 0x04, 0xa5, 0x02, 0x0b, 0x17, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x1e, 0x02, 0x03, 0x01, 0x12, 0x04, 0xa5, 0x02, 0x18, 0x29, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x1e, 0x02, 0x03, 0x03, 0x12, 0x04, 0xa5, 0x02, 0x2c, 0x2d, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x1e, 0x02, 0x04, 0x12, 0x04, 0xa6, 0x02, 0x02, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02,
-    0x04, 0x04, 0x12, 0x04, 0xa6, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x04,
-    0x06, 0x12, 0x04, 0xa6, 0x02, 0x0b, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x04, 0x01,
-    0x12, 0x04, 0xa6, 0x02, 0x16, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x04, 0x03, 0x12,
-    0x04, 0xa6, 0x02, 0x20, 0x21, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x1f, 0x12, 0x06, 0xa8, 0x02, 0x00,
-    0xb5, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1f, 0x01, 0x12, 0x04, 0xa8, 0x02, 0x08, 0x14,
-    0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x1f, 0x04, 0x00, 0x12, 0x06, 0xa9, 0x02, 0x02, 0xae, 0x02, 0x03,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x04, 0x00, 0x01, 0x12, 0x04, 0xa9, 0x02, 0x07, 0x11, 0x0a,
-    0x0e, 0x0a, 0x06, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x00, 0x12, 0x04, 0xaa, 0x02, 0x04, 0x1f, 0x0a,
-    0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xaa, 0x02, 0x04, 0x1a,
-    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0xaa, 0x02, 0x1d,
-    0x1e, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0xab, 0x02, 0x04,
-    0x1b, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xab, 0x02,
-    0x04, 0x16, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0xab,
-    0x02, 0x19, 0x1a, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0xac,
-    0x02, 0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04,
-    0xac, 0x02, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12,
-    0x04, 0xac, 0x02, 0x18, 0x19, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x03, 0x12,
-    0x04, 0xad, 0x02, 0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x03, 0x01,
-    0x12, 0x04, 0xad, 0x02, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x03,
-    0x02, 0x12, 0x04, 0xad, 0x02, 0x18, 0x19, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x00, 0x12,
-    0x04, 0xaf, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x00, 0x05, 0x12, 0x04,
-    0xaf, 0x02, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x00, 0x01, 0x12, 0x04, 0xaf,
-    0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x00, 0x03, 0x12, 0x04, 0xaf, 0x02,
-    0x10, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x01, 0x12, 0x04, 0xb0, 0x02, 0x02, 0x29,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x01, 0x06, 0x12, 0x04, 0xb0, 0x02, 0x02, 0x19, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x01, 0x01, 0x12, 0x04, 0xb0, 0x02, 0x1a, 0x24, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x1f, 0x02, 0x01, 0x03, 0x12, 0x04, 0xb0, 0x02, 0x27, 0x28, 0x0a, 0x0c, 0x0a,
+    0x04, 0x0a, 0x02, 0x02, 0x03, 0x12, 0x04, 0x84, 0x01, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0a, 0x02, 0x02, 0x06, 0x12, 0x04, 0x84, 0x01, 0x04, 0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0a,
+    0x04, 0x00, 0x12, 0x04, 0x7b, 0x02, 0x7f, 0x03, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x0a, 0x04, 0x00,
+    0x01, 0x12, 0x03, 0x7b, 0x07, 0x13, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x00,
+    0x12, 0x03, 0x7c, 0x04, 0x23, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x00, 0x01,
+    0x12, 0x03, 0x7c, 0x04, 0x1e, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x00, 0x02,
+    0x12, 0x03, 0x7c, 0x21, 0x22, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x01, 0x12,
+    0x03, 0x7d, 0x04, 0x28, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12,
+    0x03, 0x7d, 0x04, 0x23, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12,
+    0x03, 0x7d, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x02, 0x12, 0x03,
+    0x7e, 0x04, 0x28, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03,
+    0x7e, 0x04, 0x23, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x0a, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x03,
+    0x7e, 0x26, 0x27, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x0a, 0x08, 0x00, 0x12, 0x06, 0x82, 0x01, 0x02,
+    0x85, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0a, 0x08, 0x00, 0x01, 0x12, 0x04, 0x82, 0x01,
+    0x08, 0x11, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x0b, 0x12, 0x06, 0x88, 0x01, 0x00, 0x8b, 0x01, 0x01,
+    0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0b, 0x01, 0x12, 0x04, 0x88, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a,
+    0x04, 0x04, 0x0b, 0x02, 0x00, 0x12, 0x04, 0x89, 0x01, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0b, 0x02, 0x00, 0x01, 0x12, 0x04, 0x89, 0x01, 0x09, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b,
+    0x02, 0x00, 0x03, 0x12, 0x04, 0x89, 0x01, 0x16, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02,
+    0x00, 0x05, 0x12, 0x04, 0x89, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0b, 0x02, 0x01,
+    0x12, 0x04, 0x8a, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x01, 0x01, 0x12,
+    0x04, 0x8a, 0x01, 0x10, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x01, 0x03, 0x12, 0x04,
+    0x8a, 0x01, 0x19, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0b, 0x02, 0x01, 0x06, 0x12, 0x04, 0x8a,
+    0x01, 0x02, 0x0f, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x0c, 0x12, 0x06, 0x8d, 0x01, 0x00, 0x90, 0x01,
+    0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0c, 0x01, 0x12, 0x04, 0x8d, 0x01, 0x08, 0x16, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x0c, 0x02, 0x00, 0x12, 0x04, 0x8e, 0x01, 0x02, 0x2f, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x0c, 0x02, 0x00, 0x01, 0x12, 0x04, 0x8e, 0x01, 0x1a, 0x2a, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0c, 0x02, 0x00, 0x03, 0x12, 0x04, 0x8e, 0x01, 0x2d, 0x2e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c,
+    0x02, 0x00, 0x04, 0x12, 0x04, 0x8e, 0x01, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02,
+    0x00, 0x06, 0x12, 0x04, 0x8e, 0x01, 0x0b, 0x19, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0c, 0x02, 0x01,
+    0x12, 0x04, 0x8f, 0x01, 0x02, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x01, 0x01, 0x12,
+    0x04, 0x8f, 0x01, 0x11, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x01, 0x03, 0x12, 0x04,
+    0x8f, 0x01, 0x1a, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x01, 0x04, 0x12, 0x04, 0x8f,
+    0x01, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0c, 0x02, 0x01, 0x06, 0x12, 0x04, 0x8f, 0x01,
+    0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x0d, 0x12, 0x06, 0x92, 0x01, 0x00, 0xa8, 0x01, 0x01,
+    0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0d, 0x01, 0x12, 0x04, 0x92, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a,
+    0x04, 0x04, 0x0d, 0x02, 0x00, 0x12, 0x04, 0x9e, 0x01, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0d, 0x02, 0x00, 0x01, 0x12, 0x04, 0x9e, 0x01, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d,
+    0x02, 0x00, 0x03, 0x12, 0x04, 0x9e, 0x01, 0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02,
+    0x00, 0x06, 0x12, 0x04, 0x9e, 0x01, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x01,
+    0x12, 0x04, 0xa1, 0x01, 0x04, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x01, 0x01, 0x12,
+    0x04, 0xa1, 0x01, 0x11, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x01, 0x03, 0x12, 0x04,
+    0xa1, 0x01, 0x21, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x01, 0x06, 0x12, 0x04, 0xa1,
+    0x01, 0x04, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x02, 0x12, 0x04, 0xa2, 0x01, 0x04,
+    0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x02, 0x01, 0x12, 0x04, 0xa2, 0x01, 0x13, 0x22,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x02, 0x03, 0x12, 0x04, 0xa2, 0x01, 0x25, 0x26, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x02, 0x06, 0x12, 0x04, 0xa2, 0x01, 0x04, 0x12, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x0d, 0x02, 0x03, 0x12, 0x04, 0xa3, 0x01, 0x04, 0x2a, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x0d, 0x02, 0x03, 0x01, 0x12, 0x04, 0xa3, 0x01, 0x14, 0x25, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0d, 0x02, 0x03, 0x03, 0x12, 0x04, 0xa3, 0x01, 0x28, 0x29, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d,
+    0x02, 0x03, 0x06, 0x12, 0x04, 0xa3, 0x01, 0x04, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02,
+    0x04, 0x12, 0x04, 0xa4, 0x01, 0x04, 0x21, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x04, 0x01,
+    0x12, 0x04, 0xa4, 0x01, 0x10, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x04, 0x03, 0x12,
+    0x04, 0xa4, 0x01, 0x1f, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x04, 0x06, 0x12, 0x04,
+    0xa4, 0x01, 0x04, 0x0f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x05, 0x12, 0x04, 0xa5, 0x01,
+    0x04, 0x25, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x05, 0x01, 0x12, 0x04, 0xa5, 0x01, 0x12,
+    0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x05, 0x03, 0x12, 0x04, 0xa5, 0x01, 0x23, 0x24,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x02, 0x05, 0x06, 0x12, 0x04, 0xa5, 0x01, 0x04, 0x11, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x0d, 0x02, 0x06, 0x12, 0x04, 0xa6, 0x01, 0x04, 0x28, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x0d, 0x02, 0x06, 0x01, 0x12, 0x04, 0xa6, 0x01, 0x13, 0x23, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x0d, 0x02, 0x06, 0x03, 0x12, 0x04, 0xa6, 0x01, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0d, 0x02, 0x06, 0x06, 0x12, 0x04, 0xa6, 0x01, 0x04, 0x12, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x0d,
+    0x04, 0x00, 0x12, 0x06, 0x94, 0x01, 0x02, 0x9c, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d,
+    0x04, 0x00, 0x01, 0x12, 0x04, 0x94, 0x01, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04,
+    0x00, 0x02, 0x00, 0x12, 0x04, 0x95, 0x01, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04,
+    0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0x95, 0x01, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d,
+    0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0x95, 0x01, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04,
+    0x0d, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0x96, 0x01, 0x04, 0x1b, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
+    0x0d, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0x96, 0x01, 0x04, 0x16, 0x0a, 0x0f, 0x0a, 0x07,
+    0x04, 0x0d, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0x96, 0x01, 0x19, 0x1a, 0x0a, 0x0e, 0x0a,
+    0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0x97, 0x01, 0x04, 0x1d, 0x0a, 0x0f, 0x0a,
+    0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0x97, 0x01, 0x04, 0x18, 0x0a, 0x0f,
+    0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0x97, 0x01, 0x1b, 0x1c, 0x0a,
+    0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x03, 0x12, 0x04, 0x98, 0x01, 0x04, 0x1f, 0x0a,
+    0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x04, 0x98, 0x01, 0x04, 0x1a,
+    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x04, 0x98, 0x01, 0x1d,
+    0x1e, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x04, 0x12, 0x04, 0x99, 0x01, 0x04,
+    0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x04, 0x99, 0x01,
+    0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12, 0x04, 0x99,
+    0x01, 0x18, 0x19, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x05, 0x12, 0x04, 0x9a,
+    0x01, 0x04, 0x1c, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x05, 0x01, 0x12, 0x04,
+    0x9a, 0x01, 0x04, 0x17, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x05, 0x02, 0x12,
+    0x04, 0x9a, 0x01, 0x1a, 0x1b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x06, 0x12,
+    0x04, 0x9b, 0x01, 0x04, 0x1e, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x06, 0x01,
+    0x12, 0x04, 0x9b, 0x01, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x0d, 0x04, 0x00, 0x02, 0x06,
+    0x02, 0x12, 0x04, 0x9b, 0x01, 0x1c, 0x1d, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x0d, 0x08, 0x00, 0x12,
+    0x06, 0xa0, 0x01, 0x02, 0xa7, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0d, 0x08, 0x00, 0x01,
+    0x12, 0x04, 0xa0, 0x01, 0x08, 0x0e, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x0e, 0x12, 0x06, 0xaa, 0x01,
+    0x00, 0xae, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0e, 0x01, 0x12, 0x04, 0xaa, 0x01, 0x08,
+    0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0e, 0x02, 0x00, 0x12, 0x04, 0xab, 0x01, 0x02, 0x15, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x00, 0x01, 0x12, 0x04, 0xab, 0x01, 0x09, 0x10, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x0e, 0x02, 0x00, 0x03, 0x12, 0x04, 0xab, 0x01, 0x13, 0x14, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x0e, 0x02, 0x00, 0x05, 0x12, 0x04, 0xab, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x0e, 0x02, 0x01, 0x12, 0x04, 0xac, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e,
+    0x02, 0x01, 0x01, 0x12, 0x04, 0xac, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02,
+    0x01, 0x03, 0x12, 0x04, 0xac, 0x01, 0x19, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x01,
+    0x05, 0x12, 0x04, 0xac, 0x01, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0e, 0x02, 0x02, 0x12,
+    0x04, 0xad, 0x01, 0x02, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x02, 0x01, 0x12, 0x04,
+    0xad, 0x01, 0x0f, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x02, 0x03, 0x12, 0x04, 0xad,
+    0x01, 0x18, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0e, 0x02, 0x02, 0x06, 0x12, 0x04, 0xad, 0x01,
+    0x02, 0x0e, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x0f, 0x12, 0x06, 0xb0, 0x01, 0x00, 0xb5, 0x01, 0x01,
+    0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x0f, 0x01, 0x12, 0x04, 0xb0, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a,
+    0x04, 0x04, 0x0f, 0x02, 0x00, 0x12, 0x04, 0xb1, 0x01, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0f, 0x02, 0x00, 0x01, 0x12, 0x04, 0xb1, 0x01, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f,
+    0x02, 0x00, 0x03, 0x12, 0x04, 0xb1, 0x01, 0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02,
+    0x00, 0x05, 0x12, 0x04, 0xb1, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0f, 0x02, 0x01,
+    0x12, 0x04, 0xb2, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x01, 0x01, 0x12,
+    0x04, 0xb2, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x01, 0x03, 0x12, 0x04,
+    0xb2, 0x01, 0x19, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x01, 0x05, 0x12, 0x04, 0xb2,
+    0x01, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x0f, 0x02, 0x02, 0x12, 0x04, 0xb3, 0x01, 0x02,
+    0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x02, 0x01, 0x12, 0x04, 0xb3, 0x01, 0x10, 0x14,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x02, 0x03, 0x12, 0x04, 0xb3, 0x01, 0x17, 0x18, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x0f, 0x02, 0x02, 0x06, 0x12, 0x04, 0xb3, 0x01, 0x02, 0x0f, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x0f, 0x02, 0x03, 0x12, 0x04, 0xb4, 0x01, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x0f, 0x02, 0x03, 0x01, 0x12, 0x04, 0xb4, 0x01, 0x09, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x0f, 0x02, 0x03, 0x03, 0x12, 0x04, 0xb4, 0x01, 0x14, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x0f,
+    0x02, 0x03, 0x05, 0x12, 0x04, 0xb4, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x10, 0x12,
+    0x06, 0xb7, 0x01, 0x00, 0xbc, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x10, 0x01, 0x12, 0x04,
+    0xb7, 0x01, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x10, 0x02, 0x00, 0x12, 0x04, 0xb8, 0x01,
+    0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x00, 0x01, 0x12, 0x04, 0xb8, 0x01, 0x08,
+    0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x00, 0x03, 0x12, 0x04, 0xb8, 0x01, 0x19, 0x1a,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x00, 0x05, 0x12, 0x04, 0xb8, 0x01, 0x02, 0x07, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x10, 0x02, 0x01, 0x12, 0x04, 0xb9, 0x01, 0x02, 0x14, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x10, 0x02, 0x01, 0x01, 0x12, 0x04, 0xb9, 0x01, 0x09, 0x0f, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x10, 0x02, 0x01, 0x03, 0x12, 0x04, 0xb9, 0x01, 0x12, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x10, 0x02, 0x01, 0x05, 0x12, 0x04, 0xb9, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x10,
+    0x02, 0x02, 0x12, 0x04, 0xba, 0x01, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x02,
+    0x01, 0x12, 0x04, 0xba, 0x01, 0x09, 0x0c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x02, 0x03,
+    0x12, 0x04, 0xba, 0x01, 0x0f, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x02, 0x05, 0x12,
+    0x04, 0xba, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x10, 0x02, 0x03, 0x12, 0x04, 0xbb,
+    0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x03, 0x01, 0x12, 0x04, 0xbb, 0x01,
+    0x12, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x03, 0x03, 0x12, 0x04, 0xbb, 0x01, 0x19,
+    0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x10, 0x02, 0x03, 0x06, 0x12, 0x04, 0xbb, 0x01, 0x02, 0x11,
+    0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x11, 0x12, 0x06, 0xbe, 0x01, 0x00, 0xc1, 0x01, 0x01, 0x0a, 0x0b,
+    0x0a, 0x03, 0x04, 0x11, 0x01, 0x12, 0x04, 0xbe, 0x01, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
+    0x11, 0x02, 0x00, 0x12, 0x04, 0xbf, 0x01, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02,
+    0x00, 0x01, 0x12, 0x04, 0xbf, 0x01, 0x09, 0x0c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x00,
+    0x03, 0x12, 0x04, 0xbf, 0x01, 0x0f, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x00, 0x05,
+    0x12, 0x04, 0xbf, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x11, 0x02, 0x01, 0x12, 0x04,
+    0xc0, 0x01, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x01, 0x01, 0x12, 0x04, 0xc0,
+    0x01, 0x09, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x01, 0x03, 0x12, 0x04, 0xc0, 0x01,
+    0x14, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x11, 0x02, 0x01, 0x05, 0x12, 0x04, 0xc0, 0x01, 0x02,
+    0x08, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x12, 0x12, 0x06, 0xc3, 0x01, 0x00, 0xc7, 0x01, 0x01, 0x0a,
+    0x0b, 0x0a, 0x03, 0x04, 0x12, 0x01, 0x12, 0x04, 0xc3, 0x01, 0x08, 0x13, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x12, 0x02, 0x00, 0x12, 0x04, 0xc4, 0x01, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12,
+    0x02, 0x00, 0x01, 0x12, 0x04, 0xc4, 0x01, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02,
+    0x00, 0x03, 0x12, 0x04, 0xc4, 0x01, 0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x00,
+    0x05, 0x12, 0x04, 0xc4, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x12, 0x02, 0x01, 0x12,
+    0x04, 0xc5, 0x01, 0x02, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x01, 0x01, 0x12, 0x04,
+    0xc5, 0x01, 0x08, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x01, 0x03, 0x12, 0x04, 0xc5,
+    0x01, 0x19, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x01, 0x05, 0x12, 0x04, 0xc5, 0x01,
+    0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x12, 0x02, 0x02, 0x12, 0x04, 0xc6, 0x01, 0x02, 0x1e,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x02, 0x01, 0x12, 0x04, 0xc6, 0x01, 0x15, 0x19, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x12, 0x02, 0x02, 0x03, 0x12, 0x04, 0xc6, 0x01, 0x1c, 0x1d, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x12, 0x02, 0x02, 0x06, 0x12, 0x04, 0xc6, 0x01, 0x02, 0x14, 0x0a, 0x0c, 0x0a,
+    0x02, 0x04, 0x13, 0x12, 0x06, 0xc9, 0x01, 0x00, 0xcf, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
+    0x13, 0x01, 0x12, 0x04, 0xc9, 0x01, 0x08, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x13, 0x02, 0x00,
+    0x12, 0x04, 0xca, 0x01, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x00, 0x01, 0x12,
+    0x04, 0xca, 0x01, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x00, 0x03, 0x12, 0x04,
+    0xca, 0x01, 0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x00, 0x05, 0x12, 0x04, 0xca,
+    0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x13, 0x02, 0x01, 0x12, 0x04, 0xcb, 0x01, 0x02,
+    0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x01, 0x01, 0x12, 0x04, 0xcb, 0x01, 0x08, 0x16,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x01, 0x03, 0x12, 0x04, 0xcb, 0x01, 0x19, 0x1a, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x01, 0x05, 0x12, 0x04, 0xcb, 0x01, 0x02, 0x07, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x13, 0x02, 0x02, 0x12, 0x04, 0xcc, 0x01, 0x02, 0x19, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x13, 0x02, 0x02, 0x01, 0x12, 0x04, 0xcc, 0x01, 0x10, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x13, 0x02, 0x02, 0x03, 0x12, 0x04, 0xcc, 0x01, 0x17, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13,
+    0x02, 0x02, 0x06, 0x12, 0x04, 0xcc, 0x01, 0x02, 0x0f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x13, 0x02,
+    0x03, 0x12, 0x04, 0xcd, 0x01, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x03, 0x01,
+    0x12, 0x04, 0xcd, 0x01, 0x09, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x03, 0x03, 0x12,
+    0x04, 0xcd, 0x01, 0x14, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x03, 0x05, 0x12, 0x04,
+    0xcd, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x13, 0x02, 0x04, 0x12, 0x04, 0xce, 0x01,
+    0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x04, 0x01, 0x12, 0x04, 0xce, 0x01, 0x09,
+    0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x04, 0x03, 0x12, 0x04, 0xce, 0x01, 0x10, 0x11,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x13, 0x02, 0x04, 0x05, 0x12, 0x04, 0xce, 0x01, 0x02, 0x08, 0x0a,
+    0x0c, 0x0a, 0x02, 0x04, 0x14, 0x12, 0x06, 0xd1, 0x01, 0x00, 0xd6, 0x01, 0x01, 0x0a, 0x0b, 0x0a,
+    0x03, 0x04, 0x14, 0x01, 0x12, 0x04, 0xd1, 0x01, 0x08, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x14,
+    0x02, 0x00, 0x12, 0x04, 0xd2, 0x01, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x00,
+    0x01, 0x12, 0x04, 0xd2, 0x01, 0x09, 0x0c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x00, 0x03,
+    0x12, 0x04, 0xd2, 0x01, 0x0f, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x00, 0x05, 0x12,
+    0x04, 0xd2, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x14, 0x02, 0x01, 0x12, 0x04, 0xd3,
+    0x01, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x01, 0x01, 0x12, 0x04, 0xd3, 0x01,
+    0x09, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x01, 0x03, 0x12, 0x04, 0xd3, 0x01, 0x14,
+    0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x01, 0x05, 0x12, 0x04, 0xd3, 0x01, 0x02, 0x08,
+    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x14, 0x02, 0x02, 0x12, 0x04, 0xd4, 0x01, 0x02, 0x13, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x14, 0x02, 0x02, 0x01, 0x12, 0x04, 0xd4, 0x01, 0x09, 0x0e, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x14, 0x02, 0x02, 0x03, 0x12, 0x04, 0xd4, 0x01, 0x11, 0x12, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x14, 0x02, 0x02, 0x05, 0x12, 0x04, 0xd4, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
+    0x14, 0x02, 0x03, 0x12, 0x04, 0xd5, 0x01, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02,
+    0x03, 0x01, 0x12, 0x04, 0xd5, 0x01, 0x09, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x03,
+    0x03, 0x12, 0x04, 0xd5, 0x01, 0x16, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x14, 0x02, 0x03, 0x05,
+    0x12, 0x04, 0xd5, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x15, 0x12, 0x06, 0xd8, 0x01,
+    0x00, 0xdd, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x15, 0x01, 0x12, 0x04, 0xd8, 0x01, 0x08,
+    0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x15, 0x02, 0x00, 0x12, 0x04, 0xd9, 0x01, 0x02, 0x1b, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x00, 0x01, 0x12, 0x04, 0xd9, 0x01, 0x08, 0x16, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x15, 0x02, 0x00, 0x03, 0x12, 0x04, 0xd9, 0x01, 0x19, 0x1a, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x15, 0x02, 0x00, 0x05, 0x12, 0x04, 0xd9, 0x01, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x15, 0x02, 0x01, 0x12, 0x04, 0xda, 0x01, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15,
+    0x02, 0x01, 0x01, 0x12, 0x04, 0xda, 0x01, 0x09, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02,
+    0x01, 0x03, 0x12, 0x04, 0xda, 0x01, 0x12, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x01,
+    0x05, 0x12, 0x04, 0xda, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x15, 0x02, 0x02, 0x12,
+    0x04, 0xdb, 0x01, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x02, 0x01, 0x12, 0x04,
+    0xdb, 0x01, 0x09, 0x0c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x02, 0x03, 0x12, 0x04, 0xdb,
+    0x01, 0x0f, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x02, 0x05, 0x12, 0x04, 0xdb, 0x01,
+    0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x15, 0x02, 0x03, 0x12, 0x04, 0xdc, 0x01, 0x02, 0x1a,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x03, 0x01, 0x12, 0x04, 0xdc, 0x01, 0x11, 0x15, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x15, 0x02, 0x03, 0x03, 0x12, 0x04, 0xdc, 0x01, 0x18, 0x19, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x15, 0x02, 0x03, 0x06, 0x12, 0x04, 0xdc, 0x01, 0x02, 0x10, 0x0a, 0x0c, 0x0a,
+    0x02, 0x04, 0x16, 0x12, 0x06, 0xdf, 0x01, 0x00, 0xf2, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
+    0x16, 0x01, 0x12, 0x04, 0xdf, 0x01, 0x08, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x00,
+    0x12, 0x04, 0xe9, 0x01, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x00, 0x01, 0x12,
+    0x04, 0xe9, 0x01, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x00, 0x03, 0x12, 0x04,
+    0xe9, 0x01, 0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x00, 0x06, 0x12, 0x04, 0xe9,
+    0x01, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x01, 0x12, 0x04, 0xec, 0x01, 0x04,
+    0x34, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x01, 0x01, 0x12, 0x04, 0xec, 0x01, 0x19, 0x2f,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x01, 0x03, 0x12, 0x04, 0xec, 0x01, 0x32, 0x33, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x01, 0x06, 0x12, 0x04, 0xec, 0x01, 0x04, 0x18, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x16, 0x02, 0x02, 0x12, 0x04, 0xed, 0x01, 0x04, 0x25, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x16, 0x02, 0x02, 0x01, 0x12, 0x04, 0xed, 0x01, 0x12, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x16, 0x02, 0x02, 0x03, 0x12, 0x04, 0xed, 0x01, 0x23, 0x24, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16,
+    0x02, 0x02, 0x06, 0x12, 0x04, 0xed, 0x01, 0x04, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02,
+    0x03, 0x12, 0x04, 0xee, 0x01, 0x04, 0x32, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x03, 0x01,
+    0x12, 0x04, 0xee, 0x01, 0x18, 0x2d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x03, 0x03, 0x12,
+    0x04, 0xee, 0x01, 0x30, 0x31, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x03, 0x06, 0x12, 0x04,
+    0xee, 0x01, 0x04, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x04, 0x12, 0x04, 0xef, 0x01,
+    0x04, 0x2a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x04, 0x01, 0x12, 0x04, 0xef, 0x01, 0x14,
+    0x25, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x04, 0x03, 0x12, 0x04, 0xef, 0x01, 0x28, 0x29,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x02, 0x04, 0x06, 0x12, 0x04, 0xef, 0x01, 0x04, 0x13, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x16, 0x02, 0x05, 0x12, 0x04, 0xf0, 0x01, 0x04, 0x29, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x16, 0x02, 0x05, 0x01, 0x12, 0x04, 0xf0, 0x01, 0x14, 0x24, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x16, 0x02, 0x05, 0x03, 0x12, 0x04, 0xf0, 0x01, 0x27, 0x28, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x16, 0x02, 0x05, 0x06, 0x12, 0x04, 0xf0, 0x01, 0x04, 0x13, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x16,
+    0x04, 0x00, 0x12, 0x06, 0xe0, 0x01, 0x02, 0xe7, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16,
+    0x04, 0x00, 0x01, 0x12, 0x04, 0xe0, 0x01, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04,
+    0x00, 0x02, 0x00, 0x12, 0x04, 0xe1, 0x01, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04,
+    0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xe1, 0x01, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16,
+    0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0xe1, 0x01, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04,
+    0x16, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0xe2, 0x01, 0x04, 0x24, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
+    0x16, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xe2, 0x01, 0x04, 0x1f, 0x0a, 0x0f, 0x0a, 0x07,
+    0x04, 0x16, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0xe2, 0x01, 0x22, 0x23, 0x0a, 0x0e, 0x0a,
+    0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0xe3, 0x01, 0x04, 0x1c, 0x0a, 0x0f, 0x0a,
+    0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0xe3, 0x01, 0x04, 0x17, 0x0a, 0x0f,
+    0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0xe3, 0x01, 0x1a, 0x1b, 0x0a,
+    0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x03, 0x12, 0x04, 0xe4, 0x01, 0x04, 0x23, 0x0a,
+    0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x04, 0xe4, 0x01, 0x04, 0x1e,
+    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x04, 0xe4, 0x01, 0x21,
+    0x22, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x04, 0x12, 0x04, 0xe5, 0x01, 0x04,
+    0x1f, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x04, 0xe5, 0x01,
+    0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12, 0x04, 0xe5,
+    0x01, 0x1d, 0x1e, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x16, 0x04, 0x00, 0x02, 0x05, 0x12, 0x04, 0xe6,
+    0x01, 0x04, 0x1e, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x05, 0x01, 0x12, 0x04,
+    0xe6, 0x01, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x16, 0x04, 0x00, 0x02, 0x05, 0x02, 0x12,
+    0x04, 0xe6, 0x01, 0x1c, 0x1d, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x16, 0x08, 0x00, 0x12, 0x06, 0xeb,
+    0x01, 0x02, 0xf1, 0x01, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x16, 0x08, 0x00, 0x01, 0x12, 0x04,
+    0xeb, 0x01, 0x08, 0x0f, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x17, 0x12, 0x06, 0xf4, 0x01, 0x00, 0xf9,
+    0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x17, 0x01, 0x12, 0x04, 0xf4, 0x01, 0x08, 0x1c, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x17, 0x02, 0x00, 0x12, 0x04, 0xf5, 0x01, 0x02, 0x1f, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x17, 0x02, 0x00, 0x01, 0x12, 0x04, 0xf5, 0x01, 0x12, 0x1a, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x17, 0x02, 0x00, 0x03, 0x12, 0x04, 0xf5, 0x01, 0x1d, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x17, 0x02, 0x00, 0x06, 0x12, 0x04, 0xf5, 0x01, 0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x17,
+    0x02, 0x01, 0x12, 0x04, 0xf6, 0x01, 0x02, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x01,
+    0x01, 0x12, 0x04, 0xf6, 0x01, 0x14, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x01, 0x03,
+    0x12, 0x04, 0xf6, 0x01, 0x25, 0x26, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x01, 0x04, 0x12,
+    0x04, 0xf6, 0x01, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x01, 0x06, 0x12, 0x04,
+    0xf6, 0x01, 0x0b, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x17, 0x02, 0x02, 0x12, 0x04, 0xf7, 0x01,
+    0x02, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x02, 0x01, 0x12, 0x04, 0xf7, 0x01, 0x12,
+    0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x02, 0x03, 0x12, 0x04, 0xf7, 0x01, 0x1e, 0x1f,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x02, 0x04, 0x12, 0x04, 0xf7, 0x01, 0x02, 0x0a, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x17, 0x02, 0x02, 0x05, 0x12, 0x04, 0xf7, 0x01, 0x0b, 0x11, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x17, 0x02, 0x03, 0x12, 0x04, 0xf8, 0x01, 0x02, 0x23, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x17, 0x02, 0x03, 0x01, 0x12, 0x04, 0xf8, 0x01, 0x09, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x17, 0x02, 0x03, 0x03, 0x12, 0x04, 0xf8, 0x01, 0x21, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x17,
+    0x02, 0x03, 0x05, 0x12, 0x04, 0xf8, 0x01, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x18, 0x12,
+    0x06, 0xfb, 0x01, 0x00, 0xfe, 0x01, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x18, 0x01, 0x12, 0x04,
+    0xfb, 0x01, 0x08, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x18, 0x02, 0x00, 0x12, 0x04, 0xfc, 0x01,
+    0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18, 0x02, 0x00, 0x01, 0x12, 0x04, 0xfc, 0x01, 0x08,
+    0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18, 0x02, 0x00, 0x03, 0x12, 0x04, 0xfc, 0x01, 0x13, 0x14,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x18, 0x02, 0x00, 0x05, 0x12, 0x04, 0xfc, 0x01, 0x02, 0x07, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x18, 0x02, 0x01, 0x12, 0x04, 0xfd, 0x01, 0x02, 0x17, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x18, 0x02, 0x01, 0x01, 0x12, 0x04, 0xfd, 0x01, 0x0f, 0x12, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x18, 0x02, 0x01, 0x03, 0x12, 0x04, 0xfd, 0x01, 0x15, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x18, 0x02, 0x01, 0x06, 0x12, 0x04, 0xfd, 0x01, 0x02, 0x0e, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x19,
+    0x12, 0x06, 0x80, 0x02, 0x00, 0x84, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x19, 0x01, 0x12,
+    0x04, 0x80, 0x02, 0x08, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x19, 0x02, 0x00, 0x12, 0x04, 0x81,
+    0x02, 0x02, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x00, 0x01, 0x12, 0x04, 0x81, 0x02,
+    0x15, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x00, 0x03, 0x12, 0x04, 0x81, 0x02, 0x1c,
+    0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x00, 0x06, 0x12, 0x04, 0x81, 0x02, 0x02, 0x14,
+    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x19, 0x02, 0x01, 0x12, 0x04, 0x82, 0x02, 0x02, 0x27, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x19, 0x02, 0x01, 0x01, 0x12, 0x04, 0x82, 0x02, 0x14, 0x22, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x19, 0x02, 0x01, 0x03, 0x12, 0x04, 0x82, 0x02, 0x25, 0x26, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x19, 0x02, 0x01, 0x04, 0x12, 0x04, 0x82, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x19, 0x02, 0x01, 0x06, 0x12, 0x04, 0x82, 0x02, 0x0b, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x19,
+    0x02, 0x02, 0x12, 0x04, 0x83, 0x02, 0x02, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x02,
+    0x01, 0x12, 0x04, 0x83, 0x02, 0x12, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x02, 0x03,
+    0x12, 0x04, 0x83, 0x02, 0x1e, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x02, 0x04, 0x12,
+    0x04, 0x83, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x19, 0x02, 0x02, 0x05, 0x12, 0x04,
+    0x83, 0x02, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x1a, 0x12, 0x06, 0x86, 0x02, 0x00, 0x89,
+    0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1a, 0x01, 0x12, 0x04, 0x86, 0x02, 0x08, 0x17, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x1a, 0x02, 0x00, 0x12, 0x04, 0x87, 0x02, 0x02, 0x1e, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x1a, 0x02, 0x00, 0x01, 0x12, 0x04, 0x87, 0x02, 0x09, 0x19, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x1a, 0x02, 0x00, 0x03, 0x12, 0x04, 0x87, 0x02, 0x1c, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x1a, 0x02, 0x00, 0x05, 0x12, 0x04, 0x87, 0x02, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1a,
+    0x02, 0x01, 0x12, 0x04, 0x88, 0x02, 0x02, 0x3e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01,
+    0x01, 0x12, 0x04, 0x88, 0x02, 0x26, 0x39, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01, 0x03,
+    0x12, 0x04, 0x88, 0x02, 0x3c, 0x3d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01, 0x04, 0x12,
+    0x04, 0x88, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1a, 0x02, 0x01, 0x06, 0x12, 0x04,
+    0x88, 0x02, 0x0b, 0x25, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x1b, 0x12, 0x06, 0x8b, 0x02, 0x00, 0x96,
+    0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1b, 0x01, 0x12, 0x04, 0x8b, 0x02, 0x08, 0x22, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x1b, 0x02, 0x00, 0x12, 0x04, 0x91, 0x02, 0x02, 0x10, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x1b, 0x02, 0x00, 0x01, 0x12, 0x04, 0x91, 0x02, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x1b, 0x02, 0x00, 0x03, 0x12, 0x04, 0x91, 0x02, 0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x1b, 0x02, 0x00, 0x06, 0x12, 0x04, 0x91, 0x02, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1b,
+    0x02, 0x01, 0x12, 0x04, 0x94, 0x02, 0x04, 0x34, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x01,
+    0x01, 0x12, 0x04, 0x94, 0x02, 0x19, 0x2f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x01, 0x03,
+    0x12, 0x04, 0x94, 0x02, 0x32, 0x33, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x02, 0x01, 0x06, 0x12,
+    0x04, 0x94, 0x02, 0x04, 0x18, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x1b, 0x04, 0x00, 0x12, 0x06, 0x8c,
+    0x02, 0x02, 0x8f, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x04, 0x00, 0x01, 0x12, 0x04,
+    0x8c, 0x02, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1b, 0x04, 0x00, 0x02, 0x00, 0x12, 0x04,
+    0x8d, 0x02, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12,
+    0x04, 0x8d, 0x02, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b, 0x04, 0x00, 0x02, 0x00, 0x02,
+    0x12, 0x04, 0x8d, 0x02, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1b, 0x04, 0x00, 0x02, 0x01,
+    0x12, 0x04, 0x8e, 0x02, 0x04, 0x24, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b, 0x04, 0x00, 0x02, 0x01,
+    0x01, 0x12, 0x04, 0x8e, 0x02, 0x04, 0x1f, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1b, 0x04, 0x00, 0x02,
+    0x01, 0x02, 0x12, 0x04, 0x8e, 0x02, 0x22, 0x23, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x1b, 0x08, 0x00,
+    0x12, 0x06, 0x93, 0x02, 0x02, 0x95, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1b, 0x08, 0x00,
+    0x01, 0x12, 0x04, 0x93, 0x02, 0x08, 0x0f, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x1c, 0x12, 0x06, 0x98,
+    0x02, 0x00, 0x9a, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1c, 0x01, 0x12, 0x04, 0x98, 0x02,
+    0x08, 0x1b, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1c, 0x02, 0x00, 0x12, 0x04, 0x99, 0x02, 0x02, 0x2a,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1c, 0x02, 0x00, 0x01, 0x12, 0x04, 0x99, 0x02, 0x1e, 0x25, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x1c, 0x02, 0x00, 0x03, 0x12, 0x04, 0x99, 0x02, 0x28, 0x29, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x1c, 0x02, 0x00, 0x04, 0x12, 0x04, 0x99, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x1c, 0x02, 0x00, 0x06, 0x12, 0x04, 0x99, 0x02, 0x0b, 0x1d, 0x0a, 0x0c, 0x0a, 0x02,
+    0x04, 0x1d, 0x12, 0x06, 0x9c, 0x02, 0x00, 0x9f, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1d,
+    0x01, 0x12, 0x04, 0x9c, 0x02, 0x08, 0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1d, 0x02, 0x00, 0x12,
+    0x04, 0x9d, 0x02, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02, 0x00, 0x01, 0x12, 0x04,
+    0x9d, 0x02, 0x08, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02, 0x00, 0x03, 0x12, 0x04, 0x9d,
+    0x02, 0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02, 0x00, 0x05, 0x12, 0x04, 0x9d, 0x02,
+    0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1d, 0x02, 0x01, 0x12, 0x04, 0x9e, 0x02, 0x02, 0x15,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02, 0x01, 0x01, 0x12, 0x04, 0x9e, 0x02, 0x0d, 0x10, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x1d, 0x02, 0x01, 0x03, 0x12, 0x04, 0x9e, 0x02, 0x13, 0x14, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x1d, 0x02, 0x01, 0x06, 0x12, 0x04, 0x9e, 0x02, 0x02, 0x0c, 0x0a, 0x0c, 0x0a,
+    0x02, 0x04, 0x1e, 0x12, 0x06, 0xa1, 0x02, 0x00, 0xa7, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
+    0x1e, 0x01, 0x12, 0x04, 0xa1, 0x02, 0x08, 0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1e, 0x02, 0x00,
+    0x12, 0x04, 0xa2, 0x02, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x00, 0x01, 0x12,
+    0x04, 0xa2, 0x02, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x00, 0x03, 0x12, 0x04,
+    0xa2, 0x02, 0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x00, 0x05, 0x12, 0x04, 0xa2,
+    0x02, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1e, 0x02, 0x01, 0x12, 0x04, 0xa3, 0x02, 0x02,
+    0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x01, 0x01, 0x12, 0x04, 0xa3, 0x02, 0x09, 0x0d,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x01, 0x03, 0x12, 0x04, 0xa3, 0x02, 0x10, 0x11, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x01, 0x05, 0x12, 0x04, 0xa3, 0x02, 0x02, 0x08, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x1e, 0x02, 0x02, 0x12, 0x04, 0xa4, 0x02, 0x02, 0x24, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x1e, 0x02, 0x02, 0x01, 0x12, 0x04, 0xa4, 0x02, 0x18, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x1e, 0x02, 0x02, 0x03, 0x12, 0x04, 0xa4, 0x02, 0x22, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e,
+    0x02, 0x02, 0x04, 0x12, 0x04, 0xa4, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02,
+    0x02, 0x06, 0x12, 0x04, 0xa4, 0x02, 0x0b, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1e, 0x02, 0x03,
+    0x12, 0x04, 0xa5, 0x02, 0x02, 0x2e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x03, 0x01, 0x12,
+    0x04, 0xa5, 0x02, 0x18, 0x29, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x03, 0x03, 0x12, 0x04,
+    0xa5, 0x02, 0x2c, 0x2d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x03, 0x04, 0x12, 0x04, 0xa5,
+    0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x03, 0x06, 0x12, 0x04, 0xa5, 0x02,
+    0x0b, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1e, 0x02, 0x04, 0x12, 0x04, 0xa6, 0x02, 0x02, 0x22,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x04, 0x01, 0x12, 0x04, 0xa6, 0x02, 0x16, 0x1d, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x1e, 0x02, 0x04, 0x03, 0x12, 0x04, 0xa6, 0x02, 0x20, 0x21, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x1e, 0x02, 0x04, 0x04, 0x12, 0x04, 0xa6, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x1e, 0x02, 0x04, 0x06, 0x12, 0x04, 0xa6, 0x02, 0x0b, 0x15, 0x0a, 0x0c, 0x0a, 0x02,
+    0x04, 0x1f, 0x12, 0x06, 0xa8, 0x02, 0x00, 0xb5, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x1f,
+    0x01, 0x12, 0x04, 0xa8, 0x02, 0x08, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x00, 0x12,
+    0x04, 0xaf, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x00, 0x01, 0x12, 0x04,
+    0xaf, 0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x00, 0x03, 0x12, 0x04, 0xaf,
+    0x02, 0x10, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x00, 0x05, 0x12, 0x04, 0xaf, 0x02,
+    0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x01, 0x12, 0x04, 0xb0, 0x02, 0x02, 0x29,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x01, 0x01, 0x12, 0x04, 0xb0, 0x02, 0x1a, 0x24, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x01, 0x03, 0x12, 0x04, 0xb0, 0x02, 0x27, 0x28, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x1f, 0x02, 0x01, 0x06, 0x12, 0x04, 0xb0, 0x02, 0x02, 0x19, 0x0a, 0x0c, 0x0a,
     0x04, 0x04, 0x1f, 0x02, 0x02, 0x12, 0x04, 0xb1, 0x02, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x1f, 0x02, 0x02, 0x05, 0x12, 0x04, 0xb1, 0x02, 0x02, 0x06, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f,
-    0x02, 0x02, 0x01, 0x12, 0x04, 0xb1, 0x02, 0x07, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02,
-    0x02, 0x03, 0x12, 0x04, 0xb1, 0x02, 0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x03,
-    0x12, 0x04, 0xb2, 0x02, 0x02, 0x40, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x04, 0x12,
-    0x04, 0xb2, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x06, 0x12, 0x04,
-    0xb2, 0x02, 0x0b, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x01, 0x12, 0x04, 0xb2,
-    0x02, 0x28, 0x3b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x03, 0x12, 0x04, 0xb2, 0x02,
-    0x3e, 0x3f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x04, 0x12, 0x04, 0xb3, 0x02, 0x02, 0x1f,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x04, 0x04, 0x12, 0x04, 0xb3, 0x02, 0x02, 0x0a, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x04, 0x06, 0x12, 0x04, 0xb3, 0x02, 0x0b, 0x13, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x1f, 0x02, 0x04, 0x01, 0x12, 0x04, 0xb3, 0x02, 0x14, 0x1a, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x1f, 0x02, 0x04, 0x03, 0x12, 0x04, 0xb3, 0x02, 0x1d, 0x1e, 0x0a, 0x0c, 0x0a, 0x04,
+    0x1f, 0x02, 0x02, 0x01, 0x12, 0x04, 0xb1, 0x02, 0x07, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f,
+    0x02, 0x02, 0x03, 0x12, 0x04, 0xb1, 0x02, 0x12, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02,
+    0x02, 0x05, 0x12, 0x04, 0xb1, 0x02, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x03,
+    0x12, 0x04, 0xb2, 0x02, 0x02, 0x40, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x01, 0x12,
+    0x04, 0xb2, 0x02, 0x28, 0x3b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x03, 0x12, 0x04,
+    0xb2, 0x02, 0x3e, 0x3f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x04, 0x12, 0x04, 0xb2,
+    0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x03, 0x06, 0x12, 0x04, 0xb2, 0x02,
+    0x0b, 0x27, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x1f, 0x02, 0x04, 0x12, 0x04, 0xb3, 0x02, 0x02, 0x1f,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x04, 0x01, 0x12, 0x04, 0xb3, 0x02, 0x14, 0x1a, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x04, 0x03, 0x12, 0x04, 0xb3, 0x02, 0x1d, 0x1e, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x1f, 0x02, 0x04, 0x04, 0x12, 0x04, 0xb3, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x1f, 0x02, 0x04, 0x06, 0x12, 0x04, 0xb3, 0x02, 0x0b, 0x13, 0x0a, 0x0c, 0x0a, 0x04,
     0x04, 0x1f, 0x02, 0x05, 0x12, 0x04, 0xb4, 0x02, 0x02, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f,
-    0x02, 0x05, 0x04, 0x12, 0x04, 0xb4, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02,
-    0x05, 0x06, 0x12, 0x04, 0xb4, 0x02, 0x0b, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x05,
-    0x01, 0x12, 0x04, 0xb4, 0x02, 0x14, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x05, 0x03,
-    0x12, 0x04, 0xb4, 0x02, 0x1d, 0x1e, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x20, 0x12, 0x06, 0xb7, 0x02,
-    0x00, 0xbd, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x20, 0x01, 0x12, 0x04, 0xb7, 0x02, 0x08,
-    0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x20, 0x02, 0x00, 0x12, 0x04, 0xb8, 0x02, 0x02, 0x12, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x00, 0x05, 0x12, 0x04, 0xb8, 0x02, 0x02, 0x08, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x20, 0x02, 0x00, 0x01, 0x12, 0x04, 0xb8, 0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x20, 0x02, 0x00, 0x03, 0x12, 0x04, 0xb8, 0x02, 0x10, 0x11, 0x0a, 0x0c, 0x0a, 0x04,
-    0x04, 0x20, 0x02, 0x01, 0x12, 0x04, 0xb9, 0x02, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20,
-    0x02, 0x01, 0x05, 0x12, 0x04, 0xb9, 0x02, 0x02, 0x06, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02,
-    0x01, 0x01, 0x12, 0x04, 0xb9, 0x02, 0x07, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x01,
-    0x03, 0x12, 0x04, 0xb9, 0x02, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x20, 0x02, 0x02, 0x12,
-    0x04, 0xba, 0x02, 0x02, 0x25, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x02, 0x04, 0x12, 0x04,
-    0xba, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x02, 0x06, 0x12, 0x04, 0xba,
-    0x02, 0x0b, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x02, 0x01, 0x12, 0x04, 0xba, 0x02,
-    0x17, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x02, 0x03, 0x12, 0x04, 0xba, 0x02, 0x23,
-    0x24, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x20, 0x02, 0x03, 0x12, 0x04, 0xbb, 0x02, 0x02, 0x3e, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x03, 0x04, 0x12, 0x04, 0xbb, 0x02, 0x02, 0x0a, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x20, 0x02, 0x03, 0x06, 0x12, 0x04, 0xbb, 0x02, 0x0b, 0x25, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x20, 0x02, 0x03, 0x01, 0x12, 0x04, 0xbb, 0x02, 0x26, 0x39, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x20, 0x02, 0x03, 0x03, 0x12, 0x04, 0xbb, 0x02, 0x3c, 0x3d, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x20, 0x02, 0x04, 0x12, 0x04, 0xbc, 0x02, 0x02, 0x26, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02,
-    0x04, 0x04, 0x12, 0x04, 0xbc, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x04,
-    0x06, 0x12, 0x04, 0xbc, 0x02, 0x0b, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x04, 0x01,
-    0x12, 0x04, 0xbc, 0x02, 0x1b, 0x21, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x04, 0x03, 0x12,
-    0x04, 0xbc, 0x02, 0x24, 0x25, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x21, 0x12, 0x06, 0xbf, 0x02, 0x00,
-    0xc2, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x21, 0x01, 0x12, 0x04, 0xbf, 0x02, 0x08, 0x22,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x21, 0x02, 0x00, 0x12, 0x04, 0xc0, 0x02, 0x02, 0x27, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x21, 0x02, 0x00, 0x04, 0x12, 0x04, 0xc0, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x21, 0x02, 0x00, 0x06, 0x12, 0x04, 0xc0, 0x02, 0x0b, 0x16, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x21, 0x02, 0x00, 0x01, 0x12, 0x04, 0xc0, 0x02, 0x17, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x21, 0x02, 0x00, 0x03, 0x12, 0x04, 0xc0, 0x02, 0x25, 0x26, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x21,
-    0x02, 0x01, 0x12, 0x04, 0xc1, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x01,
-    0x05, 0x12, 0x04, 0xc1, 0x02, 0x02, 0x06, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x01, 0x01,
-    0x12, 0x04, 0xc1, 0x02, 0x07, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x01, 0x03, 0x12,
-    0x04, 0xc1, 0x02, 0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x22, 0x12, 0x06, 0xc4, 0x02, 0x00,
-    0xc7, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x22, 0x01, 0x12, 0x04, 0xc4, 0x02, 0x08, 0x17,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x22, 0x02, 0x00, 0x12, 0x04, 0xc5, 0x02, 0x02, 0x12, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x22, 0x02, 0x00, 0x05, 0x12, 0x04, 0xc5, 0x02, 0x02, 0x08, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x22, 0x02, 0x00, 0x01, 0x12, 0x04, 0xc5, 0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x22, 0x02, 0x00, 0x03, 0x12, 0x04, 0xc5, 0x02, 0x10, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x22, 0x02, 0x01, 0x12, 0x04, 0xc6, 0x02, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02,
-    0x01, 0x06, 0x12, 0x04, 0xc6, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02, 0x01,
-    0x01, 0x12, 0x04, 0xc6, 0x02, 0x0b, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02, 0x01, 0x03,
-    0x12, 0x04, 0xc6, 0x02, 0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x23, 0x12, 0x06, 0xc9, 0x02,
-    0x00, 0xcb, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x23, 0x01, 0x12, 0x04, 0xc9, 0x02, 0x08,
-    0x24, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x23, 0x02, 0x00, 0x12, 0x04, 0xca, 0x02, 0x02, 0x27, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x23, 0x02, 0x00, 0x04, 0x12, 0x04, 0xca, 0x02, 0x02, 0x0a, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x23, 0x02, 0x00, 0x06, 0x12, 0x04, 0xca, 0x02, 0x0b, 0x16, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x23, 0x02, 0x00, 0x01, 0x12, 0x04, 0xca, 0x02, 0x17, 0x22, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x23, 0x02, 0x00, 0x03, 0x12, 0x04, 0xca, 0x02, 0x25, 0x26, 0x0a, 0x0c, 0x0a, 0x02, 0x05,
-    0x00, 0x12, 0x06, 0xcd, 0x02, 0x00, 0xdd, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x05, 0x00, 0x01,
-    0x12, 0x04, 0xcd, 0x02, 0x05, 0x0e, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x00, 0x12, 0x04,
-    0xce, 0x02, 0x02, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xce,
-    0x02, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0xce, 0x02,
-    0x1b, 0x1c, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x01, 0x12, 0x04, 0xcf, 0x02, 0x02, 0x16,
-    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xcf, 0x02, 0x02, 0x11, 0x0a,
-    0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0xcf, 0x02, 0x14, 0x15, 0x0a, 0x0c,
-    0x0a, 0x04, 0x05, 0x00, 0x02, 0x02, 0x12, 0x04, 0xd0, 0x02, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05,
-    0x05, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0xd0, 0x02, 0x02, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x05,
-    0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0xd0, 0x02, 0x12, 0x13, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00,
-    0x02, 0x03, 0x12, 0x04, 0xd1, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x03,
-    0x01, 0x12, 0x04, 0xd1, 0x02, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x03, 0x02,
-    0x12, 0x04, 0xd1, 0x02, 0x13, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x04, 0x12, 0x04,
-    0xd2, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04, 0x01, 0x12, 0x04, 0xd2,
-    0x02, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04, 0x02, 0x12, 0x04, 0xd2, 0x02,
-    0x13, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x05, 0x12, 0x04, 0xd3, 0x02, 0x02, 0x15,
-    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05, 0x01, 0x12, 0x04, 0xd3, 0x02, 0x02, 0x10, 0x0a,
-    0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05, 0x02, 0x12, 0x04, 0xd3, 0x02, 0x13, 0x14, 0x0a, 0x0c,
-    0x0a, 0x04, 0x05, 0x00, 0x02, 0x06, 0x12, 0x04, 0xd4, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05,
-    0x05, 0x00, 0x02, 0x06, 0x01, 0x12, 0x04, 0xd4, 0x02, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x05,
-    0x00, 0x02, 0x06, 0x02, 0x12, 0x04, 0xd4, 0x02, 0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00,
-    0x02, 0x07, 0x12, 0x04, 0xd5, 0x02, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x07,
-    0x01, 0x12, 0x04, 0xd5, 0x02, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x07, 0x02,
-    0x12, 0x04, 0xd5, 0x02, 0x14, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x08, 0x12, 0x04,
-    0xd6, 0x02, 0x02, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x08, 0x01, 0x12, 0x04, 0xd6,
-    0x02, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x08, 0x02, 0x12, 0x04, 0xd6, 0x02,
-    0x17, 0x18, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x09, 0x12, 0x04, 0xd7, 0x02, 0x02, 0x18,
-    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x09, 0x01, 0x12, 0x04, 0xd7, 0x02, 0x02, 0x13, 0x0a,
-    0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x09, 0x02, 0x12, 0x04, 0xd7, 0x02, 0x16, 0x17, 0x0a, 0x2b,
-    0x0a, 0x04, 0x05, 0x00, 0x02, 0x0a, 0x12, 0x04, 0xd8, 0x02, 0x02, 0x18, 0x22, 0x1d, 0x20, 0x60,
-    0x7b, 0x20, 0x69, 0x74, 0x65, 0x6d, 0x73, 0x3a, 0x20, 0x42, 0x6f, 0x78, 0x3c, 0x4d, 0x6f, 0x76,
-    0x65, 0x54, 0x79, 0x70, 0x65, 0x3e, 0x20, 0x7d, 0x60, 0x2c, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x05,
-    0x00, 0x02, 0x0a, 0x01, 0x12, 0x04, 0xd8, 0x02, 0x02, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00,
-    0x02, 0x0a, 0x02, 0x12, 0x04, 0xd8, 0x02, 0x16, 0x17, 0x0a, 0x22, 0x0a, 0x04, 0x05, 0x00, 0x02,
-    0x0b, 0x12, 0x04, 0xd9, 0x02, 0x02, 0x18, 0x22, 0x14, 0x20, 0x60, 0x28, 0x4d, 0x6f, 0x76, 0x65,
-    0x53, 0x74, 0x72, 0x75, 0x63, 0x74, 0x54, 0x61, 0x67, 0x29, 0x60, 0x2c, 0x0a, 0x0a, 0x0d, 0x0a,
-    0x05, 0x05, 0x00, 0x02, 0x0b, 0x01, 0x12, 0x04, 0xd9, 0x02, 0x02, 0x13, 0x0a, 0x0d, 0x0a, 0x05,
-    0x05, 0x00, 0x02, 0x0b, 0x02, 0x12, 0x04, 0xd9, 0x02, 0x16, 0x17, 0x0a, 0x22, 0x0a, 0x04, 0x05,
-    0x00, 0x02, 0x0c, 0x12, 0x04, 0xda, 0x02, 0x02, 0x24, 0x22, 0x14, 0x20, 0x60, 0x7b, 0x20, 0x69,
-    0x6e, 0x64, 0x65, 0x78, 0x3a, 0x20, 0x75, 0x31, 0x36, 0x20, 0x7d, 0x60, 0x60, 0x2c, 0x0a, 0x0a,
-    0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0c, 0x01, 0x12, 0x04, 0xda, 0x02, 0x02, 0x1f, 0x0a, 0x0d,
-    0x0a, 0x05, 0x05, 0x00, 0x02, 0x0c, 0x02, 0x12, 0x04, 0xda, 0x02, 0x22, 0x23, 0x0a, 0x37, 0x0a,
-    0x04, 0x05, 0x00, 0x02, 0x0d, 0x12, 0x04, 0xdb, 0x02, 0x02, 0x1c, 0x22, 0x29, 0x20, 0x60, 0x7b,
-    0x20, 0x6d, 0x75, 0x74, 0x61, 0x62, 0x6c, 0x65, 0x3a, 0x20, 0x62, 0x6f, 0x6f, 0x6c, 0x2c, 0x20,
-    0x74, 0x6f, 0x3a, 0x20, 0x42, 0x6f, 0x78, 0x3c, 0x4d, 0x6f, 0x76, 0x65, 0x54, 0x79, 0x70, 0x65,
-    0x3e, 0x20, 0x7d, 0x60, 0x2c, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0d, 0x01, 0x12,
-    0x04, 0xdb, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0d, 0x02, 0x12, 0x04,
-    0xdb, 0x02, 0x19, 0x1b, 0x0a, 0x1b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0e, 0x12, 0x04, 0xdc, 0x02,
-    0x02, 0x1d, 0x22, 0x0d, 0x20, 0x60, 0x28, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x29, 0x60, 0x2c,
-    0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0e, 0x01, 0x12, 0x04, 0xdc, 0x02, 0x02, 0x17,
-    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0e, 0x02, 0x12, 0x04, 0xdc, 0x02, 0x1a, 0x1c, 0x0a,
-    0x0c, 0x0a, 0x02, 0x04, 0x24, 0x12, 0x06, 0xdf, 0x02, 0x00, 0xee, 0x02, 0x01, 0x0a, 0x0b, 0x0a,
-    0x03, 0x04, 0x24, 0x01, 0x12, 0x04, 0xdf, 0x02, 0x08, 0x10, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x24,
-    0x03, 0x00, 0x12, 0x06, 0xe1, 0x02, 0x02, 0xe4, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24,
-    0x03, 0x00, 0x01, 0x12, 0x04, 0xe1, 0x02, 0x0a, 0x17, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x24, 0x03,
-    0x00, 0x02, 0x00, 0x12, 0x04, 0xe2, 0x02, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x24, 0x03,
-    0x00, 0x02, 0x00, 0x05, 0x12, 0x04, 0xe2, 0x02, 0x04, 0x08, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x24,
-    0x03, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xe2, 0x02, 0x09, 0x10, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
-    0x24, 0x03, 0x00, 0x02, 0x00, 0x03, 0x12, 0x04, 0xe2, 0x02, 0x13, 0x14, 0x0a, 0x0e, 0x0a, 0x06,
-    0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x12, 0x04, 0xe3, 0x02, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07,
-    0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x06, 0x12, 0x04, 0xe3, 0x02, 0x04, 0x0c, 0x0a, 0x0f, 0x0a,
-    0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xe3, 0x02, 0x0d, 0x0f, 0x0a, 0x0f,
-    0x0a, 0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x03, 0x12, 0x04, 0xe3, 0x02, 0x12, 0x13, 0x0a,
-    0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02, 0x00, 0x12, 0x04, 0xe6, 0x02, 0x02, 0x15, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x24, 0x02, 0x00, 0x06, 0x12, 0x04, 0xe6, 0x02, 0x02, 0x0b, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x24, 0x02, 0x00, 0x01, 0x12, 0x04, 0xe6, 0x02, 0x0c, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x24, 0x02, 0x00, 0x03, 0x12, 0x04, 0xe6, 0x02, 0x13, 0x14, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x24,
-    0x08, 0x00, 0x12, 0x06, 0xe7, 0x02, 0x02, 0xed, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24,
-    0x08, 0x00, 0x01, 0x12, 0x04, 0xe7, 0x02, 0x08, 0x0f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02,
-    0x01, 0x12, 0x04, 0xe8, 0x02, 0x04, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x01, 0x06,
-    0x12, 0x04, 0xe8, 0x02, 0x04, 0x0c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x01, 0x01, 0x12,
+    0x02, 0x05, 0x01, 0x12, 0x04, 0xb4, 0x02, 0x14, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02,
+    0x05, 0x03, 0x12, 0x04, 0xb4, 0x02, 0x1d, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x05,
+    0x04, 0x12, 0x04, 0xb4, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x02, 0x05, 0x06,
+    0x12, 0x04, 0xb4, 0x02, 0x0b, 0x13, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x1f, 0x04, 0x00, 0x12, 0x06,
+    0xa9, 0x02, 0x02, 0xae, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x1f, 0x04, 0x00, 0x01, 0x12,
+    0x04, 0xa9, 0x02, 0x07, 0x11, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x00, 0x12,
+    0x04, 0xaa, 0x02, 0x04, 0x1f, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x00, 0x01,
+    0x12, 0x04, 0xaa, 0x02, 0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02, 0x00,
+    0x02, 0x12, 0x04, 0xaa, 0x02, 0x1d, 0x1e, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1f, 0x04, 0x00, 0x02,
+    0x01, 0x12, 0x04, 0xab, 0x02, 0x04, 0x1b, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00, 0x02,
+    0x01, 0x01, 0x12, 0x04, 0xab, 0x02, 0x04, 0x16, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04, 0x00,
+    0x02, 0x01, 0x02, 0x12, 0x04, 0xab, 0x02, 0x19, 0x1a, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x1f, 0x04,
+    0x00, 0x02, 0x02, 0x12, 0x04, 0xac, 0x02, 0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f, 0x04,
+    0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0xac, 0x02, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x1f,
+    0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0xac, 0x02, 0x18, 0x19, 0x0a, 0x0e, 0x0a, 0x06, 0x04,
+    0x1f, 0x04, 0x00, 0x02, 0x03, 0x12, 0x04, 0xad, 0x02, 0x04, 0x1a, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
+    0x1f, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x04, 0xad, 0x02, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07,
+    0x04, 0x1f, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x04, 0xad, 0x02, 0x18, 0x19, 0x0a, 0x0c, 0x0a,
+    0x02, 0x04, 0x20, 0x12, 0x06, 0xb7, 0x02, 0x00, 0xbd, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
+    0x20, 0x01, 0x12, 0x04, 0xb7, 0x02, 0x08, 0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x20, 0x02, 0x00,
+    0x12, 0x04, 0xb8, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x00, 0x01, 0x12,
+    0x04, 0xb8, 0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x00, 0x03, 0x12, 0x04,
+    0xb8, 0x02, 0x10, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x00, 0x05, 0x12, 0x04, 0xb8,
+    0x02, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x20, 0x02, 0x01, 0x12, 0x04, 0xb9, 0x02, 0x02,
+    0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x01, 0x01, 0x12, 0x04, 0xb9, 0x02, 0x07, 0x10,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x01, 0x03, 0x12, 0x04, 0xb9, 0x02, 0x13, 0x14, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x01, 0x05, 0x12, 0x04, 0xb9, 0x02, 0x02, 0x06, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x20, 0x02, 0x02, 0x12, 0x04, 0xba, 0x02, 0x02, 0x25, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x20, 0x02, 0x02, 0x01, 0x12, 0x04, 0xba, 0x02, 0x17, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x20, 0x02, 0x02, 0x03, 0x12, 0x04, 0xba, 0x02, 0x23, 0x24, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20,
+    0x02, 0x02, 0x04, 0x12, 0x04, 0xba, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02,
+    0x02, 0x06, 0x12, 0x04, 0xba, 0x02, 0x0b, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x20, 0x02, 0x03,
+    0x12, 0x04, 0xbb, 0x02, 0x02, 0x3e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x03, 0x01, 0x12,
+    0x04, 0xbb, 0x02, 0x26, 0x39, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x03, 0x03, 0x12, 0x04,
+    0xbb, 0x02, 0x3c, 0x3d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x03, 0x04, 0x12, 0x04, 0xbb,
+    0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x03, 0x06, 0x12, 0x04, 0xbb, 0x02,
+    0x0b, 0x25, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x20, 0x02, 0x04, 0x12, 0x04, 0xbc, 0x02, 0x02, 0x26,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x04, 0x01, 0x12, 0x04, 0xbc, 0x02, 0x1b, 0x21, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x20, 0x02, 0x04, 0x03, 0x12, 0x04, 0xbc, 0x02, 0x24, 0x25, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x20, 0x02, 0x04, 0x04, 0x12, 0x04, 0xbc, 0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x20, 0x02, 0x04, 0x06, 0x12, 0x04, 0xbc, 0x02, 0x0b, 0x1a, 0x0a, 0x0c, 0x0a, 0x02,
+    0x04, 0x21, 0x12, 0x06, 0xbf, 0x02, 0x00, 0xc2, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x21,
+    0x01, 0x12, 0x04, 0xbf, 0x02, 0x08, 0x22, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x21, 0x02, 0x00, 0x12,
+    0x04, 0xc0, 0x02, 0x02, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x00, 0x01, 0x12, 0x04,
+    0xc0, 0x02, 0x17, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x00, 0x03, 0x12, 0x04, 0xc0,
+    0x02, 0x25, 0x26, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x00, 0x04, 0x12, 0x04, 0xc0, 0x02,
+    0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x00, 0x06, 0x12, 0x04, 0xc0, 0x02, 0x0b,
+    0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x21, 0x02, 0x01, 0x12, 0x04, 0xc1, 0x02, 0x02, 0x16, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x21, 0x02, 0x01, 0x01, 0x12, 0x04, 0xc1, 0x02, 0x07, 0x11, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x21, 0x02, 0x01, 0x03, 0x12, 0x04, 0xc1, 0x02, 0x14, 0x15, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x21, 0x02, 0x01, 0x05, 0x12, 0x04, 0xc1, 0x02, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x02,
+    0x04, 0x22, 0x12, 0x06, 0xc4, 0x02, 0x00, 0xc7, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x22,
+    0x01, 0x12, 0x04, 0xc4, 0x02, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x22, 0x02, 0x00, 0x12,
+    0x04, 0xc5, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02, 0x00, 0x01, 0x12, 0x04,
+    0xc5, 0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02, 0x00, 0x03, 0x12, 0x04, 0xc5,
+    0x02, 0x10, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02, 0x00, 0x05, 0x12, 0x04, 0xc5, 0x02,
+    0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x22, 0x02, 0x01, 0x12, 0x04, 0xc6, 0x02, 0x02, 0x14,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02, 0x01, 0x01, 0x12, 0x04, 0xc6, 0x02, 0x0b, 0x0f, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x22, 0x02, 0x01, 0x03, 0x12, 0x04, 0xc6, 0x02, 0x12, 0x13, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x22, 0x02, 0x01, 0x06, 0x12, 0x04, 0xc6, 0x02, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
+    0x02, 0x04, 0x23, 0x12, 0x06, 0xc9, 0x02, 0x00, 0xcb, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
+    0x23, 0x01, 0x12, 0x04, 0xc9, 0x02, 0x08, 0x24, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x23, 0x02, 0x00,
+    0x12, 0x04, 0xca, 0x02, 0x02, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x23, 0x02, 0x00, 0x01, 0x12,
+    0x04, 0xca, 0x02, 0x17, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x23, 0x02, 0x00, 0x03, 0x12, 0x04,
+    0xca, 0x02, 0x25, 0x26, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x23, 0x02, 0x00, 0x04, 0x12, 0x04, 0xca,
+    0x02, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x23, 0x02, 0x00, 0x06, 0x12, 0x04, 0xca, 0x02,
+    0x0b, 0x16, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x24, 0x12, 0x06, 0xdf, 0x02, 0x00, 0xee, 0x02, 0x01,
+    0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x24, 0x01, 0x12, 0x04, 0xdf, 0x02, 0x08, 0x10, 0x0a, 0x0c, 0x0a,
+    0x04, 0x04, 0x24, 0x02, 0x00, 0x12, 0x04, 0xe6, 0x02, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x24, 0x02, 0x00, 0x01, 0x12, 0x04, 0xe6, 0x02, 0x0c, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24,
+    0x02, 0x00, 0x03, 0x12, 0x04, 0xe6, 0x02, 0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02,
+    0x00, 0x06, 0x12, 0x04, 0xe6, 0x02, 0x02, 0x0b, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02, 0x01,
+    0x12, 0x04, 0xe8, 0x02, 0x04, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x01, 0x01, 0x12,
     0x04, 0xe8, 0x02, 0x0d, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x01, 0x03, 0x12, 0x04,
-    0xe8, 0x02, 0x16, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02, 0x02, 0x12, 0x04, 0xe9, 0x02,
-    0x04, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x02, 0x06, 0x12, 0x04, 0xe9, 0x02, 0x04,
-    0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x02, 0x01, 0x12, 0x04, 0xe9, 0x02, 0x12, 0x18,
+    0xe8, 0x02, 0x16, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x01, 0x06, 0x12, 0x04, 0xe8,
+    0x02, 0x04, 0x0c, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02, 0x02, 0x12, 0x04, 0xe9, 0x02, 0x04,
+    0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x02, 0x01, 0x12, 0x04, 0xe9, 0x02, 0x12, 0x18,
     0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x02, 0x03, 0x12, 0x04, 0xe9, 0x02, 0x1b, 0x1c, 0x0a,
-    0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02, 0x03, 0x12, 0x04, 0xea, 0x02, 0x04, 0x28, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x24, 0x02, 0x03, 0x05, 0x12, 0x04, 0xea, 0x02, 0x04, 0x0a, 0x0a, 0x0d, 0x0a, 0x05,
+    0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x02, 0x06, 0x12, 0x04, 0xe9, 0x02, 0x04, 0x11, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x24, 0x02, 0x03, 0x12, 0x04, 0xea, 0x02, 0x04, 0x28, 0x0a, 0x0d, 0x0a, 0x05,
     0x04, 0x24, 0x02, 0x03, 0x01, 0x12, 0x04, 0xea, 0x02, 0x0b, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x24, 0x02, 0x03, 0x03, 0x12, 0x04, 0xea, 0x02, 0x26, 0x27, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24,
-    0x02, 0x04, 0x12, 0x04, 0xeb, 0x02, 0x04, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x04,
-    0x06, 0x12, 0x04, 0xeb, 0x02, 0x04, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x04, 0x01,
+    0x24, 0x02, 0x03, 0x03, 0x12, 0x04, 0xea, 0x02, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24,
+    0x02, 0x03, 0x05, 0x12, 0x04, 0xea, 0x02, 0x04, 0x0a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02,
+    0x04, 0x12, 0x04, 0xeb, 0x02, 0x04, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x04, 0x01,
     0x12, 0x04, 0xeb, 0x02, 0x12, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x04, 0x03, 0x12,
-    0x04, 0xeb, 0x02, 0x1e, 0x1f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02, 0x05, 0x12, 0x04, 0xec,
-    0x02, 0x04, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x05, 0x05, 0x12, 0x04, 0xec, 0x02,
-    0x04, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x05, 0x01, 0x12, 0x04, 0xec, 0x02, 0x0b,
+    0x04, 0xeb, 0x02, 0x1e, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x04, 0x06, 0x12, 0x04,
+    0xeb, 0x02, 0x04, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x24, 0x02, 0x05, 0x12, 0x04, 0xec, 0x02,
+    0x04, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x05, 0x01, 0x12, 0x04, 0xec, 0x02, 0x0b,
     0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x05, 0x03, 0x12, 0x04, 0xec, 0x02, 0x18, 0x19,
-    0x0a, 0x0c, 0x0a, 0x02, 0x05, 0x01, 0x12, 0x06, 0xf0, 0x02, 0x00, 0xf6, 0x02, 0x01, 0x0a, 0x0b,
-    0x0a, 0x03, 0x05, 0x01, 0x01, 0x12, 0x04, 0xf0, 0x02, 0x05, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x05,
-    0x01, 0x02, 0x00, 0x12, 0x04, 0xf1, 0x02, 0x02, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02,
-    0x00, 0x01, 0x12, 0x04, 0xf1, 0x02, 0x02, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x00,
-    0x02, 0x12, 0x04, 0xf1, 0x02, 0x1d, 0x1e, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x01, 0x02, 0x01, 0x12,
-    0x04, 0xf2, 0x02, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x01, 0x01, 0x12, 0x04,
-    0xf2, 0x02, 0x02, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x01, 0x02, 0x12, 0x04, 0xf2,
-    0x02, 0x16, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x01, 0x02, 0x02, 0x12, 0x04, 0xf3, 0x02, 0x02,
-    0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x02, 0x01, 0x12, 0x04, 0xf3, 0x02, 0x02, 0x13,
-    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x02, 0x02, 0x12, 0x04, 0xf3, 0x02, 0x16, 0x17, 0x0a,
-    0x0c, 0x0a, 0x04, 0x05, 0x01, 0x02, 0x03, 0x12, 0x04, 0xf4, 0x02, 0x02, 0x19, 0x0a, 0x0d, 0x0a,
-    0x05, 0x05, 0x01, 0x02, 0x03, 0x01, 0x12, 0x04, 0xf4, 0x02, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05,
-    0x05, 0x01, 0x02, 0x03, 0x02, 0x12, 0x04, 0xf4, 0x02, 0x17, 0x18, 0x0a, 0x0c, 0x0a, 0x04, 0x05,
-    0x01, 0x02, 0x04, 0x12, 0x04, 0xf5, 0x02, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02,
-    0x04, 0x01, 0x12, 0x04, 0xf5, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x04,
-    0x02, 0x12, 0x04, 0xf5, 0x02, 0x15, 0x16, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x25, 0x12, 0x06, 0xf8,
-    0x02, 0x00, 0xfa, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x25, 0x01, 0x12, 0x04, 0xf8, 0x02,
-    0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x25, 0x02, 0x00, 0x12, 0x04, 0xf9, 0x02, 0x02, 0x19,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x25, 0x02, 0x00, 0x06, 0x12, 0x04, 0xf9, 0x02, 0x02, 0x0a, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x25, 0x02, 0x00, 0x01, 0x12, 0x04, 0xf9, 0x02, 0x0b, 0x14, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x25, 0x02, 0x00, 0x03, 0x12, 0x04, 0xf9, 0x02, 0x17, 0x18, 0x0a, 0x0c, 0x0a,
-    0x02, 0x04, 0x26, 0x12, 0x06, 0xfc, 0x02, 0x00, 0xff, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04,
-    0x26, 0x01, 0x12, 0x04, 0xfc, 0x02, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x26, 0x02, 0x00,
-    0x12, 0x04, 0xfd, 0x02, 0x02, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x00, 0x06, 0x12,
-    0x04, 0xfd, 0x02, 0x02, 0x0e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x00, 0x01, 0x12, 0x04,
-    0xfd, 0x02, 0x0f, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x00, 0x03, 0x12, 0x04, 0xfd,
-    0x02, 0x18, 0x19, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x26, 0x02, 0x01, 0x12, 0x04, 0xfe, 0x02, 0x02,
-    0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x01, 0x05, 0x12, 0x04, 0xfe, 0x02, 0x02, 0x08,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x01, 0x01, 0x12, 0x04, 0xfe, 0x02, 0x09, 0x0d, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x01, 0x03, 0x12, 0x04, 0xfe, 0x02, 0x10, 0x11, 0x0a, 0x0c,
-    0x0a, 0x02, 0x04, 0x27, 0x12, 0x06, 0x81, 0x03, 0x00, 0x84, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03,
-    0x04, 0x27, 0x01, 0x12, 0x04, 0x81, 0x03, 0x08, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x27, 0x02,
-    0x00, 0x12, 0x04, 0x82, 0x03, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x00, 0x05,
-    0x12, 0x04, 0x82, 0x03, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x00, 0x01, 0x12,
-    0x04, 0x82, 0x03, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x00, 0x03, 0x12, 0x04,
-    0x82, 0x03, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x27, 0x02, 0x01, 0x12, 0x04, 0x83, 0x03,
-    0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x01, 0x05, 0x12, 0x04, 0x83, 0x03, 0x02,
-    0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x01, 0x01, 0x12, 0x04, 0x83, 0x03, 0x09, 0x0d,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x01, 0x03, 0x12, 0x04, 0x83, 0x03, 0x10, 0x11, 0x0a,
-    0x0c, 0x0a, 0x02, 0x04, 0x28, 0x12, 0x06, 0x86, 0x03, 0x00, 0x8b, 0x03, 0x01, 0x0a, 0x0b, 0x0a,
-    0x03, 0x04, 0x28, 0x01, 0x12, 0x04, 0x86, 0x03, 0x08, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x28,
-    0x02, 0x00, 0x12, 0x04, 0x87, 0x03, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x00,
-    0x05, 0x12, 0x04, 0x87, 0x03, 0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x00, 0x01,
-    0x12, 0x04, 0x87, 0x03, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x00, 0x03, 0x12,
-    0x04, 0x87, 0x03, 0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x28, 0x02, 0x01, 0x12, 0x04, 0x88,
-    0x03, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x01, 0x05, 0x12, 0x04, 0x88, 0x03,
-    0x02, 0x08, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x01, 0x01, 0x12, 0x04, 0x88, 0x03, 0x09,
-    0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x01, 0x03, 0x12, 0x04, 0x88, 0x03, 0x12, 0x13,
-    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x28, 0x02, 0x02, 0x12, 0x04, 0x89, 0x03, 0x02, 0x12, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x28, 0x02, 0x02, 0x05, 0x12, 0x04, 0x89, 0x03, 0x02, 0x08, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x28, 0x02, 0x02, 0x01, 0x12, 0x04, 0x89, 0x03, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x28, 0x02, 0x02, 0x03, 0x12, 0x04, 0x89, 0x03, 0x10, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x28, 0x02, 0x03, 0x12, 0x04, 0x8a, 0x03, 0x02, 0x2c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02,
-    0x03, 0x04, 0x12, 0x04, 0x8a, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x03,
-    0x06, 0x12, 0x04, 0x8a, 0x03, 0x0b, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x03, 0x01,
-    0x12, 0x04, 0x8a, 0x03, 0x14, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x03, 0x03, 0x12,
-    0x04, 0x8a, 0x03, 0x2a, 0x2b, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x29, 0x12, 0x06, 0x8d, 0x03, 0x00,
-    0x9c, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x29, 0x01, 0x12, 0x04, 0x8d, 0x03, 0x08, 0x11,
-    0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x29, 0x04, 0x00, 0x12, 0x06, 0x8f, 0x03, 0x02, 0x94, 0x03, 0x03,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x04, 0x00, 0x01, 0x12, 0x04, 0x8f, 0x03, 0x07, 0x0b, 0x0a,
-    0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x00, 0x12, 0x04, 0x90, 0x03, 0x04, 0x19, 0x0a,
-    0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0x90, 0x03, 0x04, 0x14,
-    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0x90, 0x03, 0x17,
-    0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0x91, 0x03, 0x04,
-    0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0x91, 0x03,
-    0x04, 0x10, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0x91,
-    0x03, 0x13, 0x14, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0x92,
-    0x03, 0x04, 0x1b, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04,
-    0x92, 0x03, 0x04, 0x16, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12,
-    0x04, 0x92, 0x03, 0x19, 0x1a, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x03, 0x12,
-    0x04, 0x93, 0x03, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x03, 0x01,
-    0x12, 0x04, 0x93, 0x03, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x03,
-    0x02, 0x12, 0x04, 0x93, 0x03, 0x17, 0x18, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x29, 0x02, 0x00, 0x12,
-    0x04, 0x96, 0x03, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x00, 0x06, 0x12, 0x04,
-    0x96, 0x03, 0x02, 0x06, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x00, 0x01, 0x12, 0x04, 0x96,
-    0x03, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x00, 0x03, 0x12, 0x04, 0x96, 0x03,
-    0x0e, 0x0f, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x29, 0x08, 0x00, 0x12, 0x06, 0x97, 0x03, 0x02, 0x9b,
-    0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x08, 0x00, 0x01, 0x12, 0x04, 0x97, 0x03, 0x08,
-    0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x29, 0x02, 0x01, 0x12, 0x04, 0x98, 0x03, 0x04, 0x21, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x01, 0x06, 0x12, 0x04, 0x98, 0x03, 0x04, 0x14, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x29, 0x02, 0x01, 0x01, 0x12, 0x04, 0x98, 0x03, 0x15, 0x1c, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x29, 0x02, 0x01, 0x03, 0x12, 0x04, 0x98, 0x03, 0x1f, 0x20, 0x0a, 0x0c, 0x0a, 0x04,
-    0x04, 0x29, 0x02, 0x02, 0x12, 0x04, 0x99, 0x03, 0x04, 0x2c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29,
-    0x02, 0x02, 0x06, 0x12, 0x04, 0x99, 0x03, 0x04, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02,
-    0x02, 0x01, 0x12, 0x04, 0x99, 0x03, 0x1a, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x02,
-    0x03, 0x12, 0x04, 0x99, 0x03, 0x2a, 0x2b, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x29, 0x02, 0x03, 0x12,
-    0x04, 0x9a, 0x03, 0x04, 0x28, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x03, 0x06, 0x12, 0x04,
-    0x9a, 0x03, 0x04, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x03, 0x01, 0x12, 0x04, 0x9a,
-    0x03, 0x18, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x03, 0x03, 0x12, 0x04, 0x9a, 0x03,
-    0x26, 0x27, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2a, 0x12, 0x06, 0x9e, 0x03, 0x00, 0xa1, 0x03, 0x01,
-    0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2a, 0x01, 0x12, 0x04, 0x9e, 0x03, 0x08, 0x18, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x2a, 0x02, 0x00, 0x12, 0x04, 0x9f, 0x03, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x2a, 0x02, 0x00, 0x05, 0x12, 0x04, 0x9f, 0x03, 0x02, 0x07, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a,
-    0x02, 0x00, 0x01, 0x12, 0x04, 0x9f, 0x03, 0x08, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a, 0x02,
-    0x00, 0x03, 0x12, 0x04, 0x9f, 0x03, 0x15, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2a, 0x02, 0x01,
-    0x12, 0x04, 0xa0, 0x03, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a, 0x02, 0x01, 0x05, 0x12,
-    0x04, 0xa0, 0x03, 0x02, 0x07, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a, 0x02, 0x01, 0x01, 0x12, 0x04,
-    0xa0, 0x03, 0x08, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a, 0x02, 0x01, 0x03, 0x12, 0x04, 0xa0,
-    0x03, 0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2b, 0x12, 0x06, 0xa3, 0x03, 0x00, 0xa8, 0x03,
-    0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2b, 0x01, 0x12, 0x04, 0xa3, 0x03, 0x08, 0x1d, 0x0a, 0x0c,
-    0x0a, 0x04, 0x04, 0x2b, 0x02, 0x00, 0x12, 0x04, 0xa4, 0x03, 0x02, 0x21, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x2b, 0x02, 0x00, 0x04, 0x12, 0x04, 0xa4, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x2b, 0x02, 0x00, 0x05, 0x12, 0x04, 0xa4, 0x03, 0x0b, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b,
-    0x02, 0x00, 0x01, 0x12, 0x04, 0xa4, 0x03, 0x11, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02,
-    0x00, 0x03, 0x12, 0x04, 0xa4, 0x03, 0x1f, 0x20, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2b, 0x02, 0x01,
-    0x12, 0x04, 0xa5, 0x03, 0x02, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x01, 0x04, 0x12,
-    0x04, 0xa5, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x01, 0x05, 0x12, 0x04,
-    0xa5, 0x03, 0x0b, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x01, 0x01, 0x12, 0x04, 0xa5,
-    0x03, 0x11, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x01, 0x03, 0x12, 0x04, 0xa5, 0x03,
-    0x1e, 0x1f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2b, 0x02, 0x02, 0x12, 0x04, 0xa6, 0x03, 0x02, 0x17,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x02, 0x05, 0x12, 0x04, 0xa6, 0x03, 0x02, 0x08, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x02, 0x01, 0x12, 0x04, 0xa6, 0x03, 0x09, 0x12, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x2b, 0x02, 0x02, 0x03, 0x12, 0x04, 0xa6, 0x03, 0x15, 0x16, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x2b, 0x02, 0x03, 0x12, 0x04, 0xa7, 0x03, 0x02, 0x29, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x2b, 0x02, 0x03, 0x04, 0x12, 0x04, 0xa7, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b,
-    0x02, 0x03, 0x05, 0x12, 0x04, 0xa7, 0x03, 0x0b, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02,
-    0x03, 0x01, 0x12, 0x04, 0xa7, 0x03, 0x12, 0x24, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x03,
-    0x03, 0x12, 0x04, 0xa7, 0x03, 0x27, 0x28, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2c, 0x12, 0x06, 0xaa,
-    0x03, 0x00, 0xae, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2c, 0x01, 0x12, 0x04, 0xaa, 0x03,
-    0x08, 0x1b, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2c, 0x02, 0x00, 0x12, 0x04, 0xab, 0x03, 0x02, 0x1e,
-    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x00, 0x06, 0x12, 0x04, 0xab, 0x03, 0x02, 0x12, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x00, 0x01, 0x12, 0x04, 0xab, 0x03, 0x13, 0x19, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x2c, 0x02, 0x00, 0x03, 0x12, 0x04, 0xab, 0x03, 0x1c, 0x1d, 0x0a, 0x0c, 0x0a,
-    0x04, 0x04, 0x2c, 0x02, 0x01, 0x12, 0x04, 0xac, 0x03, 0x02, 0x31, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
-    0x2c, 0x02, 0x01, 0x04, 0x12, 0x04, 0xac, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c,
-    0x02, 0x01, 0x05, 0x12, 0x04, 0xac, 0x03, 0x0b, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02,
-    0x01, 0x01, 0x12, 0x04, 0xac, 0x03, 0x12, 0x2c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x01,
-    0x03, 0x12, 0x04, 0xac, 0x03, 0x2f, 0x30, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2c, 0x02, 0x02, 0x12,
-    0x04, 0xad, 0x03, 0x02, 0x32, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x02, 0x04, 0x12, 0x04,
-    0xad, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x02, 0x06, 0x12, 0x04, 0xad,
-    0x03, 0x0b, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x02, 0x01, 0x12, 0x04, 0xad, 0x03,
-    0x1c, 0x2d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x02, 0x03, 0x12, 0x04, 0xad, 0x03, 0x30,
-    0x31, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2d, 0x12, 0x06, 0xb0, 0x03, 0x00, 0xbc, 0x03, 0x01, 0x0a,
-    0x0b, 0x0a, 0x03, 0x04, 0x2d, 0x01, 0x12, 0x04, 0xb0, 0x03, 0x08, 0x18, 0x0a, 0x0e, 0x0a, 0x04,
-    0x04, 0x2d, 0x04, 0x00, 0x12, 0x06, 0xb1, 0x03, 0x02, 0xb5, 0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x2d, 0x04, 0x00, 0x01, 0x12, 0x04, 0xb1, 0x03, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04,
-    0x2d, 0x04, 0x00, 0x02, 0x00, 0x12, 0x04, 0xb2, 0x03, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
-    0x2d, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xb2, 0x03, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07,
-    0x04, 0x2d, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0xb2, 0x03, 0x17, 0x18, 0x0a, 0x0e, 0x0a,
-    0x06, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0xb3, 0x03, 0x04, 0x15, 0x0a, 0x0f, 0x0a,
-    0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xb3, 0x03, 0x04, 0x10, 0x0a, 0x0f,
-    0x0a, 0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0xb3, 0x03, 0x13, 0x14, 0x0a,
-    0x0e, 0x0a, 0x06, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0xb4, 0x03, 0x04, 0x1b, 0x0a,
-    0x0f, 0x0a, 0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0xb4, 0x03, 0x04, 0x16,
-    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0xb4, 0x03, 0x19,
-    0x1a, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2d, 0x02, 0x00, 0x12, 0x04, 0xb7, 0x03, 0x02, 0x10, 0x0a,
-    0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x00, 0x06, 0x12, 0x04, 0xb7, 0x03, 0x02, 0x06, 0x0a, 0x0d,
-    0x0a, 0x05, 0x04, 0x2d, 0x02, 0x00, 0x01, 0x12, 0x04, 0xb7, 0x03, 0x07, 0x0b, 0x0a, 0x0d, 0x0a,
-    0x05, 0x04, 0x2d, 0x02, 0x00, 0x03, 0x12, 0x04, 0xb7, 0x03, 0x0e, 0x0f, 0x0a, 0x0e, 0x0a, 0x04,
-    0x04, 0x2d, 0x08, 0x00, 0x12, 0x06, 0xb8, 0x03, 0x02, 0xbb, 0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05,
-    0x04, 0x2d, 0x08, 0x00, 0x01, 0x12, 0x04, 0xb8, 0x03, 0x08, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
-    0x2d, 0x02, 0x01, 0x12, 0x04, 0xb9, 0x03, 0x04, 0x21, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02,
-    0x01, 0x06, 0x12, 0x04, 0xb9, 0x03, 0x04, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x01,
-    0x01, 0x12, 0x04, 0xb9, 0x03, 0x15, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x01, 0x03,
-    0x12, 0x04, 0xb9, 0x03, 0x1f, 0x20, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2d, 0x02, 0x02, 0x12, 0x04,
-    0xba, 0x03, 0x04, 0x2c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x02, 0x06, 0x12, 0x04, 0xba,
-    0x03, 0x04, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x02, 0x01, 0x12, 0x04, 0xba, 0x03,
-    0x1a, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x02, 0x03, 0x12, 0x04, 0xba, 0x03, 0x2a,
-    0x2b, 0x62, 0x06, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x33,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x02, 0x05, 0x05, 0x12, 0x04, 0xec, 0x02, 0x04, 0x0a, 0x0a,
+    0x0e, 0x0a, 0x04, 0x04, 0x24, 0x03, 0x00, 0x12, 0x06, 0xe1, 0x02, 0x02, 0xe4, 0x02, 0x03, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x24, 0x03, 0x00, 0x01, 0x12, 0x04, 0xe1, 0x02, 0x0a, 0x17, 0x0a, 0x0e,
+    0x0a, 0x06, 0x04, 0x24, 0x03, 0x00, 0x02, 0x00, 0x12, 0x04, 0xe2, 0x02, 0x04, 0x15, 0x0a, 0x0f,
+    0x0a, 0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xe2, 0x02, 0x09, 0x10, 0x0a,
+    0x0f, 0x0a, 0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x00, 0x03, 0x12, 0x04, 0xe2, 0x02, 0x13, 0x14,
+    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x00, 0x05, 0x12, 0x04, 0xe2, 0x02, 0x04,
+    0x08, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x12, 0x04, 0xe3, 0x02, 0x04,
+    0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xe3, 0x02,
+    0x0d, 0x0f, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x03, 0x12, 0x04, 0xe3,
+    0x02, 0x12, 0x13, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x24, 0x03, 0x00, 0x02, 0x01, 0x06, 0x12, 0x04,
+    0xe3, 0x02, 0x04, 0x0c, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x24, 0x08, 0x00, 0x12, 0x06, 0xe7, 0x02,
+    0x02, 0xed, 0x02, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x24, 0x08, 0x00, 0x01, 0x12, 0x04, 0xe7,
+    0x02, 0x08, 0x0f, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x25, 0x12, 0x06, 0xf8, 0x02, 0x00, 0xfa, 0x02,
+    0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x25, 0x01, 0x12, 0x04, 0xf8, 0x02, 0x08, 0x17, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x25, 0x02, 0x00, 0x12, 0x04, 0xf9, 0x02, 0x02, 0x19, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x25, 0x02, 0x00, 0x01, 0x12, 0x04, 0xf9, 0x02, 0x0b, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x25, 0x02, 0x00, 0x03, 0x12, 0x04, 0xf9, 0x02, 0x17, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x25,
+    0x02, 0x00, 0x06, 0x12, 0x04, 0xf9, 0x02, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x26, 0x12,
+    0x06, 0xfc, 0x02, 0x00, 0xff, 0x02, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x26, 0x01, 0x12, 0x04,
+    0xfc, 0x02, 0x08, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x26, 0x02, 0x00, 0x12, 0x04, 0xfd, 0x02,
+    0x02, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x00, 0x01, 0x12, 0x04, 0xfd, 0x02, 0x0f,
+    0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x00, 0x03, 0x12, 0x04, 0xfd, 0x02, 0x18, 0x19,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x26, 0x02, 0x00, 0x06, 0x12, 0x04, 0xfd, 0x02, 0x02, 0x0e, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x26, 0x02, 0x01, 0x12, 0x04, 0xfe, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x26, 0x02, 0x01, 0x01, 0x12, 0x04, 0xfe, 0x02, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x26, 0x02, 0x01, 0x03, 0x12, 0x04, 0xfe, 0x02, 0x10, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x26, 0x02, 0x01, 0x05, 0x12, 0x04, 0xfe, 0x02, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x27,
+    0x12, 0x06, 0x81, 0x03, 0x00, 0x84, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x27, 0x01, 0x12,
+    0x04, 0x81, 0x03, 0x08, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x27, 0x02, 0x00, 0x12, 0x04, 0x82,
+    0x03, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x00, 0x01, 0x12, 0x04, 0x82, 0x03,
+    0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x00, 0x03, 0x12, 0x04, 0x82, 0x03, 0x13,
+    0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x27, 0x02, 0x00, 0x05, 0x12, 0x04, 0x82, 0x03, 0x02, 0x08,
+    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x27, 0x02, 0x01, 0x12, 0x04, 0x83, 0x03, 0x02, 0x12, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x27, 0x02, 0x01, 0x01, 0x12, 0x04, 0x83, 0x03, 0x09, 0x0d, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x27, 0x02, 0x01, 0x03, 0x12, 0x04, 0x83, 0x03, 0x10, 0x11, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x27, 0x02, 0x01, 0x05, 0x12, 0x04, 0x83, 0x03, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x02, 0x04,
+    0x28, 0x12, 0x06, 0x86, 0x03, 0x00, 0x8b, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x28, 0x01,
+    0x12, 0x04, 0x86, 0x03, 0x08, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x28, 0x02, 0x00, 0x12, 0x04,
+    0x87, 0x03, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x00, 0x01, 0x12, 0x04, 0x87,
+    0x03, 0x09, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x00, 0x03, 0x12, 0x04, 0x87, 0x03,
+    0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x00, 0x05, 0x12, 0x04, 0x87, 0x03, 0x02,
+    0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x28, 0x02, 0x01, 0x12, 0x04, 0x88, 0x03, 0x02, 0x14, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x01, 0x01, 0x12, 0x04, 0x88, 0x03, 0x09, 0x0f, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x28, 0x02, 0x01, 0x03, 0x12, 0x04, 0x88, 0x03, 0x12, 0x13, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x28, 0x02, 0x01, 0x05, 0x12, 0x04, 0x88, 0x03, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x28, 0x02, 0x02, 0x12, 0x04, 0x89, 0x03, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28,
+    0x02, 0x02, 0x01, 0x12, 0x04, 0x89, 0x03, 0x09, 0x0d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02,
+    0x02, 0x03, 0x12, 0x04, 0x89, 0x03, 0x10, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x02,
+    0x05, 0x12, 0x04, 0x89, 0x03, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x28, 0x02, 0x03, 0x12,
+    0x04, 0x8a, 0x03, 0x02, 0x2c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x03, 0x01, 0x12, 0x04,
+    0x8a, 0x03, 0x14, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x03, 0x03, 0x12, 0x04, 0x8a,
+    0x03, 0x2a, 0x2b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x03, 0x04, 0x12, 0x04, 0x8a, 0x03,
+    0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x28, 0x02, 0x03, 0x06, 0x12, 0x04, 0x8a, 0x03, 0x0b,
+    0x13, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x29, 0x12, 0x06, 0x8d, 0x03, 0x00, 0xa2, 0x03, 0x01, 0x0a,
+    0x0b, 0x0a, 0x03, 0x04, 0x29, 0x01, 0x12, 0x04, 0x8d, 0x03, 0x08, 0x11, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x29, 0x02, 0x00, 0x12, 0x04, 0x99, 0x03, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29,
+    0x02, 0x00, 0x01, 0x12, 0x04, 0x99, 0x03, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02,
+    0x00, 0x03, 0x12, 0x04, 0x99, 0x03, 0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x00,
+    0x06, 0x12, 0x04, 0x99, 0x03, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x29, 0x02, 0x01, 0x12,
+    0x04, 0x9b, 0x03, 0x04, 0x21, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x01, 0x01, 0x12, 0x04,
+    0x9b, 0x03, 0x15, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x01, 0x03, 0x12, 0x04, 0x9b,
+    0x03, 0x1f, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x01, 0x06, 0x12, 0x04, 0x9b, 0x03,
+    0x04, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x29, 0x02, 0x02, 0x12, 0x04, 0x9c, 0x03, 0x04, 0x2c,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x02, 0x01, 0x12, 0x04, 0x9c, 0x03, 0x1a, 0x27, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x02, 0x03, 0x12, 0x04, 0x9c, 0x03, 0x2a, 0x2b, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x29, 0x02, 0x02, 0x06, 0x12, 0x04, 0x9c, 0x03, 0x04, 0x19, 0x0a, 0x0c, 0x0a,
+    0x04, 0x04, 0x29, 0x02, 0x03, 0x12, 0x04, 0x9d, 0x03, 0x04, 0x28, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x29, 0x02, 0x03, 0x01, 0x12, 0x04, 0x9d, 0x03, 0x18, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29,
+    0x02, 0x03, 0x03, 0x12, 0x04, 0x9d, 0x03, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02,
+    0x03, 0x06, 0x12, 0x04, 0x9d, 0x03, 0x04, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x29, 0x02, 0x04,
+    0x12, 0x04, 0x9e, 0x03, 0x04, 0x24, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x04, 0x01, 0x12,
+    0x04, 0x9e, 0x03, 0x16, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x04, 0x03, 0x12, 0x04,
+    0x9e, 0x03, 0x22, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x04, 0x06, 0x12, 0x04, 0x9e,
+    0x03, 0x04, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x29, 0x02, 0x05, 0x12, 0x04, 0x9f, 0x03, 0x04,
+    0x30, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x05, 0x01, 0x12, 0x04, 0x9f, 0x03, 0x1c, 0x2b,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x05, 0x03, 0x12, 0x04, 0x9f, 0x03, 0x2e, 0x2f, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x29, 0x02, 0x05, 0x06, 0x12, 0x04, 0x9f, 0x03, 0x04, 0x1b, 0x0a, 0x0c,
+    0x0a, 0x04, 0x04, 0x29, 0x02, 0x06, 0x12, 0x04, 0xa0, 0x03, 0x04, 0x24, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x29, 0x02, 0x06, 0x01, 0x12, 0x04, 0xa0, 0x03, 0x16, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x29, 0x02, 0x06, 0x03, 0x12, 0x04, 0xa0, 0x03, 0x22, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29,
+    0x02, 0x06, 0x06, 0x12, 0x04, 0xa0, 0x03, 0x04, 0x15, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x29, 0x04,
+    0x00, 0x12, 0x06, 0x8f, 0x03, 0x02, 0x97, 0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x04,
+    0x00, 0x01, 0x12, 0x04, 0x8f, 0x03, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00,
+    0x02, 0x00, 0x12, 0x04, 0x90, 0x03, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00,
+    0x02, 0x00, 0x01, 0x12, 0x04, 0x90, 0x03, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04,
+    0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0x90, 0x03, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29,
+    0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0x91, 0x03, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29,
+    0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0x91, 0x03, 0x04, 0x10, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
+    0x29, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0x91, 0x03, 0x13, 0x14, 0x0a, 0x0e, 0x0a, 0x06,
+    0x04, 0x29, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0x92, 0x03, 0x04, 0x1b, 0x0a, 0x0f, 0x0a, 0x07,
+    0x04, 0x29, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0x92, 0x03, 0x04, 0x16, 0x0a, 0x0f, 0x0a,
+    0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0x92, 0x03, 0x19, 0x1a, 0x0a, 0x0e,
+    0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x03, 0x12, 0x04, 0x93, 0x03, 0x04, 0x19, 0x0a, 0x0f,
+    0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x04, 0x93, 0x03, 0x04, 0x14, 0x0a,
+    0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x04, 0x93, 0x03, 0x17, 0x18,
+    0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x04, 0x12, 0x04, 0x94, 0x03, 0x04, 0x17,
+    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x04, 0x94, 0x03, 0x04,
+    0x12, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12, 0x04, 0x94, 0x03,
+    0x15, 0x16, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x05, 0x12, 0x04, 0x95, 0x03,
+    0x04, 0x1d, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x05, 0x01, 0x12, 0x04, 0x95,
+    0x03, 0x04, 0x18, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x05, 0x02, 0x12, 0x04,
+    0x95, 0x03, 0x1b, 0x1c, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x29, 0x04, 0x00, 0x02, 0x06, 0x12, 0x04,
+    0x96, 0x03, 0x04, 0x17, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x06, 0x01, 0x12,
+    0x04, 0x96, 0x03, 0x04, 0x12, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x29, 0x04, 0x00, 0x02, 0x06, 0x02,
+    0x12, 0x04, 0x96, 0x03, 0x15, 0x16, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x29, 0x08, 0x00, 0x12, 0x06,
+    0x9a, 0x03, 0x02, 0xa1, 0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x29, 0x08, 0x00, 0x01, 0x12,
+    0x04, 0x9a, 0x03, 0x08, 0x11, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2a, 0x12, 0x06, 0xa4, 0x03, 0x00,
+    0xa7, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2a, 0x01, 0x12, 0x04, 0xa4, 0x03, 0x08, 0x18,
+    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2a, 0x02, 0x00, 0x12, 0x04, 0xa5, 0x03, 0x02, 0x17, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x2a, 0x02, 0x00, 0x01, 0x12, 0x04, 0xa5, 0x03, 0x08, 0x12, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x2a, 0x02, 0x00, 0x03, 0x12, 0x04, 0xa5, 0x03, 0x15, 0x16, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x2a, 0x02, 0x00, 0x05, 0x12, 0x04, 0xa5, 0x03, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
+    0x2a, 0x02, 0x01, 0x12, 0x04, 0xa6, 0x03, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a, 0x02,
+    0x01, 0x01, 0x12, 0x04, 0xa6, 0x03, 0x08, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a, 0x02, 0x01,
+    0x03, 0x12, 0x04, 0xa6, 0x03, 0x14, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2a, 0x02, 0x01, 0x05,
+    0x12, 0x04, 0xa6, 0x03, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2b, 0x12, 0x06, 0xa9, 0x03,
+    0x00, 0xac, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2b, 0x01, 0x12, 0x04, 0xa9, 0x03, 0x08,
+    0x1f, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2b, 0x02, 0x00, 0x12, 0x04, 0xaa, 0x03, 0x02, 0x17, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x00, 0x01, 0x12, 0x04, 0xaa, 0x03, 0x08, 0x12, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x2b, 0x02, 0x00, 0x03, 0x12, 0x04, 0xaa, 0x03, 0x15, 0x16, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x2b, 0x02, 0x00, 0x05, 0x12, 0x04, 0xaa, 0x03, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x2b, 0x02, 0x01, 0x12, 0x04, 0xab, 0x03, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b,
+    0x02, 0x01, 0x01, 0x12, 0x04, 0xab, 0x03, 0x08, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02,
+    0x01, 0x03, 0x12, 0x04, 0xab, 0x03, 0x14, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2b, 0x02, 0x01,
+    0x05, 0x12, 0x04, 0xab, 0x03, 0x02, 0x07, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2c, 0x12, 0x06, 0xae,
+    0x03, 0x00, 0xb3, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2c, 0x01, 0x12, 0x04, 0xae, 0x03,
+    0x08, 0x1d, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2c, 0x02, 0x00, 0x12, 0x04, 0xaf, 0x03, 0x02, 0x21,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x00, 0x01, 0x12, 0x04, 0xaf, 0x03, 0x11, 0x1c, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x00, 0x03, 0x12, 0x04, 0xaf, 0x03, 0x1f, 0x20, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x2c, 0x02, 0x00, 0x04, 0x12, 0x04, 0xaf, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x2c, 0x02, 0x00, 0x05, 0x12, 0x04, 0xaf, 0x03, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x2c, 0x02, 0x01, 0x12, 0x04, 0xb0, 0x03, 0x02, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c,
+    0x02, 0x01, 0x01, 0x12, 0x04, 0xb0, 0x03, 0x11, 0x1b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02,
+    0x01, 0x03, 0x12, 0x04, 0xb0, 0x03, 0x1e, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x01,
+    0x04, 0x12, 0x04, 0xb0, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x01, 0x05,
+    0x12, 0x04, 0xb0, 0x03, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2c, 0x02, 0x02, 0x12, 0x04,
+    0xb1, 0x03, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x02, 0x01, 0x12, 0x04, 0xb1,
+    0x03, 0x09, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x02, 0x03, 0x12, 0x04, 0xb1, 0x03,
+    0x15, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x02, 0x05, 0x12, 0x04, 0xb1, 0x03, 0x02,
+    0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2c, 0x02, 0x03, 0x12, 0x04, 0xb2, 0x03, 0x02, 0x29, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x2c, 0x02, 0x03, 0x01, 0x12, 0x04, 0xb2, 0x03, 0x12, 0x24, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x2c, 0x02, 0x03, 0x03, 0x12, 0x04, 0xb2, 0x03, 0x27, 0x28, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x2c, 0x02, 0x03, 0x04, 0x12, 0x04, 0xb2, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x2c, 0x02, 0x03, 0x05, 0x12, 0x04, 0xb2, 0x03, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x02, 0x04,
+    0x2d, 0x12, 0x06, 0xb5, 0x03, 0x00, 0xbe, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2d, 0x01,
+    0x12, 0x04, 0xb5, 0x03, 0x08, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2d, 0x02, 0x00, 0x12, 0x04,
+    0xbc, 0x03, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x00, 0x01, 0x12, 0x04, 0xbc,
+    0x03, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x00, 0x03, 0x12, 0x04, 0xbc, 0x03,
+    0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x00, 0x06, 0x12, 0x04, 0xbc, 0x03, 0x02,
+    0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2d, 0x02, 0x01, 0x12, 0x04, 0xbd, 0x03, 0x02, 0x17, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x2d, 0x02, 0x01, 0x01, 0x12, 0x04, 0xbd, 0x03, 0x08, 0x12, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x2d, 0x02, 0x01, 0x03, 0x12, 0x04, 0xbd, 0x03, 0x15, 0x16, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x2d, 0x02, 0x01, 0x05, 0x12, 0x04, 0xbd, 0x03, 0x02, 0x07, 0x0a, 0x0e, 0x0a, 0x04,
+    0x04, 0x2d, 0x04, 0x00, 0x12, 0x06, 0xb6, 0x03, 0x02, 0xba, 0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x2d, 0x04, 0x00, 0x01, 0x12, 0x04, 0xb6, 0x03, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04,
+    0x2d, 0x04, 0x00, 0x02, 0x00, 0x12, 0x04, 0xb7, 0x03, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
+    0x2d, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xb7, 0x03, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07,
+    0x04, 0x2d, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0xb7, 0x03, 0x17, 0x18, 0x0a, 0x0e, 0x0a,
+    0x06, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0xb8, 0x03, 0x04, 0x15, 0x0a, 0x0f, 0x0a,
+    0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xb8, 0x03, 0x04, 0x10, 0x0a, 0x0f,
+    0x0a, 0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0xb8, 0x03, 0x13, 0x14, 0x0a,
+    0x0e, 0x0a, 0x06, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0xb9, 0x03, 0x04, 0x1d, 0x0a,
+    0x0f, 0x0a, 0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0xb9, 0x03, 0x04, 0x18,
+    0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2d, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0xb9, 0x03, 0x1b,
+    0x1c, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2e, 0x12, 0x06, 0xc0, 0x03, 0x00, 0xc9, 0x03, 0x01, 0x0a,
+    0x0b, 0x0a, 0x03, 0x04, 0x2e, 0x01, 0x12, 0x04, 0xc0, 0x03, 0x08, 0x14, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x2e, 0x02, 0x00, 0x12, 0x04, 0xc7, 0x03, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2e,
+    0x02, 0x00, 0x01, 0x12, 0x04, 0xc7, 0x03, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2e, 0x02,
+    0x00, 0x03, 0x12, 0x04, 0xc7, 0x03, 0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2e, 0x02, 0x00,
+    0x06, 0x12, 0x04, 0xc7, 0x03, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2e, 0x02, 0x01, 0x12,
+    0x04, 0xc8, 0x03, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2e, 0x02, 0x01, 0x01, 0x12, 0x04,
+    0xc8, 0x03, 0x08, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2e, 0x02, 0x01, 0x03, 0x12, 0x04, 0xc8,
+    0x03, 0x14, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2e, 0x02, 0x01, 0x05, 0x12, 0x04, 0xc8, 0x03,
+    0x02, 0x07, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x2e, 0x04, 0x00, 0x12, 0x06, 0xc1, 0x03, 0x02, 0xc5,
+    0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2e, 0x04, 0x00, 0x01, 0x12, 0x04, 0xc1, 0x03, 0x07,
+    0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x00, 0x12, 0x04, 0xc2, 0x03, 0x04,
+    0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x04, 0xc2, 0x03,
+    0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x04, 0xc2,
+    0x03, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x01, 0x12, 0x04, 0xc3,
+    0x03, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04,
+    0xc3, 0x03, 0x04, 0x10, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12,
+    0x04, 0xc3, 0x03, 0x13, 0x14, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x02, 0x12,
+    0x04, 0xc4, 0x03, 0x04, 0x1d, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x02, 0x01,
+    0x12, 0x04, 0xc4, 0x03, 0x04, 0x18, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x2e, 0x04, 0x00, 0x02, 0x02,
+    0x02, 0x12, 0x04, 0xc4, 0x03, 0x1b, 0x1c, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x2f, 0x12, 0x06, 0xcb,
+    0x03, 0x00, 0xd0, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x2f, 0x01, 0x12, 0x04, 0xcb, 0x03,
+    0x08, 0x19, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2f, 0x02, 0x00, 0x12, 0x04, 0xcc, 0x03, 0x02, 0x28,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x00, 0x01, 0x12, 0x04, 0xcc, 0x03, 0x18, 0x23, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x00, 0x03, 0x12, 0x04, 0xcc, 0x03, 0x26, 0x27, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x2f, 0x02, 0x00, 0x04, 0x12, 0x04, 0xcc, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x2f, 0x02, 0x00, 0x06, 0x12, 0x04, 0xcc, 0x03, 0x0b, 0x17, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x2f, 0x02, 0x01, 0x12, 0x04, 0xcd, 0x03, 0x02, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f,
+    0x02, 0x01, 0x01, 0x12, 0x04, 0xcd, 0x03, 0x18, 0x22, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02,
+    0x01, 0x03, 0x12, 0x04, 0xcd, 0x03, 0x25, 0x26, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x01,
+    0x04, 0x12, 0x04, 0xcd, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x01, 0x06,
+    0x12, 0x04, 0xcd, 0x03, 0x0b, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2f, 0x02, 0x02, 0x12, 0x04,
+    0xce, 0x03, 0x02, 0x21, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x02, 0x01, 0x12, 0x04, 0xce,
+    0x03, 0x09, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x02, 0x03, 0x12, 0x04, 0xce, 0x03,
+    0x1f, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x02, 0x05, 0x12, 0x04, 0xce, 0x03, 0x02,
+    0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x2f, 0x02, 0x03, 0x12, 0x04, 0xcf, 0x03, 0x02, 0x29, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x2f, 0x02, 0x03, 0x01, 0x12, 0x04, 0xcf, 0x03, 0x12, 0x24, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x2f, 0x02, 0x03, 0x03, 0x12, 0x04, 0xcf, 0x03, 0x27, 0x28, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x2f, 0x02, 0x03, 0x04, 0x12, 0x04, 0xcf, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x2f, 0x02, 0x03, 0x05, 0x12, 0x04, 0xcf, 0x03, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x02, 0x04,
+    0x30, 0x12, 0x06, 0xd2, 0x03, 0x00, 0xd6, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x30, 0x01,
+    0x12, 0x04, 0xd2, 0x03, 0x08, 0x1b, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x30, 0x02, 0x00, 0x12, 0x04,
+    0xd3, 0x03, 0x02, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02, 0x00, 0x01, 0x12, 0x04, 0xd3,
+    0x03, 0x13, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02, 0x00, 0x03, 0x12, 0x04, 0xd3, 0x03,
+    0x1c, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02, 0x00, 0x06, 0x12, 0x04, 0xd3, 0x03, 0x02,
+    0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x30, 0x02, 0x01, 0x12, 0x04, 0xd4, 0x03, 0x02, 0x31, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02, 0x01, 0x01, 0x12, 0x04, 0xd4, 0x03, 0x12, 0x2c, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x30, 0x02, 0x01, 0x03, 0x12, 0x04, 0xd4, 0x03, 0x2f, 0x30, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x30, 0x02, 0x01, 0x04, 0x12, 0x04, 0xd4, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x30, 0x02, 0x01, 0x05, 0x12, 0x04, 0xd4, 0x03, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
+    0x30, 0x02, 0x02, 0x12, 0x04, 0xd5, 0x03, 0x02, 0x32, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02,
+    0x02, 0x01, 0x12, 0x04, 0xd5, 0x03, 0x1c, 0x2d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02, 0x02,
+    0x03, 0x12, 0x04, 0xd5, 0x03, 0x30, 0x31, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02, 0x02, 0x04,
+    0x12, 0x04, 0xd5, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x30, 0x02, 0x02, 0x06, 0x12,
+    0x04, 0xd5, 0x03, 0x0b, 0x1b, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x31, 0x12, 0x06, 0xd8, 0x03, 0x00,
+    0xde, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x31, 0x01, 0x12, 0x04, 0xd8, 0x03, 0x08, 0x19,
+    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x31, 0x02, 0x00, 0x12, 0x04, 0xd9, 0x03, 0x02, 0x1e, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x31, 0x02, 0x00, 0x01, 0x12, 0x04, 0xd9, 0x03, 0x13, 0x19, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x31, 0x02, 0x00, 0x03, 0x12, 0x04, 0xd9, 0x03, 0x1c, 0x1d, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x31, 0x02, 0x00, 0x06, 0x12, 0x04, 0xd9, 0x03, 0x02, 0x12, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
+    0x31, 0x02, 0x01, 0x12, 0x04, 0xda, 0x03, 0x02, 0x31, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02,
+    0x01, 0x01, 0x12, 0x04, 0xda, 0x03, 0x12, 0x2c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x01,
+    0x03, 0x12, 0x04, 0xda, 0x03, 0x2f, 0x30, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x01, 0x04,
+    0x12, 0x04, 0xda, 0x03, 0x02, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x01, 0x05, 0x12,
+    0x04, 0xda, 0x03, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x31, 0x02, 0x02, 0x12, 0x04, 0xdb,
+    0x03, 0x02, 0x32, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x02, 0x01, 0x12, 0x04, 0xdb, 0x03,
+    0x1c, 0x2d, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x02, 0x03, 0x12, 0x04, 0xdb, 0x03, 0x30,
+    0x31, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x02, 0x04, 0x12, 0x04, 0xdb, 0x03, 0x02, 0x0a,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x02, 0x06, 0x12, 0x04, 0xdb, 0x03, 0x0b, 0x1b, 0x0a,
+    0x0c, 0x0a, 0x04, 0x04, 0x31, 0x02, 0x03, 0x12, 0x04, 0xdc, 0x03, 0x02, 0x1f, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x31, 0x02, 0x03, 0x01, 0x12, 0x04, 0xdc, 0x03, 0x09, 0x1a, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x31, 0x02, 0x03, 0x03, 0x12, 0x04, 0xdc, 0x03, 0x1d, 0x1e, 0x0a, 0x0d, 0x0a, 0x05, 0x04,
+    0x31, 0x02, 0x03, 0x05, 0x12, 0x04, 0xdc, 0x03, 0x02, 0x08, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x31,
+    0x02, 0x04, 0x12, 0x04, 0xdd, 0x03, 0x02, 0x28, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x04,
+    0x01, 0x12, 0x04, 0xdd, 0x03, 0x13, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x04, 0x03,
+    0x12, 0x04, 0xdd, 0x03, 0x26, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x31, 0x02, 0x04, 0x06, 0x12,
+    0x04, 0xdd, 0x03, 0x02, 0x12, 0x0a, 0x0c, 0x0a, 0x02, 0x04, 0x32, 0x12, 0x06, 0xe0, 0x03, 0x00,
+    0xf0, 0x03, 0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x04, 0x32, 0x01, 0x12, 0x04, 0xe0, 0x03, 0x08, 0x18,
+    0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x32, 0x02, 0x00, 0x12, 0x04, 0xe9, 0x03, 0x02, 0x10, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x32, 0x02, 0x00, 0x01, 0x12, 0x04, 0xe9, 0x03, 0x07, 0x0b, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x32, 0x02, 0x00, 0x03, 0x12, 0x04, 0xe9, 0x03, 0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x05,
+    0x04, 0x32, 0x02, 0x00, 0x06, 0x12, 0x04, 0xe9, 0x03, 0x02, 0x06, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
+    0x32, 0x02, 0x01, 0x12, 0x04, 0xeb, 0x03, 0x04, 0x21, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02,
+    0x01, 0x01, 0x12, 0x04, 0xeb, 0x03, 0x15, 0x1c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02, 0x01,
+    0x03, 0x12, 0x04, 0xeb, 0x03, 0x1f, 0x20, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02, 0x01, 0x06,
+    0x12, 0x04, 0xeb, 0x03, 0x04, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x32, 0x02, 0x02, 0x12, 0x04,
+    0xec, 0x03, 0x04, 0x2c, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02, 0x02, 0x01, 0x12, 0x04, 0xec,
+    0x03, 0x1a, 0x27, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02, 0x02, 0x03, 0x12, 0x04, 0xec, 0x03,
+    0x2a, 0x2b, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02, 0x02, 0x06, 0x12, 0x04, 0xec, 0x03, 0x04,
+    0x19, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x32, 0x02, 0x03, 0x12, 0x04, 0xed, 0x03, 0x04, 0x30, 0x0a,
+    0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02, 0x03, 0x01, 0x12, 0x04, 0xed, 0x03, 0x1c, 0x2b, 0x0a, 0x0d,
+    0x0a, 0x05, 0x04, 0x32, 0x02, 0x03, 0x03, 0x12, 0x04, 0xed, 0x03, 0x2e, 0x2f, 0x0a, 0x0d, 0x0a,
+    0x05, 0x04, 0x32, 0x02, 0x03, 0x06, 0x12, 0x04, 0xed, 0x03, 0x04, 0x1b, 0x0a, 0x0c, 0x0a, 0x04,
+    0x04, 0x32, 0x02, 0x04, 0x12, 0x04, 0xee, 0x03, 0x04, 0x24, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32,
+    0x02, 0x04, 0x01, 0x12, 0x04, 0xee, 0x03, 0x16, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02,
+    0x04, 0x03, 0x12, 0x04, 0xee, 0x03, 0x22, 0x23, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x02, 0x04,
+    0x06, 0x12, 0x04, 0xee, 0x03, 0x04, 0x15, 0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x32, 0x04, 0x00, 0x12,
+    0x06, 0xe1, 0x03, 0x02, 0xe7, 0x03, 0x03, 0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x04, 0x00, 0x01,
+    0x12, 0x04, 0xe1, 0x03, 0x07, 0x0b, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x32, 0x04, 0x00, 0x02, 0x00,
+    0x12, 0x04, 0xe2, 0x03, 0x04, 0x19, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x32, 0x04, 0x00, 0x02, 0x00,
+    0x01, 0x12, 0x04, 0xe2, 0x03, 0x04, 0x14, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x32, 0x04, 0x00, 0x02,
+    0x00, 0x02, 0x12, 0x04, 0xe2, 0x03, 0x17, 0x18, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x32, 0x04, 0x00,
+    0x02, 0x01, 0x12, 0x04, 0xe3, 0x03, 0x04, 0x15, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x32, 0x04, 0x00,
+    0x02, 0x01, 0x01, 0x12, 0x04, 0xe3, 0x03, 0x04, 0x10, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x32, 0x04,
+    0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0xe3, 0x03, 0x13, 0x14, 0x0a, 0x0e, 0x0a, 0x06, 0x04, 0x32,
+    0x04, 0x00, 0x02, 0x02, 0x12, 0x04, 0xe4, 0x03, 0x04, 0x1b, 0x0a, 0x0f, 0x0a, 0x07, 0x04, 0x32,
+    0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0xe4, 0x03, 0x04, 0x16, 0x0a, 0x0f, 0x0a, 0x07, 0x04,
+    0x32, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0xe4, 0x03, 0x19, 0x1a, 0x0a, 0x0e, 0x0a, 0x06,
+    0x04, 0x32, 0x04, 0x00, 0x02, 0x03, 0x12, 0x04, 0xe5, 0x03, 0x04, 0x1d, 0x0a, 0x0f, 0x0a, 0x07,
+    0x04, 0x32, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x04, 0xe5, 0x03, 0x04, 0x18, 0x0a, 0x0f, 0x0a,
+    0x07, 0x04, 0x32, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x04, 0xe5, 0x03, 0x1b, 0x1c, 0x0a, 0x0e,
+    0x0a, 0x06, 0x04, 0x32, 0x04, 0x00, 0x02, 0x04, 0x12, 0x04, 0xe6, 0x03, 0x04, 0x17, 0x0a, 0x0f,
+    0x0a, 0x07, 0x04, 0x32, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x04, 0xe6, 0x03, 0x04, 0x12, 0x0a,
+    0x0f, 0x0a, 0x07, 0x04, 0x32, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12, 0x04, 0xe6, 0x03, 0x15, 0x16,
+    0x0a, 0x0e, 0x0a, 0x04, 0x04, 0x32, 0x08, 0x00, 0x12, 0x06, 0xea, 0x03, 0x02, 0xef, 0x03, 0x03,
+    0x0a, 0x0d, 0x0a, 0x05, 0x04, 0x32, 0x08, 0x00, 0x01, 0x12, 0x04, 0xea, 0x03, 0x08, 0x11, 0x0a,
+    0x0c, 0x0a, 0x02, 0x05, 0x00, 0x12, 0x06, 0xcd, 0x02, 0x00, 0xdd, 0x02, 0x01, 0x0a, 0x0b, 0x0a,
+    0x03, 0x05, 0x00, 0x01, 0x12, 0x04, 0xcd, 0x02, 0x05, 0x0e, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00,
+    0x02, 0x00, 0x12, 0x04, 0xce, 0x02, 0x02, 0x1d, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x00,
+    0x01, 0x12, 0x04, 0xce, 0x02, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x00, 0x02,
+    0x12, 0x04, 0xce, 0x02, 0x1b, 0x1c, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x01, 0x12, 0x04,
+    0xcf, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x01, 0x12, 0x04, 0xcf,
+    0x02, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x02, 0x12, 0x04, 0xcf, 0x02,
+    0x14, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x02, 0x12, 0x04, 0xd0, 0x02, 0x02, 0x14,
+    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x02, 0x01, 0x12, 0x04, 0xd0, 0x02, 0x02, 0x0f, 0x0a,
+    0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x02, 0x02, 0x12, 0x04, 0xd0, 0x02, 0x12, 0x13, 0x0a, 0x0c,
+    0x0a, 0x04, 0x05, 0x00, 0x02, 0x03, 0x12, 0x04, 0xd1, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05,
+    0x05, 0x00, 0x02, 0x03, 0x01, 0x12, 0x04, 0xd1, 0x02, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x05,
+    0x00, 0x02, 0x03, 0x02, 0x12, 0x04, 0xd1, 0x02, 0x13, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00,
+    0x02, 0x04, 0x12, 0x04, 0xd2, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04,
+    0x01, 0x12, 0x04, 0xd2, 0x02, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04, 0x02,
+    0x12, 0x04, 0xd2, 0x02, 0x13, 0x15, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x05, 0x12, 0x04,
+    0xd3, 0x02, 0x02, 0x15, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05, 0x01, 0x12, 0x04, 0xd3,
+    0x02, 0x02, 0x10, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05, 0x02, 0x12, 0x04, 0xd3, 0x02,
+    0x13, 0x14, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x06, 0x12, 0x04, 0xd4, 0x02, 0x02, 0x16,
+    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x06, 0x01, 0x12, 0x04, 0xd4, 0x02, 0x02, 0x11, 0x0a,
+    0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x06, 0x02, 0x12, 0x04, 0xd4, 0x02, 0x14, 0x15, 0x0a, 0x0c,
+    0x0a, 0x04, 0x05, 0x00, 0x02, 0x07, 0x12, 0x04, 0xd5, 0x02, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05,
+    0x05, 0x00, 0x02, 0x07, 0x01, 0x12, 0x04, 0xd5, 0x02, 0x02, 0x11, 0x0a, 0x0d, 0x0a, 0x05, 0x05,
+    0x00, 0x02, 0x07, 0x02, 0x12, 0x04, 0xd5, 0x02, 0x14, 0x16, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00,
+    0x02, 0x08, 0x12, 0x04, 0xd6, 0x02, 0x02, 0x19, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x08,
+    0x01, 0x12, 0x04, 0xd6, 0x02, 0x02, 0x14, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x08, 0x02,
+    0x12, 0x04, 0xd6, 0x02, 0x17, 0x18, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x09, 0x12, 0x04,
+    0xd7, 0x02, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x09, 0x01, 0x12, 0x04, 0xd7,
+    0x02, 0x02, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x09, 0x02, 0x12, 0x04, 0xd7, 0x02,
+    0x16, 0x17, 0x0a, 0x2b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0a, 0x12, 0x04, 0xd8, 0x02, 0x02, 0x18,
+    0x22, 0x1d, 0x20, 0x60, 0x7b, 0x20, 0x69, 0x74, 0x65, 0x6d, 0x73, 0x3a, 0x20, 0x42, 0x6f, 0x78,
+    0x3c, 0x4d, 0x6f, 0x76, 0x65, 0x54, 0x79, 0x70, 0x65, 0x3e, 0x20, 0x7d, 0x60, 0x2c, 0x0a, 0x0a,
+    0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0a, 0x01, 0x12, 0x04, 0xd8, 0x02, 0x02, 0x13, 0x0a, 0x0d,
+    0x0a, 0x05, 0x05, 0x00, 0x02, 0x0a, 0x02, 0x12, 0x04, 0xd8, 0x02, 0x16, 0x17, 0x0a, 0x22, 0x0a,
+    0x04, 0x05, 0x00, 0x02, 0x0b, 0x12, 0x04, 0xd9, 0x02, 0x02, 0x18, 0x22, 0x14, 0x20, 0x60, 0x28,
+    0x4d, 0x6f, 0x76, 0x65, 0x53, 0x74, 0x72, 0x75, 0x63, 0x74, 0x54, 0x61, 0x67, 0x29, 0x60, 0x2c,
+    0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0b, 0x01, 0x12, 0x04, 0xd9, 0x02, 0x02, 0x13,
+    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0b, 0x02, 0x12, 0x04, 0xd9, 0x02, 0x16, 0x17, 0x0a,
+    0x22, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0c, 0x12, 0x04, 0xda, 0x02, 0x02, 0x24, 0x22, 0x14, 0x20,
+    0x60, 0x7b, 0x20, 0x69, 0x6e, 0x64, 0x65, 0x78, 0x3a, 0x20, 0x75, 0x31, 0x36, 0x20, 0x7d, 0x60,
+    0x60, 0x2c, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0c, 0x01, 0x12, 0x04, 0xda, 0x02,
+    0x02, 0x1f, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0c, 0x02, 0x12, 0x04, 0xda, 0x02, 0x22,
+    0x23, 0x0a, 0x37, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0d, 0x12, 0x04, 0xdb, 0x02, 0x02, 0x1c, 0x22,
+    0x29, 0x20, 0x60, 0x7b, 0x20, 0x6d, 0x75, 0x74, 0x61, 0x62, 0x6c, 0x65, 0x3a, 0x20, 0x62, 0x6f,
+    0x6f, 0x6c, 0x2c, 0x20, 0x74, 0x6f, 0x3a, 0x20, 0x42, 0x6f, 0x78, 0x3c, 0x4d, 0x6f, 0x76, 0x65,
+    0x54, 0x79, 0x70, 0x65, 0x3e, 0x20, 0x7d, 0x60, 0x2c, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00,
+    0x02, 0x0d, 0x01, 0x12, 0x04, 0xdb, 0x02, 0x02, 0x16, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02,
+    0x0d, 0x02, 0x12, 0x04, 0xdb, 0x02, 0x19, 0x1b, 0x0a, 0x1b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0e,
+    0x12, 0x04, 0xdc, 0x02, 0x02, 0x1d, 0x22, 0x0d, 0x20, 0x60, 0x28, 0x53, 0x74, 0x72, 0x69, 0x6e,
+    0x67, 0x29, 0x60, 0x2c, 0x0a, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0e, 0x01, 0x12, 0x04,
+    0xdc, 0x02, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0e, 0x02, 0x12, 0x04, 0xdc,
+    0x02, 0x1a, 0x1c, 0x0a, 0x0c, 0x0a, 0x02, 0x05, 0x01, 0x12, 0x06, 0xf0, 0x02, 0x00, 0xf6, 0x02,
+    0x01, 0x0a, 0x0b, 0x0a, 0x03, 0x05, 0x01, 0x01, 0x12, 0x04, 0xf0, 0x02, 0x05, 0x10, 0x0a, 0x0c,
+    0x0a, 0x04, 0x05, 0x01, 0x02, 0x00, 0x12, 0x04, 0xf1, 0x02, 0x02, 0x1f, 0x0a, 0x0d, 0x0a, 0x05,
+    0x05, 0x01, 0x02, 0x00, 0x01, 0x12, 0x04, 0xf1, 0x02, 0x02, 0x1a, 0x0a, 0x0d, 0x0a, 0x05, 0x05,
+    0x01, 0x02, 0x00, 0x02, 0x12, 0x04, 0xf1, 0x02, 0x1d, 0x1e, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x01,
+    0x02, 0x01, 0x12, 0x04, 0xf2, 0x02, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x01,
+    0x01, 0x12, 0x04, 0xf2, 0x02, 0x02, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x01, 0x02,
+    0x12, 0x04, 0xf2, 0x02, 0x16, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x01, 0x02, 0x02, 0x12, 0x04,
+    0xf3, 0x02, 0x02, 0x18, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x02, 0x01, 0x12, 0x04, 0xf3,
+    0x02, 0x02, 0x13, 0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x02, 0x02, 0x12, 0x04, 0xf3, 0x02,
+    0x16, 0x17, 0x0a, 0x0c, 0x0a, 0x04, 0x05, 0x01, 0x02, 0x03, 0x12, 0x04, 0xf4, 0x02, 0x02, 0x19,
+    0x0a, 0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x03, 0x01, 0x12, 0x04, 0xf4, 0x02, 0x02, 0x14, 0x0a,
+    0x0d, 0x0a, 0x05, 0x05, 0x01, 0x02, 0x03, 0x02, 0x12, 0x04, 0xf4, 0x02, 0x17, 0x18, 0x0a, 0x0c,
+    0x0a, 0x04, 0x05, 0x01, 0x02, 0x04, 0x12, 0x04, 0xf5, 0x02, 0x02, 0x17, 0x0a, 0x0d, 0x0a, 0x05,
+    0x05, 0x01, 0x02, 0x04, 0x01, 0x12, 0x04, 0xf5, 0x02, 0x02, 0x12, 0x0a, 0x0d, 0x0a, 0x05, 0x05,
+    0x01, 0x02, 0x04, 0x02, 0x12, 0x04, 0xf5, 0x02, 0x15, 0x16, 0x0a, 0x4e, 0x0a, 0x01, 0x0c, 0x12,
+    0x03, 0x03, 0x00, 0x12, 0x32, 0x44, 0x20, 0x43, 0x6f, 0x70, 0x79, 0x72, 0x69, 0x67, 0x68, 0x74,
+    0x20, 0xc2, 0xa9, 0x20, 0x41, 0x70, 0x74, 0x6f, 0x73, 0x20, 0x46, 0x6f, 0x75, 0x6e, 0x64, 0x61,
+    0x74, 0x69, 0x6f, 0x6e, 0x0a, 0x20, 0x53, 0x50, 0x44, 0x58, 0x2d, 0x4c, 0x69, 0x63, 0x65, 0x6e,
+    0x73, 0x65, 0x2d, 0x49, 0x64, 0x65, 0x6e, 0x74, 0x69, 0x66, 0x69, 0x65, 0x72, 0x3a, 0x20, 0x41,
+    0x70, 0x61, 0x63, 0x68, 0x65, 0x2d, 0x32, 0x2e, 0x30, 0x0a, 0x62, 0x06, 0x70, 0x72, 0x6f, 0x74,
+    0x6f, 0x33,
 ];
 include!("aptos.transaction.v1.serde.rs");
 // @@protoc_insertion_point(module)
@@ -29,6 +29,12 @@ impl serde::Serialize for AccountSignature {
                 account_signature::Signature::MultiEd25519(v) => {
                     struct_ser.serialize_field("multiEd25519", v)?;
                 }
+                account_signature::Signature::Secp256k1Ecdsa(v) => {
+                    struct_ser.serialize_field("secp256k1Ecdsa", v)?;
+                }
+                account_signature::Signature::MultiKey(v) => {
+                    struct_ser.serialize_field("multiKey", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -45,6 +51,10 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
             "ed25519",
             "multi_ed25519",
             "multiEd25519",
+            "secp256k1_ecdsa",
+            "secp256k1Ecdsa",
+            "multi_key",
+            "multiKey",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -52,6 +62,8 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
             Type,
             Ed25519,
             MultiEd25519,
+            Secp256k1Ecdsa,
+            MultiKey,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -76,6 +88,8 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                             "type" => Ok(GeneratedField::Type),
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" | "multi_ed25519" => Ok(GeneratedField::MultiEd25519),
+                            "secp256k1Ecdsa" | "secp256k1_ecdsa" => Ok(GeneratedField::Secp256k1Ecdsa),
+                            "multiKey" | "multi_key" => Ok(GeneratedField::MultiKey),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -117,6 +131,20 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                                 return Err(serde::de::Error::duplicate_field("multiEd25519"));
                             }
                             signature__ = map.next_value::<::std::option::Option<_>>()?.map(account_signature::Signature::MultiEd25519)
+;
+                        }
+                        GeneratedField::Secp256k1Ecdsa => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secp256k1Ecdsa"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(account_signature::Signature::Secp256k1Ecdsa)
+;
+                        }
+                        GeneratedField::MultiKey => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("multiKey"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(account_signature::Signature::MultiKey)
 ;
                         }
                     }
@@ -140,6 +168,8 @@ impl serde::Serialize for account_signature::Type {
             Self::Unspecified => "TYPE_UNSPECIFIED",
             Self::Ed25519 => "TYPE_ED25519",
             Self::MultiEd25519 => "TYPE_MULTI_ED25519",
+            Self::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+            Self::MultiKey => "TYPE_MULTI_KEY",
         };
         serializer.serialize_str(variant)
     }
@@ -154,6 +184,8 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
             "TYPE_UNSPECIFIED",
             "TYPE_ED25519",
             "TYPE_MULTI_ED25519",
+            "TYPE_SECP256K1_ECDSA",
+            "TYPE_MULTI_KEY",
         ];
 
         struct GeneratedVisitor;
@@ -199,6 +231,385 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
                     "TYPE_UNSPECIFIED" => Ok(account_signature::Type::Unspecified),
                     "TYPE_ED25519" => Ok(account_signature::Type::Ed25519),
                     "TYPE_MULTI_ED25519" => Ok(account_signature::Type::MultiEd25519),
+                    "TYPE_SECP256K1_ECDSA" => Ok(account_signature::Type::Secp256k1Ecdsa),
+                    "TYPE_MULTI_KEY" => Ok(account_signature::Type::MultiKey),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for AnyPublicKey {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.r#type != 0 {
+            len += 1;
+        }
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.AnyPublicKey", len)?;
+        if self.r#type != 0 {
+            let v = any_public_key::Type::from_i32(self.r#type)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.r#type)))?;
+            struct_ser.serialize_field("type", &v)?;
+        }
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field("publicKey", pbjson::private::base64::encode(&self.public_key).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for AnyPublicKey {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "type",
+            "public_key",
+            "publicKey",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Type,
+            PublicKey,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "type" => Ok(GeneratedField::Type),
+                            "publicKey" | "public_key" => Ok(GeneratedField::PublicKey),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = AnyPublicKey;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.AnyPublicKey")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<AnyPublicKey, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut r#type__ = None;
+                let mut public_key__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Type => {
+                            if r#type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("type"));
+                            }
+                            r#type__ = Some(map.next_value::<any_public_key::Type>()? as i32);
+                        }
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(AnyPublicKey {
+                    r#type: r#type__.unwrap_or_default(),
+                    public_key: public_key__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.AnyPublicKey", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for any_public_key::Type {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "TYPE_UNSPECIFIED",
+            Self::Ed25519 => "TYPE_ED25519",
+            Self::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for any_public_key::Type {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "TYPE_UNSPECIFIED",
+            "TYPE_ED25519",
+            "TYPE_SECP256K1_ECDSA",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = any_public_key::Type;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(any_public_key::Type::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(any_public_key::Type::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "TYPE_UNSPECIFIED" => Ok(any_public_key::Type::Unspecified),
+                    "TYPE_ED25519" => Ok(any_public_key::Type::Ed25519),
+                    "TYPE_SECP256K1_ECDSA" => Ok(any_public_key::Type::Secp256k1Ecdsa),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for AnySignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.r#type != 0 {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.AnySignature", len)?;
+        if self.r#type != 0 {
+            let v = any_signature::Type::from_i32(self.r#type)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.r#type)))?;
+            struct_ser.serialize_field("type", &v)?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for AnySignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "type",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Type,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "type" => Ok(GeneratedField::Type),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = AnySignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.AnySignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<AnySignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut r#type__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Type => {
+                            if r#type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("type"));
+                            }
+                            r#type__ = Some(map.next_value::<any_signature::Type>()? as i32);
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(AnySignature {
+                    r#type: r#type__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.AnySignature", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for any_signature::Type {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "TYPE_UNSPECIFIED",
+            Self::Ed25519 => "TYPE_ED25519",
+            Self::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for any_signature::Type {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "TYPE_UNSPECIFIED",
+            "TYPE_ED25519",
+            "TYPE_SECP256K1_ECDSA",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = any_signature::Type;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(any_signature::Type::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(any_signature::Type::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "TYPE_UNSPECIFIED" => Ok(any_signature::Type::Unspecified),
+                    "TYPE_ED25519" => Ok(any_signature::Type::Ed25519),
+                    "TYPE_SECP256K1_ECDSA" => Ok(any_signature::Type::Secp256k1Ecdsa),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -1798,21 +2209,184 @@ impl<'de> serde::Deserialize<'de> for EventKey {
                                 Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
                             ;
                         }
-                        GeneratedField::AccountAddress => {
-                            if account_address__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("accountAddress"));
+                        GeneratedField::AccountAddress => {
+                            if account_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("accountAddress"));
+                            }
+                            account_address__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(EventKey {
+                    creation_number: creation_number__.unwrap_or_default(),
+                    account_address: account_address__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.EventKey", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for FeePayerSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.sender.is_some() {
+            len += 1;
+        }
+        if !self.secondary_signer_addresses.is_empty() {
+            len += 1;
+        }
+        if !self.secondary_signers.is_empty() {
+            len += 1;
+        }
+        if !self.fee_payer_address.is_empty() {
+            len += 1;
+        }
+        if self.fee_payer_signer.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.FeePayerSignature", len)?;
+        if let Some(v) = self.sender.as_ref() {
+            struct_ser.serialize_field("sender", v)?;
+        }
+        if !self.secondary_signer_addresses.is_empty() {
+            struct_ser.serialize_field("secondarySignerAddresses", &self.secondary_signer_addresses)?;
+        }
+        if !self.secondary_signers.is_empty() {
+            struct_ser.serialize_field("secondarySigners", &self.secondary_signers)?;
+        }
+        if !self.fee_payer_address.is_empty() {
+            struct_ser.serialize_field("feePayerAddress", &self.fee_payer_address)?;
+        }
+        if let Some(v) = self.fee_payer_signer.as_ref() {
+            struct_ser.serialize_field("feePayerSigner", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for FeePayerSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sender",
+            "secondary_signer_addresses",
+            "secondarySignerAddresses",
+            "secondary_signers",
+            "secondarySigners",
+            "fee_payer_address",
+            "feePayerAddress",
+            "fee_payer_signer",
+            "feePayerSigner",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Sender,
+            SecondarySignerAddresses,
+            SecondarySigners,
+            FeePayerAddress,
+            FeePayerSigner,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sender" => Ok(GeneratedField::Sender),
+                            "secondarySignerAddresses" | "secondary_signer_addresses" => Ok(GeneratedField::SecondarySignerAddresses),
+                            "secondarySigners" | "secondary_signers" => Ok(GeneratedField::SecondarySigners),
+                            "feePayerAddress" | "fee_payer_address" => Ok(GeneratedField::FeePayerAddress),
+                            "feePayerSigner" | "fee_payer_signer" => Ok(GeneratedField::FeePayerSigner),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = FeePayerSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.FeePayerSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<FeePayerSignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sender__ = None;
+                let mut secondary_signer_addresses__ = None;
+                let mut secondary_signers__ = None;
+                let mut fee_payer_address__ = None;
+                let mut fee_payer_signer__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Sender => {
+                            if sender__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sender"));
+                            }
+                            sender__ = map.next_value()?;
+                        }
+                        GeneratedField::SecondarySignerAddresses => {
+                            if secondary_signer_addresses__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secondarySignerAddresses"));
+                            }
+                            secondary_signer_addresses__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SecondarySigners => {
+                            if secondary_signers__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secondarySigners"));
+                            }
+                            secondary_signers__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FeePayerAddress => {
+                            if fee_payer_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayerAddress"));
+                            }
+                            fee_payer_address__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FeePayerSigner => {
+                            if fee_payer_signer__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayerSigner"));
                             }
-                            account_address__ = Some(map.next_value()?);
+                            fee_payer_signer__ = map.next_value()?;
                         }
                     }
                 }
-                Ok(EventKey {
-                    creation_number: creation_number__.unwrap_or_default(),
-                    account_address: account_address__.unwrap_or_default(),
+                Ok(FeePayerSignature {
+                    sender: sender__,
+                    secondary_signer_addresses: secondary_signer_addresses__.unwrap_or_default(),
+                    secondary_signers: secondary_signers__.unwrap_or_default(),
+                    fee_payer_address: fee_payer_address__.unwrap_or_default(),
+                    fee_payer_signer: fee_payer_signer__,
                 })
             }
         }
-        deserializer.deserialize_struct("aptos.transaction.v1.EventKey", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("aptos.transaction.v1.FeePayerSignature", FIELDS, GeneratedVisitor)
     }
 }
 impl serde::Serialize for GenesisTransaction {
@@ -4135,6 +4709,156 @@ impl<'de> serde::Deserialize<'de> for MultiEd25519Signature {
         deserializer.deserialize_struct("aptos.transaction.v1.MultiEd25519Signature", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for MultiKeySignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_keys.is_empty() {
+            len += 1;
+        }
+        if !self.signatures.is_empty() {
+            len += 1;
+        }
+        if self.signatures_required != 0 {
+            len += 1;
+        }
+        if !self.public_key_indices.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.MultiKeySignature", len)?;
+        if !self.public_keys.is_empty() {
+            struct_ser.serialize_field("publicKeys", &self.public_keys)?;
+        }
+        if !self.signatures.is_empty() {
+            struct_ser.serialize_field("signatures", &self.signatures)?;
+        }
+        if self.signatures_required != 0 {
+            struct_ser.serialize_field("signaturesRequired", &self.signatures_required)?;
+        }
+        if !self.public_key_indices.is_empty() {
+            struct_ser.serialize_field("publicKeyIndices", &self.public_key_indices)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for MultiKeySignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "public_keys",
+            "publicKeys",
+            "signatures",
+            "signatures_required",
+            "signaturesRequired",
+            "public_key_indices",
+            "publicKeyIndices",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKeys,
+            Signatures,
+            SignaturesRequired,
+            PublicKeyIndices,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKeys" | "public_keys" => Ok(GeneratedField::PublicKeys),
+                            "signatures" => Ok(GeneratedField::Signatures),
+                            "signaturesRequired" | "signatures_required" => Ok(GeneratedField::SignaturesRequired),
+                            "publicKeyIndices" | "public_key_indices" => Ok(GeneratedField::PublicKeyIndices),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = MultiKeySignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.MultiKeySignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<MultiKeySignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut public_keys__ = None;
+                let mut signatures__ = None;
+                let mut signatures_required__ = None;
+                let mut public_key_indices__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKeys => {
+                            if public_keys__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKeys"));
+                            }
+                            public_keys__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Signatures => {
+                            if signatures__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signatures"));
+                            }
+                            signatures__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::SignaturesRequired => {
+                            if signatures_required__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signaturesRequired"));
+                            }
+                            signatures_required__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::PublicKeyIndices => {
+                            if public_key_indices__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKeyIndices"));
+                            }
+                            public_key_indices__ =
+                                Some(map.next_value::<Vec<::pbjson::private::NumberDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                    }
+                }
+                Ok(MultiKeySignature {
+                    public_keys: public_keys__.unwrap_or_default(),
+                    signatures: signatures__.unwrap_or_default(),
+                    signatures_required: signatures_required__.unwrap_or_default(),
+                    public_key_indices: public_key_indices__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.MultiKeySignature", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for MultisigPayload {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -4669,6 +5393,119 @@ impl<'de> serde::Deserialize<'de> for ScriptWriteSet {
         deserializer.deserialize_struct("aptos.transaction.v1.ScriptWriteSet", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for Secp256k1EcdsaSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.Secp256k1EcdsaSignature", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field("publicKey", pbjson::private::base64::encode(&self.public_key).as_str())?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Secp256k1EcdsaSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "public_key",
+            "publicKey",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" | "public_key" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Secp256k1EcdsaSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.Secp256k1EcdsaSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Secp256k1EcdsaSignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(Secp256k1EcdsaSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.Secp256k1EcdsaSignature", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for Signature {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -4700,6 +5537,15 @@ impl serde::Serialize for Signature {
                 signature::Signature::MultiAgent(v) => {
                     struct_ser.serialize_field("multiAgent", v)?;
                 }
+                signature::Signature::FeePayer(v) => {
+                    struct_ser.serialize_field("feePayer", v)?;
+                }
+                signature::Signature::Secp256k1Ecdsa(v) => {
+                    struct_ser.serialize_field("secp256k1Ecdsa", v)?;
+                }
+                signature::Signature::MultiKey(v) => {
+                    struct_ser.serialize_field("multiKey", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -4718,6 +5564,12 @@ impl<'de> serde::Deserialize<'de> for Signature {
             "multiEd25519",
             "multi_agent",
             "multiAgent",
+            "fee_payer",
+            "feePayer",
+            "secp256k1_ecdsa",
+            "secp256k1Ecdsa",
+            "multi_key",
+            "multiKey",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -4726,6 +5578,9 @@ impl<'de> serde::Deserialize<'de> for Signature {
             Ed25519,
             MultiEd25519,
             MultiAgent,
+            FeePayer,
+            Secp256k1Ecdsa,
+            MultiKey,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4751,6 +5606,9 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" | "multi_ed25519" => Ok(GeneratedField::MultiEd25519),
                             "multiAgent" | "multi_agent" => Ok(GeneratedField::MultiAgent),
+                            "feePayer" | "fee_payer" => Ok(GeneratedField::FeePayer),
+                            "secp256k1Ecdsa" | "secp256k1_ecdsa" => Ok(GeneratedField::Secp256k1Ecdsa),
+                            "multiKey" | "multi_key" => Ok(GeneratedField::MultiKey),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4799,6 +5657,27 @@ impl<'de> serde::Deserialize<'de> for Signature {
                                 return Err(serde::de::Error::duplicate_field("multiAgent"));
                             }
                             signature__ = map.next_value::<::std::option::Option<_>>()?.map(signature::Signature::MultiAgent)
+;
+                        }
+                        GeneratedField::FeePayer => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("feePayer"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(signature::Signature::FeePayer)
+;
+                        }
+                        GeneratedField::Secp256k1Ecdsa => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secp256k1Ecdsa"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(signature::Signature::Secp256k1Ecdsa)
+;
+                        }
+                        GeneratedField::MultiKey => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("multiKey"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(signature::Signature::MultiKey)
 ;
                         }
                     }
@@ -4823,6 +5702,9 @@ impl serde::Serialize for signature::Type {
             Self::Ed25519 => "TYPE_ED25519",
             Self::MultiEd25519 => "TYPE_MULTI_ED25519",
             Self::MultiAgent => "TYPE_MULTI_AGENT",
+            Self::FeePayer => "TYPE_FEE_PAYER",
+            Self::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+            Self::MultiKey => "TYPE_MULTI_KEY",
         };
         serializer.serialize_str(variant)
     }
@@ -4838,6 +5720,9 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
             "TYPE_ED25519",
             "TYPE_MULTI_ED25519",
             "TYPE_MULTI_AGENT",
+            "TYPE_FEE_PAYER",
+            "TYPE_SECP256K1_ECDSA",
+            "TYPE_MULTI_KEY",
         ];
 
         struct GeneratedVisitor;
@@ -4884,6 +5769,9 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
                     "TYPE_ED25519" => Ok(signature::Type::Ed25519),
                     "TYPE_MULTI_ED25519" => Ok(signature::Type::MultiEd25519),
                     "TYPE_MULTI_AGENT" => Ok(signature::Type::MultiAgent),
+                    "TYPE_FEE_PAYER" => Ok(signature::Type::FeePayer),
+                    "TYPE_SECP256K1_ECDSA" => Ok(signature::Type::Secp256k1Ecdsa),
+                    "TYPE_MULTI_KEY" => Ok(signature::Type::MultiKey),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -174,6 +174,15 @@ impl<Key: Eq + Hash + Clone + Debug> TokenBucketRateLimiter<Key> {
         }
         remove
     }
+
+    /// Garbage collects the buckets which are full and not in use, as they behave like new
+    /// buckets.  Returns the number of buckets removed.
+    pub fn garbage_collect_full_buckets(&self) -> usize {
+        let mut buckets = self.buckets.write();
+        let num_buckets = buckets.len();
+        buckets.retain(|_, bucket| Arc::strong_count(bucket) > 1 || !bucket.lock().is_full());
+        num_buckets - buckets.len()
+    }
 }
 
 /// A token bucket object that keeps track of everything related to a key
@@ -291,6 +300,12 @@ impl Bucket {
         }
     }
 
+    /// Refills the bucket, and tells whether it has all its tokens
+    pub fn is_full(&mut self) -> bool {
+        self.refill();
+        self.tokens >= self.size
+    }
+
     /// Determine if an entire batch can be passed through
     /// This is important for message based rate limiting, where the whole message has
     /// to make it through, or else it must be rejected.  A result of `None` means it cannot
@@ -549,4 +564,27 @@ mod tests {
         assert!(!rate_limiter.try_garbage_collect_key(&key_to_keep));
         assert_num_keys(&rate_limiter, 1);
     }
+
+    #[test]
+    fn test_garbage_collection_of_full_buckets() {
+        let rate_limiter = TokenBucketRateLimiter::test(5, 1);
+
+        // Create a full bucket, an empty one, and one that is in use
+        {
+            let _full_bucket = rate_limiter.bucket("full");
+            rate_limiter
+                .bucket("empty")
+                .lock()
+                .acquire_all_tokens(5)
+                .unwrap();
+        }
+        let _bucket_arc = rate_limiter.bucket("in use");
+        assert_num_keys(&rate_limiter, 3);
+
+        // Only the full bucket that isn't in use should be removed
+        assert_eq!(1, rate_limiter.garbage_collect_full_buckets());
+        assert_num_keys(&rate_limiter, 2);
+        assert!(!rate_limiter.try_garbage_collect_key(&"in use"));
+        assert!(rate_limiter.try_garbage_collect_key(&"empty"));
+    }
 }
@@ -155,6 +155,8 @@ pub enum RestError {
     UrlParse(url::ParseError),
     #[error("Timeout waiting for transaction {0}")]
     Timeout(&'static str),
+    #[error("Invalid proof {0}")]
+    InvalidProof(anyhow::Error),
     #[error("Unknown error {0}")]
     Unknown(anyhow::Error),
     #[error("HTTP error {0}: {1}")]
@@ -13,6 +13,7 @@ pub use response::Response;
 pub mod client_builder;
 pub mod state;
 pub mod types;
+pub mod verifying_client;
 
 pub use crate::client_builder::{AptosBaseUrl, ClientBuilder};
 use crate::{
@@ -26,9 +27,9 @@ pub use aptos_api_types::{
 use aptos_api_types::{
     deserialize_from_string,
     mime_types::{BCS, BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE, JSON},
-    AptosError, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse, MoveModuleId,
-    TransactionData, TransactionOnChainData, TransactionsBatchSubmissionResult, UserTransaction,
-    VersionedEvent, ViewRequest,
+    AptosError, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse, MempoolTransaction,
+    MoveModuleId, SimulateTransactionRequest, TransactionData, TransactionOnChainData,
+    TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent, ViewRequest,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::{debug, info, sample, sample::SampleRate};
@@ -36,6 +37,7 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, CoinStoreResource, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
+    state_proof::{StateProof, StateValueWithStateProof},
     state_store::state_key::StateKey,
     transaction::SignedTransaction,
 };
@@ -307,6 +309,21 @@ impl Client {
         self.json(response).await
     }
 
+    /// Like `view`, but returns the BCS encoded return values of the view function, which can be
+    /// deserialized into their Rust types.
+    pub async fn view_bcs(
+        &self,
+        request: &ViewRequest,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Vec<u8>>>> {
+        let mut url = self.build_path("view")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("ledger_version={}", version).as_str()));
+        }
+        let response = self.post_bcs(url, serde_json::to_value(request)?).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn simulate(
         &self,
         txn: &SignedTransaction,
@@ -349,6 +366,30 @@ impl Client {
         self.json(response).await
     }
 
+    /// Simulates a transaction against the state at `version`, the latest if not provided, with
+    /// the request's state overrides applied on top of it.
+    pub async fn simulate_with_overrides(
+        &self,
+        request: &SimulateTransactionRequest,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<UserTransaction>>> {
+        let request = serde_json::to_string(request)?;
+        let mut url = self.build_path("transactions/simulate")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("ledger_version={}", version).as_str()));
+        }
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, JSON)
+            .body(request)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
     pub async fn simulate_bcs(
         &self,
         txn: &SignedTransaction,
@@ -905,6 +946,32 @@ impl Client {
         Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
     }
 
+    /// Gets the transactions of an account waiting in the mempool of the node
+    pub async fn get_account_pending_transactions(
+        &self,
+        address: AccountAddress,
+    ) -> AptosResult<Response<Vec<MempoolTransaction>>> {
+        let url = self.build_path(&format!("accounts/{}/pending_transactions", address))?;
+        let response = self.inner.get(url).send().await?;
+        self.json(response).await
+    }
+
+    /// Gets the transactions waiting in the mempool of the node, which requires the mempool
+    /// inspection API to be enabled on the node
+    pub async fn get_mempool_transactions(
+        &self,
+        limit: Option<u16>,
+    ) -> AptosResult<Response<Vec<MempoolTransaction>>> {
+        let url = self.build_path("mempool/transactions")?;
+        let mut request = self.inner.get(url);
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+        self.json(response).await
+    }
+
     pub async fn get_account_resources(
         &self,
         address: AccountAddress,
@@ -1280,6 +1347,39 @@ impl Client {
         Ok(response.map(|inner| inner.to_vec()))
     }
 
+    /// Returns the latest ledger info with signatures, along with the epoch change proof from the
+    /// epoch of `known_version` to the latest epoch. The response is not verified, see
+    /// [`verifying_client::VerifyingClient`] to verify it against a trusted state.
+    pub async fn get_state_proof(&self, known_version: u64) -> AptosResult<Response<StateProof>> {
+        let url = self.build_path(&format!(
+            "experimental/state_proof?known_version={}",
+            known_version
+        ))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
+    /// Returns the state value at the version of the latest ledger info with the proofs
+    /// authenticating it, along with the state proof from `known_version` to that ledger info.
+    /// The response is not verified, see [`verifying_client::VerifyingClient`] to verify it
+    /// against a trusted state.
+    pub async fn get_state_value_with_proof(
+        &self,
+        state_key: &StateKey,
+        known_version: u64,
+    ) -> AptosResult<Response<StateValueWithStateProof>> {
+        let url = self.build_path(&format!(
+            "experimental/state_values/proof?known_version={}",
+            known_version
+        ))?;
+        let data = json!({
+            "key": hex::encode(bcs::to_bytes(state_key)?),
+        });
+
+        let response = self.post_bcs(url, data).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address))?;
         let response = self.inner.get(url).send().await?;
@@ -1440,7 +1540,7 @@ impl Client {
                     | RestError::Json(_)
                     | RestError::Timeout(_)
                     | RestError::Unknown(_) => true,
-                    RestError::UrlParse(_) => false,
+                    RestError::UrlParse(_) | RestError::InvalidProof(_) => false,
                 },
             };
 
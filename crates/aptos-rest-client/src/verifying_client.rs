@@ -0,0 +1,160 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::RestError, Client, Response};
+use anyhow::anyhow;
+use aptos_infallible::RwLock;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    state_proof::{StateProof, StateValueWithProof},
+    state_store::{state_key::StateKey, state_value::StateValue},
+    trusted_state::{TrustedState, TrustedStateChange},
+    waypoint::Waypoint,
+};
+use move_core_types::language_storage::StructTag;
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+
+type AptosResult<T> = Result<T, RestError>;
+
+/// The maximum number of requests made to catch up with the latest epoch, as the epoch change
+/// proof served with a state proof is capped.
+const MAX_SYNC_ROUNDS: usize = 100;
+
+/// A client that verifies the state it reads from a fullnode, so that the fullnode doesn't have
+/// to be trusted.
+///
+/// The client keeps a [`TrustedState`], initially a waypoint, which it ratchets forward with the
+/// state proofs served along with the state values. The state values are then verified against
+/// the latest ledger info of the state proof.
+pub struct VerifyingClient {
+    inner: Client,
+    trusted_state: RwLock<TrustedState>,
+}
+
+impl VerifyingClient {
+    /// Creates a client trusting the epoch change ledger info committed to by the waypoint, e.g.
+    /// the genesis waypoint.
+    pub fn new(inner: Client, waypoint: Waypoint) -> Self {
+        Self::new_with_trusted_state(inner, TrustedState::from_epoch_waypoint(waypoint))
+    }
+
+    pub fn new_with_trusted_state(inner: Client, trusted_state: TrustedState) -> Self {
+        Self {
+            inner,
+            trusted_state: RwLock::new(trusted_state),
+        }
+    }
+
+    /// The underlying client, whose responses are not verified
+    pub fn inner(&self) -> &Client {
+        &self.inner
+    }
+
+    pub fn trusted_state(&self) -> TrustedState {
+        self.trusted_state.read().clone()
+    }
+
+    /// Ratchets the trusted state forward to the latest ledger info of the fullnode.
+    pub async fn sync(&self) -> AptosResult<TrustedState> {
+        for _ in 0..MAX_SYNC_ROUNDS {
+            let known_version = self.trusted_state.read().version();
+            let state_proof = self
+                .inner
+                .get_state_proof(known_version)
+                .await?
+                .into_inner();
+            if self.ratchet(&state_proof)? {
+                return Ok(self.trusted_state());
+            }
+        }
+        Err(sync_rounds_exhausted())
+    }
+
+    /// Returns the state value at the version of the latest ledger info of the fullnode, verified
+    /// against the trusted state. `None` means that the state key is proven not to exist.
+    pub async fn get_state_value(
+        &self,
+        state_key: &StateKey,
+    ) -> AptosResult<Response<Option<StateValue>>> {
+        for _ in 0..MAX_SYNC_ROUNDS {
+            let known_version = self.trusted_state.read().version();
+            let (response, state) = self
+                .inner
+                .get_state_value_with_proof(state_key, known_version)
+                .await?
+                .into_parts();
+            let (state_proof, state_value_with_proof) = response.into_inner();
+            if let Some(value) =
+                self.verify_state_value(state_key, &state_proof, state_value_with_proof)?
+            {
+                return Ok(Response::new(value, state));
+            }
+        }
+        Err(sync_rounds_exhausted())
+    }
+
+    /// Verifies a state value served along with a state proof, and ratchets the trusted state
+    /// forward. Returns `None` if the state proof doesn't reach the epoch of its latest ledger
+    /// info yet, in which case the state value can't be verified.
+    pub fn verify_state_value(
+        &self,
+        state_key: &StateKey,
+        state_proof: &StateProof,
+        state_value_with_proof: StateValueWithProof,
+    ) -> AptosResult<Option<Option<StateValue>>> {
+        if !self.ratchet(state_proof)? {
+            return Ok(None);
+        }
+        state_value_with_proof
+            .verify(state_proof.latest_ledger_info(), state_key)
+            .map_err(RestError::InvalidProof)?;
+        Ok(Some(state_value_with_proof.into_value()))
+    }
+
+    /// Returns the resource of the account, verified against the trusted state. `None` means
+    /// that the resource is proven not to exist.
+    pub async fn get_account_resource_bcs<T: DeserializeOwned>(
+        &self,
+        address: AccountAddress,
+        resource_type: &str,
+    ) -> AptosResult<Response<Option<T>>> {
+        let resource_type = StructTag::from_str(resource_type)?;
+        let state_key =
+            StateKey::access_path(AccessPath::resource_access_path(address, resource_type)?);
+        let response = self.get_state_value(&state_key).await?;
+        Ok(response.and_then(|value| {
+            value
+                .map(|value| bcs::from_bytes(value.bytes()))
+                .transpose()
+        })?)
+    }
+
+    /// Verifies the state proof against the trusted state and ratchets the trusted state forward.
+    /// Returns whether the latest ledger info of the state proof is verified, which is not the
+    /// case when the epoch change proof doesn't reach its epoch.
+    fn ratchet(&self, state_proof: &StateProof) -> AptosResult<bool> {
+        let mut trusted_state = self.trusted_state.write();
+        let change = trusted_state
+            .verify_and_ratchet(state_proof)
+            .map_err(RestError::InvalidProof)?;
+        let verified = match &change {
+            TrustedStateChange::NoChange | TrustedStateChange::Version { .. } => true,
+            TrustedStateChange::Epoch { new_state, .. } => {
+                new_state.version() == state_proof.latest_ledger_info().version()
+            },
+        };
+        if let Some(new_state) = change.new_state() {
+            *trusted_state = new_state;
+        }
+        Ok(verified)
+    }
+}
+
+fn sync_rounds_exhausted() -> RestError {
+    RestError::InvalidProof(anyhow!(
+        "Failed to catch up with the latest epoch in {} rounds",
+        MAX_SYNC_ROUNDS
+    ))
+}
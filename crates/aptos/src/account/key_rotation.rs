@@ -14,7 +14,8 @@ use crate::common::{
 use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-    PrivateKey, SigningKey,
+    secp256k1_ecdsa::Secp256k1EcdsaPublicKey,
+    CryptoMaterialError, PrivateKey, SigningKey, ValidCryptoMaterialStringExt,
 };
 use aptos_rest_client::{
     aptos_api_types::{AptosError, AptosErrorCode},
@@ -22,8 +23,9 @@ use aptos_rest_client::{
     Client,
 };
 use aptos_types::{
-    account_address::AccountAddress, account_config::CORE_CODE_ADDRESS,
-    transaction::authenticator::AuthenticationKey,
+    account_address::AccountAddress,
+    account_config::CORE_CODE_ADDRESS,
+    transaction::authenticator::{AnyPublicKey, AuthenticationKey, MultiKey, Scheme},
 };
 use async_trait::async_trait;
 use clap::Parser;
@@ -37,6 +39,9 @@ use std::{collections::BTreeMap, path::PathBuf};
 /// rotated you will need to use the original account address, with the
 /// new private key.  There is an interactive prompt to help you add it
 /// to a new profile.
+///
+/// If the current key is a secp256k1 ECDSA key, the key is rotated without
+/// a rotation proof, so the account can't be looked up by its new key.
 #[derive(Debug, Parser)]
 pub struct RotateKey {
     #[clap(flatten)]
@@ -102,46 +107,60 @@ impl CliCommand<RotateSummary> for RotateKey {
                 )
             })?;
 
-        let (current_private_key, sender_address) = self.txn_options.get_key_and_address()?;
-
-        // Get sequence number for account
-        let sequence_number = self.txn_options.sequence_number(sender_address).await?;
-        let auth_key = self.txn_options.auth_key(sender_address).await?;
-
-        let rotation_proof = RotationProofChallenge {
-            account_address: CORE_CODE_ADDRESS,
-            module_name: "account".to_string(),
-            struct_name: "RotationProofChallenge".to_string(),
-            sequence_number,
-            originator: sender_address,
-            current_auth_key: AccountAddress::from_bytes(auth_key)
-                .map_err(|err| CliError::UnableToParse("auth_key", err.to_string()))?,
-            new_public_key: new_private_key.public_key().to_bytes().to_vec(),
+        let (current_key, sender_address) = self.txn_options.get_account_key_and_address()?;
+
+        let payload = match current_key.secp256k1_ecdsa_private_key() {
+            None => {
+                let current_private_key = current_key.private_key();
+
+                // Get sequence number for account
+                let sequence_number = self.txn_options.sequence_number(sender_address).await?;
+                let auth_key = self.txn_options.auth_key(sender_address).await?;
+
+                let rotation_proof = RotationProofChallenge {
+                    account_address: CORE_CODE_ADDRESS,
+                    module_name: "account".to_string(),
+                    struct_name: "RotationProofChallenge".to_string(),
+                    sequence_number,
+                    originator: sender_address,
+                    current_auth_key: AccountAddress::from_bytes(auth_key)
+                        .map_err(|err| CliError::UnableToParse("auth_key", err.to_string()))?,
+                    new_public_key: new_private_key.public_key().to_bytes().to_vec(),
+                };
+
+                let rotation_msg = bcs::to_bytes(&rotation_proof)
+                    .map_err(|err| CliError::BCS("rotation_proof", err))?;
+
+                // Signs the struct using both the current private key and the next private key
+                let rotation_proof_signed_by_current_private_key =
+                    current_private_key.sign_arbitrary_message(&rotation_msg.clone());
+                let rotation_proof_signed_by_new_private_key =
+                    new_private_key.sign_arbitrary_message(&rotation_msg);
+
+                aptos_stdlib::account_rotate_authentication_key(
+                    0,
+                    // Existing public key
+                    current_private_key.public_key().to_bytes().to_vec(),
+                    0,
+                    // New public key
+                    new_private_key.public_key().to_bytes().to_vec(),
+                    rotation_proof_signed_by_current_private_key
+                        .to_bytes()
+                        .to_vec(),
+                    rotation_proof_signed_by_new_private_key.to_bytes().to_vec(),
+                )
+            },
+            // A secp256k1 ECDSA key can't sign a rotation proof that is verifiable on chain, so
+            // the key is rotated without one
+            Some(_) => aptos_stdlib::account_rotate_authentication_key_call(
+                Scheme::Ed25519 as u8,
+                new_private_key.public_key().to_bytes().to_vec(),
+            ),
         };
 
-        let rotation_msg =
-            bcs::to_bytes(&rotation_proof).map_err(|err| CliError::BCS("rotation_proof", err))?;
-
-        // Signs the struct using both the current private key and the next private key
-        let rotation_proof_signed_by_current_private_key =
-            current_private_key.sign_arbitrary_message(&rotation_msg.clone());
-        let rotation_proof_signed_by_new_private_key =
-            new_private_key.sign_arbitrary_message(&rotation_msg);
-
         let txn_summary = self
             .txn_options
-            .submit_transaction(aptos_stdlib::account_rotate_authentication_key(
-                0,
-                // Existing public key
-                current_private_key.public_key().to_bytes().to_vec(),
-                0,
-                // New public key
-                new_private_key.public_key().to_bytes().to_vec(),
-                rotation_proof_signed_by_current_private_key
-                    .to_bytes()
-                    .to_vec(),
-                rotation_proof_signed_by_new_private_key.to_bytes().to_vec(),
-            ))
+            .submit_transaction(payload)
             .await
             .map(TransactionSummary::from)?;
 
@@ -219,6 +238,8 @@ impl CliCommand<RotateSummary> for RotateKey {
         let mut profile_config = ProfileConfig {
             private_key: Some(new_private_key.clone()),
             public_key: Some(new_private_key.public_key()),
+            secp256k1_ecdsa_private_key: None,
+            secp256k1_ecdsa_public_key: None,
             account: Some(sender_address),
             ..self.txn_options.profile_options.profile()?
         };
@@ -247,6 +268,96 @@ impl CliCommand<RotateSummary> for RotateKey {
     }
 }
 
+/// Rotate an account's authentication key to a K-of-N multi key
+///
+/// The members of the multi key can be a mix of Ed25519 and secp256k1 ECDSA public keys.  The
+/// transaction is signed by the current key of the account only, as the new keys can't prove
+/// their knowledge on chain.  Once it is rotated, transactions of the account must be signed by
+/// at least `--signatures-required` of the new keys.
+#[derive(Debug, Parser)]
+pub struct RotateKeyToMultiKey {
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+
+    /// Public keys of the multi key, in order, as `<scheme>:<hex encoded public key>`
+    ///
+    /// The scheme is one of `ed25519` or `secp256k1_ecdsa`, e.g. `ed25519:0x1234...`
+    #[clap(
+        long,
+        required = true,
+        multiple_values = true,
+        parse(try_from_str = parse_any_public_key)
+    )]
+    pub(crate) public_keys: Vec<AnyPublicKey>,
+
+    /// Number of signatures required to sign a transaction of the account
+    #[clap(long)]
+    pub(crate) signatures_required: u8,
+}
+
+/// Parses a public key given as `<scheme>:<hex encoded public key>`
+fn parse_any_public_key(str: &str) -> CliTypedResult<AnyPublicKey> {
+    let (scheme, public_key) = str.split_once(':').ok_or_else(|| {
+        CliError::CommandArgumentError(format!(
+            "Invalid public key '{}', expected <scheme>:<public key>",
+            str
+        ))
+    })?;
+    let parse_error = |err: CryptoMaterialError| {
+        CliError::UnableToParse("public key", format!("{}: {}", str, err))
+    };
+    match scheme {
+        "ed25519" => Ok(AnyPublicKey::ed25519(
+            Ed25519PublicKey::from_encoded_string(public_key).map_err(parse_error)?,
+        )),
+        "secp256k1_ecdsa" => Ok(AnyPublicKey::secp256k1_ecdsa(
+            Secp256k1EcdsaPublicKey::from_encoded_string(public_key).map_err(parse_error)?,
+        )),
+        _ => Err(CliError::CommandArgumentError(format!(
+            "Invalid public key scheme '{}', expected one of [ed25519, secp256k1_ecdsa]",
+            scheme
+        ))),
+    }
+}
+
+#[async_trait]
+impl CliCommand<RotateSummary> for RotateKeyToMultiKey {
+    fn command_name(&self) -> &'static str {
+        "RotateKeyToMultiKey"
+    }
+
+    async fn execute(self) -> CliTypedResult<RotateSummary> {
+        let multi_key = MultiKey::new(self.public_keys, self.signatures_required)
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+        let new_auth_key = AuthenticationKey::multi_key(&multi_key);
+
+        let txn_summary = self
+            .txn_options
+            .submit_transaction(aptos_stdlib::account_rotate_authentication_key_call(
+                Scheme::MultiKey as u8,
+                multi_key.to_bytes(),
+            ))
+            .await
+            .map(TransactionSummary::from)?;
+
+        match txn_summary.success {
+            Some(true) => Ok(RotateSummary {
+                message: Some(format!(
+                    "Authentication key is rotated to the multi key {}",
+                    new_auth_key
+                )),
+                transaction: txn_summary,
+            }),
+            Some(false) => Err(CliError::ApiError(
+                "Transaction was not executed successfully".to_string(),
+            )),
+            None => Err(CliError::UnexpectedError(
+                "Malformed transaction response".to_string(),
+            )),
+        }
+    }
+}
+
 /// Lookup the account address through the on-chain lookup table
 ///
 /// If the account is rotated, it will provide the address accordingly.  If the account was not
@@ -26,6 +26,7 @@ pub enum AccountTool {
     List(list::ListAccount),
     LookupAddress(key_rotation::LookupAddress),
     RotateKey(key_rotation::RotateKey),
+    RotateKeyToMultiKey(key_rotation::RotateKeyToMultiKey),
     Transfer(transfer::TransferCoins),
 }
 
@@ -39,6 +40,7 @@ impl AccountTool {
             AccountTool::List(tool) => tool.execute_serialized().await,
             AccountTool::LookupAddress(tool) => tool.execute_serialized().await,
             AccountTool::RotateKey(tool) => tool.execute_serialized().await,
+            AccountTool::RotateKeyToMultiKey(tool) => tool.execute_serialized().await,
             AccountTool::Transfer(tool) => tool.execute_serialized().await,
         }
     }
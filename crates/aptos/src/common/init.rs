@@ -6,17 +6,21 @@ use crate::{
     common::{
         types::{
             account_address_from_public_key, CliCommand, CliConfig, CliError, CliTypedResult,
-            ConfigSearchMode, EncodingOptions, PrivateKeyInputOptions, ProfileConfig,
+            ConfigSearchMode, EncodingOptions, KeyType, PrivateKeyInputOptions, ProfileConfig,
             ProfileOptions, PromptOptions, RngArgs, DEFAULT_PROFILE,
         },
         utils::{fund_account, prompt_yes_with_override, read_line, wait_for_transactions},
     },
 };
-use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, ValidCryptoMaterialStringExt};
+use aptos_crypto::{
+    ed25519::Ed25519PrivateKey, secp256k1_ecdsa::Secp256k1EcdsaPrivateKey, PrivateKey,
+    ValidCryptoMaterialStringExt,
+};
 use aptos_rest_client::{
     aptos_api_types::{AptosError, AptosErrorCode},
     error::{AptosErrorResponse, RestError},
 };
+use aptos_types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey};
 use async_trait::async_trait;
 use clap::Parser;
 use reqwest::Url;
@@ -49,6 +53,10 @@ pub struct InitTool {
     #[clap(long)]
     pub skip_faucet: bool,
 
+    /// Type of the account key to use. Must be one of [ed25519, secp256k1-ecdsa]
+    #[clap(long, default_value_t = KeyType::Ed25519)]
+    pub key_type: KeyType,
+
     #[clap(flatten)]
     pub rng_args: RngArgs,
     #[clap(flatten)]
@@ -132,32 +140,18 @@ impl CliCommand<()> for InitTool {
         }
 
         // Private key
-        let private_key = if let Some(private_key) = self
-            .private_key_options
-            .extract_private_key_cli(self.encoding_options.encoding)?
-        {
-            eprintln!("Using command line argument for private key");
-            private_key
-        } else {
-            eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
-            let input = read_line("Private key")?;
-            let input = input.trim();
-            if input.is_empty() {
-                if let Some(private_key) = profile_config.private_key {
-                    eprintln!("No key given, keeping existing key...");
-                    private_key
-                } else {
-                    eprintln!("No key given, generating key...");
-                    self.rng_args
-                        .key_generator()?
-                        .generate_ed25519_private_key()
-                }
-            } else {
-                Ed25519PrivateKey::from_encoded_string(input)
-                    .map_err(|err| CliError::UnableToParse("Ed25519PrivateKey", err.to_string()))?
-            }
+        let derived_address = match self.key_type {
+            KeyType::Ed25519 => self.ed25519_key(&mut profile_config)?,
+            KeyType::Secp256k1Ecdsa => self.secp256k1_ecdsa_key(&mut profile_config)?,
+            key_type => {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Invalid key type {}.  Must be one of [{}, {}]",
+                    key_type,
+                    KeyType::Ed25519,
+                    KeyType::Secp256k1Ecdsa
+                )));
+            },
         };
-        let public_key = private_key.public_key();
 
         let client = aptos_rest_client::Client::new(
             Url::parse(
@@ -171,11 +165,7 @@ impl CliCommand<()> for InitTool {
 
         // lookup the address from onchain instead of deriving it
         // if this is the rotated key, deriving it will outputs an incorrect address
-        let derived_address = account_address_from_public_key(&public_key);
         let address = lookup_address(&client, derived_address, false).await?;
-
-        profile_config.private_key = Some(private_key);
-        profile_config.public_key = Some(public_key);
         profile_config.account = Some(address);
 
         // Create account if it doesn't exist (and there's a faucet)
@@ -259,6 +249,85 @@ impl CliCommand<()> for InitTool {
 }
 
 impl InitTool {
+    /// Selects an Ed25519 private key for the profile, and returns the derived account address
+    fn ed25519_key(&self, profile_config: &mut ProfileConfig) -> CliTypedResult<AccountAddress> {
+        let private_key = if let Some(private_key) = self
+            .private_key_options
+            .extract_private_key_cli(self.encoding_options.encoding)?
+        {
+            eprintln!("Using command line argument for private key");
+            private_key
+        } else {
+            eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
+            let input = read_line("Private key")?;
+            let input = input.trim();
+            if input.is_empty() {
+                if let Some(private_key) = profile_config.private_key.take() {
+                    eprintln!("No key given, keeping existing key...");
+                    private_key
+                } else {
+                    eprintln!("No key given, generating key...");
+                    self.rng_args
+                        .key_generator()?
+                        .generate_ed25519_private_key()
+                }
+            } else {
+                Ed25519PrivateKey::from_encoded_string(input)
+                    .map_err(|err| CliError::UnableToParse("Ed25519PrivateKey", err.to_string()))?
+            }
+        };
+        let public_key = private_key.public_key();
+        let derived_address = account_address_from_public_key(&public_key);
+
+        profile_config.private_key = Some(private_key);
+        profile_config.public_key = Some(public_key);
+        profile_config.secp256k1_ecdsa_private_key = None;
+        profile_config.secp256k1_ecdsa_public_key = None;
+        Ok(derived_address)
+    }
+
+    /// Selects a secp256k1 ECDSA private key for the profile, and returns the derived account
+    /// address
+    fn secp256k1_ecdsa_key(
+        &self,
+        profile_config: &mut ProfileConfig,
+    ) -> CliTypedResult<AccountAddress> {
+        let private_key = if let Some(private_key) = self
+            .private_key_options
+            .extract_secp256k1_ecdsa_private_key_cli(self.encoding_options.encoding)?
+        {
+            eprintln!("Using command line argument for private key");
+            private_key
+        } else {
+            eprintln!("Enter your secp256k1 ECDSA private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.secp256k1_ecdsa_private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
+            let input = read_line("Private key")?;
+            let input = input.trim();
+            if input.is_empty() {
+                if let Some(private_key) = profile_config.secp256k1_ecdsa_private_key.take() {
+                    eprintln!("No key given, keeping existing key...");
+                    private_key
+                } else {
+                    eprintln!("No key given, generating key...");
+                    self.rng_args
+                        .key_generator()?
+                        .generate_secp256k1_ecdsa_private_key()
+                }
+            } else {
+                Secp256k1EcdsaPrivateKey::from_encoded_string(input).map_err(|err| {
+                    CliError::UnableToParse("Secp256k1EcdsaPrivateKey", err.to_string())
+                })?
+            }
+        };
+        let public_key = private_key.public_key();
+        let derived_address = AuthenticationKey::secp256k1_ecdsa(&public_key).derived_address();
+
+        profile_config.private_key = None;
+        profile_config.public_key = None;
+        profile_config.secp256k1_ecdsa_private_key = Some(private_key);
+        profile_config.secp256k1_ecdsa_public_key = Some(public_key);
+        Ok(derived_address)
+    }
+
     /// Custom network created, which requires a REST URL
     fn custom_network(&self, profile_config: &mut ProfileConfig) -> CliTypedResult<()> {
         // Rest Endpoint
@@ -6,10 +6,10 @@ use crate::{
         init::Network,
         utils::{
             check_if_file_exists, create_dir_if_not_exist, dir_default_to_current,
-            get_account_with_state, get_auth_key, get_sequence_number, parse_json_file,
-            prompt_yes_with_override, read_from_file, start_logger, to_common_result,
-            to_common_success_result, write_to_file, write_to_file_with_opts,
-            write_to_user_only_file,
+            generate_gas_profiling_report, get_account_with_state, get_auth_key,
+            get_sequence_number, parse_json_file, prompt_yes_with_override, read_from_file,
+            start_logger, to_common_result, to_common_success_result, write_to_file,
+            write_to_file_with_opts, write_to_user_only_file,
         },
     },
     config::GlobalConfig,
@@ -19,10 +19,10 @@ use crate::{
 use anyhow::Context;
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    secp256k1_ecdsa::{Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature},
     x25519, PrivateKey, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
 use aptos_debugger::AptosDebugger;
-use aptos_gas_profiling::FrameName;
 use aptos_global_constants::adjust_gas_headroom;
 use aptos_keygen::KeyGen;
 use aptos_logger::Level;
@@ -31,7 +31,10 @@ use aptos_rest_client::{
     error::RestError,
     AptosBaseUrl, Client, Transaction,
 };
-use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
+use aptos_sdk::{
+    transaction_builder::TransactionFactory,
+    types::{AccountKey, LocalAccount},
+};
 use aptos_types::{
     chain_id::ChainId,
     transaction::{
@@ -212,6 +215,12 @@ pub struct ProfileConfig {
     /// Public key for commands
     #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key: Option<Ed25519PublicKey>,
+    /// Secp256k1 ECDSA private key for commands, used instead of `private_key` if present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secp256k1_ecdsa_private_key: Option<Secp256k1EcdsaPrivateKey>,
+    /// Secp256k1 ECDSA public key for commands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secp256k1_ecdsa_public_key: Option<Secp256k1EcdsaPublicKey>,
     /// Account for commands
     #[serde(skip_serializing_if = "Option::is_none")]
     pub account: Option<AccountAddress>,
@@ -223,6 +232,19 @@ pub struct ProfileConfig {
     pub faucet_url: Option<String>,
 }
 
+impl ProfileConfig {
+    /// Address derived from the profile's private key, using the secp256k1 ECDSA key if present
+    /// as commands do
+    pub fn derived_address(&self) -> Option<AccountAddress> {
+        let auth_key = if let Some(ref private_key) = self.secp256k1_ecdsa_private_key {
+            AuthenticationKey::secp256k1_ecdsa(&private_key.public_key())
+        } else {
+            AuthenticationKey::ed25519(&self.private_key.as_ref()?.public_key())
+        };
+        Some(account_address_from_auth_key(&auth_key))
+    }
+}
+
 /// ProfileConfig but without the private parts
 #[derive(Debug, Serialize)]
 pub struct ProfileSummary {
@@ -230,6 +252,8 @@ pub struct ProfileSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key: Option<Ed25519PublicKey>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub secp256k1_ecdsa_public_key: Option<Secp256k1EcdsaPublicKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub account: Option<AccountAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rest_url: Option<String>,
@@ -240,8 +264,10 @@ pub struct ProfileSummary {
 impl From<&ProfileConfig> for ProfileSummary {
     fn from(config: &ProfileConfig) -> Self {
         ProfileSummary {
-            has_private_key: config.private_key.is_some(),
+            has_private_key: config.private_key.is_some()
+                || config.secp256k1_ecdsa_private_key.is_some(),
             public_key: config.public_key.clone(),
+            secp256k1_ecdsa_public_key: config.secp256k1_ecdsa_public_key.clone(),
             account: config.account,
             rest_url: config.rest_url.clone(),
             faucet_url: config.faucet_url.clone(),
@@ -367,6 +393,8 @@ pub enum KeyType {
     X25519,
     /// A BLS12381 key for consensus
     Bls12381,
+    /// A secp256k1 ECDSA key used for signing
+    Secp256k1Ecdsa,
 }
 
 impl Display for KeyType {
@@ -375,6 +403,7 @@ impl Display for KeyType {
             KeyType::Ed25519 => "ed25519",
             KeyType::X25519 => "x25519",
             KeyType::Bls12381 => "bls12381",
+            KeyType::Secp256k1Ecdsa => "secp256k1-ecdsa",
         };
         write!(f, "{}", str)
     }
@@ -388,7 +417,10 @@ impl FromStr for KeyType {
             "ed25519" => Ok(KeyType::Ed25519),
             "x25519" => Ok(KeyType::X25519),
             "bls12381" => Ok(KeyType::Bls12381),
-            _ => Err("Invalid key type: Must be one of [ed25519, x25519]"),
+            "secp256k1-ecdsa" => Ok(KeyType::Secp256k1Ecdsa),
+            _ => {
+                Err("Invalid key type: Must be one of [ed25519, x25519, bls12381, secp256k1-ecdsa]")
+            },
         }
     }
 }
@@ -814,6 +846,52 @@ impl PrivateKeyInputOptions {
         }
     }
 
+    /// Extract the signing key of the account from CLI args with fallback to config
+    ///
+    /// Unlike [`Self::extract_private_key_and_address`], this also supports profiles with a
+    /// secp256k1 ECDSA key.  Keys given on the command line are always Ed25519 keys.
+    pub fn extract_account_key_and_address(
+        &self,
+        encoding: EncodingType,
+        profile: &ProfileOptions,
+        maybe_address: Option<AccountAddress>,
+    ) -> CliTypedResult<(AccountKey, AccountAddress)> {
+        if self.extract_private_key_cli(encoding)?.is_none() {
+            if let Some((Some(key), maybe_config_address)) = CliConfig::load_profile(
+                profile.profile_name(),
+                ConfigSearchMode::CurrentDirAndParents,
+            )?
+            .map(|p| (p.secp256k1_ecdsa_private_key, p.account))
+            {
+                let key = AccountKey::from_secp256k1_ecdsa_private_key(key);
+                let address = maybe_address
+                    .or(maybe_config_address)
+                    .unwrap_or_else(|| key.authentication_key().derived_address());
+                return Ok((key, address));
+            }
+        }
+
+        self.extract_private_key_and_address(encoding, profile, maybe_address)
+            .map(|(key, address)| (AccountKey::from_private_key(key), address))
+    }
+
+    /// Extract secp256k1 ECDSA private key from CLI args
+    pub fn extract_secp256k1_ecdsa_private_key_cli(
+        &self,
+        encoding: EncodingType,
+    ) -> CliTypedResult<Option<Secp256k1EcdsaPrivateKey>> {
+        if let Some(ref file) = self.private_key_file {
+            Ok(Some(
+                encoding.load_key("--private-key-file", file.as_path())?,
+            ))
+        } else if let Some(ref key) = self.private_key {
+            let key = key.as_bytes().to_vec();
+            Ok(Some(encoding.decode_key("--private-key", key)?))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Extract private key from CLI args with fallback to config
     pub fn extract_private_key(
         &self,
@@ -1045,12 +1123,11 @@ pub fn load_account_arg(str: &str) -> Result<AccountAddress, CliError> {
             .map(|p| p.account)
     {
         Ok(account_address)
-    } else if let Some(Some(private_key)) =
+    } else if let Some(Some(account_address)) =
         CliConfig::load_profile(Some(str), ConfigSearchMode::CurrentDirAndParents)?
-            .map(|p| p.private_key)
+            .map(|p| p.derived_address())
     {
-        let public_key = private_key.public_key();
-        Ok(account_address_from_public_key(&public_key))
+        Ok(account_address)
     } else {
         Err(CliError::CommandArgumentError(
             "'--account' or '--profile' after using aptos init must be provided".to_string(),
@@ -1086,12 +1163,11 @@ pub fn load_manifest_account_arg(str: &str) -> Result<Option<AccountAddress>, Cl
             })
     } else if let Ok(account_address) = AccountAddress::from_str(str) {
         Ok(Some(account_address))
-    } else if let Some(Some(private_key)) =
+    } else if let Some(Some(account_address)) =
         CliConfig::load_profile(Some(str), ConfigSearchMode::CurrentDirAndParents)?
-            .map(|p| p.private_key)
+            .map(|p| p.derived_address())
     {
-        let public_key = private_key.public_key();
-        Ok(Some(account_address_from_public_key(&public_key)))
+        Ok(Some(account_address))
     } else {
         Err(CliError::CommandArgumentError(
             "Invalid Move manifest account address".to_string(),
@@ -1372,8 +1448,18 @@ impl TransactionOptions {
         )
     }
 
+    /// Retrieves the signing key of the sender, which may be an Ed25519 or secp256k1 ECDSA key,
+    /// and the associated address
+    pub fn get_account_key_and_address(&self) -> CliTypedResult<(AccountKey, AccountAddress)> {
+        self.private_key_options.extract_account_key_and_address(
+            self.encoding_options.encoding,
+            &self.profile_options,
+            self.sender_account,
+        )
+    }
+
     pub fn sender_address(&self) -> CliTypedResult<AccountAddress> {
-        Ok(self.get_key_and_address()?.1)
+        Ok(self.get_account_key_and_address()?.1)
     }
 
     /// Gets the auth key by account address. We need to fetch the auth key from Rest API rather than creating an
@@ -1402,7 +1488,7 @@ impl TransactionOptions {
         payload: TransactionPayload,
     ) -> CliTypedResult<Transaction> {
         let client = self.rest_client()?;
-        let (sender_key, sender_address) = self.get_key_and_address()?;
+        let (sender_key, sender_address) = self.get_account_key_and_address()?;
 
         // Ask to confirm price if the gas unit price is estimated above the lowest value when
         // it is automatically estimated
@@ -1456,11 +1542,18 @@ impl TransactionOptions {
                 .expiration_timestamp_secs(expiration_time_secs)
                 .build();
 
-            let signed_transaction = SignedTransaction::new(
-                unsigned_transaction,
-                sender_key.public_key(),
-                Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
-            );
+            let signed_transaction = match sender_key.secp256k1_ecdsa_public_key() {
+                Some(public_key) => SignedTransaction::new_secp256k1_ecdsa(
+                    unsigned_transaction,
+                    public_key.clone(),
+                    Secp256k1EcdsaSignature::try_from([0u8; 64].as_ref()).unwrap(),
+                ),
+                None => SignedTransaction::new(
+                    unsigned_transaction,
+                    sender_key.public_key().clone(),
+                    Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
+                ),
+            };
 
             let txns = client
                 .simulate_with_gas_estimation(&signed_transaction, true, false)
@@ -1524,7 +1617,7 @@ impl TransactionOptions {
         const DEFAULT_GAS_UNIT_PRICE: u64 = 100;
         const DEFAULT_MAX_GAS: u64 = 2_000_000;
 
-        let (sender_key, sender_address) = self.get_key_and_address()?;
+        let (sender_key, sender_address) = self.get_account_key_and_address()?;
         let gas_unit_price = self
             .gas_options
             .gas_unit_price
@@ -1565,89 +1658,10 @@ impl TransactionOptions {
             CliError::UnexpectedError(format!("failed to simulate txn with gas profiler: {}", err))
         })?;
 
-        // Generate the file name for the flamegraphs
-        let entry_point = gas_log.entry_point();
-
-        let human_readable_name = match entry_point {
-            FrameName::Script => "script".to_string(),
-            FrameName::Function {
-                module_id, name, ..
-            } => {
-                let addr_short = module_id.address().short_str_lossless();
-                let addr_truncated = if addr_short.len() > 4 {
-                    &addr_short[..4]
-                } else {
-                    addr_short.as_str()
-                };
-                format!("0x{}-{}-{}", addr_truncated, module_id.name(), name)
-            },
-        };
-        let raw_file_name = format!("txn-{}-{}", hash, human_readable_name);
-
-        // Create the directory if it does not exist yet.
-        let dir: &Path = Path::new("gas-profiling");
-
-        macro_rules! create_dir {
-            () => {
-                if let Err(err) = std::fs::create_dir(dir) {
-                    if err.kind() != std::io::ErrorKind::AlreadyExists {
-                        return Err(CliError::UnexpectedError(format!(
-                            "failed to create directory {}",
-                            dir.display()
-                        )));
-                    }
-                }
-            };
-        }
-
-        // Generate the execution & IO flamegraph.
+        // Generate the gas profiling report
         println!();
-        match gas_log.to_flamegraph(format!("Transaction {} -- Execution & IO", hash))? {
-            Some(graph_bytes) => {
-                create_dir!();
-                let graph_file_path = Path::join(dir, format!("{}.exec_io.svg", raw_file_name));
-                std::fs::write(&graph_file_path, graph_bytes).map_err(|err| {
-                    CliError::UnexpectedError(format!(
-                        "Failed to write flamegraph to file {} : {:?}",
-                        graph_file_path.display(),
-                        err
-                    ))
-                })?;
-                println!(
-                    "Execution & IO Gas flamegraph saved to {}",
-                    graph_file_path.display()
-                );
-            },
-            None => {
-                println!("Skipped generating execution & IO flamegraph");
-            },
-        }
-
-        // Generate the storage fee flamegraph.
-        match gas_log
-            .storage
-            .to_flamegraph(format!("Transaction {} -- Storage Fee", hash))?
-        {
-            Some(graph_bytes) => {
-                create_dir!();
-                let graph_file_path = Path::join(dir, format!("{}.storage.svg", raw_file_name));
-                std::fs::write(&graph_file_path, graph_bytes).map_err(|err| {
-                    CliError::UnexpectedError(format!(
-                        "Failed to write flamegraph to file {} : {:?}",
-                        graph_file_path.display(),
-                        err
-                    ))
-                })?;
-                println!(
-                    "Storage fee flamegraph saved to {}",
-                    graph_file_path.display()
-                );
-            },
-            None => {
-                println!("Skipped generating storage fee flamegraph");
-            },
-        }
-
+        let report_path = generate_gas_profiling_report(hash, &gas_log)?;
+        println!("Gas profiling report saved to {}", report_path.display());
         println!();
 
         // Generate the transaction summary
@@ -11,6 +11,7 @@ use crate::{
 };
 use aptos_build_info::build_information;
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use aptos_gas_profiling::{FrameName, TransactionGasLog};
 use aptos_keygen::KeyGen;
 use aptos_logger::{debug, Level};
 use aptos_rest_client::{aptos_api_types::HashValue, Account, Client, State};
@@ -483,6 +484,43 @@ pub async fn profile_or_submit(
     }
 }
 
+/// Generates the HTML gas profiling report of a transaction in the `gas-profiling` directory, and
+/// returns its path.
+pub fn generate_gas_profiling_report(
+    txn_hash: aptos_crypto::HashValue,
+    gas_log: &TransactionGasLog,
+) -> CliTypedResult<PathBuf> {
+    // Generate the file name for the report
+    let human_readable_name = match gas_log.entry_point() {
+        FrameName::Script => "script".to_string(),
+        FrameName::Function {
+            module_id, name, ..
+        } => {
+            let addr_short = module_id.address().short_str_lossless();
+            let addr_truncated = if addr_short.len() > 4 {
+                &addr_short[..4]
+            } else {
+                addr_short.as_str()
+            };
+            format!("0x{}-{}-{}", addr_truncated, module_id.name(), name)
+        },
+    };
+    let raw_file_name = format!("txn-{}-{}", txn_hash, human_readable_name);
+
+    let report = gas_log
+        .to_html_report(format!("Transaction {}", txn_hash))
+        .map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to generate gas profiling report: {}", err))
+        })?;
+
+    let dir = Path::new("gas-profiling");
+    create_dir_if_not_exist(dir)?;
+    let report_path = dir.join(format!("{}.html", raw_file_name));
+    std::fs::write(&report_path, report)
+        .map_err(|err| CliError::IO(report_path.display().to_string(), err))?;
+    Ok(report_path)
+}
+
 /// Try parsing JSON in file at path into a specified type.
 pub fn parse_json_file<T: for<'a> Deserialize<'a>>(path_ref: &Path) -> CliTypedResult<T> {
     serde_json::from_slice::<T>(&read_from_file(path_ref)?).map_err(|err| {
@@ -0,0 +1,94 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliError, CliTypedResult},
+    utils::{dir_default_to_current, write_to_file},
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_command_line_common::files::{find_move_filenames, FileHash};
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer,
+    formatter::{format_source, FormatOptions},
+};
+use move_package::source_package::layout::SourcePackageLayout;
+use move_symbol_pool::Symbol;
+use std::{collections::HashMap, path::PathBuf};
+
+/// Formats the Move source files of a package
+///
+/// The files in the `sources`, `scripts`, `tests` and `examples` directories of the package are
+/// formatted in place. Only whitespace is changed: the indentation of lines and the spacing
+/// between tokens are normalized, while the line breaks and comments are kept.
+#[derive(Parser)]
+pub struct FormatPackage {
+    /// Path to a move package (the folder with a Move.toml file)
+    #[clap(long, parse(from_os_str))]
+    pub(crate) package_dir: Option<PathBuf>,
+
+    /// Number of spaces per indentation level
+    #[clap(long, default_value_t = 4)]
+    pub(crate) indent_width: usize,
+
+    /// Only check that the files are formatted, and fail with the ones that aren't
+    #[clap(long)]
+    pub(crate) check: bool,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for FormatPackage {
+    fn command_name(&self) -> &'static str {
+        "FormatPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let package_dir = dir_default_to_current(self.package_dir)?;
+        let dirs: Vec<_> = [
+            SourcePackageLayout::Sources,
+            SourcePackageLayout::Scripts,
+            SourcePackageLayout::Tests,
+            SourcePackageLayout::Examples,
+        ]
+        .iter()
+        .map(|dir| package_dir.join(dir.path()))
+        .filter(|dir| dir.exists())
+        .collect();
+        let files = find_move_filenames(&dirs, false)
+            .map_err(|e| CliError::UnexpectedError(format!("{:#}", e)))?;
+
+        let options = FormatOptions {
+            indent_width: self.indent_width,
+        };
+        let mut unformatted = vec![];
+        for file in files {
+            let source = std::fs::read_to_string(&file)
+                .map_err(|e| CliError::UnableToReadFile(file.clone(), e.to_string()))?;
+            let formatted = format_source(&source, &options).map_err(|diags| {
+                let files = HashMap::from([(
+                    FileHash::new(&source),
+                    (Symbol::from(file.as_str()), source.clone()),
+                )]);
+                CliError::UnableToParse(
+                    "Move source",
+                    String::from_utf8_lossy(&report_diagnostics_to_buffer(&files, diags))
+                        .to_string(),
+                )
+            })?;
+            if formatted != source {
+                if !self.check {
+                    write_to_file(file.as_ref(), &file, formatted.as_bytes())?;
+                }
+                unformatted.push(file);
+            }
+        }
+
+        if self.check && !unformatted.is_empty() {
+            return Err(CliError::UnexpectedError(format!(
+                "Files are not formatted: {}",
+                unformatted.join(", ")
+            )));
+        }
+        Ok(unformatted)
+    }
+}
@@ -4,8 +4,10 @@
 mod aptos_debug_natives;
 pub mod coverage;
 mod disassembler;
+mod format;
 mod manifest;
 pub mod package_hooks;
+mod replay;
 mod show;
 pub mod stored_package;
 mod transactional_tests_runner;
@@ -28,19 +30,23 @@ use crate::{
     move_tool::{
         coverage::SummaryCoverage,
         disassembler::Disassemble,
+        format::FormatPackage,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
+        replay::Replay,
     },
     CliCommand, CliResult,
 };
 use aptos_crypto::HashValue;
 use aptos_framework::{
-    build_model, docgen::DocgenOptions, extended_checks, natives::code::UpgradePolicy,
-    prover::ProverOptions, BuildOptions, BuiltPackage,
+    build_model, docgen::DocgenOptions, extended_checks, get_view_functions,
+    natives::code::UpgradePolicy, prover::ProverOptions, unzip_metadata, BuildOptions,
+    BuiltPackage,
 };
 use aptos_gas::{AbstractValueSizeGasParameters, NativeGasParameters};
 use aptos_rest_client::aptos_api_types::{
     EntryFunctionId, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
 };
+use aptos_sdk_builder::bindings::PackageModule;
 use aptos_transactional_test_harness::run_aptos_test;
 use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
@@ -53,6 +59,8 @@ use codespan_reporting::{
     term::termcolor::{ColorChoice, StandardStream},
 };
 use itertools::Itertools;
+use move_binary_format::CompiledModule;
+use move_bytecode_source_map::source_map::SourceMap;
 use move_cli::{self, base::test::UnitTestResult};
 use move_command_line_common::env::MOVE_HOME;
 use move_core_types::{identifier::Identifier, language_storage::ModuleId, u256::U256};
@@ -88,10 +96,13 @@ pub enum MoveTool {
     Disassemble(Disassemble),
     Document(DocumentPackage),
     Download(DownloadPackage),
+    Fmt(FormatPackage),
+    GenerateRustBindings(GenerateRustBindings),
     Init(InitPackage),
     List(ListPackage),
     Prove(ProvePackage),
     Publish(PublishPackage),
+    Replay(Replay),
     Run(RunFunction),
     RunScript(RunScript),
     #[clap(subcommand, hide = true)]
@@ -116,10 +127,13 @@ impl MoveTool {
             MoveTool::Disassemble(tool) => tool.execute_serialized().await,
             MoveTool::Document(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
+            MoveTool::Fmt(tool) => tool.execute_serialized().await,
+            MoveTool::GenerateRustBindings(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
             MoveTool::List(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
             MoveTool::Publish(tool) => tool.execute_serialized().await,
+            MoveTool::Replay(tool) => tool.execute_serialized().await,
             MoveTool::Run(tool) => tool.execute_serialized().await,
             MoveTool::RunScript(tool) => tool.execute_serialized().await,
             MoveTool::Show(tool) => tool.execute_serialized().await,
@@ -425,6 +439,17 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    /// Seed of the generation of the arguments of `#[random_test]` functions
+    ///
+    /// The seed is printed along with the arguments of a failing random test, and can be passed
+    /// to reproduce the failure. A random seed is picked if none is given.
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// Number of runs of each `#[random_test]` function, with newly generated arguments each time
+    #[clap(long, default_value = "100")]
+    pub random_iterations: usize,
 }
 
 #[async_trait]
@@ -468,6 +493,8 @@ impl CliCommand<&'static str> for TestPackage {
                 report_stacktrace_on_abort: true,
                 report_storage_on_error: self.dump_state,
                 ignore_compile_warnings: self.ignore_compile_warnings,
+                seed: self.seed,
+                random_iterations: self.random_iterations,
                 ..UnitTestingConfig::default_with_bound(None)
             },
             // TODO(Gas): we may want to switch to non-zero costs in the future
@@ -993,6 +1020,121 @@ impl CliCommand<&'static str> for DownloadPackage {
     }
 }
 
+/// Generates typed Rust bindings for a Move package
+///
+/// The bindings contain a builder for each entry function, a caller for each view function, and
+/// serde structs for the structs of the package, e.g. its resources and events. The package is
+/// either compiled from the local package directory, or downloaded from the account given by
+/// `--account`.
+#[derive(Parser)]
+pub struct GenerateRustBindings {
+    /// Address of the account containing the package, if it should be downloaded
+    #[clap(long, parse(try_from_str = crate::common::types::load_account_arg), requires = "package")]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// Name of the package to download
+    #[clap(long, requires = "account")]
+    pub package: Option<String>,
+
+    /// File to write the bindings to
+    #[clap(long, parse(from_os_str))]
+    pub output_file: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<String> for GenerateRustBindings {
+    fn command_name(&self) -> &'static str {
+        "GenerateRustBindings"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let mut bindings = vec![];
+        match (self.account, &self.package) {
+            (Some(account), Some(package)) => {
+                self.generate_onchain_bindings(account, package, &mut bindings)
+                    .await?
+            },
+            _ => self.generate_local_bindings(&mut bindings)?,
+        }
+        write_to_file(self.output_file.as_path(), "Rust bindings", &bindings)?;
+        Ok(format!(
+            "Rust bindings written to {}",
+            self.output_file.display()
+        ))
+    }
+}
+
+impl GenerateRustBindings {
+    fn generate_local_bindings(&self, out: &mut Vec<u8>) -> CliTypedResult<()> {
+        let build_options = BuildOptions {
+            with_srcs: false,
+            with_abis: false,
+            with_source_maps: true,
+            with_error_map: false,
+            with_docs: false,
+            install_dir: self.move_options.output_dir.clone(),
+            named_addresses: self.move_options.named_addresses(),
+            docgen_options: None,
+            skip_fetch_latest_git_deps: self.move_options.skip_fetch_latest_git_deps,
+            bytecode_version: self.move_options.bytecode_version,
+        };
+        let pack = BuiltPackage::build(self.move_options.get_package_path()?, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        pack.generate_rust_bindings(out)
+            .map_err(|e| CliError::UnexpectedError(format!("Failed to generate bindings: {:#}", e)))
+    }
+
+    async fn generate_onchain_bindings(
+        &self,
+        account: AccountAddress,
+        package: &str,
+        out: &mut Vec<u8>,
+    ) -> CliTypedResult<()> {
+        let url = self.rest_options.url(&self.profile_options)?;
+        let client = self.rest_options.client(&self.profile_options)?;
+        let registry = CachedPackageRegistry::create(url, account).await?;
+        let package = registry
+            .get_package(package)
+            .await
+            .map_err(|s| CliError::CommandArgumentError(s.to_string()))?;
+
+        let mut modules = vec![];
+        for name in package.module_names() {
+            let bytes = client
+                .get_account_module_bcs(account, name)
+                .await?
+                .into_inner();
+            let module = CompiledModule::deserialize(&bytes).map_err(|e| {
+                CliError::UnexpectedError(format!("Failed to deserialize module {}: {}", name, e))
+            })?;
+            // Source maps are only available if the package was published with them, and are
+            // only used to name the parameters of the generated functions.
+            let zipped_source_map = package.module(name)?.zipped_source_map_raw().to_vec();
+            let source_map = if zipped_source_map.is_empty() {
+                None
+            } else {
+                unzip_metadata(&zipped_source_map)
+                    .ok()
+                    .and_then(|bytes| bcs::from_bytes::<SourceMap>(&bytes).ok())
+            };
+            modules.push(PackageModule {
+                view_functions: get_view_functions(&module),
+                module,
+                source_map,
+            });
+        }
+        aptos_sdk_builder::bindings::output(out, package.name(), &modules)
+            .map_err(|e| CliError::UnexpectedError(format!("Failed to generate bindings: {:#}", e)))
+    }
+}
+
 /// Downloads a package and verifies the bytecode
 ///
 /// Downloads the package from onchain and verifies the bytecode matches a local compilation of the Move code
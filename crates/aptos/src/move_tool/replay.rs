@@ -0,0 +1,143 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{
+        CliCommand, CliError, CliTypedResult, ProfileOptions, RestOptions, TransactionSummary,
+    },
+    utils::generate_gas_profiling_report,
+};
+use aptos_debugger::AptosDebugger;
+use aptos_rest_client::aptos_api_types::TransactionData;
+use aptos_types::transaction::{Transaction, TransactionStatus};
+use async_trait::async_trait;
+use clap::Parser;
+
+/// Replay a committed transaction locally, against the state right before it was executed
+///
+/// This can be used to inspect the gas usage of historical transactions with `--profile-gas`.
+#[derive(Parser)]
+pub struct Replay {
+    /// Version of the transaction to replay
+    #[clap(long)]
+    pub(crate) txn_id: u64,
+
+    /// If this option is set, replay the transaction with the gas profiler enabled and generate
+    /// an HTML report of the gas usage.
+    #[clap(long)]
+    pub(crate) profile_gas: bool,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for Replay {
+    fn command_name(&self) -> &'static str {
+        "Replay"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let client = self.rest_options.client(&self.profile_options)?;
+        let version = self.txn_id;
+
+        // Fetch the transaction and make sure it is a committed user transaction
+        let txn = match client
+            .get_transaction_by_version_bcs(version)
+            .await?
+            .into_inner()
+        {
+            TransactionData::OnChain(txn) => txn,
+            TransactionData::Pending(_) => {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Transaction {} is not committed yet",
+                    version
+                )));
+            },
+        };
+        let txn_info = txn.info;
+        let txn = match txn.transaction {
+            Transaction::UserTransaction(txn) => txn,
+            _ => {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Transaction {} is not a user transaction",
+                    version
+                )));
+            },
+        };
+        let hash = txn.clone().committed_hash();
+        let sender = txn.sender();
+        let sequence_number = txn.sequence_number();
+        let gas_unit_price = txn.gas_unit_price();
+
+        // Execute the transaction using the debugger
+        let debugger = AptosDebugger::rest_client(client)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let (vm_status, gas_used, status) = if self.profile_gas {
+            println!();
+            println!("Replaying transaction {} with the gas profiler...", version);
+            println!("This is still experimental so results may be inaccurate.");
+
+            let (vm_status, output, gas_log) = debugger
+                .execute_transaction_at_version_with_gas_profiler(version, txn)
+                .map_err(|err| {
+                    CliError::UnexpectedError(format!(
+                        "Failed to replay txn with gas profiler: {}",
+                        err
+                    ))
+                })?;
+
+            println!();
+            let report_path = generate_gas_profiling_report(hash, &gas_log)?;
+            println!("Gas profiling report saved to {}", report_path.display());
+            println!();
+
+            (
+                vm_status.to_string(),
+                output.gas_used(),
+                output.status().clone(),
+            )
+        } else {
+            let output = debugger
+                .execute_transactions_at_version(version, vec![Transaction::UserTransaction(txn)])
+                .map_err(|err| CliError::UnexpectedError(format!("Failed to replay txn: {}", err)))?
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    CliError::UnexpectedError("No output from replaying the txn".to_string())
+                })?;
+            (
+                format!("{:?}", output.status()),
+                output.gas_used(),
+                output.status().clone(),
+            )
+        };
+
+        let success = match &status {
+            TransactionStatus::Keep(exec_status) => Some(exec_status.is_success()),
+            TransactionStatus::Discard(_) | TransactionStatus::Retry => None,
+        };
+        if gas_used != txn_info.gas_used() {
+            eprintln!(
+                "Warning: replay used {} gas units, but {} were used on chain",
+                gas_used,
+                txn_info.gas_used()
+            );
+        }
+
+        Ok(TransactionSummary {
+            transaction_hash: hash.into(),
+            gas_used: Some(gas_used),
+            gas_unit_price: Some(gas_unit_price),
+            pending: None,
+            sender: Some(sender),
+            sequence_number: Some(sequence_number),
+            success,
+            timestamp_us: None,
+            version: Some(version),
+            vm_status: Some(vm_status),
+        })
+    }
+}
@@ -33,7 +33,13 @@ use aptos_logger::Level;
 use aptos_network_checker::args::{
     validate_address, CheckEndpointArgs, HandshakeArgs, NodeAddressArgs,
 };
-use aptos_rest_client::{aptos_api_types::VersionedEvent, Client, State};
+use aptos_rest_client::{
+    aptos_api_types::{
+        Address, HashValue, MempoolTimelineState, MempoolTransaction, MempoolTransactionSubmitter,
+        VersionedEvent,
+    },
+    Client, State,
+};
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{BlockResource, CORE_CODE_ADDRESS},
@@ -56,7 +62,7 @@ use rand::{rngs::StdRng, SeedableRng};
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::{TryFrom, TryInto},
     path::PathBuf,
     pin::Pin,
@@ -82,6 +88,7 @@ pub enum NodeTool {
     JoinValidatorSet(JoinValidatorSet),
     LeaveValidatorSet(LeaveValidatorSet),
     ShowEpochInfo(ShowEpochInfo),
+    ShowMempool(ShowMempool),
     ShowValidatorConfig(ShowValidatorConfig),
     ShowValidatorSet(ShowValidatorSet),
     ShowValidatorStake(ShowValidatorStake),
@@ -106,6 +113,7 @@ impl NodeTool {
             JoinValidatorSet(tool) => tool.execute_serialized().await,
             LeaveValidatorSet(tool) => tool.execute_serialized().await,
             ShowEpochInfo(tool) => tool.execute_serialized().await,
+            ShowMempool(tool) => tool.execute_serialized().await,
             ShowValidatorSet(tool) => tool.execute_serialized().await,
             ShowValidatorStake(tool) => tool.execute_serialized().await,
             ShowValidatorConfig(tool) => tool.execute_serialized().await,
@@ -1651,6 +1659,88 @@ impl Time {
     }
 }
 
+/// Show the transactions pending in mempool
+///
+/// Lists the transactions waiting in the mempool of the node, by sender, along with their state
+/// in mempool. Listing the transactions of all the accounts requires the node to enable the
+/// mempool inspection API, with `mempool_inspection_enabled` in its API config.
+#[derive(Parser)]
+pub struct ShowMempool {
+    /// Only show the transactions of this account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) account: Option<AccountAddress>,
+    /// Maximum number of transactions to show
+    ///
+    /// Defaults to the maximum number of transactions returned by the node
+    #[clap(long)]
+    pub(crate) limit: Option<u16>,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<BTreeMap<Address, Vec<PendingTransactionSummary>>> for ShowMempool {
+    fn command_name(&self) -> &'static str {
+        "ShowMempool"
+    }
+
+    async fn execute(self) -> CliTypedResult<BTreeMap<Address, Vec<PendingTransactionSummary>>> {
+        let client = self.rest_options.client(&self.profile_options)?;
+        let txns = match self.account {
+            Some(account) => client
+                .get_account_pending_transactions(account)
+                .await?
+                .into_inner()
+                .into_iter()
+                .take(self.limit.map_or(usize::MAX, usize::from))
+                .collect(),
+            None => client
+                .get_mempool_transactions(self.limit)
+                .await?
+                .into_inner(),
+        };
+
+        let mut txns_by_sender: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for txn in txns {
+            txns_by_sender
+                .entry(txn.transaction.request.sender)
+                .or_default()
+                .push(PendingTransactionSummary::from(txn));
+        }
+        Ok(txns_by_sender)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PendingTransactionSummary {
+    sequence_number: u64,
+    hash: HashValue,
+    gas_unit_price: u64,
+    ranking_score: u64,
+    timeline_state: MempoolTimelineState,
+    parked: bool,
+    submitted_by: MempoolTransactionSubmitter,
+    insertion_time: Time,
+}
+
+impl From<MempoolTransaction> for PendingTransactionSummary {
+    fn from(txn: MempoolTransaction) -> Self {
+        let request = &txn.transaction.request;
+        Self {
+            sequence_number: request.sequence_number.0,
+            hash: txn.transaction.hash,
+            gas_unit_price: request.gas_unit_price.0,
+            ranking_score: txn.ranking_score.0,
+            timeline_state: txn.timeline_state,
+            parked: txn.parked,
+            submitted_by: txn.submitted_by,
+            insertion_time: Time::new_micros(txn.insertion_timestamp_usecs.0),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{CliResult, Tool};
@@ -547,6 +547,7 @@ impl CliTestFramework {
             prompt_options: PromptOptions::yes(),
             encoding_options: EncodingOptions::default(),
             skip_faucet: false,
+            key_type: KeyType::Ed25519,
         }
         .execute()
         .await
@@ -850,6 +851,8 @@ impl CliTestFramework {
             ignore_compile_warnings: false,
             compute_coverage: false,
             dump_state: false,
+            seed: None,
+            random_iterations: 100,
         }
         .execute()
         .await
@@ -57,6 +57,7 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "move", "compile", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "compile-script", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "download", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "move", "fmt", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "init", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "list", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "prove", "--help"]).await;
@@ -109,6 +110,49 @@ async fn ensure_can_parse_args_with_urls() {
     );
 }
 
+/// Ensure `aptos move fmt` only checks the files with `--check`, and formats them in place otherwise
+#[tokio::test]
+async fn format_package() {
+    let package_dir = tempfile::tempdir().unwrap();
+    let sources_dir = package_dir.path().join("sources");
+    std::fs::create_dir(&sources_dir).unwrap();
+    let source_file = sources_dir.join("m.move");
+    let source = "module 0x1::m {\n  fun f(x:u64):u64{x+1}\n}\n";
+    std::fs::write(&source_file, source).unwrap();
+    let package_dir = package_dir.path().to_str().unwrap();
+
+    let result = run_cmd(&[
+        "aptos",
+        "move",
+        "fmt",
+        "--package-dir",
+        package_dir,
+        "--check",
+    ])
+    .await;
+    assert!(result.unwrap_err().contains("Files are not formatted"));
+    assert_eq!(std::fs::read_to_string(&source_file).unwrap(), source);
+
+    run_cmd(&["aptos", "move", "fmt", "--package-dir", package_dir])
+        .await
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&source_file).unwrap(),
+        "module 0x1::m {\n    fun f(x: u64): u64 { x + 1 }\n}\n"
+    );
+
+    run_cmd(&[
+        "aptos",
+        "move",
+        "fmt",
+        "--package-dir",
+        package_dir,
+        "--check",
+    ])
+    .await
+    .unwrap();
+}
+
 async fn assert_cmd_not_panic(args: &[&str]) {
     // When a command fails, it will have a panic in it due to an improperly setup command
     // thread 'main' panicked at 'Command propose: Argument names must be unique, but 'assume-yes' is
@@ -6,8 +6,9 @@ use crate::{models::transactions::Transaction, schema::signatures, util::standar
 use anyhow::{Context, Result};
 use aptos_api_types::{
     AccountSignature as APIAccountSignature, Ed25519Signature as APIEd25519Signature,
-    MultiAgentSignature as APIMultiAgentSignature,
-    MultiEd25519Signature as APIMultiEd25519Signature,
+    FeePayerSignature as APIFeePayerSignature, MultiAgentSignature as APIMultiAgentSignature,
+    MultiEd25519Signature as APIMultiEd25519Signature, MultiKeySignature as APIMultiKeySignature,
+    Secp256k1EcdsaSignature as APISecp256k1EcdsaSignature,
     TransactionSignature as APITransactionSignature,
 };
 use aptos_bitvec::BitVec;
@@ -74,6 +75,32 @@ impl Signature {
                 transaction_version,
                 transaction_block_height,
             ),
+            APITransactionSignature::FeePayerSignature(sig) => Self::parse_fee_payer_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+            ),
+            APITransactionSignature::Secp256k1EcdsaSignature(sig) => {
+                Ok(vec![Self::parse_secp256k1_ecdsa_signature(
+                    sig,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    true,
+                    0,
+                    None,
+                )])
+            },
+            APITransactionSignature::MultiKeySignature(sig) => Ok(Self::parse_multi_key_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                true,
+                0,
+                None,
+            )),
         }
     }
 
@@ -86,6 +113,11 @@ impl Signature {
             APITransactionSignature::MultiAgentSignature(_) => {
                 String::from("multi_agent_signature")
             },
+            APITransactionSignature::FeePayerSignature(_) => String::from("fee_payer_signature"),
+            APITransactionSignature::Secp256k1EcdsaSignature(_) => {
+                String::from("secp256k1_ecdsa_signature")
+            },
+            APITransactionSignature::MultiKeySignature(_) => String::from("multi_key_signature"),
         }
     }
 
@@ -114,6 +146,31 @@ impl Signature {
         }
     }
 
+    fn parse_secp256k1_ecdsa_signature(
+        s: &APISecp256k1EcdsaSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Self {
+        let signer = standardize_address(override_address.unwrap_or(sender));
+        Self {
+            transaction_version,
+            transaction_block_height,
+            signer,
+            is_sender_primary,
+            type_: String::from("secp256k1_ecdsa_signature"),
+            public_key: s.public_key.to_string(),
+            threshold: 1,
+            public_key_indices: serde_json::Value::Array(vec![]),
+            signature: s.signature.to_string(),
+            multi_agent_index,
+            multi_sig_index: 0,
+        }
+    }
+
     fn parse_multi_signature(
         s: &APIMultiEd25519Signature,
         sender: &String,
@@ -157,6 +214,49 @@ impl Signature {
         signatures
     }
 
+    fn parse_multi_key_signature(
+        s: &APIMultiKeySignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Vec<Self> {
+        let mut signatures = Vec::default();
+        let signer = standardize_address(override_address.unwrap_or(sender));
+
+        let public_key_indices: Vec<usize> = BitVec::from(s.bitmap.0.clone()).iter_ones().collect();
+        for (index, signature) in s.signatures.iter().enumerate() {
+            let public_key = s
+                .public_keys
+                .get(public_key_indices.clone()[index])
+                .unwrap()
+                .clone();
+            signatures.push(Self {
+                transaction_version,
+                transaction_block_height,
+                signer: signer.clone(),
+                is_sender_primary,
+                type_: String::from("multi_key_signature"),
+                public_key: public_key.public_key.to_string(),
+                threshold: s.signatures_required as i64,
+                signature: signature.signature.to_string(),
+                public_key_indices: serde_json::Value::Array(
+                    public_key_indices
+                        .iter()
+                        .map(|index| {
+                            serde_json::Value::Number(serde_json::Number::from(*index as i64))
+                        })
+                        .collect(),
+                ),
+                multi_agent_index,
+                multi_sig_index: index as i64,
+            });
+        }
+        signatures
+    }
+
     fn parse_multi_agent_signature(
         s: &APIMultiAgentSignature,
         sender: &String,
@@ -192,6 +292,51 @@ impl Signature {
         Ok(signatures)
     }
 
+    fn parse_fee_payer_signature(
+        s: &APIFeePayerSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+    ) -> Result<Vec<Self>> {
+        let mut signatures = Vec::default();
+        // process sender signature
+        signatures.append(&mut Self::parse_multi_agent_signature_helper(
+            &s.sender,
+            sender,
+            transaction_version,
+            transaction_block_height,
+            true,
+            0,
+            None,
+        ));
+        for (index, address) in s.secondary_signer_addresses.iter().enumerate() {
+            let secondary_sig = s.secondary_signers.get(index).context(format!(
+                "Failed to parse index {} for fee payer secondary signers",
+                index
+            ))?;
+            signatures.append(&mut Self::parse_multi_agent_signature_helper(
+                secondary_sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                false,
+                index as i64,
+                Some(&address.to_string()),
+            ));
+        }
+        // process fee payer signature, indexed after the secondary signers
+        signatures.append(&mut Self::parse_multi_agent_signature_helper(
+            &s.fee_payer_signer,
+            sender,
+            transaction_version,
+            transaction_block_height,
+            false,
+            s.secondary_signer_addresses.len() as i64,
+            Some(&s.fee_payer_address.to_string()),
+        ));
+        Ok(signatures)
+    }
+
     fn parse_multi_agent_signature_helper(
         s: &APIAccountSignature,
         sender: &String,
@@ -220,6 +365,26 @@ impl Signature {
                 multi_agent_index,
                 override_address,
             ),
+            APIAccountSignature::Secp256k1EcdsaSignature(sig) => {
+                vec![Self::parse_secp256k1_ecdsa_signature(
+                    sig,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    is_sender_primary,
+                    multi_agent_index,
+                    override_address,
+                )]
+            },
+            APIAccountSignature::MultiKeySignature(sig) => Self::parse_multi_key_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                is_sender_primary,
+                multi_agent_index,
+                override_address,
+            ),
         }
     }
 }
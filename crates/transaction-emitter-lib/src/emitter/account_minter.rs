@@ -4,16 +4,12 @@
 use crate::{EmitJobRequest, EmitModeParams};
 use anyhow::{anyhow, bail, format_err, Context, Result};
 use aptos::common::{types::EncodingType, utils::prompt_yes};
-use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use aptos_crypto::ed25519::Ed25519PrivateKey;
 use aptos_logger::{error, info};
 use aptos_sdk::{
     transaction_builder::{aptos_stdlib, TransactionFactory},
     types::{
-        transaction::{
-            authenticator::{AuthenticationKey, AuthenticationKeyPreimage},
-            SignedTransaction,
-        },
-        AccountKey, LocalAccount,
+        account_address::AccountAddress, transaction::SignedTransaction, AccountKey, LocalAccount,
     },
 };
 use aptos_transaction_generator_lib::{CounterState, ReliableTransactionSubmitter, SEND_AMOUNT};
@@ -299,7 +295,7 @@ impl<'t> AccountMinter<'t> {
                             self.source_account
                         },
                         coins_per_seed_account,
-                        account.public_key(),
+                        account.address(),
                         txn_factory,
                     )
                 })
@@ -353,7 +349,7 @@ impl<'t> AccountMinter<'t> {
             let txn = create_and_fund_account_request(
                 self.source_account,
                 coins_for_source,
-                new_source_account.public_key(),
+                new_source_account.address(),
                 &self.txn_factory,
             );
             if let Err(e) = txn_executor.execute_transactions(&[txn]).await {
@@ -427,7 +423,7 @@ where
                     create_and_fund_account_request(
                         &mut source_account,
                         coins_per_new_account,
-                        account.public_key(),
+                        account.address(),
                         txn_factory,
                     )
                 })
@@ -489,14 +485,12 @@ where
 pub fn create_and_fund_account_request(
     creation_account: &mut LocalAccount,
     amount: u64,
-    pubkey: &Ed25519PublicKey,
+    address: AccountAddress,
     txn_factory: &TransactionFactory,
 ) -> SignedTransaction {
-    let preimage = AuthenticationKeyPreimage::ed25519(pubkey);
-    let auth_key = AuthenticationKey::from_preimage(&preimage);
-    creation_account.sign_with_transaction_builder(txn_factory.payload(
-        aptos_stdlib::aptos_account_transfer(auth_key.derived_address(), amount),
-    ))
+    creation_account.sign_with_transaction_builder(
+        txn_factory.payload(aptos_stdlib::aptos_account_transfer(address, amount)),
+    )
 }
 
 const CREATION_PARALLELISM: usize = 500;
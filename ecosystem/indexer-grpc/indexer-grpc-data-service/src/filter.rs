@@ -0,0 +1,456 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_protos::{
+    indexer::v1::{EntryFunctionFilter, TransactionFilter},
+    transaction::v1::{
+        transaction::TxnData, transaction_payload::Payload, write_set_change::Change, Event,
+        Transaction,
+    },
+};
+
+const MAX_ADDRESS_HEX_LENGTH: usize = 64;
+
+/// The filters of a get transactions request, validated and normalized once when the request is
+/// received. A transaction matches if it matches any of the filters, and every transaction
+/// matches if there is no filter.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransactionFilters {
+    filters: Vec<CompiledTransactionFilter>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct CompiledTransactionFilter {
+    sender: Option<String>,
+    entry_function: Option<CompiledEntryFunctionFilter>,
+    event_type: Option<TypeFilter>,
+    write_set_resource_type: Option<TypeFilter>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct CompiledEntryFunctionFilter {
+    address: Option<String>,
+    module: Option<String>,
+    function: Option<String>,
+}
+
+/// A type string with its addresses in the short form, as they are in the transactions, and
+/// without whitespace. If it has no type arguments, it matches any instantiation of the type.
+#[derive(Clone, Debug, PartialEq)]
+struct TypeFilter {
+    type_str: String,
+    match_any_type_arguments: bool,
+}
+
+impl TransactionFilters {
+    pub fn new(filters: &[TransactionFilter]) -> anyhow::Result<Self> {
+        Ok(Self {
+            filters: filters
+                .iter()
+                .map(CompiledTransactionFilter::new)
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        self.is_empty()
+            || self
+                .filters
+                .iter()
+                .any(|filter| filter.matches(transaction))
+    }
+}
+
+impl CompiledTransactionFilter {
+    fn new(filter: &TransactionFilter) -> anyhow::Result<Self> {
+        let compiled = Self {
+            sender: filter.sender.as_deref().map(parse_address).transpose()?,
+            entry_function: filter
+                .entry_function
+                .as_ref()
+                .map(CompiledEntryFunctionFilter::new)
+                .transpose()?,
+            event_type: filter
+                .event_type
+                .as_deref()
+                .map(TypeFilter::new)
+                .transpose()?,
+            write_set_resource_type: filter
+                .write_set_resource_type
+                .as_deref()
+                .map(TypeFilter::new)
+                .transpose()?,
+        };
+        anyhow::ensure!(
+            compiled != Self::default(),
+            "Transaction filter must have at least one field set."
+        );
+        Ok(compiled)
+    }
+
+    fn matches(&self, transaction: &Transaction) -> bool {
+        let user_transaction = match &transaction.txn_data {
+            Some(TxnData::User(user_transaction)) => Some(user_transaction),
+            _ => None,
+        };
+        let request = user_transaction.and_then(|txn| txn.request.as_ref());
+
+        if let Some(sender) = &self.sender {
+            match request {
+                Some(request) if address_eq(&request.sender, sender) => {},
+                _ => return false,
+            }
+        }
+        if let Some(entry_function) = &self.entry_function {
+            let payload = request
+                .and_then(|request| request.payload.as_ref())
+                .and_then(|payload| payload.payload.as_ref());
+            match payload {
+                Some(Payload::EntryFunctionPayload(payload))
+                    if entry_function.matches(payload.function.as_ref()) => {},
+                _ => return false,
+            }
+        }
+        if let Some(event_type) = &self.event_type {
+            if !transaction_events(transaction)
+                .iter()
+                .any(|event| event_type.matches(&event.type_str))
+            {
+                return false;
+            }
+        }
+        if let Some(resource_type) = &self.write_set_resource_type {
+            let changes = transaction
+                .info
+                .as_ref()
+                .map_or(&[][..], |info| info.changes.as_slice());
+            if !changes.iter().any(|change| match &change.change {
+                Some(Change::WriteResource(resource)) => resource_type.matches(&resource.type_str),
+                Some(Change::DeleteResource(resource)) => resource_type.matches(&resource.type_str),
+                _ => false,
+            }) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl CompiledEntryFunctionFilter {
+    fn new(filter: &EntryFunctionFilter) -> anyhow::Result<Self> {
+        let compiled = Self {
+            address: filter.address.as_deref().map(parse_address).transpose()?,
+            module: filter.module.clone(),
+            function: filter.function.clone(),
+        };
+        anyhow::ensure!(
+            compiled != Self::default(),
+            "Entry function filter must have at least one field set."
+        );
+        Ok(compiled)
+    }
+
+    fn matches(&self, function: Option<&aptos_protos::transaction::v1::EntryFunctionId>) -> bool {
+        let function = match function {
+            Some(function) => function,
+            None => return false,
+        };
+        let module = function.module.as_ref();
+        if let Some(address) = &self.address {
+            if !module.map_or(false, |module| address_eq(&module.address, address)) {
+                return false;
+            }
+        }
+        if let Some(module_name) = &self.module {
+            if module.map(|module| &module.name) != Some(module_name) {
+                return false;
+            }
+        }
+        if let Some(function_name) = &self.function {
+            if &function.name != function_name {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl TypeFilter {
+    fn new(type_str: &str) -> anyhow::Result<Self> {
+        let type_str: String = type_str.chars().filter(|c| !c.is_whitespace()).collect();
+        anyhow::ensure!(
+            type_str.split("::").count() >= 3,
+            "Invalid type filter {}, expected <address>::<module>::<name>.",
+            type_str
+        );
+        Ok(Self {
+            match_any_type_arguments: !type_str.contains('<'),
+            type_str: shorten_addresses(&type_str)?,
+        })
+    }
+
+    fn matches(&self, type_str: &str) -> bool {
+        // The type strings of the transactions separate type arguments with ", ", so whitespace
+        // is ignored on both sides.
+        let mut type_chars = type_str.chars().filter(|c| !c.is_whitespace());
+        if !self
+            .type_str
+            .chars()
+            .all(|expected| type_chars.next() == Some(expected))
+        {
+            return false;
+        }
+        match type_chars.next() {
+            None => true,
+            Some(c) => self.match_any_type_arguments && c == '<',
+        }
+    }
+}
+
+fn transaction_events(transaction: &Transaction) -> &[Event] {
+    match &transaction.txn_data {
+        Some(TxnData::User(txn)) => &txn.events,
+        Some(TxnData::BlockMetadata(txn)) => &txn.events,
+        Some(TxnData::Genesis(txn)) => &txn.events,
+        _ => &[],
+    }
+}
+
+/// Parses a hex address, with or without the 0x prefix and leading zeros, into its short form,
+/// e.g. 0x1.
+fn parse_address(address: &str) -> anyhow::Result<String> {
+    let hex = address.strip_prefix("0x").unwrap_or(address);
+    anyhow::ensure!(
+        !hex.is_empty()
+            && hex.len() <= MAX_ADDRESS_HEX_LENGTH
+            && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        "Invalid address {}.",
+        address
+    );
+    let trimmed = hex.trim_start_matches('0').to_ascii_lowercase();
+    Ok(if trimmed.is_empty() {
+        "0x0".to_string()
+    } else {
+        format!("0x{}", trimmed)
+    })
+}
+
+fn address_eq(address: &str, expected_short_address: &str) -> bool {
+    parse_address(address).map_or(false, |address| address == expected_short_address)
+}
+
+/// Rewrites the addresses of a type string in their short form, which is how the types of the
+/// transactions are formatted.
+fn shorten_addresses(type_str: &str) -> anyhow::Result<String> {
+    let mut shortened = String::with_capacity(type_str.len());
+    let mut token = String::new();
+    for c in type_str.chars().chain(std::iter::once('\0')) {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            token.push(c);
+            continue;
+        }
+        if token.starts_with("0x") {
+            shortened.push_str(&parse_address(&token)?);
+        } else {
+            shortened.push_str(&token);
+        }
+        token.clear();
+        if c != '\0' {
+            shortened.push(c);
+        }
+    }
+    Ok(shortened)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::{
+        EntryFunctionId, EntryFunctionPayload, MoveModuleId, TransactionInfo, TransactionPayload,
+        UserTransaction, UserTransactionRequest, WriteResource, WriteSetChange,
+    };
+
+    fn user_transaction(
+        sender: &str,
+        function: (&str, &str, &str),
+        event_type: &str,
+    ) -> Transaction {
+        Transaction {
+            info: Some(TransactionInfo {
+                changes: vec![WriteSetChange {
+                    change: Some(Change::WriteResource(WriteResource {
+                        type_str: "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>".to_string(),
+                        ..WriteResource::default()
+                    })),
+                    ..WriteSetChange::default()
+                }],
+                ..TransactionInfo::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(Payload::EntryFunctionPayload(EntryFunctionPayload {
+                            function: Some(EntryFunctionId {
+                                module: Some(MoveModuleId {
+                                    address: function.0.to_string(),
+                                    name: function.1.to_string(),
+                                }),
+                                name: function.2.to_string(),
+                            }),
+                            ..EntryFunctionPayload::default()
+                        })),
+                        ..TransactionPayload::default()
+                    }),
+                    ..UserTransactionRequest::default()
+                }),
+                events: vec![Event {
+                    type_str: event_type.to_string(),
+                    ..Event::default()
+                }],
+            })),
+            ..Transaction::default()
+        }
+    }
+
+    #[test]
+    fn test_no_filter_matches_everything() {
+        let filters = TransactionFilters::new(&[]).unwrap();
+        assert!(filters.matches(&Transaction::default()));
+    }
+
+    #[test]
+    fn test_filter_fields_are_combined() {
+        let transaction = user_transaction(
+            "0xcafe",
+            ("0x1", "coin", "transfer"),
+            "0x1::coin::DepositEvent",
+        );
+        let matching = TransactionFilter {
+            sender: Some(
+                "0x000000000000000000000000000000000000000000000000000000000000CAFE".to_string(),
+            ),
+            entry_function: Some(EntryFunctionFilter {
+                address: Some("1".to_string()),
+                module: Some("coin".to_string()),
+                function: None,
+            }),
+            event_type: Some("0x01::coin::DepositEvent".to_string()),
+            write_set_resource_type: Some("0x1::coin::CoinStore".to_string()),
+        };
+        assert!(TransactionFilters::new(&[matching.clone()])
+            .unwrap()
+            .matches(&transaction));
+
+        let other_function = TransactionFilter {
+            entry_function: Some(EntryFunctionFilter {
+                function: Some("mint".to_string()),
+                ..EntryFunctionFilter::default()
+            }),
+            ..matching.clone()
+        };
+        assert!(!TransactionFilters::new(&[other_function.clone()])
+            .unwrap()
+            .matches(&transaction));
+        assert!(TransactionFilters::new(&[other_function, matching])
+            .unwrap()
+            .matches(&transaction));
+        assert!(!TransactionFilters::new(&[TransactionFilter {
+            sender: Some("0xcafe".to_string()),
+            ..TransactionFilter::default()
+        }])
+        .unwrap()
+        .matches(&Transaction::default()));
+    }
+
+    #[test]
+    fn test_type_filter() {
+        let filter = TypeFilter::new("0x1::coin::CoinStore").unwrap();
+        assert!(filter.matches("0x1::coin::CoinStore"));
+        assert!(filter.matches("0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"));
+        assert!(!filter.matches("0x1::coin::CoinStoreV2"));
+
+        let filter =
+            TypeFilter::new("0x1::coin::CoinStore<0x0001::aptos_coin::AptosCoin>").unwrap();
+        assert!(filter.matches("0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"));
+        assert!(!filter.matches("0x1::coin::CoinStore<0xcafe::moon_coin::MoonCoin>"));
+
+        let filter = TypeFilter::new("0xcafe::pool::Pool<0x1::aptos_coin::AptosCoin,u64>").unwrap();
+        assert!(filter.matches("0xcafe::pool::Pool<0x1::aptos_coin::AptosCoin, u64>"));
+        assert!(!filter.matches("0xcafe::pool::Pool<0x1::aptos_coin::AptosCoin, u8>"));
+    }
+
+    #[test]
+    fn test_filter_types_with_several_type_arguments() {
+        // Type arguments are separated by ", " in the transactions.
+        let mut transaction = user_transaction(
+            "0xcafe",
+            ("0xcafe", "pool", "swap"),
+            "0xcafe::pool::SwapEvent<0x1::aptos_coin::AptosCoin, 0xcafe::moon_coin::MoonCoin>",
+        );
+        if let Some(Change::WriteResource(resource)) =
+            &mut transaction.info.as_mut().unwrap().changes[0].change
+        {
+            resource.type_str =
+                "0xcafe::pool::Pool<0x1::aptos_coin::AptosCoin, 0xcafe::moon_coin::MoonCoin>"
+                    .to_string();
+        }
+
+        for event_type in [
+            "0xcafe::pool::SwapEvent<0x1::aptos_coin::AptosCoin,0xcafe::moon_coin::MoonCoin>",
+            "0xcafe::pool::SwapEvent<0x1::aptos_coin::AptosCoin, 0xcafe::moon_coin::MoonCoin>",
+            "0xcafe::pool::SwapEvent",
+        ] {
+            assert!(TransactionFilters::new(&[TransactionFilter {
+                event_type: Some(event_type.to_string()),
+                ..TransactionFilter::default()
+            }])
+            .unwrap()
+            .matches(&transaction));
+        }
+        assert!(!TransactionFilters::new(&[TransactionFilter {
+            event_type: Some(
+                "0xcafe::pool::SwapEvent<0xcafe::moon_coin::MoonCoin, 0x1::aptos_coin::AptosCoin>"
+                    .to_string()
+            ),
+            ..TransactionFilter::default()
+        }])
+        .unwrap()
+        .matches(&transaction));
+
+        assert!(TransactionFilters::new(&[TransactionFilter {
+            write_set_resource_type: Some(
+                "0xcafe::pool::Pool<0x01::aptos_coin::AptosCoin,0xCAFE::moon_coin::MoonCoin>"
+                    .to_string()
+            ),
+            ..TransactionFilter::default()
+        }])
+        .unwrap()
+        .matches(&transaction));
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        for filter in [
+            TransactionFilter::default(),
+            TransactionFilter {
+                sender: Some("0xzz".to_string()),
+                ..TransactionFilter::default()
+            },
+            TransactionFilter {
+                entry_function: Some(EntryFunctionFilter::default()),
+                ..TransactionFilter::default()
+            },
+            TransactionFilter {
+                event_type: Some("coin::DepositEvent".to_string()),
+                ..TransactionFilter::default()
+            },
+        ] {
+            assert!(TransactionFilters::new(&[filter]).is_err());
+        }
+    }
+}
@@ -1,9 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics::{
-    ERROR_COUNT, LATEST_PROCESSED_VERSION, OBSERVED_LATEST_PROCESSED_VERSION, PROCESSED_BATCH_SIZE,
-    PROCESSED_LATENCY_IN_SECS, PROCESSED_LATENCY_IN_SECS_ALL, PROCESSED_VERSIONS_COUNT,
+use crate::{
+    filter::TransactionFilters,
+    metrics::{
+        ERROR_COUNT, LATEST_PROCESSED_VERSION, OBSERVED_LATEST_PROCESSED_VERSION,
+        PROCESSED_BATCH_SIZE, PROCESSED_LATENCY_IN_SECS, PROCESSED_LATENCY_IN_SECS_ALL,
+        PROCESSED_VERSIONS_COUNT,
+    },
 };
 use aptos_indexer_grpc_utils::{
     build_protobuf_encoded_transaction_wrappers,
@@ -94,8 +98,11 @@ impl RawData for RawDataServerWrapper {
     ///    1.2. If the data is not in cache, fetch the data from file store.
     ///    1.3. If the data is not in file store, stream connection will break.
     ///    1.4  If error happens, retry after a short sleep.
-    /// 2. Push data into channel to stream to the client.
-    ///    2.1. If the channel is full, do not fetch and retry after a short sleep.
+    /// 2. Filter the data if the request has filters.
+    /// 3. Push data into channel to stream to the client.
+    ///    3.1. If the channel is full, do not fetch and retry after a short sleep.
+    ///    3.2. The batch is sent even if no transaction matches the filters, so that the client
+    ///         still learns the last processed version.
     async fn get_transactions(
         &self,
         req: Request<GetTransactionsRequest>,
@@ -107,7 +114,12 @@ impl RawData for RawDataServerWrapper {
         };
         let request = req.into_inner();
 
+        // Number of versions to scan, whether they match the filters or not.
         let transactions_count = request.transactions_count;
+        let filters = match TransactionFilters::new(&request.filters) {
+            Ok(filters) => filters,
+            Err(e) => return Result::Err(Status::invalid_argument(e.to_string())),
+        };
 
         // Response channel to stream the data to the client.
         let (tx, rx) = channel(MAX_RESPONSE_CHANNEL_SIZE);
@@ -227,19 +239,23 @@ impl RawData for RawDataServerWrapper {
                             transactions_count = Some(count - transaction_data.len() as u64);
                         }
                     };
-                    // 2. Push the data to the response channel, i.e. stream the data to the client.
-                    let resp_item =
-                        get_transactions_response_builder(transaction_data, chain_id as u32);
+                    // The batch may be empty once filtered, so the version is taken beforehand.
+                    let end_of_batch_version = transaction_data.last().unwrap().1;
+                    let scanned_batch_size = transaction_data.len();
+                    // 2. Filter the data.
+                    let resp_item = get_transactions_response_builder(
+                        transaction_data,
+                        chain_id as u32,
+                        end_of_batch_version,
+                        &filters,
+                    );
                     let current_batch_size = resp_item.transactions.as_slice().len();
-                    let end_of_batch_version =
-                        resp_item.transactions.as_slice().last().unwrap().version;
                     let data_latency_in_secs = resp_item
                         .transactions
                         .first()
-                        .unwrap()
-                        .timestamp
-                        .as_ref()
+                        .and_then(|transaction| transaction.timestamp.as_ref())
                         .map(time_diff_since_pb_timestamp_in_secs);
+                    // 3. Push the data to the response channel, i.e. stream the data to the client.
                     match tx.send_timeout(Result::<TransactionsResponse, Status>::Ok(resp_item), RESPONSE_CHANNEL_SEND_TIMEOUT).await {
                         Ok(_) => {
                             PROCESSED_BATCH_SIZE
@@ -263,7 +279,7 @@ impl RawData for RawDataServerWrapper {
                             if let Some(data_latency_in_secs) = data_latency_in_secs {
                                 // If it's a partial batch, we record the latency because it usually means
                                 // the data is the latest.
-                                if current_batch_size % BLOB_STORAGE_SIZE != 0 {
+                                if scanned_batch_size % BLOB_STORAGE_SIZE != 0 {
                                     PROCESSED_LATENCY_IN_SECS
                                         .with_label_values(&[
                                             request_metadata.request_token.as_str(),
@@ -285,8 +301,8 @@ impl RawData for RawDataServerWrapper {
                             break;
                         },
                     }
-                    // 3. Update the current version and record current tps.
-                    tps_calculator.tick_now(current_batch_size as u64);
+                    // 4. Update the current version and record current tps.
+                    tps_calculator.tick_now(scanned_batch_size as u64);
                     current_version = end_of_batch_version + 1;
                     if watch_sender.send(current_version).is_err() {
                         error!(
@@ -347,12 +363,16 @@ impl RawData for RawDataServerWrapper {
 }
 
 /// Builds the response for the get transactions request. Partial batch is ok, i.e., a batch with transactions < 1000.
+/// Only the transactions matching the filters are kept, so the batch can be empty.
 fn get_transactions_response_builder(
     data: Vec<EncodedTransactionWithVersion>,
     chain_id: u32,
+    processed_version: u64,
+    filters: &TransactionFilters,
 ) -> TransactionsResponse {
     TransactionsResponse {
         chain_id: Some(chain_id as u64),
+        processed_version: Some(processed_version),
         transactions: data
             .into_iter()
             .map(|(encoded, _)| {
@@ -360,6 +380,7 @@ fn get_transactions_response_builder(
                 let transaction = Transaction::decode(&*decoded_transaction);
                 transaction.unwrap()
             })
+            .filter(|transaction| filters.matches(transaction))
             .collect(),
     }
 }
@@ -6,7 +6,8 @@ use aptos_api_types::{
     EntryFunctionPayload, Event, GenesisPayload, MoveAbility, MoveFunction,
     MoveFunctionGenericTypeParam, MoveFunctionVisibility, MoveModule, MoveModuleBytecode,
     MoveModuleId, MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType,
-    MultiEd25519Signature, MultisigPayload, MultisigTransactionPayload, ScriptPayload, Transaction,
+    MultiEd25519Signature, MultiKeyMemberScheme, MultiKeySignature, MultisigPayload,
+    MultisigTransactionPayload, ScriptPayload, Secp256k1EcdsaSignature, Transaction,
     TransactionInfo, TransactionPayload, TransactionSignature, WriteSet, WriteSetChange,
 };
 use aptos_bitvec::BitVec;
@@ -541,6 +542,15 @@ pub fn convert_ed25519_signature(sig: &Ed25519Signature) -> transaction::Ed25519
     }
 }
 
+pub fn convert_secp256k1_ecdsa_signature(
+    sig: &Secp256k1EcdsaSignature,
+) -> transaction::Secp256k1EcdsaSignature {
+    transaction::Secp256k1EcdsaSignature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+    }
+}
+
 pub fn convert_multi_ed25519_signature(
     sig: &MultiEd25519Signature,
 ) -> transaction::MultiEd25519Signature {
@@ -556,6 +566,43 @@ pub fn convert_multi_ed25519_signature(
     }
 }
 
+pub fn convert_multi_key_signature(sig: &MultiKeySignature) -> transaction::MultiKeySignature {
+    let public_key_indices: Vec<usize> = BitVec::from(sig.bitmap.0.clone()).iter_ones().collect();
+    transaction::MultiKeySignature {
+        public_keys: sig
+            .public_keys
+            .iter()
+            .map(|pk| transaction::AnyPublicKey {
+                r#type: match pk.scheme {
+                    MultiKeyMemberScheme::Ed25519 => transaction::any_public_key::Type::Ed25519,
+                    MultiKeyMemberScheme::Secp256k1Ecdsa => {
+                        transaction::any_public_key::Type::Secp256k1Ecdsa
+                    },
+                } as i32,
+                public_key: pk.public_key.0.clone(),
+            })
+            .collect(),
+        signatures: sig
+            .signatures
+            .iter()
+            .map(|sig| transaction::AnySignature {
+                r#type: match sig.scheme {
+                    MultiKeyMemberScheme::Ed25519 => transaction::any_signature::Type::Ed25519,
+                    MultiKeyMemberScheme::Secp256k1Ecdsa => {
+                        transaction::any_signature::Type::Secp256k1Ecdsa
+                    },
+                } as i32,
+                signature: sig.signature.0.clone(),
+            })
+            .collect(),
+        signatures_required: sig.signatures_required as u32,
+        public_key_indices: public_key_indices
+            .iter()
+            .map(|index| *index as u32)
+            .collect(),
+    }
+}
+
 pub fn convert_account_signature(
     account_signature: &AccountSignature,
 ) -> transaction::AccountSignature {
@@ -564,6 +611,10 @@ pub fn convert_account_signature(
         AccountSignature::MultiEd25519Signature(_) => {
             transaction::account_signature::Type::MultiEd25519
         },
+        AccountSignature::Secp256k1EcdsaSignature(_) => {
+            transaction::account_signature::Type::Secp256k1Ecdsa
+        },
+        AccountSignature::MultiKeySignature(_) => transaction::account_signature::Type::MultiKey,
     };
     let signature = match account_signature {
         AccountSignature::Ed25519Signature(s) => {
@@ -574,6 +625,14 @@ pub fn convert_account_signature(
                 convert_multi_ed25519_signature(s),
             )
         },
+        AccountSignature::Secp256k1EcdsaSignature(s) => {
+            transaction::account_signature::Signature::Secp256k1Ecdsa(
+                convert_secp256k1_ecdsa_signature(s),
+            )
+        },
+        AccountSignature::MultiKeySignature(s) => {
+            transaction::account_signature::Signature::MultiKey(convert_multi_key_signature(s))
+        },
     };
     transaction::AccountSignature {
         r#type: r#type as i32,
@@ -594,6 +653,11 @@ pub fn convert_transaction_signature(
             transaction::signature::Type::MultiEd25519
         },
         TransactionSignature::MultiAgentSignature(_) => transaction::signature::Type::MultiAgent,
+        TransactionSignature::FeePayerSignature(_) => transaction::signature::Type::FeePayer,
+        TransactionSignature::Secp256k1EcdsaSignature(_) => {
+            transaction::signature::Type::Secp256k1Ecdsa
+        },
+        TransactionSignature::MultiKeySignature(_) => transaction::signature::Type::MultiKey,
     };
 
     let signature = match signature {
@@ -618,6 +682,29 @@ pub fn convert_transaction_signature(
                     .collect(),
             })
         },
+        TransactionSignature::FeePayerSignature(s) => {
+            transaction::signature::Signature::FeePayer(transaction::FeePayerSignature {
+                sender: Some(convert_account_signature(&s.sender)),
+                secondary_signer_addresses: s
+                    .secondary_signer_addresses
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+                secondary_signers: s
+                    .secondary_signers
+                    .iter()
+                    .map(convert_account_signature)
+                    .collect(),
+                fee_payer_address: s.fee_payer_address.to_string(),
+                fee_payer_signer: Some(convert_account_signature(&s.fee_payer_signer)),
+            })
+        },
+        TransactionSignature::Secp256k1EcdsaSignature(s) => {
+            transaction::signature::Signature::Secp256k1Ecdsa(convert_secp256k1_ecdsa_signature(s))
+        },
+        TransactionSignature::MultiKeySignature(s) => {
+            transaction::signature::Signature::MultiKey(convert_multi_key_signature(s))
+        },
     };
 
     Some(transaction::Signature {
@@ -1,8 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_config::config::NodeConfig;
-use aptos_crypto::{hash::HashValue, SigningKey};
+use crate::tests::{golden_output::GoldenOutputs, pretty};
+use aptos_api::{context::Context, index};
+use aptos_api_types::HexEncodedBytes;
+use aptos_config::{config::NodeConfig, keys::ConfigKey};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::HashValue, SigningKey};
 use aptos_mempool::mocks::MockSharedMempool;
 use aptos_protos::extractor::v1::Transaction as TransactionPB;
 use aptos_sdk::{
@@ -14,6 +17,7 @@ use aptos_sdk::{
 use aptos_temppath::TempPath;
 use aptos_types::{
     account_address::AccountAddress,
+    aggregated_signature::AggregatedSignature,
     block_info::BlockInfo,
     block_metadata::BlockMetadata,
     chain_id::ChainId,
@@ -22,22 +26,15 @@ use aptos_types::{
 };
 use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
+use bytes::Bytes;
 use executor::{block_executor::BlockExecutor, db_bootstrapper};
 use executor_types::BlockExecutorTrait;
-use mempool_notifications::MempoolNotificationSender;
-use storage_interface::DbReaderWriter;
-
-use crate::tests::{golden_output::GoldenOutputs, pretty};
-use aptos_api::{context::Context, index};
-use aptos_api_types::HexEncodedBytes;
-use aptos_config::keys::ConfigKey;
-use aptos_crypto::ed25519::Ed25519PrivateKey;
-use aptos_types::aggregated_signature::AggregatedSignature;
-use bytes::Bytes;
 use hyper::Response;
+use mempool_notifications::MempoolNotificationSender;
 use rand::SeedableRng;
 use serde_json::{json, Value};
 use std::{boxed::Box, iter::once, sync::Arc, time::Duration};
+use storage_interface::DbReaderWriter;
 use vm_validator::vm_validator::VMValidator;
 
 pub fn new_test_context(test_name: &str, fake_start_time_usecs: u64) -> TestContext {
@@ -128,6 +125,7 @@ impl TestContext {
             fake_time_usecs,
         }
     }
+
     pub fn rng(&mut self) -> &mut rand::rngs::StdRng {
         &mut self.rng
     }
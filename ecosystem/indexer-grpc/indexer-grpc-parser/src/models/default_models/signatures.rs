@@ -9,8 +9,10 @@ use anyhow::{Context, Result};
 use aptos_protos::transaction::v1::{
     account_signature::Signature as AccountSignatureEnum, signature::Signature as SignatureEnum,
     AccountSignature as ProtoAccountSignature, Ed25519Signature as Ed25519SignaturePB,
-    MultiAgentSignature as ProtoMultiAgentSignature,
-    MultiEd25519Signature as ProtoMultiEd25519Signature, Signature as TransactionSignaturePB,
+    FeePayerSignature as ProtoFeePayerSignature, MultiAgentSignature as ProtoMultiAgentSignature,
+    MultiEd25519Signature as ProtoMultiEd25519Signature,
+    MultiKeySignature as ProtoMultiKeySignature,
+    Secp256k1EcdsaSignature as ProtoSecp256k1EcdsaSignature, Signature as TransactionSignaturePB,
 };
 use field_count::FieldCount;
 use serde::{Deserialize, Serialize};
@@ -73,6 +75,30 @@ impl Signature {
                 transaction_version,
                 transaction_block_height,
             ),
+            SignatureEnum::FeePayer(sig) => Self::parse_fee_payer_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+            ),
+            SignatureEnum::Secp256k1Ecdsa(sig) => Ok(vec![Self::parse_secp256k1_ecdsa_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                true,
+                0,
+                None,
+            )]),
+            SignatureEnum::MultiKey(sig) => Ok(Self::parse_multi_key_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                true,
+                0,
+                None,
+            )),
         }
     }
 
@@ -81,6 +107,9 @@ impl Signature {
             SignatureEnum::Ed25519(_) => String::from("ed25519_signature"),
             SignatureEnum::MultiEd25519(_) => String::from("multi_ed25519_signature"),
             SignatureEnum::MultiAgent(_) => String::from("multi_agent_signature"),
+            SignatureEnum::FeePayer(_) => String::from("fee_payer_signature"),
+            SignatureEnum::Secp256k1Ecdsa(_) => String::from("secp256k1_ecdsa_signature"),
+            SignatureEnum::MultiKey(_) => String::from("multi_key_signature"),
         }
     }
 
@@ -109,6 +138,31 @@ impl Signature {
         }
     }
 
+    fn parse_secp256k1_ecdsa_signature(
+        s: &ProtoSecp256k1EcdsaSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Self {
+        let signer = standardize_address(override_address.unwrap_or(sender));
+        Self {
+            transaction_version,
+            transaction_block_height,
+            signer,
+            is_sender_primary,
+            type_: String::from("secp256k1_ecdsa_signature"),
+            public_key: format!("0x{}", hex::encode(s.public_key.as_slice())),
+            threshold: 1,
+            public_key_indices: serde_json::Value::Array(vec![]),
+            signature: format!("0x{}", hex::encode(s.signature.as_slice())),
+            multi_agent_index,
+            multi_sig_index: 0,
+        }
+    }
+
     fn parse_multi_signature(
         s: &ProtoMultiEd25519Signature,
         sender: &String,
@@ -156,6 +210,53 @@ impl Signature {
         signatures
     }
 
+    fn parse_multi_key_signature(
+        s: &ProtoMultiKeySignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Vec<Self> {
+        let mut signatures = Vec::default();
+        let signer = standardize_address(override_address.unwrap_or(sender));
+
+        let public_key_indices: Vec<usize> = s
+            .public_key_indices
+            .iter()
+            .map(|index| *index as usize)
+            .collect();
+        for (index, signature) in s.signatures.iter().enumerate() {
+            let public_key = s
+                .public_keys
+                .get(public_key_indices.clone()[index])
+                .unwrap()
+                .clone();
+            signatures.push(Self {
+                transaction_version,
+                transaction_block_height,
+                signer: signer.clone(),
+                is_sender_primary,
+                type_: String::from("multi_key_signature"),
+                public_key: format!("0x{}", hex::encode(public_key.public_key.as_slice())),
+                threshold: s.signatures_required as i64,
+                signature: format!("0x{}", hex::encode(signature.signature.as_slice())),
+                public_key_indices: serde_json::Value::Array(
+                    public_key_indices
+                        .iter()
+                        .map(|index| {
+                            serde_json::Value::Number(serde_json::Number::from(*index as i64))
+                        })
+                        .collect(),
+                ),
+                multi_agent_index,
+                multi_sig_index: index as i64,
+            });
+        }
+        signatures
+    }
+
     fn parse_multi_agent_signature(
         s: &ProtoMultiAgentSignature,
         sender: &String,
@@ -191,6 +292,53 @@ impl Signature {
         Ok(signatures)
     }
 
+    fn parse_fee_payer_signature(
+        s: &ProtoFeePayerSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+    ) -> Result<Vec<Self>> {
+        let mut signatures = Vec::default();
+        // process sender signature
+        signatures.append(&mut Self::parse_multi_agent_signature_helper(
+            s.sender.as_ref().unwrap(),
+            sender,
+            transaction_version,
+            transaction_block_height,
+            true,
+            0,
+            None,
+        ));
+        for (index, address) in s.secondary_signer_addresses.iter().enumerate() {
+            let secondary_sig = s.secondary_signers.get(index).context(format!(
+                "Failed to parse index {} for fee payer secondary signers",
+                index
+            ))?;
+            signatures.append(&mut Self::parse_multi_agent_signature_helper(
+                secondary_sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                false,
+                index as i64,
+                Some(&address.to_string()),
+            ));
+        }
+        // process fee payer signature, indexed after the secondary signers
+        signatures.append(&mut Self::parse_multi_agent_signature_helper(
+            s.fee_payer_signer
+                .as_ref()
+                .context("Failed to parse fee payer signature")?,
+            sender,
+            transaction_version,
+            transaction_block_height,
+            false,
+            s.secondary_signer_addresses.len() as i64,
+            Some(&s.fee_payer_address),
+        ));
+        Ok(signatures)
+    }
+
     fn parse_multi_agent_signature_helper(
         s: &ProtoAccountSignature,
         sender: &String,
@@ -220,6 +368,26 @@ impl Signature {
                 multi_agent_index,
                 override_address,
             ),
+            AccountSignatureEnum::Secp256k1Ecdsa(sig) => {
+                vec![Self::parse_secp256k1_ecdsa_signature(
+                    sig,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    is_sender_primary,
+                    multi_agent_index,
+                    override_address,
+                )]
+            },
+            AccountSignatureEnum::MultiKey(sig) => Self::parse_multi_key_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                is_sender_primary,
+                multi_agent_index,
+                override_address,
+            ),
         }
     }
 }
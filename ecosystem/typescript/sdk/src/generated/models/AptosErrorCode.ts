@@ -24,6 +24,7 @@ export enum AptosErrorCode {
     VM_ERROR = 'vm_error',
     HEALTH_CHECK_FAILED = 'health_check_failed',
     MEMPOOL_IS_FULL = 'mempool_is_full',
+    RATE_LIMIT_EXCEEDED = 'rate_limit_exceeded',
     INTERNAL_ERROR = 'internal_error',
     WEB_FRAMEWORK_ERROR = 'web_framework_error',
     BCS_NOT_SUPPORTED = 'bcs_not_supported',
@@ -83,10 +83,14 @@ impl CrossShardConflictDetector {
             // and find the first shard id that has taken a write lock on the storage location. This ensures that we find the highest txn index that is conflicting
             // with the current transaction. Please note that since we use a multi-version database, there is no conflict if any previous txn index has taken
             // a read lock on the storage location.
-            let mut current_shard_id = (self.shard_id + self.num_shards - 1) % self.num_shards; // current shard id - 1 in a wrapping fashion
+            // The current round read/write set is empty when the transactions with cross-shard dependencies in the current round
+            // are discarded, in which case we start from the last shard of the previous round.
+            let num_current_round_shards = current_round_rw_set_with_index.len().min(self.shard_id);
+            let mut current_shard_id =
+                (num_current_round_shards + self.num_shards - 1) % self.num_shards; // num_current_round_shards - 1 in a wrapping fashion
             for rw_set_with_index in current_round_rw_set_with_index
                 .iter()
-                .take(self.shard_id)
+                .take(num_current_round_shards)
                 .rev()
                 .chain(prev_rounds_rw_set_with_index.iter().rev())
             {
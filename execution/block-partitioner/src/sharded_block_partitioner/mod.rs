@@ -758,4 +758,54 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    // Generates a bunch of random conflicting transactions that are partitioned over several rounds, and
+    // ensures that the cross shard dependencies point to the shards the required transactions are in.
+    fn test_cross_shard_dependencies_across_rounds() {
+        let mut rng = OsRng;
+        let num_accounts = 50;
+        let num_txns = 500;
+        let mut accounts = Vec::new();
+        for _ in 0..num_accounts {
+            accounts.push(generate_test_account());
+        }
+        let mut transactions = Vec::new();
+        for _ in 0..num_txns {
+            // randomly select a sender and receiver from accounts
+            let sender_index = rng.gen_range(0, accounts.len());
+            let mut sender = accounts.swap_remove(sender_index);
+            let receiver_index = rng.gen_range(0, accounts.len());
+            let receiver = accounts.get(receiver_index).unwrap();
+            transactions.push(create_signed_p2p_transaction(&mut sender, vec![receiver]).remove(0));
+            accounts.push(sender)
+        }
+
+        for num_shards in 2..5 {
+            let partitioner = ShardedBlockPartitioner::new(num_shards);
+            let partitioned_txns = partitioner.partition(transactions.clone(), 3);
+
+            // Build a map of txn index to the shard it is in
+            let mut txn_index_to_shard_map = HashMap::new();
+            for (shard_id, sub_blocks_for_shard) in partitioned_txns.iter().enumerate() {
+                for sub_block in sub_blocks_for_shard.sub_block_iter() {
+                    for txn_index in sub_block.start_index..sub_block.end_index() {
+                        txn_index_to_shard_map.insert(txn_index, shard_id);
+                    }
+                }
+            }
+            assert_eq!(txn_index_to_shard_map.len(), num_txns);
+
+            for sub_blocks_for_shard in partitioned_txns.iter() {
+                for txn in sub_blocks_for_shard.iter() {
+                    for (required_txn, _) in txn.cross_shard_dependencies().required_edges_iter() {
+                        assert_eq!(
+                            txn_index_to_shard_map.get(&required_txn.txn_index),
+                            Some(&required_txn.shard_id)
+                        );
+                    }
+                }
+            }
+        }
+    }
 }
@@ -338,10 +338,7 @@ impl TransactionGenerator {
                 .map(|new_account| {
                     let txn = self.root_account.sign_with_transaction_builder(
                         self.transaction_factory
-                            .implicitly_create_user_account_and_transfer(
-                                new_account.public_key(),
-                                seed_account_balance,
-                            ),
+                            .account_transfer(new_account.address(), seed_account_balance),
                     );
                     Transaction::UserTransaction(txn)
                 })
@@ -383,10 +380,7 @@ impl TransactionGenerator {
                     let new_account = generator.generate();
                     let txn = sender.sign_with_transaction_builder(
                         self.transaction_factory
-                            .implicitly_create_user_account_and_transfer(
-                                new_account.public_key(),
-                                init_account_balance,
-                            ),
+                            .account_transfer(new_account.address(), init_account_balance),
                     );
                     Transaction::UserTransaction(txn)
                 })
@@ -2,15 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 use aptos_state_view::in_memory_state_view::InMemoryStateView;
 use aptos_types::{
+    block_executor::partitioner::SubBlocksForShard,
     transaction::{Transaction, TransactionOutput},
     vm_status::VMStatus,
 };
+use aptos_vm::sharded_block_executor::cross_shard_client::CrossShardRequirements;
 use serde::{Deserialize, Serialize};
 
 mod error;
 pub mod process_executor_service;
+pub mod remote_cross_shard_client;
 pub mod remote_executor_client;
 pub mod remote_executor_service;
+#[cfg(any(test, feature = "testing"))]
+pub mod test_utils;
 #[cfg(test)]
 mod thread_executor_service;
 
@@ -22,6 +27,7 @@ pub struct BlockExecutionResult {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum BlockExecutionRequest {
     ExecuteBlock(ExecuteBlockCommand),
+    ExecuteSubBlocks(ExecuteSubBlocksCommand),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -36,3 +42,14 @@ pub struct ExecuteBlockCommand {
     pub(crate) concurrency_level: usize,
     pub(crate) maybe_block_gas_limit: Option<u64>,
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExecuteSubBlocksCommand {
+    pub(crate) sub_blocks: SubBlocksForShard<Transaction>,
+    // The values of the state items the sub blocks depend on are exchanged directly with the
+    // executor services of the other shards, so the state view is only sent once for the block.
+    pub(crate) cross_shard_requirements: CrossShardRequirements,
+    pub(crate) state_view: InMemoryStateView,
+    pub(crate) concurrency_level: usize,
+    pub(crate) maybe_block_gas_limit: Option<u64>,
+}
@@ -12,6 +12,19 @@ struct Args {
 
     #[clap(long, default_value = "8")]
     pub num_executor_threads: usize,
+
+    /// The shard executed by the service, if it is a shard of a sharded block executor.
+    #[clap(long)]
+    pub shard_id: Option<usize>,
+
+    /// The number of shards of the sharded block executor.
+    #[clap(long, default_value = "1")]
+    pub num_shards: usize,
+
+    /// The `num_shards * num_shards` addresses the shards receive the messages of the other
+    /// shards on, shard `i` receiving the messages of shard `j` on the `i * num_shards + j`-th.
+    #[clap(long, use_value_delimiter = true)]
+    pub cross_shard_addrs: Vec<SocketAddr>,
 }
 
 fn main() {
@@ -19,7 +32,27 @@ fn main() {
     aptos_logger::Logger::new().init();
 
     let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), args.server_port);
-    let executor_service =
-        ProcessExecutorService::new(server_addr, 1000, args.num_executor_threads);
+    let executor_service = match args.shard_id {
+        Some(shard_id) => {
+            assert_eq!(
+                args.cross_shard_addrs.len(),
+                args.num_shards * args.num_shards,
+                "There must be a cross shard address for each pair of shards"
+            );
+            let cross_shard_addrs = args
+                .cross_shard_addrs
+                .chunks(args.num_shards)
+                .map(|addrs| addrs.to_vec())
+                .collect();
+            ProcessExecutorService::new_sharded(
+                server_addr,
+                1000,
+                args.num_executor_threads,
+                shard_id,
+                cross_shard_addrs,
+            )
+        },
+        None => ProcessExecutorService::new(server_addr, 1000, args.num_executor_threads),
+    };
     executor_service.run();
 }
@@ -6,6 +6,7 @@ use crate::{
 };
 use aptos_logger::info;
 use aptos_secure_net::NetworkServer;
+use aptos_types::block_executor::partitioner::ShardId;
 use std::net::SocketAddr;
 
 /// An implementation of the remote executor service that runs in a standalone process.
@@ -13,6 +14,9 @@ pub struct ProcessExecutorService {
     server_addr: SocketAddr,
     network_timeout_ms: u64,
     num_executor_threads: usize,
+    // The shard id and the cross shard addresses of the service, if it executes a shard of a
+    // sharded block executor.
+    shard: Option<(ShardId, Vec<Vec<SocketAddr>>)>,
 }
 
 impl ProcessExecutorService {
@@ -21,6 +25,22 @@ impl ProcessExecutorService {
             server_addr,
             network_timeout_ms: network_timeout,
             num_executor_threads,
+            shard: None,
+        }
+    }
+
+    /// Creates the service of a shard of a sharded block executor, see
+    /// [`ExecutorService::new_sharded`].
+    pub fn new_sharded(
+        server_addr: SocketAddr,
+        network_timeout: u64,
+        num_executor_threads: usize,
+        shard_id: ShardId,
+        cross_shard_addrs: Vec<Vec<SocketAddr>>,
+    ) -> Self {
+        Self {
+            shard: Some((shard_id, cross_shard_addrs)),
+            ..Self::new(server_addr, network_timeout, num_executor_threads)
         }
     }
 
@@ -34,7 +54,15 @@ impl ProcessExecutorService {
             self.server_addr,
             self.network_timeout_ms,
         );
-        let executor_service = ExecutorService::new(self.num_executor_threads);
+        let executor_service = match &self.shard {
+            Some((shard_id, cross_shard_addrs)) => ExecutorService::new_sharded(
+                self.num_executor_threads,
+                *shard_id,
+                cross_shard_addrs,
+                self.network_timeout_ms,
+            ),
+            None => ExecutorService::new(self.num_executor_threads),
+        };
         remote_executor_service::execute(network_server, executor_service);
     }
 }
@@ -0,0 +1,120 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use aptos_logger::error;
+use aptos_retrier::{fixed_retry_strategy, retry};
+use aptos_secure_net::{NetworkClient, NetworkServer};
+use aptos_types::block_executor::partitioner::ShardId;
+use aptos_vm::sharded_block_executor::cross_shard_client::{CrossShardClient, CrossShardMsg};
+use std::{
+    net::SocketAddr,
+    sync::{
+        mpsc::{Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+
+// Acknowledges a message, so that a message written to a connection the receiving shard already
+// dropped (e.g., after a read timeout) is sent again. Messages can't be empty.
+const CROSS_SHARD_MSG_ACK: &[u8] = &[1];
+
+/// An implementation of [`CrossShardClient`] for executor shards running in different processes.
+/// A [`NetworkServer`] only serves one connection at a time, so each shard listens for the
+/// messages of every other shard on a dedicated address: `cross_shard_addrs[i][j]` is the address
+/// shard `i` receives the messages of shard `j` on (the addresses with `i == j` are unused).
+pub struct RemoteCrossShardClient {
+    message_rx: Mutex<Receiver<CrossShardMsg>>,
+    // The clients sending the messages of this shard to the other shards, by shard id.
+    network_clients: Vec<Option<Mutex<NetworkClient>>>,
+}
+
+impl RemoteCrossShardClient {
+    pub fn new(
+        shard_id: ShardId,
+        cross_shard_addrs: &[Vec<SocketAddr>],
+        network_timeout_ms: u64,
+    ) -> Self {
+        let (message_tx, message_rx) = std::sync::mpsc::channel();
+        for (remote_shard_id, listen_addr) in cross_shard_addrs[shard_id].iter().enumerate() {
+            if remote_shard_id != shard_id {
+                let network_server =
+                    NetworkServer::new("cross-shard-client", *listen_addr, network_timeout_ms);
+                let message_tx = message_tx.clone();
+                thread::spawn(move || receive_messages(network_server, message_tx));
+            }
+        }
+        let network_clients = cross_shard_addrs
+            .iter()
+            .enumerate()
+            .map(|(remote_shard_id, remote_addrs)| {
+                (remote_shard_id != shard_id).then(|| {
+                    Mutex::new(NetworkClient::new(
+                        "cross-shard-client",
+                        remote_addrs[shard_id],
+                        network_timeout_ms,
+                    ))
+                })
+            })
+            .collect();
+        Self {
+            message_rx: Mutex::new(message_rx),
+            network_clients,
+        }
+    }
+
+    fn send_cross_shard_msg_inner(
+        &self,
+        shard_id: ShardId,
+        msg: &CrossShardMsg,
+    ) -> Result<(), Error> {
+        let network_client = self.network_clients[shard_id].as_ref().ok_or_else(|| {
+            Error::InternalError(format!("Shard {} can't send messages to itself", shard_id))
+        })?;
+        let message = bcs::to_bytes(msg)?;
+        retry(fixed_retry_strategy(5, 20), || {
+            let mut network_client = network_client.lock().unwrap();
+            network_client.write(&message)?;
+            network_client.read()?;
+            Ok(())
+        })
+    }
+}
+
+impl CrossShardClient for RemoteCrossShardClient {
+    fn send_cross_shard_msg(&self, shard_id: ShardId, msg: CrossShardMsg) {
+        if let Err(e) = self.send_cross_shard_msg_inner(shard_id, &msg) {
+            error!(
+                "Failed to send cross shard message to shard {}: {:?}",
+                shard_id, e
+            );
+        }
+    }
+
+    fn receive_cross_shard_msg(&self) -> Option<CrossShardMsg> {
+        self.message_rx.lock().unwrap().recv().ok()
+    }
+}
+
+/// Receives the messages of a remote shard, until the client is dropped.
+fn receive_messages(mut network_server: NetworkServer, message_tx: Sender<CrossShardMsg>) {
+    loop {
+        // The read fails if the connection is idle for longer than the network timeout, the remote
+        // shard then reconnects on its next message.
+        let message = match network_server.read() {
+            Ok(message) => message,
+            Err(_) => continue,
+        };
+        match bcs::from_bytes(&message) {
+            Ok(msg) => {
+                if message_tx.send(msg).is_err() {
+                    break;
+                }
+            },
+            Err(e) => error!("Failed to deserialize cross shard message: {}", e),
+        }
+        // The message is sent again if the acknowledgement fails, receiving it twice is harmless.
+        let _ = network_server.write(CROSS_SHARD_MSG_ACK);
+    }
+}
@@ -1,16 +1,22 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::Error, BlockExecutionRequest, BlockExecutionResult, ExecuteBlockCommand};
+use crate::{
+    error::Error, BlockExecutionRequest, BlockExecutionResult, ExecuteBlockCommand,
+    ExecuteSubBlocksCommand,
+};
 use aptos_logger::error;
 use aptos_retrier::{fixed_retry_strategy, retry};
 use aptos_secure_net::NetworkClient;
 use aptos_state_view::StateView;
 use aptos_types::{
+    block_executor::partitioner::SubBlocksForShard,
     transaction::{Transaction, TransactionOutput},
     vm_status::VMStatus,
 };
-use aptos_vm::sharded_block_executor::block_executor_client::BlockExecutorClient;
+use aptos_vm::sharded_block_executor::{
+    block_executor_client::BlockExecutorClient, cross_shard_client::CrossShardRequirements,
+};
 use std::{net::SocketAddr, sync::Mutex};
 
 /// An implementation of [`BlockExecutorClient`] that supports executing blocks remotely.
@@ -72,4 +78,22 @@ impl BlockExecutorClient for RemoteExecutorClient {
         });
         self.execute_block_with_retry(input).inner
     }
+
+    fn execute_sub_blocks<S: StateView + Sync>(
+        &self,
+        sub_blocks: SubBlocksForShard<Transaction>,
+        cross_shard_requirements: CrossShardRequirements,
+        state_view: &S,
+        concurrency_level: usize,
+        maybe_block_gas_limit: Option<u64>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let input = BlockExecutionRequest::ExecuteSubBlocks(ExecuteSubBlocksCommand {
+            sub_blocks,
+            cross_shard_requirements,
+            state_view: S::as_in_memory_state_view(state_view),
+            concurrency_level,
+            maybe_block_gas_limit,
+        });
+        self.execute_block_with_retry(input).inner
+    }
 }
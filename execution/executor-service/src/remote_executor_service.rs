@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    error::Error, remote_executor_client::RemoteExecutorClient, BlockExecutionRequest,
-    BlockExecutionResult,
+    error::Error, remote_cross_shard_client::RemoteCrossShardClient,
+    remote_executor_client::RemoteExecutorClient, BlockExecutionRequest, BlockExecutionResult,
 };
 use aptos_logger::{error, info};
 use aptos_secure_net::NetworkServer;
+use aptos_types::block_executor::partitioner::ShardId;
 use aptos_vm::sharded_block_executor::block_executor_client::{
     BlockExecutorClient, LocalExecutorClient,
 };
@@ -25,6 +26,25 @@ impl ExecutorService {
         }
     }
 
+    /// Creates the service of a shard of a sharded block executor, which exchanges the cross
+    /// shard state directly with the services of the other shards (see [`RemoteCrossShardClient`]
+    /// for the layout of `cross_shard_addrs`).
+    pub fn new_sharded(
+        num_executor_threads: usize,
+        shard_id: ShardId,
+        cross_shard_addrs: &[Vec<SocketAddr>],
+        network_timeout_ms: u64,
+    ) -> Self {
+        let cross_shard_client =
+            RemoteCrossShardClient::new(shard_id, cross_shard_addrs, network_timeout_ms);
+        Self {
+            client: LocalExecutorClient::with_cross_shard_client(
+                num_executor_threads,
+                Box::new(cross_shard_client),
+            ),
+        }
+    }
+
     pub fn handle_message(&self, execution_message: Vec<u8>) -> Result<Vec<u8>, Error> {
         let input = bcs::from_bytes(&execution_message)?;
         let result = self.handle_execution_request(input)?;
@@ -42,6 +62,13 @@ impl ExecutorService {
                 command.concurrency_level,
                 command.maybe_block_gas_limit,
             ),
+            BlockExecutionRequest::ExecuteSubBlocks(command) => self.client.execute_sub_blocks(
+                command.sub_blocks,
+                command.cross_shard_requirements,
+                &command.state_view,
+                command.concurrency_level,
+                command.maybe_block_gas_limit,
+            ),
         };
         Ok(BlockExecutionResult { inner: result })
     }
@@ -83,6 +110,7 @@ fn process_one_message(
 mod tests {
     use crate::{
         remote_executor_service::RemoteExecutorService,
+        test_utils::{local_cross_shard_addrs, test_sharded_block_executor_with_conflicts},
         thread_executor_service::ThreadExecutorService,
     };
     use aptos_language_e2e_tests::{
@@ -221,4 +249,21 @@ mod tests {
             verify_txn_output(1_000, &output, &mut executor, &receiver);
         }
     }
+
+    #[test]
+    fn test_sharded_remote_block_executor_with_conflicts() {
+        let cross_shard_addrs = local_cross_shard_addrs(3);
+        // A shard waits for the values it depends on from the other shards before responding, so
+        // the network timeout covers the execution of the whole block.
+        let executor_services: Vec<_> = (0..3)
+            .map(|shard_id| {
+                ThreadExecutorService::new_sharded(60000, 2, shard_id, &cross_shard_addrs)
+            })
+            .collect();
+        let clients = executor_services
+            .iter()
+            .map(|executor_service| executor_service.client())
+            .collect();
+        test_sharded_block_executor_with_conflicts(clients, 2);
+    }
 }
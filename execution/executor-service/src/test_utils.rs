@@ -0,0 +1,123 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_block_partitioner::sharded_block_partitioner::ShardedBlockPartitioner;
+use aptos_config::utils;
+use aptos_language_e2e_tests::{
+    account::AccountData, common_transactions::peer_to_peer_txn, executor::FakeExecutor,
+};
+use aptos_types::{
+    block_executor::partitioner::SubBlocksForShard,
+    transaction::{analyzed_transaction::AnalyzedTransaction, Transaction},
+};
+use aptos_vm::sharded_block_executor::{
+    block_executor_client::{BlockExecutorClient, LocalExecutorClient},
+    ShardedBlockExecutor,
+};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+/// Returns the addresses the shards of a sharded block executor running on the local machine
+/// receive the cross shard messages on, see
+/// [`RemoteCrossShardClient`](crate::remote_cross_shard_client::RemoteCrossShardClient).
+pub fn local_cross_shard_addrs(num_shards: usize) -> Vec<Vec<SocketAddr>> {
+    (0..num_shards)
+        .map(|_| {
+            (0..num_shards)
+                .map(|_| {
+                    SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), utils::get_available_port())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Generates a block of p2p transfers between `num_accounts` accounts. Every account sends to
+/// several others, so that the transactions of different shards conflict with each other.
+pub fn generate_conflicting_p2p_block(
+    executor: &mut FakeExecutor,
+    num_accounts: usize,
+    num_txns_per_account: usize,
+) -> Vec<AnalyzedTransaction> {
+    let accounts: Vec<AccountData> = (0..num_accounts)
+        .map(|_| {
+            let account = executor.create_raw_account_data(3_000_000_000, 0);
+            executor.add_account_data(&account);
+            account
+        })
+        .collect();
+    let mut txns = vec![];
+    for (sender_index, sender) in accounts.iter().enumerate() {
+        for seq_num in 0..num_txns_per_account {
+            let receiver = &accounts[(sender_index * 7 + seq_num * 3 + 1) % num_accounts];
+            txns.push(AnalyzedTransaction::from(Transaction::UserTransaction(
+                peer_to_peer_txn(
+                    sender.account(),
+                    receiver.account(),
+                    seq_num as u64,
+                    1_000,
+                    100,
+                ),
+            )));
+        }
+    }
+    txns
+}
+
+/// Returns the transactions of the partitioned block in the order of the block.
+pub fn partitioned_block_order(
+    sub_blocks: &[SubBlocksForShard<AnalyzedTransaction>],
+) -> Vec<Transaction> {
+    let mut ordered_sub_blocks: Vec<_> = sub_blocks
+        .iter()
+        .flat_map(|sub_blocks_for_shard| sub_blocks_for_shard.sub_block_iter())
+        .collect();
+    ordered_sub_blocks.sort_by_key(|sub_block| sub_block.start_index);
+    ordered_sub_blocks
+        .into_iter()
+        .flat_map(|sub_block| sub_block.iter())
+        .map(|txn_with_deps| txn_with_deps.txn().transaction().clone())
+        .collect()
+}
+
+/// Executes blocks of conflicting transactions partitioned across the executor clients, and
+/// checks that the outputs are the same as executing the blocks on a single node.
+pub fn test_sharded_block_executor_with_conflicts<
+    E: BlockExecutorClient + Sync + Send + 'static,
+>(
+    executor_clients: Vec<E>,
+    concurrency_level_per_shard: usize,
+) {
+    let num_shards = executor_clients.len();
+    let sharded_block_executor = ShardedBlockExecutor::new(executor_clients);
+    let partitioner = ShardedBlockPartitioner::new(num_shards);
+    let local_executor_client = LocalExecutorClient::new(concurrency_level_per_shard);
+    let mut executor = FakeExecutor::from_head_genesis();
+    for _ in 0..3 {
+        let txns = generate_conflicting_p2p_block(&mut executor, 20, 4);
+        let sub_blocks = partitioner.partition(txns, 2);
+        let expected_outputs = local_executor_client
+            .execute_block(
+                partitioned_block_order(&sub_blocks),
+                executor.data_store(),
+                concurrency_level_per_shard,
+                None,
+            )
+            .unwrap();
+
+        let outputs = sharded_block_executor
+            .execute_partitioned_block(
+                Arc::new(executor.data_store().clone()),
+                sub_blocks,
+                concurrency_level_per_shard,
+                None,
+            )
+            .unwrap();
+        assert_eq!(outputs, expected_outputs);
+        for output in &outputs {
+            executor.apply_write_set(output.write_set());
+        }
+    }
+}
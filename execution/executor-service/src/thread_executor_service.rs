@@ -7,6 +7,7 @@ use crate::{
 use aptos_config::utils;
 use aptos_logger::info;
 use aptos_secure_net::NetworkServer;
+use aptos_types::block_executor::partitioner::ShardId;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     thread,
@@ -24,6 +25,38 @@ pub struct ThreadExecutorService {
 
 impl ThreadExecutorService {
     pub fn new(network_timeout_ms: u64, num_executor_threads: usize) -> Self {
+        Self::start(
+            network_timeout_ms,
+            num_executor_threads,
+            ExecutorService::new(num_executor_threads),
+        )
+    }
+
+    /// Starts the service of a shard of a sharded block executor, see
+    /// [`ExecutorService::new_sharded`].
+    pub fn new_sharded(
+        network_timeout_ms: u64,
+        num_executor_threads: usize,
+        shard_id: ShardId,
+        cross_shard_addrs: &[Vec<SocketAddr>],
+    ) -> Self {
+        Self::start(
+            network_timeout_ms,
+            num_executor_threads,
+            ExecutorService::new_sharded(
+                num_executor_threads,
+                shard_id,
+                cross_shard_addrs,
+                network_timeout_ms,
+            ),
+        )
+    }
+
+    fn start(
+        network_timeout_ms: u64,
+        num_executor_threads: usize,
+        executor_service: ExecutorService,
+    ) -> Self {
         let listen_port = utils::get_available_port();
         let listen_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listen_port);
         let server_addr = listen_addr;
@@ -32,8 +65,6 @@ impl ThreadExecutorService {
         let network_server =
             NetworkServer::new("thread-executor-service", listen_addr, network_timeout_ms);
 
-        let executor_service = ExecutorService::new(num_executor_threads);
-
         let child = thread::spawn(move || {
             remote_executor_service::execute(network_server, executor_service);
         });
@@ -0,0 +1,100 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::utils;
+use aptos_executor_service::{
+    remote_executor_client::RemoteExecutorClient,
+    test_utils::{local_cross_shard_addrs, test_sharded_block_executor_with_conflicts},
+};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream},
+    process::{Child, Command},
+    thread,
+    time::{Duration, Instant},
+};
+
+// A shard waits for the values it depends on from the other shards before responding, so the
+// network timeout covers the execution of the whole block.
+const NETWORK_TIMEOUT_MS: u64 = 60000;
+
+/// Executor services running the shards of a sharded block executor in separate processes on the
+/// local machine, killed on drop.
+struct LocalExecutorServiceCluster {
+    children: Vec<Child>,
+    server_addrs: Vec<SocketAddr>,
+}
+
+impl LocalExecutorServiceCluster {
+    fn start(num_shards: usize, num_executor_threads: usize) -> Self {
+        let cross_shard_addrs = local_cross_shard_addrs(num_shards)
+            .into_iter()
+            .flatten()
+            .map(|addr| addr.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut children = vec![];
+        let mut server_addrs = vec![];
+        for shard_id in 0..num_shards {
+            let port = utils::get_available_port();
+            let child = Command::new(env!("CARGO_BIN_EXE_aptos-executor-service"))
+                .arg("--server-port")
+                .arg(port.to_string())
+                .arg("--num-executor-threads")
+                .arg(num_executor_threads.to_string())
+                .arg("--shard-id")
+                .arg(shard_id.to_string())
+                .arg("--num-shards")
+                .arg(num_shards.to_string())
+                .arg("--cross-shard-addrs")
+                .arg(&cross_shard_addrs)
+                .spawn()
+                .expect("Failed to start the executor service process");
+            children.push(child);
+            server_addrs.push(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port));
+        }
+        let cluster = Self {
+            children,
+            server_addrs,
+        };
+        cluster.wait_for_services();
+        cluster
+    }
+
+    fn wait_for_services(&self) {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        for server_addr in &self.server_addrs {
+            // The services only serve one connection at a time, the probe connection is dropped
+            // right away so that the service moves on to the next one.
+            while TcpStream::connect(server_addr).is_err() {
+                assert!(
+                    Instant::now() < deadline,
+                    "Executor service at {} didn't start",
+                    server_addr
+                );
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+    }
+
+    fn clients(&self) -> Vec<RemoteExecutorClient> {
+        self.server_addrs
+            .iter()
+            .map(|server_addr| RemoteExecutorClient::new(*server_addr, NETWORK_TIMEOUT_MS))
+            .collect()
+    }
+}
+
+impl Drop for LocalExecutorServiceCluster {
+    fn drop(&mut self) {
+        for child in &mut self.children {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[test]
+fn test_sharded_block_executor_across_processes() {
+    let cluster = LocalExecutorServiceCluster::start(2, 2);
+    test_sharded_block_executor_with_conflicts(cluster.clients(), 2);
+}
@@ -12,7 +12,6 @@ use crate::{
 use aptos_consensus_types::common::TransactionSummary;
 use aptos_logger::prelude::*;
 use aptos_types::account_address::AccountAddress;
-use rand::seq::SliceRandom;
 use std::{
     cmp::Ordering,
     collections::{btree_set::Iter, BTreeMap, BTreeSet, HashMap},
@@ -43,24 +42,15 @@ impl PriorityIndex {
     }
 
     pub(crate) fn insert(&mut self, txn: &MempoolTransaction) {
-        self.data.insert(self.make_key(txn));
+        self.data.insert(OrderedQueueKey::from(txn));
     }
 
     pub(crate) fn remove(&mut self, txn: &MempoolTransaction) {
-        self.data.remove(&self.make_key(txn));
+        self.data.remove(&OrderedQueueKey::from(txn));
     }
 
     pub(crate) fn contains(&self, txn: &MempoolTransaction) -> bool {
-        self.data.contains(&self.make_key(txn))
-    }
-
-    fn make_key(&self, txn: &MempoolTransaction) -> OrderedQueueKey {
-        OrderedQueueKey {
-            gas_ranking_score: txn.ranking_score,
-            expiration_time: txn.expiration_time,
-            address: txn.get_sender(),
-            sequence_number: txn.sequence_info,
-        }
+        self.data.contains(&OrderedQueueKey::from(txn))
     }
 
     pub(crate) fn iter(&self) -> PriorityQueueIter {
@@ -80,6 +70,17 @@ pub struct OrderedQueueKey {
     pub sequence_number: SequenceInfo,
 }
 
+impl From<&MempoolTransaction> for OrderedQueueKey {
+    fn from(txn: &MempoolTransaction) -> Self {
+        Self {
+            gas_ranking_score: txn.ranking_score,
+            expiration_time: txn.expiration_time,
+            address: txn.get_sender(),
+            sequence_number: txn.sequence_info,
+        }
+    }
+}
+
 impl PartialOrd for OrderedQueueKey {
     fn partial_cmp(&self, other: &OrderedQueueKey) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -387,96 +388,79 @@ impl MultiBucketTimelineIndex {
 /// ParkingLotIndex keeps track of "not_ready" transactions, e.g., transactions that
 /// can't be included in the next block because their sequence number is too high.
 /// We keep a separate index to be able to efficiently evict them when Mempool is full.
+/// Transactions are ordered like in `PriorityIndex`: the account whose parked transaction has the
+/// lowest priority is evicted from first, starting with its highest sequence number.
 pub struct ParkingLotIndex {
     // DS invariants:
-    // 1. for each entry (account, txns) in `data`, `txns` is never empty
-    // 2. for all accounts, data.get(account_indices.get(`account`)) == (account, sequence numbers of account's txns)
-    data: Vec<(AccountAddress, BTreeSet<u64>)>,
-    account_indices: HashMap<AccountAddress, usize>,
-    size: usize,
+    // 1. `data` contains the key of every parked txn
+    // 2. for each entry (account, txns) in `account_txns`, `txns` is never empty
+    // 3. for all accounts, account_txns.get(`account`) == (sequence numbers of account's txns -> their key in `data`)
+    data: BTreeSet<OrderedQueueKey>,
+    account_txns: HashMap<AccountAddress, BTreeMap<u64, OrderedQueueKey>>,
 }
 
 impl ParkingLotIndex {
     pub(crate) fn new() -> Self {
         Self {
-            data: vec![],
-            account_indices: HashMap::new(),
-            size: 0,
+            data: BTreeSet::new(),
+            account_txns: HashMap::new(),
         }
     }
 
     pub(crate) fn insert(&mut self, txn: &MempoolTransaction) {
-        let sender = &txn.txn.sender();
-        let sequence_number = txn.txn.sequence_number();
-        let is_new_entry = match self.account_indices.get(sender) {
-            Some(index) => {
-                if let Some((_account, seq_nums)) = self.data.get_mut(*index) {
-                    seq_nums.insert(sequence_number)
-                } else {
-                    counters::CORE_MEMPOOL_INVARIANT_VIOLATION_COUNT.inc();
-                    error!(
-                        LogSchema::new(LogEntry::InvariantViolated),
-                        "Parking lot invariant violated: for account {}, account index exists but missing entry in data",
-                        sender
-                    );
-                    return;
-                }
-            },
-            None => {
-                let seq_nums = [sequence_number].iter().cloned().collect::<BTreeSet<_>>();
-                self.data.push((*sender, seq_nums));
-                self.account_indices.insert(*sender, self.data.len() - 1);
-                true
-            },
-        };
-        if is_new_entry {
-            self.size += 1;
+        let key = OrderedQueueKey::from(txn);
+        let txns = self.account_txns.entry(key.address).or_default();
+        let sequence_number = key.sequence_number.transaction_sequence_number;
+        if !txns.contains_key(&sequence_number) {
+            txns.insert(sequence_number, key.clone());
+            self.data.insert(key);
         }
     }
 
     pub(crate) fn remove(&mut self, txn: &MempoolTransaction) {
-        let sender = &txn.txn.sender();
-        if let Some(index) = self.account_indices.get(sender).cloned() {
-            if let Some((_account, txns)) = self.data.get_mut(index) {
-                if txns.remove(&txn.txn.sequence_number()) {
-                    self.size -= 1;
-                }
-
-                // maintain DS invariant
-                if txns.is_empty() {
-                    // remove account with no more txns
-                    self.data.swap_remove(index);
-                    self.account_indices.remove(sender);
-
-                    // update DS for account that was swapped in `swap_remove`
-                    if let Some((swapped_account, _)) = self.data.get(index) {
-                        self.account_indices.insert(*swapped_account, index);
-                    }
-                }
+        let sender = &txn.get_sender();
+        if let Some(txns) = self.account_txns.get_mut(sender) {
+            if let Some(key) = txns.remove(&txn.sequence_info.transaction_sequence_number) {
+                self.data.remove(&key);
+            }
+
+            // maintain DS invariant
+            if txns.is_empty() {
+                self.account_txns.remove(sender);
             }
         }
     }
 
     pub(crate) fn contains(&self, account: &AccountAddress, seq_num: &u64) -> bool {
-        self.account_indices
+        self.account_txns
             .get(account)
-            .and_then(|idx| self.data.get(*idx))
-            .map_or(false, |(_account, txns)| txns.contains(seq_num))
-    }
-
-    /// Returns a random "non-ready" transaction (with highest sequence number for that account).
-    pub(crate) fn get_poppable(&self) -> Option<TxnPointer> {
-        let mut rng = rand::thread_rng();
-        self.data.choose(&mut rng).and_then(|(sender, txns)| {
-            txns.iter().rev().next().map(|seq_num| TxnPointer {
-                sender: *sender,
-                sequence_number: *seq_num,
-            })
-        })
+            .map_or(false, |txns| txns.contains_key(seq_num))
+    }
+
+    /// Returns the "non-ready" transaction to evict: the one with the highest sequence number of
+    /// the account that has the lowest-priority "non-ready" transaction.
+    pub(crate) fn get_poppable(&self) -> Option<&OrderedQueueKey> {
+        let sender = &self.data.iter().next()?.address;
+        match self
+            .account_txns
+            .get(sender)
+            .and_then(|txns| txns.values().next_back())
+        {
+            Some(key) => Some(key),
+            None => {
+                counters::CORE_MEMPOOL_INVARIANT_VIOLATION_COUNT.inc();
+                error!(
+                    LogSchema::new(LogEntry::InvariantViolated),
+                    "Parking lot invariant violated: for account {}, txn exists in data but missing entry in account txns",
+                    sender
+                );
+                None
+            },
+        }
     }
 
     pub(crate) fn size(&self) -> usize {
-        self.size
+        self.data.len()
     }
 }
 
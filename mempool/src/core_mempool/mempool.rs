@@ -7,7 +7,7 @@
 use crate::{
     core_mempool::{
         index::TxnPointer,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction::{InsertionInfo, MempoolTransaction, PendingTransactionInfo, TimelineState},
         transaction_store::TransactionStore,
     },
     counters,
@@ -375,6 +375,16 @@ impl Mempool {
         self.transactions.gen_snapshot()
     }
 
+    /// Returns the pending transactions of an account, or of all the accounts if none is given,
+    /// up to the given number of transactions.
+    pub(crate) fn get_pending_transactions(
+        &self,
+        account: Option<AccountAddress>,
+        limit: usize,
+    ) -> Vec<PendingTransactionInfo> {
+        self.transactions.get_pending_transactions(account, limit)
+    }
+
     #[cfg(test)]
     pub fn get_parking_lot_size(&self) -> usize {
         self.transactions.get_parking_lot_size()
@@ -10,6 +10,8 @@ mod transaction_store;
 pub use self::{
     index::TxnPointer,
     mempool::Mempool as CoreMempool,
-    transaction::{MempoolTransaction, SubmittedBy, TimelineState},
+    transaction::{
+        InsertionInfo, MempoolTransaction, PendingTransactionInfo, SubmittedBy, TimelineState,
+    },
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
@@ -141,6 +141,19 @@ impl InsertionInfo {
     }
 }
 
+/// A transaction pending in mempool, along with its state in mempool. This is what clients
+/// inspecting the content of mempool get.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingTransactionInfo {
+    pub txn: SignedTransaction,
+    pub ranking_score: u64,
+    pub timeline_state: TimelineState,
+    /// Whether the transaction is in the parking lot, i.e. it can't be included in the next block
+    /// because of a sequence number gap
+    pub parked: bool,
+    pub insertion_info: InsertionInfo,
+}
+
 #[cfg(test)]
 mod test {
     use crate::core_mempool::{MempoolTransaction, TimelineState};
@@ -9,7 +9,7 @@ use crate::{
             PriorityQueueIter, TTLIndex,
         },
         mempool::Mempool,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction::{InsertionInfo, MempoolTransaction, PendingTransactionInfo, TimelineState},
         TxnPointer,
     },
     counters,
@@ -75,6 +75,7 @@ pub struct TransactionStore {
     capacity_bytes: usize,
     capacity_per_user: usize,
     max_batch_bytes: u64,
+    replace_by_fee_min_bump_percentage: u64,
 
     // eager expiration
     eager_expire_threshold: Option<Duration>,
@@ -107,6 +108,7 @@ impl TransactionStore {
             capacity_bytes: config.capacity_bytes,
             capacity_per_user: config.capacity_per_user,
             max_batch_bytes: config.shared_mempool_max_batch_bytes,
+            replace_by_fee_min_bump_percentage: config.replace_by_fee_min_bump_percentage,
 
             // eager expiration
             eager_expire_threshold: config.eager_expire_threshold_ms.map(Duration::from_millis),
@@ -192,14 +194,15 @@ impl TransactionStore {
     }
 
     /// Insert transaction into TransactionStore. Performs validation checks and updates indexes.
-    pub(crate) fn insert(&mut self, txn: MempoolTransaction) -> MempoolStatus {
+    pub(crate) fn insert(&mut self, mut txn: MempoolTransaction) -> MempoolStatus {
         let address = txn.get_sender();
         let txn_seq_num = txn.sequence_info.transaction_sequence_number;
         let acc_seq_num = txn.sequence_info.account_sequence_number;
         let mut gas_upgraded = false;
 
         // If the transaction is already in Mempool, we only allow the user to
-        // increase the gas unit price to speed up a transaction, but not the max gas.
+        // increase the gas unit price (by at least the minimum bump) to speed up a
+        // transaction, but not the max gas.
         //
         // Transactions with all the same inputs (but possibly signed differently) are idempotent
         // since the raw transaction is the same
@@ -222,6 +225,23 @@ impl TransactionStore {
                             .to_string(),
                     );
                 } else if current_version.get_gas_price() < txn.get_gas_price() {
+                    let min_gas_price = min_replacement_gas_price(
+                        current_version.get_gas_price(),
+                        self.replace_by_fee_min_bump_percentage,
+                    );
+                    if txn.get_gas_price() < min_gas_price {
+                        return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
+                            format!(
+                                "Transaction already in mempool, the gas unit price of a replacement must be at least {}",
+                                min_gas_price
+                            ),
+                        );
+                    }
+                    // The replacement is broadcast if the replaced txn was, so that the peers
+                    // which received the replaced txn from this node get the upgrade too
+                    if current_version.timeline_state != TimelineState::NonQualified {
+                        txn.timeline_state = TimelineState::NotReady;
+                    }
                     // Update txn if gas unit price is a larger value than before
                     if let Some(txn) = txns.remove(&txn_seq_num) {
                         self.index_remove(&txn);
@@ -314,28 +334,41 @@ impl TransactionStore {
     }
 
     /// Checks if Mempool is full.
-    /// If it's full, tries to free some space by evicting transactions from the ParkingLot.
-    /// We only evict on attempt to insert a transaction that would be ready for broadcast upon insertion.
+    /// If it's full, tries to free some space by evicting transactions from the ParkingLot, the
+    /// ones with the lowest priority first. A transaction that would be ready for broadcast upon
+    /// insertion can evict any non-ready transaction, while a non-ready transaction can only evict
+    /// the ones with a lower gas ranking score.
     fn check_is_full_after_eviction(
         &mut self,
         txn: &MempoolTransaction,
         curr_sequence_number: u64,
     ) -> bool {
-        if self.is_full() && self.check_txn_ready(txn, curr_sequence_number) {
-            // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
-            if let Some(txn_pointer) = self.parking_lot_index.get_poppable() {
-                if let Some(txn) = self
+        if self.is_full() {
+            let is_ready = self.check_txn_ready(txn, curr_sequence_number);
+            while self.is_full() {
+                let txn_pointer = match self.parking_lot_index.get_poppable() {
+                    Some(key) if is_ready || key.gas_ranking_score < txn.ranking_score => {
+                        TxnPointer::from(key)
+                    },
+                    _ => break,
+                };
+                match self
                     .transactions
                     .get_mut(&txn_pointer.sender)
                     .and_then(|txns| txns.remove(&txn_pointer.sequence_number))
                 {
-                    debug!(
-                        LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(TxnsLog::new_txn(
-                            txn.get_sender(),
-                            txn.sequence_info.transaction_sequence_number
-                        ))
-                    );
-                    self.index_remove(&txn);
+                    Some(txn) => {
+                        debug!(LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(
+                            TxnsLog::new_txn(
+                                txn.get_sender(),
+                                txn.sequence_info.transaction_sequence_number
+                            )
+                        ));
+                        counters::CORE_MEMPOOL_EVICTED_TXNS.inc();
+                        self.index_remove(&txn);
+                    },
+                    // The parking lot is out of sync with the main DS, stop evicting
+                    None => break,
                 }
             }
         }
@@ -779,6 +812,36 @@ impl TransactionStore {
         txns_log
     }
 
+    /// Returns the pending transactions of an account, or of all the accounts if none is given,
+    /// ordered by account and sequence number, up to the given number of transactions.
+    pub(crate) fn get_pending_transactions(
+        &self,
+        account: Option<AccountAddress>,
+        limit: usize,
+    ) -> Vec<PendingTransactionInfo> {
+        let mut accounts: Vec<_> = match account {
+            Some(account) => vec![account],
+            None => self.transactions.keys().copied().collect(),
+        };
+        accounts.sort();
+
+        accounts
+            .iter()
+            .filter_map(|account| self.transactions.get(account).map(|txns| (account, txns)))
+            .flat_map(|(account, txns)| {
+                txns.iter()
+                    .map(move |(seq_num, txn)| PendingTransactionInfo {
+                        txn: txn.txn.clone(),
+                        ranking_score: txn.ranking_score,
+                        timeline_state: txn.timeline_state,
+                        parked: self.parking_lot_index.contains(account, seq_num),
+                        insertion_info: txn.insertion_info,
+                    })
+            })
+            .take(limit)
+            .collect()
+    }
+
     #[cfg(test)]
     pub(crate) fn get_parking_lot_size(&self) -> usize {
         self.parking_lot_index.size()
@@ -789,3 +852,10 @@ impl TransactionStore {
         &self.transactions
     }
 }
+
+/// Returns the minimum gas unit price of a transaction replacing a pending one, which is higher
+/// than the gas unit price of the pending transaction by the given percentage, and by at least 1.
+fn min_replacement_gas_price(gas_price: u64, min_bump_percentage: u64) -> u64 {
+    let min_bump = gas_price.saturating_mul(min_bump_percentage) / 100;
+    gas_price.saturating_add(max(min_bump, 1))
+}
@@ -48,7 +48,12 @@
 //! Such event “unblocks” local transaction and txn4 will be moved to OrderedQueue.
 //!
 //! Mempool only holds a limited number of transactions to prevent OOMing the system. Additionally
-//! there's a limit of number of transactions per account to prevent different abuses/attacks
+//! there's a limit of number of transactions per account to prevent different abuses/attacks.
+//! When Mempool is full, the non-ready transactions with the lowest priority are evicted to make
+//! room for ready transactions, or for non-ready transactions with a higher gas price.
+//!
+//! A pending transaction can be replaced by a transaction with the same sender, sequence number
+//! and payload, and a gas unit price higher by at least `replace_by_fee_min_bump_percentage`.
 //!
 //! Transactions in Mempool have two types of expirations: systemTTL and client-specified
 //! expiration. Once we hit either of those, the transaction is removed from Mempool. SystemTTL is
@@ -58,6 +63,7 @@
 
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
+pub use core_mempool::{InsertionInfo, PendingTransactionInfo, SubmittedBy, TimelineState};
 pub use shared_mempool::{
     bootstrap, network,
     network::MempoolSyncMsg,
@@ -157,6 +157,7 @@ pub enum LogEntry {
     ReconfigUpdate,
     JsonRpc,
     GetTransaction,
+    GetPendingTransactions,
     GetBlock,
     QuorumStore,
     StateSyncCommit,
@@ -4,7 +4,7 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool,
+    core_mempool::{CoreMempool, PendingTransactionInfo},
     network::{MempoolNetworkInterface, MempoolSyncMsg},
 };
 use anyhow::Result;
@@ -20,7 +20,8 @@ use aptos_network::{
 };
 use aptos_storage_interface::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -230,6 +231,13 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    /// Gets the pending transactions of an account, or of all the accounts if none is given, up
+    /// to the given number of transactions
+    GetPendingTransactions(
+        Option<AccountAddress>,
+        usize,
+        oneshot::Sender<Vec<PendingTransactionInfo>>,
+    ),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
@@ -655,6 +655,65 @@ fn test_parking_lot_evict_only_for_ready_txn_insertion() {
     }
 }
 
+#[test]
+fn test_parking_lot_evict_lowest_priority() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 4;
+    let mut pool = CoreMempool::new(&config);
+    // Park transactions of two accounts with different gas prices.
+    for (address, gas_price) in [(1, 1), (2, 10)] {
+        for seq in [5, 6] {
+            add_txn(&mut pool, TestTransaction::new(address, seq, gas_price)).unwrap();
+        }
+    }
+    assert_eq!(4, pool.get_parking_lot_size());
+
+    // A ready txn evicts the non-ready txn with the lowest gas price (and the highest sequence
+    // number for its account).
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    let store = pool.get_transaction_store();
+    assert!(store.get(&TestTransaction::get_address(1), 6).is_none());
+    assert!(store.get(&TestTransaction::get_address(1), 5).is_some());
+
+    // A non-ready txn only evicts the non-ready txns with a lower gas price.
+    assert!(add_txn(&mut pool, TestTransaction::new(3, 5, 1)).is_err());
+    add_txn(&mut pool, TestTransaction::new(3, 5, 5)).unwrap();
+    let store = pool.get_transaction_store();
+    assert!(store.get(&TestTransaction::get_address(1), 5).is_none());
+    assert!(add_txn(&mut pool, TestTransaction::new(3, 6, 5)).is_err());
+    assert_eq!(3, pool.get_parking_lot_size());
+}
+
+#[test]
+fn test_parking_lot_evict_account_highest_sequence_number() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 4;
+    let mut pool = CoreMempool::new(&config);
+    // Park several transactions of one account, the lowest-priority one having the lowest
+    // sequence number, and one transaction of another account.
+    for (seq, gas_price) in [(5, 1), (6, 10), (7, 5)] {
+        add_txn(&mut pool, TestTransaction::new(1, seq, gas_price)).unwrap();
+    }
+    add_txn(&mut pool, TestTransaction::new(2, 5, 3)).unwrap();
+    assert_eq!(4, pool.get_parking_lot_size());
+
+    // Ready txns evict the account with the lowest-priority txn, from its highest sequence number.
+    let address = TestTransaction::get_address(1);
+    for (ready_seq, evicted_seq) in [(0, 7), (1, 6), (2, 5)] {
+        add_txn(&mut pool, TestTransaction::new(0, ready_seq, 1)).unwrap();
+        let store = pool.get_transaction_store();
+        assert!(store.get(&address, evicted_seq).is_none());
+        for seq in 5..evicted_seq {
+            assert!(store.get(&address, seq).is_some());
+        }
+    }
+    assert_eq!(1, pool.get_parking_lot_size());
+    assert!(pool
+        .get_transaction_store()
+        .get(&TestTransaction::get_address(2), 5)
+        .is_some());
+}
+
 #[test]
 fn test_gc_ready_transaction() {
     let mut pool = setup_mempool().0;
@@ -759,6 +818,85 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
     assert_eq!(txn_by_new_hash, Some(new_txn));
 }
 
+#[test]
+fn test_get_pending_transactions() {
+    let mut pool = setup_mempool().0;
+    let txns = add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 2, 1),
+        TestTransaction::new(1, 0, 3),
+    ]);
+
+    // The transactions of an account are ordered by sequence number, and the ones after a gap
+    // are parked
+    let account_txns = pool.get_pending_transactions(Some(txns[0].sender()), 10);
+    assert_eq!(account_txns.len(), 2);
+    assert_eq!(account_txns[0].txn, txns[0]);
+    assert!(!account_txns[0].parked);
+    assert!(matches!(
+        account_txns[0].timeline_state,
+        TimelineState::Ready(_)
+    ));
+    assert_eq!(account_txns[1].txn, txns[1]);
+    assert!(account_txns[1].parked);
+    assert_eq!(account_txns[1].timeline_state, TimelineState::NotReady);
+    assert_eq!(account_txns[1].ranking_score, 1);
+
+    // All the transactions are ordered by account
+    let all_txns = pool.get_pending_transactions(None, 10);
+    assert_eq!(all_txns.len(), 3);
+    assert!(all_txns.iter().tuple_windows().all(|(a, b)| (
+        a.txn.sender(),
+        a.txn.sequence_number()
+    ) < (
+        b.txn.sender(),
+        b.txn.sequence_number()
+    )));
+    assert_eq!(pool.get_pending_transactions(None, 1).len(), 1);
+
+    // There are no transactions for unknown accounts
+    let unknown_account = TestTransaction::get_address(2);
+    assert!(pool
+        .get_pending_transactions(Some(unknown_account), 10)
+        .is_empty());
+}
+
+#[test]
+fn test_replace_by_fee_min_bump() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.replace_by_fee_min_bump_percentage = 10;
+    let mut pool = CoreMempool::new(&config);
+    add_txn(&mut pool, TestTransaction::new(0, 0, 100)).unwrap();
+
+    // The gas unit price of the replacement must be at least 10% higher.
+    let txn = TestTransaction::new(0, 0, 109).make_signed_transaction();
+    let status = pool.add_txn(txn, 109, 0, TimelineState::NotReady, false);
+    assert_eq!(status.code, MempoolStatusCode::InvalidUpdate);
+    add_txn(&mut pool, TestTransaction::new(0, 0, 110)).unwrap();
+    let txn = pool
+        .get_transaction_store()
+        .get(&TestTransaction::get_address(0), 0)
+        .unwrap();
+    assert_eq!(txn.gas_unit_price(), 110);
+}
+
+#[test]
+fn test_replacement_from_peer_is_broadcast() {
+    let mut pool = setup_mempool().0;
+    add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 0, 1)]);
+    let (timeline, timeline_id) = pool.read_timeline(&vec![0].into(), 10);
+    assert_eq!(view(timeline), vec![0]);
+
+    // Receive the replacement from a peer whose txns aren't broadcast.
+    let txn = TestTransaction::new(1, 0, 100).make_signed_transaction();
+    let status = pool.add_txn(txn.clone(), 100, 0, TimelineState::NonQualified, false);
+    assert_eq!(status.code, MempoolStatusCode::Accepted);
+
+    // The replacement is broadcast, as the replaced txn was.
+    let (timeline, _) = pool.read_timeline(&timeline_id, 10);
+    assert_eq!(timeline, vec![txn]);
+}
+
 #[test]
 fn test_bytes_limit() {
     let mut config = NodeConfig::generate_random_config();
@@ -12,10 +12,10 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
-        CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS, MAX_CONNECTION_DELAY_MS,
-        MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
-        NETWORK_CHANNEL_SIZE,
+        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, TransportProtocol,
+        CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS,
+        MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS,
+        MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
     },
     network_id::NetworkContext,
 };
@@ -84,6 +84,7 @@ impl NetworkBuilder {
         max_frame_size: usize,
         max_message_size: usize,
         enable_proxy_protocol: bool,
+        transport_protocol: TransportProtocol,
         network_channel_size: usize,
         max_concurrent_network_reqs: usize,
         inbound_connection_limit: usize,
@@ -103,6 +104,7 @@ impl NetworkBuilder {
             max_frame_size,
             max_message_size,
             enable_proxy_protocol,
+            transport_protocol,
             inbound_connection_limit,
             tcp_buffer_cfg,
         );
@@ -126,6 +128,7 @@ impl NetworkBuilder {
         network_context: NetworkContext,
         time_service: TimeService,
         listen_address: NetworkAddress,
+        transport_protocol: TransportProtocol,
         authentication_mode: AuthenticationMode,
         peers_and_metadata: Arc<PeersAndMetadata>,
     ) -> NetworkBuilder {
@@ -141,6 +144,7 @@ impl NetworkBuilder {
             MAX_FRAME_SIZE,
             MAX_MESSAGE_SIZE,
             false, /* Disable proxy protocol */
+            transport_protocol,
             NETWORK_CHANNEL_SIZE,
             MAX_CONCURRENT_NETWORK_REQS,
             MAX_INBOUND_CONNECTIONS,
@@ -192,6 +196,7 @@ impl NetworkBuilder {
             config.max_frame_size,
             config.max_message_size,
             config.enable_proxy_protocol,
+            config.transport_protocol,
             config.network_channel_size,
             config.max_concurrent_network_reqs,
             config.max_inbound_connections,
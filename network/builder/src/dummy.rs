@@ -7,7 +7,7 @@
 use crate::builder::NetworkBuilder;
 use aptos_channels::aptos_channel;
 use aptos_config::{
-    config::{Peer, PeerRole, PeerSet, RoleType, NETWORK_CHANNEL_SIZE},
+    config::{Peer, PeerRole, PeerSet, RoleType, TransportProtocol, NETWORK_CHANNEL_SIZE},
     network_id::{NetworkContext, NetworkId, PeerNetworkId},
 };
 use aptos_crypto::{test_utils::TEST_SEED, x25519, Uniform};
@@ -65,6 +65,15 @@ pub struct DummyNetwork {
 
 /// The following sets up a 2 peer network and verifies connectivity.
 pub fn setup_network() -> DummyNetwork {
+    setup_network_with_transports(TransportProtocol::Tcp, TransportProtocol::Tcp)
+}
+
+/// The following sets up a 2 peer network where the listener and the dialer
+/// listen on the given transport protocols, and verifies connectivity.
+pub fn setup_network_with_transports(
+    listener_transport_protocol: TransportProtocol,
+    dialer_transport_protocol: TransportProtocol,
+) -> DummyNetwork {
     let runtime = Runtime::new().unwrap();
     let role = RoleType::Validator;
     let network_id = NetworkId::Validator;
@@ -82,8 +91,8 @@ pub fn setup_network() -> DummyNetwork {
     let listener_identity_private_key = x25519::PrivateKey::generate(&mut rng);
 
     // Setup listen addresses
-    let dialer_addr: NetworkAddress = "/ip4/127.0.0.1/tcp/0".parse().unwrap();
-    let listener_addr: NetworkAddress = "/ip4/127.0.0.1/tcp/0".parse().unwrap();
+    let dialer_addr = listen_address(dialer_transport_protocol);
+    let listener_addr = listen_address(listener_transport_protocol);
 
     // Setup seed peers
     let mut seeds = PeerSet::new();
@@ -102,6 +111,7 @@ pub fn setup_network() -> DummyNetwork {
         network_context,
         TimeService::real(),
         listener_addr,
+        listener_transport_protocol,
         authentication_mode,
         peers_and_metadata.clone(),
     );
@@ -135,6 +145,7 @@ pub fn setup_network() -> DummyNetwork {
         network_context,
         TimeService::real(),
         dialer_addr,
+        dialer_transport_protocol,
         authentication_mode,
         peers_and_metadata.clone(),
     );
@@ -155,6 +166,11 @@ pub fn setup_network() -> DummyNetwork {
         assert_eq!(metadata.remote_peer_id, listener_peer.peer_id());
         assert_eq!(metadata.origin, ConnectionOrigin::Outbound);
         assert_eq!(metadata.role, PeerRole::Validator);
+        // The dialer dials the listener over the listener's transport protocol
+        assert_eq!(
+            metadata.addr.is_quic_addr(),
+            listener_transport_protocol == TransportProtocol::Quic
+        );
     } else {
         panic!(
             "No NewPeer event on dialer received instead: {:?}",
@@ -184,3 +200,13 @@ pub fn setup_network() -> DummyNetwork {
         listener_network_client,
     }
 }
+
+/// Returns a local listen address for the given transport protocol
+fn listen_address(transport_protocol: TransportProtocol) -> NetworkAddress {
+    match transport_protocol {
+        TransportProtocol::Tcp => "/ip4/127.0.0.1/tcp/0",
+        TransportProtocol::Quic => "/ip4/127.0.0.1/udp/0/quic",
+    }
+    .parse()
+    .unwrap()
+}
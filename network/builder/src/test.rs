@@ -3,7 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Integration tests for validator_network.
-use crate::dummy::{setup_network, DummyMsg};
+use crate::dummy::{setup_network, setup_network_with_transports, DummyMsg, DummyNetwork};
+use aptos_config::config::TransportProtocol;
 use aptos_network::{application::interface::NetworkClientInterface, protocols::network::Event};
 use futures::{future::join, StreamExt};
 use std::time::Duration;
@@ -61,7 +62,37 @@ fn test_direct_send() {
 #[test]
 fn test_rpc() {
     ::aptos_logger::Logger::init_for_testing();
-    let tn = setup_network();
+    verify_rpcs(setup_network());
+}
+
+#[test]
+fn test_rpc_quic() {
+    ::aptos_logger::Logger::init_for_testing();
+    verify_rpcs(setup_network_with_transports(
+        TransportProtocol::Quic,
+        TransportProtocol::Quic,
+    ));
+}
+
+#[test]
+fn test_rpc_mixed_transports() {
+    ::aptos_logger::Logger::init_for_testing();
+
+    // The dialer listens on TCP, but dials the listener over QUIC
+    verify_rpcs(setup_network_with_transports(
+        TransportProtocol::Quic,
+        TransportProtocol::Tcp,
+    ));
+
+    // The dialer listens on QUIC, but dials the listener over TCP
+    verify_rpcs(setup_network_with_transports(
+        TransportProtocol::Tcp,
+        TransportProtocol::Quic,
+    ));
+}
+
+/// Sends an rpc from the dialer to the listener and back, and verifies the responses
+fn verify_rpcs(tn: DummyNetwork) {
     let dialer_peer = tn.dialer_peer;
     let mut dialer_events = tn.dialer_events;
     let dialer_sender = tn.dialer_network_client;
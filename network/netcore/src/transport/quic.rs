@@ -0,0 +1,764 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! QUIC Transport
+//!
+//! Connections are established over UDP with QUIC, which provides encryption,
+//! congestion control and many independent streams per connection. The TLS
+//! handshake uses an ephemeral self-signed certificate and authenticates
+//! neither side: peer authentication is left to the upper layer (i.e., the
+//! Noise IK handshake), which runs on the connection's control stream and must
+//! then be bound to the QUIC session with [`QuicConnection::channel_binding`].
+//!
+//! Once the connection is upgraded, [`QuicConnection::multiplexed_socket`]
+//! returns a [`QuicSocket`], which spreads the length-prefixed frames written
+//! to it over unidirectional streams, so that a lost packet only delays the
+//! frames on the affected stream.
+use crate::transport::Transport;
+use aptos_types::{
+    network_address::{parse_dns_quic, parse_ip_quic, NetworkAddress},
+    PeerId,
+};
+use bytes::{Bytes, BytesMut};
+use futures::{
+    channel::mpsc,
+    future::{BoxFuture, FutureExt},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite},
+    ready,
+    sink::SinkExt,
+    stream::{self, BoxStream, StreamExt},
+};
+use quinn::{
+    ClientConfig, Connecting, Endpoint, IdleTimeout, ServerConfig, TransportConfig, VarInt,
+};
+use std::{
+    convert::TryFrom,
+    fmt, io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+use tokio::net::lookup_host;
+
+/// The ALPN protocol negotiated by AptosNet QUIC connections
+pub const APTOSNET_ALPN: &[u8] = b"aptosnet";
+
+/// The length (in bytes) of the channel binding exported from a QUIC session
+pub const CHANNEL_BINDING_LEN: usize = 32;
+
+/// The TLS exporter label used to derive the channel binding
+const CHANNEL_BINDING_LABEL: &[u8] = b"EXPORTER-aptosnet-quic-channel-binding";
+
+/// The server name used for TLS. Certificates are never verified against it.
+const SERVER_NAME: &str = "aptosnet";
+
+/// The length of the big endian u32 length prefix of each frame
+const FRAME_HEADER_LEN: usize = 4;
+
+/// The number of frames that can be buffered between a `QuicSocket` and its streams
+const FRAME_BUFFER_SIZE: usize = 64;
+
+/// Transport to build QUIC connections
+#[derive(Debug, Clone)]
+pub struct QuicTransport {
+    /// The interval at which keep-alive packets are sent on idle connections
+    pub keep_alive_interval: Duration,
+    /// The duration after which an unresponsive connection is closed
+    pub max_idle_timeout: Duration,
+    /// The maximum number of concurrent unidirectional streams a remote peer may open
+    pub max_concurrent_streams: u32,
+}
+
+impl Default for QuicTransport {
+    fn default() -> Self {
+        Self {
+            keep_alive_interval: Duration::from_secs(5),
+            max_idle_timeout: Duration::from_secs(30),
+            max_concurrent_streams: 1024,
+        }
+    }
+}
+
+impl QuicTransport {
+    fn transport_config(&self) -> io::Result<TransportConfig> {
+        let max_idle_timeout = IdleTimeout::try_from(self.max_idle_timeout)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+        let mut config = TransportConfig::default();
+        config
+            .keep_alive_interval(Some(self.keep_alive_interval))
+            .max_idle_timeout(Some(max_idle_timeout))
+            // Only the control stream is bidirectional
+            .max_concurrent_bidi_streams(VarInt::from_u32(1))
+            .max_concurrent_uni_streams(VarInt::from_u32(self.max_concurrent_streams));
+        Ok(config)
+    }
+
+    fn server_config(&self) -> io::Result<ServerConfig> {
+        let certificate = rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()])
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        let certificate_der = certificate
+            .serialize_der()
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        let private_key_der = certificate.serialize_private_key_der();
+
+        let mut crypto = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![rustls::Certificate(certificate_der)],
+                rustls::PrivateKey(private_key_der),
+            )
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        crypto.alpn_protocols = vec![APTOSNET_ALPN.to_vec()];
+
+        let mut config = ServerConfig::with_crypto(Arc::new(crypto));
+        config.transport_config(Arc::new(self.transport_config()?));
+        Ok(config)
+    }
+
+    fn client_config(&self) -> io::Result<ClientConfig> {
+        let mut crypto = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(SkipServerVerification))
+            .with_no_client_auth();
+        crypto.alpn_protocols = vec![APTOSNET_ALPN.to_vec()];
+
+        let mut config = ClientConfig::new(Arc::new(crypto));
+        config.transport_config(Arc::new(self.transport_config()?));
+        Ok(config)
+    }
+}
+
+impl Transport for QuicTransport {
+    type Error = ::std::io::Error;
+    type Inbound = BoxFuture<'static, io::Result<QuicConnection>>;
+    type Listener = BoxStream<'static, io::Result<(Self::Inbound, NetworkAddress)>>;
+    type Outbound = BoxFuture<'static, io::Result<QuicConnection>>;
+    type Output = QuicConnection;
+
+    fn listen_on(
+        &self,
+        addr: NetworkAddress,
+    ) -> Result<(Self::Listener, NetworkAddress), Self::Error> {
+        let ((ipaddr, port), addr_suffix) =
+            parse_ip_quic(addr.as_slice()).ok_or_else(|| invalid_addr_error(&addr))?;
+        if !addr_suffix.is_empty() {
+            return Err(invalid_addr_error(&addr));
+        }
+
+        let endpoint = Endpoint::server(self.server_config()?, SocketAddr::new(ipaddr, port))?;
+        let listen_addr = NetworkAddress::from_quic_socket_addr(endpoint.local_addr()?);
+
+        let listener = stream::unfold(endpoint, |endpoint| async move {
+            let connecting = endpoint.accept().await?;
+            let remote_addr = NetworkAddress::from_quic_socket_addr(connecting.remote_address());
+            let inbound = accept_connection(connecting).boxed();
+            Some((Ok((inbound, remote_addr)), endpoint))
+        })
+        .boxed();
+
+        Ok((listener, listen_addr))
+    }
+
+    fn dial(&self, _peer_id: PeerId, addr: NetworkAddress) -> Result<Self::Outbound, Self::Error> {
+        let protos = addr.as_slice();
+
+        // ensure addr is well formed to save some work before potentially
+        // spawning a dial task that will fail anyway.
+        parse_ip_quic(protos)
+            .map(|_| ())
+            .or_else(|| parse_dns_quic(protos).map(|_| ()))
+            .ok_or_else(|| invalid_addr_error(&addr))?;
+
+        Ok(resolve_and_connect(addr, self.client_config()?).boxed())
+    }
+}
+
+/// Accepts an inbound connection and its control stream
+async fn accept_connection(connecting: Connecting) -> io::Result<QuicConnection> {
+    let connection = connecting.await?;
+    let (send, recv) = connection.accept_bi().await?;
+    Ok(QuicConnection {
+        connection,
+        send,
+        recv,
+    })
+}
+
+/// Connects to the given socket address and opens the control stream. Each
+/// outbound connection uses its own endpoint (i.e., its own ephemeral UDP port).
+async fn connect(
+    remote_addr: SocketAddr,
+    client_config: ClientConfig,
+) -> io::Result<QuicConnection> {
+    let bind_addr = if remote_addr.is_ipv4() {
+        SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)
+    } else {
+        SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0)
+    };
+    let endpoint = Endpoint::client(bind_addr)?;
+
+    let connection = endpoint
+        .connect_with(client_config, remote_addr, SERVER_NAME)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?
+        .await?;
+    let (send, recv) = connection.open_bi().await?;
+    Ok(QuicConnection {
+        connection,
+        send,
+        recv,
+    })
+}
+
+/// Note: we need to take ownership of this `NetworkAddress` (instead of just
+/// borrowing the `&[Protocol]` slice) so this future can be `Send + 'static`.
+pub async fn resolve_and_connect(
+    addr: NetworkAddress,
+    client_config: ClientConfig,
+) -> io::Result<QuicConnection> {
+    let protos = addr.as_slice();
+
+    if let Some(((ipaddr, port), _addr_suffix)) = parse_ip_quic(protos) {
+        // this is an /ip4 or /ip6 address, so we can just connect without any
+        // extra resolving or filtering.
+        connect(SocketAddr::new(ipaddr, port), client_config).await
+    } else if let Some(((ip_filter, dns_name, port), _addr_suffix)) = parse_dns_quic(protos) {
+        // resolve dns name and filter
+        let socketaddr_iter = lookup_host((dns_name.as_ref(), port))
+            .await?
+            .filter(move |socketaddr| ip_filter.matches(socketaddr.ip()));
+        let mut last_err = None;
+
+        // try to connect until the first succeeds
+        for socketaddr in socketaddr_iter {
+            match connect(socketaddr, client_config.clone()).await {
+                Ok(connection) => return Ok(connection),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "could not resolve dns name to any address: name: {}, ip filter: {:?}",
+                    dns_name.as_ref(),
+                    ip_filter,
+                ),
+            )
+        }))
+    } else {
+        Err(invalid_addr_error(&addr))
+    }
+}
+
+fn invalid_addr_error(addr: &NetworkAddress) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid NetworkAddress: '{}'", addr),
+    )
+}
+
+/// Accepts any server certificate. QUIC only provides confidentiality here:
+/// peers are authenticated by the Noise handshake, which is bound to the
+/// QUIC session through the channel binding.
+struct SkipServerVerification;
+
+impl rustls::client::ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+/// An established QUIC connection. Reads and writes go through the control
+/// stream (i.e., the first bidirectional stream, opened by the dialer).
+#[derive(Debug)]
+pub struct QuicConnection {
+    connection: quinn::Connection,
+    send: quinn::SendStream,
+    recv: quinn::RecvStream,
+}
+
+impl QuicConnection {
+    /// Returns the address of the remote peer
+    pub fn remote_address(&self) -> SocketAddr {
+        self.connection.remote_address()
+    }
+
+    /// Returns keying material exported from the TLS session of the connection.
+    /// Both ends of a connection derive the same value, whereas the two halves
+    /// of an intercepted connection do not.
+    pub fn channel_binding(&self) -> io::Result<[u8; CHANNEL_BINDING_LEN]> {
+        let mut channel_binding = [0u8; CHANNEL_BINDING_LEN];
+        self.connection
+            .export_keying_material(&mut channel_binding, CHANNEL_BINDING_LABEL, &[])
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    "failed to export keying material from the QUIC session",
+                )
+            })?;
+        Ok(channel_binding)
+    }
+
+    /// Returns a socket that delivers the frames written to it over the
+    /// unidirectional streams of this connection, as chosen by `classifier`.
+    pub fn multiplexed_socket(
+        &self,
+        max_frame_size: usize,
+        classifier: FrameClassifier,
+    ) -> QuicSocket {
+        QuicSocket::new(self.connection.clone(), max_frame_size, classifier)
+    }
+}
+
+impl AsyncRead for QuicConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.recv).poll_read(context, buf)
+    }
+}
+
+impl AsyncWrite for QuicConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.send).poll_write(context, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_flush(context)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_close(context)
+    }
+}
+
+/// How a frame written to a [`QuicSocket`] is delivered to the remote peer
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameDelivery {
+    /// In order with every other `Ordered` frame, on a shared stream
+    Ordered,
+    /// On its own stream, independently of every other frame
+    Independent,
+}
+
+/// Chooses the delivery of a frame, given its payload
+pub type FrameClassifier = fn(&[u8]) -> FrameDelivery;
+
+/// A socket over the unidirectional streams of a QUIC connection.
+///
+/// Writes must consist of frames prefixed with their length (as a big endian
+/// u32), each of which is sent on a stream chosen by the `classifier`. Reads
+/// return the frames received on all streams, interleaved frame by frame.
+pub struct QuicSocket {
+    connection: quinn::Connection,
+    max_frame_size: usize,
+    classifier: FrameClassifier,
+    /// bytes written to the socket that are not yet part of a sent frame
+    write_buf: BytesMut,
+    /// frames for the shared ordered stream
+    ordered_tx: mpsc::Sender<Bytes>,
+    /// held by every independent frame write until it completes (nothing is
+    /// ever sent), so that the connection is only closed once they are done
+    pending_writes_tx: mpsc::Sender<()>,
+    /// frames received from all streams
+    inbound_rx: mpsc::Receiver<Bytes>,
+    /// the remainder of the frame currently being read
+    read_buf: Bytes,
+}
+
+/// Hand written Debug implementation, as function pointers with
+/// higher-ranked lifetimes (i.e., the classifier) do not implement Debug
+impl fmt::Debug for QuicSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuicSocket")
+            .field("connection", &self.connection)
+            .field("max_frame_size", &self.max_frame_size)
+            .field("write_buf_len", &self.write_buf.len())
+            .field("read_buf_len", &self.read_buf.len())
+            .finish()
+    }
+}
+
+impl QuicSocket {
+    fn new(
+        connection: quinn::Connection,
+        max_frame_size: usize,
+        classifier: FrameClassifier,
+    ) -> Self {
+        let (ordered_tx, ordered_rx) = mpsc::channel(FRAME_BUFFER_SIZE);
+        let (inbound_tx, inbound_rx) = mpsc::channel(FRAME_BUFFER_SIZE);
+        let (pending_writes_tx, pending_writes_rx) = mpsc::channel(0);
+
+        tokio::spawn(write_ordered_frames(
+            connection.clone(),
+            ordered_rx,
+            pending_writes_rx,
+        ));
+        tokio::spawn(accept_streams(
+            connection.clone(),
+            max_frame_size,
+            inbound_tx,
+        ));
+
+        Self {
+            connection,
+            max_frame_size,
+            classifier,
+            write_buf: BytesMut::new(),
+            ordered_tx,
+            pending_writes_tx,
+            inbound_rx,
+            read_buf: Bytes::new(),
+        }
+    }
+
+    /// Sends every complete frame in the write buffer
+    fn poll_send_frames(&mut self, context: &mut Context) -> Poll<io::Result<()>> {
+        while self.write_buf.len() >= FRAME_HEADER_LEN {
+            let mut header = [0u8; FRAME_HEADER_LEN];
+            header.copy_from_slice(&self.write_buf[..FRAME_HEADER_LEN]);
+            let frame_len = u32::from_be_bytes(header) as usize;
+            if frame_len > self.max_frame_size {
+                return Poll::Ready(Err(frame_too_large_error(frame_len, self.max_frame_size)));
+            }
+            if self.write_buf.len() < FRAME_HEADER_LEN + frame_len {
+                break;
+            }
+
+            let payload = &self.write_buf[FRAME_HEADER_LEN..FRAME_HEADER_LEN + frame_len];
+            match (self.classifier)(payload) {
+                FrameDelivery::Ordered => {
+                    ready!(self.ordered_tx.poll_ready(context)).map_err(|_| closed_error())?;
+                    let frame = self.write_buf.split_to(FRAME_HEADER_LEN + frame_len);
+                    self.ordered_tx
+                        .start_send(frame.freeze())
+                        .map_err(|_| closed_error())?;
+                },
+                FrameDelivery::Independent => {
+                    let frame = self.write_buf.split_to(FRAME_HEADER_LEN + frame_len);
+                    tokio::spawn(write_independent_frame(
+                        self.connection.clone(),
+                        frame.freeze(),
+                        self.pending_writes_tx.clone(),
+                    ));
+                },
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for QuicSocket {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.read_buf.is_empty() {
+            match ready!(self.inbound_rx.poll_next_unpin(context)) {
+                Some(frame) => self.read_buf = frame,
+                // all streams are closed
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+
+        let len = std::cmp::min(buf.len(), self.read_buf.len());
+        buf[..len].copy_from_slice(&self.read_buf.split_to(len));
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl AsyncWrite for QuicSocket {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        // wait until the previously written frames are sent
+        ready!(this.poll_send_frames(context))?;
+        this.write_buf.extend_from_slice(buf);
+
+        // eagerly send the frames completed by this write
+        if let Poll::Ready(Err(err)) = this.poll_send_frames(context) {
+            return Poll::Ready(Err(err));
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        self.get_mut().poll_send_frames(context)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_send_frames(context))?;
+
+        // the ordered stream is finished and the connection closed once all
+        // buffered frames are written
+        this.ordered_tx.close_channel();
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Writes the ordered frames to a single stream. Once the socket is closed (or
+/// dropped), waits for the independent frame writes and closes the connection.
+async fn write_ordered_frames(
+    connection: quinn::Connection,
+    mut ordered_rx: mpsc::Receiver<Bytes>,
+    mut pending_writes_rx: mpsc::Receiver<()>,
+) {
+    let result: io::Result<()> = async {
+        let mut stream = connection.open_uni().await?;
+        while let Some(frame) = ordered_rx.next().await {
+            stream.write_all(&frame).await?;
+        }
+        stream.finish().await?;
+
+        // resolves once the socket and every independent frame write are dropped
+        pending_writes_rx.next().await;
+        Ok(())
+    }
+    .await;
+
+    let reason: &[u8] = if result.is_ok() {
+        b"closed"
+    } else {
+        b"stream error"
+    };
+    connection.close(VarInt::from_u32(0), reason);
+}
+
+/// Writes a single frame to its own stream. Errors mean that the connection is
+/// closed, which the socket observes when reading.
+async fn write_independent_frame(
+    connection: quinn::Connection,
+    frame: Bytes,
+    _pending_writes_tx: mpsc::Sender<()>,
+) -> io::Result<()> {
+    let mut stream = connection.open_uni().await?;
+    stream.write_all(&frame).await?;
+    stream.finish().await?;
+    Ok(())
+}
+
+/// Accepts the streams opened by the remote peer and forwards their frames
+async fn accept_streams(
+    connection: quinn::Connection,
+    max_frame_size: usize,
+    inbound_tx: mpsc::Sender<Bytes>,
+) {
+    while let Ok(stream) = connection.accept_uni().await {
+        let connection = connection.clone();
+        let inbound_tx = inbound_tx.clone();
+        tokio::spawn(async move {
+            if read_frames(stream, max_frame_size, inbound_tx)
+                .await
+                .is_err()
+            {
+                connection.close(VarInt::from_u32(1), b"invalid stream");
+            }
+        });
+    }
+}
+
+/// Reads frames from the stream until the remote peer finishes it
+async fn read_frames(
+    mut stream: quinn::RecvStream,
+    max_frame_size: usize,
+    mut inbound_tx: mpsc::Sender<Bytes>,
+) -> io::Result<()> {
+    loop {
+        // the stream may only finish on a frame boundary
+        let mut header = [0u8; FRAME_HEADER_LEN];
+        if AsyncReadExt::read(&mut stream, &mut header[..1]).await? == 0 {
+            return Ok(());
+        }
+        AsyncReadExt::read_exact(&mut stream, &mut header[1..]).await?;
+
+        let frame_len = u32::from_be_bytes(header) as usize;
+        if frame_len > max_frame_size {
+            return Err(frame_too_large_error(frame_len, max_frame_size));
+        }
+
+        let mut frame = vec![0u8; FRAME_HEADER_LEN + frame_len];
+        frame[..FRAME_HEADER_LEN].copy_from_slice(&header);
+        AsyncReadExt::read_exact(&mut stream, &mut frame[FRAME_HEADER_LEN..]).await?;
+
+        if inbound_tx.send(Bytes::from(frame)).await.is_err() {
+            // the socket was dropped
+            return Ok(());
+        }
+    }
+}
+
+fn frame_too_large_error(frame_len: usize, max_frame_size: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "Frame of {} bytes exceeds the max frame size of {} bytes",
+            frame_len, max_frame_size
+        ),
+    )
+}
+
+fn closed_error() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "QUIC connection is closed")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::transport::{quic::*, ConnectionOrigin, Transport, TransportExt};
+    use futures::{
+        future::{join, FutureExt},
+        io::{AsyncReadExt, AsyncWriteExt},
+        stream::StreamExt,
+    };
+    use std::str::FromStr;
+
+    fn frame(payload: &[u8]) -> Vec<u8> {
+        let mut frame = (payload.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    async fn read_frame(socket: &mut QuicSocket) -> io::Result<Vec<u8>> {
+        let mut header = [0u8; FRAME_HEADER_LEN];
+        socket.read_exact(&mut header).await?;
+        let mut frame = header.to_vec();
+        frame.resize(FRAME_HEADER_LEN + u32::from_be_bytes(header) as usize, 0);
+        socket.read_exact(&mut frame[FRAME_HEADER_LEN..]).await?;
+        Ok(frame)
+    }
+
+    /// Frames starting with a 1 are delivered independently
+    fn classify(payload: &[u8]) -> FrameDelivery {
+        match payload.first() {
+            Some(1) => FrameDelivery::Independent,
+            _ => FrameDelivery::Ordered,
+        }
+    }
+
+    #[tokio::test]
+    async fn simple_listen_and_dial() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default().and_then(|mut out, _addr, origin| async move {
+            match origin {
+                // the listener only accepts the control stream once the
+                // dialer writes to it
+                ConnectionOrigin::Inbound => {
+                    let mut buf = [0; 5];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Earth");
+                    out.write_all(b"Air").await?;
+                },
+                ConnectionOrigin::Outbound => {
+                    out.write_all(b"Earth").await?;
+                    let mut buf = [0; 3];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Air");
+                },
+            }
+            Ok(out)
+        });
+
+        let (listener, addr) = t.listen_on("/ip4/127.0.0.1/udp/0/quic".parse().unwrap())?;
+        let peer_id = PeerId::random();
+        let dial = t.dial(peer_id, addr)?;
+        let listener = listener.into_future().then(|(maybe_next, _listener)| {
+            let (incoming, _addr) = maybe_next.unwrap().unwrap();
+            incoming.map(Result::unwrap)
+        });
+
+        let (outgoing, incoming) = join(dial, listener).await;
+        let outgoing = outgoing?;
+        assert_eq!(outgoing.channel_binding()?, incoming.channel_binding()?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn multiplexed_socket() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default();
+        let (listener, addr) = t.listen_on("/ip4/127.0.0.1/udp/0/quic".parse().unwrap())?;
+        let dial = t.dial(PeerId::random(), addr)?.then(|out| async move {
+            // the listener only accepts the control stream once it is written to
+            let mut out = out?;
+            out.write_all(b"hello").await?;
+            Ok::<_, io::Error>(out)
+        });
+        let listener = listener.into_future().then(|(maybe_next, _listener)| {
+            let (incoming, _addr) = maybe_next.unwrap().unwrap();
+            incoming.map(Result::unwrap)
+        });
+        let (outgoing, incoming) = join(dial, listener).await;
+        let outgoing = outgoing?;
+
+        let max_frame_size = 64;
+        let mut outbound_socket = outgoing.multiplexed_socket(max_frame_size, classify);
+        let mut inbound_socket = incoming.multiplexed_socket(max_frame_size, classify);
+
+        let frames = vec![
+            frame(b"\x00ordered"),
+            frame(b"\x01independent"),
+            frame(b"\x00last"),
+        ];
+        for frame in &frames {
+            outbound_socket.write_all(frame).await?;
+        }
+        outbound_socket.flush().await?;
+
+        // the independent frame may arrive at any point, but the ordered
+        // frames keep their relative order
+        let mut received = Vec::new();
+        for _ in 0..frames.len() {
+            received.push(read_frame(&mut inbound_socket).await?);
+        }
+        let ordered: Vec<_> = received.iter().filter(|frame| frame[4] == 0).collect();
+        assert_eq!(ordered, vec![&frames[0], &frames[2]]);
+        received.sort();
+        let mut expected = frames.clone();
+        expected.sort();
+        assert_eq!(received, expected);
+
+        // frames larger than the max frame size are rejected
+        let oversized = frame(&[0; 65]);
+        outbound_socket.write_all(&oversized).await.unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_multiaddrs() {
+        let t = QuicTransport::default();
+
+        let result = t.listen_on(NetworkAddress::from_str("/memory/0").unwrap());
+        assert!(result.is_err());
+
+        let result = t.listen_on(NetworkAddress::from_str("/ip4/127.0.0.1/tcp/0").unwrap());
+        assert!(result.is_err());
+
+        let result = t.dial(
+            PeerId::random(),
+            NetworkAddress::from_str("/ip4/127.0.0.1/tcp/22").unwrap(),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -61,6 +61,11 @@ impl<TSocket> NoiseStream<TSocket> {
     pub fn get_remote_static(&self) -> x25519::PublicKey {
         self.session.get_remote_static()
     }
+
+    /// Returns a reference to the underlying socket
+    pub fn get_ref(&self) -> &TSocket {
+        &self.socket
+    }
 }
 
 //
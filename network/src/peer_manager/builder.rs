@@ -2,10 +2,12 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(any(test, feature = "testing", feature = "fuzzing"))]
+use crate::noise::stream::NoiseStream;
 use crate::{
     application::storage::PeersAndMetadata,
     counters,
-    noise::{stream::NoiseStream, HandshakeAuthMode},
+    noise::HandshakeAuthMode,
     peer_manager::{
         conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
         PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
@@ -14,19 +16,23 @@ use crate::{
         network::{NetworkClientConfig, NetworkServiceConfig},
         wire::handshake::v1::ProtocolIdSet,
     },
-    transport::{self, AptosNetTransport, Connection, APTOS_TCP_TRANSPORT},
+    transport::{
+        self,
+        quic::{AptosNetTcpOrQuicTransport, TcpOrQuicSocket},
+        AptosNetTransport, Connection, APTOS_TCP_TRANSPORT,
+    },
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use aptos_config::{
+    config::{TransportProtocol, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
 use aptos_netcore::transport::memory::MemoryTransport;
-use aptos_netcore::transport::{
-    tcp::{TCPBufferCfg, TcpSocket, TcpTransport},
-    Transport,
-};
+use aptos_netcore::transport::{tcp::TCPBufferCfg, Transport};
 use aptos_time_service::TimeService;
 use aptos_types::{chain_id::ChainId, network_address::NetworkAddress, PeerId};
 use std::{clone::Clone, collections::HashMap, fmt::Debug, sync::Arc};
@@ -52,6 +58,7 @@ struct TransportContext {
     authentication_mode: AuthenticationMode,
     peers_and_metadata: Arc<PeersAndMetadata>,
     enable_proxy_protocol: bool,
+    transport_protocol: TransportProtocol,
 }
 
 impl TransportContext {
@@ -141,12 +148,12 @@ impl PeerManagerContext {
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
 type MemoryPeerManager =
     PeerManager<AptosNetTransport<MemoryTransport>, NoiseStream<aptos_memsocket::MemorySocket>>;
-type TcpPeerManager = PeerManager<AptosNetTransport<TcpTransport>, NoiseStream<TcpSocket>>;
+type TcpOrQuicPeerManager = PeerManager<AptosNetTcpOrQuicTransport, TcpOrQuicSocket>;
 
 enum TransportPeerManager {
     #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
     Memory(MemoryPeerManager),
-    Tcp(TcpPeerManager),
+    TcpOrQuic(TcpOrQuicPeerManager),
 }
 
 pub struct PeerManagerBuilder {
@@ -175,6 +182,7 @@ impl PeerManagerBuilder {
         max_frame_size: usize,
         max_message_size: usize,
         enable_proxy_protocol: bool,
+        transport_protocol: TransportProtocol,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
     ) -> Self {
@@ -197,6 +205,7 @@ impl PeerManagerBuilder {
                 authentication_mode,
                 peers_and_metadata: peers_and_metadata.clone(),
                 enable_proxy_protocol,
+                transport_protocol,
             }),
             peer_manager_context: Some(PeerManagerContext::new(
                 pm_reqs_tx,
@@ -255,6 +264,7 @@ impl PeerManagerBuilder {
         let protos = transport_context.supported_protocols;
         let chain_id = transport_context.chain_id;
         let enable_proxy_protocol = transport_context.enable_proxy_protocol;
+        let transport_protocol = transport_context.transport_protocol;
 
         let (key, auth_mode) = match transport_context.authentication_mode {
             AuthenticationMode::MaybeMutual(key) => (
@@ -270,26 +280,34 @@ impl PeerManagerBuilder {
         let mut aptos_tcp_transport = APTOS_TCP_TRANSPORT.clone();
         let tcp_cfg = self.get_tcp_buffers_cfg();
         aptos_tcp_transport.set_tcp_buffers(&tcp_cfg);
-
-        self.peer_manager = match self.listen_address.as_slice() {
-            [Ip4(_), Tcp(_)] | [Ip6(_), Tcp(_)] => {
-                Some(TransportPeerManager::Tcp(self.build_with_transport(
-                    AptosNetTransport::new(
-                        aptos_tcp_transport,
-                        self.network_context,
-                        self.time_service.clone(),
-                        key,
-                        auth_mode,
-                        HANDSHAKE_VERSION,
-                        chain_id,
-                        protos,
-                        enable_proxy_protocol,
+        let max_frame_size = self.get_max_frame_size();
+
+        // Listen on the configured transport protocol. Peers are dialed over
+        // the protocol of their addresses (which may differ).
+        self.peer_manager = match (transport_protocol, self.listen_address.as_slice()) {
+            (TransportProtocol::Tcp, [Ip4(_), Tcp(_)] | [Ip6(_), Tcp(_)])
+            | (TransportProtocol::Quic, [Ip4(_), Udp(_), Quic] | [Ip6(_), Udp(_), Quic]) => {
+                Some(TransportPeerManager::TcpOrQuic(self.build_with_transport(
+                    AptosNetTcpOrQuicTransport::new(
+                        AptosNetTransport::new(
+                            aptos_tcp_transport,
+                            self.network_context,
+                            self.time_service.clone(),
+                            key,
+                            auth_mode,
+                            HANDSHAKE_VERSION,
+                            chain_id,
+                            protos,
+                            enable_proxy_protocol,
+                        ),
+                        transport_protocol,
+                        max_frame_size,
                     ),
                     executor,
                 )))
             },
             #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
-            [Memory(_)] => Some(TransportPeerManager::Memory(self.build_with_transport(
+            (_, [Memory(_)]) => Some(TransportPeerManager::Memory(self.build_with_transport(
                 AptosNetTransport::new(
                     MemoryTransport,
                     self.network_context,
@@ -304,9 +322,10 @@ impl PeerManagerBuilder {
                 executor,
             ))),
             _ => panic!(
-                "{} Unsupported listen_address: '{}', expected '/memory/<port>', \
-                 '/ip4/<addr>/tcp/<port>', or '/ip6/<addr>/tcp/<port>'.",
-                self.network_context, self.listen_address
+                "{} Unsupported listen_address: '{}' for the {:?} transport protocol, expected \
+                 '/memory/<port>', '/ip4/<addr>/tcp/<port>' or '/ip6/<addr>/tcp/<port>' (TCP), \
+                 or '/ip4/<addr>/udp/<port>/quic' or '/ip6/<addr>/udp/<port>/quic' (QUIC).",
+                self.network_context, self.listen_address, transport_protocol
             ),
         };
 
@@ -375,7 +394,7 @@ impl PeerManagerBuilder {
         {
             #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
             TransportPeerManager::Memory(pm) => self.start_peer_manager(pm, executor),
-            TransportPeerManager::Tcp(pm) => self.start_peer_manager(pm, executor),
+            TransportPeerManager::TcpOrQuic(pm) => self.start_peer_manager(pm, executor),
         }
     }
 
@@ -386,6 +405,13 @@ impl PeerManagerBuilder {
             .add_connection_event_listener()
     }
 
+    fn get_max_frame_size(&self) -> usize {
+        self.peer_manager_context
+            .as_ref()
+            .expect("Cannot get the max frame size once PeerManager has been built.")
+            .max_frame_size
+    }
+
     pub fn get_tcp_buffers_cfg(&self) -> TCPBufferCfg {
         self.peer_manager_context
             .as_ref()
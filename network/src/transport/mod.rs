@@ -24,7 +24,9 @@ use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{timeout, TimeService, TimeServiceTrait};
 use aptos_types::{
     chain_id::ChainId,
-    network_address::{parse_dns_tcp, parse_ip_tcp, parse_memory, NetworkAddress},
+    network_address::{
+        parse_dns_quic, parse_dns_tcp, parse_ip_quic, parse_ip_tcp, parse_memory, NetworkAddress,
+    },
     PeerId,
 };
 use futures::{
@@ -35,6 +37,7 @@ use futures::{
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, convert::TryFrom, fmt, io, pin::Pin, sync::Arc, time::Duration};
 
+pub mod quic;
 #[cfg(test)]
 mod test;
 
@@ -410,7 +413,8 @@ pub async fn upgrade_outbound<T: TSocket>(
 ///
 /// The base transport layer is pluggable, so long as it provides a reliable,
 /// ordered, connection-oriented, byte-stream abstraction (e.g., TCP). We currently
-/// use either `MemoryTransport` or `TcpTransport` as this base layer.
+/// use either `MemoryTransport`, `TcpTransport` or `QuicTransport` (see
+/// [`quic::AptosNetQuicTransport`]) as this base layer.
 ///
 /// Inbound and outbound connections are first established with the `base_transport`
 /// and then negotiate a secure, authenticated transport layer (currently Noise
@@ -468,6 +472,19 @@ where
         }
     }
 
+    /// Returns a transport over the given base transport that shares this
+    /// transport's identity and upgrade context (e.g., to dial peers over
+    /// another protocol). The proxy protocol is disabled on the new transport.
+    pub fn with_base_transport<T>(&self, base_transport: T) -> AptosNetTransport<T> {
+        AptosNetTransport {
+            base_transport,
+            ctxt: self.ctxt.clone(),
+            time_service: self.time_service.clone(),
+            identity_pubkey: self.identity_pubkey,
+            enable_proxy_protocol: false,
+        }
+    }
+
     fn parse_dial_addr(
         addr: &NetworkAddress,
     ) -> io::Result<(NetworkAddress, x25519::PublicKey, u8)> {
@@ -482,13 +499,15 @@ where
         let (base_transport_protos, base_transport_suffix) = parse_ip_tcp(protos)
             .map(|x| (&protos[..2], x.1))
             .or_else(|| parse_dns_tcp(protos).map(|x| (&protos[..2], x.1)))
+            .or_else(|| parse_ip_quic(protos).map(|x| (&protos[..3], x.1)))
+            .or_else(|| parse_dns_quic(protos).map(|x| (&protos[..3], x.1)))
             .or_else(|| parse_memory(protos).map(|x| (&protos[..1], x.1)))
             .ok_or_else(|| {
                 io::Error::new(
                     io::ErrorKind::InvalidInput,
                     format!(
                         "Unexpected dialing network address: '{}', expected: \
-                         memory, ip+tcp, or dns+tcp",
+                         memory, ip+tcp, dns+tcp, ip+udp+quic, or dns+udp+quic",
                         addr
                     ),
                 )
@@ -534,6 +553,10 @@ where
     /// `/dns/<ipaddr>/tcp/<port>` or
     /// `/dns4/<ipaddr>/tcp/<port>` or
     /// `/dns6/<ipaddr>/tcp/<port>`
+    ///
+    /// If the base transport is `QuicTransport`, then `/<base_transport>` is:
+    ///
+    /// `/ip4/<ipaddr>/udp/<port>/quic` (and similarly for ip6, dns, dns4 and dns6)
     pub fn dial(
         &self,
         peer_id: PeerId,
@@ -580,6 +603,11 @@ where
     ///
     /// `/ip4/<ipaddr>/tcp/<port>` or
     /// `/ip6/<ipaddr>/tcp/<port>`
+    ///
+    /// If the base transport is `QuicTransport`, then we expect:
+    ///
+    /// `/ip4/<ipaddr>/udp/<port>/quic` or
+    /// `/ip6/<ipaddr>/udp/<port>/quic`
     pub fn listen_on(
         &self,
         addr: NetworkAddress,
@@ -0,0 +1,260 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! AptosNet over QUIC
+//!
+//! [`AptosNetQuicTransport`] performs the usual AptosNet upgrade (Noise IK and
+//! the AptosNet handshake) on the control stream of a QUIC connection. Both
+//! peers then exchange the channel binding of their QUIC session over the
+//! authenticated Noise stream, which ties the peers' x25519 identities to the
+//! QUIC connection itself: a man in the middle terminating two separate QUIC
+//! sessions cannot make the bindings match.
+//!
+//! The upgraded socket sends each RPC request and response on its own QUIC
+//! stream, so a lost packet only delays the RPC it belongs to. All other
+//! messages (i.e., direct sends, stream fragments and errors) are sent in
+//! order on a single shared stream.
+//!
+//! [`AptosNetTcpOrQuicTransport`] is the transport of the TCP and QUIC
+//! networks: it listens on the configured transport protocol, but dials each
+//! peer over the protocol of the peer's address, so that a network can mix
+//! TCP and QUIC peers.
+
+use crate::{
+    logging::NetworkSchema,
+    noise::stream::NoiseStream,
+    transport::{timeout_io, AptosNetTransport, Connection, TRANSPORT_TIMEOUT},
+};
+use aptos_config::{config::TransportProtocol, network_id::NetworkContext};
+use aptos_logger::prelude::*;
+use aptos_netcore::transport::{
+    quic::{FrameDelivery, QuicConnection, QuicSocket, QuicTransport, CHANNEL_BINDING_LEN},
+    tcp::{TcpSocket, TcpTransport},
+    Transport,
+};
+use aptos_short_hex_str::AsShortHexStr;
+use aptos_time_service::TimeService;
+use aptos_types::{network_address::NetworkAddress, PeerId};
+use futures::{
+    future::{BoxFuture, Either, FutureExt, TryFutureExt},
+    io::{AsyncReadExt, AsyncWriteExt},
+    stream::{BoxStream, StreamExt, TryStreamExt},
+};
+use std::io;
+
+/// The BCS variant index of `MultiplexMessage::Message`
+const MESSAGE_VARIANT: u8 = 0;
+/// The BCS variant index of `NetworkMessage::RpcRequest`
+const RPC_REQUEST_VARIANT: u8 = 1;
+/// The BCS variant index of `NetworkMessage::RpcResponse`
+const RPC_RESPONSE_VARIANT: u8 = 2;
+
+/// The AptosNet Transport over QUIC. See the module documentation for details.
+pub struct AptosNetQuicTransport {
+    inner: AptosNetTransport<QuicTransport>,
+    max_frame_size: usize,
+}
+
+impl AptosNetQuicTransport {
+    pub fn new(inner: AptosNetTransport<QuicTransport>, max_frame_size: usize) -> Self {
+        Self {
+            inner,
+            max_frame_size,
+        }
+    }
+}
+
+impl Transport for AptosNetQuicTransport {
+    type Error = io::Error;
+    type Inbound = BoxFuture<'static, io::Result<Self::Output>>;
+    type Listener = BoxStream<'static, io::Result<(Self::Inbound, NetworkAddress)>>;
+    type Outbound = BoxFuture<'static, io::Result<Self::Output>>;
+    type Output = Connection<QuicSocket>;
+
+    fn dial(&self, peer_id: PeerId, addr: NetworkAddress) -> io::Result<Self::Outbound> {
+        let upgrade_fut = self.inner.dial(peer_id, addr)?;
+        let network_context = self.inner.ctxt.noise.network_context;
+        let time_service = self.inner.time_service.clone();
+        let max_frame_size = self.max_frame_size;
+
+        Ok(async move {
+            let connection = upgrade_fut.await?;
+            bind_connection(connection, network_context, time_service, max_frame_size).await
+        }
+        .boxed())
+    }
+
+    fn listen_on(&self, addr: NetworkAddress) -> io::Result<(Self::Listener, NetworkAddress)> {
+        let (listener, listen_addr) = self.inner.listen_on(addr)?;
+        let network_context = self.inner.ctxt.noise.network_context;
+        let time_service = self.inner.time_service.clone();
+        let max_frame_size = self.max_frame_size;
+
+        let listener = listener
+            .map_ok(move |(upgrade_fut, addr)| {
+                let time_service = time_service.clone();
+                let upgrade_fut = async move {
+                    let connection = upgrade_fut.await?;
+                    bind_connection(connection, network_context, time_service, max_frame_size).await
+                }
+                .boxed();
+                (upgrade_fut, addr)
+            })
+            .boxed();
+
+        Ok((listener, listen_addr))
+    }
+}
+
+/// The socket of a connection over either TCP or QUIC
+pub type TcpOrQuicSocket = Either<NoiseStream<TcpSocket>, QuicSocket>;
+
+/// The AptosNet Transport of the TCP and QUIC networks. See the module
+/// documentation for details.
+pub struct AptosNetTcpOrQuicTransport {
+    tcp_transport: AptosNetTransport<TcpTransport>,
+    quic_transport: AptosNetQuicTransport,
+    transport_protocol: TransportProtocol,
+}
+
+impl AptosNetTcpOrQuicTransport {
+    /// Creates a transport that listens on the given transport protocol. The
+    /// QUIC transport shares the identity and upgrade context of the TCP one.
+    pub fn new(
+        tcp_transport: AptosNetTransport<TcpTransport>,
+        transport_protocol: TransportProtocol,
+        max_frame_size: usize,
+    ) -> Self {
+        let quic_transport = AptosNetQuicTransport::new(
+            tcp_transport.with_base_transport(QuicTransport::default()),
+            max_frame_size,
+        );
+        Self {
+            tcp_transport,
+            quic_transport,
+            transport_protocol,
+        }
+    }
+}
+
+impl Transport for AptosNetTcpOrQuicTransport {
+    type Error = io::Error;
+    type Inbound = BoxFuture<'static, io::Result<Self::Output>>;
+    type Listener = BoxStream<'static, io::Result<(Self::Inbound, NetworkAddress)>>;
+    type Outbound = BoxFuture<'static, io::Result<Self::Output>>;
+    type Output = Connection<TcpOrQuicSocket>;
+
+    fn dial(&self, peer_id: PeerId, addr: NetworkAddress) -> io::Result<Self::Outbound> {
+        // dial the peer over the protocol of its address
+        if addr.is_quic_addr() {
+            let outbound = self.quic_transport.dial(peer_id, addr)?;
+            Ok(outbound
+                .map_ok(|connection| map_socket(connection, Either::Right))
+                .boxed())
+        } else {
+            let outbound = self.tcp_transport.dial(peer_id, addr)?;
+            Ok(outbound
+                .map_ok(|connection| map_socket(connection, Either::Left))
+                .boxed())
+        }
+    }
+
+    fn listen_on(&self, addr: NetworkAddress) -> io::Result<(Self::Listener, NetworkAddress)> {
+        match self.transport_protocol {
+            TransportProtocol::Tcp => {
+                let (listener, listen_addr) = self.tcp_transport.listen_on(addr)?;
+                let listener = listener
+                    .map_ok(|(inbound, addr)| {
+                        let inbound = inbound
+                            .map_ok(|connection| map_socket(connection, Either::Left))
+                            .boxed();
+                        (inbound, addr)
+                    })
+                    .boxed();
+                Ok((listener, listen_addr))
+            },
+            TransportProtocol::Quic => {
+                let (listener, listen_addr) = self.quic_transport.listen_on(addr)?;
+                let listener = listener
+                    .map_ok(|(inbound, addr)| {
+                        let inbound = inbound
+                            .map_ok(|connection| map_socket(connection, Either::Right))
+                            .boxed();
+                        (inbound, addr)
+                    })
+                    .boxed();
+                Ok((listener, listen_addr))
+            },
+        }
+    }
+}
+
+/// Wraps the socket of the given connection
+fn map_socket<T, U>(connection: Connection<T>, wrap: impl FnOnce(T) -> U) -> Connection<U> {
+    let Connection { socket, metadata } = connection;
+    Connection {
+        socket: wrap(socket),
+        metadata,
+    }
+}
+
+/// Verifies that both peers observe the same QUIC session, and then replaces
+/// the control stream with a socket multiplexed over the connection's streams.
+async fn bind_connection(
+    connection: Connection<NoiseStream<QuicConnection>>,
+    network_context: NetworkContext,
+    time_service: TimeService,
+    max_frame_size: usize,
+) -> io::Result<Connection<QuicSocket>> {
+    let Connection {
+        mut socket,
+        metadata,
+    } = connection;
+
+    // exchange channel bindings over the authenticated noise stream
+    let channel_binding = socket.get_ref().channel_binding()?;
+    let remote_channel_binding = timeout_io(time_service, TRANSPORT_TIMEOUT, async {
+        socket.write_all(&channel_binding).await?;
+        socket.flush().await?;
+
+        let mut remote_channel_binding = [0u8; CHANNEL_BINDING_LEN];
+        socket.read_exact(&mut remote_channel_binding).await?;
+        Ok(remote_channel_binding)
+    })
+    .await?;
+
+    if channel_binding != remote_channel_binding {
+        let err = io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "QUIC channel binding mismatch with peer {}",
+                metadata.remote_peer_id.short_str()
+            ),
+        );
+        error!(
+            SecurityEvent::NoiseHandshake,
+            NetworkSchema::new(&network_context)
+                .network_address(&metadata.addr)
+                .connection_origin(&metadata.origin),
+            error = %err,
+        );
+        return Err(err);
+    }
+
+    let socket = socket
+        .get_ref()
+        .multiplexed_socket(max_frame_size, classify_frame);
+    Ok(Connection { socket, metadata })
+}
+
+/// Sends RPC requests and responses on their own streams, and every other
+/// `MultiplexMessage` in order on the shared stream. The BCS encoding of an
+/// enum starts with its variant index (a single byte for these variants).
+pub(crate) fn classify_frame(frame: &[u8]) -> FrameDelivery {
+    match frame {
+        [MESSAGE_VARIANT, RPC_REQUEST_VARIANT | RPC_RESPONSE_VARIANT, ..] => {
+            FrameDelivery::Independent
+        },
+        _ => FrameDelivery::Ordered,
+    }
+}
@@ -3,16 +3,29 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    protocols::wire::handshake::v1::{MessagingProtocolVersion, ProtocolId, ProtocolIdSet},
+    protocols::wire::{
+        handshake::v1::{MessagingProtocolVersion, ProtocolId, ProtocolIdSet},
+        messaging::v1::{
+            DirectSendMsg, MultiplexMessage, MultiplexMessageSink, MultiplexMessageStream,
+            NetworkMessage, RpcRequest, RpcResponse,
+        },
+    },
     testutils,
-    transport::*,
+    transport::{
+        quic::{classify_frame, AptosNetQuicTransport},
+        *,
+    },
 };
 use aptos_config::config::{Peer, PeerRole, PeerSet, HANDSHAKE_VERSION};
 use aptos_crypto::{test_utils::TEST_SEED, traits::Uniform, x25519};
 use aptos_infallible::RwLock;
 use aptos_netcore::{
     framing::{read_u16frame, write_u16frame},
-    transport::{memory, ConnectionOrigin, Transport},
+    transport::{
+        memory,
+        quic::{FrameDelivery, QuicTransport},
+        ConnectionOrigin, Transport,
+    },
 };
 use aptos_time_service::MockTimeService;
 use aptos_types::{
@@ -21,7 +34,7 @@ use aptos_types::{
     PeerId,
 };
 use bytes::{Bytes, BytesMut};
-use futures::{future, io::AsyncWriteExt, stream::StreamExt};
+use futures::{future, io::AsyncWriteExt, sink::SinkExt, stream::StreamExt};
 use rand::{rngs::StdRng, SeedableRng};
 use std::{io, iter::FromIterator, sync::Arc};
 use tokio::runtime::Runtime;
@@ -227,6 +240,22 @@ fn expect_ip4_tcp_noise_addr(addr: &NetworkAddress) {
     );
 }
 
+/// Check that the network address matches the format
+/// `"/ip4/<ipaddr>/udp/<port>/quic/noise-ik/<pubkey>/handshake/<version>"`
+fn expect_ip4_quic_noise_addr(addr: &NetworkAddress) {
+    assert!(
+        matches!(addr.as_slice(), [
+            Ip4(_),
+            Udp(_),
+            Quic,
+            NoiseIK(_),
+            Handshake(_)
+        ]),
+        "addr: '{}'",
+        addr
+    );
+}
+
 fn test_transport_success<TTransport>(
     base_transport: TTransport,
     auth: Auth,
@@ -581,3 +610,148 @@ fn test_tcp_transport_rejects_unauthed_dialer() {
         expect_ip4_tcp_noise_addr,
     );
 }
+
+//////////////////////////////////////
+// AptosNetTransport<QuicTransport> //
+//////////////////////////////////////
+
+#[test]
+fn test_quic_transport_mutual_auth() {
+    test_transport_success(
+        QuicTransport::default(),
+        Auth::Mutual,
+        "/ip4/127.0.0.1/udp/0/quic",
+        expect_ip4_quic_noise_addr,
+    );
+}
+
+#[test]
+fn test_quic_transport_rejects_unauthed_dialer() {
+    test_transport_rejects_unauthed_dialer(
+        QuicTransport::default(),
+        "/ip4/127.0.0.1/udp/0/quic",
+        expect_ip4_quic_noise_addr,
+    );
+}
+
+///////////////////////////
+// AptosNetQuicTransport //
+///////////////////////////
+
+#[test]
+fn test_aptosnet_quic_transport() {
+    let (
+        rt,
+        _mock_time,
+        (listener_peer_id, listener_transport),
+        (dialer_peer_id, dialer_transport),
+        _peers_and_metadata,
+        _supported_protocols,
+    ) = setup(QuicTransport::default(), Auth::Mutual);
+    let max_frame_size = 1024;
+    let listener_transport = AptosNetQuicTransport::new(listener_transport, max_frame_size);
+    let dialer_transport = AptosNetQuicTransport::new(dialer_transport, max_frame_size);
+
+    let _guard = rt.enter();
+    let (mut inbounds, listener_addr) = listener_transport
+        .listen_on("/ip4/127.0.0.1/udp/0/quic".parse().unwrap())
+        .unwrap();
+    expect_ip4_quic_noise_addr(&listener_addr);
+
+    let direct_send = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+        protocol_id: ProtocolId::DiscoveryDirectSend,
+        priority: 0,
+        raw_msg: vec![1, 2, 3],
+    }));
+    let rpc_request = MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+        protocol_id: ProtocolId::ConsensusRpcBcs,
+        request_id: 7,
+        priority: 0,
+        raw_request: vec![4, 5, 6],
+    }));
+    let rpc_response = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
+        request_id: 7,
+        priority: 0,
+        raw_response: vec![7, 8, 9],
+    }));
+
+    // the listener receives the direct send and the rpc request (in any order)
+    // and answers the rpc request
+    let expected_messages = vec![direct_send.clone(), rpc_request.clone()];
+    let response = rpc_response.clone();
+    let listener_task = async move {
+        let (inbound, _dialer_addr) = inbounds.next().await.unwrap().unwrap();
+        let conn = inbound.await.unwrap();
+        assert_eq!(conn.metadata.remote_peer_id, dialer_peer_id);
+        expect_ip4_quic_noise_addr(&conn.metadata.addr);
+
+        let (read_socket, write_socket) = futures::io::AsyncReadExt::split(conn.socket);
+        let mut message_stream = MultiplexMessageStream::new(read_socket, max_frame_size);
+        let mut message_sink = MultiplexMessageSink::new(write_socket, max_frame_size);
+
+        let mut messages = Vec::new();
+        for _ in 0..expected_messages.len() {
+            messages.push(message_stream.next().await.unwrap().unwrap());
+        }
+        for expected_message in &expected_messages {
+            assert!(messages.contains(expected_message));
+        }
+
+        message_sink.send(&response).await.unwrap();
+        message_sink.close().await.unwrap();
+    };
+
+    let dialer_task = async move {
+        let conn = dialer_transport
+            .dial(listener_peer_id, listener_addr.clone())
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(conn.metadata.remote_peer_id, listener_peer_id);
+        assert_eq!(conn.metadata.addr, listener_addr);
+
+        let (read_socket, write_socket) = futures::io::AsyncReadExt::split(conn.socket);
+        let mut message_stream = MultiplexMessageStream::new(read_socket, max_frame_size);
+        let mut message_sink = MultiplexMessageSink::new(write_socket, max_frame_size);
+
+        message_sink.send(&direct_send).await.unwrap();
+        message_sink.send(&rpc_request).await.unwrap();
+        assert_eq!(message_stream.next().await.unwrap().unwrap(), rpc_response);
+        message_sink.close().await.unwrap();
+    };
+
+    rt.block_on(future::join(listener_task, dialer_task));
+}
+
+#[test]
+fn test_quic_frame_classification() {
+    let frame = |message: &MultiplexMessage| bcs::to_bytes(message).unwrap();
+
+    let rpc_request = MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+        protocol_id: ProtocolId::ConsensusRpcBcs,
+        request_id: 0,
+        priority: 0,
+        raw_request: vec![],
+    }));
+    assert_eq!(
+        classify_frame(&frame(&rpc_request)),
+        FrameDelivery::Independent
+    );
+
+    let rpc_response = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
+        request_id: 0,
+        priority: 0,
+        raw_response: vec![],
+    }));
+    assert_eq!(
+        classify_frame(&frame(&rpc_response)),
+        FrameDelivery::Independent
+    );
+
+    let direct_send = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+        protocol_id: ProtocolId::DiscoveryDirectSend,
+        priority: 0,
+        raw_msg: vec![],
+    }));
+    assert_eq!(classify_frame(&frame(&direct_send)), FrameDelivery::Ordered);
+}
@@ -0,0 +1,540 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sequence number management for accounts submitting many transactions concurrently.
+//!
+//! A [`ManagedAccount`] leases sequence numbers to the transactions of an account, so that many of
+//! them can be in flight at once. A transaction which doesn't make it on chain leaves a gap in the
+//! sequence numbers, which blocks all the transactions leased after it. The account then rewinds
+//! to the sequence number of that transaction, and the transactions leased after it fail in turn
+//! without triggering another rewind, while the ones leased before it are unaffected.
+//!
+//! An [`AccountManager`] spreads transactions over a pool of managed accounts.
+
+use crate::{
+    rest_client::{aptos_api_types::TransactionOnChainData, Client, Response},
+    transaction_builder::TransactionBuilder,
+    types::{account_address::AccountAddress, transaction::SignedTransaction, LocalAccount},
+};
+use anyhow::{anyhow, Result};
+use aptos_infallible::Mutex;
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The default maximum number of transactions of an account in flight at once, which is the
+/// default capacity of an account in mempool.
+pub const DEFAULT_MAX_IN_FLIGHT_PER_ACCOUNT: u64 = 100;
+
+/// A sequence number leased to a transaction of a [`ManagedAccount`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequenceNumberLease {
+    sequence_number: u64,
+    /// The number of rewinds of the account before the lease.
+    generation: u64,
+}
+
+impl SequenceNumberLease {
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+}
+
+/// A transaction submitted by a [`ManagedAccount`], along with the lease of its sequence number.
+#[derive(Clone, Debug)]
+pub struct SubmittedTransaction {
+    transaction: SignedTransaction,
+    lease: SequenceNumberLease,
+}
+
+impl SubmittedTransaction {
+    pub fn transaction(&self) -> &SignedTransaction {
+        &self.transaction
+    }
+
+    pub fn lease(&self) -> SequenceNumberLease {
+        self.lease
+    }
+
+    pub fn into_inner(self) -> SignedTransaction {
+        self.transaction
+    }
+}
+
+#[derive(Debug)]
+struct AccountState {
+    /// The account, whose sequence number is the next one to lease.
+    account: LocalAccount,
+    /// All the transactions with a lower sequence number are known to be committed.
+    committed_sequence_number: u64,
+    /// The first sequence number leased in each generation, i.e. since each rewind, in increasing
+    /// order. Only the generations which may still have leases in flight are kept.
+    generation_starts: VecDeque<(u64, u64)>,
+}
+
+impl AccountState {
+    fn new(account: LocalAccount) -> Self {
+        Self {
+            committed_sequence_number: account.sequence_number(),
+            generation_starts: VecDeque::from([(account.sequence_number(), 0)]),
+            account,
+        }
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation_starts
+            .back()
+            .map_or(0, |(_, generation)| *generation)
+    }
+
+    /// Whether the sequence number of the lease wasn't leased again since, after a rewind.
+    fn is_valid(&self, lease: SequenceNumberLease) -> bool {
+        self.generation_starts
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= lease.sequence_number)
+            .map_or(false, |(_, generation)| *generation == lease.generation)
+    }
+
+    fn in_flight(&self) -> u64 {
+        self.account
+            .sequence_number()
+            .saturating_sub(self.committed_sequence_number)
+    }
+
+    fn lease(&mut self) -> SequenceNumberLease {
+        let lease = SequenceNumberLease {
+            sequence_number: self.account.sequence_number(),
+            generation: self.generation(),
+        };
+        *self.account.sequence_number_mut() += 1;
+        lease
+    }
+
+    /// Moves the account back to the sequence number, which invalidates the leases from it on.
+    fn rewind(&mut self, sequence_number: u64) {
+        let generation = self.generation() + 1;
+        while self
+            .generation_starts
+            .back()
+            .map_or(false, |(start, _)| *start >= sequence_number)
+        {
+            self.generation_starts.pop_back();
+        }
+        self.generation_starts
+            .push_back((sequence_number, generation));
+        *self.account.sequence_number_mut() = sequence_number;
+    }
+
+    /// Moves the account to the sequence number on chain, which invalidates all the leases not
+    /// committed yet.
+    fn resync(&mut self, sequence_number: u64) {
+        self.rewind(sequence_number);
+        self.committed_sequence_number = sequence_number;
+        self.generation_starts
+            .drain(..self.generation_starts.len() - 1);
+    }
+
+    fn mark_committed(&mut self, sequence_number: u64) {
+        self.committed_sequence_number = self.committed_sequence_number.max(sequence_number);
+        // The generations starting before the committed sequence number have no lease in flight
+        // anymore, apart from the last one of them.
+        while self
+            .generation_starts
+            .get(1)
+            .map_or(false, |(start, _)| *start <= self.committed_sequence_number)
+        {
+            self.generation_starts.pop_front();
+        }
+    }
+
+    /// Rewinds the account after the transaction with the leased sequence number failed, given
+    /// the sequence number on chain, unless the lease is no longer valid. The transactions leased
+    /// before it may still make it on chain, so only the ones from it on are invalidated. Returns
+    /// whether it rewound.
+    fn rewind_after_failure(
+        &mut self,
+        lease: SequenceNumberLease,
+        on_chain_sequence_number: u64,
+    ) -> bool {
+        if !self.is_valid(lease) {
+            return false;
+        }
+        self.mark_committed(on_chain_sequence_number);
+        if on_chain_sequence_number > lease.sequence_number {
+            // The transaction was committed even though it failed, e.g. in execution, so there
+            // is no gap.
+            return false;
+        }
+        self.rewind(lease.sequence_number);
+        true
+    }
+}
+
+/// An account whose sequence numbers are leased to concurrent transactions.
+#[derive(Debug)]
+pub struct ManagedAccount {
+    address: AccountAddress,
+    state: Mutex<AccountState>,
+}
+
+impl ManagedAccount {
+    /// Manages the account, assuming that its sequence number is the one on chain. Call
+    /// [`ManagedAccount::resync`] if it may not be.
+    pub fn new(account: LocalAccount) -> Self {
+        Self {
+            address: account.address(),
+            state: Mutex::new(AccountState::new(account)),
+        }
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    /// The next sequence number to lease.
+    pub fn sequence_number(&self) -> u64 {
+        self.state.lock().account.sequence_number()
+    }
+
+    /// The number of leased sequence numbers not known to be committed yet.
+    pub fn in_flight(&self) -> u64 {
+        self.state.lock().in_flight()
+    }
+
+    /// Leases the next sequence number.
+    pub fn lease(&self) -> SequenceNumberLease {
+        self.state.lock().lease()
+    }
+
+    /// Leases the next sequence number, unless `max_in_flight` leased ones are already in flight.
+    pub fn try_lease(&self, max_in_flight: u64) -> Option<SequenceNumberLease> {
+        let mut state = self.state.lock();
+        (state.in_flight() < max_in_flight).then(|| state.lease())
+    }
+
+    /// Signs the transaction with the leased sequence number.
+    pub fn sign(
+        &self,
+        builder: TransactionBuilder,
+        lease: SequenceNumberLease,
+    ) -> SubmittedTransaction {
+        let raw_txn = builder
+            .sender(self.address)
+            .sequence_number(lease.sequence_number)
+            .build();
+        SubmittedTransaction {
+            transaction: self.state.lock().account.sign_transaction(raw_txn),
+            lease,
+        }
+    }
+
+    /// Leases the next sequence number to the transaction and submits it.
+    pub async fn submit(
+        &self,
+        client: &Client,
+        builder: TransactionBuilder,
+    ) -> Result<SubmittedTransaction> {
+        let lease = self.lease();
+        self.submit_leased(client, builder, lease).await
+    }
+
+    /// Submits the transaction with the leased sequence number. If the submission fails, the
+    /// sequence number is returned, or the account rewinds to it if later ones are already leased.
+    pub async fn submit_leased(
+        &self,
+        client: &Client,
+        builder: TransactionBuilder,
+        lease: SequenceNumberLease,
+    ) -> Result<SubmittedTransaction> {
+        let submitted = self.sign(builder, lease);
+        if let Err(error) = client.submit_bcs(&submitted.transaction).await {
+            if !self.release(lease) {
+                // The error of the submission matters more than the one of the resync.
+                let _ = self.rewind_after_failure(client, lease).await;
+            }
+            return Err(error.into());
+        }
+        Ok(submitted)
+    }
+
+    /// Waits for the transaction to be committed. If it doesn't make it on chain, the account
+    /// rewinds to its sequence number, as all the transactions leased after it are blocked.
+    pub async fn wait(
+        &self,
+        client: &Client,
+        submitted: &SubmittedTransaction,
+    ) -> Result<Response<TransactionOnChainData>> {
+        match client
+            .wait_for_signed_transaction_bcs(&submitted.transaction)
+            .await
+        {
+            Ok(response) => {
+                self.mark_committed(submitted.lease, submitted.lease.sequence_number + 1);
+                Ok(response)
+            },
+            Err(error) => {
+                let _ = self.rewind_after_failure(client, submitted.lease).await;
+                Err(error.into())
+            },
+        }
+    }
+
+    /// Moves the account to its sequence number on chain, which invalidates all the leases.
+    pub async fn resync(&self, client: &Client) -> Result<u64> {
+        let sequence_number = Self::fetch_sequence_number(client, self.address).await?;
+        self.state.lock().resync(sequence_number);
+        Ok(sequence_number)
+    }
+
+    /// Returns the leased sequence number if no later one is leased, and whether it was returned.
+    fn release(&self, lease: SequenceNumberLease) -> bool {
+        let mut state = self.state.lock();
+        if state.is_valid(lease) && state.account.sequence_number() == lease.sequence_number + 1 {
+            *state.account.sequence_number_mut() = lease.sequence_number;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Records that all the transactions up to the sequence number are committed, unless the
+    /// sequence number of the lease was leased again since.
+    fn mark_committed(&self, lease: SequenceNumberLease, sequence_number: u64) {
+        let mut state = self.state.lock();
+        if state.is_valid(lease) {
+            state.mark_committed(sequence_number);
+        }
+    }
+
+    /// Rewinds the account to the leased sequence number after its transaction failed, unless
+    /// the lease is no longer valid. Returns whether it rewound.
+    async fn rewind_after_failure(
+        &self,
+        client: &Client,
+        lease: SequenceNumberLease,
+    ) -> Result<bool> {
+        if !self.state.lock().is_valid(lease) {
+            return Ok(false);
+        }
+        let sequence_number = Self::fetch_sequence_number(client, self.address).await?;
+        Ok(self
+            .state
+            .lock()
+            .rewind_after_failure(lease, sequence_number))
+    }
+
+    async fn fetch_sequence_number(client: &Client, address: AccountAddress) -> Result<u64> {
+        Ok(client
+            .get_account_bcs(address)
+            .await?
+            .into_inner()
+            .sequence_number())
+    }
+}
+
+/// A pool of accounts submitting transactions concurrently, each with at most a number of
+/// transactions in flight.
+pub struct AccountManager {
+    client: Client,
+    accounts: Vec<ManagedAccount>,
+    max_in_flight_per_account: u64,
+    /// The account to try first for the next transaction, so that they are spread evenly.
+    next_account: AtomicUsize,
+}
+
+impl AccountManager {
+    pub fn new(client: Client, accounts: Vec<LocalAccount>) -> Self {
+        Self {
+            client,
+            accounts: accounts.into_iter().map(ManagedAccount::new).collect(),
+            max_in_flight_per_account: DEFAULT_MAX_IN_FLIGHT_PER_ACCOUNT,
+            next_account: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn with_max_in_flight_per_account(mut self, max_in_flight_per_account: u64) -> Self {
+        self.max_in_flight_per_account = max_in_flight_per_account;
+        self
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn accounts(&self) -> &[ManagedAccount] {
+        &self.accounts
+    }
+
+    /// Resyncs all the accounts with their sequence numbers on chain.
+    pub async fn resync(&self) -> Result<()> {
+        for account in &self.accounts {
+            account.resync(&self.client).await?;
+        }
+        Ok(())
+    }
+
+    /// Leases a sequence number of the next account with room for another transaction in
+    /// flight, or returns `None` if all of them are full.
+    pub fn lease(&self) -> Option<(&ManagedAccount, SequenceNumberLease)> {
+        let start = self.next_account.fetch_add(1, Ordering::Relaxed);
+        (0..self.accounts.len()).find_map(|offset| {
+            let account = &self.accounts[(start + offset) % self.accounts.len()];
+            account
+                .try_lease(self.max_in_flight_per_account)
+                .map(|lease| (account, lease))
+        })
+    }
+
+    /// Submits the transaction from the next account with room for another transaction in
+    /// flight.
+    pub async fn submit(
+        &self,
+        builder: TransactionBuilder,
+    ) -> Result<(&ManagedAccount, SubmittedTransaction)> {
+        let (account, lease) = self.lease().ok_or_else(|| {
+            anyhow!(
+                "All {} accounts have {} transactions in flight",
+                self.accounts.len(),
+                self.max_in_flight_per_account
+            )
+        })?;
+        let submitted = account.submit_leased(&self.client, builder, lease).await?;
+        Ok((account, submitted))
+    }
+
+    /// Submits the transaction from the next account with room for another transaction in
+    /// flight, and waits for it to be committed.
+    pub async fn submit_and_wait(
+        &self,
+        builder: TransactionBuilder,
+    ) -> Result<Response<TransactionOnChainData>> {
+        let (account, submitted) = self.submit(builder).await?;
+        account.wait(&self.client, &submitted).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        chain_id::ChainId,
+        transaction::{Script, TransactionPayload},
+    };
+
+    fn builder() -> TransactionBuilder {
+        TransactionBuilder::new(
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            ChainId::test(),
+        )
+    }
+
+    #[test]
+    fn test_lease_and_release() {
+        let account = ManagedAccount::new(LocalAccount::generate(&mut rand::rngs::OsRng));
+        let first = account.lease();
+        let second = account.try_lease(2).unwrap();
+        assert_eq!(first.sequence_number(), 0);
+        assert_eq!(second.sequence_number(), 1);
+        assert_eq!(account.try_lease(2), None);
+        assert_eq!(account.in_flight(), 2);
+
+        let submitted = account.sign(builder(), second);
+        assert_eq!(submitted.transaction().sequence_number(), 1);
+        assert_eq!(submitted.transaction().sender(), account.address());
+
+        // Only the last lease can be returned without leaving a gap.
+        assert!(!account.release(first));
+        assert!(account.release(second));
+        assert_eq!(account.sequence_number(), 1);
+
+        account.mark_committed(first, 1);
+        assert_eq!(account.in_flight(), 0);
+        assert_eq!(account.try_lease(1).unwrap().sequence_number(), 1);
+    }
+
+    #[test]
+    fn test_resync_invalidates_leases() {
+        let account = ManagedAccount::new(LocalAccount::generate(&mut rand::rngs::OsRng));
+        let stale = account.lease();
+        account.lease();
+        account.state.lock().resync(0);
+        assert_eq!(account.sequence_number(), 0);
+
+        // The leases from before the resync no longer affect the account.
+        assert!(!account.release(stale));
+        account.mark_committed(stale, 1);
+        assert_eq!(account.in_flight(), 0);
+        assert_eq!(account.lease().sequence_number(), 0);
+    }
+
+    #[test]
+    fn test_failure_only_invalidates_later_leases() {
+        let account = ManagedAccount::new(LocalAccount::generate(&mut rand::rngs::OsRng));
+        let leases: Vec<_> = (0..5).map(|_| account.lease()).collect();
+
+        // The transaction with sequence number 2 fails while none is committed yet.
+        assert!(account.state.lock().rewind_after_failure(leases[2], 0));
+        assert_eq!(account.sequence_number(), 2);
+
+        // The later transactions fail in turn without rewinding the account again.
+        for lease in &leases[2..] {
+            assert!(!account.state.lock().rewind_after_failure(*lease, 0));
+            assert!(!account.release(*lease));
+        }
+        assert_eq!(account.sequence_number(), 2);
+
+        // The sequence numbers from the failed one on are leased again, while the earlier leases
+        // are still in flight.
+        let new_lease = account.lease();
+        assert_eq!(new_lease.sequence_number(), 2);
+        assert_eq!(account.in_flight(), 3);
+        account.mark_committed(leases[1], 2);
+        assert_eq!(account.in_flight(), 1);
+
+        // A failure of an earlier lease rewinds further, which invalidates the new lease too.
+        let account = ManagedAccount::new(LocalAccount::generate(&mut rand::rngs::OsRng));
+        let leases: Vec<_> = (0..5).map(|_| account.lease()).collect();
+        assert!(account.state.lock().rewind_after_failure(leases[3], 1));
+        let new_lease = account.lease();
+        assert!(account.state.lock().rewind_after_failure(leases[1], 1));
+        assert_eq!(account.sequence_number(), 1);
+        assert!(!account.state.lock().rewind_after_failure(new_lease, 1));
+        assert!(!account.state.lock().rewind_after_failure(leases[2], 1));
+
+        // A transaction already committed on chain doesn't rewind the account.
+        assert!(!account.state.lock().rewind_after_failure(leases[0], 1));
+        assert_eq!(account.sequence_number(), 1);
+        assert_eq!(account.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_manager_spreads_leases() {
+        let accounts = (0..2)
+            .map(|_| LocalAccount::generate(&mut rand::rngs::OsRng))
+            .collect();
+        let manager = AccountManager::new(
+            Client::new("http://localhost:8080".parse().unwrap()),
+            accounts,
+        )
+        .with_max_in_flight_per_account(2);
+        let mut leased: Vec<_> = (0..4)
+            .map(|_| {
+                let (account, lease) = manager.lease().unwrap();
+                (account.address(), lease.sequence_number())
+            })
+            .collect();
+        assert!(manager.lease().is_none());
+
+        leased.sort();
+        let mut expected: Vec<_> = manager
+            .accounts()
+            .iter()
+            .flat_map(|account| [(account.address(), 0), (account.address(), 1)])
+            .collect();
+        expected.sort();
+        assert_eq!(leased, expected);
+    }
+}
@@ -22,6 +22,8 @@
 
 pub use bcs;
 
+pub mod account_manager;
+
 pub mod coin_client;
 
 pub mod crypto {
@@ -5,12 +5,17 @@
 use crate::{
     crypto::{
         ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-        traits::Uniform,
+        hash::CryptoHash,
+        secp256k1_ecdsa::{Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey},
+        traits::{SigningKey, Uniform},
     },
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{AccountAuthenticator, AuthenticationKey},
+            RawTransaction, RawTransactionWithData, SignedTransaction,
+        },
     },
 };
 use anyhow::Result;
@@ -18,6 +23,7 @@ use aptos_types::event::EventKey;
 pub use aptos_types::*;
 use bip39::{Language, Mnemonic, Seed};
 use ed25519_dalek_bip32::{DerivationPath, ExtendedSecretKey};
+use serde::Serialize;
 use std::str::FromStr;
 
 /// LocalAccount represents an account on the Aptos blockchain. Internally it
@@ -82,10 +88,32 @@ impl LocalAccount {
         Self::new(address, key, 0)
     }
 
+    /// Generate a new account with a secp256k1 ECDSA key locally. Note: This
+    /// function does not actually create an account on the Aptos blockchain,
+    /// it just generates a new account locally.
+    pub fn generate_secp256k1_ecdsa<R>(rng: &mut R) -> Self
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        let key = AccountKey::generate_secp256k1_ecdsa(rng);
+        let address = key.authentication_key().derived_address();
+
+        Self::new(address, key, 0)
+    }
+
     pub fn sign_transaction(&self, txn: RawTransaction) -> SignedTransaction {
-        txn.sign(self.private_key(), self.public_key().clone())
-            .expect("Signing a txn can't fail")
-            .into_inner()
+        match &self.key.key_pair {
+            AccountKeyPair::Ed25519 {
+                private_key,
+                public_key,
+            } => txn.sign(private_key, public_key.clone()),
+            AccountKeyPair::Secp256k1Ecdsa {
+                private_key,
+                public_key,
+            } => txn.sign_secp256k1_ecdsa(private_key, public_key.clone()),
+        }
+        .expect("Signing a txn can't fail")
+        .into_inner()
     }
 
     pub fn sign_with_transaction_builder(
@@ -105,41 +133,109 @@ impl LocalAccount {
         secondary_signers: Vec<&Self>,
         builder: TransactionBuilder,
     ) -> SignedTransaction {
-        let secondary_signer_addresses = secondary_signers
+        let secondary_signer_addresses: Vec<_> = secondary_signers
             .iter()
             .map(|signer| signer.address())
             .collect();
-        let secondary_signer_privkeys = secondary_signers
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+
+        let message = RawTransactionWithData::new_multi_agent(
+            raw_txn.clone(),
+            secondary_signer_addresses.clone(),
+        );
+        let sender_authenticator = self
+            .key
+            .sign(&message)
+            .expect("Signing multi agent txn failed");
+        let secondary_authenticators = secondary_signers
+            .iter()
+            .map(|signer| signer.key.sign(&message))
+            .collect::<Result<_>>()
+            .expect("Signing multi agent txn failed");
+        SignedTransaction::new_multi_agent(
+            raw_txn,
+            sender_authenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+        )
+    }
+
+    pub fn sign_fee_payer_with_transaction_builder(
+        &mut self,
+        secondary_signers: Vec<&Self>,
+        fee_payer_signer: &Self,
+        builder: TransactionBuilder,
+    ) -> SignedTransaction {
+        let secondary_signer_addresses: Vec<_> = secondary_signers
             .iter()
-            .map(|signer| signer.private_key())
+            .map(|signer| signer.address())
             .collect();
         let raw_txn = builder
             .sender(self.address())
             .sequence_number(self.sequence_number())
             .build();
         *self.sequence_number_mut() += 1;
-        raw_txn
-            .sign_multi_agent(
-                self.private_key(),
-                secondary_signer_addresses,
-                secondary_signer_privkeys,
-            )
-            .expect("Signing multi agent txn failed")
-            .into_inner()
+
+        let message = RawTransactionWithData::new_multi_agent_with_fee_payer(
+            raw_txn.clone(),
+            secondary_signer_addresses.clone(),
+            fee_payer_signer.address(),
+        );
+        let sender_authenticator = self
+            .key
+            .sign(&message)
+            .expect("Signing fee payer txn failed");
+        let secondary_authenticators = secondary_signers
+            .iter()
+            .map(|signer| signer.key.sign(&message))
+            .collect::<Result<_>>()
+            .expect("Signing fee payer txn failed");
+        let fee_payer_authenticator = fee_payer_signer
+            .key
+            .sign(&message)
+            .expect("Signing fee payer txn failed");
+        SignedTransaction::new_fee_payer(
+            raw_txn,
+            sender_authenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+            fee_payer_signer.address(),
+            fee_payer_authenticator,
+        )
     }
 
     pub fn address(&self) -> AccountAddress {
         self.address
     }
 
+    /// Returns the Ed25519 private key of the account.
+    ///
+    /// Panics if the account is not an Ed25519 account.
     pub fn private_key(&self) -> &Ed25519PrivateKey {
         self.key.private_key()
     }
 
+    /// Returns the Ed25519 public key of the account.
+    ///
+    /// Panics if the account is not an Ed25519 account.
     pub fn public_key(&self) -> &Ed25519PublicKey {
         self.key.public_key()
     }
 
+    /// Returns the secp256k1 ECDSA private key of the account, if it is a secp256k1 ECDSA account.
+    pub fn secp256k1_ecdsa_private_key(&self) -> Option<&Secp256k1EcdsaPrivateKey> {
+        self.key.secp256k1_ecdsa_private_key()
+    }
+
+    /// Returns the secp256k1 ECDSA public key of the account, if it is a secp256k1 ECDSA account.
+    pub fn secp256k1_ecdsa_public_key(&self) -> Option<&Secp256k1EcdsaPublicKey> {
+        self.key.secp256k1_ecdsa_public_key()
+    }
+
     pub fn authentication_key(&self) -> AuthenticationKey {
         self.key.authentication_key()
     }
@@ -167,11 +263,23 @@ impl LocalAccount {
 
 #[derive(Debug)]
 pub struct AccountKey {
-    private_key: Ed25519PrivateKey,
-    public_key: Ed25519PublicKey,
+    key_pair: AccountKeyPair,
     authentication_key: AuthenticationKey,
 }
 
+/// The key pair of an account, for each supported signature scheme
+#[derive(Debug)]
+enum AccountKeyPair {
+    Ed25519 {
+        private_key: Ed25519PrivateKey,
+        public_key: Ed25519PublicKey,
+    },
+    Secp256k1Ecdsa {
+        private_key: Secp256k1EcdsaPrivateKey,
+        public_key: Secp256k1EcdsaPublicKey,
+    },
+}
+
 impl AccountKey {
     pub fn generate<R>(rng: &mut R) -> Self
     where
@@ -181,28 +289,102 @@ impl AccountKey {
         Self::from_private_key(private_key)
     }
 
+    pub fn generate_secp256k1_ecdsa<R>(rng: &mut R) -> Self
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        let private_key = Secp256k1EcdsaPrivateKey::generate(rng);
+        Self::from_secp256k1_ecdsa_private_key(private_key)
+    }
+
     pub fn from_private_key(private_key: Ed25519PrivateKey) -> Self {
         let public_key = Ed25519PublicKey::from(&private_key);
         let authentication_key = AuthenticationKey::ed25519(&public_key);
 
         Self {
-            private_key,
-            public_key,
+            key_pair: AccountKeyPair::Ed25519 {
+                private_key,
+                public_key,
+            },
             authentication_key,
         }
     }
 
+    pub fn from_secp256k1_ecdsa_private_key(private_key: Secp256k1EcdsaPrivateKey) -> Self {
+        let public_key = Secp256k1EcdsaPublicKey::from(&private_key);
+        let authentication_key = AuthenticationKey::secp256k1_ecdsa(&public_key);
+
+        Self {
+            key_pair: AccountKeyPair::Secp256k1Ecdsa {
+                private_key,
+                public_key,
+            },
+            authentication_key,
+        }
+    }
+
+    /// Returns the Ed25519 private key.
+    ///
+    /// Panics if this is not an Ed25519 key, see `secp256k1_ecdsa_private_key` for the other
+    /// scheme.
     pub fn private_key(&self) -> &Ed25519PrivateKey {
-        &self.private_key
+        match &self.key_pair {
+            AccountKeyPair::Ed25519 { private_key, .. } => private_key,
+            AccountKeyPair::Secp256k1Ecdsa { .. } => {
+                panic!("Account key is a secp256k1 ECDSA key, not an Ed25519 key")
+            },
+        }
     }
 
+    /// Returns the Ed25519 public key.
+    ///
+    /// Panics if this is not an Ed25519 key, see `secp256k1_ecdsa_public_key` for the other
+    /// scheme.
     pub fn public_key(&self) -> &Ed25519PublicKey {
-        &self.public_key
+        match &self.key_pair {
+            AccountKeyPair::Ed25519 { public_key, .. } => public_key,
+            AccountKeyPair::Secp256k1Ecdsa { .. } => {
+                panic!("Account key is a secp256k1 ECDSA key, not an Ed25519 key")
+            },
+        }
+    }
+
+    /// Returns the secp256k1 ECDSA private key, if this is a secp256k1 ECDSA key.
+    pub fn secp256k1_ecdsa_private_key(&self) -> Option<&Secp256k1EcdsaPrivateKey> {
+        match &self.key_pair {
+            AccountKeyPair::Ed25519 { .. } => None,
+            AccountKeyPair::Secp256k1Ecdsa { private_key, .. } => Some(private_key),
+        }
+    }
+
+    /// Returns the secp256k1 ECDSA public key, if this is a secp256k1 ECDSA key.
+    pub fn secp256k1_ecdsa_public_key(&self) -> Option<&Secp256k1EcdsaPublicKey> {
+        match &self.key_pair {
+            AccountKeyPair::Ed25519 { .. } => None,
+            AccountKeyPair::Secp256k1Ecdsa { public_key, .. } => Some(public_key),
+        }
     }
 
     pub fn authentication_key(&self) -> AuthenticationKey {
         self.authentication_key
     }
+
+    /// Signs `message` and returns the authenticator for the signature scheme of this key.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Result<AccountAuthenticator> {
+        Ok(match &self.key_pair {
+            AccountKeyPair::Ed25519 {
+                private_key,
+                public_key,
+            } => AccountAuthenticator::ed25519(public_key.clone(), private_key.sign(message)?),
+            AccountKeyPair::Secp256k1Ecdsa {
+                private_key,
+                public_key,
+            } => AccountAuthenticator::secp256k1_ecdsa(
+                public_key.clone(),
+                private_key.sign(message)?,
+            ),
+        })
+    }
 }
 
 impl From<Ed25519PrivateKey> for AccountKey {
@@ -211,9 +393,19 @@ impl From<Ed25519PrivateKey> for AccountKey {
     }
 }
 
+impl From<Secp256k1EcdsaPrivateKey> for AccountKey {
+    fn from(private_key: Secp256k1EcdsaPrivateKey) -> Self {
+        Self::from_secp256k1_ecdsa_private_key(private_key)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{
+        chain_id::ChainId,
+        transaction::{Script, TransactionPayload},
+    };
 
     #[test]
     fn test_recover_account_from_derive_path() {
@@ -234,4 +426,36 @@ mod tests {
         // Return an error for empty mnemonic phrase.
         assert!(LocalAccount::from_derive_path(derive_path, "", 0).is_err());
     }
+
+    #[test]
+    fn test_sign_with_secp256k1_ecdsa_account() {
+        let mut rng = rand::rngs::OsRng;
+        let mut sender = LocalAccount::generate_secp256k1_ecdsa(&mut rng);
+        let secondary_signer = LocalAccount::generate(&mut rng);
+        assert_eq!(
+            sender.address(),
+            sender.authentication_key().derived_address()
+        );
+        assert!(sender.secp256k1_ecdsa_private_key().is_some());
+        assert!(sender.secp256k1_ecdsa_public_key().is_some());
+        assert!(secondary_signer.secp256k1_ecdsa_public_key().is_none());
+
+        let builder = || {
+            TransactionBuilder::new(
+                TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+                0,
+                ChainId::test(),
+            )
+        };
+        let txn = sender.sign_with_transaction_builder(builder());
+        assert!(txn.clone().check_signature().is_ok());
+        assert_eq!(
+            txn.authenticator().sender().authentication_key(),
+            sender.authentication_key()
+        );
+
+        let txn =
+            sender.sign_multi_agent_with_transaction_builder(vec![&secondary_signer], builder());
+        assert!(txn.check_signature().is_ok());
+    }
 }
@@ -0,0 +1,238 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+};
+use anyhow::{anyhow, ensure};
+use aptos_backup_cli::{
+    backup_types::{
+        epoch_ending::restore::EpochHistoryRestoreController,
+        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        transaction::restore::TransactionRestoreBatchController,
+    },
+    metadata::{cache, cache::MetadataCacheOpt},
+    storage::{
+        command_adapter::{config::CommandAdapterConfig, CommandAdapter},
+        local_fs::LocalFs,
+        BackupStorage,
+    },
+    utils::{restore_progress::RestoreProgressTracker, GlobalRestoreOptions, RestoreRunMode},
+};
+use aptos_config::config::{BackupRestoreConfig, BackupStorageConfig};
+use aptos_db::{backup::restore_handler::RestoreHandler, state_restore::StateSnapshotRestoreMode};
+use aptos_event_notifications::{EventNotificationSender, EventSubscriptionService};
+use aptos_executor_types::VerifyExecutionMode;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_types::{transaction::Version, waypoint::Waypoint};
+use futures::channel::oneshot;
+use std::{collections::HashMap, sync::Arc};
+
+/// A simple component that restores the storage of the node from a backup
+/// storage (in the background) when bootstrapping from a backup.
+pub struct BackupRestorer {
+    // The config of the backup to restore from
+    backup_restore_config: BackupRestoreConfig,
+
+    // The subscription service to notify of the restored on-chain configs
+    event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
+
+    // The handler used to write the restored data to storage
+    restore_handler: RestoreHandler,
+
+    // The receiver for the result of the active restore (if any)
+    restore_result_receiver: Option<oneshot::Receiver<Result<Version, Error>>>,
+
+    // The trusted waypoint that the restored data is verified against
+    waypoint: Waypoint,
+}
+
+impl BackupRestorer {
+    pub fn new(
+        backup_restore_config: BackupRestoreConfig,
+        event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
+        restore_handler: RestoreHandler,
+        waypoint: Waypoint,
+    ) -> Self {
+        Self {
+            backup_restore_config,
+            event_subscription_service,
+            restore_handler,
+            restore_result_receiver: None,
+            waypoint,
+        }
+    }
+
+    /// Returns true iff a restore is currently running
+    pub fn restore_in_progress(&self) -> bool {
+        self.restore_result_receiver.is_some()
+    }
+
+    /// Starts restoring the latest data in the backup storage (in the background)
+    pub fn start_restore(&mut self) -> Result<(), Error> {
+        if self.restore_in_progress() {
+            return Err(Error::UnexpectedError(
+                "A backup restore is already in progress!".into(),
+            ));
+        }
+
+        // Spawn the restore and save the receiver for the result
+        let (result_sender, result_receiver) = oneshot::channel();
+        let backup_restore_config = self.backup_restore_config.clone();
+        let restore_handler = self.restore_handler.clone();
+        let waypoint = self.waypoint;
+        tokio::spawn(async move {
+            let result = restore_latest_backup(backup_restore_config, restore_handler, waypoint)
+                .await
+                .map_err(|error| Error::BackupRestoreError(format!("{:?}", error)));
+            if result_sender.send(result).is_err() {
+                error!(LogSchema::new(LogEntry::BackupRestorer)
+                    .message("Failed to send the result of the backup restore!"));
+            }
+        });
+        self.restore_result_receiver = Some(result_receiver);
+
+        Ok(())
+    }
+
+    /// Returns the result of the active restore (if the restore has finished).
+    /// If the restore succeeded, subscribers are notified of the restored
+    /// on-chain configs and the restored version is returned.
+    pub fn check_restore_result(&mut self) -> Option<Result<Version, Error>> {
+        let result_receiver = self.restore_result_receiver.as_mut()?;
+        let result = match result_receiver.try_recv() {
+            Ok(Some(result)) => result,
+            Ok(None) => return None, // The restore is still running
+            Err(canceled) => Err(canceled.into()),
+        };
+        self.restore_result_receiver = None;
+
+        Some(result.and_then(|restored_version| {
+            self.event_subscription_service
+                .lock()
+                .notify_initial_configs(restored_version)?;
+            Ok(restored_version)
+        }))
+    }
+}
+
+/// Restores the epoch ending ledger infos, the latest state snapshot and the
+/// transactions after it from the backup storage. The epoch history must
+/// contain the waypoint, so that all restored data is verified against it.
+/// If a previous restore was interrupted, the data it already restored is
+/// skipped. Returns the latest restored version.
+async fn restore_latest_backup(
+    backup_restore_config: BackupRestoreConfig,
+    restore_handler: RestoreHandler,
+    waypoint: Waypoint,
+) -> anyhow::Result<Version> {
+    let backup_storage: Arc<dyn BackupStorage> = match backup_restore_config.backup_storage {
+        Some(BackupStorageConfig::LocalFs { dir }) => Arc::new(LocalFs::new(dir)),
+        Some(BackupStorageConfig::CommandAdapter { config_path }) => Arc::new(CommandAdapter::new(
+            CommandAdapterConfig::load_from_file(&config_path).await?,
+        )),
+        None => return Err(anyhow!("No backup storage is configured!")),
+    };
+
+    // Load the metadata of the backups
+    let concurrent_downloads = backup_restore_config.concurrent_downloads;
+    let metadata_view = cache::sync_and_load(
+        &MetadataCacheOpt::new(backup_restore_config.metadata_cache_dir),
+        backup_storage.clone(),
+        concurrent_downloads,
+    )
+    .await?;
+    let target_version = metadata_view
+        .max_transaction_version()?
+        .ok_or_else(|| anyhow!("No transaction backup found!"))?;
+    let state_snapshot = metadata_view
+        .select_state_snapshot(target_version)?
+        .ok_or_else(|| anyhow!("No state snapshot found before version {}!", target_version))?;
+    info!(LogSchema::new(LogEntry::BackupRestorer).message(&format!(
+        "Restoring the state snapshot at version {} and the transactions up to version {}.",
+        state_snapshot.version, target_version
+    )));
+
+    let global_restore_options = GlobalRestoreOptions {
+        target_version,
+        trusted_waypoints: Arc::new(HashMap::from([(waypoint.version(), waypoint)])),
+        run_mode: Arc::new(RestoreRunMode::Restore {
+            restore_handler: restore_handler.clone(),
+        }),
+        concurrent_downloads,
+        replay_concurrency_level: backup_restore_config.replay_concurrency_level,
+        progress: Arc::new(RestoreProgressTracker::new_in_memory()),
+    };
+
+    // Restore the epoch ending ledger infos, and verify that they contain the waypoint
+    let epoch_ending_manifests = metadata_view
+        .select_epoch_ending_backups(target_version)?
+        .into_iter()
+        .map(|backup| backup.manifest)
+        .collect();
+    let epoch_history = EpochHistoryRestoreController::new(
+        epoch_ending_manifests,
+        global_restore_options.clone(),
+        backup_storage.clone(),
+    )
+    .run()
+    .await?;
+    ensure!(
+        epoch_history
+            .epoch_endings
+            .iter()
+            .any(|ledger_info| ledger_info.version() == waypoint.version()),
+        "The epoch endings in the backup don't contain the waypoint: {}",
+        waypoint
+    );
+    let epoch_history = Some(Arc::new(epoch_history));
+
+    // Restore the state snapshot (unless a previous, interrupted restore already did)
+    let restored_snapshot_version = restore_handler
+        .get_state_snapshot_before(Version::MAX)?
+        .map(|(version, _)| version);
+    if restored_snapshot_version.map_or(false, |version| version >= state_snapshot.version) {
+        info!(LogSchema::new(LogEntry::BackupRestorer).message(&format!(
+            "The state snapshot at version {} was already restored, skipping it.",
+            state_snapshot.version
+        )));
+    } else {
+        StateSnapshotRestoreController::new(
+            StateSnapshotRestoreOpt {
+                manifest_handle: state_snapshot.manifest,
+                version: state_snapshot.version,
+                validate_modules: false,
+                restore_mode: StateSnapshotRestoreMode::Default,
+            },
+            global_restore_options.clone(),
+            backup_storage.clone(),
+            epoch_history.clone(),
+        )
+        .run()
+        .await?;
+    }
+
+    // Save the transactions up to the state snapshot, and replay (and verify) the
+    // ones after it. Any transactions already in storage are skipped.
+    let transaction_manifests = metadata_view
+        .select_transaction_backups(state_snapshot.version, target_version)?
+        .into_iter()
+        .map(|backup| backup.manifest)
+        .collect();
+    TransactionRestoreBatchController::new(
+        global_restore_options,
+        backup_storage,
+        transaction_manifests,
+        None,
+        Some((state_snapshot.version + 1, false)),
+        epoch_history,
+        VerifyExecutionMode::verify_all(),
+        None,
+    )
+    .run()
+    .await?;
+
+    Ok(target_version)
+}
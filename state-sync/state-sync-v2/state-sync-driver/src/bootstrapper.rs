@@ -3,10 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_restorer::BackupRestorer,
     driver::DriverConfiguration,
     error::Error,
     logging::{LogEntry, LogSchema},
-    metadata_storage::MetadataStorageInterface,
+    metadata_storage::{BackupRestoreProgress, MetadataStorageInterface},
     metrics,
     metrics::ExecutingComponent,
     storage_synchronizer::StorageSynchronizerInterface,
@@ -284,6 +285,9 @@ pub struct Bootstrapper<MetadataStorage, StorageSyncer, StreamingClient> {
     // The currently active data stream (provided by the data streaming service)
     active_data_stream: Option<DataStreamListener>,
 
+    // The component used to restore the node from a backup storage (if configured)
+    backup_restorer: Option<BackupRestorer>,
+
     // The channel used to notify a listener of successful bootstrapping
     bootstrap_notifier_channel: Option<oneshot::Sender<Result<(), Error>>>,
 
@@ -331,6 +335,7 @@ impl<
         streaming_client: StreamingClient,
         storage: Arc<dyn DbReader>,
         storage_synchronizer: StorageSyncer,
+        backup_restorer: Option<BackupRestorer>,
     ) -> Self {
         // Load the latest epoch state from storage
         let latest_epoch_state = utils::fetch_latest_epoch_state(storage.clone())
@@ -340,6 +345,7 @@ impl<
         Self {
             state_value_syncer: StateValueSyncer::new(),
             active_data_stream: None,
+            backup_restorer,
             bootstrap_notifier_channel: None,
             bootstrapped: false,
             driver_configuration,
@@ -424,6 +430,9 @@ impl<
                 SampleRate::Duration(Duration::from_secs(PENDING_DATA_LOG_FREQ_SECS)),
                 info!("Waiting for the storage synchronizer to handle pending data!")
             );
+        } else if self.backup_restore_in_progress() {
+            // Check the progress of the backup restore
+            self.check_backup_restore_progress().await?;
         } else {
             // Fetch a new data stream to start streaming data
             self.initialize_active_data_stream(global_data_summary)
@@ -451,6 +460,12 @@ impl<
         // Reset the chunk executor to flush any invalid state currently held in-memory
         self.storage_synchronizer.reset_chunk_executor()?;
 
+        // If we're bootstrapping from a backup, the data is restored from the
+        // backup storage (instead of being fetched from the network).
+        if self.get_bootstrapping_mode() == BootstrappingMode::RestoreFromBackup {
+            return self.start_backup_restore().await;
+        }
+
         // Always fetch the new epoch ending ledger infos first
         if self.should_fetch_epoch_ending_ledger_infos() {
             return self
@@ -549,6 +564,114 @@ impl<
         }
     }
 
+    /// Starts restoring the node from the backup storage. If a previous restore
+    /// was interrupted (or failed), the restore is resumed.
+    async fn start_backup_restore(&mut self) -> Result<(), Error> {
+        match self.metadata_storage.previous_backup_restore()? {
+            Some(BackupRestoreProgress {
+                restore_completed_version: Some(restored_version),
+            }) => {
+                // The restore has already completed (and the restored data was verified)
+                info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "The node was already restored from the backup storage up to version {}!",
+                    restored_version
+                )));
+                return self.bootstrapping_complete().await;
+            },
+            Some(_) => {
+                // The restore never completed, so the storage may only hold part of
+                // the restored data. The restore must be resumed.
+                info!(LogSchema::new(LogEntry::Bootstrapper)
+                    .message("A previous backup restore did not complete! Resuming the restore."));
+            },
+            None => {
+                // If the node already has state, there's nothing to restore. If this is
+                // a fullnode, the continuous syncer will take control and get the node
+                // up-to-date. If this is a validator, consensus will take control.
+                let highest_synced_version =
+                    utils::fetch_latest_synced_version(self.storage.clone())?;
+                if highest_synced_version != GENESIS_TRANSACTION_VERSION {
+                    info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                        "The node has already synced to version {}, will skip the backup restore.",
+                        highest_synced_version
+                    )));
+                    return self.bootstrapping_complete().await;
+                }
+
+                // Record that the restore has started (so that it's resumed if interrupted)
+                self.metadata_storage.update_backup_restore_progress(None)?;
+            },
+        }
+
+        // Start the restore
+        let backup_restorer = self.backup_restorer.as_mut().ok_or_else(|| {
+            Error::UnexpectedError(
+                "The bootstrapping mode is to restore from a backup, but no backup restorer exists!"
+                    .into(),
+            )
+        })?;
+        info!(LogSchema::new(LogEntry::Bootstrapper)
+            .message("Starting to restore the node from the backup storage!"));
+        backup_restorer.start_restore()
+    }
+
+    /// Returns true iff the node is currently being restored from a backup
+    fn backup_restore_in_progress(&self) -> bool {
+        self.backup_restorer
+            .as_ref()
+            .map_or(false, |backup_restorer| {
+                backup_restorer.restore_in_progress()
+            })
+    }
+
+    /// Checks if the backup restore has finished. If so, the node has
+    /// bootstrapped, and the rest of the data will be synced from peers.
+    async fn check_backup_restore_progress(&mut self) -> Result<(), Error> {
+        let restore_result = match self.backup_restorer.as_mut() {
+            Some(backup_restorer) => backup_restorer.check_restore_result(),
+            None => None,
+        };
+        match restore_result {
+            Some(Ok(restored_version)) => {
+                info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "Successfully restored the node from the backup storage up to version {}!",
+                    restored_version
+                )));
+
+                // Verify that the storage holds all of the restored data. Otherwise, the
+                // restore is left incomplete (and will be resumed).
+                let highest_synced_version =
+                    utils::fetch_latest_synced_version(self.storage.clone())?;
+                if highest_synced_version != restored_version {
+                    return Err(Error::BackupRestoreError(format!(
+                        "The backup was restored up to version {}, but the highest synced version is {}!",
+                        restored_version, highest_synced_version
+                    )));
+                }
+
+                // Reset the chunk executor and the verified epoch states to
+                // pick up the restored data.
+                self.storage_synchronizer.reset_chunk_executor()?;
+                utils::initialize_sync_gauges(self.storage.clone())?;
+                let latest_epoch_state = utils::fetch_latest_epoch_state(self.storage.clone())?;
+                self.verified_epoch_states = VerifiedEpochStates::new(latest_epoch_state);
+
+                // Mark the restore as complete
+                self.metadata_storage
+                    .update_backup_restore_progress(Some(restored_version))?;
+                self.bootstrapping_complete().await
+            },
+            Some(Err(error)) => Err(error), // The restore will be retried
+            None => {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(PENDING_DATA_LOG_FREQ_SECS)),
+                    info!("Waiting for the backup restore to complete!")
+                );
+                Ok(())
+            },
+        }
+    }
+
     /// Attempts to fetch a data notification from the active stream
     async fn fetch_next_data_notification(&mut self) -> Result<DataNotification, Error> {
         let max_stream_wait_time_ms = self.driver_configuration.config.max_stream_wait_time_ms;
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_restorer::BackupRestorer,
     bootstrapper::Bootstrapper,
     continuous_syncer::ContinuousSyncer,
     driver_client::{ClientNotificationListener, DriverNotification},
@@ -143,6 +144,7 @@ impl<
         streaming_client: StreamingClient,
         storage: Arc<dyn DbReader>,
         time_service: TimeService,
+        backup_restorer: Option<BackupRestorer>,
     ) -> Self {
         let output_fallback_handler =
             OutputFallbackHandler::new(driver_configuration.clone(), time_service.clone());
@@ -153,6 +155,7 @@ impl<
             streaming_client.clone(),
             storage.clone(),
             storage_synchronizer.clone(),
+            backup_restorer,
         );
         let continuous_syncer = ContinuousSyncer::new(
             driver_configuration.clone(),
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_restorer::BackupRestorer,
     driver::{DriverConfiguration, StateSyncDriver},
     driver_client::{ClientNotificationListener, DriverClient, DriverNotification},
     metadata_storage::MetadataStorageInterface,
@@ -16,6 +17,7 @@ use aptos_config::config::NodeConfig;
 use aptos_consensus_notifications::ConsensusNotificationListener;
 use aptos_data_client::client::AptosDataClient;
 use aptos_data_streaming_service::streaming_client::StreamingServiceClient;
+use aptos_db::backup::restore_handler::RestoreHandler;
 use aptos_event_notifications::{EventNotificationSender, EventSubscriptionService};
 use aptos_executor_types::ChunkExecutorTrait;
 use aptos_infallible::Mutex;
@@ -52,6 +54,7 @@ impl DriverFactory {
         aptos_data_client: AptosDataClient,
         streaming_service_client: StreamingServiceClient,
         time_service: TimeService,
+        restore_handler: Option<RestoreHandler>,
     ) -> Self {
         // Notify subscribers of the initial on-chain config values
         match (&*storage.reader).fetch_latest_state_checkpoint_version() {
@@ -96,7 +99,7 @@ impl DriverFactory {
         // Create the storage synchronizer
         let event_subscription_service = Arc::new(Mutex::new(event_subscription_service));
         let (storage_synchronizer, _, _) = StorageSynchronizer::new(
-            node_config.state_sync.state_sync_driver,
+            node_config.state_sync.state_sync_driver.clone(),
             chunk_executor,
             commit_notification_sender,
             error_notification_sender,
@@ -109,11 +112,25 @@ impl DriverFactory {
 
         // Create the driver configuration
         let driver_configuration = DriverConfiguration::new(
-            node_config.state_sync.state_sync_driver,
+            node_config.state_sync.state_sync_driver.clone(),
             node_config.base.role,
             waypoint,
         );
 
+        // Create the backup restorer (if the node can restore from a backup)
+        let backup_restorer = restore_handler.map(|restore_handler| {
+            BackupRestorer::new(
+                node_config
+                    .state_sync
+                    .state_sync_driver
+                    .backup_restore
+                    .clone(),
+                event_subscription_service.clone(),
+                restore_handler,
+                waypoint,
+            )
+        });
+
         // Create the state sync driver
         let state_sync_driver = StateSyncDriver::new(
             client_notification_listener,
@@ -129,6 +146,7 @@ impl DriverFactory {
             streaming_service_client,
             storage.reader,
             time_service,
+            backup_restorer,
         );
 
         // Spawn the driver
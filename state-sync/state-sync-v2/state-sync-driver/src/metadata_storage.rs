@@ -12,7 +12,7 @@ use aptos_schemadb::{
     schema::{KeyCodec, ValueCodec},
     ColumnFamilyName, Options, SchemaBatch, DB,
 };
-use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
 use serde::{Deserialize, Serialize};
 use std::{path::Path, sync::Arc, time::Instant};
 
@@ -47,6 +47,18 @@ pub trait MetadataStorageInterface {
         last_persisted_state_value_index: u64,
         snapshot_sync_completed: bool,
     ) -> Result<(), Error>;
+
+    /// Returns the progress of any backup restore that has previously
+    /// started. If no backup restore started, None is returned.
+    fn previous_backup_restore(&self) -> Result<Option<BackupRestoreProgress>, Error>;
+
+    /// Updates the progress of the backup restore. The restore should only be
+    /// marked as completed (at the given version) once the restored data
+    /// has been verified.
+    fn update_backup_restore_progress(
+        &self,
+        restore_completed_version: Option<Version>,
+    ) -> Result<(), Error>;
 }
 
 /// The name of the state sync db file
@@ -106,10 +118,35 @@ impl PersistentMetadataStorage {
                     ))
                 })?;
         match maybe_metadata_value {
-            Some(metadata_value) => {
-                let MetadataValue::StateSnapshotSync(snapshot_progress) = metadata_value;
+            Some(MetadataValue::StateSnapshotSync(snapshot_progress)) => {
                 Ok(Some(snapshot_progress))
             },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the existing backup restore progress. Returns None if no progress is found.
+    fn get_backup_restore_progress(&self) -> Result<Option<BackupRestoreProgress>, Error> {
+        let metadata_key = MetadataKey::BackupRestore;
+        let maybe_metadata_value =
+            self.database
+                .get::<MetadataSchema>(&metadata_key)
+                .map_err(|error| {
+                    Error::StorageError(format!(
+                        "Failed to read metadata value for key: {:?}. Error: {:?}",
+                        metadata_key, error
+                    ))
+                })?;
+        match maybe_metadata_value {
+            Some(MetadataValue::BackupRestore(restore_progress)) => Ok(Some(restore_progress)),
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
             None => Ok(None),
         }
     }
@@ -224,6 +261,37 @@ impl MetadataStorageInterface for PersistentMetadataStorage {
         // Insert the new key/value pair
         self.commit_key_value(metadata_key, metadata_value)
     }
+
+    fn previous_backup_restore(&self) -> Result<Option<BackupRestoreProgress>, Error> {
+        self.get_backup_restore_progress()
+    }
+
+    fn update_backup_restore_progress(
+        &self,
+        restore_completed_version: Option<Version>,
+    ) -> Result<(), Error> {
+        // Ensure that a completed restore is never marked as incomplete
+        if let Some(restore_progress) = self.get_backup_restore_progress()? {
+            if restore_progress.restore_completed_version.is_some()
+                && restore_completed_version.is_none()
+            {
+                return Err(Error::StorageError(format!(
+                    "Failed to update the backup restore progress! The restore was already \
+                    completed at version: {:?}",
+                    restore_progress.restore_completed_version
+                )));
+            }
+        }
+
+        // Create the key/value pair
+        let metadata_key = MetadataKey::BackupRestore;
+        let metadata_value = MetadataValue::BackupRestore(BackupRestoreProgress {
+            restore_completed_version,
+        });
+
+        // Insert the new key/value pair
+        self.commit_key_value(metadata_key, metadata_value)
+    }
 }
 
 /// A simple struct for recording the progress of a state snapshot sync
@@ -234,6 +302,13 @@ pub struct StateSnapshotProgress {
     pub snapshot_sync_completed: bool,
 }
 
+/// A simple struct for recording the progress of a backup restore
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BackupRestoreProgress {
+    // The version the restore completed at, once the restored data was verified
+    pub restore_completed_version: Option<Version>,
+}
+
 /// The raw schema format used by the database
 pub mod database_schema {
     use super::*;
@@ -252,6 +327,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataKey {
         StateSnapshotSync, // A state snapshot sync that was started
+        BackupRestore,     // A backup restore that was started
     }
 
     /// A metadata value that can be inserted into the database
@@ -259,6 +335,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataValue {
         StateSnapshotSync(StateSnapshotProgress), // A state snapshot sync progress marker
+        BackupRestore(BackupRestoreProgress),     // A backup restore progress marker
     }
 
     impl KeyCodec<MetadataSchema> for MetadataKey {
@@ -154,7 +154,7 @@ impl<
         Self {
             chunk_executor: self.chunk_executor.clone(),
             commit_notification_sender: self.commit_notification_sender.clone(),
-            driver_config: self.driver_config,
+            driver_config: self.driver_config.clone(),
             error_notification_sender: self.error_notification_sender.clone(),
             executor_notifier: self.executor_notifier.clone(),
             pending_data_chunks: self.pending_data_chunks.clone(),
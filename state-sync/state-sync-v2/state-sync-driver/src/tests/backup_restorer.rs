@@ -0,0 +1,270 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{backup_restorer::BackupRestorer, error::Error};
+use aptos_backup_cli::{
+    backup_types::{
+        epoch_ending::backup::{EpochEndingBackupController, EpochEndingBackupOpt},
+        state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+        transaction::backup::{TransactionBackupController, TransactionBackupOpt},
+    },
+    storage::{local_fs::LocalFs, BackupStorage},
+    utils::{
+        backup_service_client::BackupServiceClient, test_utils::start_local_backup_service,
+        GlobalBackupOpt,
+    },
+};
+use aptos_config::config::{BackupRestoreConfig, BackupStorageConfig};
+use aptos_db::{AptosDB, GetRestoreHandler};
+use aptos_event_notifications::EventSubscriptionService;
+use aptos_executor_test_helpers::integration_test_impl::test_execution_with_storage_impl;
+use aptos_infallible::{Mutex, RwLock};
+use aptos_storage_interface::{DbReader, DbReaderWriter};
+use aptos_temppath::TempPath;
+use aptos_types::{transaction::Version, waypoint::Waypoint};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::runtime::Runtime;
+
+#[test]
+fn test_restore_from_local_backup() {
+    // Back up a database to a local directory
+    let (runtime, source_db, backup_dir, _) = create_local_backup();
+
+    // Restore a new database from the backup
+    let (target_db_dir, target_db) = create_target_db();
+    let mut backup_restorer = create_backup_restorer(&source_db, &target_db, &backup_dir);
+    let restored_version = runtime
+        .block_on(restore_backup(&mut backup_restorer))
+        .unwrap();
+
+    // Verify the restored database matches the source database
+    verify_restored_db(&source_db, &target_db, restored_version);
+
+    drop(target_db_dir);
+    runtime.shutdown_timeout(Duration::from_secs(1));
+}
+
+#[test]
+fn test_restore_from_local_backup_retry() {
+    // Back up a database to a local directory
+    let (runtime, source_db, backup_dir, state_snapshot_version) = create_local_backup();
+
+    // Make the backup of the transactions after the state snapshot unavailable
+    let transaction_backup_path = find_backup_path(
+        &backup_dir,
+        &format!("transaction_{}-", state_snapshot_version + 1),
+    );
+    let hidden_backup_path = backup_dir.path().join("hidden_backup");
+    std::fs::rename(&transaction_backup_path, &hidden_backup_path).unwrap();
+
+    // Restore a new database from the backup and verify that the restore fails partway
+    let (target_db_dir, target_db) = create_target_db();
+    let mut backup_restorer = create_backup_restorer(&source_db, &target_db, &backup_dir);
+    let error = runtime
+        .block_on(restore_backup(&mut backup_restorer))
+        .unwrap_err();
+    assert!(matches!(error, Error::BackupRestoreError(_)));
+
+    // Verify that the state snapshot was restored, but not the transactions after it
+    let (restored_snapshot_version, _) = target_db
+        .get_state_snapshot_before(Version::MAX)
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored_snapshot_version, state_snapshot_version);
+    assert!(target_db.get_latest_version().unwrap() <= state_snapshot_version);
+
+    // Make the backup available again and retry the restore
+    std::fs::rename(&hidden_backup_path, &transaction_backup_path).unwrap();
+    let restored_version = runtime
+        .block_on(restore_backup(&mut backup_restorer))
+        .unwrap();
+
+    // Verify the restored database matches the source database
+    verify_restored_db(&source_db, &target_db, restored_version);
+
+    drop(target_db_dir);
+    runtime.shutdown_timeout(Duration::from_secs(1));
+}
+
+/// Creates a database (with several epochs) and backs it up to a local
+/// directory. The transactions before and after the state snapshot are
+/// backed up separately.
+/// Returns the runtime of the backup service, the database, the backup
+/// directory and the version of the state snapshot.
+fn create_local_backup() -> (Runtime, Arc<AptosDB>, TempPath, Version) {
+    // Create the database and start the backup service
+    let source_db = test_execution_with_storage_impl();
+    let (runtime, port) = start_local_backup_service(source_db.clone());
+    let client = Arc::new(BackupServiceClient::new(format!(
+        "http://localhost:{}",
+        port
+    )));
+
+    // Create the backup storage
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let backup_storage: Arc<dyn BackupStorage> =
+        Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+    let global_backup_opt = GlobalBackupOpt {
+        max_chunk_size: 2048,
+    };
+
+    // Back up the epoch ending ledger infos
+    let latest_epoch = source_db.get_latest_epoch_state().unwrap().epoch;
+    runtime
+        .block_on(
+            EpochEndingBackupController::new(
+                EpochEndingBackupOpt {
+                    start_epoch: 0,
+                    end_epoch: latest_epoch,
+                },
+                global_backup_opt.clone(),
+                client.clone(),
+                backup_storage.clone(),
+            )
+            .run(),
+        )
+        .unwrap();
+
+    // Back up the state snapshot at the end of an earlier epoch (so that the
+    // transactions replayed after it span an epoch change).
+    let state_snapshot_epoch = latest_epoch - 2;
+    let state_snapshot_version = source_db
+        .get_epoch_ending_ledger_infos(state_snapshot_epoch, state_snapshot_epoch + 1)
+        .unwrap()
+        .ledger_info_with_sigs[0]
+        .ledger_info()
+        .version();
+    runtime
+        .block_on(
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt {
+                    epoch: state_snapshot_epoch,
+                },
+                global_backup_opt.clone(),
+                client.clone(),
+                backup_storage.clone(),
+            )
+            .run(),
+        )
+        .unwrap();
+
+    // Back up the transactions up to the state snapshot, and the ones after it
+    let latest_version = source_db.get_latest_version().unwrap();
+    for (start_version, num_transactions) in [
+        (0, state_snapshot_version + 1),
+        (
+            state_snapshot_version + 1,
+            latest_version - state_snapshot_version,
+        ),
+    ] {
+        runtime
+            .block_on(
+                TransactionBackupController::new(
+                    TransactionBackupOpt {
+                        start_version,
+                        num_transactions: num_transactions as usize,
+                    },
+                    global_backup_opt.clone(),
+                    client.clone(),
+                    backup_storage.clone(),
+                )
+                .run(),
+            )
+            .unwrap();
+    }
+
+    (runtime, source_db, backup_dir, state_snapshot_version)
+}
+
+/// Creates an empty database to restore the backup into
+fn create_target_db() -> (TempPath, Arc<AptosDB>) {
+    let target_db_dir = TempPath::new();
+    let target_db = Arc::new(AptosDB::new_for_test(&target_db_dir));
+    (target_db_dir, target_db)
+}
+
+/// Creates a backup restorer that restores the target database from the
+/// local backup directory. The restored data is verified against the
+/// genesis waypoint of the source database.
+fn create_backup_restorer(
+    source_db: &Arc<AptosDB>,
+    target_db: &Arc<AptosDB>,
+    backup_dir: &TempPath,
+) -> BackupRestorer {
+    // Create the backup restore config
+    let metadata_cache_dir = backup_dir.path().join("metadata_cache");
+    let backup_restore_config = BackupRestoreConfig {
+        backup_storage: Some(BackupStorageConfig::LocalFs {
+            dir: backup_dir.path().to_path_buf(),
+        }),
+        metadata_cache_dir: Some(metadata_cache_dir),
+        ..Default::default()
+    };
+
+    // Create the genesis waypoint
+    let genesis_ledger_info = source_db
+        .get_epoch_ending_ledger_infos(0, 1)
+        .unwrap()
+        .ledger_info_with_sigs[0]
+        .clone();
+    let waypoint = Waypoint::new_epoch_boundary(genesis_ledger_info.ledger_info()).unwrap();
+
+    // Create the event subscription service
+    let event_subscription_service = EventSubscriptionService::new(
+        &[],
+        Arc::new(RwLock::new(DbReaderWriter::new(target_db.clone()))),
+    );
+
+    BackupRestorer::new(
+        backup_restore_config,
+        Arc::new(Mutex::new(event_subscription_service)),
+        target_db.get_restore_handler(),
+        waypoint,
+    )
+}
+
+/// Returns the path of the backup with the given name prefix
+fn find_backup_path(backup_dir: &TempPath, backup_name_prefix: &str) -> PathBuf {
+    std::fs::read_dir(backup_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(backup_name_prefix)
+        })
+        .unwrap()
+        .path()
+}
+
+/// Starts the backup restore and waits for it to finish
+async fn restore_backup(backup_restorer: &mut BackupRestorer) -> Result<Version, Error> {
+    backup_restorer.start_restore()?;
+    loop {
+        if let Some(result) = backup_restorer.check_restore_result() {
+            return result;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Verifies that the target database was restored to the latest
+/// version of the source database (with the same state).
+fn verify_restored_db(
+    source_db: &Arc<AptosDB>,
+    target_db: &Arc<AptosDB>,
+    restored_version: Version,
+) {
+    let latest_version = source_db.get_latest_version().unwrap();
+    assert_eq!(restored_version, latest_version);
+    assert_eq!(
+        source_db.get_latest_transaction_info_option().unwrap(),
+        target_db.get_latest_transaction_info_option().unwrap()
+    );
+    assert_eq!(
+        source_db.get_latest_epoch_state().unwrap(),
+        target_db.get_latest_epoch_state().unwrap()
+    );
+}
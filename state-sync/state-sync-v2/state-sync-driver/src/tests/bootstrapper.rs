@@ -5,6 +5,7 @@ use crate::{
     bootstrapper::{Bootstrapper, GENESIS_TRANSACTION_VERSION},
     driver::DriverConfiguration,
     error::Error,
+    metadata_storage::BackupRestoreProgress,
     tests::{
         mocks::{
             create_mock_db_reader, create_mock_streaming_client, create_ready_storage_synchronizer,
@@ -649,6 +650,137 @@ async fn test_fetch_epoch_ending_ledger_infos() {
     assert_matches!(error, Error::DataStreamNotificationTimeout(_));
 }
 
+#[tokio::test]
+async fn test_restore_from_backup_existing_state() {
+    // Create a driver configuration with a genesis waypoint and backup restoring
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode = BootstrappingMode::RestoreFromBackup;
+
+    // Create the mock metadata storage (no backup restore was started)
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_backup_restore()
+        .returning(|| Ok(None));
+
+    // Create the bootstrapper (the node has already synced past genesis)
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        create_mock_streaming_client(),
+        metadata_storage,
+        1000,
+        true,
+    );
+
+    // Drive progress and verify that the restore is skipped and the node is bootstrapped
+    let global_data_summary = create_global_summary(1);
+    drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap();
+    assert!(bootstrapper.is_bootstrapped());
+}
+
+#[tokio::test]
+async fn test_restore_from_backup_missing_restorer() {
+    // Create a driver configuration with a genesis waypoint and backup restoring
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode = BootstrappingMode::RestoreFromBackup;
+
+    // Create the mock metadata storage (no backup restore was started)
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_backup_restore()
+        .returning(|| Ok(None));
+    metadata_storage
+        .expect_update_backup_restore_progress()
+        .with(eq(None))
+        .returning(|_| Ok(()));
+
+    // Create the bootstrapper (without a backup restorer)
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        create_mock_streaming_client(),
+        metadata_storage,
+        GENESIS_TRANSACTION_VERSION,
+        true,
+    );
+
+    // Drive progress and verify that an error is returned
+    let global_data_summary = create_global_summary(1);
+    let error = drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::UnexpectedError(_));
+    assert!(!bootstrapper.is_bootstrapped());
+}
+
+#[tokio::test]
+async fn test_restore_from_backup_completed() {
+    // Create a driver configuration with a genesis waypoint and backup restoring
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode = BootstrappingMode::RestoreFromBackup;
+
+    // Create the mock metadata storage (the backup restore has completed)
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_backup_restore()
+        .returning(|| {
+            Ok(Some(BackupRestoreProgress {
+                restore_completed_version: Some(1000),
+            }))
+        });
+
+    // Create the bootstrapper (without a backup restorer)
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        create_mock_streaming_client(),
+        metadata_storage,
+        1000,
+        true,
+    );
+
+    // Drive progress and verify that the restore is skipped and the node is bootstrapped
+    let global_data_summary = create_global_summary(1);
+    drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap();
+    assert!(bootstrapper.is_bootstrapped());
+}
+
+#[tokio::test]
+async fn test_restore_from_backup_interrupted() {
+    // Create a driver configuration with a genesis waypoint and backup restoring
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode = BootstrappingMode::RestoreFromBackup;
+
+    // Create the mock metadata storage (the backup restore started, but never completed)
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_backup_restore()
+        .returning(|| {
+            Ok(Some(BackupRestoreProgress {
+                restore_completed_version: None,
+            }))
+        });
+
+    // Create the bootstrapper (without a backup restorer). The node holds
+    // some of the restored data, i.e., it has synced past genesis.
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        create_mock_streaming_client(),
+        metadata_storage,
+        1000,
+        true,
+    );
+
+    // Drive progress and verify that the restore is resumed (and fails without a restorer)
+    let global_data_summary = create_global_summary(1);
+    let error = drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::UnexpectedError(_));
+    assert!(!bootstrapper.is_bootstrapped());
+}
+
 #[tokio::test]
 async fn test_snapshot_sync_epoch_change() {
     // Create test data
@@ -1194,6 +1326,7 @@ fn create_bootstrapper(
         mock_streaming_client,
         Arc::new(mock_database_reader),
         mock_storage_synchronizer,
+        None,
     );
 
     (bootstrapper, output_fallback_handler)
@@ -1236,6 +1369,7 @@ fn create_bootstrapper_with_storage(
         mock_streaming_client,
         Arc::new(mock_database_reader),
         mock_storage_synchronizer,
+        None,
     )
 }
 
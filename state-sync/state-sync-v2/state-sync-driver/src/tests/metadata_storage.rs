@@ -4,7 +4,8 @@
 use crate::{
     metadata_storage::{
         database_schema::{MetadataKey, MetadataSchema, MetadataValue},
-        MetadataStorageInterface, PersistentMetadataStorage, StateSnapshotProgress,
+        BackupRestoreProgress, MetadataStorageInterface, PersistentMetadataStorage,
+        StateSnapshotProgress,
     },
     tests::utils::{create_epoch_ending_ledger_info, create_ledger_info_at_version},
 };
@@ -89,6 +90,56 @@ fn test_create_then_open() {
     );
 }
 
+#[test]
+fn test_backup_restore_progress() {
+    // Create a new metadata storage
+    let tmp_dir = TempPath::new();
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+
+    // Verify the storage is empty
+    assert_none!(metadata_storage.previous_backup_restore().unwrap());
+
+    // Mark the backup restore as started (and a snapshot sync as in progress)
+    metadata_storage
+        .update_backup_restore_progress(None)
+        .unwrap();
+    let target_ledger_info = create_ledger_info_at_version(100);
+    metadata_storage
+        .update_last_persisted_state_value_index(&target_ledger_info, 10101, false)
+        .unwrap();
+
+    // Drop the handle to the storage (mimic a reboot)
+    drop(metadata_storage);
+
+    // Create another storage and verify the restore is still incomplete
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+    assert_eq!(
+        Some(BackupRestoreProgress {
+            restore_completed_version: None
+        }),
+        metadata_storage.previous_backup_restore().unwrap()
+    );
+    assert_eq!(
+        Some(target_ledger_info),
+        metadata_storage.previous_snapshot_sync_target().unwrap()
+    );
+
+    // Mark the backup restore as completed and verify the progress
+    let restored_version = 12345;
+    metadata_storage
+        .update_backup_restore_progress(Some(restored_version))
+        .unwrap();
+    assert_eq!(
+        Some(BackupRestoreProgress {
+            restore_completed_version: Some(restored_version)
+        }),
+        metadata_storage.previous_backup_restore().unwrap()
+    );
+
+    // Verify that the completed restore can't be marked as incomplete
+    assert_err!(metadata_storage.update_backup_restore_progress(None));
+}
+
 #[test]
 fn test_metadata_schema_encode_decode() {
     assert_encode_decode::<MetadataSchema>(
@@ -99,6 +150,12 @@ fn test_metadata_schema_encode_decode() {
             snapshot_sync_completed: false,
         }),
     );
+    assert_encode_decode::<MetadataSchema>(
+        &MetadataKey::BackupRestore,
+        &MetadataValue::BackupRestore(BackupRestoreProgress {
+            restore_completed_version: Some(5678),
+        }),
+    );
 }
 
 #[test]
@@ -2,6 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod backup_restorer;
 mod bootstrapper;
 mod continuous_syncer;
 mod driver;
@@ -26,7 +26,7 @@ use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_schemadb::{SchemaBatch, DB};
 use aptos_types::{
     nibble::{nibble_path::NibblePath, ROOT_NIBBLE_HEIGHT},
-    proof::{SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{SparseMerkleMultiProof, SparseMerkleProofExt, SparseMerkleRangeProof},
     state_store::state_key::StateKey,
     transaction::Version,
 };
@@ -215,6 +215,18 @@ impl StateMerkleDb {
         JellyfishMerkleTree::new(self).get_with_proof_ext(state_key.hash(), version)
     }
 
+    pub fn get_with_multi_proof(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(
+        Vec<(HashValue, Option<(HashValue, (StateKey, Version))>)>,
+        SparseMerkleMultiProof,
+    )> {
+        let keys: Vec<_> = state_keys.iter().map(|key| key.hash()).collect();
+        JellyfishMerkleTree::new(self).get_with_multi_proof(&keys, version)
+    }
+
     pub fn get_range_proof(
         &self,
         rightmost_key: HashValue,
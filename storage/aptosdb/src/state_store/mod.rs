@@ -44,7 +44,9 @@ use aptos_storage_interface::{
     DbReader, StateSnapshotReceiver,
 };
 use aptos_types::{
-    proof::{definition::LeafCount, SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{
+        definition::LeafCount, SparseMerkleMultiProof, SparseMerkleProofExt, SparseMerkleRangeProof,
+    },
     state_store::{
         create_empty_sharded_state_updates,
         state_key::StateKey,
@@ -195,6 +197,43 @@ impl DbReader for StateDb {
         ))
     }
 
+    /// Get the state values with a multi proof given the state keys and version
+    fn get_state_values_with_multi_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<Option<StateValue>>, SparseMerkleMultiProof)> {
+        // The proof has a single leaf per key, so a value can't be returned for a key twice.
+        let mut key_hashes = HashSet::with_capacity(state_keys.len());
+        for state_key in state_keys {
+            ensure!(
+                key_hashes.insert(state_key.hash()),
+                "Duplicate state key {:?} in multi proof request.",
+                state_key
+            );
+        }
+
+        let (leaf_data, proof) = self
+            .state_merkle_db
+            .get_with_multi_proof(state_keys, version)?;
+        let mut values = leaf_data
+            .into_iter()
+            .map(|(key_hash, leaf)| {
+                Ok((key_hash, match leaf {
+                    Some((_, (key, version))) => Some(self.expect_value_by_version(&key, version)?),
+                    None => None,
+                }))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok((
+            state_keys
+                .iter()
+                .map(|state_key| values.remove(&state_key.hash()).flatten())
+                .collect(),
+            proof,
+        ))
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         version.map_or(Ok(StateStorageUsage::zero()), |version| {
             Ok(self
@@ -276,6 +315,16 @@ impl DbReader for StateStore {
         self.deref()
             .get_state_value_with_proof_by_version_ext(state_key, version)
     }
+
+    /// Get the state values with a multi proof given the state keys and version
+    fn get_state_values_with_multi_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<Option<StateValue>>, SparseMerkleMultiProof)> {
+        self.deref()
+            .get_state_values_with_multi_proof_by_version(state_keys, version)
+    }
 }
 
 impl StateDb {
@@ -154,6 +154,52 @@ fn test_state_store_reader_writer() {
     verify_value_and_proof(store, key3, Some(&value3), 1, root);
 }
 
+#[test]
+fn test_state_store_multi_proof() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let keys: Vec<_> = (0..10)
+        .map(|i| StateKey::raw(format!("test_key{}", i).into_bytes()))
+        .collect();
+    let values: Vec<_> = (0..10)
+        .map(|i| StateValue::from(format!("test_val{}", i).into_bytes()))
+        .collect();
+
+    // Only the keys with even indices exist.
+    let root = put_value_set(
+        store,
+        keys.iter()
+            .cloned()
+            .zip(values.iter().cloned())
+            .step_by(2)
+            .collect(),
+        0, /* version */
+        None,
+    );
+
+    let (proven_values, proof) = store
+        .get_state_values_with_multi_proof_by_version(&keys, 0)
+        .unwrap();
+    let elements: Vec<_> = keys
+        .iter()
+        .zip(&proven_values)
+        .map(|(key, value)| (key.hash(), value.as_ref()))
+        .collect();
+    for (i, value) in proven_values.iter().enumerate() {
+        assert_eq!(value.as_ref(), (i % 2 == 0).then_some(&values[i]));
+    }
+    proof.verify(root, &elements).unwrap();
+
+    // A key can't be requested twice, whether it exists or not.
+    for key in [&keys[0], &keys[1]] {
+        let duplicate_keys = vec![key.clone(), keys[2].clone(), key.clone()];
+        assert!(store
+            .get_state_values_with_multi_proof_by_version(&duplicate_keys, 0)
+            .is_err());
+    }
+}
+
 fn traverse_values(
     store: &StateStore,
     prefix: &StateKeyPrefix,
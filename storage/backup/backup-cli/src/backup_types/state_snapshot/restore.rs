@@ -18,7 +18,10 @@ use crate::{
     },
     storage::{BackupStorage, FileHandle},
     utils::{
-        read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt, stream::StreamX,
+        read_record_bytes::ReadRecordBytes,
+        restore_progress::{RestoreProgressTracker, StateSnapshotRestoreProgress},
+        storage_ext::BackupStorageExt,
+        stream::StreamX,
         GlobalRestoreOptions, RestoreRunMode,
     },
 };
@@ -71,6 +74,7 @@ pub struct StateSnapshotRestoreController {
     concurrent_downloads: usize,
     validate_modules: bool,
     restore_mode: StateSnapshotRestoreMode,
+    progress: Arc<RestoreProgressTracker>,
 }
 
 impl StateSnapshotRestoreController {
@@ -90,6 +94,7 @@ impl StateSnapshotRestoreController {
             concurrent_downloads: global_opt.concurrent_downloads,
             validate_modules: opt.validate_modules,
             restore_mode: opt.restore_mode,
+            progress: global_opt.progress,
         }
     }
 
@@ -119,6 +124,19 @@ impl StateSnapshotRestoreController {
             );
             return Ok(());
         }
+        if let Some(checkpoint) = self.progress.get().state_snapshot {
+            if checkpoint.finished
+                && checkpoint.version == self.version
+                && checkpoint.manifest == self.manifest_handle
+                && checkpoint.restore_mode == self.restore_mode
+            {
+                info!(
+                    version = self.version,
+                    "State snapshot already restored according to the restore progress, skipping."
+                );
+                return Ok(());
+            }
+        }
 
         let manifest: StateSnapshotBackup =
             self.storage.load_json_file(&self.manifest_handle).await?;
@@ -161,6 +179,7 @@ impl StateSnapshotRestoreController {
         ver_gauge.set(self.version as i64);
         tgt_leaf_idx.set(manifest.chunks.last().map_or(0, |c| c.last_idx as i64));
         let total_chunks = manifest.chunks.len();
+        let total_leaves = manifest.chunks.last().map_or(0, |c| c.last_idx + 1);
 
         let resume_point_opt = receiver.lock().as_mut().unwrap().previous_key_hash()?;
         let chunks = if let Some(resume_point) = resume_point_opt {
@@ -182,6 +201,21 @@ impl StateSnapshotRestoreController {
         let chunks_to_add = chunks.len();
 
         let start_idx = chunks.first().map_or(0, |chunk| chunk.first_idx);
+        self.progress
+            .update(|progress| {
+                progress.state_snapshot = Some(StateSnapshotRestoreProgress {
+                    version: self.version,
+                    manifest: self.manifest_handle.clone(),
+                    restore_mode: self.restore_mode,
+                    total_chunks,
+                    total_leaves,
+                    chunks_done: total_chunks - chunks_to_add,
+                    leaves_done: chunks.first().map_or(total_leaves, |chunk| chunk.first_idx),
+                    last_key: resume_point_opt,
+                    finished: false,
+                })
+            })
+            .await?;
 
         let storage = self.storage.clone();
         let futs_iter = chunks.into_iter().enumerate().map(|(chunk_idx, chunk)| {
@@ -211,11 +245,21 @@ impl StateSnapshotRestoreController {
                 })
                 .await?;
             }
-            tokio::task::spawn_blocking(move || {
-                receiver.lock().as_mut().unwrap().add_chunk(blobs, proof)
+            let last_key = tokio::task::spawn_blocking(move || {
+                let mut receiver = receiver.lock();
+                let receiver = receiver.as_mut().unwrap();
+                receiver.add_chunk(blobs, proof)?;
+                receiver.previous_key_hash()
             })
             .await??;
             leaf_idx.set(chunk.last_idx as i64);
+            self.progress
+                .update_state_snapshot(|snapshot| {
+                    snapshot.chunks_done += 1;
+                    snapshot.leaves_done = chunk.last_idx + 1;
+                    snapshot.last_key = last_key.or(Some(chunk.last_key));
+                })
+                .await?;
             info!(
                 chunk = chunk_idx,
                 chunks_to_add = chunks_to_add,
@@ -229,6 +273,9 @@ impl StateSnapshotRestoreController {
 
         tokio::task::spawn_blocking(move || receiver.lock().take().unwrap().finish()).await??;
         self.run_mode.finish();
+        self.progress
+            .update_state_snapshot(|snapshot| snapshot.finished = true)
+            .await?;
         Ok(())
     }
 
@@ -19,6 +19,7 @@ use crate::{
     utils::{
         error_notes::ErrorNotes,
         read_record_bytes::ReadRecordBytes,
+        restore_progress::TransactionRestoreProgress,
         storage_ext::BackupStorageExt,
         stream::{StreamX, TryStreamX},
         GlobalRestoreOptions, RestoreRunMode,
@@ -291,6 +292,22 @@ impl TransactionRestoreBatchController {
             AptosVM::set_concurrency_level_once(self.global_opt.replay_concurrency_level);
 
             let kv_only = self.replay_from_version.map_or(false, |(_, k)| k);
+            let next_version = max(
+                first_version,
+                restore_handler.get_next_expected_transaction_version()?,
+            );
+            self.global_opt
+                .progress
+                .update(|progress| {
+                    progress.transaction = Some(TransactionRestoreProgress {
+                        first_version,
+                        target_version: self.global_opt.target_version,
+                        next_version,
+                        replay_from_version: self.replay_from_version.map(|(version, _)| version),
+                        kv_only_replay: kv_only,
+                    })
+                })
+                .await?;
             let txns_to_execute_stream = self
                 .save_before_replay_version(first_version, loaded_chunk_stream, restore_handler)
                 .await?;
@@ -412,6 +429,7 @@ impl TransactionRestoreBatchController {
         let start = Instant::now();
 
         let restore_handler_clone = restore_handler.clone();
+        let progress = self.global_opt.progress.clone();
         // DB doesn't allow replaying anything before what's in DB already.
         // self.replay_from_version is from cli argument. However, in fact, we either not replay or replay
         // after current DB's version.
@@ -425,6 +443,7 @@ impl TransactionRestoreBatchController {
         let mut txns_to_execute_stream = loaded_chunk_stream
             .and_then(move |chunk| {
                 let restore_handler = restore_handler_clone.clone();
+                let progress = progress.clone();
                 future::ok(async move {
                     let mut first_version = chunk.manifest.first_version;
                     let mut last_version = chunk.manifest.last_version;
@@ -471,6 +490,9 @@ impl TransactionRestoreBatchController {
                         .await??;
                         let last_saved = first_version + num_to_save as u64 - 1;
                         TRANSACTION_SAVE_VERSION.set(last_saved as i64);
+                        progress
+                            .update_transaction(|txn| txn.next_version = last_saved + 1)
+                            .await?;
                         info!(
                             version = last_saved,
                             accumulative_tps = ((last_saved - global_first_version + 1) as f64
@@ -552,23 +574,30 @@ impl TransactionRestoreBatchController {
             .and_then(future::ready);
 
         let total_replayed = db_commit_stream
-            .and_then(|version| async move {
-                let _timer = OTHER_TIMERS_SECONDS
-                    .with_label_values(&["commit_txn_chunk_kv_only"])
-                    .start_timer();
-                tokio::task::spawn_blocking(move || {
-                    // version is the latest version finishing the KV replaying
-                    let total_replayed = version - first_version;
-                    TRANSACTION_REPLAY_VERSION.set(version as i64);
-                    info!(
-                        version = version,
-                        accumulative_tps =
-                            (total_replayed as f64 / replay_start.elapsed().as_secs_f64()) as u64,
-                        "KV replayed."
-                    );
+            .and_then(|version| {
+                let progress = self.global_opt.progress.clone();
+                async move {
+                    let _timer = OTHER_TIMERS_SECONDS
+                        .with_label_values(&["commit_txn_chunk_kv_only"])
+                        .start_timer();
+                    tokio::task::spawn_blocking(move || {
+                        // version is the latest version finishing the KV replaying
+                        let total_replayed = version - first_version;
+                        TRANSACTION_REPLAY_VERSION.set(version as i64);
+                        info!(
+                            version = version,
+                            accumulative_tps = (total_replayed as f64
+                                / replay_start.elapsed().as_secs_f64())
+                                as u64,
+                            "KV replayed."
+                        );
+                    })
+                    .await?;
+                    progress
+                        .update_transaction(|txn| txn.next_version = version + 1)
+                        .await?;
                     Ok(version)
-                })
-                .await?
+                }
             })
             .try_fold(0, |_total, total| future::ok(total))
             .await?;
@@ -626,11 +655,12 @@ impl TransactionRestoreBatchController {
         let total_replayed = db_commit_stream
             .and_then(|()| {
                 let chunk_replayer = chunk_replayer.clone();
+                let progress = self.global_opt.progress.clone();
                 async move {
                     let _timer = OTHER_TIMERS_SECONDS
                         .with_label_values(&["commit_txn_chunk"])
                         .start_timer();
-                    tokio::task::spawn_blocking(move || {
+                    let v = tokio::task::spawn_blocking(move || {
                         let committed_chunk = chunk_replayer.commit()?;
                         let v = committed_chunk.result_view.version().unwrap_or(0);
                         let total_replayed = v - first_version + 1;
@@ -642,9 +672,13 @@ impl TransactionRestoreBatchController {
                                 as u64,
                             "Transactions replayed."
                         );
-                        Ok(v)
+                        Result::<_>::Ok(v)
                     })
-                    .await?
+                    .await??;
+                    progress
+                        .update_transaction(|txn| txn.next_version = v + 1)
+                        .await?;
+                    Ok(v)
                 }
             })
             .try_fold(0, |_total, total| future::ok(total))
@@ -0,0 +1,229 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    backup_types::{
+        epoch_ending::restore::{
+            EpochEndingRestoreController, EpochEndingRestoreOpt, EpochHistory,
+            EpochHistoryRestoreController,
+        },
+        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        transaction::restore::TransactionRestoreBatchController,
+    },
+    metadata,
+    metadata::{cache::MetadataCacheOpt, EpochEndingBackupMeta},
+    storage::{BackupStorage, FileHandle},
+    utils::{
+        restore_progress::RestoreProgressTracker, GlobalRestoreOptions, RestoreRunMode,
+        TrustedWaypointOpt,
+    },
+};
+use anyhow::Result;
+use aptos_db::state_restore::StateSnapshotRestoreMode;
+use aptos_executor_types::VerifyExecutionMode;
+use aptos_logger::prelude::*;
+use aptos_types::transaction::Version;
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    sync::Arc,
+};
+
+#[derive(Debug)]
+pub struct AuditFailure {
+    /// Manifest of the backup that failed to verify.
+    pub manifest: FileHandle,
+    pub error: String,
+}
+
+#[derive(Debug, Default)]
+pub struct AuditReport {
+    pub transaction_gaps: Vec<RangeInclusive<Version>>,
+    pub epoch_ending_gaps: Vec<RangeInclusive<u64>>,
+    pub num_verified: usize,
+    pub failures: Vec<AuditFailure>,
+}
+
+impl AuditReport {
+    pub fn is_healthy(&self) -> bool {
+        self.transaction_gaps.is_empty()
+            && self.epoch_ending_gaps.is_empty()
+            && self.failures.is_empty()
+    }
+
+    fn record<T>(&mut self, manifest: &FileHandle, res: Result<T>) {
+        match res {
+            Ok(_) => self.num_verified += 1,
+            Err(e) => {
+                error!(manifest = manifest, error = ?e, "Backup failed to verify.");
+                self.failures.push(AuditFailure {
+                    manifest: manifest.clone(),
+                    error: format!("{:#}", e),
+                });
+            },
+        }
+    }
+}
+
+impl Display for AuditReport {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} backups verified, {} failed.",
+            self.num_verified,
+            self.failures.len()
+        )?;
+        for gap in &self.epoch_ending_gaps {
+            writeln!(f, "Epochs not covered: {}..={}", gap.start(), gap.end())?;
+        }
+        for gap in &self.transaction_gaps {
+            writeln!(f, "Versions not covered: {}..={}", gap.start(), gap.end())?;
+        }
+        for failure in &self.failures {
+            writeln!(f, "Failed: {}: {}", failure.manifest, failure.error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Verifies that every backup in the storage is still restorable, by running the restore of each
+/// of them in verify mode, which downloads every chunk and checks it against the proofs, and
+/// reports the gaps in the epoch ending and transaction coverage.
+///
+/// Unlike the `VerifyCoordinator`, a backup failing to verify doesn't stop the audit, so that all
+/// the broken backups are reported at once.
+pub struct BackupAuditor {
+    storage: Arc<dyn BackupStorage>,
+    metadata_cache_opt: MetadataCacheOpt,
+    trusted_waypoints_opt: TrustedWaypointOpt,
+    concurrent_downloads: usize,
+}
+
+impl BackupAuditor {
+    pub fn new(
+        storage: Arc<dyn BackupStorage>,
+        metadata_cache_opt: MetadataCacheOpt,
+        trusted_waypoints_opt: TrustedWaypointOpt,
+        concurrent_downloads: usize,
+    ) -> Self {
+        Self {
+            storage,
+            metadata_cache_opt,
+            trusted_waypoints_opt,
+            concurrent_downloads,
+        }
+    }
+
+    pub async fn run(self) -> Result<AuditReport> {
+        info!("Backup audit started.");
+        let metadata_view = metadata::cache::sync_and_load(
+            &self.metadata_cache_opt,
+            Arc::clone(&self.storage),
+            self.concurrent_downloads,
+        )
+        .await?;
+
+        let global_opt = GlobalRestoreOptions {
+            target_version: Version::max_value(),
+            trusted_waypoints: Arc::new(self.trusted_waypoints_opt.verify()?),
+            run_mode: Arc::new(RestoreRunMode::Verify),
+            concurrent_downloads: self.concurrent_downloads,
+            replay_concurrency_level: 0, // won't replay, doesn't matter
+            progress: Arc::new(RestoreProgressTracker::new_in_memory()),
+        };
+        let mut report = AuditReport {
+            transaction_gaps: metadata_view.transaction_coverage_gaps(),
+            epoch_ending_gaps: metadata_view.epoch_ending_coverage_gaps(),
+            ..Default::default()
+        };
+
+        let epoch_history = self
+            .audit_epoch_endings(
+                metadata_view.all_epoch_ending_backups(),
+                &global_opt,
+                &mut report,
+            )
+            .await;
+
+        for backup in metadata_view.all_state_snapshots() {
+            let res = StateSnapshotRestoreController::new(
+                StateSnapshotRestoreOpt {
+                    manifest_handle: backup.manifest.clone(),
+                    version: backup.version,
+                    validate_modules: false,
+                    restore_mode: StateSnapshotRestoreMode::Default,
+                },
+                global_opt.clone(),
+                Arc::clone(&self.storage),
+                epoch_history.clone(),
+            )
+            .run()
+            .await;
+            report.record(&backup.manifest, res);
+        }
+
+        for backup in metadata_view.all_transaction_backups() {
+            let res = TransactionRestoreBatchController::new(
+                global_opt.clone(),
+                Arc::clone(&self.storage),
+                vec![backup.manifest.clone()],
+                None,
+                None, /* replay_from_version */
+                epoch_history.clone(),
+                VerifyExecutionMode::NoVerify,
+                None,
+            )
+            .run()
+            .await;
+            report.record(&backup.manifest, res);
+        }
+
+        info!(
+            num_verified = report.num_verified,
+            num_failed = report.failures.len(),
+            num_transaction_gaps = report.transaction_gaps.len(),
+            num_epoch_ending_gaps = report.epoch_ending_gaps.len(),
+            "Backup audit finished."
+        );
+        Ok(report)
+    }
+
+    /// Verifies the epoch ending backups, returning the epoch history if it's complete, in which
+    /// case the signatures of the ledger infos of the other backups are verified against it.
+    async fn audit_epoch_endings(
+        &self,
+        backups: &[EpochEndingBackupMeta],
+        global_opt: &GlobalRestoreOptions,
+        report: &mut AuditReport,
+    ) -> Option<Arc<EpochHistory>> {
+        if report.epoch_ending_gaps.is_empty() {
+            let res = EpochHistoryRestoreController::new(
+                backups.iter().map(|b| b.manifest.clone()).collect(),
+                global_opt.clone(),
+                Arc::clone(&self.storage),
+            )
+            .run()
+            .await;
+            if let Ok(epoch_history) = res {
+                report.num_verified += backups.len();
+                return Some(Arc::new(epoch_history));
+            }
+            warn!("Epoch history failed to verify, verifying epoch ending backups one by one.");
+        }
+
+        // Each backup can still be verified on its own, and against the trusted waypoints.
+        for backup in backups {
+            let res = EpochEndingRestoreController::new(
+                EpochEndingRestoreOpt {
+                    manifest_handle: backup.manifest.clone(),
+                },
+                global_opt.clone(),
+                Arc::clone(&self.storage),
+            )
+            .run(None)
+            .await;
+            report.record(&backup.manifest, res);
+        }
+        None
+    }
+}
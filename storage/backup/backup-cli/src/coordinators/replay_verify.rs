@@ -10,7 +10,10 @@ use crate::{
     metadata,
     metadata::cache::MetadataCacheOpt,
     storage::BackupStorage,
-    utils::{GlobalRestoreOptions, RestoreRunMode, TrustedWaypointOpt},
+    utils::{
+        restore_progress::RestoreProgressTracker, GlobalRestoreOptions, RestoreRunMode,
+        TrustedWaypointOpt,
+    },
 };
 use anyhow::{bail, ensure, Result};
 use aptos_db::backup::restore_handler::RestoreHandler;
@@ -134,6 +137,7 @@ impl ReplayVerifyCoordinator {
             run_mode,
             concurrent_downloads: self.concurrent_downloads,
             replay_concurrency_level: 0, // won't replay, doesn't matter
+            progress: Arc::new(RestoreProgressTracker::new_in_memory()),
         };
 
         if let Some(backup) = state_snapshot {
@@ -103,7 +103,8 @@ impl RestoreCoordinator {
     /// a. restore the tree snapshot
     /// b. replay the txn till the target version
     ///
-    /// we are support the resume from any point when the restore is interrupted.
+    /// we are support the resume from any point when the restore is interrupted. Progress is
+    /// checkpointed into the target DB dir along the way, see `db-tool restore status`.
     async fn run_impl(self) -> Result<()> {
         if self.replay_all {
             bail!("--replay--all not supported in this version.");
@@ -193,6 +194,25 @@ impl RestoreCoordinator {
                 .expect("Cannot find tree snapshot before target version")
         };
 
+        let previous_progress = self.global_opt.progress.get();
+        if previous_progress.target_version.is_some() {
+            info!(
+                "Resuming previous restore. Progress:\n{}",
+                previous_progress
+            );
+        }
+        self.global_opt
+            .progress
+            .update(|progress| {
+                progress.target_version = Some(target_version);
+                progress.kv_snapshot_version = kv_snapshot
+                    .as_ref()
+                    .map(|snapshot| snapshot.version)
+                    .or(progress.kv_snapshot_version);
+                progress.tree_snapshot_version = Some(tree_snapshot.version);
+            })
+            .await?;
+
         let do_phase_1 = if let Some(kv_snapshot) = kv_snapshot.as_ref() {
             // if we have a kv snapshot, we need to restore the state between lhs and rs
             // if the version are equal, we don't need to restore phase 1. we can directly restore a snapshot with both tree and KV, and then replay txn till the target_version
@@ -346,8 +366,10 @@ impl RestoreCoordinator {
                 .filter(|e| e.last_version >= db_next_version)
                 .map(|e| e.manifest.clone())
                 .collect();
+            let mut transaction_restore_opt = self.global_opt;
+            transaction_restore_opt.target_version = target_version;
             TransactionRestoreBatchController::new(
-                self.global_opt,
+                transaction_restore_opt,
                 self.storage,
                 txn_manifests,
                 first_version,
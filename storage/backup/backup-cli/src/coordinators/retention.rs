@@ -0,0 +1,355 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    backup_types::state_snapshot::manifest::StateSnapshotBackup,
+    metadata,
+    metadata::{
+        cache::{LoadMetadataLines, MetadataCacheOpt},
+        Metadata, StateSnapshotBackupMeta,
+    },
+    storage::{BackupStorage, FileHandle, ShellSafeName},
+    utils::{error_notes::ErrorNotes, storage_ext::BackupStorageExt, stream::StreamX},
+};
+use anyhow::Result;
+use aptos_infallible::duration_since_epoch;
+use aptos_logger::prelude::*;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, proof::TransactionInfoWithProof};
+use futures::{stream, StreamExt, TryStreamExt};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+const SECS_PER_DAY: u64 = 24 * 3600;
+
+/// Decides which state snapshots are kept by the `BackupRetention`, by the time of the ledger
+/// info proving each snapshot.
+///
+/// Epoch ending and transaction backups are never deleted: the former are needed to verify
+/// everything else, and since nodes prune their ledger history, the latter are the only full
+/// record of it. For the same reason, the oldest state snapshot is always kept, so that any range
+/// of the ledger history stays restorable by replaying transactions on top of it, and so is the
+/// latest one, no matter how old.
+#[derive(Clone, Copy, Debug)]
+pub struct RetentionPolicy {
+    /// Keep the latest state snapshot of each of this many days, today included.
+    pub keep_daily_state_snapshots_for_days: u64,
+    /// Keep the latest state snapshot of each of this many weeks, starting on Mondays, this week
+    /// included.
+    pub keep_weekly_state_snapshots_for_weeks: u64,
+}
+
+impl RetentionPolicy {
+    /// Returns the state snapshots to be deleted, given the timestamp in seconds of each snapshot.
+    pub fn select_obsolete_state_snapshots(
+        &self,
+        snapshots: &[(StateSnapshotBackupMeta, u64)],
+        now_secs: u64,
+    ) -> Vec<StateSnapshotBackupMeta> {
+        let today = now_secs / SECS_PER_DAY;
+        let this_week = week(today);
+
+        // The latest snapshot of each day and each week in range.
+        let mut daily = BTreeMap::new();
+        let mut weekly = BTreeMap::new();
+        for (snapshot, timestamp_secs) in snapshots {
+            let day = timestamp_secs / SECS_PER_DAY;
+            if day <= today && today - day < self.keep_daily_state_snapshots_for_days {
+                Self::keep_latest(&mut daily, day, snapshot);
+            }
+            if week(day) <= this_week
+                && this_week - week(day) < self.keep_weekly_state_snapshots_for_weeks
+            {
+                Self::keep_latest(&mut weekly, week(day), snapshot);
+            }
+        }
+
+        let oldest = snapshots.iter().map(|(s, _)| s.version).min();
+        let latest = snapshots.iter().map(|(s, _)| s.version).max();
+        let to_keep: HashSet<_> = daily
+            .into_values()
+            .chain(weekly.into_values())
+            .map(|s| &s.manifest)
+            .collect();
+
+        snapshots
+            .iter()
+            .filter(|(snapshot, timestamp_secs)| {
+                // Snapshots that seem to be from the future are kept, in case the clock is off.
+                timestamp_secs / SECS_PER_DAY <= today
+                    && Some(snapshot.version) != oldest
+                    && Some(snapshot.version) != latest
+                    && !to_keep.contains(&snapshot.manifest)
+            })
+            .map(|(snapshot, _)| snapshot.clone())
+            .collect()
+    }
+
+    fn keep_latest<'a>(
+        kept: &mut BTreeMap<u64, &'a StateSnapshotBackupMeta>,
+        period: u64,
+        snapshot: &'a StateSnapshotBackupMeta,
+    ) {
+        let latest = kept.entry(period).or_insert(snapshot);
+        if snapshot.version > latest.version {
+            *latest = snapshot;
+        }
+    }
+}
+
+/// 1970-01-01 was a Thursday, shift by 3 days so that weeks start on Mondays.
+fn week(day: u64) -> u64 {
+    (day + 3) / 7
+}
+
+/// Deletes the backups that are obsolete according to a `RetentionPolicy`.
+///
+/// The metadata of the obsolete backups is removed first, by rewriting the metadata files
+/// mentioning them without them and moving the original ones to the metadata backup folder, so
+/// an interrupted run leaves at worst some unreferenced backup files behind.
+pub struct BackupRetention {
+    storage: Arc<dyn BackupStorage>,
+    metadata_cache_opt: MetadataCacheOpt,
+    concurrent_downloads: usize,
+    policy: RetentionPolicy,
+    dry_run: bool,
+}
+
+impl BackupRetention {
+    pub fn new(
+        storage: Arc<dyn BackupStorage>,
+        metadata_cache_opt: MetadataCacheOpt,
+        concurrent_downloads: usize,
+        policy: RetentionPolicy,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            storage,
+            metadata_cache_opt,
+            concurrent_downloads,
+            policy,
+            dry_run,
+        }
+    }
+
+    /// Returns the state snapshots deleted, or to be deleted in a dry run.
+    pub async fn run(self) -> Result<Vec<StateSnapshotBackupMeta>> {
+        info!(policy = ?self.policy, dry_run = self.dry_run, "Backup retention started.");
+        let metadata_view = metadata::cache::sync_and_load(
+            &self.metadata_cache_opt,
+            Arc::clone(&self.storage),
+            self.concurrent_downloads,
+        )
+        .await?;
+
+        let storage = self.storage.clone();
+        let snapshots = stream::iter(metadata_view.all_state_snapshots().to_vec())
+            .map(|snapshot| {
+                let storage = storage.clone();
+                async move {
+                    let timestamp_secs = Self::load_timestamp_secs(&storage, &snapshot)
+                        .await
+                        .err_notes(&snapshot.manifest)?;
+                    Result::<_>::Ok((snapshot, timestamp_secs))
+                }
+            })
+            .buffered_x(self.concurrent_downloads * 2, self.concurrent_downloads)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let obsolete = self
+            .policy
+            .select_obsolete_state_snapshots(&snapshots, duration_since_epoch().as_secs());
+        for snapshot in &obsolete {
+            info!(
+                epoch = snapshot.epoch,
+                version = snapshot.version,
+                manifest = snapshot.manifest,
+                "State snapshot is obsolete."
+            );
+        }
+        if self.dry_run || obsolete.is_empty() {
+            info!(
+                num_obsolete = obsolete.len(),
+                num_state_snapshots = snapshots.len(),
+                "Backup retention finished without deleting anything."
+            );
+            return Ok(obsolete);
+        }
+
+        self.remove_metadata(&obsolete).await?;
+        for snapshot in &obsolete {
+            self.delete_state_snapshot(snapshot)
+                .await
+                .err_notes(&snapshot.manifest)?;
+            info!(
+                version = snapshot.version,
+                "Obsolete state snapshot deleted."
+            );
+        }
+        info!(
+            num_deleted = obsolete.len(),
+            num_state_snapshots = snapshots.len(),
+            "Backup retention finished."
+        );
+        Ok(obsolete)
+    }
+
+    async fn load_timestamp_secs(
+        storage: &Arc<dyn BackupStorage>,
+        snapshot: &StateSnapshotBackupMeta,
+    ) -> Result<u64> {
+        let manifest: StateSnapshotBackup = storage.load_json_file(&snapshot.manifest).await?;
+        let (_txn_info_with_proof, li): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
+            storage.load_bcs_file(&manifest.proof).await?;
+        Ok(li.ledger_info().timestamp_usecs() / 1_000_000)
+    }
+
+    async fn remove_metadata(&self, obsolete: &[StateSnapshotBackupMeta]) -> Result<()> {
+        let obsolete: HashSet<_> = obsolete.iter().map(|s| &s.manifest).collect();
+        let now = duration_since_epoch().as_secs();
+
+        for (i, file_handle) in self
+            .storage
+            .list_metadata_files()
+            .await?
+            .into_iter()
+            .enumerate()
+        {
+            let entries = match metadata::cache::load_cached_metadata_file(
+                &self.metadata_cache_opt,
+                &file_handle,
+            )
+            .await
+            {
+                Ok(entries) => entries,
+                // Added after the cache was synced.
+                Err(_) => self
+                    .storage
+                    .open_for_read(&file_handle)
+                    .await?
+                    .load_metadata_lines()
+                    .await
+                    .err_notes(&file_handle)?,
+            };
+            let (removed, retained): (Vec<_>, Vec<_>) =
+                entries.into_iter().partition(|entry| match entry {
+                    Metadata::StateSnapshotBackup(s) => obsolete.contains(&s.manifest),
+                    _ => false,
+                });
+            if removed.is_empty() {
+                continue;
+            }
+
+            if !retained.is_empty() {
+                let lines = retained
+                    .iter()
+                    .map(Metadata::to_text_line)
+                    .collect::<Result<Vec<_>>>()?;
+                let name: ShellSafeName = format!("retained_{}_{}.meta", now, i).parse()?;
+                self.storage.save_metadata_lines(&name, &lines).await?;
+            }
+            self.storage
+                .backup_metadata_file(&file_handle)
+                .await
+                .err_notes(&file_handle)?;
+            info!(
+                file_handle = file_handle,
+                num_removed = removed.len(),
+                num_retained = retained.len(),
+                "Obsolete entries removed from metadata file."
+            );
+        }
+        Ok(())
+    }
+
+    async fn delete_state_snapshot(&self, snapshot: &StateSnapshotBackupMeta) -> Result<()> {
+        let manifest: StateSnapshotBackup = self.storage.load_json_file(&snapshot.manifest).await?;
+        let files: Vec<FileHandle> = manifest
+            .chunks
+            .into_iter()
+            .flat_map(|chunk| [chunk.blobs, chunk.proof])
+            .chain([manifest.proof, snapshot.manifest.clone()])
+            .collect();
+
+        let storage = self.storage.clone();
+        stream::iter(files)
+            .map(|file_handle| {
+                let storage = storage.clone();
+                async move {
+                    storage
+                        .delete_file(&file_handle)
+                        .await
+                        .err_notes(&file_handle)
+                }
+            })
+            .buffered_x(self.concurrent_downloads * 2, self.concurrent_downloads)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(version: u64) -> StateSnapshotBackupMeta {
+        StateSnapshotBackupMeta {
+            epoch: version / 10,
+            version,
+            manifest: format!("state_ver_{}/state.manifest", version),
+        }
+    }
+
+    fn versions(snapshots: Vec<StateSnapshotBackupMeta>) -> Vec<u64> {
+        snapshots.into_iter().map(|s| s.version).collect()
+    }
+
+    #[test]
+    fn test_select_obsolete_state_snapshots() {
+        // Two snapshots a day, at 6am and 6pm, over the 8 weeks to Thursday 2023-08-31 at noon.
+        let now = 19_600 * SECS_PER_DAY + SECS_PER_DAY / 2;
+        let snapshots: Vec<_> = (0..112)
+            .map(|i| {
+                let timestamp = now - 56 * SECS_PER_DAY + i * SECS_PER_DAY / 2 + 6 * 3600;
+                (snapshot(i), timestamp)
+            })
+            .collect();
+
+        let policy = RetentionPolicy {
+            keep_daily_state_snapshots_for_days: 3,
+            keep_weekly_state_snapshots_for_weeks: 2,
+        };
+        let obsolete = versions(policy.select_obsolete_state_snapshots(&snapshots, now));
+        let kept: Vec<_> = (0..112).filter(|v| !obsolete.contains(v)).collect();
+        // The oldest, the last one of the previous week on Sunday, the 6pm ones of the two days
+        // before today, and the latest, today at 6am.
+        assert_eq!(kept, vec![0, 104, 108, 110, 111]);
+
+        // Nothing but the oldest and the latest is kept without policies.
+        let policy = RetentionPolicy {
+            keep_daily_state_snapshots_for_days: 0,
+            keep_weekly_state_snapshots_for_weeks: 0,
+        };
+        assert_eq!(
+            policy
+                .select_obsolete_state_snapshots(&snapshots, now)
+                .len(),
+            110
+        );
+
+        // Snapshots from the future are kept.
+        let snapshots = vec![
+            (snapshot(1), now - 10 * SECS_PER_DAY),
+            (snapshot(2), now - 9 * SECS_PER_DAY),
+            (snapshot(3), now + 2 * SECS_PER_DAY),
+            (snapshot(4), now + 3 * SECS_PER_DAY),
+        ];
+        assert_eq!(
+            versions(policy.select_obsolete_state_snapshots(&snapshots, now)),
+            vec![2]
+        );
+    }
+}
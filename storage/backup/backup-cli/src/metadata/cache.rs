@@ -216,6 +216,23 @@ pub async fn sync_and_load(
     Ok(MetadataView::new(metadata_vec, remote_file_handles))
 }
 
+/// Load the metadata entries in a single metadata file from the local cache, which must have been
+/// synced by `sync_and_load()`.
+pub(crate) async fn load_cached_metadata_file(
+    opt: &MetadataCacheOpt,
+    file_handle: &FileHandle,
+) -> Result<Vec<Metadata>> {
+    let cached_file = opt.cache_dir().join(file_handle.file_handle_hash());
+    OpenOptions::new()
+        .read(true)
+        .open(&cached_file)
+        .await
+        .err_notes(&cached_file)?
+        .load_metadata_lines()
+        .await
+        .err_notes(&cached_file)
+}
+
 trait FileHandleHash {
     fn file_handle_hash(&self) -> String;
 }
@@ -231,7 +248,7 @@ impl FileHandleHash for FileHandle {
 }
 
 #[async_trait]
-trait LoadMetadataLines {
+pub(crate) trait LoadMetadataLines {
     async fn load_metadata_lines(&mut self) -> Result<Vec<Metadata>>;
 }
 
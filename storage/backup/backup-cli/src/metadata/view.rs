@@ -13,7 +13,7 @@ use anyhow::{anyhow, ensure, Result};
 use aptos_infallible::duration_since_epoch;
 use aptos_types::transaction::Version;
 use itertools::Itertools;
-use std::{fmt, str::FromStr};
+use std::{fmt, ops::RangeInclusive, str::FromStr};
 
 #[derive(Debug)]
 pub struct MetadataView {
@@ -229,6 +229,51 @@ impl MetadataView {
         Self::compact_backups(&self.state_snapshot_backups, compaction_cnt)
     }
 
+    pub fn all_epoch_ending_backups(&self) -> &[EpochEndingBackupMeta] {
+        &self.epoch_ending_backups
+    }
+
+    pub fn all_state_snapshots(&self) -> &[StateSnapshotBackupMeta] {
+        &self.state_snapshot_backups
+    }
+
+    pub fn all_transaction_backups(&self) -> &[TransactionBackupMeta] {
+        &self.transaction_backups
+    }
+
+    /// Version ranges between genesis and the latest transaction backed up which are not covered
+    /// by any transaction backup.
+    pub fn transaction_coverage_gaps(&self) -> Vec<RangeInclusive<Version>> {
+        Self::coverage_gaps(
+            self.transaction_backups
+                .iter()
+                .map(|b| (b.first_version, b.last_version)),
+        )
+    }
+
+    /// Epoch ranges between genesis and the latest epoch ending backed up which are not covered by
+    /// any epoch ending backup.
+    pub fn epoch_ending_coverage_gaps(&self) -> Vec<RangeInclusive<u64>> {
+        Self::coverage_gaps(
+            self.epoch_ending_backups
+                .iter()
+                .map(|b| (b.first_epoch, b.last_epoch)),
+        )
+    }
+
+    /// Takes inclusive ranges sorted by their start, which can overlap.
+    fn coverage_gaps(ranges: impl Iterator<Item = (u64, u64)>) -> Vec<RangeInclusive<u64>> {
+        let mut next = 0;
+        let mut gaps = Vec::new();
+        for (first, last) in ranges {
+            if first > next {
+                gaps.push(next..=first - 1);
+            }
+            next = next.max(last + 1);
+        }
+        gaps
+    }
+
     pub fn get_file_handles(&self) -> Vec<FileHandle> {
         self.select_latest_compaction_timestamps()
             .as_ref()
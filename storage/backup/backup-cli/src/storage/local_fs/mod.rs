@@ -22,7 +22,7 @@ use std::{
     str::FromStr,
 };
 use tokio::{
-    fs::{create_dir_all, read_dir, rename, OpenOptions},
+    fs::{create_dir_all, read_dir, remove_dir, remove_file, rename, OpenOptions},
     io::{AsyncRead, AsyncWrite, AsyncWriteExt},
 };
 
@@ -151,6 +151,19 @@ impl BackupStorage for LocalFs {
         Ok(())
     }
 
+    async fn delete_file(&self, file_handle: &FileHandleRef) -> Result<()> {
+        let path = self.dir.join(file_handle);
+        remove_file(&path).await.err_notes(&path)?;
+        // Remove the backup dir as well once it's emptied, keeping it if other files remain.
+        if let Some(dir) = path.parent().filter(|dir| *dir != self.dir) {
+            let mut entries = read_dir(dir).await.err_notes(dir)?;
+            if entries.next_entry().await.err_notes(dir)?.is_none() {
+                remove_dir(dir).await.err_notes(dir)?;
+            }
+        }
+        Ok(())
+    }
+
     async fn save_metadata_lines(
         &self,
         name: &ShellSafeName,
@@ -0,0 +1,104 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::error_notes::ErrorNotes;
+use anyhow::{ensure, format_err, Result};
+use serde::Deserialize;
+use std::path::Path;
+use tokio::io::AsyncReadExt;
+
+/// S3 rejects parts smaller than this in a multipart upload, except for the last one.
+pub const MIN_PART_SIZE: usize = 5 << 20;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3Config {
+    /// Endpoint of the object store, like "https://s3.us-west-2.amazonaws.com", or
+    /// "http://localhost:9000" for a local MinIO.
+    pub endpoint: String,
+    /// Region the requests are signed for.
+    #[serde(default = "default_region")]
+    pub region: String,
+    pub bucket: String,
+    /// Key prefix under which the backups are stored, like "backup1/e1".
+    #[serde(default)]
+    pub prefix: String,
+    /// Address the bucket as a sub domain of the endpoint, instead of as the first segment of
+    /// the path, which is what most S3 compatible stores expect.
+    #[serde(default)]
+    pub virtual_hosted_style: bool,
+    /// Size of the parts a file is uploaded in, which is also what it's downloaded in.
+    #[serde(default = "default_part_size")]
+    pub part_size: usize,
+    /// Number of parts of a file uploaded or downloaded in parallel.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// Number of times a failed request is retried before giving up.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: usize,
+    /// Credentials, defaulting to the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+    /// `AWS_SESSION_TOKEN` env vars, so that they don't need to be written in the config.
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    #[serde(default)]
+    pub session_token: Option<String>,
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_part_size() -> usize {
+    16 << 20
+}
+
+fn default_concurrency() -> usize {
+    8
+}
+
+fn default_max_retries() -> usize {
+    5
+}
+
+impl S3Config {
+    pub async fn load_from_file(path: &Path) -> Result<Self> {
+        let path_str = path.to_str().unwrap_or_default();
+        let mut file = tokio::fs::File::open(path).await.err_notes(path_str)?;
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).await.err_notes(path_str)?;
+
+        Self::load_from_str(std::str::from_utf8(&content)?)
+    }
+
+    pub fn load_from_str(content: &str) -> Result<Self> {
+        let config: Self = serde_yaml::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.part_size >= MIN_PART_SIZE,
+            "part_size must be at least {} bytes.",
+            MIN_PART_SIZE,
+        );
+        ensure!(self.concurrency > 0, "concurrency must be positive.");
+        Ok(())
+    }
+
+    /// Returns the access key id, secret access key and session token.
+    pub fn credentials(&self) -> Result<(String, String, Option<String>)> {
+        let from_env =
+            |value: &Option<String>, var: &str| value.clone().or_else(|| std::env::var(var).ok());
+        Ok((
+            from_env(&self.access_key_id, "AWS_ACCESS_KEY_ID")
+                .ok_or_else(|| format_err!("Missing access_key_id or AWS_ACCESS_KEY_ID."))?,
+            from_env(&self.secret_access_key, "AWS_SECRET_ACCESS_KEY").ok_or_else(|| {
+                format_err!("Missing secret_access_key or AWS_SECRET_ACCESS_KEY.")
+            })?,
+            from_env(&self.session_token, "AWS_SESSION_TOKEN"),
+        ))
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod client;
+pub mod config;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    storage::{
+        s3::{
+            client::{S3Client, UploadedPart},
+            config::S3Config,
+        },
+        BackupHandle, BackupHandleRef, BackupStorage, FileHandle, FileHandleRef, ShellSafeName,
+        TextLine,
+    },
+    utils::{error_notes::ErrorNotes, stream::StreamX},
+};
+use anyhow::{format_err, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use clap::Parser;
+use futures::{
+    future::BoxFuture,
+    stream::{self, FuturesUnordered},
+    task::{Context, Poll},
+    Future, FutureExt, StreamExt, TryStreamExt,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, DuplexStream};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+
+#[derive(Parser, Debug, Serialize, Deserialize)]
+pub struct S3Opt {
+    #[clap(long = "config", help = "Config file for the S3 backup store.")]
+    config: PathBuf,
+}
+
+impl FromStr for S3Opt {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(S3Opt {
+            config: PathBuf::from(s),
+        })
+    }
+}
+
+/// A BackupStorage that talks to an S3 compatible object store directly, see `S3Config`.
+///
+/// Files are uploaded in parts, several of them in parallel, and read back part by part, in
+/// parallel as well. Failed requests are retried, and the payload of every request is checksummed
+/// by the store against the SHA-256 hash it's signed with. The store also keeps the SHA-256
+/// checksum of every uploaded part, which every part read back is verified against.
+pub struct S3 {
+    client: Arc<S3Client>,
+}
+
+impl S3 {
+    const METADATA_BACKUP_DIR: &'static str = "metadata_backup";
+    const METADATA_DIR: &'static str = "metadata";
+
+    pub fn new(config: S3Config) -> Result<Self> {
+        Ok(Self {
+            client: Arc::new(S3Client::new(config)?),
+        })
+    }
+
+    pub async fn new_with_opt(opt: S3Opt) -> Result<Self> {
+        Self::new(S3Config::load_from_file(&opt.config).await?)
+    }
+
+    /// Uploads everything read from the reader to the key, in one request if it fits in a part.
+    async fn upload(client: Arc<S3Client>, key: String, mut reader: DuplexStream) -> Result<()> {
+        let part_size = client.config().part_size;
+        let first_part = read_part(&mut reader, part_size).await?;
+        if first_part.len() < part_size {
+            return client.put_object(&key, first_part).await;
+        }
+
+        let upload_id = client.create_multipart_upload(&key).await?;
+        match Self::upload_parts(&client, &key, &upload_id, first_part, reader).await {
+            Ok(parts) => {
+                client
+                    .complete_multipart_upload(&key, &upload_id, &parts)
+                    .await
+            },
+            Err(e) => {
+                // Parts of an upload which is neither completed nor aborted are kept, and billed.
+                let _ = client
+                    .abort_multipart_upload(&key, &upload_id)
+                    .await
+                    .err_notes(&key);
+                Err(e)
+            },
+        }
+    }
+
+    /// Uploads the parts of a multipart upload, a number of them in parallel, and returns them in
+    /// order.
+    async fn upload_parts(
+        client: &Arc<S3Client>,
+        key: &str,
+        upload_id: &str,
+        first_part: Bytes,
+        mut reader: DuplexStream,
+    ) -> Result<Vec<UploadedPart>> {
+        let concurrency = client.config().concurrency;
+        let part_size = client.config().part_size;
+        let mut uploaded_parts = BTreeMap::new();
+        let mut uploads = FuturesUnordered::new();
+        let mut part = first_part;
+        let mut part_number = 1;
+        while !part.is_empty() {
+            if uploads.len() >= concurrency {
+                let (number, uploaded_part) =
+                    uploads.next().await.expect("Uploads are in progress.")??;
+                uploaded_parts.insert(number, uploaded_part);
+            }
+            // Spawned, so that the uploads progress while the next part is read.
+            let client = client.clone();
+            let key = key.to_string();
+            let upload_id = upload_id.to_string();
+            uploads.push(tokio::spawn(async move {
+                let uploaded_part = client
+                    .upload_part(&key, &upload_id, part_number, part)
+                    .await?;
+                Ok::<_, anyhow::Error>((part_number, uploaded_part))
+            }));
+            part_number += 1;
+            part = read_part(&mut reader, part_size).await?;
+        }
+        while let Some(result) = uploads.next().await {
+            let (number, uploaded_part) = result??;
+            uploaded_parts.insert(number, uploaded_part);
+        }
+        Ok(uploaded_parts.into_values().collect())
+    }
+
+    fn metadata_file_handle(name: &str) -> FileHandle {
+        format!("{}/{}", Self::METADATA_DIR, name)
+    }
+}
+
+#[async_trait]
+impl BackupStorage for S3 {
+    async fn create_backup(&self, name: &ShellSafeName) -> Result<BackupHandle> {
+        // There are no directories to create in an object store.
+        Ok(name.to_string())
+    }
+
+    async fn create_for_write(
+        &self,
+        backup_handle: &BackupHandleRef,
+        name: &ShellSafeName,
+    ) -> Result<(FileHandle, Box<dyn AsyncWrite + Send + Unpin>)> {
+        let file_handle = format!("{}/{}", backup_handle, name.as_ref());
+        let key = self.client.key(&file_handle);
+        let (writer, reader) = tokio::io::duplex(self.client.config().part_size.min(1 << 20));
+        let upload = tokio::spawn(Self::upload(self.client.clone(), key.clone(), reader));
+        let upload = async move {
+            upload
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result)
+                .err_notes(&key)
+        };
+        Ok((
+            file_handle,
+            Box::new(UploadSink::new(writer, upload.boxed())),
+        ))
+    }
+
+    async fn open_for_read(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+        let key = self.client.key(file_handle);
+        let parts_count = self.client.parts_count(&key).await.err_notes(&key)?;
+        let concurrency = self.client.config().concurrency;
+
+        let client = self.client.clone();
+        let parts = (1..=parts_count).map(move |part_number| {
+            let client = client.clone();
+            let key = key.clone();
+            async move { client.get_part(&key, part_number).await.err_notes(&key) }
+        });
+        let parts = stream::iter(parts)
+            .buffered_x(concurrency * 2, concurrency)
+            .map_err(|e| futures::io::Error::new(futures::io::ErrorKind::Other, e));
+        Ok(Box::new(Box::pin(parts).into_async_read().compat()))
+    }
+
+    async fn list_metadata_files(&self) -> Result<Vec<FileHandle>> {
+        let dir = self.client.key(Self::METADATA_DIR);
+        self.client
+            .list_objects(&format!("{}/", dir))
+            .await
+            .err_notes(&dir)?
+            .iter()
+            .map(|key| {
+                let name = Path::new(key)
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .ok_or_else(|| format_err!("cannot extract filename from {}", key))?;
+                Ok(Self::metadata_file_handle(name))
+            })
+            .collect()
+    }
+
+    async fn backup_metadata_file(&self, file_handle: &FileHandleRef) -> Result<()> {
+        let name = Path::new(file_handle)
+            .file_name()
+            .and_then(OsStr::to_str)
+            .ok_or_else(|| format_err!("cannot extract filename from {}", file_handle))?;
+        let key = self.client.key(file_handle);
+        let backup_key = self
+            .client
+            .key(&format!("{}/{}", Self::METADATA_BACKUP_DIR, name));
+
+        // Objects can't be moved, only copied and then deleted.
+        self.client
+            .copy_object(&key, &backup_key)
+            .await
+            .err_notes(&key)?;
+        self.client.delete_object(&key).await.err_notes(&key)
+    }
+
+    async fn delete_file(&self, file_handle: &FileHandleRef) -> Result<()> {
+        let key = self.client.key(file_handle);
+        self.client.delete_object(&key).await.err_notes(&key)
+    }
+
+    async fn save_metadata_lines(
+        &self,
+        name: &ShellSafeName,
+        lines: &[TextLine],
+    ) -> Result<FileHandle> {
+        let file_handle = Self::metadata_file_handle(name.as_ref());
+        let key = self.client.key(&file_handle);
+        let content = lines
+            .iter()
+            .map(|e| e.as_ref())
+            .collect::<Vec<&str>>()
+            .join("");
+        self.client
+            .put_object(&key, content.into())
+            .await
+            .err_notes(&key)?;
+        Ok(file_handle)
+    }
+}
+
+/// Reads up to a part of the file, which is shorter only at the end of the file.
+async fn read_part(reader: &mut DuplexStream, part_size: usize) -> Result<Bytes> {
+    let mut buf = Vec::with_capacity(part_size);
+    reader.take(part_size as u64).read_to_end(&mut buf).await?;
+    Ok(buf.into())
+}
+
+/// The writer of a file, whose content is piped to the upload. Shutting it down waits for the
+/// upload to finish.
+struct UploadSink {
+    pipe: Option<DuplexStream>,
+    upload: BoxFuture<'static, Result<()>>,
+}
+
+impl UploadSink {
+    fn new(pipe: DuplexStream, upload: BoxFuture<'static, Result<()>>) -> Self {
+        Self {
+            pipe: Some(pipe),
+            upload,
+        }
+    }
+}
+
+impl AsyncWrite for UploadSink {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, tokio::io::Error>> {
+        match self.pipe.as_mut() {
+            Some(pipe) => Pin::new(pipe).poll_write(cx, buf),
+            None => Poll::Ready(Err(tokio::io::ErrorKind::BrokenPipe.into())),
+        }
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), tokio::io::Error>> {
+        match self.pipe.as_mut() {
+            Some(pipe) => Pin::new(pipe).poll_flush(cx),
+            None => Poll::Ready(Err(tokio::io::ErrorKind::BrokenPipe.into())),
+        }
+    }
+
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), tokio::io::Error>> {
+        if let Some(pipe) = self.pipe.as_mut() {
+            let res = Pin::new(pipe).poll_shutdown(cx);
+            if let Poll::Ready(Ok(_)) = res {
+                // The upload sees the end of the file.
+                self.pipe = None;
+            } else {
+                return res;
+            }
+        }
+
+        Pin::new(&mut self.upload)
+            .poll(cx)
+            .map_err(|e| tokio::io::Error::new(tokio::io::ErrorKind::Other, e))
+    }
+}
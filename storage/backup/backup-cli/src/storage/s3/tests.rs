@@ -0,0 +1,466 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::storage::test_util::{
+    arb_backups, arb_metadata_files, test_save_and_list_metadata_files_impl,
+    test_write_and_read_impl,
+};
+use aptos_infallible::Mutex;
+use proptest::prelude::*;
+use ring::digest;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    runtime::Runtime,
+};
+use warp::{
+    http::{HeaderMap, Method, Response, StatusCode},
+    hyper::Body,
+    path::FullPath,
+    Filter,
+};
+
+const BUCKET: &str = "test-bucket";
+/// Small parts, so that most files are uploaded and read in several of them.
+const PART_SIZE: usize = 100;
+/// Keys listed per page, so that listings are paginated.
+const LIST_PAGE_SIZE: usize = 2;
+
+/// A part of an object, along with the checksum it was uploaded with, if any.
+type FakePart = (Bytes, Option<String>);
+
+/// An in memory stand-in for an S3 compatible object store, serving the subset of the API used
+/// by `S3`.
+#[derive(Default)]
+struct FakeS3 {
+    objects: Mutex<BTreeMap<String, Vec<FakePart>>>,
+    uploads: Mutex<HashMap<String, BTreeMap<usize, FakePart>>>,
+    next_upload_id: AtomicUsize,
+    completed_uploads: AtomicUsize,
+    /// Number of the next requests failed with a server error, to exercise the retries.
+    failures: AtomicUsize,
+    /// Number of the next reads whose content is corrupted, to exercise the checksums.
+    corrupted_reads: AtomicUsize,
+}
+
+impl FakeS3 {
+    fn content(&self, key: &str) -> Option<Vec<u8>> {
+        self.objects.lock().get(key).map(|parts| {
+            parts
+                .iter()
+                .flat_map(|(part, _)| part.iter().copied())
+                .collect()
+        })
+    }
+
+    fn handle(
+        &self,
+        method: Method,
+        path: FullPath,
+        query: HashMap<String, String>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> Response<Body> {
+        if self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Self::response(StatusCode::SERVICE_UNAVAILABLE, "");
+        }
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+        if !header("authorization")
+            .unwrap_or_default()
+            .starts_with("AWS4-HMAC-SHA256 Credential=")
+        {
+            return Self::response(
+                StatusCode::FORBIDDEN,
+                "<Error><Code>AccessDenied</Code></Error>",
+            );
+        }
+        if header("x-amz-content-sha256")
+            != Some(hex::encode(digest::digest(&digest::SHA256, &body)))
+        {
+            return Self::response(
+                StatusCode::BAD_REQUEST,
+                "<Error><Code>XAmzContentSHA256Mismatch</Code></Error>",
+            );
+        }
+        let checksum = header("x-amz-checksum-sha256");
+        if checksum
+            .as_ref()
+            .map_or(false, |checksum| *checksum != client::checksum(&body))
+        {
+            return Self::response(
+                StatusCode::BAD_REQUEST,
+                "<Error><Code>BadDigest</Code></Error>",
+            );
+        }
+        let key = match path.as_str().strip_prefix(&format!("/{}/", BUCKET)) {
+            Some(key) => key.to_string(),
+            None => return Self::response(StatusCode::NOT_FOUND, ""),
+        };
+
+        match method {
+            Method::GET if query.get("list-type").map(String::as_str) == Some("2") => {
+                self.list(&query["prefix"], query.get("continuation-token"))
+            },
+            Method::GET | Method::HEAD => {
+                let parts = match self.objects.lock().get(&key) {
+                    Some(parts) => parts.clone(),
+                    None => return Self::response(StatusCode::NOT_FOUND, ""),
+                };
+                let mut response = Response::builder();
+                let (mut content, checksum) = match query.get("partNumber") {
+                    Some(part_number) => {
+                        let part_number: usize = part_number.parse().unwrap();
+                        if parts.len() > 1 {
+                            response = response.header("x-amz-mp-parts-count", parts.len());
+                        }
+                        match parts.get(part_number - 1) {
+                            Some((part, checksum)) => (part.to_vec(), checksum.clone()),
+                            None => {
+                                return Self::response(
+                                    StatusCode::RANGE_NOT_SATISFIABLE,
+                                    "<Error><Code>InvalidPartNumber</Code></Error>",
+                                )
+                            },
+                        }
+                    },
+                    None => (self.content(&key).unwrap(), None),
+                };
+                if let Some(checksum) =
+                    checksum.filter(|_| header("x-amz-checksum-mode").as_deref() == Some("ENABLED"))
+                {
+                    response = response.header("x-amz-checksum-sha256", checksum);
+                }
+                if method == Method::HEAD {
+                    content.clear();
+                } else if self
+                    .corrupted_reads
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok()
+                {
+                    content[0] ^= 1;
+                }
+                response.body(content.into()).unwrap()
+            },
+            Method::PUT if headers.contains_key("x-amz-copy-source") => {
+                let source = header("x-amz-copy-source").unwrap();
+                let source_key = source.strip_prefix(&format!("/{}/", BUCKET)).unwrap();
+                let object = match self.objects.lock().get(source_key) {
+                    Some(object) => object.clone(),
+                    None => return Self::response(StatusCode::NOT_FOUND, ""),
+                };
+                self.objects.lock().insert(key, object);
+                Self::response(StatusCode::OK, "<CopyObjectResult></CopyObjectResult>")
+            },
+            Method::PUT if query.contains_key("uploadId") => {
+                let part_number: usize = query["partNumber"].parse().unwrap();
+                match self.uploads.lock().get_mut(&query["uploadId"]) {
+                    Some(parts) => parts.insert(part_number, (body, checksum)),
+                    None => return Self::response(StatusCode::NOT_FOUND, ""),
+                };
+                Response::builder()
+                    .header("etag", format!("\"{}\"", part_number))
+                    .body(Body::empty())
+                    .unwrap()
+            },
+            Method::PUT => {
+                self.objects.lock().insert(key, vec![(body, checksum)]);
+                Self::response(StatusCode::OK, "")
+            },
+            Method::POST if query.contains_key("uploads") => {
+                let upload_id = self
+                    .next_upload_id
+                    .fetch_add(1, Ordering::SeqCst)
+                    .to_string();
+                self.uploads
+                    .lock()
+                    .insert(upload_id.clone(), BTreeMap::new());
+                Self::response(
+                    StatusCode::OK,
+                    &format!(
+                        "<InitiateMultipartUploadResult><UploadId>{}</UploadId>\
+                         </InitiateMultipartUploadResult>",
+                        upload_id
+                    ),
+                )
+            },
+            Method::POST if query.contains_key("uploadId") => {
+                let parts = match self.uploads.lock().remove(&query["uploadId"]) {
+                    Some(parts) => parts,
+                    None => return Self::response(StatusCode::NOT_FOUND, ""),
+                };
+                let body = std::str::from_utf8(&body).unwrap();
+                let expected_parts: Vec<_> = parts
+                    .iter()
+                    .map(|(number, (_, checksum))| {
+                        format!(
+                            "<ETag>&quot;{}&quot;</ETag><ChecksumSHA256>{}</ChecksumSHA256>",
+                            number,
+                            checksum.clone().unwrap_or_default()
+                        )
+                    })
+                    .collect();
+                if parts.keys().copied().ne(1..=parts.len())
+                    || expected_parts.iter().any(|part| !body.contains(part))
+                {
+                    return Self::response(
+                        StatusCode::OK,
+                        "<Error><Code>InvalidPart</Code></Error>",
+                    );
+                }
+                self.objects
+                    .lock()
+                    .insert(key, parts.into_values().collect());
+                self.completed_uploads.fetch_add(1, Ordering::SeqCst);
+                Self::response(
+                    StatusCode::OK,
+                    "<CompleteMultipartUploadResult></CompleteMultipartUploadResult>",
+                )
+            },
+            Method::DELETE if query.contains_key("uploadId") => {
+                self.uploads.lock().remove(&query["uploadId"]);
+                Self::response(StatusCode::NO_CONTENT, "")
+            },
+            Method::DELETE => {
+                self.objects.lock().remove(&key);
+                Self::response(StatusCode::NO_CONTENT, "")
+            },
+            _ => Self::response(StatusCode::METHOD_NOT_ALLOWED, ""),
+        }
+    }
+
+    fn list(&self, prefix: &str, continuation_token: Option<&String>) -> Response<Body> {
+        let objects = self.objects.lock();
+        let keys: Vec<_> = objects
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .filter(|key| continuation_token.map_or(true, |token| *key > token))
+            .take(LIST_PAGE_SIZE + 1)
+            .collect();
+        let is_truncated = keys.len() > LIST_PAGE_SIZE;
+        let page = &keys[..keys.len().min(LIST_PAGE_SIZE)];
+        let contents: String = page
+            .iter()
+            .map(|key| format!("<Contents><Key>{}</Key></Contents>", key))
+            .collect();
+        let next = if is_truncated {
+            format!(
+                "<NextContinuationToken>{}</NextContinuationToken>",
+                page.last().unwrap()
+            )
+        } else {
+            String::new()
+        };
+        Self::response(
+            StatusCode::OK,
+            &format!(
+                "<ListBucketResult><IsTruncated>{}</IsTruncated>{}{}</ListBucketResult>",
+                is_truncated, contents, next
+            ),
+        )
+    }
+
+    fn response(status: StatusCode, body: &str) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(body.to_string().into())
+            .unwrap()
+    }
+}
+
+/// Serves a fake object store, and returns it along with an `S3` storage backed by it.
+fn start_fake_s3() -> (Arc<FakeS3>, S3) {
+    let fake = Arc::new(FakeS3::default());
+    let handler = fake.clone();
+    let route = warp::method()
+        .and(warp::path::full())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::header::headers_cloned())
+        .and(warp::body::bytes())
+        .map(move |method, path, query, headers, body| {
+            handler.handle(method, path, query, headers, body)
+        });
+    let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+    let (addr, svr) = warp::serve(route).bind_ephemeral(address);
+    tokio::spawn(svr);
+
+    let store = S3::new(S3Config {
+        endpoint: format!("http://localhost:{}", addr.port()),
+        region: "us-east-1".to_string(),
+        bucket: BUCKET.to_string(),
+        prefix: "backups/e1".to_string(),
+        virtual_hosted_style: false,
+        part_size: PART_SIZE,
+        concurrency: 3,
+        max_retries: 3,
+        access_key_id: Some("test".to_string()),
+        secret_access_key: Some("test".to_string()),
+        session_token: None,
+    })
+    .unwrap();
+    (fake, store)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_write_and_read(
+        backups in arb_backups()
+    ) {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let (_fake, store) = start_fake_s3();
+            test_write_and_read_impl(Box::new(store), backups).await
+        });
+    }
+
+    #[test]
+    fn test_save_list_metadata_files(
+        input in arb_metadata_files(),
+    ) {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let (_fake, store) = start_fake_s3();
+            test_save_and_list_metadata_files_impl(Box::new(store), input).await
+        });
+    }
+}
+
+async fn write_file(store: &S3, name: &str, content: &[u8]) -> FileHandle {
+    let backup_handle = store
+        .create_backup(&"backup".parse().unwrap())
+        .await
+        .unwrap();
+    let (file_handle, mut file) = store
+        .create_for_write(&backup_handle, &name.parse().unwrap())
+        .await
+        .unwrap();
+    file.write_all(content).await.unwrap();
+    file.shutdown().await.unwrap();
+    file_handle
+}
+
+async fn read_file(store: &S3, file_handle: &FileHandleRef) -> Vec<u8> {
+    let mut content = Vec::new();
+    store
+        .open_for_read(file_handle)
+        .await
+        .unwrap()
+        .read_to_end(&mut content)
+        .await
+        .unwrap();
+    content
+}
+
+#[tokio::test]
+async fn test_multipart_upload() {
+    let (fake, store) = start_fake_s3();
+    let content: Vec<u8> = (0..PART_SIZE * 10 + 1).map(|i| i as u8).collect();
+
+    let small = write_file(&store, "small_file", &content[..PART_SIZE - 1]).await;
+    assert_eq!(fake.completed_uploads.load(Ordering::SeqCst), 0);
+    let exact = write_file(&store, "exact_file", &content[..PART_SIZE * 2]).await;
+    let large = write_file(&store, "large_file", &content).await;
+    assert_eq!(fake.completed_uploads.load(Ordering::SeqCst), 2);
+    assert!(fake.uploads.lock().is_empty());
+    assert!(fake
+        .objects
+        .lock()
+        .contains_key("backups/e1/backup/large_file"));
+
+    assert_eq!(read_file(&store, &small).await, &content[..PART_SIZE - 1]);
+    assert_eq!(read_file(&store, &exact).await, &content[..PART_SIZE * 2]);
+    assert_eq!(read_file(&store, &large).await, content);
+}
+
+#[tokio::test]
+async fn test_retries() {
+    let (fake, store) = start_fake_s3();
+    let content: Vec<u8> = (0..PART_SIZE * 3).map(|i| i as u8).collect();
+
+    fake.failures.store(2, Ordering::SeqCst);
+    let file_handle = write_file(&store, "file", &content).await;
+    fake.failures.store(2, Ordering::SeqCst);
+    assert_eq!(read_file(&store, &file_handle).await, content);
+    assert_eq!(fake.failures.load(Ordering::SeqCst), 0);
+
+    // Give up after the configured number of retries.
+    fake.failures.store(10, Ordering::SeqCst);
+    assert!(store.open_for_read(&file_handle).await.is_err());
+}
+
+#[tokio::test]
+async fn test_checksums() {
+    let (fake, store) = start_fake_s3();
+    let content: Vec<u8> = (0..PART_SIZE * 3).map(|i| i as u8).collect();
+    let file_handle = write_file(&store, "file", &content).await;
+    let key = "backups/e1/backup/file";
+    assert!(fake.objects.lock()[key]
+        .iter()
+        .all(|(_, checksum)| checksum.is_some()));
+
+    // Reads which don't match the checksums are retried.
+    fake.corrupted_reads.store(2, Ordering::SeqCst);
+    assert_eq!(read_file(&store, &file_handle).await, content);
+    assert_eq!(fake.corrupted_reads.load(Ordering::SeqCst), 0);
+
+    // Give up after the configured number of retries.
+    fake.corrupted_reads.store(10, Ordering::SeqCst);
+    let mut file = store.open_for_read(&file_handle).await.unwrap();
+    assert!(file.read_to_end(&mut Vec::new()).await.is_err());
+    fake.corrupted_reads.store(0, Ordering::SeqCst);
+
+    // Files without checksums can't be verified.
+    for (_, checksum) in fake.objects.lock().get_mut(key).unwrap() {
+        *checksum = None;
+    }
+    let mut file = store.open_for_read(&file_handle).await.unwrap();
+    assert!(file.read_to_end(&mut Vec::new()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_backup_metadata_file() {
+    let (fake, store) = start_fake_s3();
+    let line = TextLine::new("line").unwrap();
+    let file_handle = store
+        .save_metadata_line(&"epoch_ending_1.meta".parse().unwrap(), &line)
+        .await
+        .unwrap();
+    assert_eq!(file_handle, "metadata/epoch_ending_1.meta");
+    assert_eq!(store.list_metadata_files().await.unwrap(), vec![
+        file_handle.clone()
+    ]);
+
+    store.backup_metadata_file(&file_handle).await.unwrap();
+    assert!(store.list_metadata_files().await.unwrap().is_empty());
+    assert_eq!(
+        fake.content("backups/e1/metadata_backup/epoch_ending_1.meta")
+            .unwrap(),
+        b"line\n"
+    );
+}
+
+#[test]
+fn test_sample_configs() {
+    let config = S3Config::load_from_str(include_str!("sample_configs/s3.sample.yaml")).unwrap();
+    assert_eq!(config.part_size, 16 << 20);
+    assert!(config.access_key_id.is_none());
+    let config = S3Config::load_from_str(include_str!("sample_configs/minio.sample.yaml")).unwrap();
+    assert_eq!(config.region, "us-east-1");
+    assert!(!config.virtual_hosted_style);
+
+    assert!(S3Config::load_from_str("endpoint: \"x\"\nbucket: \"y\"\npart_size: 1024").is_err());
+}
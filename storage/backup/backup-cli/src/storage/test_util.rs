@@ -46,6 +46,24 @@ pub async fn test_write_and_read_impl(
             assert_eq!(content, &buf);
         }
     }
+
+    for (backup_name, files) in &backups {
+        for name in files.keys() {
+            let handle = to_file_name(backup_name, name);
+            store.delete_file(&handle).await.unwrap();
+            assert!(async {
+                let mut buf = Vec::new();
+                store
+                    .open_for_read(&handle)
+                    .await?
+                    .read_to_end(&mut buf)
+                    .await?;
+                Result::<()>::Ok(())
+            }
+            .await
+            .is_err());
+        }
+    }
 }
 
 pub fn arb_backups(
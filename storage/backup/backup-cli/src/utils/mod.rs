@@ -5,6 +5,7 @@
 pub mod backup_service_client;
 pub(crate) mod error_notes;
 pub mod read_record_bytes;
+pub mod restore_progress;
 pub mod storage_ext;
 pub(crate) mod stream;
 
@@ -36,6 +37,7 @@ use aptos_types::{
     waypoint::Waypoint,
 };
 use clap::Parser;
+use restore_progress::RestoreProgressTracker;
 use std::{
     collections::HashMap,
     convert::TryFrom,
@@ -273,6 +275,7 @@ pub struct GlobalRestoreOptions {
     pub run_mode: Arc<RestoreRunMode>,
     pub concurrent_downloads: usize,
     pub replay_concurrency_level: usize,
+    pub progress: Arc<RestoreProgressTracker>,
 }
 
 impl TryFrom<GlobalRestoreOpt> for GlobalRestoreOptions {
@@ -282,7 +285,7 @@ impl TryFrom<GlobalRestoreOpt> for GlobalRestoreOptions {
         let target_version = opt.target_version.unwrap_or(Version::max_value());
         let concurrent_downloads = opt.concurrent_downloads.get();
         let replay_concurrency_level = opt.replay_concurrency_level.get();
-        let run_mode = if let Some(db_dir) = &opt.db_dir {
+        let (run_mode, progress) = if let Some(db_dir) = &opt.db_dir {
             // for restore, we can always start state store with empty buffered_state since we will restore
             let restore_handler = Arc::new(AptosDB::open_kv_only(
                 db_dir,
@@ -295,9 +298,15 @@ impl TryFrom<GlobalRestoreOpt> for GlobalRestoreOptions {
             )?)
             .get_restore_handler();
 
-            RestoreRunMode::Restore { restore_handler }
+            (
+                RestoreRunMode::Restore { restore_handler },
+                RestoreProgressTracker::new(db_dir)?,
+            )
         } else {
-            RestoreRunMode::Verify
+            (
+                RestoreRunMode::Verify,
+                RestoreProgressTracker::new_in_memory(),
+            )
         };
         Ok(Self {
             target_version,
@@ -305,6 +314,7 @@ impl TryFrom<GlobalRestoreOpt> for GlobalRestoreOptions {
             run_mode: Arc::new(run_mode),
             concurrent_downloads,
             replay_concurrency_level,
+            progress: Arc::new(progress),
         })
     }
 }
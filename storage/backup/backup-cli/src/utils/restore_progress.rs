@@ -0,0 +1,292 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{storage::FileHandle, utils::error_notes::ErrorNotes};
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use aptos_db::state_restore::StateSnapshotRestoreMode;
+use aptos_infallible::Mutex;
+use aptos_types::transaction::Version;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Progress of a restore into a DB, checkpointed into this file in the DB dir after every state
+/// snapshot chunk and every batch of transactions, so that an interrupted restore can be
+/// inspected and resumed from where it stopped.
+pub const RESTORE_PROGRESS_FILE: &str = "restore_progress.json";
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RestoreProgress {
+    /// Version the restore coordinator restores the DB to.
+    pub target_version: Option<Version>,
+    /// Version of the KV only snapshot the ledger history is restored on top of, if any.
+    pub kv_snapshot_version: Option<Version>,
+    /// Version of the snapshot the state tree is restored from.
+    pub tree_snapshot_version: Option<Version>,
+    /// The state snapshot being restored, or the last one restored.
+    pub state_snapshot: Option<StateSnapshotRestoreProgress>,
+    /// The transactions being saved or replayed, or the last ones.
+    pub transaction: Option<TransactionRestoreProgress>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateSnapshotRestoreProgress {
+    pub version: Version,
+    pub manifest: FileHandle,
+    pub restore_mode: StateSnapshotRestoreMode,
+    pub total_chunks: usize,
+    pub total_leaves: usize,
+    /// Number of chunks at the start of the manifest whose state values are all in the DB.
+    pub chunks_done: usize,
+    /// Number of state values in those chunks.
+    pub leaves_done: usize,
+    /// Hash of the last state key restored, which the restore resumes after.
+    pub last_key: Option<HashValue>,
+    pub finished: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TransactionRestoreProgress {
+    /// First version saved or replayed by the current run.
+    pub first_version: Version,
+    /// Version the transactions are restored up to, inclusive.
+    pub target_version: Version,
+    /// Version of the next transaction to be saved or replayed.
+    pub next_version: Version,
+    /// Transactions from this version on are replayed instead of being saved as they are.
+    pub replay_from_version: Option<Version>,
+    pub kv_only_replay: bool,
+}
+
+impl RestoreProgress {
+    pub fn load(db_dir: &Path) -> Result<Option<Self>> {
+        let path = db_dir.join(RESTORE_PROGRESS_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let path_str = path.to_str().unwrap_or_default();
+        let content = std::fs::read(&path).err_notes(path_str)?;
+        Ok(Some(serde_json::from_slice(&content).err_notes(path_str)?))
+    }
+}
+
+impl Display for RestoreProgress {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if let Some(target_version) = self.target_version {
+            write!(f, "Restoring to version {}", target_version)?;
+            if let Some(version) = self.kv_snapshot_version {
+                write!(f, ", ledger history from KV snapshot at {}", version)?;
+            }
+            if let Some(version) = self.tree_snapshot_version {
+                write!(f, ", state tree from snapshot at {}", version)?;
+            }
+            writeln!(f, ".")?;
+        }
+
+        match &self.state_snapshot {
+            Some(snapshot) => {
+                write!(
+                    f,
+                    "State snapshot at {} ({:?} mode): {}/{} chunks, {}/{} state values",
+                    snapshot.version,
+                    snapshot.restore_mode,
+                    snapshot.chunks_done,
+                    snapshot.total_chunks,
+                    snapshot.leaves_done,
+                    snapshot.total_leaves,
+                )?;
+                if snapshot.finished {
+                    writeln!(f, ", finished.")?;
+                } else {
+                    writeln!(
+                        f,
+                        ", {} chunks remaining, resuming after key {}.",
+                        snapshot.total_chunks - snapshot.chunks_done,
+                        snapshot
+                            .last_key
+                            .map_or_else(|| "<none>".to_string(), |key| key.to_hex()),
+                    )?;
+                }
+                writeln!(f, "  manifest: {}", snapshot.manifest)?;
+            },
+            None => writeln!(f, "No state snapshot restored.")?,
+        }
+
+        match &self.transaction {
+            Some(txn) => {
+                let remaining = (txn.target_version + 1).saturating_sub(txn.next_version);
+                write!(
+                    f,
+                    "Transactions from {} to {}: next version {}, {} remaining",
+                    txn.first_version, txn.target_version, txn.next_version, remaining,
+                )?;
+                if let Some(version) = txn.replay_from_version {
+                    let kind = if txn.kv_only_replay { "KV " } else { "" };
+                    write!(f, ", {}replaying from {}", kind, version)?;
+                }
+                writeln!(f, ".")
+            },
+            None => writeln!(f, "No transactions restored."),
+        }
+    }
+}
+
+/// Keeps the `RestoreProgress` of a restore, persisting every update into the DB dir, unless
+/// created in memory, like for a dry run.
+pub struct RestoreProgressTracker {
+    path: Option<PathBuf>,
+    progress: Mutex<RestoreProgress>,
+    /// Held across writing a checkpoint, so that checkpoints land in the order of the updates.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl RestoreProgressTracker {
+    pub fn new(db_dir: &Path) -> Result<Self> {
+        Ok(Self {
+            path: Some(db_dir.join(RESTORE_PROGRESS_FILE)),
+            progress: Mutex::new(RestoreProgress::load(db_dir)?.unwrap_or_default()),
+            write_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    pub fn new_in_memory() -> Self {
+        Self {
+            path: None,
+            progress: Mutex::new(RestoreProgress::default()),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn get(&self) -> RestoreProgress {
+        self.progress.lock().clone()
+    }
+
+    pub async fn update(&self, f: impl FnOnce(&mut RestoreProgress)) -> Result<()> {
+        let _write_guard = self.write_lock.lock().await;
+        let checkpoint = {
+            let mut progress = self.progress.lock();
+            f(&mut progress);
+            match &self.path {
+                Some(path) => Some((path.clone(), serde_json::to_vec_pretty(&*progress)?)),
+                None => None,
+            }
+        };
+
+        if let Some((path, bytes)) = checkpoint {
+            tokio::task::spawn_blocking(move || Self::write_checkpoint(&path, &bytes)).await??;
+        }
+        Ok(())
+    }
+
+    pub async fn update_state_snapshot(
+        &self,
+        f: impl FnOnce(&mut StateSnapshotRestoreProgress),
+    ) -> Result<()> {
+        self.update(|progress| {
+            if let Some(snapshot) = progress.state_snapshot.as_mut() {
+                f(snapshot)
+            }
+        })
+        .await
+    }
+
+    pub async fn update_transaction(
+        &self,
+        f: impl FnOnce(&mut TransactionRestoreProgress),
+    ) -> Result<()> {
+        self.update(|progress| {
+            if let Some(txn) = progress.transaction.as_mut() {
+                f(txn)
+            }
+        })
+        .await
+    }
+
+    /// Written aside, synced and renamed, so that neither a crash nor a power loss leaves a torn
+    /// or empty checkpoint behind.
+    fn write_checkpoint(path: &Path, bytes: &[u8]) -> Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        let path_str = path.to_str().unwrap_or_default();
+        let mut file = File::create(&tmp_path).err_notes(path_str)?;
+        file.write_all(bytes).err_notes(path_str)?;
+        file.sync_all().err_notes(path_str)?;
+        std::fs::rename(&tmp_path, path).err_notes(path_str)?;
+        // The rename only survives a power loss once the directory is synced too.
+        if let Some(dir) = path.parent() {
+            File::open(dir)
+                .and_then(|dir| dir.sync_all())
+                .err_notes(path_str)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_temppath::TempPath;
+
+    #[tokio::test]
+    async fn test_checkpoint_round_trip() {
+        let db_dir = TempPath::new();
+        db_dir.create_as_dir().unwrap();
+        assert_eq!(RestoreProgress::load(db_dir.path()).unwrap(), None);
+
+        let tracker = RestoreProgressTracker::new(db_dir.path()).unwrap();
+        tracker
+            .update(|progress| {
+                progress.target_version = Some(100);
+                progress.state_snapshot = Some(StateSnapshotRestoreProgress {
+                    version: 50,
+                    manifest: "snapshot/state.manifest".to_string(),
+                    restore_mode: StateSnapshotRestoreMode::Default,
+                    total_chunks: 3,
+                    total_leaves: 30,
+                    chunks_done: 0,
+                    leaves_done: 0,
+                    last_key: None,
+                    finished: false,
+                });
+            })
+            .await
+            .unwrap();
+        tracker
+            .update_state_snapshot(|snapshot| {
+                snapshot.chunks_done = 1;
+                snapshot.leaves_done = 10;
+                snapshot.last_key = Some(HashValue::random());
+            })
+            .await
+            .unwrap();
+        // No transactions being restored, nothing to update.
+        tracker
+            .update_transaction(|txn| txn.next_version = 51)
+            .await
+            .unwrap();
+
+        let loaded = RestoreProgress::load(db_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, tracker.get());
+        assert_eq!(loaded.state_snapshot.as_ref().unwrap().chunks_done, 1);
+        assert!(loaded.transaction.is_none());
+        assert!(loaded.to_string().contains("2 chunks remaining"));
+
+        // A restarted restore picks up the checkpoint.
+        let tracker = RestoreProgressTracker::new(db_dir.path()).unwrap();
+        assert_eq!(tracker.get(), loaded);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_persists_nothing() {
+        let tracker = RestoreProgressTracker::new_in_memory();
+        tracker
+            .update(|progress| progress.target_version = Some(1))
+            .await
+            .unwrap();
+        assert_eq!(tracker.get().target_version, Some(1));
+    }
+}
@@ -1,9 +1,15 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use aptos_backup_cli::{
-    coordinators::backup::BackupCompactor, metadata::cache::MetadataCacheOpt,
-    storage::DBToolStorageOpt, utils::ConcurrentDownloadsOpt,
+    coordinators::{
+        audit::BackupAuditor,
+        backup::BackupCompactor,
+        retention::{BackupRetention, RetentionPolicy},
+    },
+    metadata::cache::MetadataCacheOpt,
+    storage::DBToolStorageOpt,
+    utils::{ConcurrentDownloadsOpt, TrustedWaypointOpt},
 };
 use clap::{Parser, Subcommand};
 
@@ -14,6 +20,10 @@ pub enum Command {
     Compact(CompactionOpt),
     #[clap(about = "Cleanup the backup metadata files")]
     Cleanup(CleanupOpt),
+    #[clap(about = "Delete state snapshots which are obsolete according to a retention policy")]
+    Retain(RetentionOpt),
+    #[clap(about = "Verify that all backups are restorable and report gaps in their coverage")]
+    Audit(AuditOpt),
 }
 
 #[derive(Parser)]
@@ -48,6 +58,37 @@ pub struct CleanupOpt {
     pub storage: DBToolStorageOpt,
 }
 
+#[derive(Parser)]
+pub struct RetentionOpt {
+    /// Keep the latest state snapshot of each of this many days, today included
+    #[clap(long, default_value = "7")]
+    pub keep_daily_state_snapshots_for_days: u64,
+    /// Keep the latest state snapshot of each of this many weeks, this week included
+    #[clap(long, default_value = "4")]
+    pub keep_weekly_state_snapshots_for_weeks: u64,
+    /// Only log the state snapshots which would be deleted
+    #[clap(long)]
+    pub dry_run: bool,
+    #[clap(flatten)]
+    pub metadata_cache_opt: MetadataCacheOpt,
+    #[clap(flatten)]
+    pub storage: DBToolStorageOpt,
+    #[clap(flatten)]
+    pub concurrent_downloads: ConcurrentDownloadsOpt,
+}
+
+#[derive(Parser)]
+pub struct AuditOpt {
+    #[clap(flatten)]
+    pub metadata_cache_opt: MetadataCacheOpt,
+    #[clap(flatten)]
+    pub trusted_waypoints_opt: TrustedWaypointOpt,
+    #[clap(flatten)]
+    pub storage: DBToolStorageOpt,
+    #[clap(flatten)]
+    pub concurrent_downloads: ConcurrentDownloadsOpt,
+}
+
 impl Command {
     pub async fn run(self) -> Result<()> {
         match self {
@@ -66,6 +107,40 @@ impl Command {
             Command::Cleanup(_) => {
                 // TODO: add cleanup logic for removing obsolete metadata files
             },
+            Command::Retain(opt) => {
+                let retention = BackupRetention::new(
+                    opt.storage.init_storage().await?,
+                    opt.metadata_cache_opt,
+                    opt.concurrent_downloads.get(),
+                    RetentionPolicy {
+                        keep_daily_state_snapshots_for_days: opt
+                            .keep_daily_state_snapshots_for_days,
+                        keep_weekly_state_snapshots_for_weeks: opt
+                            .keep_weekly_state_snapshots_for_weeks,
+                    },
+                    opt.dry_run,
+                );
+                for snapshot in retention.run().await? {
+                    println!(
+                        "{} state snapshot at version {}: {}",
+                        if opt.dry_run { "Obsolete" } else { "Deleted" },
+                        snapshot.version,
+                        snapshot.manifest,
+                    );
+                }
+            },
+            Command::Audit(opt) => {
+                let report = BackupAuditor::new(
+                    opt.storage.init_storage().await?,
+                    opt.metadata_cache_opt,
+                    opt.trusted_waypoints_opt,
+                    opt.concurrent_downloads.get(),
+                )
+                .run()
+                .await?;
+                print!("{}", report);
+                ensure!(report.is_healthy(), "Backups failed the audit.");
+            },
         }
         Ok(())
     }
@@ -10,10 +10,11 @@ use aptos_backup_cli::{
     },
     coordinators::restore::{RestoreCoordinator, RestoreCoordinatorOpt},
     storage::DBToolStorageOpt,
-    utils::GlobalRestoreOpt,
+    utils::{restore_progress::RestoreProgress, GlobalRestoreOpt},
 };
 use aptos_executor_types::VerifyExecutionMode;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 /// Restore the database using either a one-time or continuous backup.
 #[derive(Subcommand)]
@@ -22,6 +23,8 @@ pub enum Command {
     BootstrapDB(BootstrapDB),
     #[clap(subcommand)]
     Oneoff(Oneoff),
+    #[clap(about = "show the progress of an interrupted or ongoing restore, and what remains")]
+    Status(Status),
 }
 
 #[derive(Parser)]
@@ -34,6 +37,12 @@ pub struct BootstrapDB {
     global: GlobalRestoreOpt,
 }
 
+#[derive(Parser)]
+pub struct Status {
+    #[clap(long = "target-db-dir", parse(from_os_str))]
+    db_dir: PathBuf,
+}
+
 #[derive(Parser)]
 pub enum Oneoff {
     EpochEnding {
@@ -120,6 +129,13 @@ impl Command {
                 .run()
                 .await?;
             },
+            Command::Status(status) => {
+                // Only the checkpoint file is read, so this works while a restore holds the DB.
+                match RestoreProgress::load(&status.db_dir)? {
+                    Some(progress) => print!("{}", progress),
+                    None => println!("No restore progress found in {:?}.", status.db_dir),
+                }
+            },
         }
 
         Ok(())
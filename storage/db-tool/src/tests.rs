@@ -46,6 +46,25 @@ fn test_various_cmd_parsing() {
         ".",
     ]);
 
+    run_cmd(&["aptos-db-tool", "restore", "status", "--target-db-dir", "."]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "backup-maintenance",
+        "retain",
+        "--keep-daily-state-snapshots-for-days",
+        "3",
+        "--dry-run",
+        "--local-fs-dir",
+        ".",
+    ]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "backup-maintenance",
+        "audit",
+        "--local-fs-dir",
+        ".",
+    ]);
+
     run_cmd(&["aptos-db-tool", "backup", "verify", "--local-fs-dir", "."]);
     run_cmd(&[
         "aptos-db-tool",
@@ -78,7 +97,7 @@ mod compaction_tests {
         metadata,
         metadata::{cache::MetadataCacheOpt, view::MetadataView},
         storage::{local_fs::LocalFs, BackupStorage},
-        utils::test_utils::start_local_backup_service,
+        utils::{restore_progress::RestoreProgress, test_utils::start_local_backup_service},
     };
     use aptos_config::config::RocksdbConfigs;
     use aptos_db::AptosDB;
@@ -266,6 +285,22 @@ mod compaction_tests {
             ))
             .unwrap();
         assert_metadata_view_eq(&old_metaview, &new_metaview);
+
+        // the compacted backups are complete and still restorable
+        rt.block_on(
+            DBTool::try_parse_from([
+                "aptos-db-tool",
+                "backup-maintenance",
+                "audit",
+                "--metadata-cache-dir",
+                metadata_cache_dir.path().to_str().unwrap(),
+                "--local-fs-dir",
+                backup_dir.path().to_str().unwrap(),
+            ])
+            .unwrap()
+            .run(),
+        )
+        .unwrap();
         rt.shutdown_timeout(Duration::from_secs(1));
     }
 
@@ -543,6 +578,29 @@ mod compaction_tests {
             .run(),
         )
         .unwrap();
+
+        // The tree snapshot was restored by the first run, the second run only replayed.
+        let progress = RestoreProgress::load(new_db_dir.path()).unwrap().unwrap();
+        assert_eq!(progress.target_version, Some(18));
+        let snapshot = progress.state_snapshot.unwrap();
+        assert_eq!(snapshot.version, 13);
+        assert!(snapshot.finished);
+        assert_eq!(snapshot.chunks_done, snapshot.total_chunks);
+        let txn = progress.transaction.unwrap();
+        assert_eq!(txn.first_version, 17);
+        assert_eq!(txn.next_version, 19);
+        rt.block_on(
+            DBTool::try_parse_from([
+                "aptos-db-tool",
+                "restore",
+                "status",
+                "--target-db-dir",
+                new_db_dir.path().to_str().unwrap(),
+            ])
+            .unwrap()
+            .run(),
+        )
+        .unwrap();
         rt.shutdown_timeout(Duration::from_secs(1));
     }
 }
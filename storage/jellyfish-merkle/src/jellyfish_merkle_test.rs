@@ -646,6 +646,65 @@ fn many_keys_get_proof_and_verify_tree_root(seed: &[u8], num_keys: usize) {
     }
 }
 
+fn many_keys_get_multi_proof_and_verify_tree_root(seed: &[u8], num_keys: usize) {
+    assert!(seed.len() < 32);
+    let mut actual_seed = [0u8; 32];
+    actual_seed[..seed.len()].copy_from_slice(seed);
+    let mut rng: StdRng = StdRng::from_seed(actual_seed);
+
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+
+    let mut kvs = vec![];
+
+    let values: Vec<_> = (0..num_keys).map(|_i| gen_value()).collect();
+
+    for (index, _) in values.iter().enumerate() {
+        let key = HashValue::random_with_rng(&mut rng);
+        kvs.push((key, Some(&values[index])));
+    }
+
+    let (root, batch) = tree
+        .put_value_set_test(kvs.clone(), 0 /* version */)
+        .unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+
+    // Prove every other existing key along with as many non-existing keys.
+    let mut elements: Vec<_> = kvs
+        .iter()
+        .step_by(2)
+        .map(|(k, v)| (*k, v.map(|x| x.0)))
+        .chain((0..num_keys / 2).map(|_| (HashValue::random_with_rng(&mut rng), None)))
+        .collect();
+    let keys: Vec<_> = elements.iter().map(|(k, _)| *k).collect();
+    let (values, proof) = tree.get_with_multi_proof(&keys, 0).unwrap();
+
+    elements.sort_unstable_by_key(|(k, _)| *k);
+    assert_eq!(
+        values
+            .iter()
+            .map(|(k, v)| (*k, v.as_ref().map(|x| x.0)))
+            .collect::<Vec<_>>(),
+        elements
+    );
+    assert!(proof.verify_by_hash(root, &elements).is_ok());
+
+    // The siblings shared by the paths are not duplicated.
+    let num_siblings: usize = keys
+        .iter()
+        .map(|k| tree.get_with_proof(*k, 0).unwrap().1.siblings().len())
+        .sum();
+    assert!(proof.siblings().len() < num_siblings);
+
+    // Flipping an element makes the proof invalid.
+    let mut wrong_elements = elements.clone();
+    wrong_elements[0].1 = match wrong_elements[0].1 {
+        Some(_) => None,
+        None => Some(HashValue::random_with_rng(&mut rng)),
+    };
+    assert!(proof.verify_by_hash(root, &wrong_elements).is_err());
+}
+
 fn many_keys_deletion(seed: &[u8], num_keys: usize) {
     assert!(seed.len() < 32);
     let mut actual_seed = [0u8; 32];
@@ -707,6 +766,12 @@ fn test_1000_keys() {
     many_keys_get_proof_and_verify_tree_root(seed, 1000);
 }
 
+#[test]
+fn test_1000_keys_multi_proof() {
+    let seed: &[_] = &[1, 2, 3, 4];
+    many_keys_get_multi_proof_and_verify_tree_root(seed, 1000);
+}
+
 #[test]
 fn test_2000_keys_deletion() {
     let seed: &[_] = &[1, 2, 3, 4];
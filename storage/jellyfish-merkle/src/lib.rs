@@ -88,7 +88,9 @@ use aptos_crypto::{
 };
 use aptos_types::{
     nibble::{nibble_path::NibblePath, Nibble, ROOT_NIBBLE_HEIGHT},
-    proof::{SparseMerkleProof, SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{
+        SparseMerkleMultiProof, SparseMerkleProof, SparseMerkleProofExt, SparseMerkleRangeProof,
+    },
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::Version,
 };
@@ -704,6 +706,34 @@ where
         bail!("Jellyfish Merkle tree has cyclic graph inside.");
     }
 
+    /// Returns the values (if applicable) of the keys, ordered by key, and a multi proof
+    /// authenticating all of them, which carries each sibling once. Duplicate keys are only
+    /// returned once.
+    ///
+    /// Each key is looked up with its own walk from the root, so this reads O(N·depth) nodes for N
+    /// keys, as many as `get_with_proof` for each key. Only the proof is shared between the keys.
+    pub fn get_with_multi_proof(
+        &self,
+        keys: &[HashValue],
+        version: Version,
+    ) -> Result<(
+        Vec<(HashValue, Option<(HashValue, (K, Version))>)>,
+        SparseMerkleMultiProof,
+    )> {
+        let mut keys = keys.to_vec();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let mut values = Vec::with_capacity(keys.len());
+        let mut proofs = Vec::with_capacity(keys.len());
+        for key in keys {
+            let (value, proof) = self.get_with_proof(key, version)?;
+            values.push((key, value));
+            proofs.push((key, proof));
+        }
+        Ok((values, SparseMerkleMultiProof::from_proofs(proofs)?))
+    }
+
     /// Gets the proof that shows a list of keys up to `rightmost_key_to_prove` exist at `version`.
     pub fn get_range_proof(
         &self,
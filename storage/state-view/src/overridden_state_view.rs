@@ -0,0 +1,48 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{StateViewId, TStateView};
+use anyhow::Result;
+use aptos_types::state_store::{
+    state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+};
+use std::collections::HashMap;
+
+/// A state view that serves a set of overridden state items and falls back to `base` for the
+/// rest. An override of `None` means the state item is treated as deleted.
+///
+/// This is used to execute transactions against a modified version of an existing state, e.g. to
+/// simulate a transaction as if a module was already upgraded.
+pub struct OverriddenStateView<'a, S> {
+    base: &'a S,
+    overrides: &'a HashMap<StateKey, Option<StateValue>>,
+}
+
+impl<'a, S> OverriddenStateView<'a, S> {
+    pub fn new(base: &'a S, overrides: &'a HashMap<StateKey, Option<StateValue>>) -> Self {
+        Self { base, overrides }
+    }
+}
+
+impl<'a, S: TStateView<Key = StateKey>> TStateView for OverriddenStateView<'a, S> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        match self.overrides.get(state_key) {
+            Some(value) => Ok(value.clone()),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
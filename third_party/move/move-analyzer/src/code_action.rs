@@ -0,0 +1,218 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quick fixes for common compiler diagnostics. The diagnostics are recognized by their messages,
+//! as they are sent back by the client, and the fixes are computed on the current content of the
+//! file.
+
+use crate::{
+    context::Context,
+    symbols::Symbols,
+    utils::{offset_to_position, position_to_offset},
+};
+use lsp_server::Request;
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Diagnostic, Position, Range,
+    TextEdit, WorkspaceEdit,
+};
+use move_symbol_pool::Symbol;
+use std::collections::{BTreeSet, HashMap};
+
+/// Sends the quick fixes for the diagnostics in the range of a code action request.
+pub fn on_code_action_request(context: &Context, request: &Request, symbols: &Symbols) {
+    eprintln!("handling code action request");
+    let parameters = serde_json::from_value::<CodeActionParams>(request.params.clone())
+        .expect("could not deserialize code action request");
+
+    let uri = parameters.text_document.uri;
+    let path = uri.to_file_path().unwrap();
+    let mut actions = vec![];
+    match context.files.get(&path) {
+        Some(buffer) => {
+            for diag in &parameters.context.diagnostics {
+                for (title, edit) in quick_fixes(buffer, symbols, diag) {
+                    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                        title,
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diag.clone()]),
+                        edit: Some(WorkspaceEdit::new(HashMap::from([(uri.clone(), vec![
+                            edit,
+                        ])]))),
+                        ..Default::default()
+                    }));
+                }
+            }
+        },
+        None => eprintln!(
+            "Could not read '{:?}' when handling code action request",
+            path
+        ),
+    }
+
+    let result = serde_json::to_value(actions).expect("could not serialize code action response");
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send code action response: {:?}", err);
+    }
+}
+
+/// Returns the titles and edits of the quick fixes for a diagnostic.
+fn quick_fixes(buffer: &str, symbols: &Symbols, diag: &Diagnostic) -> Vec<(String, TextEdit)> {
+    let msg = diag.message.as_str();
+    if let Some(name) = quoted_after(msg, "Unused local variable '")
+        .or_else(|| quoted_after(msg, "Unused parameter '"))
+    {
+        let start = diag.range.start;
+        return vec![(
+            format!("Prefix '{}' with an underscore", name),
+            TextEdit::new(Range::new(start, start), "_".to_string()),
+        )];
+    }
+
+    // unbound names are imported from any module (of the package or its dependencies) declaring
+    // them
+    let mods = || symbols.file_mods().values().flatten();
+    let use_paths: BTreeSet<String> =
+        if let Some(name) = quoted_after(msg, "Unbound module alias '") {
+            mods()
+                .filter(|m| m.module_name().as_str() == name)
+                .map(|m| m.use_path())
+                .collect()
+        } else if let Some(name) = quoted_after(msg, "Unbound type '") {
+            mods()
+                .filter(|m| m.struct_names().any(|s| s.as_str() == name))
+                .map(|m| format!("{}::{}", m.use_path(), name))
+                .collect()
+        } else if let Some(name) = quoted_after(msg, "Unbound function: '") {
+            mods()
+                .filter(|m| m.functions().contains_key(&Symbol::from(name)))
+                .map(|m| format!("{}::{}", m.use_path(), name))
+                .collect()
+        } else {
+            return missing_acquires_fix(buffer, diag).into_iter().collect();
+        };
+    match use_position(buffer, diag.range.start.line) {
+        Some((pos, indent)) => use_paths
+            .into_iter()
+            .map(|path| {
+                (
+                    format!("Import '{}'", path),
+                    TextEdit::new(Range::new(pos, pos), format!("{}use {};\n", indent, path)),
+                )
+            })
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Returns the text between single quotes following the prefix the message starts with.
+fn quoted_after<'a>(msg: &'a str, prefix: &str) -> Option<&'a str> {
+    msg.strip_prefix(prefix)?.split('\'').next()
+}
+
+/// Returns where to insert a `use` declaration in the module or script containing the given line,
+/// which is after its last `use` declaration, or at its start if there are none, along with the
+/// indentation of the declaration.
+fn use_position(buffer: &str, line: u32) -> Option<(Position, String)> {
+    let mut position = None;
+    for (idx, text) in buffer.lines().enumerate().take(line as usize + 1) {
+        let trimmed = text.trim_start();
+        let indent = &text[..text.len() - trimmed.len()];
+        if trimmed.starts_with("module ") || trimmed.starts_with("script") {
+            position = Some((idx + 1, format!("{}    ", indent)));
+        } else if trimmed.starts_with("use ") && position.is_some() {
+            position = Some((idx + 1, indent.to_string()));
+        }
+    }
+    position.map(|(line, indent)| (Position::new(line as u32, 0), indent))
+}
+
+/// Returns the fix adding a struct to the acquires list of the function calling an inline
+/// function which acquires it.
+fn missing_acquires_fix(buffer: &str, diag: &Diagnostic) -> Option<(String, TextEdit)> {
+    let msg = diag.message.as_str();
+    let acquired = quoted_after(msg, "The call acquires '")?;
+    let struct_name = acquired.rsplit("::").next()?;
+    let fun_name = msg.split("current function '").nth(1)?.split('`').next()?;
+
+    // the header of the calling function is the last one with its name before the call
+    let call_offset = position_to_offset(buffer, &diag.range.start)?;
+    let fun_decl = format!("fun {}", fun_name);
+    let mut header_start = call_offset;
+    loop {
+        header_start = buffer[..header_start].rfind(&fun_decl)?;
+        let next = buffer[header_start + fun_decl.len()..].chars().next();
+        if matches!(next, Some(c) if c == '(' || c == '<' || c.is_whitespace()) {
+            break;
+        }
+    }
+    // types can't contain braces, so the first one after the name starts the function body
+    let header_len = buffer[header_start..].find('{')?;
+    let header = buffer[header_start..header_start + header_len].trim_end();
+    let has_acquires = header
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .any(|word| word == "acquires");
+    let new_text = if has_acquires {
+        format!(", {}", struct_name)
+    } else {
+        format!(" acquires {}", struct_name)
+    };
+    let pos = offset_to_position(buffer, header_start + header.len());
+    Some((
+        format!(
+            "Add '{}' to the acquires list of '{}'",
+            struct_name, fun_name
+        ),
+        TextEdit::new(Range::new(pos, pos), new_text),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diag(line: u32, character: u32, message: &str) -> Diagnostic {
+        let pos = Position::new(line, character);
+        Diagnostic {
+            range: Range::new(pos, pos),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn use_position_test() {
+        let buffer = "module 0x1::M {\n    use std::vector;\n\n    fun f() {}\n}\n";
+        assert_eq!(
+            use_position(buffer, 3),
+            Some((Position::new(2, 0), "    ".to_string()))
+        );
+        let buffer = "address 0x1 {\n    module M {\n        fun f() {}\n    }\n}\n";
+        assert_eq!(
+            use_position(buffer, 2),
+            Some((Position::new(2, 0), "        ".to_string()))
+        );
+        assert_eq!(use_position("fun f() {}", 0), None);
+    }
+
+    #[test]
+    fn missing_acquires_fix_test() {
+        let msg = "The call acquires '0x1::M::R', but the 'acquires' list for the current \
+                   function 'f` does not contain this type. It must be present in the calling \
+                   context's acquires list";
+        let buffer = "module 0x1::M {\n    fun f_other() {}\n    fun f(a: u64): u64 {\n        \
+                      g(a)\n    }\n}\n";
+        let (_, edit) = missing_acquires_fix(buffer, &diag(3, 8, msg)).unwrap();
+        assert_eq!(edit.range.start, Position::new(2, 22));
+        assert_eq!(edit.new_text, " acquires R");
+
+        let buffer = "module 0x1::M {\n    fun f() acquires S {\n        g()\n    }\n}\n";
+        let (_, edit) = missing_acquires_fix(buffer, &diag(2, 8, msg)).unwrap();
+        assert_eq!(edit.range.start, Position::new(1, 22));
+        assert_eq!(edit.new_text, ", R");
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::Context;
+use lsp_server::Request;
+use lsp_types::{DocumentFormattingParams, Position, Range, TextEdit};
+use move_compiler::formatter::{format_source, FormatOptions};
+
+/// Sends the edits formatting a Move file, in the form of a single edit replacing the whole
+/// content of the file, if it changes. No edits are sent if the file can't be formatted, e.g.,
+/// because it contains invalid tokens.
+pub fn on_formatting_request(context: &Context, request: &Request) {
+    eprintln!("handling formatting request");
+    let parameters = serde_json::from_value::<DocumentFormattingParams>(request.params.clone())
+        .expect("could not deserialize formatting request");
+
+    let path = parameters.text_document.uri.to_file_path().unwrap();
+    let buffer = match context.files.get(&path) {
+        Some(buffer) => Some(buffer.to_string()),
+        None => std::fs::read_to_string(&path).ok(),
+    };
+    let options = FormatOptions {
+        indent_width: parameters.options.tab_size as usize,
+    };
+
+    let mut edits = vec![];
+    match buffer {
+        Some(buffer) => match format_source(&buffer, &options) {
+            Ok(formatted) if formatted != buffer => edits.push(TextEdit {
+                range: Range::new(Position::new(0, 0), end_position(&buffer)),
+                new_text: formatted,
+            }),
+            Ok(_) => {},
+            Err(_) => eprintln!("Could not format '{:?}', it contains invalid tokens", path),
+        },
+        None => eprintln!(
+            "Could not read '{:?}' when handling formatting request",
+            path
+        ),
+    }
+
+    let result = serde_json::to_value(edits).expect("could not serialize formatting response");
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send formatting response: {:?}", err);
+    }
+}
+
+/// Returns the position of the end of the text, which only contains ASCII characters, since Move
+/// source files are restricted to them.
+fn end_position(text: &str) -> Position {
+    let last_line = text.rsplit('\n').next().unwrap_or_default();
+    Position::new(text.matches('\n').count() as u32, last_line.len() as u32)
+}
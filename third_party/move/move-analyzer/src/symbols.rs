@@ -49,7 +49,7 @@
 use crate::{
     context::Context,
     diagnostics::{lsp_diagnostics, lsp_empty_diagnostics},
-    utils::get_loc,
+    utils::{get_loc, offset_to_position, position_to_offset},
 };
 use anyhow::{anyhow, Result};
 use codespan_reporting::files::SimpleFiles;
@@ -59,14 +59,20 @@ use im::ordmap::OrdMap;
 use lsp_server::{Request, RequestId};
 use lsp_types::{
     request::GotoTypeDefinitionParams, Diagnostic, DocumentSymbol, DocumentSymbolParams,
-    GotoDefinitionParams, Hover, HoverContents, HoverParams, LanguageString, Location,
-    MarkedString, Position, Range, ReferenceParams, SymbolKind,
+    Documentation, GotoDefinitionParams, Hover, HoverContents, HoverParams, LanguageString,
+    Location, MarkedString, ParameterInformation, ParameterLabel, Position, Range, ReferenceParams,
+    RenameParams, SignatureHelp, SignatureHelpParams, SignatureInformation, SymbolKind,
+    TextDocumentIdentifier, TextEdit, WorkspaceEdit,
 };
 use move_command_line_common::files::FileHash;
 use move_compiler::{
     expansion::ast::{Address, Fields, ModuleIdent, ModuleIdent_},
     naming::ast::{StructDefinition, StructFields, TParam, Type, TypeName_, Type_},
-    parser::ast::StructName,
+    parser::{
+        ast::StructName,
+        keywords::KEYWORDS,
+        lexer::{Lexer, Tok},
+    },
     shared::Identifier,
     typing::ast::{
         BuiltinFunction_, Exp, ExpListItem, Function, FunctionBody_, LValue, LValueList, LValue_,
@@ -77,10 +83,12 @@ use move_compiler::{
 use move_ir_types::location::*;
 use move_package::compilation::build_plan::BuildPlan;
 use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
 use std::{
     cmp,
     collections::{BTreeMap, BTreeSet, HashMap},
     fmt,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     sync::{Arc, Condvar, Mutex},
     thread,
@@ -149,6 +157,9 @@ pub struct UseDef {
     type_def_loc: Option<DefLoc>,
     /// Doc string for the relevant identifier/function
     doc_string: String,
+    /// Whether this is the definition of a local variable bound without a type annotation, whose
+    /// type is then shown to the user as an inlay hint
+    inferred_type: bool,
 }
 
 /// Definition of a struct field
@@ -228,6 +239,8 @@ pub struct Symbols {
     file_name_mapping: BTreeMap<FileHash, Symbol>,
     /// A mapping from filePath to ModuleDefs
     file_mods: BTreeMap<PathBuf, BTreeSet<ModuleDefs>>,
+    /// Hashes of the files of the package itself, as opposed to the files of its dependencies
+    workspace_files: BTreeSet<FileHash>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -246,6 +259,23 @@ impl ModuleDefs {
     pub fn functions(&self) -> &BTreeMap<Symbol, FunctionDef> {
         &self.functions
     }
+
+    pub fn struct_names(&self) -> impl Iterator<Item = &Symbol> {
+        self.structs.keys()
+    }
+
+    pub fn module_name(&self) -> Symbol {
+        self.name.module.value()
+    }
+
+    /// Returns the fully qualified name of the module, as used in `use` declarations
+    pub fn use_path(&self) -> String {
+        format!(
+            "{}::{}",
+            addr_to_ide_string(&self.name.address),
+            self.name.module.value()
+        )
+    }
 }
 
 impl fmt::Display for IdentType {
@@ -530,6 +560,7 @@ impl UseDef {
             def_loc,
             type_def_loc,
             doc_string,
+            inferred_type: false,
         }
     }
 }
@@ -599,11 +630,86 @@ impl Symbols {
         self.file_use_defs.extend(other.file_use_defs);
         self.file_name_mapping.extend(other.file_name_mapping);
         self.file_mods.extend(other.file_mods);
+        self.workspace_files.extend(other.workspace_files);
     }
 
     pub fn file_mods(&self) -> &BTreeMap<PathBuf, BTreeSet<ModuleDefs>> {
         &self.file_mods
     }
+
+    /// Returns the type and the doc string of the function of a call. The function is looked up by
+    /// the position of its name if the symbols are up to date with the source file, and otherwise
+    /// by its name, in which case there is no doc string.
+    fn called_function(
+        &self,
+        fpath: &Path,
+        buffer: &str,
+        call: &EnclosingCall,
+    ) -> Option<(IdentType, String)> {
+        let name_start = offset_to_position(buffer, call.name_offset);
+        let use_def = self
+            .file_use_defs
+            .get(fpath)
+            .and_then(|use_defs| use_defs.get(name_start.line))
+            .and_then(|uses| {
+                uses.into_iter()
+                    .find(|u| u.col_start == name_start.character)
+            });
+        if let Some(u) = use_def {
+            if let IdentType::FunctionType(_, name, ..) = &u.use_type {
+                if name.as_str() == call.name {
+                    return Some((u.use_type, u.doc_string));
+                }
+            }
+        }
+
+        let name = Symbol::from(call.name.as_str());
+        let mut mods: Box<dyn Iterator<Item = &ModuleDefs>> = match &call.module {
+            Some(module) => Box::new(
+                self.file_mods
+                    .values()
+                    .flatten()
+                    .filter(move |m| m.module_name().as_str() == module),
+            ),
+            None => Box::new(self.file_mods.get(fpath).into_iter().flatten()),
+        };
+        mods.find_map(|m| m.functions.get(&name))
+            .map(|fun_def| (fun_def.ident_type.clone(), String::new()))
+    }
+
+    /// Returns the inlay hints showing the types of the local variables bound without a type
+    /// annotation on the given lines of a file
+    fn inlay_hints(&self, fpath: &Path, lines: RangeInclusive<u32>) -> Vec<InlayHint> {
+        let mut hints = vec![];
+        let use_defs = match self.file_use_defs.get(fpath) {
+            Some(use_defs) if !lines.is_empty() => use_defs,
+            _ => return hints,
+        };
+        for (line, uses) in use_defs.0.range(lines) {
+            for u in uses.iter().filter(|u| u.inferred_type) {
+                let t = match &u.use_type {
+                    IdentType::RegularType(t) => t,
+                    IdentType::FunctionType(..) => continue,
+                };
+                // types are not known if the program has errors
+                if matches!(
+                    t.value,
+                    Type_::Anything | Type_::Var(_) | Type_::UnresolvedError
+                ) {
+                    continue;
+                }
+                hints.push(InlayHint {
+                    position: Position {
+                        line: *line,
+                        character: u.col_end,
+                    },
+                    label: format!(": {}", type_to_ide_string(t)),
+                    kind: Some(INLAY_HINT_KIND_TYPE),
+                });
+            }
+        }
+        hints
+    }
 }
 
 impl Symbolicator {
@@ -634,10 +740,17 @@ impl Symbolicator {
         let mut file_id_mapping = HashMap::new();
         let mut file_id_to_lines = HashMap::new();
         let mut file_name_mapping = BTreeMap::new();
+        let mut workspace_files = BTreeSet::new();
+        let pkg_root = dunce::canonicalize(pkg_path).unwrap_or_else(|_| pkg_path.to_path_buf());
         for (fhash, (fname, source)) in source_files {
             let id = files.add(*fname, source.clone());
             file_id_mapping.insert(*fhash, id);
             file_name_mapping.insert(*fhash, *fname);
+            let fpath = dunce::canonicalize(fname.as_str())
+                .unwrap_or_else(|_| PathBuf::from(fname.as_str()));
+            if fpath.starts_with(&pkg_root) {
+                workspace_files.insert(*fhash);
+            }
             let lines: Vec<String> = source.lines().map(String::from).collect();
             file_id_to_lines.insert(id, lines);
         }
@@ -773,6 +886,7 @@ impl Symbolicator {
             file_use_defs,
             file_name_mapping,
             file_mods,
+            workspace_files,
         };
 
         eprintln!("get_symbols load complete");
@@ -787,6 +901,7 @@ impl Symbolicator {
             references: BTreeMap::new(),
             file_name_mapping: BTreeMap::new(),
             file_mods: BTreeMap::new(),
+            workspace_files: BTreeSet::new(),
         }
     }
 
@@ -1088,6 +1203,7 @@ impl Symbolicator {
                 references,
                 use_defs,
                 ptype.clone(),
+                false,
             );
         }
 
@@ -1219,7 +1335,11 @@ impl Symbolicator {
                         None => (),
                     }
                 }
-                self.lvalue_list_symbols(true, lvalues, scope, references, use_defs);
+                // a type annotation of the bound variables is represented by annotating the RHS
+                let inferred_type = !matches!(e.exp.value, UnannotatedExp_::Annotate(..));
+                for lval in &lvalues.value {
+                    self.lvalue_symbols(true, inferred_type, lval, scope, references, use_defs);
+                }
             },
         }
     }
@@ -1234,7 +1354,7 @@ impl Symbolicator {
         use_defs: &mut UseDefMap,
     ) {
         for lval in &lvalues.value {
-            self.lvalue_symbols(define, lval, scope, references, use_defs);
+            self.lvalue_symbols(define, false, lval, scope, references, use_defs);
         }
     }
 
@@ -1242,6 +1362,7 @@ impl Symbolicator {
     fn lvalue_symbols(
         &self,
         define: bool,
+        inferred_type: bool,
         lval: &LValue,
         scope: &mut OrdMap<Symbol, DefLoc>,
         references: &mut BTreeMap<DefLoc, BTreeSet<UseLoc>>,
@@ -1257,6 +1378,7 @@ impl Symbolicator {
                         references,
                         use_defs,
                         *t.clone(),
+                        inferred_type,
                     );
                 } else {
                     self.add_local_use_def(
@@ -1317,7 +1439,7 @@ impl Symbolicator {
                 t,
             );
             // add definition or use of a variable used for struct field unpacking
-            self.lvalue_symbols(define, lvalue, scope, references, use_defs);
+            self.lvalue_symbols(define, false, lvalue, scope, references, use_defs);
         }
         // add type params
         for t in tparams {
@@ -1905,6 +2027,7 @@ impl Symbolicator {
         references: &mut BTreeMap<DefLoc, BTreeSet<UseLoc>>,
         use_defs: &mut UseDefMap,
         use_type: Type,
+        inferred_type: bool,
     ) {
         match Self::get_start_loc(pos, &self.files, &self.file_id_mapping) {
             Some(name_start) => {
@@ -1922,20 +2045,19 @@ impl Symbolicator {
                 // enter self-definition for def name
                 let ident_type = IdentType::RegularType(use_type);
                 let ident_type_def = self.ident_type_def_loc(&ident_type);
-                use_defs.insert(
-                    name_start.line,
-                    UseDef::new(
-                        references,
-                        pos.file_hash(),
-                        name_start,
-                        pos.file_hash(),
-                        name_start,
-                        name,
-                        ident_type,
-                        ident_type_def,
-                        doc_string,
-                    ),
+                let mut use_def = UseDef::new(
+                    references,
+                    pos.file_hash(),
+                    name_start,
+                    pos.file_hash(),
+                    name_start,
+                    name,
+                    ident_type,
+                    ident_type_def,
+                    doc_string,
                 );
+                use_def.inferred_type = inferred_type;
+                use_defs.insert(name_start.line, use_def);
             },
             None => {
                 debug_assert!(false);
@@ -2201,6 +2323,265 @@ pub fn on_hover_request(context: &Context, request: &Request, symbols: &Symbols)
     );
 }
 
+/// Handles rename request of the language server, by renaming the definition of the identifier
+/// and all its uses
+pub fn on_rename_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let parameters = serde_json::from_value::<RenameParams>(request.params.clone())
+        .expect("could not deserialize rename request");
+
+    let fpath = parameters
+        .text_document_position
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let loc = parameters.text_document_position.position;
+    let line = loc.line;
+    let col = loc.character;
+    let new_name = parameters.new_name;
+
+    if !is_valid_identifier(&new_name) {
+        send_rename_error(
+            context,
+            request,
+            format!("'{}' is not a valid identifier", new_name),
+        );
+        return;
+    }
+    // renaming an identifier defined in a dependency would require editing files which are not
+    // part of the package, and most likely not owned by the user
+    let def_loc = symbols
+        .file_use_defs
+        .get(&fpath)
+        .and_then(|use_defs| use_defs.get(line))
+        .and_then(|uses| {
+            uses.into_iter()
+                .find(|u| col >= u.col_start && col <= u.col_end)
+        })
+        .map(|u| u.def_loc);
+    if let Some(def_loc) = def_loc {
+        if !symbols.workspace_files.contains(&def_loc.fhash) {
+            send_rename_error(
+                context,
+                request,
+                "Cannot rename an identifier defined outside of the workspace".to_string(),
+            );
+            return;
+        }
+    }
+
+    on_use_request(
+        context,
+        symbols,
+        &fpath,
+        line,
+        col,
+        request.id.clone(),
+        |u| {
+            let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+            // the definition itself is among the references
+            for ref_loc in symbols.references.get(&u.def_loc).into_iter().flatten() {
+                let range = Range {
+                    start: ref_loc.start,
+                    end: Position {
+                        line: ref_loc.start.line,
+                        character: ref_loc.col_end,
+                    },
+                };
+                let path = symbols.file_name_mapping.get(&ref_loc.fhash).unwrap();
+                changes
+                    .entry(Url::from_file_path(path.as_str()).unwrap())
+                    .or_insert_with(Vec::new)
+                    .push(TextEdit::new(range, new_name.clone()));
+            }
+            Some(serde_json::to_value(WorkspaceEdit::new(changes)).unwrap())
+        },
+    );
+}
+
+fn send_rename_error(context: &Context, request: &Request, message: String) {
+    let response = lsp_server::Response::new_err(
+        request.id.clone(),
+        lsp_server::ErrorCode::InvalidParams as i32,
+        message,
+    );
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send rename response: {:?}", err);
+    }
+}
+
+fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name)
+}
+
+/// Handles signature help request of the language server, showing the signature of the function
+/// called with the arguments the cursor is in
+pub fn on_signature_help_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let parameters = serde_json::from_value::<SignatureHelpParams>(request.params.clone())
+        .expect("could not deserialize signature help request");
+
+    let fpath = parameters
+        .text_document_position_params
+        .text_document
+        .uri
+        .to_file_path()
+        .unwrap();
+    let loc = parameters.text_document_position_params.position;
+
+    let signature_help = context.files.get(&fpath).and_then(|buffer| {
+        let call = enclosing_call(buffer, &loc)?;
+        let (ident_type, doc_string) = symbols.called_function(&fpath, buffer, &call)?;
+        let signature = signature_information(ident_type, doc_string)?;
+        Some(SignatureHelp {
+            signatures: vec![signature],
+            active_signature: Some(0),
+            active_parameter: Some(call.arg_idx),
+        })
+    });
+
+    let result = serde_json::to_value(signature_help).unwrap();
+    eprintln!("about to send signature help response");
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send signature help response: {:?}", err);
+    }
+}
+
+/// A function call whose arguments contain the cursor
+#[derive(Debug, PartialEq, Eq)]
+struct EnclosingCall {
+    /// Module (alias) the function is accessed through, if any
+    module: Option<String>,
+    /// Name of the called function
+    name: String,
+    /// Offset of the name of the called function in the source file
+    name_offset: usize,
+    /// Index of the argument the cursor is at
+    arg_idx: u32,
+}
+
+/// Finds the innermost function call whose arguments contain the given position, by lexing the
+/// source file up to it. This only relies on the tokens, so that it still works while the user is
+/// typing the call and the file doesn't compile.
+fn enclosing_call(buffer: &str, pos: &Position) -> Option<EnclosingCall> {
+    let cursor = position_to_offset(buffer, pos)?;
+    let mut lexer = Lexer::new(buffer, FileHash::new(buffer));
+    // calls started by the brackets open at the current token (other brackets are kept as `None`
+    // so that the commas between the brackets are not counted as separating call arguments)
+    let mut calls: Vec<Option<EnclosingCall>> = vec![];
+    // names of the name access chain (e.g., `0x42::M::foo`) ending at the current token, with
+    // their offsets
+    let mut path: Vec<(&str, usize)> = vec![];
+    // name access chains preceding type arguments, and the one whose type arguments just ended
+    let mut generic_paths: Vec<Vec<(&str, usize)>> = vec![];
+    let mut generic_path = None;
+    let mut prev = Tok::EOF;
+    loop {
+        lexer.advance().ok()?;
+        let tok = lexer.peek();
+        if tok == Tok::EOF || lexer.start_loc() >= cursor {
+            break;
+        }
+        match tok {
+            Tok::LParen => {
+                let callee = match prev {
+                    Tok::Identifier => Some(path.clone()),
+                    Tok::Greater | Tok::GreaterGreater => generic_path.take(),
+                    _ => None,
+                };
+                calls.push(callee.and_then(|callee| {
+                    let (name, name_offset) = *callee.last()?;
+                    Some(EnclosingCall {
+                        module: callee
+                            .len()
+                            .checked_sub(2)
+                            .map(|idx| callee[idx].0.to_string()),
+                        name: name.to_string(),
+                        name_offset,
+                        arg_idx: 0,
+                    })
+                }));
+            },
+            Tok::LBrace | Tok::LBracket => calls.push(None),
+            Tok::RParen | Tok::RBrace | Tok::RBracket => {
+                calls.pop();
+            },
+            Tok::Comma => {
+                if let Some(Some(call)) = calls.last_mut() {
+                    call.arg_idx += 1;
+                }
+            },
+            Tok::Less if prev == Tok::Identifier => generic_paths.push(path.clone()),
+            Tok::Greater => generic_path = generic_paths.pop(),
+            Tok::GreaterGreater => {
+                generic_paths.pop();
+                generic_path = generic_paths.pop();
+            },
+            _ => (),
+        }
+        match tok {
+            Tok::Identifier if prev == Tok::ColonColon => {
+                path.push((lexer.content(), lexer.start_loc()))
+            },
+            Tok::Identifier => path = vec![(lexer.content(), lexer.start_loc())],
+            Tok::ColonColon => (),
+            _ => path.clear(),
+        }
+        prev = tok;
+    }
+    calls.into_iter().rev().flatten().next()
+}
+
+/// Builds the signature of a function as shown by signature help, with the offsets of the
+/// parameters in it
+fn signature_information(
+    ident_type: IdentType,
+    doc_string: String,
+) -> Option<SignatureInformation> {
+    let (arg_names, arg_types) = match &ident_type {
+        IdentType::FunctionType(_, _, _, arg_names, arg_types, _, _) => (arg_names, arg_types),
+        IdentType::RegularType(_) => return None,
+    };
+    let label = ident_type.to_string();
+    // type arguments can't contain parentheses, so the first one opens the parameter list
+    let mut start = label.find('(')? as u32 + 1;
+    let parameters = arg_names
+        .iter()
+        .zip(arg_types)
+        .map(|(name, typ)| {
+            let len = format!("{}: {}", name, type_to_ide_string(typ)).len() as u32;
+            let param = ParameterInformation {
+                label: ParameterLabel::LabelOffsets([start, start + len]),
+                documentation: None,
+            };
+            // skip the ", " separating the parameters
+            start += len + 2;
+            param
+        })
+        .collect();
+    Some(SignatureInformation {
+        label,
+        documentation: if doc_string.is_empty() {
+            None
+        } else {
+            Some(Documentation::String(doc_string))
+        },
+        parameters: Some(parameters),
+        active_parameter: None,
+    })
+}
+
 /// Helper function to handle language server queries related to identifier uses
 pub fn on_use_request(
     context: &Context,
@@ -2380,6 +2761,61 @@ fn handle_struct_fields(struct_def: StructDef, fields: &mut Vec<DocumentSymbol>)
     }
 }
 
+/// The `textDocument/inlayHint` request, which is not supported by the version of `lsp_types` in
+/// use. The types of its parameters and results are a subset of the ones of the protocol.
+pub enum InlayHintRequest {}
+
+impl lsp_types::request::Request for InlayHintRequest {
+    type Params = InlayHintParams;
+    type Result = Option<Vec<InlayHint>>;
+
+    const METHOD: &'static str = "textDocument/inlayHint";
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlayHintParams {
+    /// The document to compute the hints for
+    pub text_document: TextDocumentIdentifier,
+    /// The visible part of the document to compute the hints for
+    pub range: Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlayHint {
+    /// Position the hint is shown at
+    pub position: Position,
+    pub label: String,
+    /// Kind of the hint, see `INLAY_HINT_KIND_TYPE`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<u32>,
+}
+
+/// Kind of the inlay hints showing types
+pub const INLAY_HINT_KIND_TYPE: u32 = 1;
+
+/// Handles inlay hint request of the language server, showing the types of the local variables
+/// bound without a type annotation
+pub fn on_inlay_hint_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let parameters = serde_json::from_value::<InlayHintParams>(request.params.clone())
+        .expect("could not deserialize inlay hint request");
+
+    let fpath = parameters.text_document.uri.to_file_path().unwrap();
+    let range = parameters.range;
+
+    let hints = symbols.inlay_hints(&fpath, range.start.line..=range.end.line);
+
+    let response = lsp_server::Response::new_ok(request.id.clone(), hints);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send inlay hint response: {:?}", err);
+    }
+}
+
 #[cfg(test)]
 fn assert_use_def_with_doc_string(
     mod_symbols: &UseDefMap,
@@ -3669,3 +4105,227 @@ fn symbols_test() {
         None,
     );
 }
+
+#[test]
+/// Tests if inlay hints are produced for the local variables bound without a type annotation.
+fn inlay_hints_test() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push("tests/symbols");
+
+    let (symbols_opt, _) = Symbolicator::get_symbols(path.as_path()).unwrap();
+    let symbols = symbols_opt.unwrap();
+
+    let mut fpath = path.clone();
+    fpath.push("sources/M1.move");
+    let cpath = dunce::canonicalize(&fpath).unwrap();
+
+    let hint = |line, character, label: &str| InlayHint {
+        position: Position { line, character },
+        label: label.to_string(),
+        kind: Some(INLAY_HINT_KIND_TYPE),
+    };
+    // local variables (mut function)
+    assert_eq!(symbols.inlay_hints(&cpath, 55..=56), vec![
+        hint(55, 15, ": u64"),
+        hint(56, 13, ": &mut u64"),
+    ]);
+    // annotated local variables (cast and annot functions)
+    assert!(symbols.inlay_hints(&cpath, 113..=113).is_empty());
+    assert!(symbols.inlay_hints(&cpath, 118..=118).is_empty());
+    // local variable of a struct type from another module (struct_var function)
+    assert_eq!(symbols.inlay_hints(&cpath, 127..=127), vec![hint(
+        127,
+        15,
+        ": Symbols::M2::SomeOtherStruct"
+    )]);
+}
+
+#[test]
+/// Tests if renaming an identifier edits its definition and all its uses, and if renaming is
+/// rejected for invalid names and for identifiers defined in dependencies.
+fn rename_test() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push("tests/symbols");
+
+    let (symbols_opt, _) = Symbolicator::get_symbols(path.as_path()).unwrap();
+    let symbols = symbols_opt.unwrap();
+
+    let mut fpath = path.clone();
+    fpath.push("sources/M8.move");
+    let cpath = dunce::canonicalize(&fpath).unwrap();
+    let uri = Url::from_file_path(&cpath).unwrap();
+
+    let (connection, client) = lsp_server::Connection::memory();
+    let context = Context {
+        connection,
+        files: crate::vfs::VirtualFileSystem::default(),
+        symbols: Arc::new(Mutex::new(Symbolicator::empty_symbols())),
+    };
+    let rename = |line, character, new_name: &str| {
+        let params = RenameParams {
+            text_document_position: lsp_types::TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            new_name: new_name.to_string(),
+            work_done_progress_params: Default::default(),
+        };
+        let request = Request::new(
+            RequestId::from(0),
+            "textDocument/rename".to_string(),
+            params,
+        );
+        on_rename_request(&context, &request, &symbols);
+        match client.receiver.recv().unwrap() {
+            lsp_server::Message::Response(response) => response,
+            message => panic!("unexpected message: {:?}", message),
+        }
+    };
+    let edit = |line, start, end| {
+        TextEdit::new(
+            Range {
+                start: Position {
+                    line,
+                    character: start,
+                },
+                end: Position {
+                    line,
+                    character: end,
+                },
+            },
+            "nth".to_string(),
+        )
+    };
+
+    // function defined in the package (first_plus_len function)
+    let response = rename(9, 30, "nth");
+    let workspace_edit: WorkspaceEdit = serde_json::from_value(response.result.unwrap()).unwrap();
+    assert_eq!(
+        workspace_edit.changes.unwrap(),
+        HashMap::from([(uri.clone(), vec![edit(4, 15, 17), edit(9, 29, 31)])])
+    );
+
+    // invalid new name
+    let error = rename(9, 30, "fun").error.unwrap();
+    assert_eq!(error.code, lsp_server::ErrorCode::InvalidParams as i32);
+    assert_eq!(error.message, "'fun' is not a valid identifier");
+
+    // function defined in a dependency (first_plus_len function)
+    let error = rename(9, 17, "len").error.unwrap();
+    assert_eq!(error.code, lsp_server::ErrorCode::InvalidParams as i32);
+    assert_eq!(
+        error.message,
+        "Cannot rename an identifier defined outside of the workspace"
+    );
+}
+
+#[test]
+/// Tests if new names of identifiers are validated.
+fn is_valid_identifier_test() {
+    assert!(is_valid_identifier("foo"));
+    assert!(is_valid_identifier("_foo"));
+    assert!(is_valid_identifier("Foo_1"));
+    assert!(!is_valid_identifier(""));
+    assert!(!is_valid_identifier("1foo"));
+    assert!(!is_valid_identifier("foo-bar"));
+    assert!(!is_valid_identifier("foo::bar"));
+    assert!(!is_valid_identifier("fun"));
+    assert!(!is_valid_identifier("while"));
+}
+
+#[test]
+/// Tests if the function call enclosing a position is found, along with the argument the position
+/// is at.
+fn enclosing_call_test() {
+    let buffer = "module 0x42::M {\n    fun f() { N::g(1, vector[2, 3], h<u8>(4)); }\n}";
+    let position = |pattern: &str| {
+        let offset = buffer.find(pattern).unwrap();
+        offset_to_position(buffer, offset)
+    };
+
+    // first argument of a call through a module
+    assert_eq!(
+        enclosing_call(buffer, &position("1,")),
+        Some(EnclosingCall {
+            module: Some("N".to_string()),
+            name: "g".to_string(),
+            name_offset: buffer.find("g(").unwrap(),
+            arg_idx: 0,
+        })
+    );
+    // the commas of a vector don't separate the arguments of the call
+    assert_eq!(
+        enclosing_call(buffer, &position("3]")),
+        Some(EnclosingCall {
+            module: Some("N".to_string()),
+            name: "g".to_string(),
+            name_offset: buffer.find("g(").unwrap(),
+            arg_idx: 1,
+        })
+    );
+    // nested call with type arguments
+    assert_eq!(
+        enclosing_call(buffer, &position("4)")),
+        Some(EnclosingCall {
+            module: None,
+            name: "h".to_string(),
+            name_offset: buffer.find("h<").unwrap(),
+            arg_idx: 0,
+        })
+    );
+    // outside of any call
+    assert_eq!(enclosing_call(buffer, &position("; }")), None);
+    assert_eq!(enclosing_call(buffer, &position("N::")), None);
+}
+
+#[test]
+/// Tests if the signature of a called function is built with the offsets of its parameters.
+fn signature_information_test() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push("tests/symbols");
+
+    let (symbols_opt, _) = Symbolicator::get_symbols(path.as_path()).unwrap();
+    let symbols = symbols_opt.unwrap();
+
+    let mut fpath = path.clone();
+    fpath.push("sources/M8.move");
+    let cpath = dunce::canonicalize(&fpath).unwrap();
+
+    let mod_symbols = symbols.file_use_defs.get(&cpath).unwrap();
+    // function name in a call (first_plus_len function)
+    let uses = mod_symbols.get(9).unwrap();
+    let use_def = uses.iter().find(|u| u.col_start == 29).unwrap();
+
+    let signature =
+        signature_information(use_def.use_type.clone(), use_def.doc_string.clone()).unwrap();
+    assert_eq!(
+        signature.label,
+        "fun Symbols::M8::at(v: vector<u64>, i: u64): u64"
+    );
+    assert_eq!(
+        signature.documentation,
+        Some(Documentation::String(
+            "Returns the element of `v` at index `i`\n".to_string()
+        ))
+    );
+    assert_eq!(
+        signature.parameters,
+        Some(vec![
+            ParameterInformation {
+                label: ParameterLabel::LabelOffsets([20, 34]),
+                documentation: None,
+            },
+            ParameterInformation {
+                label: ParameterLabel::LabelOffsets([36, 42]),
+                documentation: None,
+            },
+        ])
+    );
+
+    // local variable (first_plus_len function)
+    let use_def = uses.iter().find(|u| u.col_start == 24).unwrap();
+    assert!(signature_information(use_def.use_type.clone(), use_def.doc_string.clone()).is_none());
+}
@@ -29,3 +29,31 @@ pub fn get_loc(
         Err(_) => None,
     }
 }
+
+/// Converts a line/character position in a text buffer to a byte offset, counting characters as
+/// bytes since Move source files only contain ASCII characters. Returns `None` if the buffer does
+/// not contain the position (e.g., because it's out of date).
+pub fn position_to_offset(buffer: &str, pos: &Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..pos.line {
+        line_start += buffer[line_start..].find('\n')? + 1;
+    }
+    let line_len = buffer[line_start..]
+        .find('\n')
+        .unwrap_or(buffer.len() - line_start);
+    if pos.character as usize > line_len {
+        return None;
+    }
+    Some(line_start + pos.character as usize)
+}
+
+/// Converts a byte offset in a text buffer to a line/character position, the inverse of
+/// `position_to_offset`.
+pub fn offset_to_position(buffer: &str, offset: usize) -> Position {
+    let before = &buffer[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: (offset - line_start) as u32,
+    }
+}
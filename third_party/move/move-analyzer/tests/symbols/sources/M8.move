@@ -0,0 +1,12 @@
+module Symbols::M8 {
+    use std::vector;
+
+    /// Returns the element of `v` at index `i`
+    public fun at(v: vector<u64>, i: u64): u64 {
+        *vector::borrow(&v, i)
+    }
+
+    public fun first_plus_len(v: vector<u64>): u64 {
+        vector::length(&v) + at(v, 0)
+    }
+}
@@ -237,6 +237,7 @@ codes!(
         BytecodeGeneration: { msg: "BYTECODE GENERATION FAILED", severity: Bug },
         BytecodeVerification: { msg: "BYTECODE VERIFICATION FAILED", severity: Bug },
         Unimplemented: { msg: "Not yet implemented", severity: BlockingError },
+        Formatting: { msg: "FORMATTING FAILED", severity: Bug },
     ],
     Derivation: [
         DeriveFailed: { msg: "attribute derivation failed", severity: BlockingError }
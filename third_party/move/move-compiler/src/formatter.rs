@@ -0,0 +1,548 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A formatter for Move source, including specs.
+//!
+//! The formatter works on the tokens of the source rather than on its AST, which keeps it
+//! independent of the parser and lets it preserve every comment where it was written. It
+//! normalizes the whitespace between the tokens of a line and the indentation of the lines, while
+//! keeping the line breaks of the source, except that runs of blank lines are collapsed into one.
+//! Where the spacing around a token depends on how the token is used, e.g., `<` as an operator or
+//! to start type arguments, the spacing of the source is kept.
+//!
+//! Only whitespace is ever changed: the tokens and comments of the formatted source are checked
+//! against the ones of the original source.
+
+use crate::{
+    diag,
+    diagnostics::{Diagnostic, Diagnostics},
+    parser::{lexer::*, syntax::make_loc},
+};
+use move_command_line_common::files::FileHash;
+
+#[derive(Clone, Debug)]
+pub struct FormatOptions {
+    /// Number of spaces per indentation level.
+    pub indent_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent_width: 4 }
+    }
+}
+
+/// Formats the content of a Move source file. Fails with the diagnostics of the lexer if the
+/// source can't be tokenized.
+pub fn format_source(source: &str, options: &FormatOptions) -> Result<String, Diagnostics> {
+    let file_hash = FileHash::new(source);
+    let items = tokenize(source, file_hash).map_err(|diag| Diagnostics::from(vec![*diag]))?;
+    let formatted = Printer::new(options).print(&items);
+
+    // Guard against the formatter ever changing the meaning of the source.
+    let formatted_items = tokenize(&formatted, FileHash::new(&formatted))
+        .map_err(|diag| Diagnostics::from(vec![*diag]))?;
+    if !items
+        .iter()
+        .map(|item| &item.piece)
+        .eq(formatted_items.iter().map(|item| &item.piece))
+    {
+        let loc = make_loc(file_hash, 0, 0);
+        return Err(Diagnostics::from(vec![diag!(
+            Bug::Formatting,
+            (
+                loc,
+                "Formatting changed more than the whitespace of the source"
+            )
+        )]));
+    }
+    Ok(formatted)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Piece<'a> {
+    Token(Tok, &'a str),
+    Comment(&'a str),
+}
+
+/// A token or comment, with the whitespace before it in the source.
+struct Item<'a> {
+    piece: Piece<'a>,
+    newlines_before: usize,
+    space_before: bool,
+}
+
+fn tokenize(source: &str, file_hash: FileHash) -> Result<Vec<Item>, Box<Diagnostic>> {
+    let mut lexer = Lexer::new(source, file_hash);
+    let mut items = vec![];
+    loop {
+        lexer.advance()?;
+        let gap = &source[lexer.previous_end_loc()..lexer.start_loc()];
+        let (newlines_before, space_before) = split_comments(gap, &mut items);
+        if lexer.peek() == Tok::EOF {
+            return Ok(items);
+        }
+        items.push(Item {
+            // `&mut` is lexed with the space after it.
+            piece: Piece::Token(lexer.peek(), lexer.content().trim_end()),
+            newlines_before,
+            space_before,
+        });
+    }
+}
+
+/// Adds the comments in whitespace between two tokens to the items, and returns the whitespace
+/// after the last one.
+fn split_comments<'a>(mut gap: &'a str, items: &mut Vec<Item<'a>>) -> (usize, bool) {
+    loop {
+        let rest = gap.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        let whitespace = &gap[..gap.len() - rest.len()];
+        let newlines_before = whitespace.matches('\n').count();
+        let space_before = !whitespace.is_empty();
+        if rest.is_empty() {
+            return (newlines_before, space_before);
+        }
+
+        let len = comment_len(rest);
+        items.push(Item {
+            piece: Piece::Comment(rest[..len].trim_end()),
+            newlines_before,
+            space_before,
+        });
+        gap = &rest[len..];
+    }
+}
+
+/// Returns the length of the comment the text starts with. Block comments can be nested, and are
+/// known to be closed since the lexer accepted them.
+fn comment_len(text: &str) -> usize {
+    if text.starts_with("//") {
+        return text.find('\n').unwrap_or(text.len());
+    }
+    let mut depth = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        if text[pos..].starts_with("/*") {
+            depth += 1;
+            pos += 2;
+        } else if text[pos..].starts_with("*/") {
+            depth -= 1;
+            pos += 2;
+            if depth == 0 {
+                return pos;
+            }
+        } else {
+            pos += 1;
+        }
+    }
+    text.len()
+}
+
+struct OpenBracket {
+    line: usize,
+    indented: bool,
+    /// Whether there are no spaces inside the brackets, like in `use 0x1::m::{Self, T}`.
+    tight: bool,
+}
+
+struct Printer<'a, 'o> {
+    options: &'o FormatOptions,
+    out: String,
+    line: usize,
+    indent: usize,
+    brackets: Vec<OpenBracket>,
+    /// Last token printed, comments aside.
+    last_token: Option<Tok>,
+    /// The last piece printed, if it was a token.
+    last_piece: Option<(Tok, &'a str)>,
+    /// First token of the statement or item being printed.
+    first_token: Option<&'a str>,
+    /// Whether the last token printed is a `<`, `>` or `>>` known to be an operator rather than
+    /// a bracket of type arguments.
+    after_operator: bool,
+}
+
+impl<'a, 'o> Printer<'a, 'o> {
+    fn new(options: &'o FormatOptions) -> Self {
+        Self {
+            options,
+            out: String::new(),
+            line: 0,
+            indent: 0,
+            brackets: vec![],
+            last_token: None,
+            last_piece: None,
+            first_token: None,
+            after_operator: false,
+        }
+    }
+
+    fn print(mut self, items: &[Item<'a>]) -> String {
+        for (i, item) in items.iter().enumerate() {
+            let closing = matches!(
+                item.piece,
+                Piece::Token(Tok::RBrace | Tok::RParen | Tok::RBracket, _)
+            );
+            let closed_bracket = if closing { self.brackets.pop() } else { None };
+            if let Some(OpenBracket { indented: true, .. }) = closed_bracket {
+                self.indent -= 1;
+            }
+
+            if i > 0 && item.newlines_before > 0 {
+                let after_open_bracket = matches!(
+                    self.last_piece,
+                    Some((Tok::LBrace | Tok::LParen | Tok::LBracket, _))
+                );
+                let blank_line = item.newlines_before > 1 && !after_open_bracket && !closing;
+                self.new_line(item, blank_line, closing);
+            } else if i > 0 && self.space_between(item, closed_bracket.as_ref()) {
+                self.out.push(' ');
+            }
+
+            match item.piece {
+                Piece::Token(tok, content) => {
+                    self.out.push_str(content);
+                    if matches!(tok, Tok::LBrace | Tok::LParen | Tok::LBracket) {
+                        self.brackets.push(OpenBracket {
+                            line: self.line,
+                            indented: false,
+                            tight: self.last_token == Some(Tok::ColonColon),
+                        });
+                    }
+                    if matches!(
+                        self.last_token,
+                        None | Some(Tok::Semicolon | Tok::LBrace | Tok::RBrace)
+                    ) {
+                        self.first_token = Some(content);
+                    }
+                    self.after_operator =
+                        matches!(tok, Tok::Less | Tok::Greater | Tok::GreaterGreater)
+                            && self.last_token.map_or(false, ends_operand);
+                    self.last_token = Some(tok);
+                    self.last_piece = Some((tok, content));
+                },
+                Piece::Comment(content) => {
+                    self.out.push_str(content);
+                    self.line += content.matches('\n').count();
+                    self.last_piece = None;
+                },
+            }
+        }
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.out
+    }
+
+    fn new_line(&mut self, item: &Item, blank_line: bool, closing: bool) {
+        self.out.push('\n');
+        if blank_line {
+            self.out.push('\n');
+        }
+
+        // The lines after a bracket are indented until it's closed, if it's left open at the end
+        // of its line.
+        let mut continuation =
+            !closing && !matches!(item.piece, Piece::Token(Tok::LBrace | Tok::Acquires, _));
+        if let Some(bracket) = self.brackets.last_mut() {
+            if !bracket.indented && bracket.line == self.line && !closing {
+                bracket.indented = true;
+                self.indent += 1;
+                continuation = false;
+            }
+        }
+        // Otherwise, the continuation of a statement or an expression is indented once more. Lists
+        // are continued over lines at the same indentation, but for the properties of pragmas.
+        let pragma = self.first_token == Some("pragma");
+        continuation &= !matches!(
+            self.last_token,
+            None | Some(
+                Tok::Semicolon
+                    | Tok::LBrace
+                    | Tok::RBrace
+                    | Tok::LParen
+                    | Tok::LBracket
+                    | Tok::RBracket
+            )
+        ) && (self.last_token != Some(Tok::Comma) || pragma);
+        self.line += 1;
+
+        let indent = self.indent + continuation as usize;
+        self.out
+            .extend(std::iter::repeat(' ').take(indent * self.options.indent_width));
+    }
+
+    /// Whether there is a space between the last piece printed and the next one, on the same line.
+    fn space_between(&self, item: &Item, closed_bracket: Option<&OpenBracket>) -> bool {
+        match (&self.last_piece, item.piece) {
+            (Some((Tok::LBrace, _)), _) if self.brackets.last().map_or(false, |b| b.tight) => false,
+            (_, Piece::Token(Tok::RBrace, _)) if closed_bracket.map_or(false, |b| b.tight) => false,
+            (Some(_), Piece::Token(..)) if self.after_operator => true,
+            (Some((prev, prev_content)), Piece::Token(next, _)) => {
+                token_spacing(*prev, prev_content, next).unwrap_or(item.space_before)
+            },
+            (_, Piece::Comment(content)) => item.space_before || content.starts_with("//"),
+            (None, _) => item.space_before,
+        }
+    }
+}
+
+/// Words starting spec conditions and other spec block members, which aren't keywords.
+const SPEC_WORDS: &[&str] = &[
+    "aborts_if",
+    "aborts_with",
+    "apply",
+    "assert",
+    "assume",
+    "decreases",
+    "emits",
+    "ensures",
+    "include",
+    "modifies",
+    "pragma",
+    "requires",
+    "succeeds_if",
+];
+
+/// Whether there is a space between two tokens on the same line, or `None` if it depends on the
+/// use of the tokens, in which case the spacing of the source is kept.
+fn token_spacing(prev: Tok, prev_content: &str, next: Tok) -> Option<bool> {
+    use Tok::*;
+    let spacing = match (prev, next) {
+        (LParen | LBracket | NumSign | AtSign | ColonColon | Period, _) => false,
+        (_, RParen | RBracket | Comma | Semicolon | Colon | ColonColon | Period) => false,
+        (Comma | Semicolon | Colon | AmpMut, _) => true,
+        (LBrace, RBrace) => false,
+        (LBrace, _) | (_, LBrace | RBrace) => true,
+        (
+            Equal
+            | EqualEqual
+            | ExclaimEqual
+            | LessEqual
+            | GreaterEqual
+            | AmpAmp
+            | PipePipe
+            | EqualEqualGreater
+            | LessEqualEqualGreater
+            | Plus
+            | Slash
+            | Percent
+            | Caret
+            | LessLess,
+            _,
+        )
+        | (
+            _,
+            Equal
+            | EqualEqual
+            | ExclaimEqual
+            | LessEqual
+            | GreaterEqual
+            | AmpAmp
+            | PipePipe
+            | EqualEqualGreater
+            | LessEqualEqualGreater
+            | Plus
+            | Slash
+            | Percent
+            | Caret
+            | LessLess,
+        ) => true,
+        (If | While | Invariant, LParen | LBracket) => true,
+        (Identifier, LParen | LBracket) if SPEC_WORDS.contains(&prev_content) => true,
+        // Calls, macro calls and indexing.
+        (Identifier, LParen | LBracket) | (Exclaim, LParen) => false,
+        (Exclaim, _) => false,
+        (prev, next) if ends_word(prev) && starts_word(next) => true,
+        // After type arguments as well as after the operators.
+        (Greater | GreaterGreater, next) if starts_word(next) => true,
+        // Type arguments only follow names, so these are operators.
+        (prev, Less | Greater | GreaterGreater) if ends_operand(prev) => true,
+        _ => return None,
+    };
+    Some(spacing)
+}
+
+fn starts_word(tok: Tok) -> bool {
+    use Tok::*;
+    !matches!(
+        tok,
+        EOF | Exclaim
+            | ExclaimEqual
+            | Percent
+            | Amp
+            | AmpAmp
+            | AmpMut
+            | LParen
+            | RParen
+            | LBracket
+            | RBracket
+            | Star
+            | Plus
+            | Comma
+            | Minus
+            | Period
+            | PeriodPeriod
+            | Slash
+            | Colon
+            | ColonColon
+            | Semicolon
+            | Less
+            | LessEqual
+            | LessLess
+            | Equal
+            | EqualEqual
+            | EqualEqualGreater
+            | LessEqualEqualGreater
+            | Greater
+            | GreaterEqual
+            | GreaterGreater
+            | Caret
+            | LBrace
+            | Pipe
+            | PipePipe
+            | RBrace
+            | NumSign
+            | AtSign
+    )
+}
+
+fn ends_word(tok: Tok) -> bool {
+    starts_word(tok) || matches!(tok, Tok::RParen | Tok::RBracket | Tok::RBrace)
+}
+
+/// Whether the token ends an operand which can't take type arguments, e.g., a literal or a call.
+fn ends_operand(tok: Tok) -> bool {
+    matches!(
+        tok,
+        Tok::NumValue | Tok::NumTypedValue | Tok::ByteStringValue | Tok::RParen | Tok::RBracket
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> String {
+        let formatted = format_source(source, &FormatOptions::default()).unwrap();
+        assert_eq!(
+            format_source(&formatted, &FormatOptions::default()).unwrap(),
+            formatted,
+            "formatting isn't idempotent"
+        );
+        formatted
+    }
+
+    #[test]
+    fn test_spacing_and_indentation() {
+        let source = r#"
+module 0x1::m{
+use std::vector::{ Self,empty};
+
+
+  struct S<T:copy+drop>has copy,drop{f:vector<T>}
+        fun f(x:&mut u64,s:S<u8>):u64 acquires R{
+    let v=vector[1,2];
+  if(x==0&&!vector::is_empty(&v)){*x=*x+1}else{ assert!(s.f[0]<1<<3,0)};
+  S<u8>{f:empty()};
+        *x
+    }
+}
+"#;
+        let expected = r#"module 0x1::m {
+    use std::vector::{Self, empty};
+
+    struct S<T: copy + drop> has copy, drop { f: vector<T> }
+    fun f(x: &mut u64, s: S<u8>): u64 acquires R {
+        let v = vector[1, 2];
+        if (x == 0 && !vector::is_empty(&v)) { *x = *x + 1 } else { assert!(s.f[0] < 1 << 3, 0) };
+        S<u8> { f: empty() };
+        *x
+    }
+}
+"#;
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let source =
+            "module 0x1::m { fun f(v: vector<u64>): bool { v[0]<1 && g()>2 && 3>>1>(0) } }";
+        let expected =
+            "module 0x1::m { fun f(v: vector<u64>): bool { v[0] < 1 && g() > 2 && 3 >> 1 > (0) } }\n";
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_comments_and_line_breaks() {
+        let source = r#"
+// Header.
+module 0x1::m {
+    /// Doc comment.
+  fun f(
+  a: u64, // Trailing comment.
+        /* Block comment. */ b: u64,
+  ): u64 {
+  let c = a +
+  b;
+     /* Nested /* block */
+        comment. */
+
+
+        c
+    }
+}
+"#;
+        let expected = r#"// Header.
+module 0x1::m {
+    /// Doc comment.
+    fun f(
+        a: u64, // Trailing comment.
+        /* Block comment. */ b: u64,
+    ): u64 {
+        let c = a +
+            b;
+        /* Nested /* block */
+        comment. */
+
+        c
+    }
+}
+"#;
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_specs() {
+        let source = r#"
+spec 0x1::m {
+    spec f(x: u64): u64 {
+        pragma opaque = true,
+            verify = false;
+    aborts_if !exists<R>(@0x1) with 1;
+        ensures[abstract] result == old(x)+1;
+        invariant forall i in 0..len(v): v[i] > 0;
+    }
+}
+"#;
+        let expected = r#"spec 0x1::m {
+    spec f(x: u64): u64 {
+        pragma opaque = true,
+            verify = false;
+        aborts_if !exists<R>(@0x1) with 1;
+        ensures [abstract] result == old(x) + 1;
+        invariant forall i in 0..len(v): v[i] > 0;
+    }
+}
+"#;
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_invalid_source() {
+        assert!(format_source("module 0x1::m { /* unclosed }", &FormatOptions::default()).is_err());
+        assert!(format_source("module 0x1::m { $ }", &FormatOptions::default()).is_err());
+        assert_eq!(format(""), "");
+        assert_eq!(format("\n\n// Only a comment.\n\n"), "// Only a comment.\n");
+    }
+}
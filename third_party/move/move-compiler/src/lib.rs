@@ -13,6 +13,7 @@ pub mod command_line;
 pub mod compiled_unit;
 pub mod diagnostics;
 pub mod expansion;
+pub mod formatter;
 pub mod hlir;
 pub mod inlining;
 pub mod interface_generator;
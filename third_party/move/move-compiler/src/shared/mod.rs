@@ -437,6 +437,8 @@ pub mod known_attributes {
         TestOnly,
         // Is a test that will be run
         Test,
+        // Is a test that will be run with randomly generated arguments
+        RandomTest,
         // This test is expected to fail
         ExpectedFailure,
     }
@@ -473,6 +475,7 @@ pub mod known_attributes {
         pub fn resolve(attribute_str: impl AsRef<str>) -> Option<Self> {
             Some(match attribute_str.as_ref() {
                 TestingAttribute::TEST => Self::Testing(TestingAttribute::Test),
+                TestingAttribute::RANDOM_TEST => Self::Testing(TestingAttribute::RandomTest),
                 TestingAttribute::TEST_ONLY => Self::Testing(TestingAttribute::TestOnly),
                 TestingAttribute::EXPECTED_FAILURE => {
                     Self::Testing(TestingAttribute::ExpectedFailure)
@@ -512,6 +515,7 @@ pub mod known_attributes {
         pub const MAJOR_STATUS_NAME: &'static str = "major_status";
         pub const MINOR_STATUS_NAME: &'static str = "minor_status";
         pub const OUT_OF_GAS_NAME: &'static str = "out_of_gas";
+        pub const RANDOM_TEST: &'static str = "random_test";
         pub const TEST: &'static str = "test";
         pub const TEST_ONLY: &'static str = "test_only";
         pub const VECTOR_ERROR_NAME: &'static str = "vector_error";
@@ -519,6 +523,7 @@ pub mod known_attributes {
         pub const fn name(&self) -> &str {
             match self {
                 Self::Test => Self::TEST,
+                Self::RandomTest => Self::RANDOM_TEST,
                 Self::TestOnly => Self::TEST_ONLY,
                 Self::ExpectedFailure => Self::EXPECTED_FAILURE,
             }
@@ -543,7 +548,7 @@ pub mod known_attributes {
                 Lazy::new(|| IntoIterator::into_iter([AttributePosition::Function]).collect());
             match self {
                 TestingAttribute::TestOnly => &TEST_ONLY_POSITIONS,
-                TestingAttribute::Test => &TEST_POSITIONS,
+                TestingAttribute::Test | TestingAttribute::RandomTest => &TEST_POSITIONS,
                 TestingAttribute::ExpectedFailure => &EXPECTED_FAILURE_POSITIONS,
             }
         }
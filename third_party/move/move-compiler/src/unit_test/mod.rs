@@ -8,8 +8,11 @@ use crate::{
     shared::NumericalAddress,
 };
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
-    value::MoveValue, vm_status::StatusCode,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use std::{collections::BTreeMap, fmt};
 
@@ -36,6 +39,9 @@ pub struct TestCase {
     pub test_name: TestName,
     pub arguments: Vec<MoveValue>,
     pub expected_failure: Option<ExpectedFailure>,
+    // the names and layouts of the parameters of a #[random_test], for which the test runner
+    // generates the arguments of each run
+    pub random_arguments: Option<Vec<(String, MoveTypeLayout)>>,
 }
 
 #[derive(Debug, Clone)]
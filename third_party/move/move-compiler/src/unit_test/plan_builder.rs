@@ -8,6 +8,8 @@ use crate::{
     expansion::ast::{
         self as E, Address, Attribute, AttributeValue, ModuleAccess_, ModuleIdent, ModuleIdent_,
     },
+    hlir::ast as H,
+    naming::ast::BuiltinTypeName_,
     parser::ast::ConstantName,
     shared::{
         known_attributes::{KnownAttribute, TestingAttribute},
@@ -17,8 +19,11 @@ use crate::{
     unit_test::{ExpectedFailure, ExpectedMoveError, ModuleTestPlan, TestCase},
 };
 use move_core_types::{
-    account_address::AccountAddress as MoveAddress, language_storage::ModuleId, u256::U256,
-    value::MoveValue, vm_status::StatusCode,
+    account_address::AccountAddress as MoveAddress,
+    language_storage::ModuleId,
+    u256::U256,
+    value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
@@ -124,9 +129,22 @@ fn build_test_info<'func>(
     const IN_THIS_TEST_MSG: &str = "Error found in this test";
 
     let test_attribute_opt = get_attrs(TestingAttribute::Test);
+    let random_test_attribute_opt = get_attrs(TestingAttribute::RandomTest);
     let abort_attribute_opt = get_attrs(TestingAttribute::ExpectedFailure);
     let test_only_attribute_opt = get_attrs(TestingAttribute::TestOnly);
 
+    if let Some(random_test_attribute) = random_test_attribute_opt {
+        return build_random_test_info(
+            context,
+            fn_loc,
+            fn_name,
+            function,
+            random_test_attribute,
+            test_attribute_opt.or(test_only_attribute_opt),
+            abort_attribute_opt,
+        );
+    }
+
     let test_attribute = match test_attribute_opt {
         None => {
             // expected failures cannot be annotated on non-#[test] functions
@@ -184,6 +202,111 @@ fn build_test_info<'func>(
         test_name: fn_name.to_string(),
         arguments,
         expected_failure,
+        random_arguments: None,
+    })
+}
+
+// Builds the test case of a #[random_test] function, whose arguments are generated by the test
+// runner instead of being assigned in the attribute.
+fn build_random_test_info(
+    context: &mut Context,
+    fn_loc: Loc,
+    fn_name: &str,
+    function: &G::Function,
+    random_test_attribute: &E::Attribute,
+    other_test_attribute_opt: Option<&E::Attribute>,
+    abort_attribute_opt: Option<&E::Attribute>,
+) -> Option<TestCase> {
+    const IN_THIS_TEST_MSG: &str = "Error found in this test";
+
+    // A #[random_test] function cannot also be annotated #[test] or #[test_only]
+    if let Some(other_test_attribute) = other_test_attribute_opt {
+        let msg = format!(
+            "Function annotated as both #[{}] and #[{}]. You need to declare it as either one or \
+             the other",
+            TestingAttribute::RANDOM_TEST,
+            other_test_attribute.value.attribute_name().value,
+        );
+        context.env.add_diag(diag!(
+            Attributes::InvalidUsage,
+            (other_test_attribute.loc, msg),
+            (random_test_attribute.loc, "Previously annotated here"),
+            (fn_loc, IN_THIS_TEST_MSG),
+        ));
+        return None;
+    }
+
+    if !matches!(random_test_attribute.value, E::Attribute_::Name(_)) {
+        let msg = format!(
+            "Unexpected arguments in #[{}]. The values of all of the parameters of the test are \
+             generated",
+            TestingAttribute::RANDOM_TEST
+        );
+        context.env.add_diag(diag!(
+            Attributes::InvalidTest,
+            (random_test_attribute.loc, msg),
+            (fn_loc, IN_THIS_TEST_MSG),
+        ));
+    }
+
+    let mut random_arguments = Vec::new();
+    for (var, ty) in &function.signature.parameters {
+        match random_argument_layout(ty) {
+            Some(layout) => random_arguments.push((var.value().to_string(), layout)),
+            None => {
+                let msg = "Unsupported parameter type in random test. Only values of primitive, \
+                           vector and address types can be generated";
+                context.env.add_diag(diag!(
+                    Attributes::InvalidTest,
+                    (ty.loc, msg),
+                    (fn_loc, IN_THIS_TEST_MSG),
+                ))
+            },
+        }
+    }
+
+    let expected_failure = match abort_attribute_opt {
+        None => None,
+        Some(abort_attribute) => parse_failure_attribute(context, abort_attribute),
+    };
+
+    Some(TestCase {
+        test_name: fn_name.to_string(),
+        arguments: vec![],
+        expected_failure,
+        random_arguments: Some(random_arguments),
+    })
+}
+
+// Returns the layout of the values generated for a parameter of a random test, if values of its
+// type can be generated.
+fn random_argument_layout(sp!(_, ty_): &H::SingleType) -> Option<MoveTypeLayout> {
+    match ty_ {
+        H::SingleType_::Base(bt) => random_base_type_layout(bt),
+        H::SingleType_::Ref(_, _) => None,
+    }
+}
+
+fn random_base_type_layout(sp!(_, bt_): &H::BaseType) -> Option<MoveTypeLayout> {
+    let (builtin, ty_args) = match bt_ {
+        H::BaseType_::Apply(_, sp!(_, H::TypeName_::Builtin(sp!(_, builtin))), ty_args) => {
+            (builtin, ty_args)
+        },
+        _ => return None,
+    };
+    Some(match builtin {
+        BuiltinTypeName_::Address => MoveTypeLayout::Address,
+        BuiltinTypeName_::U8 => MoveTypeLayout::U8,
+        BuiltinTypeName_::U16 => MoveTypeLayout::U16,
+        BuiltinTypeName_::U32 => MoveTypeLayout::U32,
+        BuiltinTypeName_::U64 => MoveTypeLayout::U64,
+        BuiltinTypeName_::U128 => MoveTypeLayout::U128,
+        BuiltinTypeName_::U256 => MoveTypeLayout::U256,
+        BuiltinTypeName_::Bool => MoveTypeLayout::Bool,
+        BuiltinTypeName_::Vector => {
+            MoveTypeLayout::Vector(Box::new(random_base_type_layout(ty_args.first()?)?))
+        },
+        BuiltinTypeName_::Signer | BuiltinTypeName_::Fun => return None,
     })
 }
 
@@ -0,0 +1,27 @@
+// #[random_test] functions can only have parameters whose values can be generated, and which are
+// not assigned in the attribute
+module 0x1::M {
+    struct S has drop {}
+
+    #[random_test]
+    fun signer_param(
+        _s: signer,
+    ) { }
+
+    #[random_test]
+    fun struct_param(
+        _s: S,
+    ) { }
+
+    #[random_test]
+    fun ref_param(
+        _x: &u64,
+    ) { }
+
+    #[random_test(_x = 0)]
+    fun assigned_param(_x: u64) { }
+
+    #[random_test]
+    #[test]
+    fun both_tests() { }
+}
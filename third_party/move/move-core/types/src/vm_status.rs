@@ -526,8 +526,9 @@ pub enum StatusCode {
     MULTISIG_TRANSACTION_NOT_FOUND = 33,
     MULTISIG_TRANSACTION_INSUFFICIENT_APPROVALS = 34,
     MULTISIG_TRANSACTION_PAYLOAD_DOES_NOT_MATCH_HASH = 35,
+    // Transactions with a separate fee payer are not enabled on chain
+    FEE_PAYER_NOT_ENABLED = 36,
     // Reserved error code for future use
-    RESERVED_VALIDATION_ERROR_1 = 36,
     RESERVED_VALIDATION_ERROR_2 = 37,
     RESERVED_VALIDATION_ERROR_3 = 38,
     RESERVED_VALIDATION_ERROR_4 = 39,
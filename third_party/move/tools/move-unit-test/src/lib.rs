@@ -4,6 +4,7 @@
 
 pub mod cargo_runner;
 pub mod extensions;
+pub mod random_arguments;
 pub mod test_reporter;
 pub mod test_runner;
 
@@ -30,6 +31,9 @@ use std::{
 /// The default value bounding the amount of gas consumed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 1_000_000;
 
+/// The default number of runs of a random test.
+const DEFAULT_RANDOM_ITERATIONS: usize = 100;
+
 #[derive(Debug, Parser, Clone)]
 #[clap(author, version, about)]
 pub struct UnitTestingConfig {
@@ -115,6 +119,19 @@ pub struct UnitTestingConfig {
     #[clap(short = 'v', long = "verbose")]
     pub report_writeset: bool,
 
+    /// Number of runs of each #[random_test], with newly generated arguments each time
+    #[clap(
+        name = "random_iterations",
+        default_value = "100",
+        long = "random_iterations"
+    )]
+    pub random_iterations: usize,
+
+    /// Seed of the generation of the arguments of #[random_test]s, to reproduce a failure. A
+    /// random seed is picked if none is given.
+    #[clap(name = "seed", long = "seed")]
+    pub seed: Option<u64>,
+
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
     #[cfg(feature = "evm-backend")]
@@ -148,6 +165,8 @@ impl UnitTestingConfig {
             list: false,
             named_address_values: vec![],
             report_writeset: false,
+            random_iterations: DEFAULT_RANDOM_ITERATIONS,
+            seed: None,
 
             #[cfg(feature = "evm-backend")]
             evm: false,
@@ -249,6 +268,8 @@ impl UnitTestingConfig {
             native_function_table,
             cost_table,
             self.report_writeset,
+            self.random_iterations,
+            self.seed.unwrap_or_else(rand::random),
             #[cfg(feature = "evm-backend")]
             self.evm,
         )
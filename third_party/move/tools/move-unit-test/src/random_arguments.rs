@@ -0,0 +1,184 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation and shrinking of the arguments of `#[random_test]` functions.
+
+use move_core_types::{
+    account_address::AccountAddress,
+    u256::U256,
+    value::{MoveTypeLayout, MoveValue},
+};
+use rand::{
+    distributions::{uniform::SampleUniform, Distribution, Standard},
+    Rng,
+};
+use std::ops::{Shr, Sub};
+
+/// The maximal length of the generated vectors
+const MAX_VECTOR_LENGTH: usize = 16;
+
+/// Generates the arguments of one run of a random test, given the layouts of its parameters.
+pub fn generate_arguments<'a, R: Rng>(
+    rng: &mut R,
+    layouts: impl IntoIterator<Item = &'a MoveTypeLayout>,
+) -> Vec<MoveValue> {
+    layouts
+        .into_iter()
+        .map(|layout| generate_value(rng, layout))
+        .collect()
+}
+
+/// Generates a value of the given layout, which must be a primitive, vector or address layout.
+pub fn generate_value<R: Rng>(rng: &mut R, layout: &MoveTypeLayout) -> MoveValue {
+    match layout {
+        MoveTypeLayout::Bool => MoveValue::Bool(rng.gen()),
+        MoveTypeLayout::U8 => MoveValue::U8(generate_integer(rng, u8::MAX)),
+        MoveTypeLayout::U16 => MoveValue::U16(generate_integer(rng, u16::MAX)),
+        MoveTypeLayout::U32 => MoveValue::U32(generate_integer(rng, u32::MAX)),
+        MoveTypeLayout::U64 => MoveValue::U64(generate_integer(rng, u64::MAX)),
+        MoveTypeLayout::U128 => MoveValue::U128(generate_integer(rng, u128::MAX)),
+        MoveTypeLayout::U256 => MoveValue::U256(generate_integer(rng, U256::max_value())),
+        MoveTypeLayout::Address => {
+            // well-known addresses are as likely to be generated as arbitrary ones
+            let address = if rng.gen() {
+                u256_to_address(U256::from(rng.gen_range(0..=16u8)))
+            } else {
+                let mut bytes = [0u8; AccountAddress::LENGTH];
+                rng.fill(&mut bytes[..]);
+                AccountAddress::new(bytes)
+            };
+            MoveValue::Address(address)
+        },
+        MoveTypeLayout::Vector(elem_layout) => {
+            let len = rng.gen_range(0..=MAX_VECTOR_LENGTH);
+            MoveValue::Vector((0..len).map(|_| generate_value(rng, elem_layout)).collect())
+        },
+        MoveTypeLayout::Signer | MoveTypeLayout::Struct(_) => {
+            unreachable!("values of layout {} cannot be generated", layout)
+        },
+    }
+}
+
+/// Generates an integer, favoring the edge cases and small values, which are the most likely to
+/// make a test fail.
+fn generate_integer<R: Rng, T>(rng: &mut R, max: T) -> T
+where
+    T: From<u8> + PartialOrd + SampleUniform,
+    Standard: Distribution<T>,
+{
+    match rng.gen_range(0..8) {
+        0 => T::from(0),
+        1 => T::from(1),
+        2 => max,
+        3..=5 => rng.gen_range(T::from(0)..=T::from(u8::MAX)),
+        _ => rng.gen(),
+    }
+}
+
+/// Returns the candidates to replace the arguments of a failing run with, which are obtained by
+/// shrinking one argument at a time. The simplest candidates come first.
+pub fn shrink_arguments(arguments: &[MoveValue]) -> Vec<Vec<MoveValue>> {
+    let mut candidates = vec![];
+    for (idx, argument) in arguments.iter().enumerate() {
+        for value in shrink_value(argument) {
+            let mut candidate = arguments.to_vec();
+            candidate[idx] = value;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Returns the values simpler than the given one, the simplest first.
+pub fn shrink_value(value: &MoveValue) -> Vec<MoveValue> {
+    match value {
+        MoveValue::Bool(b) => {
+            if *b {
+                vec![MoveValue::Bool(false)]
+            } else {
+                vec![]
+            }
+        },
+        MoveValue::U8(u) => shrink_integer(*u).into_iter().map(MoveValue::U8).collect(),
+        MoveValue::U16(u) => shrink_integer(*u).into_iter().map(MoveValue::U16).collect(),
+        MoveValue::U32(u) => shrink_integer(*u).into_iter().map(MoveValue::U32).collect(),
+        MoveValue::U64(u) => shrink_integer(*u).into_iter().map(MoveValue::U64).collect(),
+        MoveValue::U128(u) => shrink_integer(*u)
+            .into_iter()
+            .map(MoveValue::U128)
+            .collect(),
+        MoveValue::U256(u) => shrink_integer(*u)
+            .into_iter()
+            .map(MoveValue::U256)
+            .collect(),
+        MoveValue::Address(a) => shrink_integer(address_to_u256(a))
+            .into_iter()
+            .map(|u| MoveValue::Address(u256_to_address(u)))
+            .collect(),
+        MoveValue::Vector(elems) => {
+            let mut candidates = vec![];
+            if !elems.is_empty() {
+                candidates.push(vec![]);
+            }
+            if elems.len() > 2 {
+                let mid = elems.len() / 2;
+                candidates.push(elems[..mid].to_vec());
+                candidates.push(elems[mid..].to_vec());
+            }
+            for idx in 0..elems.len() {
+                let mut candidate = elems.clone();
+                candidate.remove(idx);
+                candidates.push(candidate);
+            }
+            for (idx, elem) in elems.iter().enumerate() {
+                for value in shrink_value(elem) {
+                    let mut candidate = elems.clone();
+                    candidate[idx] = value;
+                    candidates.push(candidate);
+                }
+            }
+            candidates.into_iter().map(MoveValue::Vector).collect()
+        },
+        MoveValue::Signer(_) | MoveValue::Struct(_) => vec![],
+    }
+}
+
+/// Returns zero, followed by the integers between the given one and zero, from the furthest to the
+/// closest, with a distance halving each time. Taking the first candidate which still makes a test
+/// fail quickly converges to the smallest failing integer.
+fn shrink_integer<T>(value: T) -> Vec<T>
+where
+    T: Copy + PartialEq + From<u8> + Sub<Output = T> + Shr<u8, Output = T>,
+{
+    let zero = T::from(0);
+    let one = T::from(1);
+    if value == zero {
+        return vec![];
+    }
+    let mut candidates = vec![zero];
+    let mut shift = 1;
+    loop {
+        let delta = value >> shift;
+        if delta == zero {
+            break;
+        }
+        candidates.push(value - delta);
+        if delta == one {
+            break;
+        }
+        shift += 1;
+    }
+    candidates
+}
+
+fn address_to_u256(address: &AccountAddress) -> U256 {
+    let mut bytes = address.into_bytes();
+    bytes.reverse();
+    U256::from_le_bytes(&bytes)
+}
+
+fn u256_to_address(value: U256) -> AccountAddress {
+    let mut bytes = value.to_le_bytes();
+    bytes.reverse();
+    AccountAddress::new(bytes)
+}
@@ -58,6 +58,8 @@ pub struct TestFailure {
     pub vm_error: Option<VMError>,
     pub failure_reason: FailureReason,
     pub storage_state: Option<String>,
+    // the arguments a random test failed with
+    pub counterexample: Option<String>,
 }
 
 #[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
@@ -155,6 +157,7 @@ impl TestFailure {
             vm_error,
             failure_reason,
             storage_state,
+            counterexample: None,
         }
     }
 
@@ -234,6 +237,11 @@ impl TestFailure {
             },
         };
 
+        let error_string = match &self.counterexample {
+            None => error_string,
+            Some(counterexample) => format!("{}\n{}", error_string, counterexample),
+        };
+
         match &self.storage_state {
             None => error_string,
             Some(storage_state) => {
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    extensions, format_module_id,
+    extensions, format_module_id, random_arguments,
     test_reporter::{
         FailureReason, MoveError, TestFailure, TestResults, TestRunInfo, TestStatistics,
     },
@@ -17,7 +17,7 @@ use move_core_types::{
     account_address::AccountAddress,
     effects::{ChangeSet, Op},
     identifier::IdentStr,
-    value::serialize_values,
+    value::{serialize_values, MoveTypeLayout, MoveValue},
     vm_status::StatusCode,
 };
 use move_resource_viewer::MoveValueAnnotator;
@@ -29,8 +29,14 @@ use move_vm_test_utils::{
     gas_schedule::{zero_cost_schedule, CostTable, Gas, GasCost, GasStatus},
     InMemoryStorage,
 };
+use rand::{rngs::StdRng, SeedableRng};
 use rayon::prelude::*;
-use std::{io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    io::Write,
+    marker::Send,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 #[cfg(feature = "evm-backend")]
 use {
     evm::{backend::MemoryVicinity, ExitReason},
@@ -38,7 +44,6 @@ use {
     move_to_yul,
     primitive_types::{H160, U256},
     std::convert::TryInto,
-    std::time::Duration,
 };
 
 /// Test state common to all tests
@@ -52,11 +57,16 @@ pub struct SharedTestingConfig {
     #[allow(dead_code)] // used by some features
     source_files: Vec<String>,
     record_writeset: bool,
+    random_iterations: usize,
+    seed: u64,
 
     #[cfg(feature = "evm-backend")]
     evm: bool,
 }
 
+/// The maximal number of runs of a random test while shrinking the arguments of a failing run
+const MAX_SHRINK_RUNS: usize = 10_000;
+
 pub struct TestRunner {
     num_threads: usize,
     testing_config: SharedTestingConfig,
@@ -132,6 +142,8 @@ impl TestRunner {
         native_function_table: Option<NativeFunctionTable>,
         cost_table: Option<CostTable>,
         record_writeset: bool,
+        random_iterations: usize,
+        seed: u64,
         #[cfg(feature = "evm-backend")] evm: bool,
     ) -> Result<Self> {
         let source_files = tests
@@ -162,6 +174,8 @@ impl TestRunner {
                 cost_table: cost_table.unwrap_or_else(unit_cost_table),
                 source_files,
                 record_writeset,
+                random_iterations,
+                seed,
                 #[cfg(feature = "evm-backend")]
                 evm,
             },
@@ -206,6 +220,13 @@ impl TestRunner {
     }
 }
 
+/// The outcome of one run of a test
+enum TestOutcome {
+    Passed(TestRunInfo),
+    Failed(TestFailure),
+    TimedOut(TestFailure),
+}
+
 // TODO: do not expose this to backend implementations
 struct TestOutput<'a, 'b, W> {
     test_plan: &'a ModuleTestPlan,
@@ -252,7 +273,7 @@ impl SharedTestingConfig {
         &self,
         test_plan: &ModuleTestPlan,
         function_name: &str,
-        test_info: &TestCase,
+        arguments: &[MoveValue],
     ) -> (
         VMResult<ChangeSet>,
         VMResult<NativeContextExtensions>,
@@ -271,7 +292,7 @@ impl SharedTestingConfig {
             &test_plan.module_id,
             IdentStr::new(function_name).unwrap(),
             vec![], // no ty args, at least for now
-            serialize_values(test_info.arguments.iter()),
+            serialize_values(arguments),
             &mut gas_meter,
         );
         let mut return_result = serialized_return_values_result.map(|res| {
@@ -309,135 +330,231 @@ impl SharedTestingConfig {
         let mut stats = TestStatistics::new();
 
         for (function_name, test_info) in &test_plan.tests {
-            let (cs_result, ext_result, exec_result, test_run_info) =
-                self.execute_via_move_vm(test_plan, function_name, test_info);
-
-            if self.record_writeset {
-                stats.test_output(
-                    function_name.to_string(),
-                    test_plan,
-                    format!("{:?}", cs_result),
-                );
+            let (outcome, writeset) = match &test_info.random_arguments {
+                None => self.exec_test(test_plan, function_name, test_info, &test_info.arguments),
+                Some(parameters) => {
+                    self.exec_random_test(test_plan, function_name, test_info, parameters)
+                },
+            };
+
+            if let Some(writeset) = writeset {
+                stats.test_output(function_name.to_string(), test_plan, writeset);
             }
 
-            let save_session_state = || {
-                if self.save_storage_state_on_failure {
-                    cs_result.ok().and_then(|changeset| {
-                        ext_result.ok().and_then(|extensions| {
-                            print_resources_and_extensions(
-                                &changeset,
-                                extensions,
-                                &self.starting_storage_state,
-                            )
-                            .ok()
-                        })
+            match outcome {
+                TestOutcome::Passed(test_run_info) => {
+                    output.pass(function_name);
+                    stats.test_success(test_run_info, test_plan);
+                },
+                TestOutcome::Failed(test_failure) => {
+                    output.fail(function_name);
+                    stats.test_failure(test_failure, test_plan)
+                },
+                TestOutcome::TimedOut(test_failure) => {
+                    output.timeout(function_name);
+                    stats.test_failure(test_failure, test_plan)
+                },
+            }
+        }
+
+        stats
+    }
+
+    /// Runs a test once with the given arguments, returning its outcome along with the change set
+    /// it produced if it is recorded.
+    fn exec_test(
+        &self,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        arguments: &[MoveValue],
+    ) -> (TestOutcome, Option<String>) {
+        let (cs_result, ext_result, exec_result, test_run_info) =
+            self.execute_via_move_vm(test_plan, function_name, arguments);
+
+        let writeset = if self.record_writeset {
+            Some(format!("{:?}", cs_result))
+        } else {
+            None
+        };
+
+        let save_session_state = || {
+            if self.save_storage_state_on_failure {
+                cs_result.ok().and_then(|changeset| {
+                    ext_result.ok().and_then(|extensions| {
+                        print_resources_and_extensions(
+                            &changeset,
+                            extensions,
+                            &self.starting_storage_state,
+                        )
+                        .ok()
                     })
+                })
+            } else {
+                None
+            }
+        };
+        let outcome = match exec_result {
+            Err(err) => {
+                let actual_err =
+                    MoveError(err.major_status(), err.sub_status(), err.location().clone());
+                assert!(err.major_status() != StatusCode::EXECUTED);
+                match test_info.expected_failure.as_ref() {
+                    Some(ExpectedFailure::Expected) => TestOutcome::Passed(test_run_info),
+                    Some(ExpectedFailure::ExpectedWithError(expected_err))
+                        if expected_err == &actual_err =>
+                    {
+                        TestOutcome::Passed(test_run_info)
+                    },
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
+                        if actual_err.0 == StatusCode::ABORTED
+                            && actual_err.1.is_some()
+                            && actual_err.1.unwrap() == *code =>
+                    {
+                        TestOutcome::Passed(test_run_info)
+                    },
+                    // incorrect cases
+                    Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
+                        TestOutcome::Failed(TestFailure::new(
+                            FailureReason::wrong_error(expected_err.clone(), actual_err),
+                            test_run_info,
+                            Some(err),
+                            save_session_state(),
+                        ))
+                    },
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => {
+                        TestOutcome::Failed(TestFailure::new(
+                            FailureReason::wrong_abort_deprecated(*expected_code, actual_err),
+                            test_run_info,
+                            Some(err),
+                            save_session_state(),
+                        ))
+                    },
+                    None if err.major_status() == StatusCode::OUT_OF_GAS => {
+                        // Ran out of ticks, report a test timeout and log a test failure
+                        TestOutcome::TimedOut(TestFailure::new(
+                            FailureReason::timeout(),
+                            test_run_info,
+                            Some(err),
+                            save_session_state(),
+                        ))
+                    },
+                    None => TestOutcome::Failed(TestFailure::new(
+                        FailureReason::unexpected_error(actual_err),
+                        test_run_info,
+                        Some(err),
+                        save_session_state(),
+                    )),
+                }
+            },
+            Ok(_) => {
+                // Expected the test to fail, but it executed
+                if test_info.expected_failure.is_some() {
+                    TestOutcome::Failed(TestFailure::new(
+                        FailureReason::no_error(),
+                        test_run_info,
+                        None,
+                        save_session_state(),
+                    ))
                 } else {
-                    None
+                    // Expected the test to execute fully and it did
+                    TestOutcome::Passed(test_run_info)
                 }
-            };
-            match exec_result {
-                Err(err) => {
-                    let actual_err =
-                        MoveError(err.major_status(), err.sub_status(), err.location().clone());
-                    assert!(err.major_status() != StatusCode::EXECUTED);
-                    match test_info.expected_failure.as_ref() {
-                        Some(ExpectedFailure::Expected) => {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        },
-                        Some(ExpectedFailure::ExpectedWithError(expected_err))
-                            if expected_err == &actual_err =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        },
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
-                            if actual_err.0 == StatusCode::ABORTED
-                                && actual_err.1.is_some()
-                                && actual_err.1.unwrap() == *code =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        },
-                        // incorrect cases
-                        Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_error(expected_err.clone(), actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        },
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_abort_deprecated(
-                                        *expected_code,
-                                        actual_err,
-                                    ),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        },
-                        None if err.major_status() == StatusCode::OUT_OF_GAS => {
-                            // Ran out of ticks, report a test timeout and log a test failure
-                            output.timeout(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::timeout(),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        },
-                        None => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::unexpected_error(actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        },
-                    }
+            },
+        };
+        (outcome, writeset)
+    }
+
+    /// Runs a random test with newly generated arguments until it fails or the number of
+    /// iterations is reached. The arguments of a failing run are then shrunk to the simplest ones
+    /// which still make the test fail, which are reported along with the failure.
+    fn exec_random_test(
+        &self,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        parameters: &[(String, MoveTypeLayout)],
+    ) -> (TestOutcome, Option<String>) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut elapsed_time = Duration::ZERO;
+        let mut instructions_executed = 0;
+        let mut writeset = None;
+        for _ in 0..self.random_iterations.max(1) {
+            let arguments =
+                random_arguments::generate_arguments(&mut rng, parameters.iter().map(|(_, l)| l));
+            let (outcome, run_writeset) =
+                self.exec_test(test_plan, function_name, test_info, &arguments);
+            match outcome {
+                TestOutcome::Passed(test_run_info) => {
+                    elapsed_time += test_run_info.elapsed_time;
+                    instructions_executed += test_run_info.instructions_executed;
+                    writeset = run_writeset;
                 },
-                Ok(_) => {
-                    // Expected the test to fail, but it executed
-                    if test_info.expected_failure.is_some() {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::no_error(),
-                                test_run_info,
-                                None,
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    } else {
-                        // Expected the test to execute fully and it did
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
+                TestOutcome::Failed(_) | TestOutcome::TimedOut(_) => {
+                    return self.shrink_random_test(
+                        test_plan,
+                        function_name,
+                        test_info,
+                        parameters,
+                        arguments,
+                        (outcome, run_writeset),
+                    )
                 },
             }
         }
+        let test_run_info = TestRunInfo::new(
+            function_name.to_string(),
+            elapsed_time,
+            instructions_executed,
+        );
+        (TestOutcome::Passed(test_run_info), writeset)
+    }
 
-        stats
+    fn shrink_random_test(
+        &self,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        parameters: &[(String, MoveTypeLayout)],
+        mut arguments: Vec<MoveValue>,
+        mut failure: (TestOutcome, Option<String>),
+    ) -> (TestOutcome, Option<String>) {
+        let mut shrink_runs = 0;
+        let mut shrunk = true;
+        while shrunk {
+            shrunk = false;
+            for candidate in random_arguments::shrink_arguments(&arguments) {
+                if shrink_runs == MAX_SHRINK_RUNS {
+                    break;
+                }
+                shrink_runs += 1;
+                let result = self.exec_test(test_plan, function_name, test_info, &candidate);
+                if !matches!(result.0, TestOutcome::Passed(_)) {
+                    arguments = candidate;
+                    failure = result;
+                    shrunk = true;
+                    break;
+                }
+            }
+        }
+
+        let counterexample = format!(
+            "────── Arguments generated with seed {} ──────\n{}",
+            self.seed,
+            parameters
+                .iter()
+                .zip(&arguments)
+                .map(|((name, _), value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        let (mut outcome, writeset) = failure;
+        if let TestOutcome::Failed(test_failure) | TestOutcome::TimedOut(test_failure) =
+            &mut outcome
+        {
+            test_failure.counterexample = Some(counterexample);
+        }
+        (outcome, writeset)
     }
 
     #[cfg(feature = "evm-backend")]
@@ -90,6 +90,8 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
             .collect(),
         report_writeset: true,
         report_stacktrace_on_abort: true,
+        // random tests are run with a fixed seed so that their output is deterministic
+        seed: Some(0),
 
         ..UnitTestingConfig::default_with_bound(None)
     };
@@ -0,0 +1,37 @@
+module 0x1::M {
+    use std::vector;
+
+    #[random_test]
+    fun xor_self(x: u64) {
+        assert!(x ^ x == 0, 0);
+    }
+
+    #[random_test]
+    fun address_equality(a: address, b: address) {
+        assert!((a == b) == (b == a), 0);
+    }
+
+    #[random_test]
+    #[expected_failure(arithmetic_error, location=Self)]
+    fun always_overflows(x: u8) {
+        x + 255 + 1;
+    }
+
+    // failures are reported with the simplest arguments failing the test
+
+    #[random_test]
+    fun small_values(x: u64) {
+        if (x >= 1000) abort 1
+    }
+
+    #[random_test]
+    fun short_vectors(v: vector<u8>) {
+        if (vector::length(&v) >= 3) abort 2
+    }
+
+    #[random_test]
+    #[expected_failure]
+    fun never_aborts(x: u8) {
+        x;
+    }
+}
@@ -2,12 +2,13 @@
 
 use crate::transaction::{analyzed_transaction::StorageLocation, Transaction};
 use aptos_crypto::HashValue;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 pub type ShardId = usize;
 pub type TxnIndex = usize;
 
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct TxnIdxWithShardId {
     pub txn_index: TxnIndex,
     pub shard_id: ShardId,
@@ -22,7 +23,7 @@ impl TxnIdxWithShardId {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 /// Denotes a set of cross shard edges, which contains the set (required or dependent) transaction
 /// indices and the relevant storage locations that are conflicting.
 pub struct CrossShardEdges {
@@ -73,7 +74,7 @@ impl IntoIterator for CrossShardEdges {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Deserialize, Serialize)]
 /// Represents the dependencies of a transaction on other transactions across shards. Two types
 /// of dependencies are supported:
 /// 1. `required_edges`: The transaction depends on the execution of the transactions in the set. In this
@@ -138,7 +139,7 @@ impl CrossShardDependencies {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 /// A contiguous chunk of transactions (along with their dependencies) in a block.
 ///
 /// Each `SubBlock` represents a sequential section of transactions within a block.
@@ -218,7 +219,7 @@ impl<T> IntoIterator for SubBlock<T> {
 }
 
 // A set of sub blocks assigned to a shard.
-#[derive(Default)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct SubBlocksForShard<T> {
     pub shard_id: ShardId,
     pub sub_blocks: Vec<SubBlock<T>>,
@@ -270,7 +271,7 @@ impl<T> SubBlocksForShard<T> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TransactionWithDependencies<T> {
     pub txn: T,
     pub cross_shard_dependencies: CrossShardDependencies,
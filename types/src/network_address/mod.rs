@@ -43,6 +43,13 @@ const MAX_DNS_NAME_SIZE: usize = 255;
 ///    connection with the peer.
 /// 4. Perform a AptosNet version negotiation handshake (version 1).
 ///
+/// Validators may instead advertise a QUIC address like:
+///
+/// `/dns/example.com/udp/6180/quic/noise-ik/<x25519-pubkey>/handshake/1`
+///
+/// which establishes a QUIC connection over UDP in step 2, and then performs
+/// the same Noise IK and AptosNet handshakes as above.
+///
 /// ## Self-describing, Upgradable
 ///
 /// One key concept behind `NetworkAddress` is that it is fully self-describing,
@@ -123,6 +130,9 @@ pub enum Protocol {
     // probably need to move network wire into its own crate to avoid circular
     // dependency b/w network and types.
     Handshake(u8),
+    Udp(u16),
+    // QUIC over the preceding UDP address
+    Quic,
 }
 
 /// A minimally parsed DNS name. We don't really do any checking other than
@@ -193,11 +203,11 @@ pub enum ParseError {
     NetworkLayerMissing,
 
     #[error(
-        "NetworkAddress must start with one of Protocol::Ip4/Ip6/Dns/Dns4/Dns6 followed by TCP"
+        "NetworkAddress must start with one of Protocol::Ip4/Ip6/Dns/Dns4/Dns6 followed by TCP or UDP+QUIC"
     )]
     TransportLayerMissing,
 
-    #[error("NetworkAddress must have a NoiseIK protocol following the TCP protocol")]
+    #[error("NetworkAddress must have a NoiseIK protocol following the TCP or QUIC protocol")]
     SessionLayerMissing,
 
     #[error("NetworkAddress must have a Handshake protocol following the NoiseIK protocol")]
@@ -237,7 +247,7 @@ fn is_network_layer(p: Option<&Protocol>) -> bool {
 fn is_transport_layer(p: Option<&Protocol>) -> bool {
     use Protocol::*;
 
-    matches!(p, Some(Tcp(_)))
+    matches!(p, Some(Tcp(_)) | Some(Udp(_)))
 }
 
 fn is_session_layer(p: Option<&Protocol>, allow_empty: bool) -> bool {
@@ -282,6 +292,11 @@ impl NetworkAddress {
             if !is_transport_layer(p) {
                 return Err(ParseError::TransportLayerMissing);
             }
+
+            // UDP is only supported as the base of QUIC
+            if matches!(p, Some(Udp(_))) && !matches!(iter.next(), Some(Quic)) {
+                return Err(ParseError::TransportLayerMissing);
+            }
         }
 
         p = iter.next();
@@ -356,6 +371,7 @@ impl NetworkAddress {
     /// `"/dns4/<domain>/tcp/<port>"` or
     /// `"/dns6/<domain>/tcp/<port>"` or
     /// `"/dns/<domain>/tcp/<port>"` or
+    /// `"/ip4/<addr>/udp/<port>/quic"` (and similarly for ip6, dns, dns4 and dns6) or
     /// cfg!(test) `"/memory/<port>"`
     ///
     /// followed by transport upgrade handshake protocols:
@@ -385,14 +401,26 @@ impl NetworkAddress {
         })
     }
 
-    /// Retrieves the port from the network address
+    /// Retrieves the (TCP or UDP) port from the network address
     pub fn find_port(&self) -> Option<u16> {
         self.0.iter().find_map(|proto| match proto {
-            Protocol::Tcp(port) => Some(*port),
+            Protocol::Tcp(port) | Protocol::Udp(port) => Some(*port),
             _ => None,
         })
     }
 
+    /// Returns true iff the network address uses the QUIC transport
+    pub fn is_quic_addr(&self) -> bool {
+        self.0.iter().any(|proto| matches!(proto, Protocol::Quic))
+    }
+
+    /// Returns the `"/<ip4|ip6>/<addr>/udp/<port>/quic"` address of the given socket address
+    pub fn from_quic_socket_addr(sockaddr: SocketAddr) -> NetworkAddress {
+        let ip_proto = Protocol::from(sockaddr.ip());
+        let udp_proto = Protocol::Udp(sockaddr.port());
+        NetworkAddress::from_protocols(vec![ip_proto, udp_proto, Protocol::Quic]).unwrap()
+    }
+
     /// A temporary, hacky function to parse out the first `/noise-ik/<pubkey>` from
     /// a `NetworkAddress`. We can remove this soon, when we move to the interim
     /// "monolithic" transport model.
@@ -470,9 +498,12 @@ impl ToSocketAddrs for NetworkAddress {
     type Iter = std::vec::IntoIter<SocketAddr>;
 
     fn to_socket_addrs(&self) -> Result<Self::Iter, std::io::Error> {
-        if let Some(((ipaddr, port), _)) = parse_ip_tcp(self.as_slice()) {
+        let protos = self.as_slice();
+        if let Some(((ipaddr, port), _)) = parse_ip_tcp(protos).or_else(|| parse_ip_quic(protos)) {
             Ok(vec![SocketAddr::new(ipaddr, port)].into_iter())
-        } else if let Some(((ip_filter, dns_name, port), _)) = parse_dns_tcp(self.as_slice()) {
+        } else if let Some(((ip_filter, dns_name, port), _)) =
+            parse_dns_tcp(protos).or_else(|| parse_dns_quic(protos))
+        {
             format!("{}:{}", dns_name, port).to_socket_addrs().map(|v| {
                 v.filter(|addr| ip_filter.matches(addr.ip()))
                     .collect::<Vec<_>>()
@@ -578,6 +609,16 @@ pub fn arb_aptosnet_addr() -> impl Strategy<Value = NetworkAddress> {
             .prop_map(|(name, port)| vec![Protocol::Dns4(name), Protocol::Tcp(port)]),
         any::<(DnsName, u16)>()
             .prop_map(|(name, port)| vec![Protocol::Dns6(name), Protocol::Tcp(port)]),
+        any::<(Ipv4Addr, u16)>().prop_map(|(addr, port)| vec![
+            Protocol::Ip4(addr),
+            Protocol::Udp(port),
+            Protocol::Quic
+        ]),
+        any::<(DnsName, u16)>().prop_map(|(name, port)| vec![
+            Protocol::Dns(name),
+            Protocol::Udp(port),
+            Protocol::Quic
+        ]),
     ];
     let arb_aptosnet_protos = any::<(x25519::PublicKey, u8)>()
         .prop_map(|(pubkey, hs)| vec![Protocol::NoiseIK(pubkey), Protocol::Handshake(hs)]);
@@ -613,6 +654,8 @@ impl fmt::Display for Protocol {
                     .expect("ValidCryptoMaterialStringExt::to_encoded_string is infallible")
             ),
             Handshake(version) => write!(f, "/handshake/{}", version),
+            Udp(port) => write!(f, "/udp/{}", port),
+            Quic => write!(f, "/quic"),
         }
     }
 }
@@ -643,6 +686,8 @@ impl Protocol {
                 args.next().ok_or(ParseError::UnexpectedEnd)?,
             )?),
             "handshake" => Protocol::Handshake(parse_one(args)?),
+            "udp" => Protocol::Udp(parse_one(args)?),
+            "quic" => Protocol::Quic,
             unknown => return Err(ParseError::UnknownProtocolType(unknown.to_string())),
         };
         Ok(protocol)
@@ -819,6 +864,42 @@ pub fn parse_dns_tcp(protos: &[Protocol]) -> Option<((IpFilter, &DnsName, u16),
     }
 }
 
+/// parse the `&[Protocol]` into the `"/ip4/<addr>/udp/<port>/quic"` or
+/// `"/ip6/<addr>/udp/<port>/quic"` prefix and unparsed `&[Protocol]` suffix.
+pub fn parse_ip_quic(protos: &[Protocol]) -> Option<((IpAddr, u16), &[Protocol])> {
+    use Protocol::*;
+
+    if protos.len() < 3 {
+        return None;
+    }
+
+    let (prefix, suffix) = protos.split_at(3);
+    match prefix {
+        [Ip4(ip), Udp(port), Quic] => Some(((IpAddr::V4(*ip), *port), suffix)),
+        [Ip6(ip), Udp(port), Quic] => Some(((IpAddr::V6(*ip), *port), suffix)),
+        _ => None,
+    }
+}
+
+/// parse the `&[Protocol]` into the `"/dns/<domain>/udp/<port>/quic"`,
+/// `"/dns4/<domain>/udp/<port>/quic"`, or `"/dns6/<domain>/udp/<port>/quic"`
+/// prefix and unparsed `&[Protocol]` suffix.
+pub fn parse_dns_quic(protos: &[Protocol]) -> Option<((IpFilter, &DnsName, u16), &[Protocol])> {
+    use Protocol::*;
+
+    if protos.len() < 3 {
+        return None;
+    }
+
+    let (prefix, suffix) = protos.split_at(3);
+    match prefix {
+        [Dns(name), Udp(port), Quic] => Some(((IpFilter::Any, name, *port), suffix)),
+        [Dns4(name), Udp(port), Quic] => Some(((IpFilter::OnlyIp4, name, *port), suffix)),
+        [Dns6(name), Udp(port), Quic] => Some(((IpFilter::OnlyIp6, name, *port), suffix)),
+        _ => None,
+    }
+}
+
 pub fn parse_tcp(protos: &[Protocol]) -> Option<((String, u16), &[Protocol])> {
     use Protocol::*;
 
@@ -863,11 +944,15 @@ fn parse_aptosnet_protos(protos: &[Protocol]) -> Option<&[Protocol]> {
     // ---
     // parse_ip_tcp
     // <or> parse_dns_tcp
+    // <or> parse_ip_quic
+    // <or> parse_dns_quic
     // <or> cfg!(test) parse_memory
 
     let transport_suffix = parse_ip_tcp(protos)
         .map(|x| x.1)
         .or_else(|| parse_dns_tcp(protos).map(|x| x.1))
+        .or_else(|| parse_ip_quic(protos).map(|x| x.1))
+        .or_else(|| parse_dns_quic(protos).map(|x| x.1))
         .or_else(|| {
             if cfg!(test) {
                 parse_memory(protos).map(|x| x.1)
@@ -959,6 +1044,11 @@ mod test {
                 Dns(DnsName("example.com".to_owned())),
                 Tcp(80),
             ]),
+            ("/ip4/12.34.56.78/udp/6180/quic", vec![
+                Ip4(Ipv4Addr::new(12, 34, 56, 78)),
+                Udp(6180),
+                Quic,
+            ]),
             (&noise_addr_str, vec![
                 Dns(DnsName("example.com".to_owned())),
                 Tcp(1234),
@@ -992,6 +1082,9 @@ mod test {
             "/ip4/1.1.1.1.",
             "/ip4/1.1.1.1.1",
             "/ip4/1.1.1.999.1",
+            "/ip4/1.1.1.1/udp/1234",
+            "/ip4/1.1.1.1/udp/1234/noise-ik",
+            "/ip4/1.1.1.1/tcp/1234/quic",
         ];
 
         for &addr_str in &test_cases {
@@ -1063,6 +1156,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_quic() {
+        let addr = NetworkAddress::from_str("/ip6/::1/udp/123/quic").unwrap();
+        let expected_suffix: &[Protocol] = &[];
+        assert_eq!(
+            parse_ip_quic(addr.as_slice()).unwrap(),
+            ((IpAddr::from_str("::1").unwrap(), 123), expected_suffix)
+        );
+        assert!(parse_ip_tcp(addr.as_slice()).is_none());
+        assert!(addr.is_quic_addr());
+        assert_eq!(addr.find_port(), Some(123));
+
+        let dns_name = DnsName::from_str("example.com").unwrap();
+        let addr = NetworkAddress::from_str("/dns4/example.com/udp/123/quic").unwrap();
+        assert_eq!(
+            parse_dns_quic(addr.as_slice()).unwrap(),
+            ((IpFilter::OnlyIp4, &dns_name, 123), expected_suffix)
+        );
+        assert!(parse_dns_tcp(addr.as_slice()).is_none());
+
+        let sockaddr = SocketAddr::from_str("127.0.0.1:6180").unwrap();
+        assert_eq!(
+            NetworkAddress::from_quic_socket_addr(sockaddr).to_string(),
+            "/ip4/127.0.0.1/udp/6180/quic"
+        );
+    }
+
     #[test]
     fn test_find_noise_proto() {
         let pubkey_str = "080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120";
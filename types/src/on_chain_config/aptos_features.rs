@@ -28,6 +28,9 @@ pub enum FeatureFlag {
     SIGNATURE_CHECKER_V2 = 18,
     STORAGE_SLOT_METADATA = 19,
     CHARGE_INVARIANT_VIOLATION = 20,
+    FEE_PAYER_ENABLED = 21,
+    SECP256K1_ECDSA_AUTHENTICATOR = 22,
+    MULTI_KEY_AUTHENTICATOR = 23,
 }
 
 /// Representation of features on chain as a bitset.
@@ -13,6 +13,8 @@ use std::collections::HashMap;
 pub enum OnChainConsensusConfig {
     V1(ConsensusConfigV1),
     V2(ConsensusConfigV1),
+    /// DAG based consensus, which always runs with quorum store and decoupled execution.
+    DAG(DagConsensusConfigV1),
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -23,6 +25,7 @@ impl OnChainConsensusConfig {
             OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
                 config.exclude_round
             },
+            OnChainConsensusConfig::DAG(_) => 0,
         }
     }
 
@@ -38,15 +41,18 @@ impl OnChainConsensusConfig {
             OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
                 config.max_failed_authors_to_store
             },
+            OnChainConsensusConfig::DAG(config) => config.max_failed_authors_to_store,
         }
     }
 
     // Type and configuration used for proposer election.
-    pub fn proposer_election_type(&self) -> &ProposerElectionType {
+    // DAG consensus doesn't elect proposers, anchors are elected by the DAG itself.
+    pub fn proposer_election_type(&self) -> Option<&ProposerElectionType> {
         match &self {
             OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                &config.proposer_election_type
+                Some(&config.proposer_election_type)
             },
+            OnChainConsensusConfig::DAG(_) => None,
         }
     }
 
@@ -54,6 +60,19 @@ impl OnChainConsensusConfig {
         match &self {
             OnChainConsensusConfig::V1(_config) => false,
             OnChainConsensusConfig::V2(_config) => true,
+            OnChainConsensusConfig::DAG(_config) => true,
+        }
+    }
+
+    pub fn is_dag_enabled(&self) -> bool {
+        matches!(self, OnChainConsensusConfig::DAG(_))
+    }
+
+    /// The DAG specific config, if DAG consensus is enabled.
+    pub fn dag_config(&self) -> Option<&DagConsensusConfigV1> {
+        match &self {
+            OnChainConsensusConfig::DAG(config) => Some(config),
+            _ => None,
         }
     }
 }
@@ -120,6 +139,23 @@ impl Default for ConsensusConfigV1 {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DagConsensusConfigV1 {
+    // Number of rounds below an anchor from which its causal history is ordered.
+    // Older unordered nodes are never ordered.
+    pub dag_ordering_causal_history_window: usize,
+    pub max_failed_authors_to_store: usize,
+}
+
+impl Default for DagConsensusConfigV1 {
+    fn default() -> Self {
+        Self {
+            dag_ordering_causal_history_window: 10,
+            max_failed_authors_to_store: 10,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum ProposerElectionType {
@@ -225,10 +261,22 @@ mod test {
         let result = serde_yaml::from_str::<OnChainConsensusConfig>(&s).unwrap();
         assert!(matches!(
             result.proposer_election_type(),
-            ProposerElectionType::RoundProposer(_value)
+            Some(ProposerElectionType::RoundProposer(_value))
         ));
     }
 
+    #[test]
+    fn test_dag_config_bcs_serialization() {
+        let config = OnChainConsensusConfig::DAG(DagConsensusConfigV1::default());
+        let s = bcs::to_bytes(&config).unwrap();
+        let result = bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap();
+
+        assert!(result.is_dag_enabled());
+        assert!(result.quorum_store_enabled());
+        assert!(result.proposer_election_type().is_none());
+        assert_eq!(result.dag_config(), Some(&DagConsensusConfigV1::default()));
+    }
+
     #[test]
     fn test_config_onchain_payload() {
         let consensus_config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
@@ -251,7 +299,7 @@ mod test {
         let result: OnChainConsensusConfig = payload.get().unwrap();
         assert!(matches!(
             result.proposer_election_type(),
-            ProposerElectionType::RoundProposer(_value)
+            Some(ProposerElectionType::RoundProposer(_value))
         ));
     }
 }
@@ -25,7 +25,7 @@ use aptos_crypto::{
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use std::{any::type_name, marker::PhantomData};
+use std::{any::type_name, marker::PhantomData, ops::Range};
 
 /// A proof that can be used authenticate an element in an accumulator given trusted root hash. For
 /// example, both `LedgerInfoToTransactionInfoProof` and `TransactionInfoToEventProof` can be
@@ -285,62 +285,7 @@ impl SparseMerkleProof {
             self.siblings.len(),
         );
 
-        match (element_hash, self.leaf) {
-            (Some(hash), Some(leaf)) => {
-                // This is an inclusion proof, so the key and value hash provided in the proof
-                // should match element_key and element_value_hash. `siblings` should prove the
-                // route from the leaf node to the root.
-                ensure!(
-                    element_key == leaf.key,
-                    "Keys do not match. Key in proof: {:x}. Expected key: {:x}. \
-                     Element hash: {:x}. Value hash in proof {:x}",
-                    leaf.key,
-                    element_key,
-                    hash,
-                    leaf.value_hash
-                );
-                ensure!(
-                    hash == leaf.value_hash,
-                    "Value hashes do not match for key {:x}. Value hash in proof: {:x}. \
-                     Expected value hash: {:x}. ",
-                    element_key,
-                    leaf.value_hash,
-                    hash
-                );
-            },
-            (Some(hash), None) => {
-                bail!(
-                    "Expected inclusion proof, value hash: {:x}. Found non-inclusion proof.",
-                    hash
-                )
-            },
-            (None, Some(leaf)) => {
-                // This is a non-inclusion proof. The proof intends to show that if a leaf node
-                // representing `element_key` is inserted, it will break a currently existing leaf
-                // node represented by `proof_key` into a branch. `siblings` should prove the
-                // route from that leaf node to the root.
-                ensure!(
-                    element_key != leaf.key,
-                    "Expected non-inclusion proof, but key exists in proof. \
-                     Key: {:x}. Key in proof: {:x}.",
-                    element_key,
-                    leaf.key,
-                );
-                ensure!(
-                    element_key.common_prefix_bits_len(leaf.key) >= self.siblings.len(),
-                    "Key would not have ended up in the subtree where the provided key in proof \
-                     is the only existing key, if it existed. So this is not a valid \
-                     non-inclusion proof. Key: {:x}. Key in proof: {:x}.",
-                    element_key,
-                    leaf.key
-                );
-            },
-            (None, None) => {
-                // This is a non-inclusion proof. The proof intends to show that if a leaf node
-                // representing `element_key` is inserted, it will show up at a currently empty
-                // position. `sibling` should prove the route from this empty position to the root.
-            },
-        }
+        verify_leaf(element_key, element_hash, self.leaf, self.siblings.len())?;
 
         let current_hash = self
             .leaf
@@ -373,6 +318,312 @@ impl SparseMerkleProof {
     }
 }
 
+/// Verifies that `leaf`, found at `depth` on the path to `element_key`, proves the inclusion of
+/// the element if `element_hash` is present, or its non-inclusion otherwise.
+fn verify_leaf(
+    element_key: HashValue,
+    element_hash: Option<HashValue>,
+    leaf: Option<SparseMerkleLeafNode>,
+    depth: usize,
+) -> Result<()> {
+    match (element_hash, leaf) {
+        (Some(hash), Some(leaf)) => {
+            // This is an inclusion proof, so the key and value hash provided in the proof
+            // should match element_key and element_value_hash. `siblings` should prove the
+            // route from the leaf node to the root.
+            ensure!(
+                element_key == leaf.key,
+                "Keys do not match. Key in proof: {:x}. Expected key: {:x}. \
+                 Element hash: {:x}. Value hash in proof {:x}",
+                leaf.key,
+                element_key,
+                hash,
+                leaf.value_hash
+            );
+            ensure!(
+                hash == leaf.value_hash,
+                "Value hashes do not match for key {:x}. Value hash in proof: {:x}. \
+                 Expected value hash: {:x}. ",
+                element_key,
+                leaf.value_hash,
+                hash
+            );
+        },
+        (Some(hash), None) => {
+            bail!(
+                "Expected inclusion proof, value hash: {:x}. Found non-inclusion proof.",
+                hash
+            )
+        },
+        (None, Some(leaf)) => {
+            // This is a non-inclusion proof. The proof intends to show that if a leaf node
+            // representing `element_key` is inserted, it will break a currently existing leaf
+            // node represented by `proof_key` into a branch. `siblings` should prove the
+            // route from that leaf node to the root.
+            ensure!(
+                element_key != leaf.key,
+                "Expected non-inclusion proof, but key exists in proof. \
+                 Key: {:x}. Key in proof: {:x}.",
+                element_key,
+                leaf.key,
+            );
+            ensure!(
+                element_key.common_prefix_bits_len(leaf.key) >= depth,
+                "Key would not have ended up in the subtree where the provided key in proof \
+                 is the only existing key, if it existed. So this is not a valid \
+                 non-inclusion proof. Key: {:x}. Key in proof: {:x}.",
+                element_key,
+                leaf.key
+            );
+        },
+        (None, None) => {
+            // This is a non-inclusion proof. The proof intends to show that if a leaf node
+            // representing `element_key` is inserted, it will show up at a currently empty
+            // position. `sibling` should prove the route from this empty position to the root.
+        },
+    }
+
+    Ok(())
+}
+
+/// A proof that can be used to authenticate a batch of elements in a Sparse Merkle Tree given
+/// trusted root hash. It is equivalent to one `SparseMerkleProof` per element, except that each
+/// sibling is included only once, and siblings on the path to another element in the batch are
+/// not included at all, since the verifier computes them.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SparseMerkleMultiProof {
+    /// The leaf at the end of the path to each element, like `SparseMerkleProof::leaf`, ordered
+    /// by element key.
+    leaves: Vec<Option<SparseMerkleLeafNode>>,
+
+    /// The depth at which the path to each element ends, i.e. the number of siblings in its
+    /// `SparseMerkleProof`, ordered by element key.
+    depths: Vec<u16>,
+
+    /// The siblings not on the path to any element of the batch, ordered as visited by a
+    /// depth-first, left to right traversal from the root.
+    siblings: Vec<HashValue>,
+}
+
+impl SparseMerkleMultiProof {
+    /// Constructs a new `SparseMerkleMultiProof` using leaves, depths and siblings.
+    pub fn new(
+        leaves: Vec<Option<SparseMerkleLeafNode>>,
+        depths: Vec<u16>,
+        siblings: Vec<HashValue>,
+    ) -> Self {
+        Self {
+            leaves,
+            depths,
+            siblings,
+        }
+    }
+
+    /// Merges the proofs of the elements, keyed by element key, into a multi proof. The proofs
+    /// must be taken against the same root.
+    pub fn from_proofs(mut proofs: Vec<(HashValue, SparseMerkleProof)>) -> Result<Self> {
+        proofs.sort_unstable_by_key(|(key, _)| *key);
+        ensure!(
+            proofs.windows(2).all(|pair| pair[0].0 != pair[1].0),
+            "Duplicate keys in Sparse Merkle Tree proofs."
+        );
+        for (_, proof) in &proofs {
+            ensure!(
+                proof.siblings.len() <= HashValue::LENGTH_IN_BITS,
+                "Sparse Merkle Tree proof has more than {} ({}) siblings.",
+                HashValue::LENGTH_IN_BITS,
+                proof.siblings.len(),
+            );
+        }
+
+        let mut siblings = vec![];
+        if !proofs.is_empty() {
+            Self::collect_siblings(&proofs, 0, &mut siblings)?;
+        }
+        let (leaves, depths) = proofs
+            .iter()
+            .map(|(_, proof)| (proof.leaf, proof.siblings.len() as u16))
+            .unzip();
+        Ok(Self::new(leaves, depths, siblings))
+    }
+
+    fn collect_siblings(
+        proofs: &[(HashValue, SparseMerkleProof)],
+        depth: usize,
+        siblings: &mut Vec<HashValue>,
+    ) -> Result<()> {
+        if proofs
+            .iter()
+            .any(|(_, proof)| proof.siblings.len() == depth)
+        {
+            let first = &proofs[0].1;
+            ensure!(
+                proofs
+                    .iter()
+                    .all(|(_, proof)| proof.siblings.len() == depth && proof.leaf == first.leaf),
+                "Sparse Merkle Tree proofs disagree on the subtree at depth {}.",
+                depth,
+            );
+            return Ok(());
+        }
+
+        let mid = proofs.partition_point(|(key, _)| !key.bit(depth));
+        let (left, right) = proofs.split_at(mid);
+        if left.is_empty() || right.is_empty() {
+            // Siblings in a single proof are ordered from the bottom level to the root level.
+            let proof = &proofs[0].1;
+            siblings.push(proof.siblings[proof.siblings.len() - 1 - depth]);
+        }
+        for half in [left, right] {
+            if !half.is_empty() {
+                Self::collect_siblings(half, depth + 1, siblings)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the leaf at the end of the path to each element, ordered by element key.
+    pub fn leaves(&self) -> &[Option<SparseMerkleLeafNode>] {
+        &self.leaves
+    }
+
+    /// Returns the depth at which the path to each element ends, ordered by element key.
+    pub fn depths(&self) -> &[u16] {
+        &self.depths
+    }
+
+    /// Returns the list of siblings in this proof.
+    pub fn siblings(&self) -> &[HashValue] {
+        &self.siblings
+    }
+
+    pub fn verify<V: CryptoHash>(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<&V>)],
+    ) -> Result<()> {
+        let elements: Vec<_> = elements
+            .iter()
+            .map(|(key, value)| (*key, value.map(|v| v.hash())))
+            .collect();
+        self.verify_by_hash(expected_root_hash, &elements)
+    }
+
+    /// Verifies, for each element, that it exists in the Sparse Merkle Tree with the value
+    /// authenticated by its hash if present, or that its key doesn't exist in the tree otherwise.
+    /// The elements can be in any order, but their keys must be distinct.
+    pub fn verify_by_hash(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<HashValue>)],
+    ) -> Result<()> {
+        ensure!(
+            elements.len() == self.leaves.len() && elements.len() == self.depths.len(),
+            "Sparse Merkle Tree multi proof is for {} leaves at {} depths, but {} elements are \
+             given.",
+            self.leaves.len(),
+            self.depths.len(),
+            elements.len(),
+        );
+        let mut elements = elements.to_vec();
+        elements.sort_unstable_by_key(|(key, _)| *key);
+        ensure!(
+            elements.windows(2).all(|pair| pair[0].0 != pair[1].0),
+            "Duplicate keys in elements to verify."
+        );
+
+        for (((key, hash), leaf), depth) in elements.iter().zip(&self.leaves).zip(&self.depths) {
+            ensure!(
+                *depth as usize <= HashValue::LENGTH_IN_BITS,
+                "Sparse Merkle Tree multi proof has a path deeper than {} ({}).",
+                HashValue::LENGTH_IN_BITS,
+                depth,
+            );
+            verify_leaf(*key, *hash, *leaf, *depth as usize)?;
+        }
+
+        let actual_root_hash = if elements.is_empty() {
+            ensure!(
+                self.siblings.is_empty(),
+                "Sparse Merkle Tree multi proof for no elements has siblings."
+            );
+            expected_root_hash
+        } else {
+            let keys: Vec<_> = elements.iter().map(|(key, _)| *key).collect();
+            let mut siblings = self.siblings.iter();
+            let root_hash = self.compute_root_hash(&keys, 0..keys.len(), 0, &mut siblings)?;
+            ensure!(
+                siblings.next().is_none(),
+                "Sparse Merkle Tree multi proof has more siblings than needed."
+            );
+            root_hash
+        };
+        ensure!(
+            actual_root_hash == expected_root_hash,
+            "{}: Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            type_name::<Self>(),
+            actual_root_hash,
+            expected_root_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Computes the hash of the subtree at `depth` which the sorted `keys[range]` fall into.
+    fn compute_root_hash<'a>(
+        &self,
+        keys: &[HashValue],
+        range: Range<usize>,
+        depth: usize,
+        siblings: &mut impl Iterator<Item = &'a HashValue>,
+    ) -> Result<HashValue> {
+        if self.depths[range.clone()]
+            .iter()
+            .any(|d| *d as usize == depth)
+        {
+            // The subtree holds at most one leaf, so all the paths into it must end here.
+            let leaf = self.leaves[range.start];
+            ensure!(
+                self.depths[range.clone()]
+                    .iter()
+                    .all(|d| *d as usize == depth)
+                    && self.leaves[range].iter().all(|l| *l == leaf),
+                "Sparse Merkle Tree multi proof has inconsistent paths at depth {}.",
+                depth,
+            );
+            return Ok(leaf.map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash()));
+        }
+
+        let mid = range.start + keys[range.clone()].partition_point(|key| !key.bit(depth));
+        let (left_hash, right_hash) = if mid == range.start {
+            let left_hash = Self::next_sibling(siblings)?;
+            (
+                left_hash,
+                self.compute_root_hash(keys, range, depth + 1, siblings)?,
+            )
+        } else if mid == range.end {
+            let right_hash = Self::next_sibling(siblings)?;
+            (
+                self.compute_root_hash(keys, range, depth + 1, siblings)?,
+                right_hash,
+            )
+        } else {
+            (
+                self.compute_root_hash(keys, range.start..mid, depth + 1, siblings)?,
+                self.compute_root_hash(keys, mid..range.end, depth + 1, siblings)?,
+            )
+        };
+        Ok(SparseMerkleInternalNode::new(left_hash, right_hash).hash())
+    }
+
+    fn next_sibling<'a>(siblings: &mut impl Iterator<Item = &'a HashValue>) -> Result<HashValue> {
+        siblings
+            .next()
+            .copied()
+            .ok_or_else(|| format_err!("Sparse Merkle Tree multi proof runs out of siblings."))
+    }
+}
+
 /// An in-memory accumulator for storing a summary of the core transaction info
 /// accumulator. It is a summary in the sense that it only stores maximally
 /// frozen subtree nodes rather than storing all leaves and internal nodes.
@@ -11,9 +11,9 @@ use crate::{
     ledger_info::LedgerInfo,
     proof::{
         definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccumulatorExtensionProof, AccumulatorRangeProof,
-        SparseMerkleInternalNode, SparseMerkleLeafNode, TestAccumulatorInternalNode,
-        TestAccumulatorProof, TransactionAccumulatorInternalNode, TransactionAccumulatorProof,
-        TransactionInfoListWithProof, TransactionInfoWithProof,
+        SparseMerkleInternalNode, SparseMerkleLeafNode, SparseMerkleMultiProof,
+        TestAccumulatorInternalNode, TestAccumulatorProof, TransactionAccumulatorInternalNode,
+        TransactionAccumulatorProof, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_store::state_value::StateValue,
     transaction::{
@@ -266,6 +266,105 @@ fn test_verify_three_element_sparse_merkle() {
     }
 }
 
+#[test]
+fn test_verify_sparse_merkle_multi_proof() {
+    //            root
+    //           /    \
+    //          a      default
+    //         / \
+    //     key1   b
+    //           / \
+    //       key2   key3
+    let key1 = b"hello".test_only_hash();
+    let key2 = b"world".test_only_hash();
+    let key3 = b"!".test_only_hash();
+    let non_existing_key = b"def".test_only_hash();
+    assert_eq!(non_existing_key[0], 0b1000_1110);
+
+    let blob1 = StateValue::from(b"1".to_vec());
+    let blob2 = StateValue::from(b"2".to_vec());
+    let blob3 = StateValue::from(b"3".to_vec());
+
+    let leaf1_hash = SparseMerkleLeafNode::new(key1, blob1.hash()).hash();
+    let leaf2 = SparseMerkleLeafNode::new(key2, blob2.hash());
+    let leaf3 = SparseMerkleLeafNode::new(key3, blob3.hash());
+    let internal_b_hash = SparseMerkleInternalNode::new(leaf2.hash(), leaf3.hash()).hash();
+    let internal_a_hash = SparseMerkleInternalNode::new(leaf1_hash, internal_b_hash).hash();
+    let root_hash =
+        SparseMerkleInternalNode::new(internal_a_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH).hash();
+
+    let proof = SparseMerkleMultiProof::from_proofs(vec![
+        (
+            non_existing_key,
+            SparseMerkleProof::new(None, vec![internal_a_hash]),
+        ),
+        (
+            key3,
+            SparseMerkleProof::new(Some(leaf3), vec![
+                leaf2.hash(),
+                leaf1_hash,
+                *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            ]),
+        ),
+        (
+            key2,
+            SparseMerkleProof::new(Some(leaf2), vec![
+                leaf3.hash(),
+                leaf1_hash,
+                *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            ]),
+        ),
+    ])
+    .unwrap();
+    // Only the sibling off the paths to the three keys is needed.
+    assert_eq!(proof.siblings(), &[leaf1_hash]);
+    assert_eq!(proof.depths(), &[3, 3, 1]);
+
+    // The elements can be given in any order.
+    assert!(proof
+        .verify(root_hash, &[
+            (key3, Some(&blob3)),
+            (non_existing_key, None),
+            (key2, Some(&blob2)),
+        ])
+        .is_ok());
+    // Trying to show that a key has another value.
+    assert!(proof
+        .verify(root_hash, &[
+            (key2, Some(&blob2)),
+            (key3, Some(&blob2)),
+            (non_existing_key, None),
+        ])
+        .is_err());
+    // Trying to show that an existing key doesn't exist.
+    assert!(proof
+        .verify(root_hash, &[
+            (key2, Some(&blob2)),
+            (key3, None),
+            (non_existing_key, None),
+        ])
+        .is_err());
+    // The proof is for exactly three keys.
+    assert!(proof
+        .verify(root_hash, &[(key2, Some(&blob2)), (key3, Some(&blob3))])
+        .is_err());
+    assert!(proof
+        .verify(root_hash, &[
+            (key1, Some(&blob1)),
+            (key3, Some(&blob3)),
+            (non_existing_key, None),
+        ])
+        .is_err());
+    // Trying to verify against another root.
+    assert!(proof
+        .verify(internal_a_hash, &[
+            (key2, Some(&blob2)),
+            (key3, Some(&blob3)),
+            (non_existing_key, None),
+        ])
+        .is_err());
+}
+
 #[test]
 fn test_verify_transaction() {
     //            root
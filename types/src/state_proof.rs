@@ -5,7 +5,12 @@
 use crate::{
     epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{SparseMerkleProof, TransactionInfoWithProof},
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::Version,
 };
+use anyhow::{ensure, format_err, Result};
+use aptos_crypto::hash::CryptoHash;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -61,6 +66,95 @@ impl StateProof {
     }
 }
 
+/// A state value (or its absence) at the version of a ledger info, along with the proofs that
+/// authenticate it against that ledger info: the proof of the transaction info at the version,
+/// whose state checkpoint hash is the root of the state tree, and the sparse merkle proof of the
+/// state value in the state tree.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateValueWithProof {
+    version: Version,
+    value: Option<StateValue>,
+    transaction_info_with_proof: TransactionInfoWithProof,
+    sparse_merkle_proof: SparseMerkleProof,
+}
+
+impl StateValueWithProof {
+    pub fn new(
+        version: Version,
+        value: Option<StateValue>,
+        transaction_info_with_proof: TransactionInfoWithProof,
+        sparse_merkle_proof: SparseMerkleProof,
+    ) -> Self {
+        Self {
+            version,
+            value,
+            transaction_info_with_proof,
+            sparse_merkle_proof,
+        }
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn value(&self) -> Option<&StateValue> {
+        self.value.as_ref()
+    }
+
+    pub fn into_value(self) -> Option<StateValue> {
+        self.value
+    }
+
+    /// Verifies that the state value is the value of `state_key` at the version of the ledger
+    /// info, `None` meaning that the state key doesn't exist.
+    pub fn verify(&self, ledger_info: &LedgerInfo, state_key: &StateKey) -> Result<()> {
+        ensure!(
+            self.version == ledger_info.version(),
+            "State value version {} doesn't match the ledger info version {}.",
+            self.version,
+            ledger_info.version(),
+        );
+        self.transaction_info_with_proof
+            .verify(ledger_info, self.version)?;
+        let state_root_hash = self
+            .transaction_info_with_proof
+            .transaction_info()
+            .state_checkpoint_hash()
+            .ok_or_else(|| format_err!("Version {} is not a state checkpoint.", self.version))?;
+        self.sparse_merkle_proof
+            .verify(state_root_hash, state_key.hash(), self.value.as_ref())
+    }
+}
+
+/// A response to a request for a state value with proof: the `StateProof` from the version known
+/// to the client to the latest ledger info, and the state value proven against that ledger info.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateValueWithStateProof {
+    state_proof: StateProof,
+    state_value_with_proof: StateValueWithProof,
+}
+
+impl StateValueWithStateProof {
+    pub fn new(state_proof: StateProof, state_value_with_proof: StateValueWithProof) -> Self {
+        Self {
+            state_proof,
+            state_value_with_proof,
+        }
+    }
+
+    pub fn into_inner(self) -> (StateProof, StateValueWithProof) {
+        (self.state_proof, self.state_value_with_proof)
+    }
+
+    pub fn state_proof(&self) -> &StateProof {
+        &self.state_proof
+    }
+
+    pub fn state_value_with_proof(&self) -> &StateValueWithProof {
+        &self.state_value_with_proof
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -14,6 +14,7 @@ pub use move_core_types::abi::{
 use move_core_types::{
     account_address::AccountAddress, language_storage::StructTag, move_resource::MoveStructType,
 };
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
 #[derive(Clone, Debug)]
@@ -32,7 +33,7 @@ pub struct AnalyzedTransaction {
     hash: HashValue,
 }
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Hash, Eq, PartialEq, Serialize)]
 // TODO(skedia): Evaluate if we need to cache the HashValue for efficiency reasons.
 pub enum StorageLocation {
     // A specific storage location denoted by an address and a struct tag.
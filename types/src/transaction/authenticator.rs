@@ -6,11 +6,14 @@ use crate::{
     account_address::AccountAddress,
     transaction::{RawTransaction, RawTransactionWithData},
 };
-use anyhow::{ensure, Error, Result};
+use anyhow::{bail, ensure, Error, Result};
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
-    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    multi_ed25519::{
+        MultiEd25519PublicKey, MultiEd25519Signature, BITMAP_NUM_OF_BYTES, MAX_NUM_OF_KEYS,
+    },
+    secp256k1_ecdsa::{Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature},
     traits::Signature,
     CryptoMaterialError, HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
@@ -57,6 +60,24 @@ pub enum TransactionAuthenticator {
         secondary_signer_addresses: Vec<AccountAddress>,
         secondary_signers: Vec<AccountAuthenticator>,
     },
+    /// Multi-agent transaction where gas is paid by a separate fee payer account.
+    FeePayer {
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    },
+    /// Single secp256k1 ECDSA signature
+    Secp256k1Ecdsa {
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    },
+    /// K-of-N multisignature with keys of mixed schemes
+    MultiKey {
+        public_key: MultiKey,
+        signature: MultiKeySignature,
+    },
 }
 
 impl TransactionAuthenticator {
@@ -79,6 +100,25 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    ) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a multisignature authenticator with keys of mixed schemes
+    pub fn multi_key(public_key: MultiKey, signature: MultiKeySignature) -> Self {
+        Self::MultiKey {
+            public_key,
+            signature,
+        }
+    }
+
     /// Create a multi-agent authenticator
     pub fn multi_agent(
         sender: AccountAuthenticator,
@@ -92,6 +132,23 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a fee payer authenticator
+    pub fn fee_payer(
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    ) -> Self {
+        Self::FeePayer {
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+            fee_payer_address,
+            fee_payer_signer,
+        }
+    }
+
     /// Return Ok if all AccountAuthenticator's public keys match their signatures, Err otherwise
     pub fn verify(&self, raw_txn: &RawTransaction) -> Result<()> {
         let num_sigs: usize = self.sender().number_of_signatures()
@@ -99,7 +156,10 @@ impl TransactionAuthenticator {
                 .secondary_signers()
                 .iter()
                 .map(|auth| auth.number_of_signatures())
-                .sum::<usize>();
+                .sum::<usize>()
+            + self
+                .fee_payer_signer()
+                .map_or(0, |auth| auth.number_of_signatures());
         if num_sigs > MAX_NUM_OF_SIGS {
             return Err(Error::new(AuthenticationError::MaxSignaturesExceeded));
         }
@@ -127,6 +187,32 @@ impl TransactionAuthenticator {
                 }
                 Ok(())
             },
+            Self::FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => {
+                let message = RawTransactionWithData::new_multi_agent_with_fee_payer(
+                    raw_txn.clone(),
+                    secondary_signer_addresses.clone(),
+                    *fee_payer_address,
+                );
+                sender.verify(&message)?;
+                for signer in secondary_signers {
+                    signer.verify(&message)?;
+                }
+                fee_payer_signer.verify(&message)
+            },
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(raw_txn, public_key),
+            Self::MultiKey {
+                public_key,
+                signature,
+            } => signature.verify(raw_txn, public_key),
         }
     }
 
@@ -143,7 +229,15 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => AccountAuthenticator::multi_ed25519(public_key.clone(), signature.clone()),
-            Self::MultiAgent { sender, .. } => sender.clone(),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => AccountAuthenticator::secp256k1_ecdsa(public_key.clone(), signature.clone()),
+            Self::MultiKey {
+                public_key,
+                signature,
+            } => AccountAuthenticator::multi_key(public_key.clone(), signature.clone()),
+            Self::MultiAgent { sender, .. } | Self::FeePayer { sender, .. } => sender.clone(),
         }
     }
 
@@ -153,11 +247,18 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::Secp256k1Ecdsa { .. }
+            | Self::MultiKey { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses,
                 ..
+            }
+            | Self::FeePayer {
+                sender: _,
+                secondary_signer_addresses,
+                ..
             } => secondary_signer_addresses.to_vec(),
         }
     }
@@ -168,14 +269,48 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::Secp256k1Ecdsa { .. }
+            | Self::MultiKey { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses: _,
                 secondary_signers,
+            }
+            | Self::FeePayer {
+                sender: _,
+                secondary_signer_addresses: _,
+                secondary_signers,
+                ..
             } => secondary_signers.to_vec(),
         }
     }
+
+    pub fn fee_payer_address(&self) -> Option<AccountAddress> {
+        match self {
+            Self::FeePayer {
+                fee_payer_address, ..
+            } => Some(*fee_payer_address),
+            _ => None,
+        }
+    }
+
+    pub fn fee_payer_signer(&self) -> Option<AccountAuthenticator> {
+        match self {
+            Self::FeePayer {
+                fee_payer_signer, ..
+            } => Some(fee_payer_signer.clone()),
+            _ => None,
+        }
+    }
+
+    /// Return the authenticators of the sender, the secondary signers and the fee payer
+    pub fn all_signers(&self) -> Vec<AccountAuthenticator> {
+        let mut signers = vec![self.sender()];
+        signers.extend(self.secondary_signers());
+        signers.extend(self.fee_payer_signer());
+        signers
+    }
 }
 
 impl fmt::Display for TransactionAuthenticator {
@@ -201,6 +336,26 @@ impl fmt::Display for TransactionAuthenticator {
                     self.sender()
                 )
             },
+            Self::Secp256k1Ecdsa {
+                public_key: _,
+                signature: _,
+            } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: Secp256k1Ecdsa, sender: {}]",
+                    self.sender()
+                )
+            },
+            Self::MultiKey {
+                public_key: _,
+                signature: _,
+            } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: MultiKey, sender: {}]",
+                    self.sender()
+                )
+            },
             Self::MultiAgent {
                 sender,
                 secondary_signer_addresses,
@@ -224,6 +379,33 @@ impl fmt::Display for TransactionAuthenticator {
                     sender, sec_addrs, sec_signers,
                 )
             },
+            Self::FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => {
+                let mut sec_addrs: String = "".to_string();
+                for sec_addr in secondary_signer_addresses {
+                    sec_addrs = format!("{}\n\t\t\t{:#?},", sec_addrs, sec_addr);
+                }
+                let mut sec_signers: String = "".to_string();
+                for sec_signer in secondary_signers {
+                    sec_signers = format!("{}\n\t\t\t{:#?},", sec_signers, sec_signer);
+                }
+                write!(
+                    f,
+                    "TransactionAuthenticator[\n\
+                        \tscheme: FeePayer, \n\
+                        \tsender: {}\n\
+                        \tsecondary signer addresses: {}\n\
+                        \tsecondary signers: {}\n\
+                        \tfee payer address: {}\n\
+                        \tfee payer signer: {}]",
+                    sender, sec_addrs, sec_signers, fee_payer_address, fee_payer_signer,
+                )
+            },
         }
     }
 }
@@ -241,6 +423,8 @@ impl fmt::Display for TransactionAuthenticator {
 pub enum Scheme {
     Ed25519 = 0,
     MultiEd25519 = 1,
+    Secp256k1Ecdsa = 2,
+    MultiKey = 3,
     // ... add more schemes here
     /// Scheme identifier used to derive addresses (not the authentication key) of objects and
     /// resources accounts. This application serves to domain separate hashes. Without such
@@ -257,6 +441,8 @@ impl fmt::Display for Scheme {
         let display = match self {
             Scheme::Ed25519 => "Ed25519",
             Scheme::MultiEd25519 => "MultiEd25519",
+            Scheme::Secp256k1Ecdsa => "Secp256k1Ecdsa",
+            Scheme::MultiKey => "MultiKey",
             Scheme::DeriveObjectAddressFromObject => "DeriveObjectAddressFromObject",
             Scheme::DeriveObjectAddressFromGuid => "DeriveObjectAddressFromGuid",
             Scheme::DeriveObjectAddressFromSeed => "DeriveObjectAddressFromSeed",
@@ -278,6 +464,16 @@ pub enum AccountAuthenticator {
         public_key: MultiEd25519PublicKey,
         signature: MultiEd25519Signature,
     },
+    /// Single secp256k1 ECDSA signature
+    Secp256k1Ecdsa {
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    },
+    /// K-of-N multisignature with keys of mixed schemes
+    MultiKey {
+        public_key: MultiKey,
+        signature: MultiKeySignature,
+    },
     // ... add more schemes here
 }
 
@@ -287,6 +483,21 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::Secp256k1Ecdsa { .. } => Scheme::Secp256k1Ecdsa,
+            Self::MultiKey { .. } => Scheme::MultiKey,
+        }
+    }
+
+    /// Schemes of the individual keys behind this authenticator: the members of a `MultiKey`,
+    /// or the authenticator's own scheme otherwise
+    pub fn key_schemes(&self) -> Vec<Scheme> {
+        match self {
+            Self::MultiKey { public_key, .. } => public_key
+                .public_keys()
+                .iter()
+                .map(AnyPublicKey::scheme)
+                .collect(),
+            _ => vec![self.scheme()],
         }
     }
 
@@ -309,6 +520,25 @@ impl AccountAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    ) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a multisignature authenticator with keys of mixed schemes
+    pub fn multi_key(public_key: MultiKey, signature: MultiKeySignature) -> Self {
+        Self::MultiKey {
+            public_key,
+            signature,
+        }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
@@ -320,6 +550,14 @@ impl AccountAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::MultiKey {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
         }
     }
 
@@ -328,6 +566,8 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::MultiKey { public_key, .. } => public_key.to_bytes(),
         }
     }
 
@@ -336,6 +576,8 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { signature, .. } => signature.to_bytes().to_vec(),
+            Self::MultiKey { signature, .. } => signature.to_bytes(),
         }
     }
 
@@ -354,10 +596,246 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => 1,
             Self::MultiEd25519 { signature, .. } => signature.signatures().len(),
+            Self::Secp256k1Ecdsa { .. } => 1,
+            Self::MultiKey { signature, .. } => signature.signatures().len(),
         }
     }
 }
 
+/// A public key of any of the signature schemes that can be a member of a `MultiKey`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AnyPublicKey {
+    Ed25519 { public_key: Ed25519PublicKey },
+    Secp256k1Ecdsa { public_key: Secp256k1EcdsaPublicKey },
+    // ... add more schemes here
+}
+
+impl AnyPublicKey {
+    pub fn ed25519(public_key: Ed25519PublicKey) -> Self {
+        Self::Ed25519 { public_key }
+    }
+
+    pub fn secp256k1_ecdsa(public_key: Secp256k1EcdsaPublicKey) -> Self {
+        Self::Secp256k1Ecdsa { public_key }
+    }
+
+    /// Unique identifier for the signature scheme of the key
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            Self::Ed25519 { .. } => Scheme::Ed25519,
+            Self::Secp256k1Ecdsa { .. } => Scheme::Secp256k1Ecdsa,
+        }
+    }
+
+    /// Return the raw bytes of the public key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519 { public_key } => public_key.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { public_key } => public_key.to_bytes().to_vec(),
+        }
+    }
+}
+
+/// A signature of any of the signature schemes that can be a member of a `MultiKey`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AnySignature {
+    Ed25519 { signature: Ed25519Signature },
+    Secp256k1Ecdsa { signature: Secp256k1EcdsaSignature },
+    // ... add more schemes here
+}
+
+impl AnySignature {
+    pub fn ed25519(signature: Ed25519Signature) -> Self {
+        Self::Ed25519 { signature }
+    }
+
+    pub fn secp256k1_ecdsa(signature: Secp256k1EcdsaSignature) -> Self {
+        Self::Secp256k1Ecdsa { signature }
+    }
+
+    /// Return Ok if the signature is valid for the public key, which must be of the same scheme
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &AnyPublicKey,
+    ) -> Result<()> {
+        match (self, public_key) {
+            (Self::Ed25519 { signature }, AnyPublicKey::Ed25519 { public_key }) => {
+                signature.verify(message, public_key)
+            },
+            (Self::Secp256k1Ecdsa { signature }, AnyPublicKey::Secp256k1Ecdsa { public_key }) => {
+                signature.verify(message, public_key)
+            },
+            _ => bail!("Signature scheme doesn't match the scheme of the public key"),
+        }
+    }
+
+    /// Return the raw bytes of the signature
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519 { signature } => signature.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { signature } => signature.to_bytes().to_vec(),
+        }
+    }
+}
+
+/// A K-of-N public key whose members can be of different signature schemes. Unlike
+/// `MultiEd25519PublicKey`, it is validated when verifying a signature rather than when it is
+/// deserialized.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MultiKey {
+    public_keys: Vec<AnyPublicKey>,
+    signatures_required: u8,
+}
+
+impl MultiKey {
+    pub fn new(public_keys: Vec<AnyPublicKey>, signatures_required: u8) -> Result<Self> {
+        let multi_key = Self {
+            public_keys,
+            signatures_required,
+        };
+        multi_key.validate()?;
+        Ok(multi_key)
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.public_keys.is_empty() && self.public_keys.len() <= MAX_NUM_OF_KEYS,
+            "MultiKey must have between 1 and {} public keys, found {}",
+            MAX_NUM_OF_KEYS,
+            self.public_keys.len()
+        );
+        ensure!(
+            self.signatures_required > 0
+                && self.signatures_required as usize <= self.public_keys.len(),
+            "MultiKey must require between 1 and {} signatures, found {}",
+            self.public_keys.len(),
+            self.signatures_required
+        );
+        Ok(())
+    }
+
+    pub fn public_keys(&self) -> &[AnyPublicKey] {
+        &self.public_keys
+    }
+
+    pub fn signatures_required(&self) -> u8 {
+        self.signatures_required
+    }
+
+    /// Return the BCS bytes of the multi key, which the authentication key is derived from
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Only unhandleable errors happen here.")
+    }
+}
+
+/// The signatures of a `MultiKey`, ordered by the index of their public key. The i-th bit of the
+/// bitmap, starting from the most significant bit of the first byte, is set if the i-th public
+/// key signed.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MultiKeySignature {
+    signatures: Vec<AnySignature>,
+    bitmap: [u8; BITMAP_NUM_OF_BYTES],
+}
+
+impl MultiKeySignature {
+    /// Create the signatures from the signatures along with the index of their public key. The
+    /// signatures are sorted by index.
+    pub fn new(mut signatures: Vec<(u8, AnySignature)>) -> Result<Self> {
+        ensure!(
+            !signatures.is_empty() && signatures.len() <= MAX_NUM_OF_KEYS,
+            "MultiKeySignature must have between 1 and {} signatures, found {}",
+            MAX_NUM_OF_KEYS,
+            signatures.len()
+        );
+        signatures.sort_by_key(|(index, _)| *index);
+        let mut bitmap = [0u8; BITMAP_NUM_OF_BYTES];
+        for (index, _) in &signatures {
+            ensure!(
+                (*index as usize) < MAX_NUM_OF_KEYS,
+                "Signature index {} is out of range",
+                index
+            );
+            ensure!(
+                !bitmap_get_bit(&bitmap, *index as usize),
+                "Duplicate signature index {}",
+                index
+            );
+            bitmap_set_bit(&mut bitmap, *index as usize);
+        }
+        Ok(Self {
+            signatures: signatures
+                .into_iter()
+                .map(|(_, signature)| signature)
+                .collect(),
+            bitmap,
+        })
+    }
+
+    /// Create the signatures from signatures ordered by the index of their public key, whose
+    /// indices are set in the bitmap.
+    pub fn new_with_signatures_and_bitmap(
+        signatures: Vec<AnySignature>,
+        bitmap: [u8; BITMAP_NUM_OF_BYTES],
+    ) -> Self {
+        Self { signatures, bitmap }
+    }
+
+    pub fn signatures(&self) -> &[AnySignature] {
+        &self.signatures
+    }
+
+    pub fn bitmap(&self) -> &[u8; BITMAP_NUM_OF_BYTES] {
+        &self.bitmap
+    }
+
+    /// Return the BCS bytes of the signatures
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Only unhandleable errors happen here.")
+    }
+
+    /// Return Ok if at least `signatures_required` members of the multi key signed the message,
+    /// Err otherwise
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &MultiKey,
+    ) -> Result<()> {
+        public_key.validate()?;
+        let indices: Vec<usize> = (0..MAX_NUM_OF_KEYS)
+            .filter(|index| bitmap_get_bit(&self.bitmap, *index))
+            .collect();
+        ensure!(
+            indices.len() == self.signatures.len(),
+            "Bitmap has {} bits set but there are {} signatures",
+            indices.len(),
+            self.signatures.len()
+        );
+        ensure!(
+            indices.len() >= public_key.signatures_required as usize,
+            "Not enough signatures, {} are required but found {}",
+            public_key.signatures_required,
+            indices.len()
+        );
+        for (index, signature) in indices.into_iter().zip(&self.signatures) {
+            let member = public_key
+                .public_keys
+                .get(index)
+                .ok_or_else(|| anyhow::anyhow!("Signature index {} is out of range", index))?;
+            signature.verify(message, member)?;
+        }
+        Ok(())
+    }
+}
+
+fn bitmap_set_bit(bitmap: &mut [u8; BITMAP_NUM_OF_BYTES], index: usize) {
+    bitmap[index / 8] |= 128 >> (index % 8);
+}
+
+fn bitmap_get_bit(bitmap: &[u8; BITMAP_NUM_OF_BYTES], index: usize) -> bool {
+    bitmap[index / 8] & (128 >> (index % 8)) != 0
+}
+
 /// A struct that represents an account authentication key. An account's address is the last 32
 /// bytes of authentication key used to create it
 #[derive(
@@ -406,6 +884,16 @@ impl AuthenticationKey {
         Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
     }
 
+    /// Create an authentication key from a secp256k1 ECDSA public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1EcdsaPublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256k1_ecdsa(public_key))
+    }
+
+    /// Create an authentication key from a multi key
+    pub fn multi_key(public_key: &MultiKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::multi_key(public_key))
+    }
+
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
     /// authentication key.
     pub fn derived_address(&self) -> AccountAddress {
@@ -461,6 +949,16 @@ impl AuthenticationKeyPreimage {
         Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
     }
 
+    /// Construct a preimage from a secp256k1 ECDSA public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1EcdsaPublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes().to_vec(), Scheme::Secp256k1Ecdsa)
+    }
+
+    /// Construct a preimage from a multi key
+    pub fn multi_key(public_key: &MultiKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes(), Scheme::MultiKey)
+    }
+
     /// Construct a vector from this authentication key
     pub fn into_vec(self) -> Vec<u8> {
         self.0
@@ -535,11 +1033,142 @@ impl fmt::Display for AuthenticationKey {
 
 #[cfg(test)]
 mod tests {
-    use crate::transaction::authenticator::AuthenticationKey;
+    use crate::{
+        chain_id::ChainId,
+        transaction::{
+            authenticator::{
+                AnyPublicKey, AnySignature, AuthenticationKey, MultiKey, MultiKeySignature, Scheme,
+                TransactionAuthenticator,
+            },
+            RawTransaction, Script,
+        },
+    };
+    use aptos_crypto::{
+        ed25519::Ed25519PrivateKey,
+        secp256k1_ecdsa::{Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey},
+        HashValue, PrivateKey, SigningKey, Uniform,
+    };
+    use rand::rngs::OsRng;
     use std::str::FromStr;
 
     #[test]
     fn test_from_str_should_not_panic_by_given_empty_string() {
         assert!(AuthenticationKey::from_str("").is_err());
     }
+
+    #[test]
+    fn test_secp256k1_ecdsa_authenticator() {
+        let private_key = Secp256k1EcdsaPrivateKey::generate_for_testing();
+        let public_key: Secp256k1EcdsaPublicKey = private_key.public_key();
+
+        // The authentication key is sha3(public_key | 0x02)
+        let mut preimage = public_key.to_bytes().to_vec();
+        preimage.push(2);
+        let auth_key = AuthenticationKey::secp256k1_ecdsa(&public_key);
+        assert_eq!(
+            auth_key.to_vec(),
+            HashValue::sha3_256_of(&preimage).to_vec()
+        );
+
+        let raw_txn = RawTransaction::new_script(
+            auth_key.derived_address(),
+            0,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        );
+        let signature = private_key.sign(&raw_txn).unwrap();
+        let authenticator =
+            TransactionAuthenticator::secp256k1_ecdsa(public_key.clone(), signature.clone());
+        assert!(authenticator.verify(&raw_txn).is_ok());
+        assert_eq!(authenticator.sender().authentication_key(), auth_key);
+
+        let other_public_key = Secp256k1EcdsaPrivateKey::generate(&mut OsRng).public_key();
+        let authenticator = TransactionAuthenticator::secp256k1_ecdsa(other_public_key, signature);
+        assert!(authenticator.verify(&raw_txn).is_err());
+    }
+
+    #[test]
+    fn test_multi_key_authenticator() {
+        let ed25519_private_key = Ed25519PrivateKey::generate(&mut OsRng);
+        let secp256k1_private_key = Secp256k1EcdsaPrivateKey::generate(&mut OsRng);
+        let other_private_key = Ed25519PrivateKey::generate(&mut OsRng);
+        let multi_key = MultiKey::new(
+            vec![
+                AnyPublicKey::ed25519(ed25519_private_key.public_key()),
+                AnyPublicKey::secp256k1_ecdsa(secp256k1_private_key.public_key()),
+                AnyPublicKey::ed25519(other_private_key.public_key()),
+            ],
+            2,
+        )
+        .unwrap();
+
+        // The authentication key is sha3(bcs(multi_key) | 0x03)
+        let mut preimage = bcs::to_bytes(&multi_key).unwrap();
+        preimage.push(3);
+        let auth_key = AuthenticationKey::multi_key(&multi_key);
+        assert_eq!(
+            auth_key.to_vec(),
+            HashValue::sha3_256_of(&preimage).to_vec()
+        );
+
+        let raw_txn = RawTransaction::new_script(
+            auth_key.derived_address(),
+            0,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        );
+        let ed25519_signature = AnySignature::ed25519(ed25519_private_key.sign(&raw_txn).unwrap());
+        let secp256k1_signature =
+            AnySignature::secp256k1_ecdsa(secp256k1_private_key.sign(&raw_txn).unwrap());
+
+        let signature = MultiKeySignature::new(vec![
+            (1, secp256k1_signature.clone()),
+            (0, ed25519_signature.clone()),
+        ])
+        .unwrap();
+        assert_eq!(signature.bitmap(), &[0b1100_0000, 0, 0, 0]);
+        let authenticator = TransactionAuthenticator::multi_key(multi_key.clone(), signature);
+        assert!(authenticator.verify(&raw_txn).is_ok());
+        assert_eq!(authenticator.sender().authentication_key(), auth_key);
+        assert_eq!(authenticator.sender().number_of_signatures(), 2);
+        assert!(matches!(authenticator.sender().key_schemes()[..], [
+            Scheme::Ed25519,
+            Scheme::Secp256k1Ecdsa,
+            Scheme::Ed25519
+        ]));
+
+        // Not enough signatures.
+        let signature = MultiKeySignature::new(vec![(0, ed25519_signature.clone())]).unwrap();
+        let authenticator = TransactionAuthenticator::multi_key(multi_key.clone(), signature);
+        assert!(authenticator.verify(&raw_txn).is_err());
+
+        // A signature of a scheme different from its public key.
+        let signature = MultiKeySignature::new(vec![
+            (0, ed25519_signature.clone()),
+            (2, secp256k1_signature),
+        ])
+        .unwrap();
+        let authenticator = TransactionAuthenticator::multi_key(multi_key.clone(), signature);
+        assert!(authenticator.verify(&raw_txn).is_err());
+
+        // A signature of a public key that is not a member.
+        let signature =
+            MultiKeySignature::new(vec![(0, ed25519_signature.clone()), (3, ed25519_signature)])
+                .unwrap();
+        let authenticator = TransactionAuthenticator::multi_key(multi_key, signature);
+        assert!(authenticator.verify(&raw_txn).is_err());
+
+        assert!(MultiKey::new(vec![], 1).is_err());
+        assert!(MultiKey::new(
+            vec![AnyPublicKey::ed25519(ed25519_private_key.public_key())],
+            2
+        )
+        .is_err());
+    }
 }